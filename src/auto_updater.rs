@@ -1,5 +1,5 @@
-use crate::self_update::UpdateInfo;
-use log::debug;
+use crate::self_update::{UpdateChannel, UpdateInfo, UpdateVerification};
+use log::{debug, error, warn};
 use std::path::PathBuf;
 
 #[derive(Clone)]
@@ -26,11 +26,21 @@ impl AutoUpdater {
         }
     }
 
-    /// Download and prepare update, then schedule replacement on next restart
+    /// Download and prepare update, then schedule replacement on next restart.
+    /// Pre-releases are only installed when `channel` is the beta channel, and
+    /// nothing is staged when the download fails checksum verification.
     pub async fn download_and_stage_update(
         &self,
         update_info: &UpdateInfo,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        channel: UpdateChannel,
+    ) -> Result<UpdateVerification, Box<dyn std::error::Error>> {
+        if update_info.prerelease && !channel.includes_prereleases() {
+            return Err(format!(
+                "{} is a pre-release; switch the update channel to Beta to install it",
+                update_info.latest_version
+            )
+            .into());
+        }
         let download_url = update_info
             .download_url
             .as_ref()
@@ -57,6 +67,8 @@ impl AutoUpdater {
         let content = response.bytes().await?;
         debug!("📦 Downloaded {} bytes", content.len());
 
+        let verification = verify_download(&client, &content, update_info).await?;
+
         #[cfg(target_os = "macos")]
         {
             self.stage_macos_update(&content, update_info).await?;
@@ -72,7 +84,7 @@ impl AutoUpdater {
             self.stage_windows_update(&content, update_info).await?;
         }
 
-        Ok(())
+        Ok(verification)
     }
 
     #[cfg(target_os = "macos")]
@@ -131,6 +143,62 @@ impl AutoUpdater {
     }
 }
 
+/// Compare the download against the release's published SHA-256 checksum.
+/// Errors name only the asset, never local paths.
+async fn verify_download(
+    client: &reqwest::Client,
+    content: &[u8],
+    update_info: &UpdateInfo,
+) -> Result<UpdateVerification, Box<dyn std::error::Error>> {
+    let asset_name = update_info.asset_name.as_deref().unwrap_or("update");
+    let Some(checksum_url) = &update_info.checksum_url else {
+        warn!("⚠️ No published checksum for {}; installing unverified", asset_name);
+        return Ok(UpdateVerification::NoChecksum);
+    };
+
+    let response = client
+        .get(checksum_url)
+        .header(
+            "User-Agent",
+            format!("Tabular/{}", env!("CARGO_PKG_VERSION")),
+        )
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        error!("❌ Checksum download for {} failed: {}", asset_name, response.status());
+        return Err(format!(
+            "Could not download the checksum for {} ({}); update aborted",
+            asset_name,
+            response.status()
+        )
+        .into());
+    }
+    let checksums = response.text().await?;
+    let Some(expected) = crate::self_update::expected_sha256(&checksums, asset_name) else {
+        error!("❌ Checksum file has no entry for {}", asset_name);
+        return Err(format!(
+            "The release checksum file has no entry for {}; update aborted",
+            asset_name
+        )
+        .into());
+    };
+
+    let actual = crate::self_update::sha256_hex(content);
+    if actual != expected {
+        error!(
+            "❌ Checksum mismatch for {}: expected {}, got {}",
+            asset_name, expected, actual
+        );
+        return Err(format!(
+            "Checksum mismatch for {}: the download is corrupted or was tampered with; update aborted",
+            asset_name
+        )
+        .into());
+    }
+    debug!("🔒 SHA-256 verified for {}", asset_name);
+    Ok(UpdateVerification::Verified)
+}
+
 #[cfg(target_os = "linux")]
 impl AutoUpdater {
     pub async fn stage_linux_update(
@@ -105,6 +105,61 @@ pub(crate) fn get_table_database_from_cache(
     result.ok().flatten().map(|(d,)| d)
 }
 
+/// Every cached table/view named `table_name` (any case) for the connection, as
+/// (database, table, table_type). More than one row means the name exists in
+/// several databases.
+pub(crate) fn find_cached_tables(
+    tabular: &Tabular,
+    connection_id: i64,
+    table_name: &str,
+) -> Vec<(String, String, String)> {
+    let Some(pool) = tabular.db_pool.clone() else {
+        return Vec::new();
+    };
+    let fut = async {
+        sqlx::query_as::<_, (String, String, String)>(
+            "SELECT DISTINCT database_name, table_name, table_type FROM table_cache WHERE connection_id = ? AND table_name = ? COLLATE NOCASE AND table_type IN ('table','view') ORDER BY database_name",
+        )
+        .bind(connection_id)
+        .bind(table_name)
+        .fetch_all(pool.as_ref())
+        .await
+    };
+    let result = if let Some(rt) = tabular.runtime.clone() {
+        rt.block_on(fut)
+    } else {
+        tokio::runtime::Runtime::new().unwrap().block_on(fut)
+    };
+    result.unwrap_or_default()
+}
+
+/// Every cached table that has a column named `column_name` (any case), as
+/// (database, table, column as cached).
+pub(crate) fn find_cached_column_tables(
+    tabular: &Tabular,
+    connection_id: i64,
+    column_name: &str,
+) -> Vec<(String, String, String)> {
+    let Some(pool) = tabular.db_pool.clone() else {
+        return Vec::new();
+    };
+    let fut = async {
+        sqlx::query_as::<_, (String, String, String)>(
+            "SELECT DISTINCT database_name, table_name, column_name FROM column_cache WHERE connection_id = ? AND column_name = ? COLLATE NOCASE ORDER BY database_name, table_name",
+        )
+        .bind(connection_id)
+        .bind(column_name)
+        .fetch_all(pool.as_ref())
+        .await
+    };
+    let result = if let Some(rt) = tabular.runtime.clone() {
+        rt.block_on(fut)
+    } else {
+        tokio::runtime::Runtime::new().unwrap().block_on(fut)
+    };
+    result.unwrap_or_default()
+}
+
 /// Every cached table/view name across ALL connections and databases. Last-ditch
 /// autocomplete fallback when neither the tab's connection nor the database can
 /// be resolved but `table_cache` does hold data.
@@ -125,6 +180,69 @@ pub(crate) fn get_all_cached_tables_global(tabular: &Tabular) -> Option<Vec<Stri
     result.ok().map(|rows| rows.into_iter().map(|(n,)| n).collect())
 }
 
+/// Cached tables and views, of one connection or all of them, with when each
+/// was last opened: (connection, database, table, table_type, last opened).
+pub(crate) fn get_table_palette_candidates(
+    tabular: &Tabular,
+    connection_id: Option<i64>,
+) -> Vec<(i64, String, String, String, Option<i64>)> {
+    let Some(pool) = tabular.db_pool.clone() else {
+        return Vec::new();
+    };
+    let fut = async {
+        sqlx::query_as::<_, (i64, String, String, String, Option<i64>)>(
+            "SELECT DISTINCT t.connection_id, t.database_name, t.table_name, t.table_type, a.last_opened_at \
+             FROM table_cache t LEFT JOIN table_access a ON a.connection_id = t.connection_id \
+             AND a.database_name = t.database_name AND a.table_name = t.table_name \
+             WHERE t.table_type IN ('table','view') AND (? IS NULL OR t.connection_id = ?)",
+        )
+        .bind(connection_id)
+        .bind(connection_id)
+        .fetch_all(pool.as_ref())
+        .await
+    };
+    let result = if let Some(rt) = tabular.runtime.clone() {
+        rt.block_on(fut)
+    } else {
+        tokio::runtime::Runtime::new().unwrap().block_on(fut)
+    };
+    result.unwrap_or_else(|e| {
+        debug!("get_table_palette_candidates error: {}", e);
+        Vec::new()
+    })
+}
+
+/// Remember that a table was opened now; ranks it first in the table palette.
+pub(crate) fn record_table_access(
+    tabular: &Tabular,
+    connection_id: i64,
+    database_name: &str,
+    table_name: &str,
+) {
+    let Some(pool) = tabular.db_pool.clone() else {
+        return;
+    };
+    let fut = async {
+        sqlx::query(
+            "INSERT OR REPLACE INTO table_access (connection_id, database_name, table_name, last_opened_at) VALUES (?, ?, ?, ?)",
+        )
+        .bind(connection_id)
+        .bind(database_name)
+        .bind(table_name)
+        .bind(chrono::Utc::now().timestamp())
+        .execute(pool.as_ref())
+        .await
+    };
+    let result = if let Some(rt) = tabular.runtime.clone() {
+        rt.block_on(fut)
+    } else {
+        tokio::runtime::Runtime::new().unwrap().block_on(fut)
+    };
+    if let Err(e) = result {
+        debug!("record_table_access error: {}", e);
+    }
+}
+
 /// Like `get_columns_from_cache` but NOT scoped to a database. Returns the first
 /// cached column set found for `table_name` under the connection (any database).
 pub(crate) fn get_columns_for_connection_any_db(
@@ -393,6 +511,7 @@ pub(crate) fn fetch_and_cache_connection_data(
                         driver_sqlite::fetch_tables_from_sqlite_connection(
                             tabular,
                             connection_id,
+                            database_name,
                             table_type,
                         )
                     }
@@ -965,6 +1084,34 @@ pub(crate) fn get_redis_browser_preview_from_cache(
     })
 }
 
+/// Drop the cached preview of a key after it was changed or removed.
+pub(crate) fn remove_redis_browser_preview_from_cache(
+    tabular: &mut window_egui::Tabular,
+    connection_id: i64,
+    database_name: &str,
+    key_name: &str,
+) {
+    if let Some(ref pool) = tabular.db_pool {
+        let pool_clone = pool.clone();
+        let cache_name = redis_browser_preview_cache_name(key_name);
+        let fut = async {
+            let _ = sqlx::query(
+                "DELETE FROM row_cache WHERE connection_id = ? AND database_name = ? AND table_name = ?",
+            )
+            .bind(connection_id)
+            .bind(database_name)
+            .bind(&cache_name)
+            .execute(pool_clone.as_ref())
+            .await;
+        };
+        if let Some(rt) = tabular.runtime.clone() {
+            rt.block_on(fut)
+        } else {
+            tokio::runtime::Runtime::new().unwrap().block_on(fut)
+        };
+    }
+}
+
 pub(crate) fn get_table_rows_from_cache(
     tabular: &mut window_egui::Tabular,
     connection_id: i64,
@@ -1137,6 +1284,7 @@ pub(crate) fn get_indexes_from_cache(
                         method,
                         unique: is_unique_i != 0,
                         columns,
+                        ..Default::default()
                     });
                 }
                 debug!(
@@ -1283,3 +1431,333 @@ pub(crate) fn get_partitions_from_cache(
     }
 }
 
+
+// Query result cache: opt-in snapshots of read-only query results, keyed by
+// (connection, database, structural fingerprint of the SQL) with a TTL.
+
+/// Statement keywords that may return cached rows. Anything else runs live.
+const RESULT_CACHE_LEADING: [&str; 7] = ["SELECT", "WITH", "SHOW", "DESCRIBE", "DESC", "EXPLAIN", "VALUES"];
+
+/// Words that make a statement write or lock data (also inside CTEs / `SELECT ... INTO`).
+const RESULT_CACHE_BLOCKED: [&str; 12] = [
+    "INSERT", "UPDATE", "DELETE", "MERGE", "CREATE", "ALTER", "DROP", "TRUNCATE", "GRANT",
+    "REVOKE", "INTO", "CALL",
+];
+
+/// True when `sql` only reads data, so its result may be served from the cache.
+/// Conservative: a blocked word anywhere (even in a string literal) disables caching.
+pub(crate) fn is_result_cacheable(sql: &str) -> bool {
+    let words: Vec<String> = strip_leading_sql_comments(sql)
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_ascii_uppercase())
+        .collect();
+    match words.first() {
+        Some(first) if RESULT_CACHE_LEADING.contains(&first.as_str()) => {}
+        _ => return false,
+    }
+    !words.iter().any(|w| RESULT_CACHE_BLOCKED.contains(&w.as_str()))
+}
+
+pub(crate) fn strip_leading_sql_comments(sql: &str) -> &str {
+    let mut rest = sql.trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix("--") {
+            rest = after.split_once('\n').map(|(_, r)| r).unwrap_or("").trim_start();
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.split_once("*/").map(|(_, r)| r).unwrap_or("").trim_start();
+        } else {
+            return rest;
+        }
+    }
+}
+
+/// Cache key of `sql`: its text, trimmed, without the trailing `;` and with
+/// whitespace runs outside quotes collapsed. Case and quote characters are
+/// kept, since identifiers and strings may be case sensitive.
+fn result_cache_fingerprint(sql: &str) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut norm = String::with_capacity(sql.len());
+    let mut quote: Option<char> = None;
+    let mut pending_space = false;
+    // A backslash in a literal may or may not escape the quote depending on
+    // the server; from there on the text is kept as is.
+    let mut verbatim = false;
+    for c in sql.trim().trim_end_matches(';').trim_end().chars() {
+        if verbatim {
+            norm.push(c);
+            continue;
+        }
+        match quote {
+            Some(close) => {
+                norm.push(c);
+                if c == close {
+                    quote = None;
+                } else if c == '\\' {
+                    verbatim = true;
+                }
+            }
+            None if c.is_whitespace() => pending_space = true,
+            None => {
+                if pending_space {
+                    norm.push(' ');
+                    pending_space = false;
+                }
+                norm.push(c);
+                quote = match c {
+                    '\'' | '"' | '`' => Some(c),
+                    '[' => Some(']'),
+                    _ => None,
+                };
+            }
+        }
+    }
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    norm.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Cached (headers, rows, cached_at unix seconds) for `sql`, if present and not expired.
+pub(crate) fn get_query_result_from_cache(
+    tabular: &Tabular,
+    connection_id: i64,
+    database_name: &str,
+    sql: &str,
+) -> Option<(Vec<String>, Vec<Vec<String>>, i64)> {
+    let pool = tabular.db_pool.as_ref()?.clone();
+    let fingerprint = result_cache_fingerprint(sql);
+    let now = chrono::Utc::now().timestamp();
+    let fut = async {
+        sqlx::query_as::<_, (String, String, i64)>(
+            "SELECT headers_json, rows_json, cached_at FROM query_result_cache WHERE connection_id = ? AND database_name = ? AND fingerprint = ? AND expires_at > ?",
+        )
+        .bind(connection_id)
+        .bind(database_name)
+        .bind(&fingerprint)
+        .bind(now)
+        .fetch_optional(pool.as_ref())
+        .await
+    };
+    let result = if let Some(rt) = tabular.runtime.clone() {
+        rt.block_on(fut)
+    } else {
+        tokio::runtime::Runtime::new().unwrap().block_on(fut)
+    };
+    match result {
+        Ok(Some((headers_json, rows_json, cached_at))) => {
+            let headers: Vec<String> = serde_json::from_str(&headers_json).ok()?;
+            let rows: Vec<Vec<String>> = serde_json::from_str(&rows_json).ok()?;
+            debug!(
+                "📦 Result cache hit for {}/{}/{} ({} rows)",
+                connection_id,
+                database_name,
+                fingerprint,
+                rows.len()
+            );
+            Some((headers, rows, cached_at))
+        }
+        Ok(None) => None,
+        Err(e) => {
+            debug!("Result cache lookup error: {}", e);
+            None
+        }
+    }
+}
+
+/// Store a query result with the configured TTL, then evict expired and oldest
+/// entries until the cache fits `result_cache_max_mb`. Results larger than the
+/// whole budget are not stored.
+pub(crate) fn save_query_result_to_cache(
+    tabular: &Tabular,
+    connection_id: i64,
+    database_name: &str,
+    sql: &str,
+    headers: &[String],
+    rows: &[Vec<String>],
+) {
+    let Some(pool) = tabular.db_pool.clone() else {
+        return;
+    };
+    let headers_json = serde_json::to_string(headers).unwrap_or_else(|_| "[]".to_string());
+    let rows_json = serde_json::to_string(rows).unwrap_or_else(|_| "[]".to_string());
+    let byte_size = (headers_json.len() + rows_json.len()) as i64;
+    let max_bytes = (tabular.result_cache_max_mb.max(1) as i64).saturating_mul(1024 * 1024);
+    if byte_size > max_bytes {
+        debug!("Result too large for the result cache ({} bytes)", byte_size);
+        return;
+    }
+    let fingerprint = result_cache_fingerprint(sql);
+    let now = chrono::Utc::now().timestamp();
+    let expires_at = now.saturating_add(tabular.result_cache_ttl_secs as i64);
+    let fut = async {
+        let _ = sqlx::query(
+            r#"INSERT INTO query_result_cache (connection_id, database_name, fingerprint, sql_text, headers_json, rows_json, byte_size, cached_at, expires_at)
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+               ON CONFLICT(connection_id, database_name, fingerprint)
+               DO UPDATE SET sql_text=excluded.sql_text, headers_json=excluded.headers_json, rows_json=excluded.rows_json,
+                             byte_size=excluded.byte_size, cached_at=excluded.cached_at, expires_at=excluded.expires_at"#,
+        )
+        .bind(connection_id)
+        .bind(database_name)
+        .bind(&fingerprint)
+        .bind(sql)
+        .bind(&headers_json)
+        .bind(&rows_json)
+        .bind(byte_size)
+        .bind(now)
+        .bind(expires_at)
+        .execute(pool.as_ref())
+        .await;
+
+        let _ = sqlx::query("DELETE FROM query_result_cache WHERE expires_at <= ?")
+            .bind(now)
+            .execute(pool.as_ref())
+            .await;
+        loop {
+            let total: i64 = sqlx::query_scalar("SELECT COALESCE(SUM(byte_size), 0) FROM query_result_cache")
+                .fetch_one(pool.as_ref())
+                .await
+                .unwrap_or(0);
+            if total <= max_bytes {
+                break;
+            }
+            let evicted = sqlx::query(
+                "DELETE FROM query_result_cache WHERE id = (SELECT id FROM query_result_cache ORDER BY cached_at ASC, id ASC LIMIT 1)",
+            )
+            .execute(pool.as_ref())
+            .await
+            .map(|r| r.rows_affected())
+            .unwrap_or(0);
+            if evicted == 0 {
+                break;
+            }
+        }
+    };
+    if let Some(rt) = tabular.runtime.clone() {
+        rt.block_on(fut)
+    } else {
+        tokio::runtime::Runtime::new().unwrap().block_on(fut)
+    };
+    debug!(
+        "💾 Cached query result for {}/{}/{} ({} bytes)",
+        connection_id, database_name, fingerprint, byte_size
+    );
+}
+
+/// Drop cached results: one statement, one connection, or everything.
+pub(crate) fn invalidate_query_result_cache(
+    tabular: &Tabular,
+    connection_id: Option<i64>,
+    target: Option<(&str, &str)>, // (database_name, sql)
+) -> u64 {
+    let Some(pool) = tabular.db_pool.clone() else {
+        return 0;
+    };
+    let fut = async {
+        let result = match (connection_id, target) {
+            (Some(id), Some((database_name, sql))) => {
+                sqlx::query("DELETE FROM query_result_cache WHERE connection_id = ? AND database_name = ? AND fingerprint = ?")
+                    .bind(id)
+                    .bind(database_name)
+                    .bind(result_cache_fingerprint(sql))
+                    .execute(pool.as_ref())
+                    .await
+            }
+            (Some(id), None) => {
+                sqlx::query("DELETE FROM query_result_cache WHERE connection_id = ?")
+                    .bind(id)
+                    .execute(pool.as_ref())
+                    .await
+            }
+            (None, _) => sqlx::query("DELETE FROM query_result_cache").execute(pool.as_ref()).await,
+        };
+        result.map(|r| r.rows_affected()).unwrap_or(0)
+    };
+    if let Some(rt) = tabular.runtime.clone() {
+        rt.block_on(fut)
+    } else {
+        tokio::runtime::Runtime::new().unwrap().block_on(fut)
+    }
+}
+
+/// (entries, bytes) currently held by the result cache.
+pub(crate) fn query_result_cache_usage(tabular: &Tabular) -> (i64, i64) {
+    let Some(pool) = tabular.db_pool.clone() else {
+        return (0, 0);
+    };
+    let fut = async {
+        sqlx::query_as::<_, (i64, i64)>("SELECT COUNT(*), COALESCE(SUM(byte_size), 0) FROM query_result_cache")
+            .fetch_one(pool.as_ref())
+            .await
+            .unwrap_or((0, 0))
+    };
+    if let Some(rt) = tabular.runtime.clone() {
+        rt.block_on(fut)
+    } else {
+        tokio::runtime::Runtime::new().unwrap().block_on(fut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_read_only_statements_are_cacheable() {
+        assert!(is_result_cacheable("SELECT region, SUM(total) FROM orders GROUP BY region"));
+        assert!(is_result_cacheable("-- monthly\n/* heavy */ with m AS (select 1) select * from m"));
+        assert!(is_result_cacheable("show tables"));
+        assert!(!is_result_cacheable("UPDATE orders SET total = 0"));
+        assert!(!is_result_cacheable("WITH d AS (DELETE FROM t RETURNING *) SELECT * FROM d"));
+        assert!(!is_result_cacheable("SELECT * INTO backup FROM orders"));
+        assert!(!is_result_cacheable("SELECT * FROM accounts FOR UPDATE"));
+        assert!(!is_result_cacheable(""));
+    }
+
+    #[test]
+    fn fingerprint_ignores_spacing_outside_quotes_only() {
+        assert_eq!(
+            result_cache_fingerprint("  SELECT  id\nFROM `users`;"),
+            result_cache_fingerprint("SELECT id FROM `users`")
+        );
+        assert_ne!(
+            result_cache_fingerprint("select id from users"),
+            result_cache_fingerprint("select id from orders")
+        );
+        assert_ne!(
+            result_cache_fingerprint("SELECT * FROM users WHERE name = 'Ann'"),
+            result_cache_fingerprint("SELECT * FROM users WHERE name = 'ann'")
+        );
+        assert_ne!(
+            result_cache_fingerprint("SELECT * FROM t WHERE a = 'x  y'"),
+            result_cache_fingerprint("SELECT * FROM t WHERE a = 'x y'")
+        );
+        assert_ne!(
+            result_cache_fingerprint("SELECT * FROM t WHERE a = 'it''s A'"),
+            result_cache_fingerprint("SELECT * FROM t WHERE a = 'it''s a'")
+        );
+        assert_ne!(
+            result_cache_fingerprint("SELECT * FROM t WHERE a = 'it\\'s  a'"),
+            result_cache_fingerprint("SELECT * FROM t WHERE a = 'it\\'s a'")
+        );
+    }
+
+    #[test]
+    fn fingerprint_keeps_case_sensitive_names_and_strings_apart() {
+        // PostgreSQL quoted identifiers
+        assert_ne!(
+            result_cache_fingerprint(r#"SELECT * FROM "Users""#),
+            result_cache_fingerprint(r#"SELECT * FROM "users""#)
+        );
+        // MySQL double-quoted strings
+        assert_ne!(
+            result_cache_fingerprint(r#"SELECT * FROM t WHERE name = "Ann""#),
+            result_cache_fingerprint(r#"SELECT * FROM t WHERE name = "ann""#)
+        );
+        // MySQL with case-sensitive table names
+        assert_ne!(
+            result_cache_fingerprint("SELECT * FROM Users"),
+            result_cache_fingerprint("SELECT * FROM users")
+        );
+    }
+}
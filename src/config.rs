@@ -5,6 +5,8 @@ use sqlx::{Pool, Row, Sqlite, sqlite::SqlitePoolOptions};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::self_update::UpdateChannel;
+
 /// File name to store the current data directory location
 const CONFIG_LOCATION_FILE: &str = "config_location.txt";
 
@@ -40,6 +42,53 @@ impl std::str::FromStr for AppTheme {
     }
 }
 
+/// Zone used to display temporal result columns in the data grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayTimezone {
+    /// Show values exactly as returned by the driver.
+    #[default]
+    AsStored,
+    Utc,
+    Local,
+    Zone(chrono_tz::Tz),
+}
+
+impl DisplayTimezone {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DisplayTimezone::AsStored => "AS_STORED",
+            DisplayTimezone::Utc => "UTC",
+            DisplayTimezone::Local => "LOCAL",
+            DisplayTimezone::Zone(tz) => tz.name(),
+        }
+    }
+    pub fn display_name(self) -> String {
+        match self {
+            DisplayTimezone::AsStored => "As stored".to_string(),
+            DisplayTimezone::Utc => "UTC".to_string(),
+            DisplayTimezone::Local => {
+                format!("Local ({})", chrono::Local::now().format("%:z"))
+            }
+            DisplayTimezone::Zone(tz) => tz.name().to_string(),
+        }
+    }
+}
+
+impl std::str::FromStr for DisplayTimezone {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim() {
+            "" | "AS_STORED" => DisplayTimezone::AsStored,
+            "UTC" => DisplayTimezone::Utc,
+            "LOCAL" => DisplayTimezone::Local,
+            other => other
+                .parse::<chrono_tz::Tz>()
+                .map(DisplayTimezone::Zone)
+                .map_err(|_| ())?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum AiProvider {
     #[default]
@@ -170,6 +219,11 @@ pub struct AppPreferences {
     pub word_wrap: bool,
     pub data_directory: Option<String>,
     pub auto_check_updates: bool,
+    #[serde(default)]
+    pub update_channel: UpdateChannel,
+    // Airgapped use: no update checks or eager background connections
+    #[serde(default)]
+    pub offline_mode: bool,
     pub use_server_pagination: bool,
     // RFC3339 timestamp of the last time we checked GitHub releases (persisted)
     pub last_update_check_iso: Option<String>,
@@ -186,12 +240,128 @@ pub struct AppPreferences {
     pub ai_base_url: String,
     #[serde(default = "default_redis_browser_auto_refresh_seconds")]
     pub redis_browser_auto_refresh_seconds: u32,
+    // Data grid display timezone (DisplayTimezone::as_str; empty = as stored)
+    #[serde(default)]
+    pub display_timezone: String,
+    // Apply the display timezone to cell editing and export as well
+    #[serde(default)]
+    pub timezone_for_edit_export: bool,
+    // Left sidebar layout
+    #[serde(default)]
+    pub sidebar_collapsed: bool,
+    #[serde(default = "default_sidebar_width")]
+    pub sidebar_width: f32,
+    // Default editor share of the editor/result split (tabs may override)
+    #[serde(default = "default_editor_split_ratio")]
+    pub editor_split_ratio: f32,
+    // Opt-in cache of read-only query results (see cache_data::save_query_result_to_cache)
+    #[serde(default)]
+    pub result_cache_enabled: bool,
+    #[serde(default = "default_result_cache_ttl_secs")]
+    pub result_cache_ttl_secs: u64,
+    #[serde(default = "default_result_cache_max_mb")]
+    pub result_cache_max_mb: u64,
+    // User keyboard shortcut overrides as JSON (see window_egui::keybindings)
+    #[serde(default)]
+    pub keybindings: String,
+    #[serde(default)]
+    pub vim_mode: bool,
+    // Multi-statement runs: one editor tab per SELECT instead of result sub-tabs
+    #[serde(default)]
+    pub batch_results_in_tabs: bool,
+    // Destructive statements run without a prompt when a dry-run count shows
+    // at most this many affected rows (0 = always confirm); stored under its
+    // original key
+    #[serde(default, rename = "destructive_confirm_min_rows")]
+    pub destructive_confirm_over_rows: u64,
+    // Safe mode: data-modifying statements run in a transaction that rolls
+    // back unless kept within the window (see connection::safe_mode)
+    #[serde(default)]
+    pub safe_mode: bool,
+    #[serde(default = "default_safe_mode_window_secs")]
+    pub safe_mode_window_secs: u64,
+    // Scripts of several statements run in one transaction, rolled back on
+    // any error (see connection::session::spawn_transaction_batch)
+    #[serde(default)]
+    pub run_in_transaction: bool,
+    // Suggest indexes for filtered columns of queries slower than the
+    // threshold (see data_table::index_advice)
+    #[serde(default)]
+    pub index_hints: bool,
+    #[serde(default = "default_index_hint_min_ms")]
+    pub index_hint_min_ms: u64,
+    // Column display formatter rules as JSON (see data_table::formatters)
+    #[serde(default)]
+    pub column_formatters: String,
+    #[serde(default)]
+    pub export_formatted_values: bool,
+    // Digit grouping for numeric columns (see data_table::number_format);
+    // empty decimals keep the stored precision
+    #[serde(default)]
+    pub number_format: bool,
+    #[serde(default)]
+    pub number_style: String,
+    #[serde(default)]
+    pub number_decimals: String,
+    // Cells longer than this show a preview (see data_table::cell_preview)
+    #[serde(default = "default_cell_preview_chars")]
+    pub cell_preview_chars: usize,
+    // Streamed SELECTs pause after this many rows (see connection::stream)
+    #[serde(default = "default_stream_row_ceiling")]
+    pub stream_row_ceiling: usize,
+    // Rows per result page for new tabs and at startup
+    #[serde(default = "default_page_size")]
+    pub default_page_size: usize,
+    #[serde(default)]
+    pub export_skip_hidden_columns: bool,
+    // Saved column widths per browsed table as JSON (see data_table::layout)
+    #[serde(default)]
+    pub table_layouts: String,
+    // Proxy for connections without their own, as JSON (see proxy_tunnel)
+    #[serde(default)]
+    pub global_proxy: String,
 }
 
 fn default_redis_browser_auto_refresh_seconds() -> u32 {
     5
 }
 
+pub fn default_sidebar_width() -> f32 {
+    250.0
+}
+
+pub fn default_editor_split_ratio() -> f32 {
+    0.6
+}
+
+pub fn default_result_cache_ttl_secs() -> u64 {
+    300
+}
+
+pub fn default_safe_mode_window_secs() -> u64 {
+    10
+}
+
+pub fn default_cell_preview_chars() -> usize {
+    2000
+}
+
+pub fn default_stream_row_ceiling() -> usize {
+    200_000
+}
+
+pub fn default_page_size() -> usize {
+    500
+}
+
+pub fn default_index_hint_min_ms() -> u64 {
+    200
+}
+
+pub fn default_result_cache_max_mb() -> u64 {
+    64
+}
+
 impl Default for AppPreferences {
     fn default() -> Self {
         Self {
@@ -202,6 +372,8 @@ impl Default for AppPreferences {
             word_wrap: true,
             data_directory: None,
             auto_check_updates: true,
+            update_channel: UpdateChannel::Stable,
+            offline_mode: false,
             use_server_pagination: true,
             last_update_check_iso: None,
             enable_debug_logging: false,
@@ -210,6 +382,34 @@ impl Default for AppPreferences {
             ai_provider: AiProvider::OpenAI,
             ai_base_url: String::new(),
             redis_browser_auto_refresh_seconds: default_redis_browser_auto_refresh_seconds(),
+            display_timezone: String::new(),
+            timezone_for_edit_export: false,
+            sidebar_collapsed: false,
+            sidebar_width: default_sidebar_width(),
+            editor_split_ratio: default_editor_split_ratio(),
+            result_cache_enabled: false,
+            result_cache_ttl_secs: default_result_cache_ttl_secs(),
+            result_cache_max_mb: default_result_cache_max_mb(),
+            keybindings: String::new(),
+            vim_mode: false,
+            batch_results_in_tabs: false,
+            destructive_confirm_over_rows: 0,
+            safe_mode: false,
+            safe_mode_window_secs: default_safe_mode_window_secs(),
+            run_in_transaction: false,
+            index_hints: false,
+            index_hint_min_ms: default_index_hint_min_ms(),
+            column_formatters: String::new(),
+            export_formatted_values: false,
+            number_format: false,
+            number_style: String::new(),
+            number_decimals: String::new(),
+            cell_preview_chars: default_cell_preview_chars(),
+            stream_row_ceiling: default_stream_row_ceiling(),
+            default_page_size: default_page_size(),
+            export_skip_hidden_columns: false,
+            table_layouts: String::new(),
+            global_proxy: String::new(),
         }
     }
 }
@@ -300,6 +500,8 @@ impl ConfigStore {
                 word_wrap: true,
                 data_directory: None,
                 auto_check_updates: true,
+                update_channel: UpdateChannel::Stable,
+                offline_mode: false,
                 use_server_pagination: true, // Default to true for better performance
                 last_update_check_iso: None,
                 enable_debug_logging: false,
@@ -308,11 +510,41 @@ impl ConfigStore {
                 ai_provider: AiProvider::OpenAI,
                 ai_base_url: String::new(),
                 redis_browser_auto_refresh_seconds: default_redis_browser_auto_refresh_seconds(),
+                display_timezone: String::new(),
+                timezone_for_edit_export: false,
+                sidebar_collapsed: false,
+                sidebar_width: default_sidebar_width(),
+                editor_split_ratio: default_editor_split_ratio(),
+                result_cache_enabled: false,
+                result_cache_ttl_secs: default_result_cache_ttl_secs(),
+                result_cache_max_mb: default_result_cache_max_mb(),
+                keybindings: String::new(),
+                vim_mode: false,
+                batch_results_in_tabs: false,
+                destructive_confirm_over_rows: 0,
+                safe_mode: false,
+                safe_mode_window_secs: default_safe_mode_window_secs(),
+                run_in_transaction: false,
+                index_hints: false,
+                index_hint_min_ms: default_index_hint_min_ms(),
+                column_formatters: String::new(),
+                export_formatted_values: false,
+                number_format: false,
+                number_style: String::new(),
+                number_decimals: String::new(),
+                cell_preview_chars: default_cell_preview_chars(),
+                stream_row_ceiling: default_stream_row_ceiling(),
+                default_page_size: default_page_size(),
+                export_skip_hidden_columns: false,
+                table_layouts: String::new(),
+                global_proxy: String::new(),
             };
 
             // Set when a legacy plaintext AI key was migrated to the secret
             // store during this load; the row is rewritten below.
             let mut ai_key_rewrite: Option<String> = None;
+            // Same for a plaintext proxy password inside `global_proxy`.
+            let mut global_proxy_rewrite: Option<String> = None;
 
             if let Ok(rows) = sqlx::query("SELECT key, value FROM preferences")
                 .fetch_all(pool)
@@ -334,6 +566,8 @@ impl ConfigStore {
                             prefs.data_directory = if v.is_empty() { None } else { Some(v) }
                         }
                         "auto_check_updates" => prefs.auto_check_updates = v == "1",
+                        "update_channel" => prefs.update_channel = v.parse().unwrap_or_default(),
+                        "offline_mode" => prefs.offline_mode = v == "1",
                         "use_server_pagination" => prefs.use_server_pagination = v == "1",
                         "last_update_check_iso" => {
                             prefs.last_update_check_iso = if v.is_empty() { None } else { Some(v) }
@@ -351,17 +585,84 @@ impl ConfigStore {
                         "redis_browser_auto_refresh_seconds" => {
                             prefs.redis_browser_auto_refresh_seconds = v.parse().unwrap_or(default_redis_browser_auto_refresh_seconds())
                         }
+                        "display_timezone" => prefs.display_timezone = v,
+                        "timezone_for_edit_export" => prefs.timezone_for_edit_export = v == "1",
+                        "sidebar_collapsed" => prefs.sidebar_collapsed = v == "1",
+                        "sidebar_width" => {
+                            prefs.sidebar_width = v.parse().unwrap_or(default_sidebar_width())
+                        }
+                        "editor_split_ratio" => {
+                            prefs.editor_split_ratio =
+                                v.parse().unwrap_or(default_editor_split_ratio())
+                        }
+                        "result_cache_enabled" => prefs.result_cache_enabled = v == "1",
+                        "result_cache_ttl_secs" => {
+                            prefs.result_cache_ttl_secs =
+                                v.parse().unwrap_or(default_result_cache_ttl_secs())
+                        }
+                        "result_cache_max_mb" => {
+                            prefs.result_cache_max_mb =
+                                v.parse().unwrap_or(default_result_cache_max_mb())
+                        }
+                        "keybindings" => prefs.keybindings = v,
+                        "vim_mode" => prefs.vim_mode = v == "1",
+                        "batch_results_in_tabs" => prefs.batch_results_in_tabs = v == "1",
+                        "destructive_confirm_min_rows" => {
+                            prefs.destructive_confirm_over_rows = v.parse().unwrap_or(0)
+                        }
+                        "safe_mode" => prefs.safe_mode = v == "1",
+                        "run_in_transaction" => prefs.run_in_transaction = v == "1",
+                        "safe_mode_window_secs" => {
+                            prefs.safe_mode_window_secs =
+                                v.parse().unwrap_or(default_safe_mode_window_secs())
+                        }
+                        "index_hints" => prefs.index_hints = v == "1",
+                        "index_hint_min_ms" => {
+                            prefs.index_hint_min_ms =
+                                v.parse().unwrap_or(default_index_hint_min_ms())
+                        }
+                        "column_formatters" => prefs.column_formatters = v,
+                        "export_formatted_values" => prefs.export_formatted_values = v == "1",
+                        "number_format" => prefs.number_format = v == "1",
+                        "number_style" => prefs.number_style = v,
+                        "number_decimals" => prefs.number_decimals = v,
+                        "cell_preview_chars" => {
+                            prefs.cell_preview_chars =
+                                v.parse().unwrap_or(default_cell_preview_chars())
+                        }
+                        "stream_row_ceiling" => {
+                            prefs.stream_row_ceiling =
+                                v.parse().unwrap_or(default_stream_row_ceiling())
+                        }
+                        "default_page_size" => {
+                            prefs.default_page_size = v.parse().unwrap_or(default_page_size())
+                        }
+                        "export_skip_hidden_columns" => {
+                            prefs.export_skip_hidden_columns = v == "1"
+                        }
+                        "table_layouts" => prefs.table_layouts = v,
+                        "global_proxy" => {
+                            let (real, rewrite) =
+                                crate::proxy_tunnel::global_proxy_from_storage(&v);
+                            prefs.global_proxy = real;
+                            global_proxy_rewrite = rewrite;
+                        }
                         _ => {}
                     }
                 }
             }
 
-            if let Some(value) = ai_key_rewrite {
-                let _ = sqlx::query("REPLACE INTO preferences (key,value) VALUES (?,?)")
-                    .bind("ai_api_key")
-                    .bind(value)
-                    .execute(pool)
-                    .await;
+            for (key, rewrite) in [
+                ("ai_api_key", ai_key_rewrite),
+                ("global_proxy", global_proxy_rewrite),
+            ] {
+                if let Some(value) = rewrite {
+                    let _ = sqlx::query("REPLACE INTO preferences (key,value) VALUES (?,?)")
+                        .bind(key)
+                        .bind(value)
+                        .execute(pool)
+                        .await;
+                }
             }
 
             debug!(
@@ -403,10 +704,22 @@ impl ConfigStore {
         if let Some(ref pool) = self.pool {
             let font_size_string = prefs.font_size.to_string();
             let redis_browser_auto_refresh_seconds = prefs.redis_browser_auto_refresh_seconds.to_string();
+            let sidebar_width = prefs.sidebar_width.to_string();
+            let editor_split_ratio = prefs.editor_split_ratio.to_string();
+            let result_cache_ttl_secs = prefs.result_cache_ttl_secs.to_string();
+            let result_cache_max_mb = prefs.result_cache_max_mb.to_string();
+            let destructive_confirm_over_rows = prefs.destructive_confirm_over_rows.to_string();
+            let safe_mode_window_secs = prefs.safe_mode_window_secs.to_string();
+            let index_hint_min_ms = prefs.index_hint_min_ms.to_string();
+            let cell_preview_chars = prefs.cell_preview_chars.to_string();
+            let stream_row_ceiling = prefs.stream_row_ceiling.to_string();
+            let default_page_size = prefs.default_page_size.to_string();
             // The key goes to the OS keychain; the row keeps only a sentinel.
             let ai_api_key_stored =
                 crate::secrets::store_or_keep("pref:ai_api_key", &prefs.ai_api_key);
-            let entries: [(&str, &str); 14] = [
+            let global_proxy_stored =
+                crate::proxy_tunnel::global_proxy_for_storage(&prefs.global_proxy);
+            let entries: [(&str, &str); 44] = [
                 ("theme", prefs.theme.as_str()),
                 (
                     "link_editor_theme",
@@ -440,6 +753,57 @@ impl ConfigStore {
                 ("ai_provider", prefs.ai_provider.as_str()),
                 ("ai_base_url", prefs.ai_base_url.as_str()),
                 ("redis_browser_auto_refresh_seconds", &redis_browser_auto_refresh_seconds),
+                ("display_timezone", prefs.display_timezone.as_str()),
+                (
+                    "timezone_for_edit_export",
+                    if prefs.timezone_for_edit_export { "1" } else { "0" },
+                ),
+                (
+                    "sidebar_collapsed",
+                    if prefs.sidebar_collapsed { "1" } else { "0" },
+                ),
+                ("sidebar_width", &sidebar_width),
+                ("editor_split_ratio", &editor_split_ratio),
+                (
+                    "result_cache_enabled",
+                    if prefs.result_cache_enabled { "1" } else { "0" },
+                ),
+                ("result_cache_ttl_secs", &result_cache_ttl_secs),
+                ("result_cache_max_mb", &result_cache_max_mb),
+                ("keybindings", prefs.keybindings.as_str()),
+                ("vim_mode", if prefs.vim_mode { "1" } else { "0" }),
+                (
+                    "batch_results_in_tabs",
+                    if prefs.batch_results_in_tabs { "1" } else { "0" },
+                ),
+                ("destructive_confirm_min_rows", &destructive_confirm_over_rows),
+                ("safe_mode", if prefs.safe_mode { "1" } else { "0" }),
+                ("safe_mode_window_secs", &safe_mode_window_secs),
+                (
+                    "run_in_transaction",
+                    if prefs.run_in_transaction { "1" } else { "0" },
+                ),
+                ("index_hints", if prefs.index_hints { "1" } else { "0" }),
+                ("index_hint_min_ms", &index_hint_min_ms),
+                ("column_formatters", prefs.column_formatters.as_str()),
+                (
+                    "export_formatted_values",
+                    if prefs.export_formatted_values { "1" } else { "0" },
+                ),
+                ("number_format", if prefs.number_format { "1" } else { "0" }),
+                ("number_style", prefs.number_style.as_str()),
+                ("number_decimals", prefs.number_decimals.as_str()),
+                ("cell_preview_chars", &cell_preview_chars),
+                ("stream_row_ceiling", &stream_row_ceiling),
+                ("default_page_size", &default_page_size),
+                (
+                    "export_skip_hidden_columns",
+                    if prefs.export_skip_hidden_columns { "1" } else { "0" },
+                ),
+                ("table_layouts", prefs.table_layouts.as_str()),
+                ("global_proxy", global_proxy_stored.as_str()),
+                ("update_channel", prefs.update_channel.as_str()),
+                ("offline_mode", if prefs.offline_mode { "1" } else { "0" }),
             ];
 
             for (k, v) in entries.iter() {
@@ -484,16 +848,24 @@ impl ConfigStore {
         let content = std::fs::read_to_string(&path)?;
         let mut prefs: AppPreferences = serde_json::from_str(&content)?;
         let (real, rewrite) = crate::secrets::resolve_stored("pref:ai_api_key", &prefs.ai_api_key);
-        if let Some(value) = rewrite {
-            // Legacy plaintext key migrated to the secret store: rewrite the
-            // JSON file so it only holds the sentinel.
+        let (real_proxy, proxy_rewrite) =
+            crate::proxy_tunnel::global_proxy_from_storage(&prefs.global_proxy);
+        if rewrite.is_some() || proxy_rewrite.is_some() {
+            // Legacy plaintext secrets migrated to the secret store: rewrite
+            // the JSON file so it only holds sentinels.
             let mut sanitized = prefs.clone();
-            sanitized.ai_api_key = value;
+            if let Some(value) = rewrite {
+                sanitized.ai_api_key = value;
+            }
+            if let Some(value) = proxy_rewrite {
+                sanitized.global_proxy = value;
+            }
             if let Ok(json) = serde_json::to_string_pretty(&sanitized) {
                 let _ = std::fs::write(&path, json);
             }
         }
         prefs.ai_api_key = real;
+        prefs.global_proxy = real_proxy;
         debug!(
             "Loaded prefs from JSON: theme={:?}, link_editor_theme={}, editor_theme={}, font_size={}, word_wrap={}, data_directory={:?}, auto_check_updates={}",
             prefs.theme,
@@ -509,9 +881,10 @@ impl ConfigStore {
 
     fn save_to_json(&self, prefs: &AppPreferences) -> Result<(), Box<dyn std::error::Error>> {
         let path = Self::json_path();
-        // The key goes to the OS keychain; the file keeps only a sentinel.
+        // Secrets go to the OS keychain; the file keeps only sentinels.
         let mut sanitized = prefs.clone();
         sanitized.ai_api_key = crate::secrets::store_or_keep("pref:ai_api_key", &prefs.ai_api_key);
+        sanitized.global_proxy = crate::proxy_tunnel::global_proxy_for_storage(&prefs.global_proxy);
         let content = serde_json::to_string_pretty(&sanitized)?;
         std::fs::write(path, content)?;
         Ok(())
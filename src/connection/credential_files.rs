@@ -0,0 +1,287 @@
+//! Passwords from the client credential files instead of Tabular's own store:
+//! `~/.pgpass` (or `$PGPASSFILE`) for PostgreSQL and `~/.my.cnf` for MySQL.
+//! A connection opts in with `use_credential_file`; the file is read when
+//! connections load (see pool::resolve_loaded_connection).
+//! Files readable or writable by others are refused, like the stock clients do.
+
+use crate::models;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PgpassEntry {
+    host: String,
+    port: String,
+    database: String,
+    username: String,
+    password: String,
+}
+
+impl PgpassEntry {
+    /// libpq rules: each of the first four fields matches literally or is `*`.
+    /// An empty `username` (the connection has none set) matches any entry.
+    fn matches(&self, host: &str, port: &str, database: &str, username: &str) -> bool {
+        let field = |pattern: &str, value: &str| pattern == "*" || pattern == value;
+        field(&self.host, host)
+            && field(&self.port, port)
+            && field(&self.database, database)
+            && (username.is_empty() || field(&self.username, username))
+    }
+}
+
+/// Lines of a `.pgpass` file: `host:port:database:username:password`, with
+/// `\:` and `\\` escapes. Comments and lines with too few fields are skipped.
+fn parse_pgpass(text: &str) -> Vec<PgpassEntry> {
+    text.lines()
+        .filter(|line| !line.trim_start().starts_with('#') && !line.trim().is_empty())
+        .filter_map(|line| {
+            let mut fields = Vec::with_capacity(5);
+            let mut current = String::new();
+            let mut chars = line.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => current.extend(chars.next()),
+                    // The password is the rest of the line, colons included
+                    ':' if fields.len() < 4 => fields.push(std::mem::take(&mut current)),
+                    c => current.push(c),
+                }
+            }
+            fields.push(current);
+            let [host, port, database, username, password] =
+                <[String; 5]>::try_from(fields).ok()?;
+            Some(PgpassEntry {
+                host,
+                port,
+                database,
+                username,
+                password,
+            })
+        })
+        .collect()
+}
+
+/// Client options of a MySQL option file: the `[client]` group, overridden
+/// by `[mysql]`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct MyCnfClient {
+    host: Option<String>,
+    port: Option<String>,
+    user: Option<String>,
+    password: Option<String>,
+    database: Option<String>,
+}
+
+fn parse_my_cnf(text: &str) -> MyCnfClient {
+    let mut client = MyCnfClient::default();
+    let mut overrides = MyCnfClient::default();
+    let mut group: Option<&mut MyCnfClient> = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['#', ';', '!']) {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            group = match name.trim() {
+                "client" => Some(&mut client),
+                "mysql" => Some(&mut overrides),
+                _ => None,
+            };
+            continue;
+        }
+        let Some(target) = group.as_deref_mut() else {
+            continue;
+        };
+        // `password` alone asks for a prompt; nothing to read
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = value.trim();
+        let value = match value.strip_prefix(['"', '\'']) {
+            Some(rest) => rest.strip_suffix(&value[..1]).unwrap_or(rest).to_string(),
+            None => value
+                .split_once(" #")
+                .map_or(value, |(v, _)| v)
+                .trim_end()
+                .to_string(),
+        };
+        let slot = match key.trim().replace('-', "_").as_str() {
+            "host" => &mut target.host,
+            "port" => &mut target.port,
+            "user" => &mut target.user,
+            "password" => &mut target.password,
+            "database" => &mut target.database,
+            _ => continue,
+        };
+        *slot = Some(value);
+    }
+    MyCnfClient {
+        host: overrides.host.or(client.host),
+        port: overrides.port.or(client.port),
+        user: overrides.user.or(client.user),
+        password: overrides.password.or(client.password),
+        database: overrides.database.or(client.database),
+    }
+}
+
+fn pgpass_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("PGPASSFILE") {
+        return Some(PathBuf::from(path));
+    }
+    if cfg!(windows) {
+        return dirs::config_dir().map(|d| d.join("postgresql").join("pgpass.conf"));
+    }
+    dirs::home_dir().map(|d| d.join(".pgpass"))
+}
+
+fn my_cnf_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|d| d.join(".my.cnf"))
+}
+
+/// Contents of a credential file, refusing one that others can read (or,
+/// for `.my.cnf`, write) the way libpq and the MySQL client ignore it.
+fn read_private_file(path: &Path, forbidden_mode: u32) -> Result<String, String> {
+    let shown = path.display();
+    let metadata = std::fs::metadata(path).map_err(|e| format!("Cannot read {}: {}", shown, e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = metadata.permissions().mode() & 0o777;
+        if mode & forbidden_mode != 0 {
+            return Err(format!(
+                "{} has permissions {:o}; restrict it with `chmod 600 {}`",
+                shown, mode, shown
+            ));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = (metadata, forbidden_mode);
+    std::fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {}", shown, e))
+}
+
+/// Fill in the password (and a missing username or database) of `connection`
+/// from its credential file. Connections of other types are left as they are.
+pub(crate) fn apply_credential_file(
+    connection: &mut models::structs::ConnectionConfig,
+) -> Result<(), String> {
+    match connection.connection_type {
+        models::enums::DatabaseType::PostgreSQL => {
+            let path = pgpass_path().ok_or("No home directory for .pgpass")?;
+            let text = read_private_file(&path, 0o077)?;
+            let port = if connection.port.is_empty() {
+                "5432"
+            } else {
+                &connection.port
+            };
+            let database = if connection.database.is_empty() {
+                "postgres"
+            } else {
+                &connection.database
+            };
+            let entry = parse_pgpass(&text)
+                .into_iter()
+                .find(|e| e.matches(&connection.host, port, database, &connection.username))
+                .ok_or_else(|| {
+                    format!(
+                        "No entry in {} matches {}:{}:{}:{}",
+                        path.display(),
+                        connection.host,
+                        port,
+                        database,
+                        connection.username
+                    )
+                })?;
+            if connection.username.is_empty() && entry.username != "*" {
+                connection.username = entry.username;
+            }
+            connection.password = entry.password;
+        }
+        models::enums::DatabaseType::MySQL => {
+            let path = my_cnf_path().ok_or("No home directory for .my.cnf")?;
+            let options = parse_my_cnf(&read_private_file(&path, 0o002)?);
+            // Options the file pins must agree with the connection
+            for (name, pinned, value) in [
+                ("host", &options.host, &connection.host),
+                ("port", &options.port, &connection.port),
+                ("user", &options.user, &connection.username),
+            ] {
+                if let Some(pinned) = pinned
+                    && !value.is_empty()
+                    && !pinned.eq_ignore_ascii_case(value)
+                {
+                    return Err(format!(
+                        "{} is for {} {}, not {}",
+                        path.display(),
+                        name,
+                        pinned,
+                        value
+                    ));
+                }
+            }
+            let password = options
+                .password
+                .ok_or_else(|| format!("{} has no password in [client]", path.display()))?;
+            if connection.username.is_empty()
+                && let Some(user) = options.user
+            {
+                connection.username = user;
+            }
+            if connection.database.is_empty()
+                && let Some(database) = options.database
+            {
+                connection.database = database;
+            }
+            connection.password = password;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pgpass_wildcards_and_escapes() {
+        let entries = parse_pgpass(
+            "# local dev\n\
+             localhost:5432:app:alice:s3cr\\:et\n\
+             *:5432:*:bob:any:colon\n\
+             db.example.com:*:reports:*:wild\n\
+             broken:line\n",
+        );
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].password, "s3cr:et");
+        assert_eq!(entries[1].password, "any:colon");
+
+        let find = |host, port, db, user| {
+            entries
+                .iter()
+                .find(|e| e.matches(host, port, db, user))
+                .map(|e| e.password.as_str())
+        };
+        assert_eq!(find("localhost", "5432", "app", "alice"), Some("s3cr:et"));
+        assert_eq!(find("localhost", "5433", "app", "alice"), None);
+        assert_eq!(find("anywhere", "5432", "shop", "bob"), Some("any:colon"));
+        assert_eq!(
+            find("db.example.com", "6432", "reports", "carol"),
+            Some("wild")
+        );
+        // The first matching line wins; no username matches on the other fields
+        assert_eq!(find("localhost", "5432", "app", ""), Some("s3cr:et"));
+        assert_eq!(find("db.example.com", "6432", "other", "carol"), None);
+    }
+
+    #[test]
+    fn my_cnf_client_group_with_mysql_overrides() {
+        let options = parse_my_cnf(
+            "[mysqld]\npassword=server\n\
+             [client]\nuser = app\npassword = \"p#ss word\"\nhost=db1 # primary\n\
+             [mysql]\ndatabase=shop\nuser=cli\n",
+        );
+        assert_eq!(options.password.as_deref(), Some("p#ss word"));
+        assert_eq!(options.host.as_deref(), Some("db1"));
+        assert_eq!(options.user.as_deref(), Some("cli"));
+        assert_eq!(options.database.as_deref(), Some("shop"));
+        assert_eq!(options.port, None);
+    }
+}
@@ -17,11 +17,12 @@ pub(crate) fn update_connection_in_database(
     if let Some(ref pool) = tabular.db_pool {
         if let Some(id) = connection.id {
             let pool_clone = pool.clone();
-            let connection = connection.clone();
+            let connection = connection.for_storage();
             let rt = tokio::runtime::Runtime::new().unwrap();
 
             // Restart any existing SSH tunnel with updated settings
             ssh_tunnel::shutdown_for_connection(&connection);
+            crate::proxy_tunnel::shutdown_by_id(id);
 
             // Externalize credentials to the secret store; columns get the
             // sentinel (or plaintext when no backend is available).
@@ -37,10 +38,18 @@ pub(crate) fn update_connection_in_database(
                 &crate::secrets::connection_secret_name(id, "ssh_password"),
                 &connection.ssh_password,
             );
+            let ssh_passphrase_stored = crate::secrets::store_or_keep(
+                &crate::secrets::connection_secret_name(id, "ssh_key_passphrase"),
+                &connection.ssh_key_passphrase,
+            );
+            let proxy_stored = crate::proxy_tunnel::proxy_json_for_storage(
+                &connection.proxy,
+                &crate::secrets::connection_secret_name(id, "proxy_password"),
+            );
 
             let result = rt.block_on(async {
                 sqlx::query(
-                    "UPDATE connections SET name = ?, host = ?, port = ?, username = ?, password = ?, database_name = ?, connection_type = ?, folder = ?, ssh_enabled = ?, ssh_host = ?, ssh_port = ?, ssh_username = ?, ssh_auth_method = ?, ssh_private_key = ?, ssh_password = ?, ssh_accept_unknown_host_keys = ? WHERE id = ?"
+                    "UPDATE connections SET name = ?, host = ?, port = ?, username = ?, password = ?, database_name = ?, connection_type = ?, folder = ?, ssh_enabled = ?, ssh_host = ?, ssh_port = ?, ssh_username = ?, ssh_auth_method = ?, ssh_private_key = ?, ssh_password = ?, ssh_key_passphrase = ?, ssh_accept_unknown_host_keys = ?, mssql_encrypt = ?, mssql_trust_server_certificate = ?, sqlite_attachments = ?, default_schema = ?, use_credential_file = ?, label = ?, color = ?, proxy = ? WHERE id = ?"
                 )
                 .bind(connection.name)
                 .bind(connection.host)
@@ -57,7 +66,16 @@ pub(crate) fn update_connection_in_database(
                 .bind(connection.ssh_auth_method.as_db_value())
                 .bind(ssh_key_stored)
                 .bind(ssh_password_stored)
+                .bind(ssh_passphrase_stored)
                 .bind(if connection.ssh_accept_unknown_host_keys { 1 } else { 0 })
+                .bind(if connection.mssql_encrypt { 1 } else { 0 })
+                .bind(if connection.mssql_trust_server_certificate { 1 } else { 0 })
+                .bind(serde_json::to_string(&connection.sqlite_attachments).unwrap_or_else(|_| "[]".to_string()))
+                .bind(connection.default_schema)
+                .bind(if connection.use_credential_file { 1 } else { 0 })
+                .bind(connection.label)
+                .bind(connection.color)
+                .bind(proxy_stored)
                 .bind(id)
                 .execute(pool_clone.as_ref())
                 .await
@@ -86,6 +104,65 @@ pub(crate) fn update_connection_in_database(
     }
 }
 
+/// Database a new tab on `connection` starts in: the one last picked in a tab,
+/// else the configured default. SQLite and HTTP connections have none.
+pub(crate) fn default_tab_database(connection: &models::structs::ConnectionConfig) -> Option<String> {
+    if matches!(
+        connection.connection_type,
+        models::enums::DatabaseType::SQLite | models::enums::DatabaseType::ApiHttp
+    ) {
+        return None;
+    }
+    connection
+        .last_used_database
+        .iter()
+        .chain(std::iter::once(&connection.database))
+        .map(|db| db.trim())
+        .find(|db| !db.is_empty())
+        .map(str::to_string)
+}
+
+/// Schema (PostgreSQL search_path) a new tab on `connection` starts in.
+pub(crate) fn default_tab_schema(connection: &models::structs::ConnectionConfig) -> Option<String> {
+    let schema = connection.default_schema.trim();
+    (connection.connection_type == models::enums::DatabaseType::PostgreSQL && !schema.is_empty())
+        .then(|| schema.to_string())
+}
+
+/// Record `database` as the last one used on `connection_id` so new tabs and
+/// the next session start there.
+pub(crate) fn remember_last_used_database(
+    tabular: &mut window_egui::Tabular,
+    connection_id: i64,
+    database: Option<&str>,
+) {
+    let database = database.map(str::trim).filter(|db| !db.is_empty()).map(str::to_string);
+    let Some(connection) = tabular
+        .connections
+        .iter_mut()
+        .find(|c| c.id == Some(connection_id))
+    else {
+        return;
+    };
+    if connection.last_used_database == database {
+        return;
+    }
+    connection.last_used_database = database.clone();
+    if let Some(pool) = tabular.db_pool.clone() {
+        tabular.get_runtime().spawn(async move {
+            if let Err(e) =
+                sqlx::query("UPDATE connections SET last_used_database = ? WHERE id = ?")
+                    .bind(database)
+                    .bind(connection_id)
+                    .execute(pool.as_ref())
+                    .await
+            {
+                warn!("Failed to save last used database: {}", e);
+            }
+        });
+    }
+}
+
 pub(crate) fn remove_connection(tabular: &mut window_egui::Tabular, connection_id: i64) {
     if let Some(ref pool) = tabular.db_pool {
         let pool_clone = pool.clone();
@@ -139,212 +216,287 @@ pub(crate) fn remove_connection(tabular: &mut window_egui::Tabular, connection_i
     tabular.connection_pools.remove(&connection_id);
     tabular.pending_connection_pools.remove(&connection_id);
     ssh_tunnel::shutdown_by_id(connection_id);
+    crate::proxy_tunnel::shutdown_by_id(connection_id);
 
     crate::sidebar_database::remove_connection_from_tree(tabular, connection_id);
 
     tabular.needs_refresh = true;
 }
 
-pub(crate) fn test_database_connection(
+/// A dead host fails the test after this long instead of hanging it.
+const CONNECTION_TEST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Run `query` and time it, for the latency half of a [`models::structs::ConnectionProbe`].
+async fn timed<T, E>(
+    query: impl std::future::IntoFuture<Output = Result<T, E>>,
+) -> Result<(T, std::time::Duration), E> {
+    let started = std::time::Instant::now();
+    let value = query.await?;
+    Ok((value, started.elapsed()))
+}
+
+/// Connect with `connection`, then time a server version query. Errors are
+/// user-facing and name the driver and the step that failed.
+pub(crate) async fn probe_database_connection(
     connection: &models::structs::ConnectionConfig,
-) -> (bool, String) {
-    let rt = tokio::runtime::Runtime::new().unwrap();
+) -> Result<models::structs::ConnectionProbe, String> {
+    let connection = super::pool::resolve_connection_env(connection)?;
+    let connection = &connection;
+    let (server_version, latency) = match connection.connection_type {
+        models::enums::DatabaseType::MySQL => {
+            let (target_host, target_port) = resolve_connection_target(connection)?;
+            let encoded_username = modules::url_encode(&connection.username);
+            let encoded_password = modules::url_encode(&connection.password);
+            let connection_string = format!(
+                "mysql://{}:{}@{}:{}/{}",
+                encoded_username,
+                encoded_password,
+                target_host,
+                target_port,
+                connection.database
+            );
 
-    rt.block_on(async {
-        match connection.connection_type {
-            models::enums::DatabaseType::MySQL => {
-                let (target_host, target_port) = match resolve_connection_target(connection) {
-                    Ok(tuple) => tuple,
-                    Err(err) => return (false, err),
-                };
-                let encoded_username = modules::url_encode(&connection.username);
-                let encoded_password = modules::url_encode(&connection.password);
-                let connection_string = format!(
-                    "mysql://{}:{}@{}:{}/{}",
-                    encoded_username,
-                    encoded_password,
-                    target_host,
-                    target_port,
-                    connection.database
-                );
-
-                match MySqlPoolOptions::new()
-                    .max_connections(1)
-                    .acquire_timeout(std::time::Duration::from_secs(10))
-                    .connect(&connection_string)
-                    .await
-                {
-                    Ok(pool) => match sqlx::query("SELECT 1").execute(&pool).await {
-                        Ok(_) => (true, "MySQL connection successful!".to_string()),
-                        Err(e) => (false, format!("MySQL query failed: {}", e)),
-                    },
-                    Err(e) => (false, format!("MySQL connection failed: {}", e)),
+            let pool = MySqlPoolOptions::new()
+                .max_connections(1)
+                .acquire_timeout(std::time::Duration::from_secs(10))
+                .connect(&connection_string)
+                .await
+                .map_err(|e| format!("MySQL connection failed: {}", e))?;
+            timed(
+                sqlx::query_scalar::<_, String>(
+                    "SELECT CONCAT(@@version, ' ', @@version_comment)",
+                )
+                .fetch_one(&pool),
+            )
+            .await
+            .map_err(|e| format!("MySQL query failed: {}", e))?
+        }
+        models::enums::DatabaseType::PostgreSQL => {
+            let (target_host, target_port) = resolve_connection_target(connection)?;
+            let connection_string = format!(
+                "postgresql://{}:{}@{}:{}/{}",
+                connection.username,
+                connection.password,
+                target_host,
+                target_port,
+                connection.database
+            );
+
+            let pool = PgPoolOptions::new()
+                .max_connections(1)
+                .acquire_timeout(std::time::Duration::from_secs(10))
+                .connect(&connection_string)
+                .await
+                .map_err(|e| format!("PostgreSQL connection failed: {}", e))?;
+            timed(sqlx::query_scalar::<_, String>("SELECT version()").fetch_one(&pool))
+                .await
+                .map_err(|e| format!("PostgreSQL query failed: {}", e))?
+        }
+        models::enums::DatabaseType::SQLite => {
+            let raw = if connection.database.starts_with("sqlite:") {
+                connection.database.clone()
+            } else if !connection.host.is_empty() && connection.host.starts_with("sqlite:") {
+                connection.host.clone()
+            } else if !connection.host.is_empty() {
+                format!("sqlite:{}", connection.host)
+            } else {
+                format!("sqlite:{}", connection.database)
+            };
+
+            if let Some(path_str) = raw.strip_prefix("sqlite:") {
+                let path = std::path::PathBuf::from(path_str);
+                if let Some(parent) = path.parent() {
+                    let _ = std::fs::create_dir_all(parent);
                 }
-            }
-            models::enums::DatabaseType::PostgreSQL => {
-                let (target_host, target_port) = match resolve_connection_target(connection) {
-                    Ok(tuple) => tuple,
-                    Err(err) => return (false, err),
-                };
-                let connection_string = format!(
-                    "postgresql://{}:{}@{}:{}/{}",
-                    connection.username,
-                    connection.password,
-                    target_host,
-                    target_port,
-                    connection.database
-                );
-
-                match PgPoolOptions::new()
-                    .max_connections(1)
-                    .acquire_timeout(std::time::Duration::from_secs(10))
-                    .connect(&connection_string)
-                    .await
+                if !path.exists()
+                    && let Ok(_file) = std::fs::File::create(&path)
                 {
-                    Ok(pool) => match sqlx::query("SELECT 1").execute(&pool).await {
-                        Ok(_) => (true, "PostgreSQL connection successful!".to_string()),
-                        Err(e) => (false, format!("PostgreSQL query failed: {}", e)),
-                    },
-                    Err(e) => (false, format!("PostgreSQL connection failed: {}", e)),
+                    // file created successfully
                 }
             }
-            models::enums::DatabaseType::SQLite => {
-                let raw = if connection.database.starts_with("sqlite:") {
-                    connection.database.clone()
-                } else if !connection.host.is_empty() && connection.host.starts_with("sqlite:") {
-                    connection.host.clone()
-                } else if !connection.host.is_empty() {
-                    format!("sqlite:{}", connection.host)
-                } else {
-                    format!("sqlite:{}", connection.database)
-                };
-
-                if let Some(path_str) = raw.strip_prefix("sqlite:") {
-                    let path = std::path::PathBuf::from(path_str);
-                    if let Some(parent) = path.parent() {
-                        let _ = std::fs::create_dir_all(parent);
-                    }
-                    if !path.exists()
-                        && let Ok(_file) = std::fs::File::create(&path)
-                    {
-                        // file created successfully
-                    }
-                }
 
-                match SqlitePoolOptions::new()
-                    .max_connections(1)
-                    .acquire_timeout(std::time::Duration::from_secs(10))
-                    .connect(&raw)
-                    .await
-                {
-                    Ok(pool) => match sqlx::query("SELECT 1").execute(&pool).await {
-                        Ok(_) => (true, "SQLite connection successful!".to_string()),
-                        Err(e) => (false, format!("SQLite query failed: {}", e)),
-                    },
-                    Err(e) => (false, format!("SQLite connection failed: {}", e)),
-                }
-            }
-            models::enums::DatabaseType::MongoDB => {
-                let (target_host, target_port) = match resolve_connection_target(connection) {
-                    Ok(tuple) => tuple,
-                    Err(err) => return (false, err),
-                };
-                let uri = if connection.username.is_empty() {
-                    format!("mongodb://{}:{}", target_host, target_port)
-                } else if connection.password.is_empty() {
-                    format!(
-                        "mongodb://{}@{}:{}",
-                        connection.username, target_host, target_port
-                    )
-                } else {
-                    let enc_user = modules::url_encode(&connection.username);
-                    let enc_pass = modules::url_encode(&connection.password);
-                    format!(
-                        "mongodb://{}:{}@{}:{}",
-                        enc_user, enc_pass, target_host, target_port
-                    )
-                };
-                match MongoClient::with_uri_str(uri).await {
-                    Ok(client) => {
-                        let admin = client.database("admin");
-                        match admin.run_command(mongodb::bson::doc!("ping": 1)).await {
-                            Ok(_) => (true, "MongoDB connection successful!".to_string()),
-                            Err(e) => (false, format!("MongoDB ping failed: {}", e)),
-                        }
-                    }
-                    Err(e) => (false, format!("MongoDB client error: {}", e)),
-                }
-            }
-            models::enums::DatabaseType::Redis => {
-                let (target_host, target_port) = match resolve_connection_target(connection) {
-                    Ok(tuple) => tuple,
-                    Err(err) => return (false, err),
-                };
-                let connection_string = if connection.password.is_empty() {
-                    format!("redis://{}:{}", target_host, target_port)
-                } else {
-                    format!(
-                        "redis://{}:{}@{}:{}",
-                        connection.username, connection.password, target_host, target_port
-                    )
-                };
-
-                match Client::open(connection_string) {
-                    Ok(client) => match client.get_connection() {
-                        Ok(mut conn) => {
-                            match redis::cmd("PING").query::<String>(&mut conn) {
-                                Ok(response) => {
-                                    if response == "PONG" {
-                                        (true, "Redis connection successful!".to_string())
-                                    } else {
-                                        (
-                                            false,
-                                            "Redis PING returned unexpected response".to_string(),
-                                        )
-                                    }
-                                }
-                                Err(e) => (false, format!("Redis PING failed: {}", e)),
-                            }
-                        }
-                        Err(e) => (false, format!("Redis connection failed: {}", e)),
-                    },
-                    Err(e) => (false, format!("Redis client creation failed: {}", e)),
-                }
-            }
-            models::enums::DatabaseType::MsSQL => {
-                let (target_host, target_port) = match resolve_connection_target(connection) {
-                    Ok(tuple) => tuple,
-                    Err(err) => return (false, err),
-                };
-                let host = target_host.clone();
-                let port: u16 = target_port.parse().unwrap_or(1433);
-                let db = connection.database.clone();
-                let user = connection.username.clone();
-                let pass = connection.password.clone();
-                let res = async {
-                    let mut client =
-                        crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db))
-                            .await?;
+            let pool = SqlitePoolOptions::new()
+                .max_connections(1)
+                .acquire_timeout(std::time::Duration::from_secs(10))
+                .connect(&raw)
+                .await
+                .map_err(|e| format!("SQLite connection failed: {}", e))?;
+            let (version, latency) = timed(
+                sqlx::query_scalar::<_, String>("SELECT sqlite_version()").fetch_one(&pool),
+            )
+            .await
+            .map_err(|e| format!("SQLite query failed: {}", e))?;
+            (format!("SQLite {}", version), latency)
+        }
+        models::enums::DatabaseType::MongoDB => {
+            let (target_host, target_port) = resolve_connection_target(connection)?;
+            let uri = if connection.username.is_empty() {
+                format!("mongodb://{}:{}", target_host, target_port)
+            } else if connection.password.is_empty() {
+                format!(
+                    "mongodb://{}@{}:{}",
+                    connection.username, target_host, target_port
+                )
+            } else {
+                let enc_user = modules::url_encode(&connection.username);
+                let enc_pass = modules::url_encode(&connection.password);
+                format!(
+                    "mongodb://{}:{}@{}:{}",
+                    enc_user, enc_pass, target_host, target_port
+                )
+            };
+            let client = MongoClient::with_uri_str(uri)
+                .await
+                .map_err(|e| format!("MongoDB client error: {}", e))?;
+            let admin = client.database("admin");
+            // The first command also selects a server; ping first so the
+            // timed buildInfo is a plain round trip.
+            admin
+                .run_command(mongodb::bson::doc!("ping": 1))
+                .await
+                .map_err(|e| format!("MongoDB ping failed: {}", e))?;
+            let (info, latency) = timed(admin.run_command(mongodb::bson::doc!("buildInfo": 1)))
+                .await
+                .map_err(|e| format!("MongoDB buildInfo failed: {}", e))?;
+            let version = info.get_str("version").unwrap_or_default();
+            (format!("MongoDB {}", version), latency)
+        }
+        models::enums::DatabaseType::Redis => {
+            let (target_host, target_port) = resolve_connection_target(connection)?;
+            let connection_string = if connection.password.is_empty() {
+                format!("redis://{}:{}", target_host, target_port)
+            } else {
+                format!(
+                    "redis://{}:{}@{}:{}",
+                    connection.username, connection.password, target_host, target_port
+                )
+            };
+
+            let client = Client::open(connection_string)
+                .map_err(|e| format!("Redis client creation failed: {}", e))?;
+            let mut conn = client
+                .get_multiplexed_async_connection()
+                .await
+                .map_err(|e| format!("Redis connection failed: {}", e))?;
+            let (info, latency) = timed(
+                redis::cmd("INFO")
+                    .arg("server")
+                    .query_async::<String>(&mut conn),
+            )
+            .await
+            .map_err(|e| format!("Redis INFO failed: {}", e))?;
+            let version = info
+                .lines()
+                .find_map(|line| line.strip_prefix("redis_version:"))
+                .unwrap_or_default()
+                .trim();
+            (format!("Redis {}", version), latency)
+        }
+        models::enums::DatabaseType::MsSQL => {
+            let (target_host, target_port) = resolve_connection_target(connection)?;
+            let port: u16 = target_port.parse().unwrap_or(1433);
+            let tls = crate::driver_mssql::MssqlTls::from_connection(connection);
+            let res = async {
+                let mut client = crate::driver_mssql::connect_mssql(
+                    &target_host,
+                    port,
+                    &connection.username,
+                    &connection.password,
+                    Some(&connection.database),
+                    tls,
+                )
+                .await?;
+                let (rows, latency) = timed(async {
                     client
-                        .simple_query("SELECT 1")
+                        .query("SELECT CAST(@@VERSION AS NVARCHAR(4000))", &[])
                         .await
-                        .map_err(|e| e.to_string())?;
-                    Ok::<_, String>(())
-                }
-                .await;
-                match res {
-                    Ok(_) => (true, "MsSQL connection successful!".to_string()),
-                    Err(e) => (false, format!("MsSQL connection failed: {}", e)),
-                }
+                        .map_err(|e| e.to_string())?
+                        .collect_all()
+                        .await
+                        .map_err(|e| e.to_string())
+                })
+                .await?;
+                let version = rows.first().and_then(|r| r.get_string(0)).unwrap_or_default();
+                Ok::<_, String>((version, latency))
             }
-            models::enums::DatabaseType::ApiHttp => (
-                false,
-                "API-HTTP connections do not support database testing".to_string(),
-            ),
+            .await;
+            res.map_err(|e| format!("MsSQL connection failed: {}", e))?
+        }
+        models::enums::DatabaseType::ApiHttp => {
+            return Err("API-HTTP connections do not support database testing".to_string());
         }
+    };
+    Ok(models::structs::ConnectionProbe {
+        latency,
+        server_version,
     })
 }
 
+/// Blocking connection test for the connection dialog: `(success, message)`,
+/// with latency and server version on success.
+pub(crate) fn test_database_connection(
+    connection: &models::structs::ConnectionConfig,
+) -> (bool, String) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let probe = rt.block_on(async {
+        tokio::time::timeout(
+            CONNECTION_TEST_TIMEOUT,
+            probe_database_connection(connection),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(format!(
+                "No response within {} seconds",
+                CONNECTION_TEST_TIMEOUT.as_secs()
+            ))
+        })
+    });
+    match probe {
+        Ok(probe) => (
+            true,
+            format!(
+                "{} connection successful! {}",
+                connection.connection_type.badge_label(),
+                probe.summary()
+            ),
+        ),
+        Err(e) => (false, e),
+    }
+}
+
+/// Run [`test_database_connection`] on a worker thread; the dialog polls the
+/// receiver while showing a spinner.
+pub(crate) fn start_connection_test(
+    tabular: &mut window_egui::Tabular,
+    connection: models::structs::ConnectionConfig,
+) {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(test_database_connection(&connection));
+    });
+    tabular.test_connection_status = None;
+    tabular.test_connection_in_progress = true;
+    tabular.test_connection_receiver = Some(rx);
+}
+
+/// Pick up a finished [`start_connection_test`]; true while it is running.
+pub(crate) fn poll_connection_test(tabular: &mut window_egui::Tabular) -> bool {
+    let Some(rx) = &tabular.test_connection_receiver else {
+        return false;
+    };
+    let result = match rx.try_recv() {
+        Ok(result) => result,
+        Err(std::sync::mpsc::TryRecvError::Empty) => return true,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+            (false, "Connection test stopped unexpectedly".to_string())
+        }
+    };
+    tabular.test_connection_status = Some(result);
+    tabular.test_connection_in_progress = false;
+    tabular.test_connection_receiver = None;
+    false
+}
+
 /// Returns true if the error is a SQLite database corruption error (SQLITE_CORRUPT, code 11).
 fn is_sqlite_corrupt(e: &sqlx::Error) -> bool {
     if let sqlx::Error::Database(db_err) = e {
@@ -475,6 +627,29 @@ async fn recover_corrupt_cache(cache_pool: &SqlitePool) -> bool {
     all_ok
 }
 
+/// Sample how far a refresh has filled the schema cache for `connection_id`.
+pub(crate) async fn refresh_progress_snapshot(
+    cache_pool: &SqlitePool,
+    connection_id: i64,
+) -> models::structs::SchemaRefreshProgress {
+    let counts = sqlx::query_as::<_, (i64, i64, i64)>(
+        "SELECT (SELECT COUNT(*) FROM database_cache WHERE connection_id = ?), \
+                (SELECT COUNT(DISTINCT database_name) FROM table_cache WHERE connection_id = ?), \
+                (SELECT COUNT(*) FROM table_cache WHERE connection_id = ?)",
+    )
+    .bind(connection_id)
+    .bind(connection_id)
+    .bind(connection_id)
+    .fetch_one(cache_pool)
+    .await
+    .unwrap_or((0, 0, 0));
+    models::structs::SchemaRefreshProgress {
+        databases_total: counts.0.max(0) as usize,
+        databases_done: counts.1.max(0) as usize,
+        tables_cached: counts.2.max(0) as usize,
+    }
+}
+
 #[allow(dead_code)]
 pub(crate) async fn refresh_connection_background_async(
     connection_id: i64,
@@ -495,7 +670,17 @@ pub(crate) async fn refresh_connection_background_async(
                     COALESCE(ssh_auth_method, 'key') AS ssh_auth_method, \
                     COALESCE(ssh_private_key, '') AS ssh_private_key, \
                     COALESCE(ssh_password, '') AS ssh_password, \
-                    COALESCE(ssh_accept_unknown_host_keys, 0) AS ssh_accept_unknown_host_keys \
+                    COALESCE(ssh_key_passphrase, '') AS ssh_key_passphrase, \
+                    COALESCE(ssh_accept_unknown_host_keys, 0) AS ssh_accept_unknown_host_keys, \
+                    COALESCE(mssql_encrypt, 1) AS mssql_encrypt, \
+                    COALESCE(mssql_trust_server_certificate, 1) AS mssql_trust_server_certificate, \
+                    COALESCE(sqlite_attachments, '[]') AS sqlite_attachments, \
+                    COALESCE(default_schema, '') AS default_schema, \
+                    COALESCE(use_credential_file, 0) AS use_credential_file, \
+                    COALESCE(label, '') AS label, \
+                    COALESCE(color, '') AS color, \
+                    COALESCE(proxy, '{}') AS proxy, \
+                    last_used_database \
              FROM connections WHERE id = ?"
         )
         .bind(connection_id)
@@ -532,9 +717,33 @@ pub(crate) async fn refresh_connection_background_async(
                 .try_get::<String, _>("ssh_private_key")
                 .unwrap_or_default();
             let ssh_password = row.try_get::<String, _>("ssh_password").unwrap_or_default();
+            let ssh_key_passphrase = row
+                .try_get::<String, _>("ssh_key_passphrase")
+                .unwrap_or_default();
             let ssh_accept_unknown_host_keys = row
                 .try_get::<i64, _>("ssh_accept_unknown_host_keys")
                 .unwrap_or(0);
+            let mssql_encrypt = row.try_get::<i64, _>("mssql_encrypt").unwrap_or(1);
+            let mssql_trust_server_certificate = row
+                .try_get::<i64, _>("mssql_trust_server_certificate")
+                .unwrap_or(1);
+            let sqlite_attachments = row
+                .try_get::<String, _>("sqlite_attachments")
+                .ok()
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+            let default_schema = row.try_get::<String, _>("default_schema").unwrap_or_default();
+            let use_credential_file = row.try_get::<i64, _>("use_credential_file").unwrap_or(0);
+            let label = row.try_get::<String, _>("label").unwrap_or_default();
+            let color = row.try_get::<String, _>("color").unwrap_or_default();
+            let (proxy, _) = crate::proxy_tunnel::proxy_from_storage(
+                &row.try_get::<String, _>("proxy").unwrap_or_default(),
+                &crate::secrets::connection_secret_name(id, "proxy_password"),
+                false,
+            );
+            let last_used_database = row
+                .try_get::<Option<String>, _>("last_used_database")
+                .unwrap_or(None);
 
             // Hydrate credentials from the secret store (read-only; the main
             // loader in sidebar_database.rs owns legacy plaintext migration).
@@ -550,8 +759,12 @@ pub(crate) async fn refresh_connection_background_async(
                 &crate::secrets::connection_secret_name(id, "ssh_password"),
                 &ssh_password,
             );
+            let ssh_key_passphrase = crate::secrets::resolve_readonly(
+                &crate::secrets::connection_secret_name(id, "ssh_key_passphrase"),
+                &ssh_key_passphrase,
+            );
 
-            let connection = models::structs::ConnectionConfig {
+            let mut connection = models::structs::ConnectionConfig {
                 id: Some(id),
                 name,
                 host,
@@ -575,10 +788,22 @@ pub(crate) async fn refresh_connection_background_async(
                 ssh_auth_method: models::enums::SshAuthMethod::from_db_value(&ssh_auth_method),
                 ssh_private_key,
                 ssh_password,
+                ssh_key_passphrase,
                 ssh_accept_unknown_host_keys: ssh_accept_unknown_host_keys != 0,
+                mssql_encrypt: mssql_encrypt != 0,
+                mssql_trust_server_certificate: mssql_trust_server_certificate != 0,
                 custom_views: Vec::new(),
+                sqlite_attachments,
                 replication_master_id: None,
+                default_schema,
+                last_used_database,
+                use_credential_file: use_credential_file != 0,
+                label,
+                color,
+                proxy,
+                templates: None,
             };
+            super::pool::resolve_loaded_connection(&mut connection);
 
             debug!(
                 "[refresh_connection] clearing SQLite cache rows for connection {} before reload",
@@ -731,3 +956,31 @@ pub(crate) async fn refresh_connection_background_async(
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use models::{enums::DatabaseType, structs::ConnectionConfig};
+
+    #[test]
+    fn new_tabs_start_in_last_used_then_configured_database() {
+        let mut connection = ConnectionConfig {
+            connection_type: DatabaseType::MySQL,
+            database: "shop".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(default_tab_database(&connection).as_deref(), Some("shop"));
+        connection.last_used_database = Some("analytics".to_string());
+        assert_eq!(default_tab_database(&connection).as_deref(), Some("analytics"));
+        connection.last_used_database = Some(" ".to_string());
+        assert_eq!(default_tab_database(&connection).as_deref(), Some("shop"));
+
+        connection.connection_type = DatabaseType::SQLite;
+        assert_eq!(default_tab_database(&connection), None);
+
+        connection.default_schema = "billing".to_string();
+        assert_eq!(default_tab_schema(&connection), None);
+        connection.connection_type = DatabaseType::PostgreSQL;
+        assert_eq!(default_tab_schema(&connection).as_deref(), Some("billing"));
+    }
+}
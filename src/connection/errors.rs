@@ -0,0 +1,89 @@
+// connection/errors.rs
+// Classification of driver error messages into coarse categories the UI can
+// act on (e.g. offering "Reconnect and retry" when the connection dropped).
+
+/// Coarse category of a failed query, derived from the driver error text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryErrorCategory {
+    /// The underlying connection is gone (closed socket, server restart,
+    /// idle timeout, dead SSH tunnel). The pool must be rebuilt before retrying.
+    ConnectionLost,
+    /// Anything else (syntax errors, constraint violations, permissions, ...).
+    Other,
+}
+
+/// Lower-cased fragments emitted by sqlx (MySQL/PostgreSQL/SQLite), the
+/// MsSQL client, Redis and MongoDB when the transport dies mid-session.
+const CONNECTION_LOST_PATTERNS: &[&str] = &[
+    "broken pipe",
+    "connection reset",
+    "connection closed",
+    "connection refused",
+    "connection aborted",
+    "connection was closed",
+    "connection is closed",
+    "closed connection",
+    "server has gone away",          // MySQL 2006
+    "lost connection to",            // MySQL 2013
+    "terminating connection",        // PostgreSQL admin shutdown / idle timeout
+    "server closed the connection",  // PostgreSQL
+    "unexpected eof",
+    "unexpectedeof",
+    "pooltimedout",
+    "pool timed out",
+    "pool was closed",
+    "poolclosed",
+    "os error 32",                   // EPIPE
+    "os error 54",                   // ECONNRESET (macOS)
+    "os error 104",                  // ECONNRESET (Linux)
+    "os error 10053",                // WSAECONNABORTED
+    "os error 10054",                // WSAECONNRESET
+    "not connected",
+];
+
+/// Map a driver error message to a [`QueryErrorCategory`].
+pub fn classify_query_error(message: &str) -> QueryErrorCategory {
+    let lower = message.to_lowercase();
+    if CONNECTION_LOST_PATTERNS.iter().any(|p| lower.contains(p)) {
+        QueryErrorCategory::ConnectionLost
+    } else {
+        QueryErrorCategory::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_disconnects_across_drivers() {
+        for msg in [
+            "error communicating with database: Broken pipe (os error 32)",
+            "error returned from database: 2006 (HY000): MySQL server has gone away",
+            "Lost connection to MySQL server during query",
+            "FATAL: terminating connection due to administrator command",
+            "pool timed out while waiting for an open connection",
+            "Connection reset by peer (os error 104)",
+        ] {
+            assert_eq!(
+                classify_query_error(msg),
+                QueryErrorCategory::ConnectionLost,
+                "{msg}"
+            );
+        }
+    }
+
+    #[test]
+    fn ordinary_errors_are_not_disconnects() {
+        for msg in [
+            "syntax error at or near \"SELEC\"",
+            "Table 'shop.orders' doesn't exist",
+            "duplicate key value violates unique constraint \"users_pkey\"",
+            // File and parse errors are not dropped connections
+            "io error: No such file or directory (os error 2)",
+            "invalid input syntax: unexpected end of file",
+        ] {
+            assert_eq!(classify_query_error(msg), QueryErrorCategory::Other, "{msg}");
+        }
+    }
+}
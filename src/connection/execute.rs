@@ -1,10 +1,11 @@
 use crate::{
-    driver_mssql, driver_mysql, driver_sqlite, models, modules,
+    driver_mssql, driver_mysql, driver_postgres, driver_sqlite, models, modules, query_tools,
     window_egui::Tabular,
 };
 use log::debug;
-use sqlx::{Column, Row, TypeInfo};
+use sqlx::{Column, Row, SqlSafeStr, TypeInfo};
 use sqlx::Connection as SqlxConnection;
+use sqlx::Executor;
 use sqlx::mysql::MySqlConnection;
 use std::sync::Arc;
 use std::time::Instant;
@@ -12,7 +13,8 @@ use std::time::Instant;
 use super::pool::{resolve_connection_target, try_get_connection_pool};
 use super::sql::{
     infer_column_origins, infer_select_headers, is_simple_select_statement,
-    query_contains_pagination, should_enable_auto_pagination,
+    query_contains_pagination, should_enable_auto_pagination, split_sql_statements,
+    statement_returns_rows,
 };
 use super::types::{
     QueryExecutionError, QueryExecutionOptions, QueryJob, QueryJobOutput, QueryPreparationError,
@@ -24,6 +26,18 @@ pub(crate) fn prepare_query_job(
     connection_id: i64,
     query: String,
     job_id: u64,
+) -> Result<QueryJob, QueryPreparationError> {
+    prepare_bound_query_job(tabular, connection_id, query, job_id, &[])
+}
+
+/// Like [`prepare_query_job`], with `inputs` (placeholder name, raw value)
+/// bound natively to the placeholders of `query`.
+pub(crate) fn prepare_bound_query_job(
+    tabular: &mut Tabular,
+    connection_id: i64,
+    query: String,
+    job_id: u64,
+    inputs: &[(String, String)],
 ) -> Result<QueryJob, QueryPreparationError> {
     let connection = tabular
         .connections
@@ -38,6 +52,12 @@ pub(crate) fn prepare_query_job(
         .and_then(|t| t.database_name.clone())
         .filter(|s| !s.trim().is_empty());
 
+    let selected_schema = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.schema_name.clone())
+        .filter(|s| !s.trim().is_empty());
+
     let dba_special_mode = tabular
         .query_tabs
         .get(tabular.active_tab_index)
@@ -60,18 +80,30 @@ pub(crate) fn prepare_query_job(
         Some(tabular.current_base_query.clone())
     };
 
+    // Values from the parameters panel are bound, not written into the text
+    let target = query_tools::ParamTarget::for_database(&connection.connection_type);
+    let (query, params) = if inputs.is_empty() || !target.binds_natively() {
+        (query, Vec::new())
+    } else {
+        let bound = query_tools::bind_query(&query, inputs, target);
+        (bound.sql, bound.values)
+    };
+
     let options = QueryExecutionOptions {
         connection_id,
         connection,
         query,
         selected_database,
+        selected_schema,
         use_server_pagination: tabular.use_server_pagination,
         current_page: tabular.current_page,
         page_size: tabular.page_size,
         base_query,
         dba_special_mode,
         save_to_history: true,
-        ast_enabled: cfg!(feature = "query_ast"),
+        ast_enabled: cfg!(feature = "query_ast") && params.is_empty(),
+        stream: None,
+        params,
     };
 
     Ok(QueryJob {
@@ -134,6 +166,33 @@ pub(crate) fn spawn_query_job_batch(
     Ok(handle)
 }
 
+/// Run `query` in the background on the active tab's connection, database
+/// and schema, outside the result grid and query history. The receiver yields
+/// the finished message; used for side queries such as dry-run row counts.
+pub(crate) fn spawn_side_query(
+    tabular: &mut Tabular,
+    connection_id: i64,
+    query: String,
+) -> Result<std::sync::mpsc::Receiver<QueryResultMessage>, QueryPreparationError> {
+    if !tabular.connection_pools.contains_key(&connection_id) {
+        super::pool::ensure_background_pool_creation(tabular, connection_id);
+    }
+    let runtime = tabular
+        .runtime
+        .clone()
+        .ok_or(QueryPreparationError::RuntimeUnavailable)?;
+    let mut job = prepare_query_job(tabular, connection_id, query, 0)?;
+    job.options.use_server_pagination = false;
+    job.options.base_query = None;
+    job.options.save_to_history = false;
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    runtime.spawn(async move {
+        let _ = sender.send(execute_query_job(job).await);
+    });
+    Ok(receiver)
+}
+
 fn skipped_statement_message(job: &QueryJob) -> QueryResultMessage {
     let message = "Skipped: a previous statement in this batch failed".to_string();
     QueryResultMessage {
@@ -149,11 +208,12 @@ fn skipped_statement_message(job: &QueryJob) -> QueryResultMessage {
         ast_debug_sql: None,
         ast_headers: None,
         affected_rows: None,
+        produced_result_set: false,
         column_metadata: None,
     }
 }
 
-async fn execute_query_job(job: QueryJob) -> QueryResultMessage {
+pub(super) async fn execute_query_job(job: QueryJob) -> QueryResultMessage {
     let start = job.started_at;
     let connection_id = job.options.connection_id;
     let query = job.options.query.clone();
@@ -196,7 +256,8 @@ async fn execute_query_job(job: QueryJob) -> QueryResultMessage {
             dba_special_mode,
             ast_debug_sql: output.ast_debug_sql,
             ast_headers: output.ast_headers,
-            affected_rows: Some(output.rows.len()),
+            affected_rows: output.rows_affected.map(|n| n as usize),
+            produced_result_set: output.rows_affected.is_none(),
             column_metadata: output.column_metadata,
         },
         Err(err) => {
@@ -214,6 +275,7 @@ async fn execute_query_job(job: QueryJob) -> QueryResultMessage {
                 ast_debug_sql: None,
                 ast_headers: None,
                 affected_rows: None,
+                produced_result_set: false,
                 column_metadata: None,
             }
         }
@@ -226,6 +288,65 @@ fn describe_execution_error(err: QueryExecutionError) -> String {
     }
 }
 
+fn result_column_names<C: Column>(columns: &[C]) -> Vec<String> {
+    columns.iter().map(|c| c.name().to_string()).collect()
+}
+
+/// Bind the parameters panel's values in placeholder order with the types
+/// they read as (MySQL, SQLite).
+pub(super) fn bind_typed<'q, DB>(
+    mut query: sqlx::query::Query<'q, DB, <DB as sqlx::Database>::Arguments>,
+    params: &[query_tools::ParamValue],
+) -> sqlx::query::Query<'q, DB, <DB as sqlx::Database>::Arguments>
+where
+    DB: sqlx::Database,
+    for<'t> bool: sqlx::Encode<'t, DB> + sqlx::Type<DB>,
+    for<'t> i64: sqlx::Encode<'t, DB> + sqlx::Type<DB>,
+    for<'t> f64: sqlx::Encode<'t, DB> + sqlx::Type<DB>,
+    for<'t> Option<String>: sqlx::Encode<'t, DB> + sqlx::Type<DB>,
+{
+    for value in params {
+        query = match value {
+            query_tools::ParamValue::Null => query.bind(None::<String>),
+            query_tools::ParamValue::Bool(b) => query.bind(*b),
+            query_tools::ParamValue::Int(n) => query.bind(*n),
+            query_tools::ParamValue::Float(f) => query.bind(*f),
+            query_tools::ParamValue::Text(t) => query.bind(Some(t.clone())),
+        };
+    }
+    query
+}
+
+/// Bind the parameters panel's values as text; the statement casts each one
+/// to the type PostgreSQL expects (see `cast_postgres_params`).
+pub(super) fn bind_text<'q>(
+    mut query: sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments>,
+    params: &[query_tools::ParamValue],
+) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+    for value in params {
+        query = query.bind(value.as_text());
+    }
+    query
+}
+
+/// `sql` with each `$n` cast to the type the server infers for it, so values
+/// bound as text still compare as integers, dates, UUIDs and so on.
+pub(super) async fn cast_postgres_params(conn: &mut sqlx::PgConnection, sql: &str) -> String {
+    let types: Vec<String> = match conn.describe(sqlx::AssertSqlSafe(sql).into_sql_str()).await {
+        Ok(described) => match described.parameters() {
+            Some(sqlx::Either::Left(types)) => {
+                types.iter().map(|t| t.name().to_string()).collect()
+            }
+            _ => Vec::new(),
+        },
+        Err(e) => {
+            debug!("[postgres] parameter types not described: {}", e);
+            Vec::new()
+        }
+    };
+    query_tools::cast_numbered_params(sql, &types)
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Per-driver async execution helpers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -382,6 +503,8 @@ async fn execute_mysql_query_job(
         let mut final_headers: Vec<String> = Vec::new();
         let mut final_data: Vec<Vec<String>> = Vec::new();
         let mut final_column_metadata: Option<Vec<models::structs::ColumnMetadata>> = None;
+        let mut rows_affected = None;
+        let mut streamed_data = None;
         let mut execution_success = true;
 
         for (idx, statement) in statements_ref.iter().enumerate() {
@@ -457,15 +580,48 @@ async fn execute_mysql_query_job(
                 continue;
             }
 
-            let query_result = tokio::time::timeout(
-                std::time::Duration::from_secs(60),
-                sqlx::query(sqlx::AssertSqlSafe(trimmed)).fetch_all(&mut conn),
-            )
-            .await;
+            let is_last = idx == statements_ref.len() - 1;
+            let params: &[query_tools::ParamValue] = if is_last { &options.params } else { &[] };
+            let stream = options
+                .stream
+                .as_ref()
+                .filter(|_| is_last && statement_returns_rows(trimmed));
+            let query_result = if let Some(stream) = stream {
+                super::stream::collect_streamed(
+                    bind_typed(sqlx::query(sqlx::AssertSqlSafe(trimmed)), params).fetch(&mut conn),
+                    stream,
+                    std::time::Duration::from_secs(60),
+                    |row| result_column_names(row.columns()),
+                    driver_mysql::convert_mysql_row,
+                )
+                .await
+                .map(|r| {
+                    r.map(|(first_row, cells)| {
+                        streamed_data = Some(cells);
+                        (first_row.into_iter().collect(), None)
+                    })
+                })
+            } else {
+                tokio::time::timeout(std::time::Duration::from_secs(60), async {
+                    if is_last && !statement_returns_rows(trimmed) {
+                        bind_typed(sqlx::query(sqlx::AssertSqlSafe(trimmed)), params)
+                            .execute(&mut conn)
+                            .await
+                            .map(|r| (Vec::new(), Some(r.rows_affected())))
+                    } else {
+                        bind_typed(sqlx::query(sqlx::AssertSqlSafe(trimmed)), params)
+                            .fetch_all(&mut conn)
+                            .await
+                            .map(|rows| (rows, None))
+                    }
+                })
+                .await
+            };
 
             match query_result {
-                Ok(Ok(rows)) => {
-                    if idx == statements_ref.len() - 1 {
+                Ok(Ok((rows, affected))) => {
+                    if is_last {
+                        rows_affected = affected;
                         if !rows.is_empty() {
                             final_headers = rows[0]
                                 .columns()
@@ -652,11 +808,14 @@ async fn execute_mysql_query_job(
                                     table_name,
                                     original_name: Some(col.name().to_string()),
                                     is_primary_key: is_pk,
+                                    pg_kind: None,
                                 });
                             }
                             final_column_metadata = Some(meta_vec);
 
-                            final_data = driver_mysql::convert_mysql_rows_to_table_data(rows);
+                            final_data = streamed_data.take().unwrap_or_else(|| {
+                                driver_mysql::convert_mysql_rows_to_table_data(rows)
+                            });
 
                             if replication_status_mode || master_status_mode {
                                 let version_str = match sqlx::query("SELECT VERSION() AS v")
@@ -789,7 +948,7 @@ async fn execute_mysql_query_job(
                                     }
                                 }
                             }
-                        } else {
+                        } else if affected.is_none() {
                             #[cfg(feature = "query_ast")]
                             if final_headers.is_empty()
                                 && ast_debug_sql.is_some()
@@ -798,6 +957,14 @@ async fn execute_mysql_query_job(
                             {
                                 final_headers = hh;
                             }
+                            // Empty result set: ask the server for its shape
+                            if final_headers.is_empty()
+                                && let Ok(described) = (&mut conn)
+                                    .describe(sqlx::AssertSqlSafe(trimmed).into_sql_str())
+                                    .await
+                            {
+                                final_headers = result_column_names(described.columns());
+                            }
 
                             if final_headers.is_empty()
                                 && trimmed.to_uppercase().starts_with("SELECT")
@@ -880,6 +1047,7 @@ async fn execute_mysql_query_job(
                 ast_debug_sql,
                 ast_headers,
                 column_metadata: final_column_metadata,
+                rows_affected,
             });
         }
     }
@@ -964,93 +1132,197 @@ async fn execute_postgres_query_job(
 
     let mut final_headers = Vec::new();
     let mut final_data = Vec::new();
-
-    for (i, statement) in statements_ref.iter().enumerate() {
-        let trimmed = statement.trim();
-        if trimmed.is_empty() || trimmed.starts_with("--") || trimmed.starts_with("/*") {
-            continue;
+    let mut column_metadata = None;
+    let mut rows_affected = None;
+    let mut streamed_data = None;
+
+    // One session for the whole job so the tab's search_path applies to every statement
+    let mut conn = pg_pool
+        .acquire()
+        .await
+        .map_err(|e| QueryExecutionError::Message(format!("PostgreSQL error: {}", e)))?;
+    let mut search_path_set = false;
+    if let Some(schema) = options.selected_schema.as_deref() {
+        let set_path = format!(
+            "SET search_path TO \"{}\", public",
+            schema.replace('"', "\"\"")
+        );
+        match sqlx::query(sqlx::AssertSqlSafe(set_path)).execute(&mut *conn).await {
+            Ok(_) => search_path_set = true,
+            Err(e) => debug!("[postgres] search_path {} not applied: {}", schema, e),
         }
+    }
 
-        let result = tokio::time::timeout(
-            std::time::Duration::from_secs(15),
-            sqlx::query(sqlx::AssertSqlSafe(trimmed)).fetch_all(pg_pool.as_ref()),
-        )
-        .await;
+    let outcome = async {
+        for (i, statement) in statements_ref.iter().enumerate() {
+            let trimmed = statement.trim();
+            if trimmed.is_empty() || trimmed.starts_with("--") || trimmed.starts_with("/*") {
+                continue;
+            }
 
-        match result {
-            Ok(Ok(rows)) => {
-                if i == statements_ref.len() - 1 {
-                    if !rows.is_empty() {
-                        final_headers = rows[0]
-                            .columns()
-                            .iter()
-                            .map(|c| c.name().to_string())
-                            .collect();
-                        final_data = rows
-                            .into_iter()
-                            .map(|row| {
-                                (0..row.len())
-                                    .map(|idx| match row.try_get::<Option<String>, _>(idx) {
-                                        Ok(Some(v)) => v,
-                                        Ok(None) => "NULL".to_string(),
-                                        Err(_) => {
-                                            if let Ok(val) = row.try_get::<i64, _>(idx) {
-                                                val.to_string()
-                                            } else if let Ok(val) = row.try_get::<f64, _>(idx) {
-                                                val.to_string()
-                                            } else if let Ok(val) = row.try_get::<bool, _>(idx) {
-                                                val.to_string()
-                                            } else {
-                                                "[unsupported]".to_string()
-                                            }
-                                        }
+            let is_last = i == statements_ref.len() - 1;
+            let params: &[query_tools::ParamValue] = if is_last { &options.params } else { &[] };
+            let cast_sql;
+            let trimmed = if params.is_empty() {
+                trimmed
+            } else {
+                cast_sql = cast_postgres_params(&mut conn, trimmed).await;
+                cast_sql.as_str()
+            };
+            let stream = options
+                .stream
+                .as_ref()
+                .filter(|_| is_last && statement_returns_rows(trimmed));
+            let result = if let Some(stream) = stream {
+                super::stream::collect_streamed(
+                    bind_text(sqlx::query(sqlx::AssertSqlSafe(trimmed)), params).fetch(&mut *conn),
+                    stream,
+                    std::time::Duration::from_secs(15),
+                    |row| result_column_names(row.columns()),
+                    |row| {
+                        (0..row.len())
+                            .map(|idx| driver_postgres::pg_cell_to_string(row, idx))
+                            .collect()
+                    },
+                )
+                .await
+                .map(|r| {
+                    r.map(|(first_row, cells)| {
+                        streamed_data = Some(cells);
+                        (first_row.into_iter().collect(), None)
+                    })
+                })
+            } else {
+                tokio::time::timeout(std::time::Duration::from_secs(15), async {
+                    // Statements without a result set run through `execute` to learn the affected count
+                    if is_last && !statement_returns_rows(trimmed) {
+                        bind_text(sqlx::query(sqlx::AssertSqlSafe(trimmed)), params)
+                            .execute(&mut *conn)
+                            .await
+                            .map(|r| (Vec::new(), Some(r.rows_affected())))
+                    } else {
+                        bind_text(sqlx::query(sqlx::AssertSqlSafe(trimmed)), params)
+                            .fetch_all(&mut *conn)
+                            .await
+                            .map(|rows| (rows, None))
+                    }
+                })
+                .await
+            };
+
+            match result {
+                Ok(Ok((rows, affected))) => {
+                    if is_last {
+                        rows_affected = affected;
+                        if !rows.is_empty() {
+                            final_headers = rows[0]
+                                .columns()
+                                .iter()
+                                .map(|c| c.name().to_string())
+                                .collect();
+                            let (_, involved_tables) = infer_column_origins(trimmed);
+                            let single_table = (involved_tables.len() == 1)
+                                .then(|| involved_tables[0].as_str());
+                            column_metadata =
+                                Some(driver_postgres::pg_column_metadata(&rows[0], single_table));
+                            final_data = streamed_data.take().unwrap_or_else(|| {
+                                rows.iter()
+                                    .map(|row| {
+                                        (0..row.len())
+                                            .map(|idx| driver_postgres::pg_cell_to_string(row, idx))
+                                            .collect()
                                     })
                                     .collect()
-                            })
-                            .collect();
-                    } else {
-                        #[cfg(feature = "query_ast")]
-                        if final_headers.is_empty()
-                            && let Some(hh) = inferred_headers_from_ast.clone()
-                            && !hh.is_empty()
-                        {
-                            final_headers = hh;
-                        }
-                        if final_headers.is_empty()
-                            && trimmed.to_uppercase().starts_with("SELECT")
-                        {
-                            let inferred = infer_select_headers(trimmed);
-                            if !inferred.is_empty() {
-                                final_headers = inferred;
+                            });
+                        } else if affected.is_none() {
+                            #[cfg(feature = "query_ast")]
+                            if final_headers.is_empty()
+                                && let Some(hh) = inferred_headers_from_ast.clone()
+                                && !hh.is_empty()
+                            {
+                                final_headers = hh;
                             }
+                            // Empty result set: ask the server for its shape
+                            if final_headers.is_empty()
+                                && let Ok(described) = (&mut *conn)
+                                    .describe(sqlx::AssertSqlSafe(trimmed).into_sql_str())
+                                    .await
+                            {
+                                final_headers = result_column_names(described.columns());
+                            }
+                            if final_headers.is_empty()
+                                && trimmed.to_uppercase().starts_with("SELECT")
+                            {
+                                let inferred = infer_select_headers(trimmed);
+                                if !inferred.is_empty() {
+                                    final_headers = inferred;
+                                }
+                            }
+                            final_data = Vec::new();
                         }
-                        final_data = Vec::new();
                     }
                 }
+                Ok(Err(e)) => {
+                    return Err(QueryExecutionError::Message(format!(
+                        "PostgreSQL error: {}",
+                        e
+                    )));
+                }
+                Err(_) => {
+                    return Err(QueryExecutionError::Message(
+                        "PostgreSQL query timed out".to_string(),
+                    ));
+                }
             }
-            Ok(Err(e)) => {
-                return Err(QueryExecutionError::Message(format!(
-                    "PostgreSQL error: {}",
-                    e
-                )));
-            }
-            Err(_) => {
-                return Err(QueryExecutionError::Message(
-                    "PostgreSQL query timed out".to_string(),
-                ));
-            }
+        }
+        Ok::<(), QueryExecutionError>(())
+    }
+    .await;
+    // The connection goes back to the pool: undo the tab's search_path so
+    // the next job on it starts from the server default
+    if search_path_set {
+        let reset = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            sqlx::query("RESET search_path").execute(&mut *conn),
+        )
+        .await;
+        if !matches!(reset, Ok(Ok(_))) {
+            debug!("[postgres] search_path not reset, dropping the connection");
+            conn.close_on_drop();
         }
     }
+    outcome?;
 
     Ok(QueryJobOutput {
         headers: final_headers,
         rows: final_data,
         ast_debug_sql,
         ast_headers,
-        column_metadata: None,
+        column_metadata,
+        rows_affected,
     })
 }
 
+fn sqlite_row_to_strings(row: &sqlx::sqlite::SqliteRow) -> Vec<String> {
+    (0..row.len())
+        .map(|idx| match row.try_get::<Option<String>, _>(idx) {
+            Ok(Some(v)) => v,
+            Ok(None) => "NULL".to_string(),
+            Err(_) => {
+                if let Ok(val) = row.try_get::<i64, _>(idx) {
+                    val.to_string()
+                } else if let Ok(val) = row.try_get::<f64, _>(idx) {
+                    val.to_string()
+                } else if let Ok(val) = row.try_get::<bool, _>(idx) {
+                    val.to_string()
+                } else {
+                    "[unsupported]".to_string()
+                }
+            }
+        })
+        .collect()
+}
+
 async fn execute_sqlite_query_job(
     options: &QueryExecutionOptions,
     pool: models::enums::DatabasePool,
@@ -1124,6 +1396,8 @@ async fn execute_sqlite_query_job(
 
     let mut final_headers = Vec::new();
     let mut final_data = Vec::new();
+    let mut rows_affected = None;
+    let mut streamed_data = None;
 
     for (i, statement) in statements_ref.iter().enumerate() {
         let trimmed = statement.trim();
@@ -1131,44 +1405,58 @@ async fn execute_sqlite_query_job(
             continue;
         }
 
-        let result = tokio::time::timeout(
-            std::time::Duration::from_secs(10),
-            sqlx::query(sqlx::AssertSqlSafe(trimmed)).fetch_all(sqlite_pool.as_ref()),
-        )
-        .await;
+        let is_last = i == statements_ref.len() - 1;
+        let params: &[query_tools::ParamValue] = if is_last { &options.params } else { &[] };
+        let stream = options
+            .stream
+            .as_ref()
+            .filter(|_| is_last && statement_returns_rows(trimmed));
+        let result = if let Some(stream) = stream {
+            super::stream::collect_streamed(
+                bind_typed(sqlx::query(sqlx::AssertSqlSafe(trimmed)), params).fetch(sqlite_pool.as_ref()),
+                stream,
+                std::time::Duration::from_secs(10),
+                |row| result_column_names(row.columns()),
+                sqlite_row_to_strings,
+            )
+            .await
+            .map(|r| {
+                r.map(|(first_row, cells)| {
+                    streamed_data = Some(cells);
+                    (first_row.into_iter().collect(), None)
+                })
+            })
+        } else {
+            tokio::time::timeout(std::time::Duration::from_secs(10), async {
+                if is_last && !statement_returns_rows(trimmed) {
+                    bind_typed(sqlx::query(sqlx::AssertSqlSafe(trimmed)), params)
+                        .execute(sqlite_pool.as_ref())
+                        .await
+                        .map(|r| (Vec::new(), Some(r.rows_affected())))
+                } else {
+                    bind_typed(sqlx::query(sqlx::AssertSqlSafe(trimmed)), params)
+                        .fetch_all(sqlite_pool.as_ref())
+                        .await
+                        .map(|rows| (rows, None))
+                }
+            })
+            .await
+        };
 
         match result {
-            Ok(Ok(rows)) => {
-                if i == statements_ref.len() - 1 {
+            Ok(Ok((rows, affected))) => {
+                if is_last {
+                    rows_affected = affected;
                     if !rows.is_empty() {
                         final_headers = rows[0]
                             .columns()
                             .iter()
                             .map(|c| c.name().to_string())
                             .collect();
-                        final_data = rows
-                            .into_iter()
-                            .map(|row| {
-                                (0..row.len())
-                                    .map(|idx| match row.try_get::<Option<String>, _>(idx) {
-                                        Ok(Some(v)) => v,
-                                        Ok(None) => "NULL".to_string(),
-                                        Err(_) => {
-                                            if let Ok(val) = row.try_get::<i64, _>(idx) {
-                                                val.to_string()
-                                            } else if let Ok(val) = row.try_get::<f64, _>(idx) {
-                                                val.to_string()
-                                            } else if let Ok(val) = row.try_get::<bool, _>(idx) {
-                                                val.to_string()
-                                            } else {
-                                                "[unsupported]".to_string()
-                                            }
-                                        }
-                                    })
-                                    .collect()
-                            })
-                            .collect();
-                    } else {
+                        final_data = streamed_data
+                            .take()
+                            .unwrap_or_else(|| rows.iter().map(sqlite_row_to_strings).collect());
+                    } else if affected.is_none() {
                         #[cfg(feature = "query_ast")]
                         if final_headers.is_empty()
                             && let Some(hh) = inferred_headers_from_ast.clone()
@@ -1176,6 +1464,14 @@ async fn execute_sqlite_query_job(
                         {
                             final_headers = hh;
                         }
+                        if final_headers.is_empty()
+                            && let Ok(described) = sqlite_pool
+                                .as_ref()
+                                .describe(sqlx::AssertSqlSafe(trimmed).into_sql_str())
+                                .await
+                        {
+                            final_headers = result_column_names(described.columns());
+                        }
                         if final_headers.is_empty()
                             && trimmed.to_uppercase().starts_with("SELECT")
                         {
@@ -1205,6 +1501,7 @@ async fn execute_sqlite_query_job(
         ast_debug_sql,
         ast_headers,
         column_metadata: None,
+        rows_affected,
     })
 }
 
@@ -1281,6 +1578,7 @@ async fn execute_redis_query_job(
                     ast_debug_sql: None,
                     ast_headers: None,
                     column_metadata: None,
+                    rows_affected: None,
                 }),
                 Ok(Ok(None)) => Ok(QueryJobOutput {
                     headers: vec!["Key".to_string(), "Value".to_string()],
@@ -1288,6 +1586,7 @@ async fn execute_redis_query_job(
                     ast_debug_sql: None,
                     ast_headers: None,
                     column_metadata: None,
+                    rows_affected: None,
                 }),
                 _ => Err(QueryExecutionError::Message(
                     "Redis GET timed out or failed".to_string(),
@@ -1315,6 +1614,7 @@ async fn execute_redis_query_job(
                         ast_debug_sql: None,
                         ast_headers: None,
                         column_metadata: None,
+                        rows_affected: None,
                     })
                 }
                 _ => Err(QueryExecutionError::Message(
@@ -1422,6 +1722,7 @@ async fn execute_redis_query_job(
                         ast_debug_sql: None,
                         ast_headers: None,
                         column_metadata: None,
+                        rows_affected: None,
                     })
                 }
                 _ => Err(QueryExecutionError::Message(
@@ -1455,6 +1756,7 @@ async fn execute_redis_query_job(
                         ast_debug_sql: None,
                         ast_headers: None,
                         column_metadata: None,
+                        rows_affected: None,
                     })
                 }
                 _ => Err(QueryExecutionError::Message(
@@ -1495,6 +1797,7 @@ async fn execute_redis_query_job(
                         ast_debug_sql: None,
                         ast_headers: None,
                         column_metadata: None,
+                        rows_affected: None,
                     })
                 }
                 _ => Err(QueryExecutionError::Message(
@@ -1533,13 +1836,44 @@ async fn execute_mssql_query_job(
         query_str = query_str.replace("TOP 10000", "");
     }
 
-    match driver_mssql::execute_query(config.clone(), &query_str).await {
+    if let Some(db) = options.selected_database.as_deref()
+        && !query_str.to_uppercase().starts_with("USE ")
+    {
+        query_str = format!("USE [{}];\n{}", db.replace(']', "]]"), query_str);
+    }
+
+    // A batch ending in a statement without a result set runs through
+    // `execute` to learn the affected count
+    let returns_rows = split_sql_statements(&options.query, false)
+        .last()
+        .is_none_or(|last| statement_returns_rows(last));
+    if !returns_rows {
+        return driver_mssql::execute_statement(config.clone(), &query_str, &options.params)
+            .await
+            .map(|affected| QueryJobOutput {
+                headers: Vec::new(),
+                rows: Vec::new(),
+                ast_debug_sql: None,
+                ast_headers: None,
+                column_metadata: None,
+                rows_affected: Some(affected),
+            })
+            .map_err(|e| QueryExecutionError::Message(format!("Query error: {}", e)));
+    }
+
+    let result = if options.params.is_empty() {
+        driver_mssql::execute_query(config.clone(), &query_str).await
+    } else {
+        driver_mssql::execute_bound_query(config.clone(), &query_str, &options.params).await
+    };
+    match result {
         Ok((headers, rows)) => Ok(QueryJobOutput {
             headers,
             rows,
             ast_debug_sql: None,
             ast_headers: None,
             column_metadata: None,
+            rows_affected: None,
         }),
         Err(e) => Err(QueryExecutionError::Message(format!("Query error: {}", e))),
     }
@@ -1559,6 +1893,7 @@ async fn execute_mongodb_query_job(
             ast_debug_sql: None,
             ast_headers: None,
             column_metadata: None,
+            rows_affected: None,
         }),
         _ => Err(QueryExecutionError::Message(
             "Invalid pool type for MongoDB".to_string(),
@@ -1568,6 +1903,11 @@ async fn execute_mongodb_query_job(
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Synchronous execution entry points
+//
+// These block the calling (UI) thread until the query finishes. Editor runs,
+// pagination and sidebar views go through `spawn_query_job` instead; keep
+// these for short metadata/DDL statements whose result the caller needs
+// immediately.
 // ─────────────────────────────────────────────────────────────────────────────
 
 pub(crate) fn execute_query_with_connection(
@@ -2124,11 +2464,7 @@ pub(crate) fn execute_table_query_sync(
                                         if !rows.is_empty() {
                                             final_headers = rows[0].columns().iter().map(|c| c.name().to_string()).collect();
                                             final_data = rows.iter().map(|row| {
-                                                (0..row.len()).map(|j| match row.try_get::<Option<String>, _>(j) {
-                                                    Ok(Some(v)) => v,
-                                                    Ok(None) => "NULL".to_string(),
-                                                    Err(_) => "Error".to_string(),
-                                                }).collect()
+                                                (0..row.len()).map(|j| driver_postgres::pg_cell_to_string(row, j)).collect()
                                             }).collect();
                                         } else {
                                             #[cfg(feature = "query_ast")]
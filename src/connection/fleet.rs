@@ -0,0 +1,519 @@
+//! Running one statement on several connections at once (e.g. a health check
+//! across replicas). Each connection runs through the regular query job on
+//! the runtime, at most [`FLEET_CONCURRENCY`] at a time; the result sets are
+//! merged into one grid with a leading source column, and connections that
+//! failed are reported separately in the message panel.
+
+use std::collections::BTreeSet;
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use futures_util::stream::StreamExt;
+
+use super::execute::execute_query_job;
+use super::types::{QueryExecutionOptions, QueryJob, QueryResultMessage};
+use crate::{data_table, editor, models, window_egui};
+
+/// Connections queried at the same time.
+pub(crate) const FLEET_CONCURRENCY: usize = 4;
+/// Header of the column naming the connection each row came from.
+pub(crate) const SOURCE_COLUMN: &str = "source_connection";
+
+pub struct FleetRunState {
+    pub query: String,
+    pub selected: BTreeSet<i64>,
+    pub running: Option<FleetProgress>,
+    /// Warning for a destructive statement (see [`editor::destructive_statement`])
+    /// waiting for one confirmation that covers every selected connection.
+    pub pending_confirm: Option<String>,
+}
+
+pub struct FleetProgress {
+    receiver: Receiver<QueryResultMessage>,
+    pub total: usize,
+    pub done: Vec<QueryResultMessage>,
+}
+
+/// One grid for every connection's result, plus what did not fit in it.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct FleetMerge {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// `(connection, error)` for connections that failed.
+    pub errors: Vec<(String, String)>,
+}
+
+fn runs_sql(connection_type: &models::enums::DatabaseType) -> bool {
+    !matches!(
+        connection_type,
+        models::enums::DatabaseType::Redis
+            | models::enums::DatabaseType::MongoDB
+            | models::enums::DatabaseType::ApiHttp
+    )
+}
+
+/// Merge per-connection outcomes (`(connection name, message)`). Result sets
+/// are lined up by column name, columns missing on a connection read NULL.
+/// When no connection returned rows, each gets a row with its affected count.
+pub(crate) fn merge_fleet_results(outcomes: &[(String, QueryResultMessage)]) -> FleetMerge {
+    let mut merge = FleetMerge::default();
+    let with_rows = outcomes
+        .iter()
+        .any(|(_, m)| m.success && m.produced_result_set);
+    merge.headers.push(SOURCE_COLUMN.to_string());
+    if !with_rows {
+        merge.headers.push("rows_affected".to_string());
+    }
+    for (name, message) in outcomes {
+        if !message.success {
+            let error = message
+                .error
+                .clone()
+                .unwrap_or_else(|| "Unknown error".to_string());
+            merge.errors.push((name.clone(), error));
+            continue;
+        }
+        if !with_rows {
+            let affected = message.affected_rows.unwrap_or(0);
+            merge.rows.push(vec![name.clone(), affected.to_string()]);
+            continue;
+        }
+        if !message.produced_result_set {
+            continue;
+        }
+        let positions: Vec<usize> = message
+            .headers
+            .iter()
+            .map(|h| match merge.headers[1..].iter().position(|m| m == h) {
+                Some(p) => p + 1,
+                None => {
+                    merge.headers.push(h.clone());
+                    merge.headers.len() - 1
+                }
+            })
+            .collect();
+        for row in &message.rows {
+            let mut merged = vec!["NULL".to_string(); merge.headers.len()];
+            merged[0] = name.clone();
+            for (value, &p) in row.iter().zip(&positions) {
+                merged[p] = value.clone();
+            }
+            merge.rows.push(merged);
+        }
+    }
+    // Rows merged before a later connection added columns are padded
+    let width = merge.headers.len();
+    for row in &mut merge.rows {
+        row.resize(width, "NULL".to_string());
+    }
+    merge
+}
+
+fn failed_message(connection_id: i64, query: &str, error: String) -> QueryResultMessage {
+    QueryResultMessage {
+        job_id: 0,
+        connection_id,
+        success: false,
+        headers: vec!["Error".to_string()],
+        rows: vec![vec![error.clone()]],
+        error: Some(error),
+        duration: Duration::ZERO,
+        query: query.to_string(),
+        dba_special_mode: None,
+        ast_debug_sql: None,
+        ast_headers: None,
+        affected_rows: None,
+        produced_result_set: false,
+        column_metadata: None,
+    }
+}
+
+/// Open the connection picker for the selected text, or the statement at the
+/// cursor. The active tab's connection starts out selected.
+pub(crate) fn open_fleet_run(tabular: &mut window_egui::Tabular) {
+    let query = if !tabular.selected_text.trim().is_empty() {
+        tabular.selected_text.trim().to_string()
+    } else {
+        editor::extract_query_from_cursor(tabular)
+    };
+    if query.trim().is_empty() {
+        tabular
+            .toasts
+            .info("Write or select a statement to run first");
+        return;
+    }
+    let selected = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.connection_id)
+        .into_iter()
+        .collect();
+    tabular.fleet_run = Some(FleetRunState {
+        query,
+        selected,
+        running: None,
+        pending_confirm: None,
+    });
+}
+
+/// Run the statement on every selected connection in the background. Missing
+/// pools are created by the task and handed back through the shared pool map.
+/// A destructive statement first asks for confirmation, once for the fleet.
+fn start_fleet_run(tabular: &mut window_egui::Tabular, confirmed: bool) {
+    let Some(runtime) = tabular.runtime.clone() else {
+        return;
+    };
+    let include_filtered_dml = tabular.destructive_confirm_over_rows > 0;
+    let Some(state) = tabular.fleet_run.as_mut() else {
+        return;
+    };
+    state.pending_confirm = None;
+    if !confirmed
+        && let Some((kind, statements)) =
+            editor::destructive_statement(&state.query, include_filtered_dml)
+    {
+        state.pending_confirm = Some(match kind {
+            "DROP" => "This DROP permanently removes the object".to_string(),
+            "TRUNCATE" => "This TRUNCATE empties the whole table".to_string(),
+            kind if statements
+                .iter()
+                .any(|s| editor::is_unsafe_dml_query(s).is_some()) =>
+            {
+                format!("This {} has no WHERE clause and touches every row", kind)
+            }
+            kind => format!("This {} changes data", kind),
+        });
+        return;
+    }
+    let state = &*state;
+    let query = state.query.clone();
+    let mut work = Vec::new();
+    for connection in tabular
+        .connections
+        .iter()
+        .filter(|c| c.id.is_some_and(|id| state.selected.contains(&id)))
+    {
+        let connection_id = connection.id.unwrap_or_default();
+        let pool = tabular
+            .connection_pools
+            .get(&connection_id)
+            .cloned()
+            .or_else(|| {
+                tabular
+                    .shared_connection_pools
+                    .lock()
+                    .ok()
+                    .and_then(|shared| shared.get(&connection_id).cloned())
+            });
+        let options = QueryExecutionOptions {
+            connection_id,
+            connection: connection.clone(),
+            query: query.clone(),
+            selected_database: super::default_tab_database(connection),
+            selected_schema: super::default_tab_schema(connection),
+            use_server_pagination: false,
+            current_page: 0,
+            page_size: 0,
+            base_query: None,
+            dba_special_mode: None,
+            save_to_history: false,
+            ast_enabled: false,
+            stream: None,
+            params: Vec::new(),
+        };
+        work.push((options, pool));
+    }
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let total = work.len();
+    let shared_pools = tabular.shared_connection_pools.clone();
+    runtime.spawn(async move {
+        futures_util::stream::iter(work)
+            .map(|(options, pool)| {
+                let sender = sender.clone();
+                let shared_pools = shared_pools.clone();
+                async move {
+                    let connection_id = options.connection_id;
+                    let pool = match pool {
+                        Some(pool) => Some(pool),
+                        None => {
+                            let created =
+                                super::pool::create_connection_pool_for_config(&options.connection)
+                                    .await;
+                            if let (Some(pool), Ok(mut shared)) = (&created, shared_pools.lock()) {
+                                shared.insert(connection_id, pool.clone());
+                            }
+                            created
+                        }
+                    };
+                    let message = match pool {
+                        Some(connection_pool) => {
+                            execute_query_job(QueryJob {
+                                job_id: 0,
+                                options,
+                                connection_pool,
+                                started_at: Instant::now(),
+                            })
+                            .await
+                        }
+                        None => failed_message(
+                            connection_id,
+                            &options.query,
+                            "Could not connect".to_string(),
+                        ),
+                    };
+                    let _ = sender.send(message);
+                }
+            })
+            .buffer_unordered(FLEET_CONCURRENCY)
+            .for_each(|_| async {})
+            .await;
+    });
+
+    if let Some(state) = tabular.fleet_run.as_mut() {
+        state.running = Some(FleetProgress {
+            receiver,
+            total,
+            done: Vec::new(),
+        });
+    }
+}
+
+/// Collect finished connections; true once every connection has reported.
+fn poll_fleet_run(progress: &mut FleetProgress) -> bool {
+    loop {
+        match progress.receiver.try_recv() {
+            Ok(message) => progress.done.push(message),
+            Err(TryRecvError::Empty) => return progress.done.len() >= progress.total,
+            Err(TryRecvError::Disconnected) => return true,
+        }
+    }
+}
+
+/// Show the merged result in a new tab, with failures in the message panel.
+fn show_fleet_results(
+    tabular: &mut window_egui::Tabular,
+    query: String,
+    done: Vec<QueryResultMessage>,
+) {
+    let name_of = |id: i64| {
+        tabular
+            .get_connection_name(id)
+            .unwrap_or_else(|| format!("Connection {}", id))
+    };
+    let mut outcomes: Vec<(String, QueryResultMessage)> = done
+        .into_iter()
+        .map(|m| (name_of(m.connection_id), m))
+        .collect();
+    outcomes.sort_by(|a, b| a.0.cmp(&b.0));
+    let total = outcomes.len();
+    let merge = merge_fleet_results(&outcomes);
+
+    editor::create_new_tab(tabular, format!("Fleet: {} connections", total), query);
+    tabular.is_table_browse_mode = false;
+    tabular.current_base_query.clear();
+    tabular.current_table_headers = merge.headers;
+    tabular.current_column_metadata = None;
+    data_table::update_pagination_data(tabular, merge.rows);
+    tabular.current_table_name = format!(
+        "Results from {} of {} connections ({} rows)",
+        total - merge.errors.len(),
+        total,
+        tabular.total_rows
+    );
+
+    let mut message = format!(
+        "Ran on {} connection(s): {} succeeded, {} failed",
+        total,
+        total - merge.errors.len(),
+        merge.errors.len()
+    );
+    for (name, error) in &merge.errors {
+        message.push_str(&format!("\n{}: {}", name, error));
+    }
+    tabular.query_message = message;
+    tabular.query_message_is_error = !merge.errors.is_empty();
+    tabular.show_message_panel = true;
+    tabular.message_shown_at = Some(Instant::now());
+    tabular.table_bottom_view = models::structs::TableBottomView::Data;
+    data_table::initialize_column_widths(tabular);
+
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.has_executed_query = true;
+        tab.result_headers = tabular.current_table_headers.clone();
+        tab.result_rows = tabular.current_table_data.clone();
+        tab.result_all_rows = tabular.all_table_data.clone();
+        tab.result_table_name = tabular.current_table_name.clone();
+        tab.total_rows = tabular.total_rows;
+        tab.current_page = tabular.current_page;
+        tab.page_size = tabular.page_size;
+        tab.base_query.clear();
+        tab.query_message = tabular.query_message.clone();
+        tab.query_message_is_error = tabular.query_message_is_error;
+    }
+}
+
+pub(crate) fn render_fleet_run_dialog(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(state) = tabular.fleet_run.as_mut() else {
+        return;
+    };
+    if let Some(progress) = state.running.as_mut() {
+        if poll_fleet_run(progress) {
+            let query = state.query.clone();
+            let done = std::mem::take(&mut progress.done);
+            tabular.fleet_run = None;
+            show_fleet_results(tabular, query, done);
+            return;
+        }
+        ctx.request_repaint_after(Duration::from_millis(100));
+    }
+
+    let connections: Vec<(i64, String, &'static str)> = tabular
+        .connections
+        .iter()
+        .filter(|c| runs_sql(&c.connection_type))
+        .filter_map(|c| {
+            c.id.map(|id| (id, c.name.clone(), c.connection_type.icon()))
+        })
+        .collect();
+    let Some(state) = tabular.fleet_run.as_mut() else {
+        return;
+    };
+    let mut open = true;
+    let mut run = false;
+    let mut confirmed = false;
+    egui::Window::new("Run on Multiple Connections")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(460.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .open(&mut open)
+        .show(ctx, |ui| {
+            let preview: String = state.query.lines().take(6).collect::<Vec<_>>().join("\n");
+            ui.label(egui::RichText::new(preview).monospace().weak());
+            ui.separator();
+            if let Some(progress) = &state.running {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(format!(
+                        "{} of {} connections finished…",
+                        progress.done.len(),
+                        progress.total
+                    ));
+                });
+                return;
+            }
+            if let Some(warning) = &state.pending_confirm {
+                ui.colored_label(
+                    egui::Color32::from_rgb(220, 80, 60),
+                    format!("⚠ {} on each of {} connections.", warning, state.selected.len()),
+                );
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(format!("Run on {} anyway", state.selected.len()))
+                        .clicked()
+                    {
+                        run = true;
+                        confirmed = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        state.pending_confirm = None;
+                    }
+                });
+                return;
+            }
+            ui.horizontal(|ui| {
+                if ui.small_button("All").clicked() {
+                    state.selected = connections.iter().map(|(id, _, _)| *id).collect();
+                }
+                if ui.small_button("None").clicked() {
+                    state.selected.clear();
+                }
+                ui.label(egui::RichText::new(format!("{} at a time", FLEET_CONCURRENCY)).weak());
+            });
+            egui::ScrollArea::vertical()
+                .max_height(260.0)
+                .show(ui, |ui| {
+                    for (id, name, icon) in &connections {
+                        let mut checked = state.selected.contains(id);
+                        if ui
+                            .checkbox(&mut checked, format!("{} {}", icon, name))
+                            .changed()
+                        {
+                            if checked {
+                                state.selected.insert(*id);
+                            } else {
+                                state.selected.remove(id);
+                            }
+                        }
+                    }
+                });
+            ui.separator();
+            let count = state.selected.len();
+            if ui
+                .add_enabled(count > 0, egui::Button::new(format!("▶ Run on {}", count)))
+                .clicked()
+            {
+                run = true;
+            }
+        });
+    if !open {
+        // Closing while running abandons the remaining results
+        tabular.fleet_run = None;
+    } else if run {
+        start_fleet_run(tabular, confirmed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(headers: &[&str], rows: &[&[&str]]) -> QueryResultMessage {
+        QueryResultMessage {
+            success: true,
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: rows
+                .iter()
+                .map(|r| r.iter().map(|v| v.to_string()).collect())
+                .collect(),
+            error: None,
+            produced_result_set: true,
+            ..failed_message(1, "", String::new())
+        }
+    }
+
+    #[test]
+    fn fleet_results_line_up_by_column_and_keep_errors_apart() {
+        let outcomes = vec![
+            ("a".to_string(), result(&["id", "lag"], &[&["1", "0"]])),
+            (
+                "b".to_string(),
+                failed_message(2, "", "timeout".to_string()),
+            ),
+            (
+                "c".to_string(),
+                result(&["lag", "role"], &[&["5", "replica"]]),
+            ),
+        ];
+        let merge = merge_fleet_results(&outcomes);
+        assert_eq!(merge.headers, vec![SOURCE_COLUMN, "id", "lag", "role"]);
+        assert_eq!(
+            merge.rows,
+            vec![
+                vec!["a", "1", "0", "NULL"],
+                vec!["c", "NULL", "5", "replica"]
+            ]
+        );
+        assert_eq!(merge.errors, vec![("b".to_string(), "timeout".to_string())]);
+
+        let mut update = failed_message(1, "", String::new());
+        update.success = true;
+        update.error = None;
+        update.affected_rows = Some(3);
+        let merge = merge_fleet_results(&[("a".to_string(), update)]);
+        assert_eq!(merge.headers, vec![SOURCE_COLUMN, "rows_affected"]);
+        assert_eq!(merge.rows, vec![vec!["a", "3"]]);
+    }
+}
@@ -0,0 +1,161 @@
+// connection/import.rs
+// Background CSV import: the file is read a chunk at a time and each chunk is
+// inserted in its own transaction through the driver, with progress, cancel,
+// and per-row error handling shared with the import dialog.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use log::debug;
+
+use crate::csv_import::{CsvChunks, CsvRecord, InsertPlan};
+use crate::models::structs::{CsvImportProgress, CsvImportStatus, CsvRowErrorPolicy};
+use crate::window_egui::Tabular;
+use super::session::execute_in_transaction;
+use crate::models;
+
+/// Records per INSERT and per transaction.
+pub(crate) const CSV_IMPORT_BATCH_ROWS: usize = 500;
+/// Row errors kept for the dialog; later ones are only counted.
+const MAX_REPORTED_ROW_ERRORS: usize = 200;
+
+/// Everything a background import needs, captured from the dialog.
+pub(crate) struct CsvImportJob {
+    pub path: PathBuf,
+    pub delimiter: char,
+    pub has_header_row: bool,
+    /// Run first when importing into a new table.
+    pub create_table_sql: Option<String>,
+    pub plan: InsertPlan,
+    pub row_error_policy: CsvRowErrorPolicy,
+}
+
+fn update_progress(progress: &Mutex<CsvImportProgress>, update: impl FnOnce(&mut CsvImportProgress)) {
+    if let Ok(mut progress) = progress.lock() {
+        update(&mut progress);
+    }
+}
+
+/// Insert one chunk as a single statement. If the database rejects it, the
+/// rows are retried one statement each (still in one transaction) to find
+/// the offending row, which is then skipped or ends the import.
+async fn insert_chunk(
+    pool: &models::enums::DatabasePool,
+    job: &CsvImportJob,
+    mut records: Vec<CsvRecord>,
+    progress: &Mutex<CsvImportProgress>,
+) -> Result<usize, String> {
+    if execute_in_transaction(pool, None, &[job.plan.batch_insert(&records)])
+        .await
+        .is_ok()
+    {
+        return Ok(records.len());
+    }
+    while !records.is_empty() {
+        let statements: Vec<String> = records.iter().map(|r| job.plan.row_insert(r)).collect();
+        let (index, error) = match execute_in_transaction(pool, None, &statements).await {
+            Ok(()) => return Ok(records.len()),
+            Err(failure) => failure,
+        };
+        let Some(record) = records.get(index) else {
+            return Err(error);
+        };
+        let line = record.line;
+        if job.row_error_policy == CsvRowErrorPolicy::Abort {
+            return Err(format!("Line {}: {}", line, error));
+        }
+        debug!("CSV import skipped line {}: {}", line, error);
+        update_progress(progress, |p| {
+            p.rows_skipped += 1;
+            if p.row_errors.len() < MAX_REPORTED_ROW_ERRORS {
+                p.row_errors.push((line, error));
+            }
+        });
+        records.remove(index);
+    }
+    Ok(0)
+}
+
+async fn run_csv_import(
+    pool: models::enums::DatabasePool,
+    job: CsvImportJob,
+    progress: &Mutex<CsvImportProgress>,
+    cancel: &AtomicBool,
+) -> CsvImportStatus {
+    if let Some(sql) = &job.create_table_sql
+        && let Err((_, e)) = execute_in_transaction(&pool, None, std::slice::from_ref(sql)).await
+    {
+        return CsvImportStatus::Failed(format!("Creating the table failed: {}", e));
+    }
+    let mut chunks = match CsvChunks::open(&job.path, job.delimiter, job.has_header_row) {
+        Ok(chunks) => chunks,
+        Err(e) => return CsvImportStatus::Failed(e),
+    };
+    let mut imported = 0;
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return CsvImportStatus::Cancelled(imported);
+        }
+        let records = match chunks.next_chunk(CSV_IMPORT_BATCH_ROWS) {
+            Ok(records) if records.is_empty() => break,
+            Ok(records) => records,
+            Err(e) => return CsvImportStatus::Failed(format!("{} ({} rows imported)", e, imported)),
+        };
+        match insert_chunk(&pool, &job, records, progress).await {
+            Ok(count) => imported += count,
+            Err(e) => {
+                return CsvImportStatus::Failed(format!(
+                    "{} ({} rows imported before it)",
+                    e, imported
+                ));
+            }
+        }
+        let fraction = chunks.fraction_read();
+        update_progress(progress, |p| {
+            p.rows_imported = imported;
+            p.fraction = fraction;
+        });
+    }
+    CsvImportStatus::Done(imported)
+}
+
+/// Start importing on the runtime. The dialog reads the returned progress
+/// each frame and stops the import through the cancel flag.
+pub(crate) fn spawn_csv_import(
+    tabular: &mut Tabular,
+    connection_id: i64,
+    job: CsvImportJob,
+    cancel: Arc<AtomicBool>,
+) -> Result<Arc<Mutex<CsvImportProgress>>, String> {
+    let connection = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(connection_id))
+        .cloned()
+        .ok_or_else(|| "Connection not found".to_string())?;
+    // An open pool is reused; otherwise the task connects, off the UI thread
+    let open_pool = super::session::session_pool(tabular, connection_id);
+    let shared_pools = tabular.shared_connection_pools.clone();
+    let runtime = tabular.get_runtime();
+    let progress = Arc::new(Mutex::new(CsvImportProgress::default()));
+    let shared = progress.clone();
+    runtime.spawn(async move {
+        let pool = match open_pool {
+            Some(pool) => Some(pool),
+            None => {
+                let pool = super::pool::create_connection_pool_for_config(&connection).await;
+                if let (Some(pool), Ok(mut pools)) = (&pool, shared_pools.lock()) {
+                    pools.insert(connection_id, pool.clone());
+                }
+                pool
+            }
+        };
+        let status = match pool {
+            Some(pool) => run_csv_import(pool, job, &shared, &cancel).await,
+            None => CsvImportStatus::Failed("Could not connect to the database".to_string()),
+        };
+        update_progress(&shared, |p| p.finished = Some(status));
+    });
+    Ok(progress)
+}
@@ -0,0 +1,211 @@
+//! Terminating server sessions from the DBA "Processes" / "Blocked Query" views.
+//!
+//! The grid only knows header names, so [`session_columns`] maps a result set
+//! back to the column holding the server session id (and, when present, the
+//! statement that session is running) for each supported engine.
+
+use crate::models;
+use crate::window_egui::Tabular;
+
+/// Column positions of a session listing inside the current result grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SessionColumns {
+    pub id: usize,
+    pub query: Option<usize>,
+}
+
+/// `(session id column, query column)` candidates per engine, most useful first.
+/// In blocking views the blocker is what a DBA wants to kill.
+fn candidates(db_type: &models::enums::DatabaseType) -> &'static [(&'static str, &'static str)] {
+    use models::enums::DatabaseType;
+    match db_type {
+        // SHOW FULL PROCESSLIST -> Id/Info, information_schema.PROCESSLIST -> ID/INFO
+        DatabaseType::MySQL => &[("id", "info")],
+        DatabaseType::PostgreSQL => &[
+            ("blocking_pid", "blocking_query"),
+            ("pid", "query"),
+            ("blocked_pid", "blocked_query"),
+        ],
+        DatabaseType::MsSQL => &[
+            ("blocking_session_id", "blocking_query"),
+            ("session_id", "query"),
+            ("spid", "cmd"),
+            ("blocked_session_id", "blocked_query"),
+        ],
+        _ => &[],
+    }
+}
+
+/// `query` is the built-in "Processes" or "Blocked Query" view of `db_type`,
+/// the only result sets whose rows are sessions that may be killed.
+pub(crate) fn is_session_view_query(db_type: &models::enums::DatabaseType, query: &str) -> bool {
+    use models::enums::NodeType;
+    let query = query.trim();
+    crate::sidebar_database::get_default_dba_views(db_type)
+        .iter()
+        .any(|(_, node_type, view_query)| {
+            matches!(node_type, NodeType::ProcessesFolder | NodeType::BlockedQueriesFolder)
+                && view_query.trim() == query
+        })
+}
+
+/// Locate the session id column in `headers`, or `None` when the result set
+/// is not a session listing for `db_type`.
+pub(crate) fn session_columns(
+    db_type: &models::enums::DatabaseType,
+    headers: &[String],
+) -> Option<SessionColumns> {
+    let find = |name: &str| headers.iter().position(|h| h.eq_ignore_ascii_case(name));
+    candidates(db_type).iter().find_map(|(id_col, query_col)| {
+        find(id_col).map(|id| SessionColumns {
+            id,
+            query: find(query_col),
+        })
+    })
+}
+
+/// Statement that terminates session `id`. The id must be a plain integer so a
+/// crafted cell value can never be spliced into the SQL.
+pub(crate) fn kill_session_statement(
+    db_type: &models::enums::DatabaseType,
+    id: &str,
+) -> Option<String> {
+    use models::enums::DatabaseType;
+    let id: i64 = id.trim().parse().ok().filter(|v| *v > 0)?;
+    match db_type {
+        DatabaseType::MySQL | DatabaseType::MsSQL => Some(format!("KILL {}", id)),
+        DatabaseType::PostgreSQL => Some(format!("SELECT pg_terminate_backend({})", id)),
+        _ => None,
+    }
+}
+
+const PERMISSION_PATTERNS: &[&str] = &[
+    "access denied",                   // MySQL 1227
+    "not owner of thread",             // MySQL 1095
+    "permission denied",               // PostgreSQL 42501
+    "must be a superuser",             // PostgreSQL, superuser backends
+    "must be a member of the role",    // PostgreSQL 16+
+    "does not have permission",        // MsSQL 6102
+];
+
+/// Turn a driver error into a message that says plainly when the account lacks
+/// the privilege to kill other sessions.
+pub(crate) fn describe_kill_error(
+    db_type: &models::enums::DatabaseType,
+    id: &str,
+    message: &str,
+) -> String {
+    use models::enums::DatabaseType;
+    let lower = message.to_lowercase();
+    if !PERMISSION_PATTERNS.iter().any(|p| lower.contains(p)) {
+        return format!("Failed to kill session {}: {}", id, message);
+    }
+    let needed = match db_type {
+        DatabaseType::MySQL => "CONNECTION_ADMIN (or SUPER/PROCESS)",
+        DatabaseType::PostgreSQL => "pg_signal_backend membership or superuser",
+        DatabaseType::MsSQL => "ALTER ANY CONNECTION",
+        _ => "administrative privileges",
+    };
+    format!(
+        "Permission denied: killing session {} requires {}.\n{}",
+        id, needed, message
+    )
+}
+
+/// Run the kill statement for `id` on the connection's pool.
+pub(crate) fn kill_session(tabular: &mut Tabular, connection_id: i64, id: &str) -> Result<(), String> {
+    let db_type = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(connection_id))
+        .map(|c| c.connection_type.clone())
+        .ok_or_else(|| "Connection not found".to_string())?;
+    let statement = kill_session_statement(&db_type, id)
+        .ok_or_else(|| format!("'{}' is not a valid session id", id))?;
+
+    let rt = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+    let result = rt.block_on(async {
+        let pool = super::get_or_create_connection_pool(tabular, connection_id)
+            .await
+            .ok_or_else(|| "Connection pool is not ready yet, try again".to_string())?;
+        match pool {
+            models::enums::DatabasePool::MySQL(pool) => sqlx::query(sqlx::AssertSqlSafe(statement.as_str()))
+                .execute(pool.as_ref())
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            models::enums::DatabasePool::PostgreSQL(pool) => {
+                let terminated: bool =
+                    sqlx::query_scalar(sqlx::AssertSqlSafe(statement.as_str()))
+                        .fetch_one(pool.as_ref())
+                        .await
+                        .map_err(|e| e.to_string())?;
+                if terminated {
+                    Ok(())
+                } else {
+                    Err(format!("session {} no longer exists", id))
+                }
+            }
+            models::enums::DatabasePool::MsSQL(pool) => {
+                let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+                let client = conn
+                    .client_mut()
+                    .ok_or_else(|| "MsSQL connection unavailable".to_string())?;
+                client
+                    .execute(&statement, &[])
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+            _ => Err("Killing sessions is not supported for this connection".to_string()),
+        }
+    });
+    result.map_err(|e| describe_kill_error(&db_type, id, &e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::enums::DatabaseType;
+
+    fn headers(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn finds_session_columns_per_engine() {
+        let mysql = headers(&["Id", "User", "Host", "db", "Command", "Time", "State", "Info"]);
+        assert_eq!(
+            session_columns(&DatabaseType::MySQL, &mysql),
+            Some(SessionColumns { id: 0, query: Some(7) })
+        );
+        let pg_blocked = headers(&["blocked_pid", "blocked_query", "blocking_pid", "blocking_query"]);
+        assert_eq!(
+            session_columns(&DatabaseType::PostgreSQL, &pg_blocked),
+            Some(SessionColumns { id: 2, query: Some(3) })
+        );
+        let mssql = headers(&["session_id", "login_name", "status"]);
+        assert_eq!(
+            session_columns(&DatabaseType::MsSQL, &mssql),
+            Some(SessionColumns { id: 0, query: None })
+        );
+        assert_eq!(session_columns(&DatabaseType::SQLite, &mysql), None);
+        assert!(is_session_view_query(&DatabaseType::MySQL, " SHOW FULL PROCESSLIST;\n"));
+        assert!(!is_session_view_query(&DatabaseType::MySQL, "SELECT id FROM users"));
+        assert!(!is_session_view_query(&DatabaseType::MySQL, "SHOW GLOBAL STATUS;"));
+        assert_eq!(session_columns(&DatabaseType::PostgreSQL, &headers(&["name", "setting"])), None);
+    }
+
+    #[test]
+    fn kill_statement_rejects_non_numeric_ids() {
+        assert_eq!(
+            kill_session_statement(&DatabaseType::PostgreSQL, " 4242 ").as_deref(),
+            Some("SELECT pg_terminate_backend(4242)")
+        );
+        assert_eq!(kill_session_statement(&DatabaseType::MySQL, "17").as_deref(), Some("KILL 17"));
+        assert_eq!(kill_session_statement(&DatabaseType::MsSQL, "1; DROP TABLE t"), None);
+        assert_eq!(kill_session_statement(&DatabaseType::MySQL, "NULL"), None);
+        assert!(describe_kill_error(&DatabaseType::MySQL, "9", "1095 (HY000): You are not owner of thread 9")
+            .starts_with("Permission denied"));
+    }
+}
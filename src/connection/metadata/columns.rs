@@ -135,15 +135,21 @@ pub(crate) fn fetch_columns_from_database(
             models::enums::DatabaseType::SQLite => {
                 let connection_string = format!("sqlite:{}", connection_clone.host);
 
-                match sqlx::sqlite::SqlitePoolOptions::new()
+                match crate::driver_sqlite::with_attachments(
+                    sqlx::sqlite::SqlitePoolOptions::new(),
+                    &connection_clone,
+                )
                     .max_connections(1)
                     .acquire_timeout(std::time::Duration::from_secs(10))
                     .connect(&connection_string)
                     .await
                 {
                     Ok(pool) => {
-                        let escaped = table_name.replace("'", "''");
-                        let query = format!("PRAGMA table_info('{}')", escaped);
+                        let query = crate::driver_sqlite::schema_pragma(
+                            Some(&database_name),
+                            "table_info",
+                            &table_name,
+                        );
                         match sqlx::query(sqlx::AssertSqlSafe(query.as_str())).fetch_all(&pool).await {
                             Ok(rows) => {
                                 use sqlx::Row;
@@ -283,11 +289,12 @@ pub(crate) fn fetch_columns_from_database(
                 let port: u16 = connection_clone.port.parse().unwrap_or(1433);
                 let user = connection_clone.username.clone();
                 let pass = connection_clone.password.clone();
+                let tls = crate::driver_mssql::MssqlTls::from_connection(&connection_clone);
                 let db = database_name.clone();
                 let table = table_name.clone();
                 let rt_res = async move {
                     let mut client =
-                        crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db))
+                        crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db), tls)
                             .await?;
 
                     let parse_qualified = |name: &str| -> (Option<String>, String) {
@@ -417,7 +417,7 @@ pub async fn fetch_databases_background_task(
     debug!("Background fetch databases for connection {}", connection_id);
 
     // 1. Get connection config from cache
-    let connection_result = sqlx::query("SELECT * FROM connection WHERE id = ?")
+    let connection_result = sqlx::query("SELECT * FROM connections WHERE id = ?")
         .bind(connection_id)
         .fetch_optional(cache_pool)
         .await;
@@ -454,8 +454,33 @@ pub async fn fetch_databases_background_task(
             let ssh_accept_unknown_host_keys = row
                 .try_get::<i64, _>("ssh_accept_unknown_host_keys")
                 .unwrap_or(0);
+            let mssql_encrypt = row.try_get::<i64, _>("mssql_encrypt").unwrap_or(1);
+            let mssql_trust_server_certificate = row
+                .try_get::<i64, _>("mssql_trust_server_certificate")
+                .unwrap_or(1);
+            let use_credential_file = row.try_get::<i64, _>("use_credential_file").unwrap_or(0);
+            let ssh_key_passphrase = row
+                .try_get::<String, _>("ssh_key_passphrase")
+                .unwrap_or_default();
+            let (proxy, _) = crate::proxy_tunnel::proxy_from_storage(
+                &row.try_get::<String, _>("proxy").unwrap_or_default(),
+                &crate::secrets::connection_secret_name(id, "proxy_password"),
+                false,
+            );
+
+            // Credentials live in the secret store; the row keeps sentinels
+            let secret = |field: &str, stored: &str| {
+                crate::secrets::resolve_readonly(
+                    &crate::secrets::connection_secret_name(id, field),
+                    stored,
+                )
+            };
+            let password = secret("password", &password);
+            let ssh_private_key = secret("ssh_private_key", &ssh_private_key);
+            let ssh_password = secret("ssh_password", &ssh_password);
+            let ssh_key_passphrase = secret("ssh_key_passphrase", &ssh_key_passphrase);
 
-            models::structs::ConnectionConfig {
+            let mut connection = models::structs::ConnectionConfig {
                 id: Some(id),
                 name,
                 host,
@@ -479,10 +504,23 @@ pub async fn fetch_databases_background_task(
                 ssh_auth_method: models::enums::SshAuthMethod::from_db_value(&ssh_auth_method),
                 ssh_private_key,
                 ssh_password,
+                ssh_key_passphrase,
                 ssh_accept_unknown_host_keys: ssh_accept_unknown_host_keys != 0,
+                mssql_encrypt: mssql_encrypt != 0,
+                mssql_trust_server_certificate: mssql_trust_server_certificate != 0,
                 custom_views: Vec::new(),
+                sqlite_attachments: Vec::new(),
                 replication_master_id: None,
-            }
+                default_schema: String::new(),
+                last_used_database: None,
+                use_credential_file: use_credential_file != 0,
+                label: String::new(),
+                color: String::new(),
+                proxy,
+                templates: None,
+            };
+            crate::connection::resolve_loaded_connection(&mut connection);
+            connection
         }
         _ => {
             debug!("Connection {} not found in cache", connection_id);
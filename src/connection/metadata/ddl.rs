@@ -250,6 +250,7 @@ pub(crate) fn fetch_view_definition(
                 let port: u16 = connection_clone.port.parse().unwrap_or(1433);
                 let user = connection_clone.username.clone();
                 let pass = connection_clone.password.clone();
+                let tls = crate::driver_mssql::MssqlTls::from_connection(&connection_clone);
                 let db = if db_name.is_empty() {
                     connection_clone.database.clone()
                 } else {
@@ -258,7 +259,7 @@ pub(crate) fn fetch_view_definition(
 
                 let rt_res: Result<Option<String>, String> = async {
                     let mut client =
-                        crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db))
+                        crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db), tls)
                             .await?;
 
                     let parse_qualified = |name: &str| -> (Option<String>, String) {
@@ -452,6 +453,7 @@ pub(crate) fn fetch_procedure_definition(
                 let port: u16 = connection_clone.port.parse().unwrap_or(1433);
                 let user = connection_clone.username.clone();
                 let pass = connection_clone.password.clone();
+                let tls = crate::driver_mssql::MssqlTls::from_connection(&connection_clone);
                 let db = if db_name.is_empty() {
                     connection_clone.database.clone()
                 } else {
@@ -460,7 +462,7 @@ pub(crate) fn fetch_procedure_definition(
 
                 let rt_res: Result<Option<String>, String> = async {
                     let mut client =
-                        crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db))
+                        crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db), tls)
                             .await?;
 
                     let parse_qualified = |name: &str| -> (Option<String>, String) {
@@ -545,7 +547,7 @@ pub(crate) async fn get_foreign_keys(
                 }
             }
             models::enums::DatabasePool::SQLite(p) => {
-                match crate::driver_sqlite::fetch_sqlite_foreign_keys(&p).await {
+                match crate::driver_sqlite::fetch_sqlite_foreign_keys(&p, database_name).await {
                     Ok(k) => keys = k,
                     Err(e) => debug!("Failed to fetch SQLite foreign keys: {}", e),
                 }
@@ -609,6 +611,7 @@ async fn fetch_mssql_foreign_keys(
         &conn.username,
         &conn.password,
         Some(&db),
+        crate::driver_mssql::MssqlTls::from_connection(conn),
     )
     .await
     {
@@ -895,6 +898,7 @@ async fn generate_mssql_ddl(
         &conn.username,
         &conn.password,
         Some(db_name),
+        crate::driver_mssql::MssqlTls::from_connection(conn),
     )
     .await
     {
@@ -1021,13 +1025,16 @@ async fn fetch_schema_columns(
                 })
         }
         models::enums::DatabasePool::SQLite(p) => {
-            let tables: Vec<String> = sqlx::query_as::<_, (String,)>(
-                "SELECT name FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%'",
-            ).fetch_all(p.as_ref()).await.unwrap_or_default()
-             .into_iter().map(|(n,)| n).collect();
+            let tables_query = format!(
+                "SELECT name FROM {} WHERE type='table' AND name NOT LIKE 'sqlite_%'",
+                crate::driver_sqlite::schema_master(Some(db_name))
+            );
+            let tables: Vec<String> = sqlx::query_as::<_, (String,)>(sqlx::AssertSqlSafe(tables_query.as_str()))
+                .fetch_all(p.as_ref()).await.unwrap_or_default()
+                .into_iter().map(|(n,)| n).collect();
             let mut map: HashMap<String, Vec<(String, String)>> = HashMap::new();
             for tbl in tables {
-                let pragma = format!("PRAGMA table_info('{}')", tbl.replace('\'', "''"));
+                let pragma = crate::driver_sqlite::schema_pragma(Some(db_name), "table_info", &tbl);
                 if let Ok(rows) = sqlx::query(sqlx::AssertSqlSafe(pragma.as_str())).fetch_all(p.as_ref()).await {
                     for row in rows {
                         use sqlx::Row;
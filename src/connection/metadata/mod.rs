@@ -5,11 +5,13 @@
 //   columns     – fetch_columns_from_database
 //   ddl         – fetch_view_definition, fetch_procedure_definition,
 //                 get_foreign_keys, fetch_table_definition
+//   table_stats – row-count / size estimates for the tree tooltip
 
 mod cache;
 mod databases;
 mod columns;
 mod ddl;
+mod table_stats;
 
 // Re-export everything that the parent connection module (and the rest of the
 // crate) expects to find at the `metadata::*` path.
@@ -24,6 +26,8 @@ pub(crate) use databases::{
 
 pub(crate) use columns::fetch_columns_from_database;
 
+pub(crate) use table_stats::{render_table_stats_tooltip, spawn_requested_table_stats};
+
 pub(crate) use ddl::{
     compute_schema_diff,
     fetch_procedure_definition, fetch_table_definition, fetch_view_definition, get_foreign_keys,
@@ -0,0 +1,193 @@
+// connection/metadata/table_stats.rs
+// Row-count and size estimates for the tree tooltip, fetched on first hover
+// in the background and kept for a short TTL.
+
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use log::debug;
+
+use crate::models::structs::{TableStats, TableStatsCache, TableStatsEntry};
+use crate::window_egui::Tabular;
+use crate::{driver_mssql, driver_mysql, driver_postgres, driver_sqlite, models};
+
+/// Estimates older than this are fetched again on the next hover.
+const TABLE_STATS_TTL: Duration = Duration::from_secs(60);
+
+/// `1234567` → `1,234,567`.
+fn group_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn stats_lines(stats: &TableStats) -> (String, String) {
+    let rows = match stats.rows {
+        Some(rows) if stats.rows_capped => format!("Rows: more than {}", group_thousands(rows)),
+        Some(rows) => format!("Rows: ~{}", group_thousands(rows)),
+        None => "Rows: unknown".to_string(),
+    };
+    let size = match stats.bytes {
+        Some(bytes) => format!(
+            "Size: {}",
+            crate::data_table::format_byte_size(bytes as f64)
+        ),
+        None => "Size: unknown".to_string(),
+    };
+    (rows, size)
+}
+
+/// Tooltip body for a table node. Missing or stale estimates are marked
+/// for fetching; [`spawn_requested_table_stats`] picks them up.
+pub(crate) fn render_table_stats_tooltip(
+    ui: &mut egui::Ui,
+    cache: &TableStatsCache,
+    key: (i64, String, String),
+) {
+    let entry = {
+        let Ok(mut cache) = cache.lock() else {
+            return;
+        };
+        let entry = cache.entry(key).or_insert(TableStatsEntry::Requested);
+        if let TableStatsEntry::Ready(_, fetched_at) = entry
+            && fetched_at.elapsed() > TABLE_STATS_TTL
+        {
+            *entry = TableStatsEntry::Requested;
+        }
+        entry.clone()
+    };
+    match entry {
+        TableStatsEntry::Ready(stats, _) => {
+            let (rows, size) = stats_lines(&stats);
+            ui.label(rows);
+            ui.label(size);
+        }
+        TableStatsEntry::Requested | TableStatsEntry::Loading => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Estimating size…");
+            });
+        }
+    }
+}
+
+async fn fetch_table_stats(
+    pool: models::enums::DatabasePool,
+    database: &str,
+    table: &str,
+) -> Result<TableStats, String> {
+    match pool {
+        models::enums::DatabasePool::MySQL(p) => {
+            driver_mysql::fetch_mysql_table_stats(&p, database, table)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        models::enums::DatabasePool::PostgreSQL(p) => {
+            driver_postgres::fetch_postgres_table_stats(&p, table)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        models::enums::DatabasePool::SQLite(p) => {
+            driver_sqlite::fetch_sqlite_table_stats(&p, database, table)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        models::enums::DatabasePool::MsSQL(p) => {
+            driver_mssql::fetch_mssql_table_stats(&p, table).await
+        }
+        _ => Ok(TableStats::default()),
+    }
+}
+
+/// Start a background fetch for every hovered table awaiting estimates.
+/// Tables whose connection has no pool yet show "unknown" until the TTL
+/// expires.
+pub(crate) fn spawn_requested_table_stats(tabular: &mut Tabular) {
+    let requested: Vec<(i64, String, String)> = match tabular.table_stats.lock() {
+        Ok(cache) => cache
+            .iter()
+            .filter(|(_, entry)| matches!(entry, TableStatsEntry::Requested))
+            .map(|(key, _)| key.clone())
+            .collect(),
+        Err(_) => return,
+    };
+    if requested.is_empty() {
+        return;
+    }
+    let runtime = tabular.get_runtime();
+    for key in requested {
+        let connection_id = key.0;
+        let pool = tabular
+            .connection_pools
+            .get(&connection_id)
+            .cloned()
+            .or_else(|| {
+                tabular
+                    .shared_connection_pools
+                    .lock()
+                    .ok()
+                    .and_then(|pools| pools.get(&connection_id).cloned())
+            });
+        let cache = tabular.table_stats.clone();
+        let Some(pool) = pool else {
+            if let Ok(mut cache) = cache.lock() {
+                cache.insert(
+                    key,
+                    TableStatsEntry::Ready(TableStats::default(), Instant::now()),
+                );
+            }
+            continue;
+        };
+        if let Ok(mut cache) = cache.lock() {
+            cache.insert(key.clone(), TableStatsEntry::Loading);
+        }
+        runtime.spawn(async move {
+            let (_, database, table) = &key;
+            let stats = fetch_table_stats(pool, database, table)
+                .await
+                .unwrap_or_else(|e| {
+                    debug!("Table stats for {}.{} unavailable: {}", database, table, e);
+                    TableStats::default()
+                });
+            if let Ok(mut cache) = cache.lock() {
+                cache.insert(key, TableStatsEntry::Ready(stats, Instant::now()));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tooltip_lines_describe_estimates() {
+        let stats = TableStats {
+            rows: Some(1_234_567),
+            rows_capped: false,
+            bytes: Some(1536),
+        };
+        assert_eq!(
+            stats_lines(&stats),
+            ("Rows: ~1,234,567".to_string(), "Size: 1.5 KiB".to_string())
+        );
+        let capped = TableStats {
+            rows: Some(100_000),
+            rows_capped: true,
+            bytes: None,
+        };
+        assert_eq!(
+            stats_lines(&capped),
+            (
+                "Rows: more than 100,000".to_string(),
+                "Size: unknown".to_string()
+            )
+        );
+    }
+}
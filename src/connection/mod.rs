@@ -8,6 +8,14 @@
 //   execute  – per-driver async query execution
 //   metadata – schema/database/column discovery and row-cache prefetch
 //   crud     – connection CRUD (update, remove, test) + background refresh
+//   errors   – driver error classification (e.g. lost connection detection)
+//   import   – background CSV import in batched transactions
+//   routines – stored procedure / function calls with bound parameters
+//   fleet    – one statement on several connections, results merged
+//   safe_mode – data changes held open for a "Keep changes" soft undo
+//   credential_files – passwords from ~/.pgpass and ~/.my.cnf
+//   tag      – per-connection color and label marking its environment
+//   stream   – SELECT rows streamed to the grid in batches, with a ceiling
 //   ui       – egui connection-selector popup
 
 pub mod types;
@@ -16,7 +24,16 @@ pub mod pool;
 pub mod execute;
 pub mod metadata;
 pub mod crud;
+pub mod errors;
+pub mod import;
+pub mod routines;
+pub mod fleet;
 pub mod session;
+pub mod safe_mode;
+pub mod credential_files;
+pub mod kill_session;
+pub mod tag;
+pub mod stream;
 pub mod ui;
 
 // ── Re-exports ────────────────────────────────────────────────────────────────
@@ -24,21 +41,28 @@ pub mod ui;
 
 // Types
 pub(crate) use types::{QueryJobStatus, QueryResultMessage};
+pub(crate) use stream::{QueryStream, StreamControl, StreamEvent, StreamProgress};
+
+// Error classification
+pub(crate) use errors::{QueryErrorCategory, classify_query_error};
 
 // SQL utilities
 pub(crate) use sql::{
     add_auto_limit_if_needed, should_enable_auto_pagination, split_sql_statements,
+    statement_returns_rows,
 };
 
 // Pool management
 pub(crate) use pool::{
     cleanup_connection_pool, ensure_background_pool_creation, get_or_create_connection_pool,
+    resolve_connection_env, resolve_connection_target, resolve_loaded_connection,
     start_background_pool_creation,
 };
 
 // Query execution
 pub(crate) use execute::{
-    execute_query_with_connection, prepare_query_job, spawn_query_job, spawn_query_job_batch,
+    execute_query_with_connection, prepare_bound_query_job, prepare_query_job, spawn_query_job,
+    spawn_query_job_batch, spawn_side_query,
 };
 
 // Metadata / schema discovery
@@ -49,13 +73,24 @@ pub(crate) use metadata::{
     fetch_columns_from_database,
     fetch_databases_from_connection_async, fetch_databases_from_connection_blocking,
     fetch_procedure_definition, fetch_table_definition, fetch_view_definition, get_foreign_keys,
+    render_table_stats_tooltip, spawn_requested_table_stats,
 };
 
 // Connection CRUD + testing
 pub(crate) use crud::{
-    refresh_connection_background_async, remove_connection, test_database_connection,
-    update_connection_in_database,
+    default_tab_database, default_tab_schema, remember_last_used_database,
+    refresh_connection_background_async, refresh_progress_snapshot, remove_connection, start_connection_test,
+    poll_connection_test, update_connection_in_database,
 };
 
+// CSV import
+pub(crate) use import::{CsvImportJob, spawn_csv_import};
+
+// Routine calls
+pub(crate) use routines::{open_routine_call, poll_routine_signature, spawn_routine_call};
+
+// Multi-connection runs
+pub(crate) use fleet::{open_fleet_run, render_fleet_run_dialog};
+
 // UI
 pub(crate) use ui::render_connection_selector;
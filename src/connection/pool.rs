@@ -1,16 +1,98 @@
-use crate::{models, modules, ssh_tunnel, window_egui::Tabular};
+use crate::{models, modules, proxy_tunnel, ssh_tunnel, window_egui::Tabular};
 use log::debug;
 use mongodb::Client as MongoClient;
 use redis::{Client, aio::ConnectionManager};
-use sqlx::{
-    mysql::MySqlPoolOptions, postgres::PgPoolOptions, sqlite::SqlitePoolOptions,
-};
+use sqlx::{mysql::MySqlPoolOptions, postgres::PgPoolOptions, sqlite::SqlitePoolOptions};
 use std::sync::Arc;
 
-/// Resolve the actual host/port to connect to, accounting for SSH tunnels.
+/// Replace `${VAR}` placeholders with values from the environment (`.env` is
+/// loaded at startup). Errors name the first variable that is not set; an
+/// unterminated `${` is kept as written.
+pub(crate) fn resolve_env_placeholders(value: &str) -> Result<String, String> {
+    let mut resolved = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        let replacement = std::env::var(name)
+            .map_err(|_| format!("Environment variable `{}` is not set", name))?;
+        resolved.push_str(&rest[..start]);
+        resolved.push_str(&replacement);
+        rest = &rest[start + 3 + len..];
+    }
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// Resolve `${VAR}` placeholders in host, port, username, password and
+/// database, and read the password from the credential file when the
+/// connection uses one. Runs once as connections are loaded; the fields as
+/// written stay in `templates`, which saving and editing go back to. On
+/// failure the fields keep their templates and connecting reports the error.
+pub(crate) fn resolve_loaded_connection(connection: &mut models::structs::ConnectionConfig) {
+    if connection.templates.is_some() {
+        return;
+    }
+    let mut templates = models::structs::ConnectionTemplates {
+        host: connection.host.clone(),
+        port: connection.port.clone(),
+        username: connection.username.clone(),
+        password: connection.password.clone(),
+        database: connection.database.clone(),
+        error: None,
+    };
+    let mut resolved = connection.clone();
+    match resolve_template_fields(&mut resolved) {
+        Ok(()) => {
+            resolved.templates = Some(templates);
+            *connection = resolved;
+        }
+        Err(err) => {
+            templates.error = Some(err);
+            connection.templates = Some(templates);
+        }
+    }
+}
+
+fn resolve_template_fields(connection: &mut models::structs::ConnectionConfig) -> Result<(), String> {
+    for (field, value) in [
+        ("Host", &mut connection.host),
+        ("Port", &mut connection.port),
+        ("Username", &mut connection.username),
+        ("Password", &mut connection.password),
+        ("Database", &mut connection.database),
+    ] {
+        *value = resolve_env_placeholders(value).map_err(|e| format!("{}: {}", field, e))?;
+    }
+    if connection.use_credential_file {
+        super::credential_files::apply_credential_file(connection)?;
+    }
+    Ok(())
+}
+
+/// Resolved copy of `connection` (a no-op for loaded connections, see
+/// [`resolve_loaded_connection`]), or why it cannot be resolved.
+pub(crate) fn resolve_connection_env(
+    connection: &models::structs::ConnectionConfig,
+) -> Result<models::structs::ConnectionConfig, String> {
+    let mut resolved = connection.clone();
+    resolve_loaded_connection(&mut resolved);
+    match resolved.templates.as_ref().and_then(|t| t.error.clone()) {
+        Some(err) => Err(err),
+        None => Ok(resolved),
+    }
+}
+
+/// Resolve the actual host/port to connect to, accounting for SSH tunnels
+/// and proxies (the connection's own, else the global one from Preferences).
 pub(crate) fn resolve_connection_target(
     connection: &models::structs::ConnectionConfig,
 ) -> Result<(String, String), String> {
+    if connection.ssh_enabled && connection.proxy.kind != models::enums::ProxyKind::None {
+        return Err("A connection cannot use both an SSH tunnel and a proxy".to_string());
+    }
     if connection.ssh_enabled {
         match connection.connection_type {
             models::enums::DatabaseType::SQLite => {
@@ -21,6 +103,9 @@ pub(crate) fn resolve_connection_target(
                 Ok(("127.0.0.1".to_string(), local_port.to_string()))
             }
         }
+    } else if let Some(proxy) = proxy_tunnel::proxy_for(connection) {
+        let local_port = proxy_tunnel::ensure_forwarder(connection, &proxy)?;
+        Ok(("127.0.0.1".to_string(), local_port.to_string()))
     } else {
         Ok((connection.host.clone(), connection.port.clone()))
     }
@@ -68,6 +153,17 @@ pub(crate) fn cleanup_stuck_pending_connections(tabular: &mut Tabular) {
 pub(crate) async fn create_connection_pool_for_config(
     connection: &models::structs::ConnectionConfig,
 ) -> Option<models::enums::DatabasePool> {
+    let connection = match resolve_connection_env(connection) {
+        Ok(resolved) => resolved,
+        Err(err) => {
+            debug!(
+                "Cannot create pool for connection {:?}: {}",
+                connection.id, err
+            );
+            return None;
+        }
+    };
+    let connection = &connection;
     match connection.connection_type {
         models::enums::DatabaseType::MySQL => {
             let (target_host, target_port) = match resolve_connection_target(connection) {
@@ -203,7 +299,7 @@ pub(crate) async fn create_connection_pool_for_config(
         models::enums::DatabaseType::SQLite => {
             let connection_string = format!("sqlite:{}", connection.host);
 
-            let pool_result = SqlitePoolOptions::new()
+            let pool_result = crate::driver_sqlite::with_attachments(SqlitePoolOptions::new(), connection)
                 .max_connections(5)
                 .min_connections(1)
                 .acquire_timeout(std::time::Duration::from_secs(10))
@@ -326,6 +422,7 @@ pub(crate) async fn create_connection_pool_for_config(
                 &connection.username,
                 &connection.password,
                 Some(&connection.database),
+                crate::driver_mssql::MssqlTls::from_connection(connection),
             );
 
             match mssql_driver_pool::Pool::builder()
@@ -353,8 +450,20 @@ pub(crate) async fn create_connection_pool_for_config(
 pub(crate) async fn create_database_pool(
     connection: &models::structs::ConnectionConfig,
 ) -> Option<models::enums::DatabasePool> {
+    let connection = match resolve_connection_env(connection) {
+        Ok(resolved) => resolved,
+        Err(err) => {
+            debug!(
+                "Cannot create pool for connection {:?}: {}",
+                connection.id, err
+            );
+            return None;
+        }
+    };
+    let connection = &connection;
     match connection.connection_type {
         models::enums::DatabaseType::MySQL => {
+            // Already resolved: the builder's own resolve is a no-op now
             return create_connection_pool_for_config(connection).await;
         }
         models::enums::DatabaseType::PostgreSQL => {
@@ -392,7 +501,7 @@ pub(crate) async fn create_database_pool(
         models::enums::DatabaseType::SQLite => {
             let connection_string = format!("sqlite:{}", connection.host);
 
-            match SqlitePoolOptions::new()
+            match crate::driver_sqlite::with_attachments(SqlitePoolOptions::new(), connection)
                 .max_connections(3)
                 .min_connections(1)
                 .acquire_timeout(std::time::Duration::from_secs(10))
@@ -450,6 +559,7 @@ pub(crate) async fn create_database_pool(
                 &connection.username,
                 &connection.password,
                 Some(&connection.database),
+                crate::driver_mssql::MssqlTls::from_connection(connection),
             );
 
             match mssql_driver_pool::Pool::builder()
@@ -551,6 +661,14 @@ pub(crate) fn start_background_pool_creation(tabular: &mut Tabular, connection_i
             return;
         }
     };
+    // Surface unset placeholders here; the pool builders only log them.
+    if let Err(err) = resolve_connection_env(&connection) {
+        tabular
+            .toasts
+            .error(format!("Cannot connect to {}: {}", connection.name, err));
+        tabular.pending_connection_pools.remove(&connection_id);
+        return;
+    }
 
     if let Some(runtime) = &tabular.runtime {
         let rt = runtime.clone();
@@ -754,4 +872,54 @@ pub(crate) fn cleanup_connection_pool(tabular: &mut Tabular, connection_id: i64)
     }
 
     ssh_tunnel::shutdown_by_id(connection_id);
+    proxy_tunnel::shutdown_by_id(connection_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_placeholders_resolve_or_name_the_missing_variable() {
+        let path = std::env::var("PATH").unwrap_or_default();
+        assert_eq!(
+            resolve_env_placeholders("a${PATH}b").as_deref(),
+            Ok(format!("a{}b", path).as_str())
+        );
+        assert_eq!(
+            resolve_env_placeholders("plain ${open").as_deref(),
+            Ok("plain ${open")
+        );
+        let err = resolve_env_placeholders("${TABULAR_TEST_SURELY_UNSET_VAR}").unwrap_err();
+        assert!(err.contains("TABULAR_TEST_SURELY_UNSET_VAR"));
+    }
+
+    #[test]
+    fn loaded_connections_resolve_once_and_save_their_templates() {
+        let path = std::env::var("PATH").unwrap_or_default();
+        let mut connection = models::structs::ConnectionConfig {
+            host: "${PATH}".to_string(),
+            password: "${PATH}".to_string(),
+            ..Default::default()
+        };
+        resolve_loaded_connection(&mut connection);
+        assert_eq!(connection.host, path);
+        assert_eq!(connection.password, path);
+        let stored = connection.for_storage();
+        assert_eq!((stored.host.as_str(), stored.password.as_str()), ("${PATH}", "${PATH}"));
+        assert!(stored.templates.is_none());
+
+        // Resolving again keeps the resolved values, even if they look like templates
+        connection.password = "${TABULAR_TEST_SURELY_UNSET_VAR}".to_string();
+        assert!(resolve_connection_env(&connection).is_ok());
+
+        let mut broken = models::structs::ConnectionConfig {
+            username: "${TABULAR_TEST_SURELY_UNSET_VAR}".to_string(),
+            ..Default::default()
+        };
+        resolve_loaded_connection(&mut broken);
+        assert_eq!(broken.username, "${TABULAR_TEST_SURELY_UNSET_VAR}");
+        let err = resolve_connection_env(&broken).unwrap_err();
+        assert!(err.starts_with("Username:"));
+    }
 }
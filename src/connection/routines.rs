@@ -0,0 +1,576 @@
+// connection/routines.rs
+// Calling stored procedures and functions from the sidebar: the parameter
+// signature comes from the driver, the call is built per dialect with the
+// values bound, and the result reaches the grid through the query channel.
+
+use std::sync::mpsc;
+use std::time::Instant;
+
+use crate::models::enums::{DatabasePool, DatabaseType};
+use crate::models::structs::{ParameterMode, RoutineCallState, RoutineKind, RoutineSignature};
+use crate::window_egui::Tabular;
+use crate::{driver_mssql, driver_mysql, driver_postgres, editor};
+
+use super::QueryResultMessage;
+
+/// Schema searched for PostgreSQL routines, matching the sidebar listing.
+const POSTGRES_ROUTINE_SCHEMA: &str = "public";
+
+/// One statement of a routine call with the parameter index bound to each
+/// placeholder, in placeholder order.
+#[derive(Debug, PartialEq)]
+pub(crate) struct RoutineStatement {
+    pub sql: String,
+    pub binds: Vec<usize>,
+}
+
+/// Hands out placeholders for one statement and records what they bind.
+struct Binder<'a> {
+    placeholder: &'a dyn Fn(usize, usize) -> String,
+    binds: Vec<usize>,
+}
+
+impl<'a> Binder<'a> {
+    fn new(placeholder: &'a dyn Fn(usize, usize) -> String) -> Self {
+        Self {
+            placeholder,
+            binds: Vec::new(),
+        }
+    }
+
+    fn arg(&mut self, index: usize) -> String {
+        self.binds.push(index);
+        (self.placeholder)(index, self.binds.len())
+    }
+
+    fn finish(self, sql: String) -> RoutineStatement {
+        RoutineStatement {
+            sql,
+            binds: self.binds,
+        }
+    }
+}
+
+fn quote_mysql(ident: &str) -> String {
+    format!("`{}`", ident.replace('`', "``"))
+}
+
+fn quote_postgres(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+fn quote_mssql(ident: &str) -> String {
+    format!("[{}]", ident.replace(']', "]]"))
+}
+
+/// Name used in the call. SQL Server tree names are already quoted
+/// (`[dbo].[proc]`) and only gain the database.
+pub(crate) fn qualified_routine_name(
+    db_type: &DatabaseType,
+    database_name: Option<&str>,
+    routine_name: &str,
+) -> String {
+    let database = database_name.filter(|d| !d.is_empty());
+    match db_type {
+        DatabaseType::MySQL => match database {
+            Some(db) => format!("{}.{}", quote_mysql(db), quote_mysql(routine_name)),
+            None => quote_mysql(routine_name),
+        },
+        DatabaseType::PostgreSQL => format!(
+            "{}.{}",
+            quote_postgres(POSTGRES_ROUTINE_SCHEMA),
+            quote_postgres(routine_name)
+        ),
+        _ => match database {
+            Some(db) => format!("{}.{}", quote_mssql(db), routine_name),
+            None => routine_name.to_string(),
+        },
+    }
+}
+
+fn mysql_statements(
+    call: &RoutineCallState,
+    placeholder: &dyn Fn(usize, usize) -> String,
+) -> Vec<RoutineStatement> {
+    // OUT and INOUT arguments go through session variables, read back last.
+    let variable = |index: usize| format!("@__tabular_arg{}", index + 1);
+    let mut statements = Vec::new();
+    for (index, parameter) in call.parameters.iter().enumerate() {
+        if parameter.mode == ParameterMode::InOut {
+            let mut binder = Binder::new(placeholder);
+            let sql = format!("SET {} = {}", variable(index), binder.arg(index));
+            statements.push(binder.finish(sql));
+        }
+    }
+    let mut binder = Binder::new(placeholder);
+    let args: Vec<String> = call
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(index, parameter)| match parameter.mode {
+            ParameterMode::In => binder.arg(index),
+            _ => variable(index),
+        })
+        .collect();
+    let sql = match call.kind {
+        RoutineKind::Procedure => format!("CALL {}({})", call.qualified_name, args.join(", ")),
+        RoutineKind::Function => format!(
+            "SELECT {}({}) AS {}",
+            call.qualified_name,
+            args.join(", "),
+            quote_mysql(&call.routine_name)
+        ),
+    };
+    statements.push(binder.finish(sql));
+    let outputs: Vec<String> = call
+        .parameters
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.mode != ParameterMode::In)
+        .map(|(index, p)| format!("{} AS {}", variable(index), quote_mysql(&p.name)))
+        .collect();
+    if !outputs.is_empty() {
+        statements.push(RoutineStatement {
+            sql: format!("SELECT {}", outputs.join(", ")),
+            binds: Vec::new(),
+        });
+    }
+    statements
+}
+
+fn postgres_statement(
+    call: &RoutineCallState,
+    placeholder: &dyn Fn(usize, usize) -> String,
+) -> RoutineStatement {
+    let mut binder = Binder::new(placeholder);
+    let mut args = Vec::new();
+    for (index, parameter) in call.parameters.iter().enumerate() {
+        if parameter.mode.takes_value() {
+            args.push(format!(
+                "CAST({} AS {})",
+                binder.arg(index),
+                parameter.data_type
+            ));
+        } else if call.kind == RoutineKind::Procedure {
+            // Procedures take OUT arguments as placeholders (PostgreSQL 14+).
+            args.push(format!("NULL::{}", parameter.data_type));
+        }
+    }
+    let sql = match call.kind {
+        RoutineKind::Procedure => format!("CALL {}({})", call.qualified_name, args.join(", ")),
+        // Works for scalar, composite and set-returning functions alike.
+        RoutineKind::Function => {
+            format!("SELECT * FROM {}({})", call.qualified_name, args.join(", "))
+        }
+    };
+    binder.finish(sql)
+}
+
+fn mssql_statement(
+    call: &RoutineCallState,
+    placeholder: &dyn Fn(usize, usize) -> String,
+) -> RoutineStatement {
+    let mut binder = Binder::new(placeholder);
+    let sql = match call.kind {
+        RoutineKind::Procedure => {
+            let variable = |index: usize| format!("@__tabular_arg{}", index + 1);
+            let mut lines = vec!["SET NOCOUNT ON;".to_string()];
+            let mut args = Vec::new();
+            let mut outputs = Vec::new();
+            for (index, parameter) in call.parameters.iter().enumerate() {
+                lines.push(format!(
+                    "DECLARE {} {} = {};",
+                    variable(index),
+                    parameter.data_type,
+                    binder.arg(index)
+                ));
+                if parameter.mode == ParameterMode::In {
+                    args.push(format!("{} = {}", parameter.name, variable(index)));
+                } else {
+                    args.push(format!("{} = {} OUTPUT", parameter.name, variable(index)));
+                    outputs.push(format!(
+                        "{} AS {}",
+                        variable(index),
+                        quote_mssql(parameter.name.trim_start_matches('@'))
+                    ));
+                }
+            }
+            lines.push(format!("EXEC {} {};", call.qualified_name, args.join(", ")));
+            if !outputs.is_empty() {
+                lines.push(format!("SELECT {};", outputs.join(", ")));
+            }
+            lines.join("\n")
+        }
+        RoutineKind::Function => {
+            let args: Vec<String> = call
+                .parameters
+                .iter()
+                .enumerate()
+                .map(|(index, p)| format!("CAST({} AS {})", binder.arg(index), p.data_type))
+                .collect();
+            if call.returns_table {
+                format!("SELECT * FROM {}({})", call.qualified_name, args.join(", "))
+            } else {
+                format!(
+                    "SELECT {}({}) AS {}",
+                    call.qualified_name,
+                    args.join(", "),
+                    quote_mssql("result")
+                )
+            }
+        }
+    };
+    binder.finish(sql)
+}
+
+fn build_statements(
+    call: &RoutineCallState,
+    placeholder: &dyn Fn(usize, usize) -> String,
+) -> Vec<RoutineStatement> {
+    match call.db_type {
+        DatabaseType::MySQL => mysql_statements(call, placeholder),
+        DatabaseType::PostgreSQL => vec![postgres_statement(call, placeholder)],
+        DatabaseType::MsSQL => vec![mssql_statement(call, placeholder)],
+        _ => Vec::new(),
+    }
+}
+
+/// The call with driver placeholders (`?`, `$n`, `@pn`).
+pub(crate) fn routine_call_statements(call: &RoutineCallState) -> Vec<RoutineStatement> {
+    let placeholder: &dyn Fn(usize, usize) -> String = match call.db_type {
+        DatabaseType::PostgreSQL => &|_, n| format!("${}", n),
+        DatabaseType::MsSQL => &|_, n| format!("@p{}", n),
+        _ => &|_, _| "?".to_string(),
+    };
+    build_statements(call, placeholder)
+}
+
+/// Value bound for parameter `index`; an empty input is NULL.
+fn bound_value(call: &RoutineCallState, index: usize) -> Option<String> {
+    call.values.get(index).filter(|v| !v.is_empty()).cloned()
+}
+
+/// The call as runnable SQL with the values written out as literals, for
+/// the query tab and history.
+pub(crate) fn routine_call_preview(call: &RoutineCallState) -> String {
+    let literal = |index: usize, _| match bound_value(call, index) {
+        None => "NULL".to_string(),
+        Some(value) => match call.db_type {
+            DatabaseType::MsSQL => format!("N'{}'", value.replace('\'', "''")),
+            DatabaseType::MySQL => {
+                format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+            }
+            _ => format!("'{}'", value.replace('\'', "''")),
+        },
+    };
+    build_statements(call, &literal)
+        .into_iter()
+        .map(|s| {
+            if s.sql.ends_with(';') {
+                s.sql
+            } else {
+                format!("{};", s.sql)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Open the call dialog for a routine node and start loading its signature.
+pub(crate) fn open_routine_call(
+    tabular: &mut Tabular,
+    connection_id: i64,
+    database_name: Option<String>,
+    routine_name: String,
+    kind: RoutineKind,
+) {
+    let Some(db_type) = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(connection_id))
+        .map(|c| c.connection_type.clone())
+    else {
+        return;
+    };
+    if !matches!(
+        db_type,
+        DatabaseType::MySQL | DatabaseType::PostgreSQL | DatabaseType::MsSQL
+    ) {
+        tabular
+            .toasts
+            .error("Calling routines is not supported for this connection type");
+        return;
+    }
+    let (sender, receiver) = mpsc::channel();
+    let runtime = tabular.get_runtime();
+    let pool = runtime.block_on(super::get_or_create_connection_pool(tabular, connection_id));
+    let database = database_name.clone().unwrap_or_default();
+    let name = routine_name.clone();
+    runtime.spawn(async move {
+        let result = match pool {
+            Some(DatabasePool::MySQL(p)) => {
+                driver_mysql::fetch_mysql_routine_parameters(&p, &database, &name, kind)
+                    .await
+                    .map(|params| (params, false))
+                    .map_err(|e| e.to_string())
+            }
+            Some(DatabasePool::PostgreSQL(p)) => {
+                driver_postgres::fetch_postgres_routine_parameters(
+                    &p,
+                    POSTGRES_ROUTINE_SCHEMA,
+                    &name,
+                )
+                .await
+                .map(|params| (params, false))
+                .map_err(|e| e.to_string())
+            }
+            Some(DatabasePool::MsSQL(p)) => {
+                driver_mssql::fetch_mssql_routine_signature(&p, &database, &name).await
+            }
+            _ => Err("Could not connect to the database".to_string()),
+        };
+        let _ = sender.send(result);
+    });
+    tabular.routine_call_state = Some(RoutineCallState {
+        connection_id,
+        qualified_name: qualified_routine_name(&db_type, database_name.as_deref(), &routine_name),
+        database_name,
+        routine_name,
+        kind,
+        db_type,
+        parameters: Vec::new(),
+        values: Vec::new(),
+        returns_table: false,
+        error: None,
+        signature_receiver: Some(receiver),
+    });
+}
+
+/// Take the loaded signature, if it has arrived.
+pub(crate) fn poll_routine_signature(call: &mut RoutineCallState) {
+    let Some(receiver) = &call.signature_receiver else {
+        return;
+    };
+    let result: Result<RoutineSignature, String> = match receiver.try_recv() {
+        Ok(result) => result,
+        Err(mpsc::TryRecvError::Empty) => return,
+        Err(mpsc::TryRecvError::Disconnected) => Err("Signature lookup stopped".to_string()),
+    };
+    call.signature_receiver = None;
+    match result {
+        Ok((parameters, returns_table)) => {
+            call.values = vec![String::new(); parameters.len()];
+            call.parameters = parameters;
+            call.returns_table = returns_table;
+        }
+        Err(e) => call.error = Some(e),
+    }
+}
+
+/// Run the call in the background. The SQL (with literals) opens in a new
+/// tab and the rows arrive through the query result channel.
+pub(crate) fn spawn_routine_call(tabular: &mut Tabular, call: &RoutineCallState) {
+    let statements: Vec<(String, Vec<Option<String>>)> = routine_call_statements(call)
+        .into_iter()
+        .map(|s| {
+            let values = s.binds.iter().map(|&i| bound_value(call, i)).collect();
+            (s.sql, values)
+        })
+        .collect();
+    let preview = routine_call_preview(call);
+    let connection_id = call.connection_id;
+
+    editor::create_new_tab_with_connection_and_database(
+        tabular,
+        format!("Call: {}", call.routine_name),
+        preview.clone(),
+        Some(connection_id),
+        call.database_name.clone(),
+    );
+
+    let runtime = tabular.get_runtime();
+    let Some(pool) = runtime.block_on(super::get_or_create_connection_pool(tabular, connection_id))
+    else {
+        tabular.toasts.error("Could not connect to the database");
+        return;
+    };
+    let job_id = tabular.next_query_job_id;
+    tabular.next_query_job_id = tabular.next_query_job_id.wrapping_add(1);
+    tabular.active_query_jobs.insert(
+        job_id,
+        super::QueryJobStatus {
+            job_id,
+            connection_id,
+            query_preview: preview.chars().take(80).collect(),
+            started_at: Instant::now(),
+            completed: false,
+        },
+    );
+    tabular.query_execution_in_progress = true;
+    tabular.current_table_name = "Running query…".to_string();
+
+    let sender = tabular.query_result_sender.clone();
+    let handle = runtime.spawn(async move {
+        let started = Instant::now();
+        let result = match &pool {
+            DatabasePool::MySQL(p) => driver_mysql::call_mysql_routine(p, &statements)
+                .await
+                .map(|(headers, rows, affected)| (headers, rows, Some(affected)))
+                .map_err(|e| e.to_string()),
+            DatabasePool::PostgreSQL(p) => match statements.first() {
+                Some((sql, values)) => driver_postgres::call_postgres_routine(p, sql, values)
+                    .await
+                    .map(|(headers, rows)| (headers, rows, None))
+                    .map_err(|e| e.to_string()),
+                None => Err("Nothing to call".to_string()),
+            },
+            DatabasePool::MsSQL(p) => match statements.first() {
+                Some((sql, values)) => driver_mssql::call_mssql_routine(p, sql, values)
+                    .await
+                    .map(|(headers, rows)| (headers, rows, None)),
+                None => Err("Nothing to call".to_string()),
+            },
+            _ => Err("Calling routines is not supported for this connection type".to_string()),
+        };
+        let (success, headers, rows, affected, error) = match result {
+            Ok((headers, rows, affected)) => (true, headers, rows, affected, None),
+            Err(e) => (false, Vec::new(), Vec::new(), None, Some(e)),
+        };
+        // A procedure without a result set reports the rows it changed
+        let affected_rows = affected
+            .filter(|_| headers.is_empty())
+            .map(|n| n as usize);
+        let _ = sender.send(QueryResultMessage {
+            job_id,
+            connection_id,
+            success,
+            produced_result_set: !headers.is_empty(),
+            headers,
+            rows,
+            error,
+            duration: started.elapsed(),
+            query: preview,
+            dba_special_mode: None,
+            ast_debug_sql: None,
+            ast_headers: None,
+            affected_rows,
+            column_metadata: None,
+        });
+    });
+    tabular.active_query_handles.insert(job_id, handle);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::structs::RoutineParameter;
+
+    fn call(
+        db_type: DatabaseType,
+        kind: RoutineKind,
+        params: &[(&str, &str, ParameterMode)],
+    ) -> RoutineCallState {
+        RoutineCallState {
+            connection_id: 1,
+            database_name: Some("shop".to_string()),
+            routine_name: "restock".to_string(),
+            qualified_name: qualified_routine_name(&db_type, Some("shop"), "restock"),
+            kind,
+            db_type,
+            parameters: params
+                .iter()
+                .map(|(name, data_type, mode)| RoutineParameter {
+                    name: name.to_string(),
+                    data_type: data_type.to_string(),
+                    mode: *mode,
+                })
+                .collect(),
+            values: vec!["5".to_string(), String::new(), "it's".to_string()],
+            returns_table: false,
+            error: None,
+            signature_receiver: None,
+        }
+    }
+
+    #[test]
+    fn mysql_procedure_reads_out_parameters_back() {
+        let c = call(
+            DatabaseType::MySQL,
+            RoutineKind::Procedure,
+            &[
+                ("qty", "int", ParameterMode::In),
+                ("total", "int", ParameterMode::Out),
+                ("note", "varchar(20)", ParameterMode::InOut),
+            ],
+        );
+        let statements = routine_call_statements(&c);
+        assert_eq!(
+            statements,
+            vec![
+                RoutineStatement {
+                    sql: "SET @__tabular_arg3 = ?".to_string(),
+                    binds: vec![2],
+                },
+                RoutineStatement {
+                    sql: "CALL `shop`.`restock`(?, @__tabular_arg2, @__tabular_arg3)".to_string(),
+                    binds: vec![0],
+                },
+                RoutineStatement {
+                    sql: "SELECT @__tabular_arg2 AS `total`, @__tabular_arg3 AS `note`".to_string(),
+                    binds: vec![],
+                },
+            ]
+        );
+        assert!(routine_call_preview(&c).starts_with("SET @__tabular_arg3 = 'it''s';\nCALL"));
+    }
+
+    #[test]
+    fn postgres_casts_bound_values_and_skips_function_outputs() {
+        let function = call(
+            DatabaseType::PostgreSQL,
+            RoutineKind::Function,
+            &[
+                ("qty", "integer", ParameterMode::In),
+                ("total", "bigint", ParameterMode::Out),
+            ],
+        );
+        assert_eq!(
+            routine_call_statements(&function),
+            vec![RoutineStatement {
+                sql: "SELECT * FROM \"public\".\"restock\"(CAST($1 AS integer))".to_string(),
+                binds: vec![0],
+            }]
+        );
+        let procedure = RoutineCallState {
+            kind: RoutineKind::Procedure,
+            ..function
+        };
+        assert_eq!(
+            routine_call_preview(&procedure),
+            "CALL \"public\".\"restock\"(CAST('5' AS integer), NULL::bigint);"
+        );
+    }
+
+    #[test]
+    fn mssql_procedure_declares_typed_arguments() {
+        let c = call(
+            DatabaseType::MsSQL,
+            RoutineKind::Procedure,
+            &[
+                ("@qty", "int", ParameterMode::In),
+                ("@total", "int", ParameterMode::InOut),
+            ],
+        );
+        let statements = routine_call_statements(&c);
+        assert_eq!(statements.len(), 1);
+        assert_eq!(statements[0].binds, vec![0, 1]);
+        assert_eq!(
+            statements[0].sql,
+            "SET NOCOUNT ON;\n\
+             DECLARE @__tabular_arg1 int = @p1;\n\
+             DECLARE @__tabular_arg2 int = @p2;\n\
+             EXEC [shop].restock @qty = @__tabular_arg1, @total = @__tabular_arg2 OUTPUT;\n\
+             SELECT @__tabular_arg2 AS [total];"
+        );
+    }
+}
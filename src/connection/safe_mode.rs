@@ -0,0 +1,366 @@
+//! Safe mode: a soft undo for data changes. When enabled in Preferences, an
+//! execution made only of INSERT / UPDATE / DELETE / MERGE / REPLACE runs on
+//! a dedicated connection inside `BEGIN` + `SAVEPOINT`, and the transaction
+//! stays open for a short window. "Keep changes" commits it; otherwise it is
+//! rolled back when the countdown runs out.
+//!
+//! This is separate from manual-commit mode (see `session`): a tab in
+//! manual-commit mode already holds its own transaction and skips safe mode.
+
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use log::warn;
+
+use super::session::{
+    acquire, begin_statement, run_execute, run_simple, session_message, session_pool,
+    supports_transactions,
+};
+use super::types::QueryResultMessage;
+use crate::{models, window_egui};
+
+/// Savepoint taken right after `BEGIN`.
+const SAVEPOINT: &str = "tabular_safe";
+
+/// Progress of a safe-mode change, reported by its task.
+#[derive(Debug)]
+pub enum SafeEvent {
+    /// The statements ran; total rows affected.
+    Applied(u64),
+    Kept,
+    RolledBack,
+    Failed(String),
+}
+
+/// A change waiting for "Keep changes". Dropping it rolls the change back.
+pub struct SafeChange {
+    pub connection_id: i64,
+    pub query: String,
+    pub window: Duration,
+    /// Rows affected and when the rollback happens, once the statements ran.
+    pub applied: Option<(u64, Instant)>,
+    decision: Option<tokio::sync::oneshot::Sender<bool>>,
+    events: Receiver<SafeEvent>,
+}
+
+/// Whether every statement is a plain data change that can be undone:
+/// DML without RETURNING / OUTPUT (those need their result set).
+pub(crate) fn is_safe_mode_batch(statements: &[String]) -> bool {
+    !statements.is_empty()
+        && statements.iter().all(|stmt| {
+            let keyword = crate::cache_data::strip_leading_sql_comments(stmt)
+                .split(|c: char| !c.is_ascii_alphabetic())
+                .next()
+                .unwrap_or("")
+                .to_ascii_uppercase();
+            matches!(
+                keyword.as_str(),
+                "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "REPLACE"
+            ) && !super::statement_returns_rows(stmt)
+        })
+}
+
+/// `(take, roll back to)` statements for the savepoint on `db`.
+fn savepoint_statements(db: &models::enums::DatabaseType) -> (String, String) {
+    match db {
+        models::enums::DatabaseType::MsSQL => (
+            format!("SAVE TRANSACTION {}", SAVEPOINT),
+            format!("ROLLBACK TRANSACTION {}", SAVEPOINT),
+        ),
+        _ => (
+            format!("SAVEPOINT {}", SAVEPOINT),
+            format!("ROLLBACK TO SAVEPOINT {}", SAVEPOINT),
+        ),
+    }
+}
+
+/// Run `statements` in safe mode when it applies: safe mode is on, the
+/// engine has transactions and the batch only changes data. `inputs` are
+/// bound to the placeholders of every statement. Returns whether the
+/// execution was taken over (including when it was refused because another
+/// change is still waiting).
+pub(crate) fn try_start_safe_change(
+    tabular: &mut window_egui::Tabular,
+    connection_id: i64,
+    statements: &[String],
+    inputs: &[(String, String)],
+) -> bool {
+    if !tabular.safe_mode || !is_safe_mode_batch(statements) {
+        return false;
+    }
+    let Some(connection_type) = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(connection_id))
+        .map(|c| c.connection_type.clone())
+        .filter(supports_transactions)
+    else {
+        return false;
+    };
+    if tabular.safe_change.is_some() {
+        tabular
+            .toasts
+            .warning("Keep or undo the pending safe-mode change first");
+        tabular.query_execution_in_progress = false;
+        return true;
+    }
+    let (Some(pool), Some(runtime)) = (
+        session_pool(tabular, connection_id),
+        tabular.runtime.clone(),
+    ) else {
+        return false;
+    };
+
+    let database_name = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.database_name.clone());
+    let query = statements.join(";\n");
+    let job_id = tabular.next_query_job_id;
+    tabular.next_query_job_id = tabular.next_query_job_id.wrapping_add(1);
+    tabular.active_query_jobs.insert(
+        job_id,
+        super::QueryJobStatus {
+            job_id,
+            connection_id,
+            query_preview: format!("[safe] {}", query.chars().take(72).collect::<String>()),
+            started_at: Instant::now(),
+            completed: false,
+        },
+    );
+
+    let window = Duration::from_secs(tabular.safe_mode_window_secs.max(1));
+    let (decision_tx, decision_rx) = tokio::sync::oneshot::channel();
+    let (events_tx, events_rx) = std::sync::mpsc::channel();
+    runtime.spawn(run_safe_change(SafeTask {
+        pool,
+        connection_type,
+        connection_id,
+        database_name,
+        statements: statements.to_vec(),
+        inputs: inputs.to_vec(),
+        query: query.clone(),
+        job_id,
+        window,
+        decision: decision_rx,
+        events: events_tx,
+        results: tabular.query_result_sender.clone(),
+    }));
+
+    tabular.safe_change = Some(SafeChange {
+        connection_id,
+        query,
+        window,
+        applied: None,
+        decision: Some(decision_tx),
+        events: events_rx,
+    });
+    tabular.current_table_name = "Running query (safe mode)…".to_string();
+    true
+}
+
+struct SafeTask {
+    pool: models::enums::DatabasePool,
+    connection_type: models::enums::DatabaseType,
+    connection_id: i64,
+    database_name: Option<String>,
+    statements: Vec<String>,
+    inputs: Vec<(String, String)>,
+    query: String,
+    job_id: u64,
+    window: Duration,
+    decision: tokio::sync::oneshot::Receiver<bool>,
+    events: Sender<SafeEvent>,
+    results: Sender<QueryResultMessage>,
+}
+
+async fn run_safe_change(task: SafeTask) {
+    let started = Instant::now();
+    let report = |outcome: Result<u64, String>| {
+        let message = session_message(
+            task.job_id,
+            task.connection_id,
+            &task.query,
+            outcome.map(|affected| (Vec::new(), Vec::new(), Some(affected))),
+            started,
+        );
+        let _ = task.results.send(message);
+    };
+
+    let mut conn = match acquire(&task.pool, task.database_name.as_deref()).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            let message = format!("Cannot open safe-mode connection: {}", e);
+            report(Err(message.clone()));
+            let _ = task.events.send(SafeEvent::Failed(message));
+            return;
+        }
+    };
+    let (savepoint, rollback_to) = savepoint_statements(&task.connection_type);
+
+    let applied = async {
+        run_simple(&mut conn, begin_statement(&task.connection_type)).await?;
+        run_simple(&mut conn, &savepoint).await?;
+        let mut affected = 0;
+        for stmt in &task.statements {
+            affected += run_execute(&mut conn, stmt, &task.inputs).await?;
+        }
+        Ok::<u64, String>(affected)
+    }
+    .await;
+    report(applied.clone());
+    let affected = match applied {
+        Ok(affected) => affected,
+        Err(e) => {
+            if let Err(e) = run_simple(&mut conn, "ROLLBACK").await {
+                warn!("safe mode: ROLLBACK after error failed: {}", e);
+            }
+            let _ = task.events.send(SafeEvent::Failed(e));
+            return;
+        }
+    };
+    let _ = task.events.send(SafeEvent::Applied(affected));
+
+    // Keep only on an explicit "Keep changes"; a timeout, "Undo now" or a
+    // dropped SafeChange all roll back.
+    let keep = matches!(
+        tokio::time::timeout(task.window, task.decision).await,
+        Ok(Ok(true))
+    );
+    let event = if keep {
+        match run_simple(&mut conn, "COMMIT").await {
+            Ok(()) => SafeEvent::Kept,
+            Err(e) => SafeEvent::Failed(format!("COMMIT failed: {}", e)),
+        }
+    } else {
+        let undone = async {
+            run_simple(&mut conn, &rollback_to).await?;
+            run_simple(&mut conn, "ROLLBACK").await
+        }
+        .await;
+        match undone {
+            Ok(()) => SafeEvent::RolledBack,
+            Err(e) => SafeEvent::Failed(format!("Rollback failed: {}", e)),
+        }
+    };
+    let _ = task.events.send(event);
+}
+
+/// Send the user's decision for the waiting change.
+fn decide(tabular: &mut window_egui::Tabular, keep: bool) {
+    if let Some(change) = tabular.safe_change.as_mut()
+        && let Some(sender) = change.decision.take()
+    {
+        let _ = sender.send(keep);
+    }
+}
+
+/// Countdown bar for the waiting change, with "Keep changes" and "Undo now".
+pub(crate) fn render_safe_change_banner(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(change) = tabular.safe_change.as_mut() else {
+        return;
+    };
+    let mut finished = None;
+    loop {
+        match change.events.try_recv() {
+            Ok(SafeEvent::Applied(affected)) => {
+                change.applied = Some((affected, Instant::now() + change.window));
+            }
+            Ok(event) => {
+                finished = Some(event);
+                break;
+            }
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => {
+                finished = Some(SafeEvent::Failed("Safe-mode task ended".to_string()));
+                break;
+            }
+        }
+    }
+    if let Some(event) = finished {
+        let affected = change.applied.map_or(0, |(n, _)| n);
+        tabular.safe_change = None;
+        match event {
+            SafeEvent::Kept => tabular
+                .toasts
+                .success(format!("Changes kept • {} row(s) committed", affected)),
+            SafeEvent::RolledBack => tabular
+                .toasts
+                .info(format!("Changes undone • {} row(s) rolled back", affected)),
+            // Errors while running already reach the message panel
+            SafeEvent::Failed(e) if affected > 0 => tabular.toasts.error(e),
+            _ => {}
+        }
+        return;
+    }
+
+    let Some((affected, deadline)) = change.applied else {
+        ctx.request_repaint_after(Duration::from_millis(100));
+        return;
+    };
+    let decided = change.decision.is_none();
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    let fraction = remaining.as_secs_f32() / change.window.as_secs_f32();
+    let mut keep = None;
+    egui::Window::new("🛟 Safe mode")
+        .id(egui::Id::new("safe_mode_banner"))
+        .title_bar(false)
+        .resizable(false)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_BOTTOM, egui::vec2(0.0, -48.0))
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("🛟 {} row(s) affected", affected));
+                ui.add(
+                    egui::ProgressBar::new(fraction)
+                        .desired_width(140.0)
+                        .text(format!("undo in {}s", remaining.as_secs() + 1)),
+                );
+                ui.add_enabled_ui(!decided, |ui| {
+                    if ui.button("✔ Keep changes").clicked() {
+                        keep = Some(true);
+                    }
+                    if ui.button("↩ Undo now").clicked() {
+                        keep = Some(false);
+                    }
+                });
+            });
+            ui.label(
+                egui::RichText::new(change.query.chars().take(80).collect::<String>())
+                    .monospace()
+                    .size(11.0)
+                    .weak(),
+            );
+        });
+    if let Some(keep) = keep {
+        decide(tabular, keep);
+    }
+    ctx.request_repaint_after(Duration::from_millis(200));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch(statements: &[&str]) -> Vec<String> {
+        statements.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn only_plain_data_changes_run_in_safe_mode() {
+        assert!(is_safe_mode_batch(&batch(&[
+            "UPDATE t SET a = 1 WHERE id = 2",
+            "-- cleanup\nDELETE FROM t WHERE a IS NULL",
+        ])));
+        assert!(is_safe_mode_batch(&batch(&["insert into t values (1)"])));
+        // Reads, DDL and DML returning rows run as usual
+        assert!(!is_safe_mode_batch(&batch(&["SELECT 1"])));
+        assert!(!is_safe_mode_batch(&batch(&[
+            "UPDATE t SET a = 1",
+            "DROP TABLE t"
+        ])));
+        assert!(!is_safe_mode_batch(&batch(&["DELETE FROM t RETURNING id"])));
+        assert!(!is_safe_mode_batch(&[]));
+    }
+}
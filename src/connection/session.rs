@@ -6,17 +6,24 @@
 //! `USE db` persist across executions. Results flow through the regular
 //! `QueryResultMessage` pipeline.
 
+use futures_util::FutureExt;
+use futures_util::future::BoxFuture;
 use log::{debug, warn};
 use sqlx::{Column, Row};
 use std::time::Instant;
 
 use super::types::QueryResultMessage;
-use crate::models;
+use crate::{models, query_tools};
 use crate::window_egui::Tabular;
 
 #[derive(Debug)]
 pub enum SessionCommand {
-    Execute { job_id: u64, sql: String },
+    /// `inputs` (placeholder name, raw value) are bound to the placeholders of `sql`.
+    Execute {
+        job_id: u64,
+        sql: String,
+        inputs: Vec<(String, String)>,
+    },
     Commit { job_id: u64 },
     Rollback { job_id: u64 },
     Close,
@@ -55,13 +62,127 @@ pub fn supports_transactions(db: &models::enums::DatabaseType) -> bool {
     )
 }
 
-enum SessionConn {
+/// Statement opening a transaction on `db`.
+pub(super) fn begin_statement(db: &models::enums::DatabaseType) -> &'static str {
+    match db {
+        models::enums::DatabaseType::MySQL => "START TRANSACTION",
+        models::enums::DatabaseType::MsSQL => "BEGIN TRANSACTION",
+        _ => "BEGIN",
+    }
+}
+
+pub(super) enum SessionConn {
     MySql(sqlx::pool::PoolConnection<sqlx::MySql>),
     Postgres(sqlx::pool::PoolConnection<sqlx::Postgres>),
     Sqlite(sqlx::pool::PoolConnection<sqlx::Sqlite>),
     MsSQL(Box<mssql_driver_pool::PooledConnection>),
 }
 
+impl SessionConn {
+    fn database_type(&self) -> models::enums::DatabaseType {
+        match self {
+            SessionConn::MySql(_) => models::enums::DatabaseType::MySQL,
+            SessionConn::Postgres(_) => models::enums::DatabaseType::PostgreSQL,
+            SessionConn::Sqlite(_) => models::enums::DatabaseType::SQLite,
+            SessionConn::MsSQL(_) => models::enums::DatabaseType::MsSQL,
+        }
+    }
+}
+
+/// How a transaction run by [`run_in_transaction`] failed.
+#[derive(Debug)]
+pub(crate) enum TransactionFailure {
+    /// BEGIN failed; nothing was run.
+    Begin(String),
+    /// Statement `index` failed; `rollback` is how rolling back went.
+    Statement {
+        index: usize,
+        error: String,
+        rollback: Result<(), String>,
+    },
+    /// COMMIT failed; the transaction was rolled back.
+    Commit(String),
+}
+
+impl TransactionFailure {
+    /// Index of the statement to blame (`count` for COMMIT) and the error.
+    pub(crate) fn into_indexed(self, count: usize) -> (usize, String) {
+        match self {
+            TransactionFailure::Begin(e) => (0, format!("BEGIN failed: {}", e)),
+            TransactionFailure::Statement { index, error, .. } => (index, error),
+            TransactionFailure::Commit(e) => (count, format!("COMMIT failed: {}", e)),
+        }
+    }
+}
+
+/// BEGIN, `run` for each of `count` statement indexes in order, then COMMIT;
+/// the first failure rolls the transaction back. Returns what the statements
+/// that ran produced, and how the transaction ended. MsSQL runs with
+/// XACT_ABORT ON, switched off again afterwards so the pooled connection
+/// goes back to the pool as it came.
+pub(super) async fn run_in_transaction<T>(
+    conn: &mut SessionConn,
+    count: usize,
+    mut run: impl for<'c> FnMut(&'c mut SessionConn, usize) -> BoxFuture<'c, Result<T, String>>,
+) -> (Vec<T>, Result<(), TransactionFailure>) {
+    let mssql = matches!(conn, SessionConn::MsSQL(_));
+    let (begin, rollback) = if mssql {
+        (
+            "SET XACT_ABORT ON; BEGIN TRANSACTION",
+            "IF @@TRANCOUNT > 0 ROLLBACK TRANSACTION",
+        )
+    } else {
+        (begin_statement(&conn.database_type()), "ROLLBACK")
+    };
+    let mut outputs = Vec::with_capacity(count);
+    let outcome = async {
+        run_simple(conn, begin)
+            .await
+            .map_err(TransactionFailure::Begin)?;
+        for index in 0..count {
+            match run(conn, index).await {
+                Ok(output) => outputs.push(output),
+                Err(error) => {
+                    let rollback = run_simple(conn, rollback).await;
+                    if let Err(e) = &rollback {
+                        warn!("ROLLBACK after failed statement failed: {}", e);
+                    }
+                    return Err(TransactionFailure::Statement { index, error, rollback });
+                }
+            }
+        }
+        if let Err(e) = run_simple(conn, "COMMIT").await {
+            if let Err(rollback) = run_simple(conn, rollback).await {
+                warn!("ROLLBACK after failed COMMIT failed: {}", rollback);
+            }
+            return Err(TransactionFailure::Commit(e));
+        }
+        Ok(())
+    }
+    .await;
+    if mssql && let Err(e) = run_simple(conn, "SET XACT_ABORT OFF").await {
+        warn!("resetting XACT_ABORT failed: {}", e);
+    }
+    (outputs, outcome)
+}
+
+/// Run `statements` on one dedicated connection in a single transaction,
+/// committed only when every statement succeeded. The error carries the
+/// index of the failing statement, or `statements.len()` when COMMIT failed.
+pub(crate) async fn execute_in_transaction(
+    pool: &models::enums::DatabasePool,
+    database_name: Option<&str>,
+    statements: &[String],
+) -> Result<(), (usize, String)> {
+    let mut conn = acquire(pool, database_name).await.map_err(|e| (0, e))?;
+    let (_, outcome) = run_in_transaction(&mut conn, statements.len(), |conn, index| {
+        let sql = statements[index].clone();
+        async move { run_simple(conn, &sql).await }.boxed()
+    })
+    .await;
+    outcome.map_err(|failure| failure.into_indexed(statements.len()))
+}
+
 /// Spawn a session task for the active tab's connection. Returns `None`
 /// when the engine is unsupported, the pool is missing, or no runtime.
 pub fn spawn_session(
@@ -78,13 +199,7 @@ pub fn spawn_session(
         return None;
     }
 
-    let pool = if let Some(p) = tabular.connection_pools.get(&connection_id) {
-        p.clone()
-    } else if let Ok(shared) = tabular.shared_connection_pools.lock() {
-        shared.get(&connection_id).cloned()?
-    } else {
-        return None;
-    };
+    let pool = session_pool(tabular, connection_id)?;
 
     let runtime = tabular.runtime.clone()?;
     let result_sender = tabular.query_result_sender.clone();
@@ -106,6 +221,22 @@ pub fn spawn_session(
     })
 }
 
+/// Pool a dedicated connection is taken from.
+pub(super) fn session_pool(
+    tabular: &Tabular,
+    connection_id: i64,
+) -> Option<models::enums::DatabasePool> {
+    if let Some(p) = tabular.connection_pools.get(&connection_id) {
+        return Some(p.clone());
+    }
+    tabular
+        .shared_connection_pools
+        .lock()
+        .ok()?
+        .get(&connection_id)
+        .cloned()
+}
+
 async fn run_session(
     pool: models::enums::DatabasePool,
     connection_type: models::enums::DatabaseType,
@@ -119,10 +250,10 @@ async fn run_session(
 
     while let Some(command) = rx.recv().await {
         match command {
-            SessionCommand::Execute { job_id, sql } => {
+            SessionCommand::Execute { job_id, sql, inputs } => {
                 // Acquire lazily so connect errors land on a real job id.
                 if conn.is_none() {
-                    match acquire(&pool, &connection_type, database_name.as_deref()).await {
+                    match acquire(&pool, database_name.as_deref()).await {
                         Ok(c) => conn = Some(c),
                         Err(e) => {
                             let _ = result_sender.send(session_message(
@@ -140,12 +271,7 @@ async fn run_session(
                 let started = Instant::now();
 
                 if !tx_open {
-                    let begin = match connection_type {
-                        models::enums::DatabaseType::MySQL => "START TRANSACTION",
-                        models::enums::DatabaseType::MsSQL => "BEGIN TRANSACTION",
-                        _ => "BEGIN",
-                    };
-                    if let Err(e) = run_simple(c, begin).await {
+                    if let Err(e) = run_simple(c, begin_statement(&connection_type)).await {
                         let _ = result_sender.send(session_message(
                             job_id,
                             connection_id,
@@ -158,7 +284,7 @@ async fn run_session(
                     tx_open = true;
                 }
 
-                let outcome = run_query(c, &sql).await;
+                let outcome = run_query(c, &sql, &inputs).await;
                 let _ = result_sender.send(session_message(
                     job_id,
                     connection_id,
@@ -203,11 +329,184 @@ async fn run_session(
     debug!("session task for connection {} ended", connection_id);
 }
 
+/// Run `statements` on one dedicated connection inside a single transaction
+/// on the runtime; the receiver gets the outcome once it committed or rolled
+/// back. Used by the data grid's Save so a batch of cell edits lands
+/// all-or-nothing without blocking the UI thread.
+pub(crate) fn spawn_in_transaction(
+    tabular: &Tabular,
+    connection_id: i64,
+    database_name: Option<String>,
+    statements: Vec<String>,
+) -> Result<std::sync::mpsc::Receiver<Result<(), String>>, String> {
+    let pool = session_pool(tabular, connection_id)
+        .ok_or_else(|| "Connection is not open yet".to_string())?;
+    let runtime = tabular
+        .runtime
+        .clone()
+        .ok_or_else(|| "No async runtime available".to_string())?;
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    runtime.spawn(async move {
+        let total = statements.len();
+        let outcome = match acquire(&pool, database_name.as_deref()).await {
+            Ok(mut conn) => {
+                let (_, outcome) = run_in_transaction(&mut conn, total, |conn, index| {
+                    let sql = statements[index].clone();
+                    async move { run_simple(conn, &sql).await }.boxed()
+                })
+                .await;
+                outcome.map_err(|failure| match failure {
+                    TransactionFailure::Statement { index, error, .. } => format!(
+                        "Statement {} of {} failed, nothing was saved: {}",
+                        index + 1,
+                        total,
+                        error
+                    ),
+                    other => other.into_indexed(total).1,
+                })
+            }
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(outcome);
+    });
+    Ok(rx)
+}
+
+/// Run `statements` (job id, SQL) for "Run in transaction": one dedicated
+/// connection, one transaction, committed only when every statement
+/// succeeded. Results are held back until COMMIT; at the first failure the
+/// transaction is rolled back, the failing statement reports why and the
+/// remaining ones report that they were skipped. `inputs` are bound to the
+/// placeholders of every statement.
+pub(crate) fn spawn_transaction_batch(
+    tabular: &Tabular,
+    connection_id: i64,
+    database_name: Option<String>,
+    statements: Vec<(u64, String)>,
+    inputs: Vec<(String, String)>,
+) -> Result<(), String> {
+    let pool = session_pool(tabular, connection_id)
+        .ok_or_else(|| "Connection is not open yet".to_string())?;
+    let runtime = tabular
+        .runtime
+        .clone()
+        .ok_or_else(|| "No async runtime available".to_string())?;
+    let sender = tabular.query_result_sender.clone();
+
+    runtime.spawn(async move {
+        let messages = run_transaction_batch(
+            pool,
+            connection_id,
+            database_name,
+            statements,
+            inputs,
+        )
+        .await;
+        for message in messages {
+            let _ = sender.send(message);
+        }
+    });
+    Ok(())
+}
+
+async fn run_transaction_batch(
+    pool: models::enums::DatabasePool,
+    connection_id: i64,
+    database_name: Option<String>,
+    statements: Vec<(u64, String)>,
+    inputs: Vec<(String, String)>,
+) -> Vec<QueryResultMessage> {
+    let started = Instant::now();
+    let message = |index: usize, result, started| {
+        let (job_id, sql) = &statements[index];
+        session_message(*job_id, connection_id, sql, result, started)
+    };
+    let mut conn = match acquire(&pool, database_name.as_deref()).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            let reason = format!("Cannot open a connection for the transaction: {}", e);
+            return (0..statements.len())
+                .map(|index| message(index, Err(reason.clone()), started))
+                .collect();
+        }
+    };
+
+    let total = statements.len();
+    let (results, outcome) = run_in_transaction(&mut conn, total, |conn, index| {
+        let sql = statements[index].1.clone();
+        let inputs = inputs.clone();
+        async move {
+            let statement_started = Instant::now();
+            run_query(conn, &sql, &inputs)
+                .await
+                .map(|result| (result, statement_started))
+        }
+        .boxed()
+    })
+    .await;
+    let mut messages: Vec<QueryResultMessage> = results
+        .into_iter()
+        .enumerate()
+        .map(|(index, (result, started))| message(index, Ok(result), started))
+        .collect();
+
+    match outcome {
+        Ok(()) => {}
+        Err(TransactionFailure::Begin(e)) => {
+            let reason = format!("BEGIN failed, nothing was run: {}", e);
+            messages = (0..total)
+                .map(|index| message(index, Err(reason.clone()), started))
+                .collect();
+        }
+        Err(TransactionFailure::Statement {
+            index,
+            error,
+            rollback,
+        }) => {
+            let rolled_back = match rollback {
+                Ok(()) => "the transaction was rolled back".to_string(),
+                Err(rollback) => format!("and ROLLBACK failed too ({})", rollback),
+            };
+            messages.push(message(
+                index,
+                Err(format!(
+                    "Statement {} of {} failed, {}: {}",
+                    index + 1,
+                    total,
+                    rolled_back,
+                    error
+                )),
+                started,
+            ));
+            for skipped in index + 1..total {
+                messages.push(message(
+                    skipped,
+                    Err("Skipped: the transaction was rolled back".to_string()),
+                    started,
+                ));
+            }
+        }
+        Err(TransactionFailure::Commit(e)) => {
+            if let Some(last) = messages.pop() {
+                messages.push(session_message(
+                    last.job_id,
+                    connection_id,
+                    &last.query,
+                    Err(format!("COMMIT failed, the transaction was rolled back: {}", e)),
+                    started,
+                ));
+            }
+        }
+    }
+    messages
+}
+
 async fn finish_tx(
     conn: Option<&mut SessionConn>,
     tx_open: &mut bool,
     verb: &str,
-) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+) -> Result<StatementOutput, String> {
     let Some(c) = conn else {
         *tx_open = false;
         return Err("No open session connection".to_string());
@@ -217,12 +516,11 @@ async fn finish_tx(
     }
     run_simple(c, verb).await?;
     *tx_open = false;
-    Ok((Vec::new(), Vec::new()))
+    Ok((Vec::new(), Vec::new(), None))
 }
 
-async fn acquire(
+pub(super) async fn acquire(
     pool: &models::enums::DatabasePool,
-    connection_type: &models::enums::DatabaseType,
     database_name: Option<&str>,
 ) -> Result<SessionConn, String> {
     match pool {
@@ -258,14 +556,11 @@ async fn acquire(
             }
             Ok(SessionConn::MsSQL(Box::new(conn)))
         }
-        _ => Err(format!(
-            "Transactions are not supported for {:?}",
-            connection_type
-        )),
+        _ => Err("Transactions are not supported for this connection type".to_string()),
     }
 }
 
-async fn run_simple(conn: &mut SessionConn, sql: &str) -> Result<(), String> {
+pub(super) async fn run_simple(conn: &mut SessionConn, sql: &str) -> Result<(), String> {
     match conn {
         SessionConn::MySql(c) => sqlx::query(sqlx::AssertSqlSafe(sql))
             .execute(&mut **c)
@@ -291,13 +586,45 @@ async fn run_simple(conn: &mut SessionConn, sql: &str) -> Result<(), String> {
     }
 }
 
+/// `sql` with `inputs` bound in the native form of the session's engine.
+pub(super) fn bind_statement(
+    conn: &SessionConn,
+    sql: &str,
+    inputs: &[(String, String)],
+) -> query_tools::BoundQuery {
+    let target = match conn {
+        SessionConn::MySql(_) | SessionConn::Sqlite(_) => query_tools::ParamTarget::Positional,
+        SessionConn::Postgres(_) => query_tools::ParamTarget::Numbered,
+        SessionConn::MsSQL(_) => query_tools::ParamTarget::AtNumbered,
+    };
+    if inputs.is_empty() {
+        query_tools::BoundQuery {
+            sql: sql.to_string(),
+            values: Vec::new(),
+        }
+    } else {
+        query_tools::bind_query(sql, inputs, target)
+    }
+}
+
+/// What a statement produced: its headers and rows, and for a statement
+/// without a result set the number of rows it affected.
+pub(super) type StatementOutput = (Vec<String>, Vec<Vec<String>>, Option<u64>);
+
 async fn run_query(
     conn: &mut SessionConn,
     sql: &str,
-) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    inputs: &[(String, String)],
+) -> Result<StatementOutput, String> {
+    if !crate::connection::statement_returns_rows(sql) {
+        let affected = run_execute(conn, sql, inputs).await?;
+        return Ok((Vec::new(), Vec::new(), Some(affected)));
+    }
+    let query_tools::BoundQuery { sql, values } = bind_statement(conn, sql, inputs);
+    let sql = sql.as_str();
     match conn {
         SessionConn::MySql(c) => {
-            let rows = sqlx::query(sqlx::AssertSqlSafe(sql))
+            let rows = super::execute::bind_typed(sqlx::query(sqlx::AssertSqlSafe(sql)), &values)
                 .fetch_all(&mut **c)
                 .await
                 .map_err(|e| e.to_string())?;
@@ -308,10 +635,17 @@ async fn run_query(
             Ok((
                 headers,
                 crate::driver_mysql::convert_mysql_rows_to_table_data(rows),
+                None,
             ))
         }
         SessionConn::Postgres(c) => {
-            let rows = sqlx::query(sqlx::AssertSqlSafe(sql))
+            let sql = if values.is_empty() {
+                sql.to_string()
+            } else {
+                super::execute::cast_postgres_params(c, sql).await
+            };
+            let query = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()));
+            let rows = super::execute::bind_text(query, &values)
                 .fetch_all(&mut **c)
                 .await
                 .map_err(|e| e.to_string())?;
@@ -323,28 +657,14 @@ async fn run_query(
                 .into_iter()
                 .map(|row| {
                     (0..row.len())
-                        .map(|idx| match row.try_get::<Option<String>, _>(idx) {
-                            Ok(Some(v)) => v,
-                            Ok(None) => "NULL".to_string(),
-                            Err(_) => {
-                                if let Ok(val) = row.try_get::<i64, _>(idx) {
-                                    val.to_string()
-                                } else if let Ok(val) = row.try_get::<f64, _>(idx) {
-                                    val.to_string()
-                                } else if let Ok(val) = row.try_get::<bool, _>(idx) {
-                                    val.to_string()
-                                } else {
-                                    "[unsupported]".to_string()
-                                }
-                            }
-                        })
+                        .map(|idx| crate::driver_postgres::pg_cell_to_string(&row, idx))
                         .collect()
                 })
                 .collect();
-            Ok((headers, data))
+            Ok((headers, data, None))
         }
         SessionConn::Sqlite(c) => {
-            let rows = sqlx::query(sqlx::AssertSqlSafe(sql))
+            let rows = super::execute::bind_typed(sqlx::query(sqlx::AssertSqlSafe(sql)), &values)
                 .fetch_all(&mut **c)
                 .await
                 .map_err(|e| e.to_string())?;
@@ -355,30 +675,85 @@ async fn run_query(
             Ok((
                 headers,
                 crate::driver_sqlite::convert_sqlite_rows_to_table_data(rows),
+                None,
             ))
         }
         SessionConn::MsSQL(c) => {
             let client = c
                 .client_mut()
                 .ok_or_else(|| "MsSQL pooled connection unavailable".to_string())?;
-            crate::driver_mssql::run_query(client, sql).await
+            let (headers, rows) = crate::driver_mssql::run_bound_query(client, sql, &values).await?;
+            Ok((headers, rows, None))
         }
     }
 }
 
-fn session_message(
+/// Execute a data-changing statement with `inputs` bound to its
+/// placeholders, returning the rows it affected.
+pub(super) async fn run_execute(
+    conn: &mut SessionConn,
+    sql: &str,
+    inputs: &[(String, String)],
+) -> Result<u64, String> {
+    let bound = bind_statement(conn, sql, inputs);
+    let (sql, values) = (bound.sql.as_str(), &bound.values);
+    match conn {
+        SessionConn::MySql(c) => {
+            super::execute::bind_typed(sqlx::query(sqlx::AssertSqlSafe(sql)), values)
+                .execute(&mut **c)
+                .await
+                .map(|r| r.rows_affected())
+                .map_err(|e| e.to_string())
+        }
+        SessionConn::Postgres(c) => {
+            let sql = if values.is_empty() {
+                sql.to_string()
+            } else {
+                super::execute::cast_postgres_params(c, sql).await
+            };
+            super::execute::bind_text(sqlx::query(sqlx::AssertSqlSafe(sql.as_str())), values)
+                .execute(&mut **c)
+                .await
+                .map(|r| r.rows_affected())
+                .map_err(|e| e.to_string())
+        }
+        SessionConn::Sqlite(c) => {
+            super::execute::bind_typed(sqlx::query(sqlx::AssertSqlSafe(sql)), values)
+                .execute(&mut **c)
+                .await
+                .map(|r| r.rows_affected())
+                .map_err(|e| e.to_string())
+        }
+        SessionConn::MsSQL(c) => {
+            let params = crate::driver_mssql::param_values_to_sql(values);
+            let refs: Vec<&(dyn mssql_client::ToSql + Sync)> = params
+                .iter()
+                .map(|v| v.as_ref() as &(dyn mssql_client::ToSql + Sync))
+                .collect();
+            c.client_mut()
+                .ok_or_else(|| "MsSQL pooled connection unavailable".to_string())?
+                .execute(sql, &refs)
+                .await
+                .map_err(|e| e.to_string())
+        }
+    }
+}
+
+pub(super) fn session_message(
     job_id: u64,
     connection_id: i64,
     query: &str,
-    outcome: Result<(Vec<String>, Vec<Vec<String>>), String>,
+    outcome: Result<StatementOutput, String>,
     started: Instant,
 ) -> QueryResultMessage {
     match outcome {
-        Ok((headers, rows)) => QueryResultMessage {
+        Ok((headers, rows, rows_affected)) => QueryResultMessage {
             job_id,
             connection_id,
             success: true,
-            affected_rows: Some(rows.len()),
+            affected_rows: rows_affected.map(|n| n as usize),
+            produced_result_set: rows_affected.is_none()
+                && (!headers.is_empty() || crate::connection::statement_returns_rows(query)),
             headers,
             rows,
             error: None,
@@ -402,7 +777,121 @@ fn session_message(
             ast_debug_sql: None,
             ast_headers: None,
             affected_rows: None,
+            produced_result_set: false,
             column_metadata: None,
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transaction_batch_rolls_back_on_failure_and_commits_otherwise() {
+        let path = std::env::temp_dir().join(format!("tabular-tx-batch-{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let pool = sqlx::sqlite::SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect_with(
+                    sqlx::sqlite::SqliteConnectOptions::new()
+                        .filename(&path)
+                        .create_if_missing(true),
+                )
+                .await
+                .unwrap();
+            sqlx::query("CREATE TABLE items (id INTEGER)").execute(&pool).await.unwrap();
+            let pool = models::enums::DatabasePool::SQLite(std::sync::Arc::new(pool));
+            let run = |statements: &[&str]| {
+                run_transaction_batch(
+                    pool.clone(),
+                    1,
+                    None,
+                    statements
+                        .iter()
+                        .enumerate()
+                        .map(|(i, sql)| (i as u64, sql.to_string()))
+                        .collect(),
+                    Vec::new(),
+                )
+            };
+            let count = || async {
+                let models::enums::DatabasePool::SQLite(p) = &pool else { unreachable!() };
+                sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM items")
+                    .fetch_one(p.as_ref())
+                    .await
+                    .unwrap()
+            };
+
+            let failed = run(&[
+                "INSERT INTO items VALUES (1)",
+                "INSERT INTO missing VALUES (2)",
+                "INSERT INTO items VALUES (3)",
+            ])
+            .await;
+            assert!(failed[0].success);
+            let reason = failed[1].error.clone().unwrap();
+            assert!(reason.starts_with("Statement 2 of 3 failed, the transaction was rolled back"), "{reason}");
+            assert!(failed[2].error.as_deref().is_some_and(|e| e.starts_with("Skipped")));
+            assert_eq!(count().await, 0);
+
+            let committed = run(&["INSERT INTO items VALUES (1)", "INSERT INTO items VALUES (2)"]).await;
+            assert!(committed.iter().all(|m| m.success));
+            // The driver's count, not the (empty) row count
+            assert_eq!(committed[0].affected_rows, Some(1));
+            assert!(!committed[0].produced_result_set);
+            assert_eq!(count().await, 2);
+
+            // Parameter values are bound, never spliced into the SQL text
+            let injection = "\\' OR 1=1 -- ";
+            let bound = run_transaction_batch(
+                pool.clone(),
+                1,
+                None,
+                vec![
+                    (0, "CREATE TABLE notes (body TEXT)".to_string()),
+                    (1, "INSERT INTO notes VALUES (:body)".to_string()),
+                    (2, "SELECT body FROM notes WHERE body = :body".to_string()),
+                ],
+                vec![(":body".to_string(), injection.to_string())],
+            )
+            .await;
+            assert!(bound.iter().all(|m| m.success), "{:?}", bound[1].error);
+            assert_eq!(bound[2].rows, vec![vec![injection.to_string()]]);
+        });
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn execute_in_transaction_names_the_failing_statement() {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        rt.block_on(async {
+            let pool = sqlx::sqlite::SqlitePoolOptions::new()
+                .max_connections(1)
+                .connect("sqlite::memory:")
+                .await
+                .unwrap();
+            let pool = models::enums::DatabasePool::SQLite(std::sync::Arc::new(pool));
+            let statements = |sql: &[&str]| sql.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+            execute_in_transaction(&pool, None, &statements(&["CREATE TABLE t (id INTEGER)"]))
+                .await
+                .unwrap();
+            let (index, _) = execute_in_transaction(
+                &pool,
+                None,
+                &statements(&["INSERT INTO t VALUES (1)", "INSERT INTO nowhere VALUES (2)"]),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(index, 1);
+            let models::enums::DatabasePool::SQLite(p) = &pool else { unreachable!() };
+            let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM t")
+                .fetch_one(p.as_ref())
+                .await
+                .unwrap();
+            assert_eq!(count, 0);
+        });
+    }
+}
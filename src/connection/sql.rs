@@ -457,6 +457,80 @@ fn clean_identifier(id: &str) -> String {
         .to_string()
 }
 
+/// Whether `statement` can produce a result set. Only statements known to
+/// return none (DML without RETURNING/OUTPUT, DDL, SET, transaction control)
+/// answer false; anything unrecognized is assumed to return rows.
+pub(crate) fn statement_returns_rows(statement: &str) -> bool {
+    let rest = skip_leading_comments(statement);
+    let keyword = rest
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or("")
+        .to_uppercase();
+    let upper = rest.to_uppercase();
+    match keyword.as_str() {
+        "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "REPLACE" => {
+            upper.contains("RETURNING") || upper.contains("OUTPUT")
+        }
+        "CREATE" | "ALTER" | "DROP" | "TRUNCATE" | "RENAME" | "COMMENT" | "GRANT" | "REVOKE"
+        | "SET" | "RESET" | "BEGIN" | "START" | "COMMIT" | "ROLLBACK" | "SAVEPOINT"
+        | "RELEASE" | "USE" | "LOCK" | "UNLOCK" | "DISCARD" | "REINDEX" | "VACUUM"
+        | "LISTEN" | "UNLISTEN" | "NOTIFY" => false,
+        _ => true,
+    }
+}
+
+/// Whether `statement` opens or ends a transaction itself (BEGIN, START
+/// TRANSACTION, COMMIT, ROLLBACK without TO). A T-SQL `BEGIN ... END` block
+/// or `BEGIN TRY` does not count.
+pub(crate) fn is_transaction_control(statement: &str) -> bool {
+    let words: Vec<String> = skip_leading_comments(statement)
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|w| !w.is_empty())
+        .take(2)
+        .map(|w| w.to_uppercase())
+        .collect();
+    let second = words.get(1).map(String::as_str);
+    match words.first().map(String::as_str) {
+        Some("BEGIN") => matches!(
+            second,
+            None | Some("TRANSACTION" | "TRAN" | "WORK" | "DEFERRED" | "IMMEDIATE" | "EXCLUSIVE" | "ISOLATION")
+        ),
+        Some("START") => second == Some("TRANSACTION"),
+        Some("COMMIT") => true,
+        Some("ROLLBACK") => second != Some("TO"),
+        _ => false,
+    }
+}
+
+/// Whether MySQL commits the open transaction before running `statement`
+/// (DDL, account changes, table locks), so it cannot be rolled back with the
+/// rest of a batch.
+pub(crate) fn commits_implicitly_on_mysql(statement: &str) -> bool {
+    let first = skip_leading_comments(statement)
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or("")
+        .to_uppercase();
+    matches!(
+        first.as_str(),
+        "CREATE" | "ALTER" | "DROP" | "TRUNCATE" | "RENAME" | "GRANT" | "REVOKE" | "LOCK" | "UNLOCK"
+    )
+}
+
+fn skip_leading_comments(statement: &str) -> &str {
+    let mut rest = statement.trim_start();
+    loop {
+        if let Some(r) = rest.strip_prefix("--") {
+            rest = r.split_once('\n').map(|(_, r)| r).unwrap_or("").trim_start();
+        } else if let Some(r) = rest.strip_prefix("/*") {
+            rest = r.split_once("*/").map(|(_, r)| r).unwrap_or("").trim_start();
+        } else {
+            return rest;
+        }
+    }
+}
+
 /// Add an auto LIMIT/TOP clause to a SELECT query if one is not already present.
 pub fn add_auto_limit_if_needed(query: &str, db_type: &models::enums::DatabaseType) -> String {
     let trimmed_query = query.trim();
@@ -574,6 +648,39 @@ pub fn split_sql_statements(sql: &str, hash_is_comment: bool) -> Vec<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn detects_statements_without_result_sets() {
+        assert!(!statement_returns_rows("UPDATE users SET name = 'x'"));
+        assert!(!statement_returns_rows("-- rename\ncreate table t (id int)"));
+        assert!(!statement_returns_rows("/* tz */ SET TIME ZONE 'UTC'"));
+        assert!(statement_returns_rows("INSERT INTO t (a) VALUES (1) RETURNING id"));
+        assert!(statement_returns_rows("SELECT * FROM t WHERE 1 = 0"));
+        assert!(statement_returns_rows("WITH x AS (SELECT 1) SELECT * FROM x"));
+        assert!(statement_returns_rows("PRAGMA table_info(t)"));
+    }
+
+    #[test]
+    fn detects_explicit_transaction_control() {
+        assert!(is_transaction_control("BEGIN"));
+        assert!(is_transaction_control("-- go\nstart transaction"));
+        assert!(is_transaction_control("BEGIN TRAN"));
+        assert!(is_transaction_control("COMMIT WORK"));
+        assert!(is_transaction_control("ROLLBACK"));
+        assert!(!is_transaction_control("ROLLBACK TO SAVEPOINT a"));
+        assert!(!is_transaction_control("BEGIN TRY SELECT 1 END TRY"));
+        assert!(!is_transaction_control("START SLAVE"));
+        assert!(!is_transaction_control("SELECT 'BEGIN'"));
+    }
+
+    #[test]
+    fn detects_mysql_implicit_commits() {
+        assert!(commits_implicitly_on_mysql("CREATE TABLE t (id INT)"));
+        assert!(commits_implicitly_on_mysql("/* v2 */ alter table t add c int"));
+        assert!(commits_implicitly_on_mysql("TRUNCATE t"));
+        assert!(!commits_implicitly_on_mysql("INSERT INTO t VALUES (1)"));
+        assert!(!commits_implicitly_on_mysql("UPDATE t SET note = 'DROP'"));
+    }
+
     #[test]
     fn simple_select_allows_auto_pagination() {
         assert!(should_enable_auto_pagination("SELECT * FROM users"));
@@ -0,0 +1,216 @@
+//! Streamed SELECT results: the last statement of a job is read with sqlx's
+//! row stream instead of `fetch_all`, and rows reach the grid in batches while
+//! the query is still running. Fetching pauses once `ceiling` rows are held
+//! so a huge result cannot exhaust memory; the user then fetches more or
+//! stops there. The finished job still reports every row it received.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+use std::time::{Duration, Instant};
+
+use futures_util::{Stream, StreamExt};
+
+/// Rows per batch sent to the grid.
+const BATCH_ROWS: usize = 1000;
+
+/// A partial batch is still sent after this long, so slow queries show rows.
+const FLUSH_EVERY: Duration = Duration::from_millis(150);
+
+pub enum StreamEvent {
+    /// More rows of `job_id`; `headers` are the result's columns.
+    Rows {
+        job_id: u64,
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+    },
+    /// `received` rows reached the ceiling; fetching waits for the user.
+    Paused { job_id: u64, received: usize },
+}
+
+/// Shared between the fetching task and the UI.
+#[derive(Debug)]
+pub struct StreamControl {
+    ceiling: AtomicUsize,
+    stop: AtomicBool,
+    resume: tokio::sync::Notify,
+}
+
+impl StreamControl {
+    pub(crate) fn new(ceiling: usize) -> Self {
+        Self {
+            ceiling: AtomicUsize::new(ceiling.max(1)),
+            stop: AtomicBool::new(false),
+            resume: tokio::sync::Notify::new(),
+        }
+    }
+
+    pub(crate) fn ceiling(&self) -> usize {
+        self.ceiling.load(Ordering::Relaxed)
+    }
+
+    /// Let a paused stream fetch `rows` more.
+    pub(crate) fn fetch_more(&self, rows: usize) {
+        self.ceiling.fetch_add(rows.max(1), Ordering::Relaxed);
+        self.resume.notify_one();
+    }
+
+    /// End the stream, keeping the rows received so far.
+    pub(crate) fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.resume.notify_one();
+    }
+
+    pub(crate) fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+}
+
+/// Where a streamed job reports its batches.
+#[derive(Clone, Debug)]
+pub struct QueryStream {
+    pub job_id: u64,
+    pub sender: Sender<StreamEvent>,
+    pub control: Arc<StreamControl>,
+}
+
+/// UI state of a streamed job.
+#[derive(Debug)]
+pub struct StreamProgress {
+    pub control: Arc<StreamControl>,
+    pub received: usize,
+    pub paused: bool,
+}
+
+/// Drain `rows`, sending batches to `stream` and pausing at its ceiling.
+/// Only the wait for the first row is bounded by `first_row_timeout`. Returns
+/// the first row (for column metadata) and every row converted by `to_cells`.
+pub(crate) async fn collect_streamed<R, S>(
+    mut rows: S,
+    stream: &QueryStream,
+    first_row_timeout: Duration,
+    headers_of: impl Fn(&R) -> Vec<String>,
+    to_cells: impl Fn(&R) -> Vec<String>,
+) -> Result<Result<(Option<R>, Vec<Vec<String>>), sqlx::Error>, tokio::time::error::Elapsed>
+where
+    S: Stream<Item = Result<R, sqlx::Error>> + Unpin,
+{
+    let control = &stream.control;
+    let mut first_row: Option<R> = None;
+    let mut headers = Vec::new();
+    let mut all: Vec<Vec<String>> = Vec::new();
+    let mut sent = 0;
+    let mut last_flush = Instant::now();
+
+    let flush = |all: &[Vec<String>], sent: &mut usize, headers: &[String]| {
+        if *sent < all.len() {
+            let _ = stream.sender.send(StreamEvent::Rows {
+                job_id: stream.job_id,
+                headers: headers.to_vec(),
+                rows: all[*sent..].to_vec(),
+            });
+            *sent = all.len();
+        }
+    };
+
+    loop {
+        let next = if first_row.is_none() {
+            tokio::time::timeout(first_row_timeout, rows.next()).await?
+        } else {
+            rows.next().await
+        };
+        let row = match next {
+            None => break,
+            Some(Err(e)) => return Ok(Err(e)),
+            Some(Ok(row)) => row,
+        };
+        all.push(to_cells(&row));
+        if first_row.is_none() {
+            headers = headers_of(&row);
+            first_row = Some(row);
+        }
+        if control.stopped() {
+            break;
+        }
+        if all.len() - sent >= BATCH_ROWS || last_flush.elapsed() >= FLUSH_EVERY {
+            flush(&all, &mut sent, &headers);
+            last_flush = Instant::now();
+        }
+        if all.len() >= control.ceiling() {
+            flush(&all, &mut sent, &headers);
+            let _ = stream.sender.send(StreamEvent::Paused {
+                job_id: stream.job_id,
+                received: all.len(),
+            });
+            while all.len() >= control.ceiling() && !control.stopped() {
+                control.resume.notified().await;
+            }
+            if control.stopped() {
+                break;
+            }
+            last_flush = Instant::now();
+        }
+    }
+    flush(&all, &mut sent, &headers);
+    Ok(Ok((first_row, all)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaming_pauses_at_the_ceiling_until_more_is_requested() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let stream = QueryStream {
+            job_id: 7,
+            sender,
+            control: Arc::new(StreamControl::new(3)),
+        };
+        let rows = futures_util::stream::iter((1..=5).map(Ok::<i32, sqlx::Error>));
+
+        let (first, all) = runtime.block_on(async {
+            let control = stream.control.clone();
+            let task = tokio::spawn(async move {
+                collect_streamed(
+                    rows,
+                    &stream,
+                    Duration::from_secs(5),
+                    |_| vec!["n".to_string()],
+                    |n| vec![n.to_string()],
+                )
+                .await
+            });
+            let mut received = Vec::new();
+            loop {
+                match receiver.try_recv() {
+                    Ok(StreamEvent::Rows { headers, rows, .. }) => {
+                        assert_eq!(headers, ["n"]);
+                        received.extend(rows);
+                    }
+                    Ok(StreamEvent::Paused { job_id, received: n }) => {
+                        assert_eq!((job_id, n), (7, 3));
+                        assert_eq!(received.len(), 3);
+                        control.fetch_more(10);
+                    }
+                    Err(_) if task.is_finished() => break,
+                    Err(_) => tokio::task::yield_now().await,
+                }
+            }
+            while let Ok(event) = receiver.try_recv() {
+                if let StreamEvent::Rows { rows, .. } = event {
+                    received.extend(rows);
+                }
+            }
+            assert_eq!(received.len(), 5);
+            task.await.unwrap().unwrap().unwrap()
+        });
+        assert_eq!(first, Some(1));
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[4], ["5"]);
+    }
+}
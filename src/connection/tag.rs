@@ -0,0 +1,213 @@
+//! Connection tag: an optional color and short label per connection (say a red
+//! "PROD"), shown on the query tabs and as a strip along the editor and results
+//! so it is always clear where a query will run.
+
+use eframe::egui;
+
+use crate::{models, window_egui};
+
+/// Longest label the connection dialog accepts.
+pub(crate) const TAG_LABEL_MAX_CHARS: usize = 12;
+
+/// Colors offered in the connection dialog, as stored.
+pub(crate) const TAG_COLORS: &[(&str, &str)] = &[
+    ("Red", "#D64545"),
+    ("Orange", "#E08A2E"),
+    ("Yellow", "#D4B72C"),
+    ("Green", "#3C9D5D"),
+    ("Blue", "#3B7DD8"),
+    ("Purple", "#8E5BD0"),
+    ("Gray", "#7A7F87"),
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ConnectionTag {
+    pub label: String,
+    pub color: Option<egui::Color32>,
+}
+
+/// `#RRGGBB` (the `#` is optional) as a color.
+pub(crate) fn parse_tag_color(s: &str) -> Option<egui::Color32> {
+    let hex = s.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let v = u32::from_str_radix(hex, 16).ok()?;
+    Some(egui::Color32::from_rgb(
+        (v >> 16) as u8,
+        (v >> 8) as u8,
+        v as u8,
+    ))
+}
+
+pub(crate) fn format_tag_color(color: egui::Color32) -> String {
+    format!("#{:02X}{:02X}{:02X}", color.r(), color.g(), color.b())
+}
+
+/// Tag of `connection`; `None` when it has neither a label nor a color.
+pub(crate) fn connection_tag(
+    connection: &models::structs::ConnectionConfig,
+) -> Option<ConnectionTag> {
+    let label = connection.label.trim().to_string();
+    let color = parse_tag_color(&connection.color);
+    (!label.is_empty() || color.is_some()).then_some(ConnectionTag { label, color })
+}
+
+pub(crate) fn tag_for_connection(
+    tabular: &window_egui::Tabular,
+    connection_id: i64,
+) -> Option<ConnectionTag> {
+    tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(connection_id))
+        .and_then(connection_tag)
+}
+
+/// Text color readable on `bg`.
+fn text_on(bg: egui::Color32) -> egui::Color32 {
+    let luma = 0.299 * bg.r() as f32 + 0.587 * bg.g() as f32 + 0.114 * bg.b() as f32;
+    if luma > 150.0 {
+        egui::Color32::from_rgb(20, 20, 20)
+    } else {
+        egui::Color32::WHITE
+    }
+}
+
+/// Banner above the editor naming the active tab's connection in its color.
+/// Returns the color so the caller can continue it along the results.
+pub(crate) fn render_connection_banner(
+    tabular: &window_egui::Tabular,
+    ui: &mut egui::Ui,
+) -> Option<egui::Color32> {
+    let tab = tabular.query_tabs.get(tabular.active_tab_index)?;
+    let connection_id = tab.connection_id?;
+    let tag = tag_for_connection(tabular, connection_id)?;
+    let name = tabular
+        .get_connection_name(connection_id)
+        .unwrap_or_default();
+    let fill = tag
+        .color
+        .unwrap_or(ui.visuals().widgets.inactive.weak_bg_fill);
+    let mut text = if tag.label.is_empty() {
+        name
+    } else {
+        format!("{}  ·  {}", tag.label, name)
+    };
+    if let Some(db) = tab.database_name.as_deref().filter(|d| !d.is_empty()) {
+        text.push_str(&format!(" / {}", db));
+    }
+    let (rect, resp) =
+        ui.allocate_exact_size(egui::vec2(ui.available_width(), 18.0), egui::Sense::hover());
+    ui.painter().rect_filled(rect, 0.0, fill);
+    ui.painter().text(
+        rect.left_center() + egui::vec2(8.0, 0.0),
+        egui::Align2::LEFT_CENTER,
+        text,
+        egui::FontId::proportional(11.0),
+        if tag.color.is_some() {
+            text_on(fill)
+        } else {
+            ui.visuals().strong_text_color()
+        },
+    );
+    resp.on_hover_text("Queries in this tab run on this connection");
+    tag.color
+}
+
+/// Strip along the left edge of the editor and results below the banner.
+pub(crate) fn paint_connection_edge(ui: &egui::Ui, top: f32, color: egui::Color32) {
+    let bottom = ui.min_rect().bottom();
+    if bottom <= top {
+        return;
+    }
+    let left = ui.min_rect().left();
+    let rect = egui::Rect::from_min_max(egui::pos2(left, top), egui::pos2(left + 3.0, bottom));
+    ui.painter().rect_filled(rect, 0.0, color);
+}
+
+/// Label and color rows of the connection dialog (inside its grid).
+pub(crate) fn render_tag_fields(
+    ui: &mut egui::Ui,
+    connection: &mut models::structs::ConnectionConfig,
+) {
+    ui.label("Label:");
+    ui.add(
+        egui::TextEdit::singleline(&mut connection.label)
+            .hint_text("e.g. PROD")
+            .char_limit(TAG_LABEL_MAX_CHARS)
+            .desired_width(120.0),
+    )
+    .on_hover_text("Shown on the tabs and above the editor of this connection");
+    ui.end_row();
+
+    ui.label("Color:");
+    ui.horizontal(|ui| {
+        let current = parse_tag_color(&connection.color);
+        if ui.selectable_label(current.is_none(), "None").clicked() {
+            connection.color.clear();
+        }
+        for (name, hex) in TAG_COLORS {
+            let Some(color) = parse_tag_color(hex) else {
+                continue;
+            };
+            let selected = current == Some(color);
+            let (rect, resp) = ui.allocate_exact_size(egui::vec2(18.0, 18.0), egui::Sense::click());
+            ui.painter().rect_filled(rect, 3.0, color);
+            if selected || resp.hovered() {
+                ui.painter().rect_stroke(
+                    rect,
+                    3.0,
+                    egui::Stroke::new(2.0, ui.visuals().strong_text_color()),
+                    egui::StrokeKind::Outside,
+                );
+            }
+            if resp.on_hover_text(*name).clicked() {
+                connection.color = hex.to_string();
+            }
+        }
+        let mut custom = current.unwrap_or(egui::Color32::GRAY);
+        if ui
+            .color_edit_button_srgba(&mut custom)
+            .on_hover_text("Custom color")
+            .changed()
+        {
+            connection.color = format_tag_color(custom);
+        }
+    });
+    ui.end_row();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_colors_round_trip_and_empty_tags_are_none() {
+        assert_eq!(
+            parse_tag_color("#d64545"),
+            Some(egui::Color32::from_rgb(0xD6, 0x45, 0x45))
+        );
+        assert_eq!(parse_tag_color("3B7DD8"), parse_tag_color("#3B7DD8"));
+        assert_eq!(parse_tag_color(""), None);
+        assert_eq!(parse_tag_color("#12345"), None);
+        assert_eq!(parse_tag_color("#GG0000"), None);
+        for (_, hex) in TAG_COLORS {
+            assert_eq!(format_tag_color(parse_tag_color(hex).unwrap()), *hex);
+        }
+
+        let mut connection = models::structs::ConnectionConfig::default();
+        assert_eq!(connection_tag(&connection), None);
+        connection.label = " PROD ".to_string();
+        assert_eq!(
+            connection_tag(&connection),
+            Some(ConnectionTag {
+                label: "PROD".to_string(),
+                color: None
+            })
+        );
+        connection.label.clear();
+        connection.color = "#D64545".to_string();
+        assert!(connection_tag(&connection).is_some_and(|t| t.color.is_some()));
+    }
+}
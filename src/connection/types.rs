@@ -7,6 +7,8 @@ pub struct QueryExecutionOptions {
     pub connection: models::structs::ConnectionConfig,
     pub query: String,
     pub selected_database: Option<String>,
+    /// PostgreSQL search_path for the job's session.
+    pub selected_schema: Option<String>,
     pub use_server_pagination: bool,
     pub current_page: usize,
     pub page_size: usize,
@@ -14,6 +16,11 @@ pub struct QueryExecutionOptions {
     pub dba_special_mode: Option<models::enums::DBASpecialMode>,
     pub save_to_history: bool,
     pub ast_enabled: bool,
+    /// Stream the last statement's rows to the grid as they arrive.
+    pub stream: Option<super::stream::QueryStream>,
+    /// Values bound to the placeholders of `query`, already rewritten into
+    /// the driver's native form.
+    pub params: Vec<crate::query_tools::ParamValue>,
 }
 
 #[derive(Clone)]
@@ -47,6 +54,9 @@ pub struct QueryResultMessage {
     pub ast_debug_sql: Option<String>,
     pub ast_headers: Option<Vec<String>>,
     pub affected_rows: Option<usize>, // Number of affected rows for INSERT/UPDATE/DELETE
+    /// False when the last statement returned no result set at all (DML,
+    /// DDL, SET), as opposed to a result set with zero rows.
+    pub produced_result_set: bool,
     pub column_metadata: Option<Vec<models::structs::ColumnMetadata>>,
 }
 
@@ -57,6 +67,9 @@ pub struct QueryJobOutput {
     pub ast_debug_sql: Option<String>,
     pub ast_headers: Option<Vec<String>>,
     pub column_metadata: Option<Vec<models::structs::ColumnMetadata>>,
+    /// Set when the last statement returned no result set, to the number of
+    /// rows it affected.
+    pub rows_affected: Option<u64>,
 }
 
 #[derive(Debug)]
@@ -0,0 +1,440 @@
+//! CSV reading for imports: delimiter and header sniffing, column type
+//! inference, chunked record reading, and the INSERT statements built from
+//! records. Quoted fields and embedded newlines are handled by the `csv`
+//! crate; nothing here loads a whole file into memory.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+
+use crate::models::enums::DatabaseType;
+
+/// Rows read for the dialog preview and for type inference.
+pub const PREVIEW_ROWS: usize = 200;
+/// Bytes read from the start of a file to guess its layout.
+const SNIFF_BYTES: u64 = 64 * 1024;
+const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+
+/// Column type inferred from CSV values, used for new tables and to render
+/// typed literals.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColumnKind {
+    Integer,
+    Decimal,
+    Boolean,
+    Date,
+    DateTime,
+    #[default]
+    Text,
+}
+
+impl ColumnKind {
+    /// Narrowest first; inference picks the first kind accepting every value.
+    pub const ALL: [ColumnKind; 6] = [
+        ColumnKind::Integer,
+        ColumnKind::Decimal,
+        ColumnKind::Boolean,
+        ColumnKind::Date,
+        ColumnKind::DateTime,
+        ColumnKind::Text,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ColumnKind::Integer => "Integer",
+            ColumnKind::Decimal => "Decimal",
+            ColumnKind::Boolean => "Boolean",
+            ColumnKind::Date => "Date",
+            ColumnKind::DateTime => "Date & time",
+            ColumnKind::Text => "Text",
+        }
+    }
+
+    fn accepts(self, value: &str) -> bool {
+        let value = value.trim();
+        match self {
+            ColumnKind::Integer => value.parse::<i64>().is_ok(),
+            ColumnKind::Decimal => {
+                value
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+                    && value.parse::<f64>().is_ok_and(f64::is_finite)
+            }
+            ColumnKind::Boolean => matches!(
+                value.to_ascii_lowercase().as_str(),
+                "true" | "false" | "t" | "f" | "yes" | "no"
+            ),
+            ColumnKind::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+            ColumnKind::DateTime => {
+                NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f").is_ok()
+                    || NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f").is_ok()
+                    || DateTime::parse_from_rfc3339(value).is_ok()
+            }
+            ColumnKind::Text => true,
+        }
+    }
+
+    /// Kind of a column from its sample values, ignoring NULLs. All-NULL
+    /// columns are text.
+    pub fn infer<'a>(values: impl Iterator<Item = &'a str> + Clone, null_value: &str) -> Self {
+        let mut present = values.filter(|v| !is_null(v, null_value)).peekable();
+        if present.peek().is_none() {
+            return ColumnKind::Text;
+        }
+        ColumnKind::ALL
+            .into_iter()
+            .find(|kind| present.clone().all(|v| kind.accepts(v)))
+            .unwrap_or(ColumnKind::Text)
+    }
+
+    pub fn sql_type(self, db_type: &DatabaseType) -> &'static str {
+        use DatabaseType::*;
+        match (self, db_type) {
+            (ColumnKind::Integer, SQLite) => "INTEGER",
+            (ColumnKind::Integer, _) => "BIGINT",
+            (ColumnKind::Decimal, MySQL) => "DOUBLE",
+            (ColumnKind::Decimal, PostgreSQL) => "DOUBLE PRECISION",
+            (ColumnKind::Decimal, MsSQL) => "FLOAT",
+            (ColumnKind::Decimal, _) => "REAL",
+            (ColumnKind::Boolean, MsSQL) => "BIT",
+            (ColumnKind::Boolean, SQLite) => "INTEGER",
+            (ColumnKind::Boolean, _) => "BOOLEAN",
+            (ColumnKind::Date, SQLite) => "TEXT",
+            (ColumnKind::Date, _) => "DATE",
+            (ColumnKind::DateTime, MySQL) => "DATETIME",
+            (ColumnKind::DateTime, PostgreSQL) => "TIMESTAMP",
+            (ColumnKind::DateTime, MsSQL) => "DATETIME2",
+            (ColumnKind::DateTime, _) => "TEXT",
+            (ColumnKind::Text, MsSQL) => "NVARCHAR(MAX)",
+            (ColumnKind::Text, _) => "TEXT",
+        }
+    }
+}
+
+/// `value` is the NULL marker; with an empty marker, empty fields are NULL.
+pub fn is_null(value: &str, null_value: &str) -> bool {
+    value == null_value || (null_value.is_empty() && value.is_empty())
+}
+
+fn sample_records(sample: &str, delimiter: char, limit: usize) -> Vec<Vec<String>> {
+    csv::ReaderBuilder::new()
+        .delimiter(delimiter as u8)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(sample.as_bytes())
+        .records()
+        .take(limit)
+        .map_while(Result::ok)
+        .map(|record| record.iter().map(str::to_string).collect())
+        .collect()
+}
+
+/// The candidate delimiter that splits the sample into the most rows of the
+/// same (and widest) field count. Falls back to a comma.
+pub fn detect_delimiter(sample: &str) -> char {
+    DELIMITERS
+        .into_iter()
+        .filter_map(|delimiter| {
+            let counts: Vec<usize> = sample_records(sample, delimiter, 20)
+                .iter()
+                .map(Vec::len)
+                .collect();
+            let first = *counts.first()?;
+            let consistent = counts.iter().filter(|&&count| count == first).count();
+            (first > 1).then_some(((consistent, first), delimiter))
+        })
+        .max_by_key(|(score, _)| *score)
+        .map_or(',', |(_, delimiter)| delimiter)
+}
+
+/// Whether the first of `rows` looks like column names: non-empty, untyped
+/// cells above a typed column, or above values that never repeat them.
+pub fn detect_header(rows: &[Vec<String>]) -> bool {
+    let Some((first, rest)) = rows.split_first() else {
+        return true;
+    };
+    let untyped = |cell: &String| ColumnKind::infer(std::iter::once(cell.as_str()), "") == ColumnKind::Text;
+    if first.iter().any(|cell| cell.trim().is_empty() || !untyped(cell)) {
+        return false;
+    }
+    if rest.is_empty() {
+        return true;
+    }
+    let typed_column = (0..first.len()).any(|i| {
+        let values = rest.iter().filter_map(|row| row.get(i)).map(String::as_str);
+        ColumnKind::infer(values, "") != ColumnKind::Text
+    });
+    typed_column || first.iter().all(|cell| rest.iter().all(|row| !row.contains(cell)))
+}
+
+/// Guess the delimiter and whether there is a header row from the start of
+/// the file.
+pub fn sniff(path: &Path) -> Result<(char, bool), String> {
+    let mut bytes = Vec::new();
+    File::open(path)
+        .and_then(|file| file.take(SNIFF_BYTES).read_to_end(&mut bytes))
+        .map_err(|e| e.to_string())?;
+    let mut sample = String::from_utf8_lossy(&bytes).into_owned();
+    // Drop a line cut off by the sample size.
+    if bytes.len() as u64 == SNIFF_BYTES
+        && let Some(end) = sample.rfind('\n')
+    {
+        sample.truncate(end);
+    }
+    let delimiter = detect_delimiter(&sample);
+    let rows = sample_records(&sample, delimiter, 50);
+    Ok((delimiter, detect_header(&rows)))
+}
+
+/// One data record and the line it starts on (1-based), for error reports.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvRecord {
+    pub line: u64,
+    pub fields: Vec<String>,
+}
+
+/// Reads a CSV file a chunk of records at a time.
+pub struct CsvChunks {
+    reader: csv::Reader<File>,
+    headers: Vec<String>,
+    total_bytes: u64,
+}
+
+impl CsvChunks {
+    pub fn open(path: &Path, delimiter: char, has_header_row: bool) -> Result<Self, String> {
+        let total_bytes = std::fs::metadata(path).map_err(|e| e.to_string())?.len();
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter as u8)
+            .has_headers(has_header_row)
+            .flexible(true)
+            .from_path(path)
+            .map_err(|e| e.to_string())?;
+        let headers = if has_header_row {
+            reader
+                .headers()
+                .map_err(|e| e.to_string())?
+                .iter()
+                .map(str::to_string)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            reader,
+            headers,
+            total_bytes,
+        })
+    }
+
+    /// Header names; empty without a header row.
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Up to `limit` records; empty at the end of the file.
+    pub fn next_chunk(&mut self, limit: usize) -> Result<Vec<CsvRecord>, String> {
+        let mut chunk = Vec::new();
+        let mut record = csv::StringRecord::new();
+        while chunk.len() < limit {
+            let line = self.reader.position().line() + 1;
+            match self.reader.read_record(&mut record) {
+                Ok(true) => chunk.push(CsvRecord {
+                    line: record.position().map_or(line, |p| p.line()),
+                    fields: record.iter().map(str::to_string).collect(),
+                }),
+                Ok(false) => break,
+                Err(e) => return Err(format!("Line {}: {}", line, e)),
+            }
+        }
+        Ok(chunk)
+    }
+
+    /// Share of the file read so far, 0.0–1.0.
+    pub fn fraction_read(&self) -> f32 {
+        if self.total_bytes == 0 {
+            return 1.0;
+        }
+        (self.reader.position().byte() as f64 / self.total_bytes as f64).min(1.0) as f32
+    }
+}
+
+/// Header names (empty without a header row) and up to `limit` rows.
+pub fn read_preview(
+    path: &Path,
+    delimiter: char,
+    has_header_row: bool,
+    limit: usize,
+) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    let mut chunks = CsvChunks::open(path, delimiter, has_header_row)?;
+    let rows = chunks
+        .next_chunk(limit)?
+        .into_iter()
+        .map(|record| record.fields)
+        .collect();
+    Ok((chunks.headers, rows))
+}
+
+pub fn quote_ident(name: &str, db_type: &DatabaseType) -> String {
+    match db_type {
+        DatabaseType::MySQL => format!("`{}`", name.replace('`', "``")),
+        DatabaseType::MsSQL => format!("[{}]", name.trim_matches(['[', ']']).replace(']', "]]")),
+        _ => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+/// `table` qualified by the database (MySQL, MsSQL) or schema (PostgreSQL)
+/// it was opened from.
+pub fn qualified_table(table: &str, database: Option<&str>, db_type: &DatabaseType) -> String {
+    match (db_type, database.filter(|d| !d.is_empty())) {
+        (DatabaseType::MySQL | DatabaseType::PostgreSQL, Some(db)) => {
+            format!("{}.{}", quote_ident(db, db_type), quote_ident(table, db_type))
+        }
+        (DatabaseType::MsSQL, Some(db)) => {
+            format!("{}.dbo.{}", quote_ident(db, db_type), quote_ident(table, db_type))
+        }
+        _ => quote_ident(table, db_type),
+    }
+}
+
+/// SQL literal for one field. Numbers and booleans are written unquoted
+/// when `kind` says so and the value parses; everything else is a string.
+pub fn sql_literal(
+    value: &str,
+    kind: Option<ColumnKind>,
+    null_value: &str,
+    db_type: &DatabaseType,
+) -> String {
+    if is_null(value, null_value) {
+        return "NULL".to_string();
+    }
+    match kind {
+        Some(kind @ (ColumnKind::Integer | ColumnKind::Decimal)) if kind.accepts(value) => {
+            return value.trim().to_string();
+        }
+        Some(ColumnKind::Boolean) if ColumnKind::Boolean.accepts(value) => {
+            let truthy = matches!(value.trim().to_ascii_lowercase().as_str(), "true" | "t" | "yes");
+            return match (db_type, truthy) {
+                (DatabaseType::PostgreSQL, true) => "TRUE",
+                (DatabaseType::PostgreSQL, false) => "FALSE",
+                (_, true) => "1",
+                (_, false) => "0",
+            }
+            .to_string();
+        }
+        _ => {}
+    }
+    match db_type {
+        DatabaseType::MySQL => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+        DatabaseType::MsSQL => format!("N'{}'", value.replace('\'', "''")),
+        _ => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+/// Where records go: the qualified table and, per target column, the CSV
+/// field feeding it and the kind used to render its literal.
+#[derive(Clone, Debug)]
+pub struct InsertPlan {
+    pub table: String,
+    pub columns: Vec<(usize, String, Option<ColumnKind>)>,
+    pub null_value: String,
+    pub db_type: DatabaseType,
+}
+
+impl InsertPlan {
+    fn prefix(&self) -> String {
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|(_, name, _)| quote_ident(name, &self.db_type))
+            .collect();
+        format!("INSERT INTO {} ({}) VALUES", self.table, columns.join(", "))
+    }
+
+    fn values(&self, fields: &[String]) -> String {
+        let values: Vec<String> = self
+            .columns
+            .iter()
+            .map(|(index, _, kind)| {
+                let value = fields.get(*index).map(String::as_str).unwrap_or("");
+                sql_literal(value, *kind, &self.null_value, &self.db_type)
+            })
+            .collect();
+        format!("({})", values.join(", "))
+    }
+
+    /// One multi-row INSERT for `records`.
+    pub fn batch_insert(&self, records: &[CsvRecord]) -> String {
+        let rows: Vec<String> = records.iter().map(|r| self.values(&r.fields)).collect();
+        format!("{}\n{}", self.prefix(), rows.join(",\n"))
+    }
+
+    /// A single-row INSERT, used to find the row a failed batch tripped on.
+    pub fn row_insert(&self, record: &CsvRecord) -> String {
+        format!("{} {}", self.prefix(), self.values(&record.fields))
+    }
+}
+
+/// `CREATE TABLE` for an import into a new table.
+pub fn create_table_sql(table: &str, columns: &[(String, ColumnKind)], db_type: &DatabaseType) -> String {
+    let definitions: Vec<String> = columns
+        .iter()
+        .map(|(name, kind)| format!("  {} {}", quote_ident(name, db_type), kind.sql_type(db_type)))
+        .collect();
+    format!("CREATE TABLE {} (\n{}\n)", table, definitions.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_delimiter_header_and_types() {
+        let sample = "id;name;joined\n1;\"Doe; Jane\";2024-01-05\n2;Bob;2024-02-11\n";
+        assert_eq!(detect_delimiter(sample), ';');
+        let rows = sample_records(sample, ';', 10);
+        assert!(detect_header(&rows));
+        assert!(!detect_header(&rows[1..]));
+
+        let ids = ["1", "", "3"];
+        assert_eq!(ColumnKind::infer(ids.iter().copied(), ""), ColumnKind::Integer);
+        let mixed = ["1", "2.5", "NULL"];
+        assert_eq!(ColumnKind::infer(mixed.iter().copied(), "NULL"), ColumnKind::Decimal);
+        let flags = ["yes", "No"];
+        assert_eq!(ColumnKind::infer(flags.iter().copied(), ""), ColumnKind::Boolean);
+        let stamps = ["2024-01-05T10:00:00", "2024-01-05 11:30:00.5"];
+        assert_eq!(ColumnKind::infer(stamps.iter().copied(), ""), ColumnKind::DateTime);
+        assert_eq!(ColumnKind::infer(["nan"].iter().copied(), ""), ColumnKind::Text);
+    }
+
+    #[test]
+    fn chunks_keep_quoted_newlines_and_line_numbers() {
+        let path = std::env::temp_dir().join(format!("tabular_csv_import_{}.csv", std::process::id()));
+        std::fs::write(&path, "id,note\n1,\"two\nlines\"\n2,\n3,\"say \"\"hi\"\"\"\n").unwrap();
+        let mut chunks = CsvChunks::open(&path, ',', true).unwrap();
+        assert_eq!(chunks.headers(), ["id", "note"]);
+        let first = chunks.next_chunk(2).unwrap();
+        assert_eq!(first[0].fields, ["1", "two\nlines"]);
+        assert_eq!((first[0].line, first[1].line), (2, 4));
+        let rest = chunks.next_chunk(10).unwrap();
+        assert_eq!(rest[0].fields, ["3", "say \"hi\""]);
+        assert!(chunks.next_chunk(10).unwrap().is_empty());
+        assert_eq!(chunks.fraction_read(), 1.0);
+        std::fs::remove_file(&path).ok();
+
+        let plan = InsertPlan {
+            table: qualified_table("people", Some("app"), &DatabaseType::PostgreSQL),
+            columns: vec![
+                (0, "id".to_string(), Some(ColumnKind::Integer)),
+                (1, "note".to_string(), None),
+            ],
+            null_value: String::new(),
+            db_type: DatabaseType::PostgreSQL,
+        };
+        assert_eq!(
+            plan.batch_insert(&first),
+            "INSERT INTO \"app\".\"people\" (\"id\", \"note\") VALUES\n(1, 'two\nlines'),\n(2, NULL)"
+        );
+    }
+}
@@ -0,0 +1,513 @@
+use log::debug;
+
+use super::utils::parse_enum_values;
+use crate::{
+    models::{
+        self,
+        structs::{AllowedValues, AllowedValuesSource, ColumnConstraints},
+    },
+    window_egui,
+};
+
+/// Referenced values shown in a foreign key lookup.
+const FOREIGN_KEY_SAMPLE_LIMIT: usize = 200;
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Value(String),
+    Comma,
+    Eq,
+    Other,
+}
+
+/// Tokenize a CHECK expression, dropping what does not affect a value list:
+/// parentheses, `::type` casts, `ARRAY[...]` brackets and literal prefixes
+/// such as `_utf8mb4'a'` or `N'a'`.
+fn tokenize(clause: &str) -> Vec<Token> {
+    let chars: Vec<char> = clause.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let read_word = |i: &mut usize| {
+        let start = *i;
+        while *i < chars.len() && (chars[*i].is_alphanumeric() || chars[*i] == '_') {
+            *i += 1;
+        }
+        chars[start..*i].iter().collect::<String>()
+    };
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            c if c.is_whitespace() || c == '(' || c == ')' => i += 1,
+            '\'' => {
+                let mut value = String::new();
+                i += 1;
+                while i < chars.len() {
+                    if chars[i] == '\'' {
+                        if chars.get(i + 1) == Some(&'\'') {
+                            value.push('\'');
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    value.push(chars[i]);
+                    i += 1;
+                }
+                i += 1;
+                tokens.push(Token::Value(value));
+            }
+            '"' | '`' | '[' => {
+                let after_array = matches!(tokens.last(), Some(Token::Word(w)) if w.eq_ignore_ascii_case("array"));
+                if c == '[' && after_array {
+                    tokens.pop();
+                    i += 1;
+                    continue;
+                }
+                let close = if c == '[' { ']' } else { c };
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && chars[i] != close {
+                    i += 1;
+                }
+                tokens.push(Token::Quoted(
+                    chars[start..i.min(chars.len())].iter().collect(),
+                ));
+                i += 1;
+            }
+            ']' => i += 1,
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '=' => {
+                tokens.push(Token::Eq);
+                i += 1;
+            }
+            ':' if chars.get(i + 1) == Some(&':') => {
+                // Cast: one type name, optionally a two-word one, optionally `[]`
+                i += 2;
+                while i < chars.len() && chars[i].is_whitespace() {
+                    i += 1;
+                }
+                let word = read_word(&mut i).to_ascii_lowercase();
+                if matches!(word.as_str(), "character" | "double" | "bit") {
+                    let mut j = i;
+                    while j < chars.len() && chars[j].is_whitespace() {
+                        j += 1;
+                    }
+                    let mut k = j;
+                    let next = read_word(&mut k).to_ascii_lowercase();
+                    if next == "varying" || next == "precision" {
+                        i = k;
+                    }
+                }
+                while chars.get(i) == Some(&'[') && chars.get(i + 1) == Some(&']') {
+                    i += 2;
+                }
+            }
+            c if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit())) =>
+            {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                tokens.push(Token::Value(chars[start..i].iter().collect()));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let word = read_word(&mut i);
+                // `_utf8mb4'a'`, `N'a'`, `E'a'`: a word glued to a literal is its prefix
+                if chars.get(i) != Some(&'\'') {
+                    tokens.push(Token::Word(word));
+                }
+            }
+            _ => {
+                tokens.push(Token::Other);
+                i += 1;
+            }
+        }
+    }
+    tokens
+}
+
+/// Value list of a CHECK expression that pins `column` to a fixed set:
+/// `col IN ('a','b')`, `col = ANY (ARRAY['a','b'])` or `col = 'a' OR col = 'b'`.
+/// Domain checks may name the column `VALUE`. Anything else yields `None`.
+pub(crate) fn parse_check_values(clause: &str, column: &str) -> Option<Vec<String>> {
+    let mut tokens = tokenize(clause);
+    // pg_get_constraintdef spells out the keyword: `CHECK (...)`
+    if matches!(tokens.first(), Some(Token::Word(w)) if w.eq_ignore_ascii_case("check")) {
+        tokens.remove(0);
+    }
+    let is_column = |t: &Token| match t {
+        Token::Word(w) => w.eq_ignore_ascii_case(column) || w.eq_ignore_ascii_case("value"),
+        Token::Quoted(q) => q.eq_ignore_ascii_case(column),
+        _ => false,
+    };
+    let is_keyword = |t: Option<&Token>, kw: &str| matches!(t, Some(Token::Word(w)) if w.eq_ignore_ascii_case(kw));
+    // Comma separated literals starting at `from`, which must run to the end
+    let value_list = |from: usize| -> Option<Vec<String>> {
+        let mut values = Vec::new();
+        let mut rest = tokens.get(from..)?.iter();
+        loop {
+            match rest.next() {
+                Some(Token::Value(v)) => values.push(v.clone()),
+                _ => return None,
+            }
+            match rest.next() {
+                None => return Some(values),
+                Some(Token::Comma) => {}
+                _ => return None,
+            }
+        }
+    };
+
+    if !tokens.first().is_some_and(is_column) {
+        return None;
+    }
+    if is_keyword(tokens.get(1), "in") {
+        return value_list(2);
+    }
+    if tokens.get(1) == Some(&Token::Eq) && is_keyword(tokens.get(2), "any") {
+        return value_list(3);
+    }
+    // col = 'a' OR col = 'b' ...
+    if tokens.len() % 4 != 3 {
+        return None;
+    }
+    let mut values = Vec::new();
+    for chunk in tokens.chunks(4) {
+        let ok = match chunk {
+            [col, Token::Eq, Token::Value(v), or] => {
+                is_column(col) && is_keyword(Some(or), "or") && {
+                    values.push(v.clone());
+                    true
+                }
+            }
+            [col, Token::Eq, Token::Value(v)] => {
+                is_column(col) && {
+                    values.push(v.clone());
+                    true
+                }
+            }
+            _ => false,
+        };
+        if !ok {
+            return None;
+        }
+    }
+    (!values.is_empty()).then_some(values)
+}
+
+/// Exhaustive value set implied by the constraints: enum labels first, then an
+/// inline enum type, then the first CHECK that pins the column to a list.
+fn from_constraints(constraints: &ColumnConstraints, column: &str) -> Option<AllowedValues> {
+    if !constraints.enum_values.is_empty() {
+        return Some(AllowedValues {
+            values: constraints.enum_values.clone(),
+            source: AllowedValuesSource::Enum,
+        });
+    }
+    if let Some(values) = constraints
+        .type_definition
+        .as_deref()
+        .and_then(parse_enum_values)
+    {
+        return Some(AllowedValues {
+            values,
+            source: AllowedValuesSource::Enum,
+        });
+    }
+    constraints
+        .check_clauses
+        .iter()
+        .find_map(|clause| parse_check_values(clause, column))
+        .map(|values| AllowedValues {
+            values,
+            source: AllowedValuesSource::Check,
+        })
+}
+
+/// Query the server for the constraints of one column and, when no fixed set
+/// exists but the column is a foreign key, a sample of referenced values.
+fn fetch_allowed_values(
+    tabular: &mut window_egui::Tabular,
+    connection_id: i64,
+    database_name: &str,
+    table_name: &str,
+    column_name: &str,
+) -> Option<AllowedValues> {
+    let connection_type = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(connection_id))
+        .map(|c| c.connection_type.clone())?;
+    if !matches!(
+        connection_type,
+        models::enums::DatabaseType::MySQL
+            | models::enums::DatabaseType::PostgreSQL
+            | models::enums::DatabaseType::SQLite
+            | models::enums::DatabaseType::MsSQL
+    ) {
+        return None;
+    }
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(async {
+        let pool = crate::connection::get_or_create_connection_pool(tabular, connection_id).await?;
+        let limit = FOREIGN_KEY_SAMPLE_LIMIT;
+        let constraints = match &pool {
+            models::enums::DatabasePool::MySQL(p) => {
+                crate::driver_mysql::fetch_mysql_column_constraints(
+                    p,
+                    database_name,
+                    table_name,
+                    column_name,
+                )
+                .await
+                .map_err(|e| e.to_string())
+            }
+            models::enums::DatabasePool::PostgreSQL(p) => {
+                crate::driver_postgres::fetch_postgres_column_constraints(
+                    p,
+                    table_name,
+                    column_name,
+                )
+                .await
+                .map_err(|e| e.to_string())
+            }
+            models::enums::DatabasePool::SQLite(p) => {
+                crate::driver_sqlite::fetch_sqlite_column_constraints(
+                    p,
+                    database_name,
+                    table_name,
+                    column_name,
+                )
+                .await
+                .map_err(|e| e.to_string())
+            }
+            models::enums::DatabasePool::MsSQL(p) => {
+                crate::driver_mssql::fetch_mssql_column_constraints(p, table_name, column_name)
+                    .await
+            }
+            _ => return None,
+        };
+        let constraints = match constraints {
+            Ok(c) => c,
+            Err(e) => {
+                debug!(
+                    "Column constraints for {}.{} unavailable: {}",
+                    table_name, column_name, e
+                );
+                return None;
+            }
+        };
+        if let Some(found) = from_constraints(&constraints, column_name) {
+            return Some(found);
+        }
+        let (ref_table, ref_column) = constraints.references?;
+        let values =
+            fetch_distinct_values(&pool, database_name, &ref_table, &ref_column, limit).await?;
+        match values {
+            Ok(values) if !values.is_empty() => Some(AllowedValues {
+                values,
+                source: AllowedValuesSource::ForeignKey {
+                    table: ref_table,
+                    column: ref_column,
+                },
+            }),
+            Ok(_) => None,
+            Err(e) => {
+                debug!(
+                    "Foreign key values for {}.{} unavailable: {}",
+                    ref_table, ref_column, e
+                );
+                None
+            }
+        }
+    })
+}
+
+/// Up to `limit` distinct non-null values of `table.column` as text, sorted.
+/// `None` when the driver has no distinct-value query.
+pub(super) async fn fetch_distinct_values(
+    pool: &models::enums::DatabasePool,
+    database_name: &str,
+    table_name: &str,
+    column_name: &str,
+    limit: usize,
+) -> Option<Result<Vec<String>, String>> {
+    Some(match pool {
+        models::enums::DatabasePool::MySQL(p) => crate::driver_mysql::fetch_mysql_distinct_values(
+            p,
+            database_name,
+            table_name,
+            column_name,
+            limit,
+        )
+        .await
+        .map_err(|e| e.to_string()),
+        models::enums::DatabasePool::PostgreSQL(p) => {
+            crate::driver_postgres::fetch_postgres_distinct_values(
+                p,
+                table_name,
+                column_name,
+                limit,
+            )
+            .await
+            .map_err(|e| e.to_string())
+        }
+        models::enums::DatabasePool::SQLite(p) => {
+            crate::driver_sqlite::fetch_sqlite_distinct_values(
+                p,
+                database_name,
+                table_name,
+                column_name,
+                limit,
+            )
+            .await
+            .map_err(|e| e.to_string())
+        }
+        models::enums::DatabasePool::MsSQL(p) => {
+            crate::driver_mssql::fetch_mssql_distinct_values(p, table_name, column_name, limit)
+                .await
+        }
+        _ => return None,
+    })
+}
+
+/// `(connection_id, database, table, column)` that result column `col_index`
+/// was read from.
+pub(super) fn column_source(
+    tabular: &mut window_egui::Tabular,
+    col_index: usize,
+) -> Option<(i64, String, String, String)> {
+    let connection_id = tabular.current_connection_id?;
+    let column_name = tabular.current_table_headers.get(col_index)?.clone();
+    let table_name = match tabular
+        .current_column_metadata
+        .as_ref()
+        .and_then(|meta| meta.get(col_index))
+        .and_then(|col| col.table_name.clone())
+        .filter(|t| !t.is_empty())
+    {
+        Some(t) => t,
+        None => super::infer_current_table_name(tabular),
+    };
+    let table_name = table_name
+        .trim_matches(|c| c == '`' || c == '"' || c == '\'')
+        .to_string();
+    if table_name.is_empty() {
+        return None;
+    }
+    let database_name = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.database_name.clone())
+        .unwrap_or_default();
+    Some((connection_id, database_name, table_name, column_name))
+}
+
+/// Allowed values of result column `col_index`, fetched once per column and
+/// cached on the connection. `None` means free text.
+pub(crate) fn allowed_values_for_column(
+    tabular: &mut window_egui::Tabular,
+    col_index: usize,
+) -> Option<AllowedValues> {
+    let key = column_source(tabular, col_index)?;
+    if let Some(cached) = tabular.column_allowed_values.get(&key) {
+        return cached.clone();
+    }
+    let found = fetch_allowed_values(tabular, key.0, &key.1, &key.2, &key.3);
+    tabular.column_allowed_values.insert(key, found.clone());
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(values: &[&str]) -> Option<Vec<String>> {
+        Some(values.iter().map(|v| v.to_string()).collect())
+    }
+
+    #[test]
+    fn check_value_lists_in_each_dialect() {
+        // MySQL
+        assert_eq!(
+            parse_check_values("(`status` in (_utf8mb4'draft',_utf8mb4'live'))", "status"),
+            strings(&["draft", "live"])
+        );
+        // PostgreSQL, table and domain constraints
+        assert_eq!(
+            parse_check_values(
+                "CHECK (((status)::text = ANY ((ARRAY['draft'::character varying, 'live'::character varying])::text[])))",
+                "status"
+            ),
+            strings(&["draft", "live"])
+        );
+        assert_eq!(
+            parse_check_values(
+                "CHECK (VALUE = ANY (ARRAY['S'::text, 'M'::text, 'L'::text]))",
+                "size"
+            ),
+            strings(&["S", "M", "L"])
+        );
+        // SQL Server rewrites IN lists into OR chains
+        assert_eq!(
+            parse_check_values("([status]='live' OR [status]='draft')", "status"),
+            strings(&["live", "draft"])
+        );
+        // SQLite keeps the text as written
+        assert_eq!(
+            parse_check_values("Status IN ('it''s', 'other')", "status"),
+            strings(&["it's", "other"])
+        );
+        assert_eq!(
+            parse_check_values("priority IN (1, 2, 3)", "priority"),
+            strings(&["1", "2", "3"])
+        );
+    }
+
+    #[test]
+    fn non_list_checks_are_ignored() {
+        assert_eq!(parse_check_values("price > 0", "price"), None);
+        assert_eq!(
+            parse_check_values("status IN ('a') AND price > 0", "status"),
+            None
+        );
+        assert_eq!(parse_check_values("other IN ('a','b')", "status"), None);
+        assert_eq!(
+            parse_check_values("status = 'a' OR other = 'b'", "status"),
+            None
+        );
+        assert_eq!(parse_check_values("status = lower(status)", "status"), None);
+    }
+
+    #[test]
+    fn enum_labels_take_priority_over_checks() {
+        let constraints = ColumnConstraints {
+            type_definition: Some("enum('a','b')".to_string()),
+            check_clauses: vec!["status IN ('x')".to_string()],
+            ..Default::default()
+        };
+        let found = from_constraints(&constraints, "status").unwrap();
+        assert_eq!(found.source, AllowedValuesSource::Enum);
+        assert_eq!(found.values, vec!["a", "b"]);
+
+        let constraints = ColumnConstraints {
+            check_clauses: vec!["price > 0".to_string(), "status IN ('x')".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            from_constraints(&constraints, "status").unwrap().source,
+            AllowedValuesSource::Check
+        );
+        assert_eq!(
+            from_constraints(&ColumnConstraints::default(), "status"),
+            None
+        );
+    }
+}
@@ -0,0 +1,466 @@
+//! Bulk paste into the data grid: a tab-separated block from the clipboard
+//! (as copied from a spreadsheet or from this grid) is laid over the cells
+//! starting at the selected one. A preview lists the changes, what fell
+//! outside the result, and values that do not fit their column's type.
+//! Applying turns each changed cell into a pending edit, so Save writes the
+//! block together with any other edits in one transaction.
+
+use eframe::egui;
+
+use super::{column_source, is_numeric_type, is_temporal_type, parse_stored_instant};
+use crate::spreadsheet::SpreadsheetOperations;
+use crate::window_egui;
+
+/// Changed cells listed in the preview; the rest are only counted.
+const PREVIEW_CELLS: usize = 50;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PastedCell {
+    pub row: usize,
+    pub col: usize,
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PasteMismatch {
+    pub row: usize,
+    pub column_name: String,
+    pub type_name: String,
+    pub value: String,
+    pub problem: String,
+}
+
+/// A pasted block waiting for confirmation.
+#[derive(Clone, Debug, Default)]
+pub struct BulkPaste {
+    /// Top-left cell (page row, column).
+    pub anchor: (usize, usize),
+    /// Size of the block that fits inside the result.
+    pub rows: usize,
+    pub cols: usize,
+    /// Rows and columns of the clipboard beyond the last row / column.
+    pub clipped_rows: usize,
+    pub clipped_cols: usize,
+    /// Cells whose value changes.
+    pub cells: Vec<PastedCell>,
+    /// Cells already holding the pasted value.
+    pub unchanged: usize,
+    pub mismatches: Vec<PasteMismatch>,
+}
+
+/// Rows of tab-separated clipboard text. Cells quoted by spreadsheets (those
+/// holding tabs, newlines or quotes) are unquoted; a final line break does
+/// not add an empty row.
+fn parse_tsv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut chars = text.chars().peekable();
+    let mut at_cell_start = true;
+    let mut in_quotes = false;
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => cell.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if at_cell_start => {
+                in_quotes = true;
+                at_cell_start = false;
+            }
+            '\t' => {
+                row.push(std::mem::take(&mut cell));
+                at_cell_start = true;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                row.push(std::mem::take(&mut cell));
+                rows.push(std::mem::take(&mut row));
+                at_cell_start = true;
+            }
+            _ => {
+                cell.push(c);
+                at_cell_start = false;
+            }
+        }
+    }
+    if !at_cell_start || !cell.is_empty() || !row.is_empty() {
+        row.push(cell);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Cells of `grid` placed at `anchor`, cut to a result of `row_count` x
+/// `col_count`, with the number of rows and columns cut off.
+fn clip_block(
+    grid: &[Vec<String>],
+    anchor: (usize, usize),
+    row_count: usize,
+    col_count: usize,
+) -> (Vec<(usize, usize, String)>, usize, usize) {
+    let fit_rows = grid.len().min(row_count.saturating_sub(anchor.0));
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let fit_cols = width.min(col_count.saturating_sub(anchor.1));
+    let cells = grid[..fit_rows]
+        .iter()
+        .enumerate()
+        .flat_map(|(r, values)| {
+            values
+                .iter()
+                .take(fit_cols)
+                .enumerate()
+                .map(move |(c, value)| (anchor.0 + r, anchor.1 + c, value.clone()))
+        })
+        .collect();
+    (cells, grid.len() - fit_rows, width - fit_cols)
+}
+
+/// Why `value` cannot be stored in a column of `type_name`, if it cannot.
+/// Empty and `NULL` always pass; unknown types are not checked.
+fn type_problem(type_name: &str, value: &str) -> Option<String> {
+    let v = value.trim();
+    if v.is_empty() || v.eq_ignore_ascii_case("null") {
+        return None;
+    }
+    let t = type_name.to_ascii_uppercase();
+    let base = t.split(['(', ' ']).next().unwrap_or_default();
+    if is_numeric_type(type_name) {
+        let whole = base.contains("INT") || base.contains("SERIAL");
+        return if whole && v.parse::<i128>().is_err() {
+            Some("not a whole number".to_string())
+        } else if v.parse::<f64>().is_err() {
+            Some("not a number".to_string())
+        } else {
+            None
+        };
+    }
+    if matches!(base, "BOOL" | "BOOLEAN") {
+        let known = [
+            "true", "false", "t", "f", "1", "0", "yes", "no", "y", "n", "on", "off",
+        ];
+        return (!known.iter().any(|k| v.eq_ignore_ascii_case(k)))
+            .then(|| "not a boolean".to_string());
+    }
+    if base == "DATE" {
+        return chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d")
+            .is_err()
+            .then(|| "not a date (YYYY-MM-DD)".to_string());
+    }
+    if is_temporal_type(type_name) {
+        return parse_stored_instant(v)
+            .is_none()
+            .then(|| "not a date and time".to_string());
+    }
+    if base.contains("CHAR") {
+        let limit = t
+            .split_once('(')
+            .and_then(|(_, rest)| rest.split(')').next())
+            .and_then(|n| n.trim().parse::<usize>().ok());
+        if let Some(limit) = limit
+            && v.chars().count() > limit
+        {
+            return Some(format!("longer than {} characters", limit));
+        }
+    }
+    None
+}
+
+/// Type names of the result columns, from the query metadata or, for a
+/// browsed table, from the cached table columns.
+fn column_types(tabular: &mut window_egui::Tabular) -> Vec<Option<String>> {
+    if let Some(meta) = tabular.current_column_metadata.as_ref() {
+        return meta.iter().map(|m| Some(m.type_name.clone())).collect();
+    }
+    let headers = tabular.current_table_headers.clone();
+    let cached = column_source(tabular, 0).and_then(|(connection_id, database, table, _)| {
+        crate::cache_data::get_columns_from_cache(tabular, connection_id, &database, &table)
+    });
+    let Some(cached) = cached else {
+        return vec![None; headers.len()];
+    };
+    headers
+        .iter()
+        .map(|h| {
+            cached
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(h))
+                .map(|(_, data_type)| data_type.clone())
+        })
+        .collect()
+}
+
+/// Prepare the preview for `text` pasted at the selected cell. Returns false
+/// when there is no selected cell or nothing to paste.
+pub(crate) fn open_bulk_paste(tabular: &mut window_egui::Tabular, text: &str) -> bool {
+    let Some(anchor) = tabular.selected_cell else {
+        return false;
+    };
+    let grid = parse_tsv(text);
+    if grid.is_empty() {
+        return false;
+    }
+    let (placed, clipped_rows, clipped_cols) = clip_block(
+        &grid,
+        anchor,
+        tabular.current_table_data.len(),
+        tabular.current_table_headers.len(),
+    );
+    let types = column_types(tabular);
+    let mut paste = BulkPaste {
+        anchor,
+        rows: grid.len() - clipped_rows,
+        cols: grid.iter().map(Vec::len).max().unwrap_or(0) - clipped_cols,
+        clipped_rows,
+        clipped_cols,
+        ..Default::default()
+    };
+    for (row, col, new) in placed {
+        let old = tabular
+            .current_table_data
+            .get(row)
+            .and_then(|r| r.get(col))
+            .cloned()
+            .unwrap_or_default();
+        if let Some(type_name) = types.get(col).cloned().flatten()
+            && let Some(problem) = type_problem(&type_name, &new)
+        {
+            paste.mismatches.push(PasteMismatch {
+                row,
+                column_name: tabular.current_table_headers[col].clone(),
+                type_name,
+                value: new.clone(),
+                problem,
+            });
+        }
+        if old == new {
+            paste.unchanged += 1;
+        } else {
+            paste.cells.push(PastedCell { row, col, old, new });
+        }
+    }
+    tabular.bulk_paste = Some(paste);
+    true
+}
+
+/// Record every changed cell as a pending edit and select the pasted block.
+fn apply_bulk_paste(tabular: &mut window_egui::Tabular, paste: &BulkPaste) {
+    if tabular.spreadsheet_state.editing_cell.is_some() {
+        tabular.spreadsheet_finish_cell_edit(true);
+    }
+    for cell in &paste.cells {
+        tabular.spreadsheet_start_cell_edit(cell.row, cell.col);
+        tabular.spreadsheet_state.cell_edit_text = cell.new.clone();
+        tabular.spreadsheet_finish_cell_edit(true);
+    }
+    if paste.rows > 0 && paste.cols > 0 {
+        let last = (
+            paste.anchor.0 + paste.rows - 1,
+            paste.anchor.1 + paste.cols - 1,
+        );
+        tabular.table_sel_anchor = (last != paste.anchor).then_some(paste.anchor);
+        tabular.selected_cell = Some(last);
+        tabular.selected_row = Some(last.0);
+    }
+    tabular.table_recently_clicked = true;
+    tabular.toasts.info(format!(
+        "{} cell(s) changed — save to write them in one transaction",
+        paste.cells.len()
+    ));
+}
+
+pub(crate) fn render_bulk_paste(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(paste) = tabular.bulk_paste.as_ref() else {
+        return;
+    };
+    let headers = &tabular.current_table_headers;
+    let column = |c: usize| headers.get(c).map_or("?", String::as_str);
+    let mut open = true;
+    let mut apply = false;
+    let mut cancel = false;
+    egui::Window::new("Paste into Cells")
+        .id(egui::Id::new("bulk_paste_window"))
+        .open(&mut open)
+        .collapsible(false)
+        .default_size(egui::vec2(560.0, 420.0))
+        .show(ctx, |ui| {
+            let (row, col) = paste.anchor;
+            let columns = if paste.cols > 1 {
+                format!("{} → {}", column(col), column(col + paste.cols - 1))
+            } else {
+                column(col).to_string()
+            };
+            ui.label(format!(
+                "{} row(s) × {} column(s) from row {}, {}",
+                paste.rows,
+                paste.cols,
+                row + 1,
+                columns
+            ));
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} cell(s) change, {} already hold the value",
+                    paste.cells.len(),
+                    paste.unchanged
+                ))
+                .weak(),
+            );
+            if paste.clipped_rows > 0 || paste.clipped_cols > 0 {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} row(s) and {} column(s) fall outside the result and are skipped",
+                        paste.clipped_rows, paste.clipped_cols
+                    ))
+                    .color(ui.visuals().warn_fg_color),
+                );
+            }
+            if !paste.mismatches.is_empty() {
+                ui.separator();
+                ui.label(
+                    egui::RichText::new(format!(
+                        "⚠ {} value(s) may be rejected by their column:",
+                        paste.mismatches.len()
+                    ))
+                    .color(ui.visuals().warn_fg_color)
+                    .strong(),
+                );
+                egui::ScrollArea::vertical()
+                    .id_salt("bulk_paste_mismatches")
+                    .max_height(110.0)
+                    .show(ui, |ui| {
+                        for m in &paste.mismatches {
+                            ui.label(format!(
+                                "row {} · {} ({}): \"{}\" is {}",
+                                m.row + 1,
+                                m.column_name,
+                                m.type_name,
+                                super::cell_preview(&m.value, 40),
+                                m.problem
+                            ));
+                        }
+                    });
+            }
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .id_salt("bulk_paste_cells")
+                .max_height(200.0)
+                .auto_shrink([false, true])
+                .show(ui, |ui| {
+                    egui::Grid::new("bulk_paste_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .spacing([12.0, 4.0])
+                        .show(ui, |ui| {
+                            for cell in paste.cells.iter().take(PREVIEW_CELLS) {
+                                ui.label(format!("row {}", cell.row + 1));
+                                ui.label(column(cell.col));
+                                ui.label(
+                                    egui::RichText::new(super::cell_preview(&cell.old, 30))
+                                        .monospace()
+                                        .weak(),
+                                );
+                                ui.label(
+                                    egui::RichText::new(super::cell_preview(&cell.new, 30))
+                                        .monospace(),
+                                );
+                                ui.end_row();
+                            }
+                        });
+                    if paste.cells.len() > PREVIEW_CELLS {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "… and {} more",
+                                paste.cells.len() - PREVIEW_CELLS
+                            ))
+                            .weak(),
+                        );
+                    }
+                });
+            ui.add_space(6.0);
+            ui.label(
+                egui::RichText::new("Changes stay pending until you save.")
+                    .size(11.0)
+                    .weak(),
+            );
+            ui.horizontal(|ui| {
+                let label = if paste.mismatches.is_empty() {
+                    format!("Apply {} change(s)", paste.cells.len())
+                } else {
+                    format!("Apply {} change(s) anyway", paste.cells.len())
+                };
+                if ui
+                    .add_enabled(!paste.cells.is_empty(), egui::Button::new(label))
+                    .clicked()
+                {
+                    apply = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+    if !open || cancel {
+        tabular.bulk_paste = None;
+    } else if apply && let Some(paste) = tabular.bulk_paste.take() {
+        apply_bulk_paste(tabular, &paste);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pasted_block_is_parsed_clipped_and_checked() {
+        let grid = parse_tsv("1\tAnn\r\n2\t\"multi\nline \"\"x\"\"\"\n3\tCid\n");
+        assert_eq!(
+            grid,
+            vec![
+                vec!["1", "Ann"],
+                vec!["2", "multi\nline \"x\""],
+                vec!["3", "Cid"],
+            ]
+        );
+        assert_eq!(parse_tsv("solo"), vec![vec!["solo"]]);
+        assert_eq!(parse_tsv("a\t\n"), vec![vec!["a", ""]]);
+        assert!(parse_tsv("").is_empty());
+
+        // Pasted at row 1, column 2 of a 3 x 3 result: one row and one column spill over
+        let (cells, clipped_rows, clipped_cols) = clip_block(&grid, (1, 2), 3, 3);
+        assert_eq!(
+            cells,
+            vec![(1, 2, "1".to_string()), (2, 2, "2".to_string())]
+        );
+        assert_eq!((clipped_rows, clipped_cols), (1, 1));
+
+        assert_eq!(type_problem("int4", "12"), None);
+        assert_eq!(
+            type_problem("BIGINT", "1.5").as_deref(),
+            Some("not a whole number")
+        );
+        assert_eq!(
+            type_problem("numeric(10,2)", "abc").as_deref(),
+            Some("not a number")
+        );
+        assert_eq!(type_problem("boolean", "yes"), None);
+        assert_eq!(
+            type_problem("date", "2024-13-01").as_deref(),
+            Some("not a date (YYYY-MM-DD)")
+        );
+        assert_eq!(type_problem("timestamp", "2024-01-02 03:04:05"), None);
+        assert_eq!(
+            type_problem("varchar(3)", "abcd").as_deref(),
+            Some("longer than 3 characters")
+        );
+        assert_eq!(type_problem("int", "NULL"), None);
+        assert_eq!(type_problem("jsonb", "{"), None);
+    }
+}
@@ -0,0 +1,110 @@
+//! Previews for oversized cells. A value of several megabytes (a JSON payload,
+//! a log blob) makes the grid stall when every frame lays it out, so views
+//! show only its first `cell_preview_chars` characters followed by "…". The
+//! full value stays in the result for the detail popup, copying and export.
+
+use std::borrow::Cow;
+
+use eframe::egui;
+
+use super::format_byte_size;
+use crate::window_egui;
+
+/// Whether `cell` is longer than the preview limit. Compares bytes, so the
+/// check costs nothing for the common short value.
+pub(crate) fn is_oversized(cell: &str, limit: usize) -> bool {
+    limit > 0 && cell.len() > limit && cell.char_indices().nth(limit).is_some()
+}
+
+/// `cell` cut to `limit` characters with a trailing "…"; unchanged when it fits
+/// or the limit is 0 (no preview).
+pub(crate) fn cell_preview(cell: &str, limit: usize) -> Cow<'_, str> {
+    if limit == 0 || cell.len() <= limit {
+        return Cow::Borrowed(cell);
+    }
+    match cell.char_indices().nth(limit) {
+        Some((end, _)) => Cow::Owned(format!("{}…", &cell[..end])),
+        None => Cow::Borrowed(cell),
+    }
+}
+
+/// Small "view full" button at the right edge of an oversized grid cell.
+/// Returns true when clicked.
+pub(crate) fn view_full_button(
+    ui: &mut egui::Ui,
+    cell_rect: egui::Rect,
+    id: egui::Id,
+    full_len: usize,
+) -> bool {
+    let size = egui::vec2(18.0, cell_rect.height().min(18.0));
+    let rect = egui::Rect::from_min_size(
+        egui::pos2(
+            cell_rect.right() - size.x - 2.0,
+            cell_rect.center().y - size.y * 0.5,
+        ),
+        size,
+    );
+    let resp = ui
+        .interact(rect, id, egui::Sense::click())
+        .on_hover_text(format!(
+            "Large value ({}) — showing a preview. Click to view it in full.",
+            format_byte_size(full_len as f64)
+        ));
+    let visuals = ui.style().interact(&resp);
+    ui.painter().rect_filled(rect, 3.0, visuals.bg_fill);
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        "⤢",
+        egui::FontId::proportional(12.0),
+        visuals.text_color(),
+    );
+    resp.clicked()
+}
+
+/// Preferences → Data Grid control for the preview length. Returns true when
+/// it changed.
+pub(crate) fn render_cell_preview_settings(
+    tabular: &mut window_egui::Tabular,
+    ui: &mut egui::Ui,
+) -> bool {
+    let changed = ui
+        .horizontal(|ui| {
+            ui.label("Preview cells longer than");
+            let changed = ui
+                .add(
+                    egui::DragValue::new(&mut tabular.cell_preview_chars)
+                        .range(100..=1_000_000)
+                        .speed(50),
+                )
+                .changed();
+            ui.label("characters");
+            changed
+        })
+        .inner;
+    ui.label(
+        egui::RichText::new(
+            "Longer values show their beginning with \"…\"; ⤢ or View Value… opens them in full. Copy and export always use the full value.",
+        )
+        .size(11.0)
+        .color(egui::Color32::from_gray(120)),
+    );
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn previews_cut_on_characters() {
+        assert_eq!(cell_preview("short", 10), "short");
+        assert_eq!(cell_preview("abcdefghij", 4), "abcd…");
+        // Multi-byte text: cut after 3 characters, not 3 bytes
+        assert_eq!(cell_preview("żółw żółw", 3), "żół…");
+        assert!(!is_oversized("żółw", 4));
+        assert!(is_oversized("żółwie", 4));
+        assert_eq!(cell_preview("anything", 0), "anything");
+        assert!(!is_oversized("anything", 0));
+    }
+}
@@ -0,0 +1,323 @@
+//! Column statistics: count, nulls, distinct values and a numeric, date or
+//! text summary of one result column, computed on request over the rows
+//! loaded on the client. Opened from the column header's context menu.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use eframe::egui;
+
+use crate::window_egui;
+
+/// How many of the most frequent values a text column lists.
+const TOP_VALUES: usize = 5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatsSummary {
+    Numeric {
+        min: f64,
+        max: f64,
+        mean: f64,
+    },
+    /// Earliest and latest value, as stored.
+    Temporal {
+        min: String,
+        max: String,
+    },
+    Text {
+        min_len: usize,
+        max_len: usize,
+        mean_len: f64,
+        top: Vec<(String, usize)>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    pub count: usize,
+    pub nulls: usize,
+    pub distinct: usize,
+    /// `None` when every value is NULL.
+    pub summary: Option<StatsSummary>,
+}
+
+pub struct ColumnStatsState {
+    pub column: String,
+    /// Rows the stats cover, and whether that is the whole result.
+    pub rows: usize,
+    pub complete: bool,
+    pub stats: ColumnStats,
+}
+
+/// Declared types whose values are text even when they look like numbers
+/// (zip codes, phone numbers).
+fn is_text_type(type_name: &str) -> bool {
+    let t = type_name.to_uppercase();
+    ["CHAR", "TEXT", "STRING", "UUID", "ENUM"]
+        .iter()
+        .any(|k| t.contains(k))
+}
+
+fn parse_instant(value: &str) -> Option<DateTime<Utc>> {
+    super::parse_stored_instant(value).or_else(|| {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .ok()
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|dt| dt.and_utc())
+    })
+}
+
+/// Summarise a column. Values are read as numbers when they all parse as
+/// numbers, as dates when they all parse as dates, and as text otherwise
+/// (always as text for declared text types).
+pub(crate) fn compute_column_stats<'a>(
+    values: impl IntoIterator<Item = &'a str>,
+    type_name: Option<&str>,
+) -> ColumnStats {
+    let mut count = 0;
+    let mut present = Vec::new();
+    for value in values {
+        count += 1;
+        if value != "NULL" {
+            present.push(value);
+        }
+    }
+    let mut frequency: HashMap<&str, usize> = HashMap::new();
+    for value in &present {
+        *frequency.entry(value).or_default() += 1;
+    }
+    let stats = ColumnStats {
+        count,
+        nulls: count - present.len(),
+        distinct: frequency.len(),
+        summary: None,
+    };
+    if present.is_empty() {
+        return stats;
+    }
+
+    let declared_text = type_name.is_some_and(is_text_type);
+    let numbers: Option<Vec<f64>> = if declared_text {
+        None
+    } else {
+        present
+            .iter()
+            .map(|v| v.trim().parse::<f64>().ok())
+            .collect()
+    };
+    if let Some(numbers) = numbers {
+        let min = numbers.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
+        return ColumnStats {
+            summary: Some(StatsSummary::Numeric { min, max, mean }),
+            ..stats
+        };
+    }
+    let instants: Option<Vec<(DateTime<Utc>, &str)>> = if declared_text {
+        None
+    } else {
+        present
+            .iter()
+            .map(|v| parse_instant(v).map(|t| (t, *v)))
+            .collect()
+    };
+    if let Some(instants) = instants {
+        let min = instants
+            .iter()
+            .min_by_key(|(t, _)| *t)
+            .map(|(_, v)| v.to_string());
+        let max = instants
+            .iter()
+            .max_by_key(|(t, _)| *t)
+            .map(|(_, v)| v.to_string());
+        return ColumnStats {
+            summary: Some(StatsSummary::Temporal {
+                min: min.unwrap_or_default(),
+                max: max.unwrap_or_default(),
+            }),
+            ..stats
+        };
+    }
+
+    let lengths: Vec<usize> = present.iter().map(|v| v.chars().count()).collect();
+    let mut top: Vec<(String, usize)> = frequency
+        .into_iter()
+        .map(|(v, n)| (v.to_string(), n))
+        .collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top.truncate(TOP_VALUES);
+    ColumnStats {
+        summary: Some(StatsSummary::Text {
+            min_len: lengths.iter().copied().min().unwrap_or(0),
+            max_len: lengths.iter().copied().max().unwrap_or(0),
+            mean_len: lengths.iter().sum::<usize>() as f64 / lengths.len() as f64,
+            top,
+        }),
+        ..stats
+    }
+}
+
+/// Compute stats for result column `col`: over the whole result when it is
+/// loaded client-side, over the current page otherwise.
+pub(crate) fn open_column_stats(tabular: &mut window_egui::Tabular, col: usize) {
+    let Some(column) = tabular.current_table_headers.get(col).cloned() else {
+        return;
+    };
+    let whole = !tabular.use_server_pagination && !tabular.all_table_data.is_empty();
+    let rows = if whole {
+        &tabular.all_table_data
+    } else {
+        &tabular.current_table_data
+    };
+    let type_name = tabular
+        .current_column_metadata
+        .as_ref()
+        .and_then(|meta| meta.get(col))
+        .map(|m| m.type_name.as_str());
+    let stats = compute_column_stats(
+        rows.iter()
+            .map(|r| r.get(col).map(String::as_str).unwrap_or("NULL")),
+        type_name,
+    );
+    let complete = whole
+        && tabular
+            .actual_total_rows
+            .is_none_or(|total| total <= rows.len());
+    tabular.column_stats = Some(ColumnStatsState {
+        column,
+        rows: rows.len(),
+        complete,
+        stats,
+    });
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{}", value as i64)
+    } else {
+        let text = format!("{:.4}", value);
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+pub(crate) fn render_column_stats(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(state) = tabular.column_stats.as_ref() else {
+        return;
+    };
+    let mut open = true;
+    egui::Window::new(format!("📊 {}", state.column))
+        .id(egui::Id::new("column_stats_window"))
+        .collapsible(false)
+        .resizable(false)
+        .open(&mut open)
+        .show(ctx, |ui| {
+            let scope = if state.complete {
+                format!("All {} rows", state.rows)
+            } else {
+                format!("{} loaded rows", state.rows)
+            };
+            ui.label(egui::RichText::new(scope).weak());
+            ui.add_space(4.0);
+            let stats = &state.stats;
+            egui::Grid::new("column_stats_grid")
+                .num_columns(2)
+                .striped(true)
+                .spacing([24.0, 4.0])
+                .show(ui, |ui| {
+                    let row = |ui: &mut egui::Ui, label: &str, value: String| {
+                        ui.label(label);
+                        ui.label(egui::RichText::new(value).monospace());
+                        ui.end_row();
+                    };
+                    row(ui, "Count", stats.count.to_string());
+                    row(ui, "Nulls", stats.nulls.to_string());
+                    row(ui, "Distinct", stats.distinct.to_string());
+                    match &stats.summary {
+                        Some(StatsSummary::Numeric { min, max, mean }) => {
+                            row(ui, "Min", format_number(*min));
+                            row(ui, "Max", format_number(*max));
+                            row(ui, "Mean", format_number(*mean));
+                        }
+                        Some(StatsSummary::Temporal { min, max }) => {
+                            row(ui, "Earliest", min.clone());
+                            row(ui, "Latest", max.clone());
+                        }
+                        Some(StatsSummary::Text {
+                            min_len,
+                            max_len,
+                            mean_len,
+                            ..
+                        }) => {
+                            row(ui, "Min length", min_len.to_string());
+                            row(ui, "Max length", max_len.to_string());
+                            row(ui, "Mean length", format_number(*mean_len));
+                        }
+                        None => {}
+                    }
+                });
+            if let Some(StatsSummary::Text { top, .. }) = &stats.summary {
+                ui.add_space(6.0);
+                ui.label(egui::RichText::new("Top values").strong());
+                egui::Grid::new("column_stats_top")
+                    .num_columns(2)
+                    .spacing([24.0, 2.0])
+                    .show(ui, |ui| {
+                        for (value, n) in top {
+                            ui.add(
+                                egui::Label::new(egui::RichText::new(value).monospace()).truncate(),
+                            );
+                            ui.label(n.to_string());
+                            ui.end_row();
+                        }
+                    });
+            }
+        });
+    if !open {
+        tabular.column_stats = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_follow_the_column_kind() {
+        let numeric = compute_column_stats(["3", "NULL", "1.5", "3"], None);
+        assert_eq!((numeric.count, numeric.nulls, numeric.distinct), (4, 1, 2));
+        assert_eq!(
+            numeric.summary,
+            Some(StatsSummary::Numeric {
+                min: 1.5,
+                max: 3.0,
+                mean: 2.5
+            })
+        );
+
+        let dates = compute_column_stats(["2024-03-01", "2023-12-31 08:00:00", "2024-01-15"], None);
+        assert_eq!(
+            dates.summary,
+            Some(StatsSummary::Temporal {
+                min: "2023-12-31 08:00:00".into(),
+                max: "2024-03-01".into()
+            })
+        );
+
+        // Numeric-looking text stays text when the column is declared as text
+        let zips = compute_column_stats(["02134", "02134", "9"], Some("VARCHAR(10)"));
+        let Some(StatsSummary::Text {
+            min_len,
+            max_len,
+            top,
+            ..
+        }) = zips.summary
+        else {
+            panic!("expected text stats");
+        };
+        assert_eq!((min_len, max_len), (1, 5));
+        assert_eq!(top[0], ("02134".to_string(), 2));
+
+        assert_eq!(compute_column_stats(["NULL"], None).summary, None);
+    }
+}
@@ -0,0 +1,196 @@
+//! Hiding result columns. The tab keeps the names of its hidden columns; the
+//! grid skips them while the data stays untouched, so a column can be shown
+//! again at any time. For browsed tables the hidden set is saved with the
+//! table's column layout (see `layout`).
+
+use eframe::egui;
+
+use crate::window_egui;
+
+/// Per-column flags for `headers`, true where the column is hidden.
+pub(crate) fn hidden_column_mask(tabular: &window_egui::Tabular, headers: &[String]) -> Vec<bool> {
+    let hidden = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .map(|t| t.hidden_columns.as_slice())
+        .unwrap_or_default();
+    headers.iter().map(|h| hidden.contains(h)).collect()
+}
+
+/// Indices of the columns to keep, or `None` when nothing is hidden.
+fn visible_indices(mask: &[bool]) -> Option<Vec<usize>> {
+    mask.contains(&true).then(|| {
+        mask.iter()
+            .enumerate()
+            .filter(|(_, hidden)| !**hidden)
+            .map(|(i, _)| i)
+            .collect()
+    })
+}
+
+/// Columns an export keeps: all of them unless hidden columns are left out.
+fn export_indices(tabular: &window_egui::Tabular) -> Option<Vec<usize>> {
+    if !tabular.export_skip_hidden_columns {
+        return None;
+    }
+    visible_indices(&hidden_column_mask(tabular, &tabular.current_table_headers))
+}
+
+/// Headers matching [`super::export_table_data`].
+pub(crate) fn export_headers(tabular: &window_egui::Tabular) -> Vec<String> {
+    match export_indices(tabular) {
+        Some(keep) => keep
+            .iter()
+            .filter_map(|&i| tabular.current_table_headers.get(i).cloned())
+            .collect(),
+        None => tabular.current_table_headers.clone(),
+    }
+}
+
+/// Drop hidden columns from export rows when exports leave them out.
+pub(crate) fn drop_hidden_for_export(
+    tabular: &window_egui::Tabular,
+    rows: Vec<Vec<String>>,
+) -> Vec<Vec<String>> {
+    match export_indices(tabular) {
+        Some(keep) => rows
+            .into_iter()
+            .map(|row| keep.iter().filter_map(|&i| row.get(i).cloned()).collect())
+            .collect(),
+        None => rows,
+    }
+}
+
+/// Hide or show `column`. The last visible column cannot be hidden.
+pub(crate) fn set_column_hidden(tabular: &mut window_egui::Tabular, column: &str, hidden: bool) {
+    let visible = tabular.current_table_headers.len();
+    let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+        return;
+    };
+    let present = tab.hidden_columns.iter().any(|c| c == column);
+    if hidden && !present {
+        let hidden_now = tabular
+            .current_table_headers
+            .iter()
+            .filter(|h| tab.hidden_columns.contains(h))
+            .count();
+        if hidden_now + 1 >= visible {
+            tabular.toasts.info("At least one column stays visible");
+            return;
+        }
+        tab.hidden_columns.push(column.to_string());
+        tab.hidden_columns_headers = tabular.current_table_headers.clone();
+    } else if !hidden && present {
+        tab.hidden_columns.retain(|c| c != column);
+    } else {
+        return;
+    }
+    super::remember_column_layout(tabular);
+}
+
+pub(crate) fn show_all_columns(tabular: &mut window_egui::Tabular) {
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index)
+        && !tab.hidden_columns.is_empty()
+    {
+        tab.hidden_columns.clear();
+        super::remember_column_layout(tabular);
+    }
+}
+
+/// "Manage Columns" window: one checkbox per column.
+pub(crate) fn render_manage_columns(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    if !tabular.show_manage_columns {
+        return;
+    }
+    let headers = tabular.current_table_headers.clone();
+    let mask = hidden_column_mask(tabular, &headers);
+    let mut open = true;
+    let mut toggled: Option<(String, bool)> = None;
+    let mut show_all = false;
+    let mut export_changed = false;
+    egui::Window::new("☰ Manage Columns")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(280.0)
+        .open(&mut open)
+        .show(ctx, |ui| {
+            if headers.is_empty() {
+                ui.label("No result columns.");
+                return;
+            }
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} of {} shown",
+                    mask.iter().filter(|h| !**h).count(),
+                    headers.len()
+                ))
+                .weak(),
+            );
+            egui::ScrollArea::vertical()
+                .max_height(360.0)
+                .show(ui, |ui| {
+                    for (header, hidden) in headers.iter().zip(&mask) {
+                        let mut shown = !hidden;
+                        if ui.checkbox(&mut shown, header).changed() {
+                            toggled = Some((header.clone(), !shown));
+                        }
+                    }
+                });
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(mask.contains(&true), egui::Button::new("Show All"))
+                    .clicked()
+                {
+                    show_all = true;
+                }
+            });
+            export_changed = ui
+                .checkbox(
+                    &mut tabular.export_skip_hidden_columns,
+                    "Leave hidden columns out of exports",
+                )
+                .changed();
+        });
+
+    if let Some((column, hidden)) = toggled {
+        set_column_hidden(tabular, &column, hidden);
+    }
+    if show_all {
+        show_all_columns(tabular);
+    }
+    if export_changed {
+        tabular.prefs_dirty = true;
+        tabular.try_save_prefs();
+    }
+    if !open {
+        tabular.show_manage_columns = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_columns_drop_out_of_the_visible_set() {
+        assert_eq!(visible_indices(&[false, false]), None);
+        assert_eq!(visible_indices(&[false, true, false]), Some(vec![0, 2]));
+        assert_eq!(visible_indices(&[]), None);
+    }
+
+    #[test]
+    fn hidden_columns_reset_when_the_result_headers_change() {
+        let mut tabular = window_egui::Tabular::default();
+        crate::editor::create_new_tab(&mut tabular, "Query".to_string(), String::new());
+        let headers = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        tabular.current_table_headers = headers(&["id", "name"]);
+        set_column_hidden(&mut tabular, "name", true);
+        crate::data_table::initialize_column_widths(&mut tabular);
+        assert_eq!(hidden_column_mask(&tabular, &tabular.current_table_headers), [false, true]);
+
+        tabular.current_table_headers = headers(&["name", "total"]);
+        crate::data_table::initialize_column_widths(&mut tabular);
+        assert_eq!(hidden_column_mask(&tabular, &tabular.current_table_headers), [false, false]);
+    }
+}
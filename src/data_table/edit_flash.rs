@@ -0,0 +1,142 @@
+//! Feedback for committed grid edits: the cells (or inserted rows) a save
+//! touched flash green when it ran and red when it failed, fading out over
+//! [`FLASH_DURATION`]. Failed updates are reverted to their previous value.
+
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+
+use crate::{models::structs::CellEditOperation, window_egui};
+
+pub(crate) const FLASH_DURATION: Duration = Duration::from_millis(1000);
+
+#[derive(Clone, Debug)]
+pub struct EditFlash {
+    pub row: usize,
+    /// `None` flashes the whole row (inserted rows, failed deletes).
+    pub col: Option<usize>,
+    pub ok: bool,
+    pub at: Instant,
+}
+
+impl EditFlash {
+    fn covers(&self, row: usize, col: usize) -> bool {
+        self.row == row && self.col.is_none_or(|c| c == col)
+    }
+}
+
+/// Remaining strength of a flash started at `at`: 1.0 right away, fading
+/// linearly to nothing after [`FLASH_DURATION`].
+pub(crate) fn flash_strength(at: Instant, now: Instant) -> Option<f32> {
+    let elapsed = now.saturating_duration_since(at);
+    (elapsed < FLASH_DURATION).then(|| 1.0 - elapsed.as_secs_f32() / FLASH_DURATION.as_secs_f32())
+}
+
+/// Flash the cells a save touched.
+pub(crate) fn flash_saved_edits(
+    tabular: &mut window_egui::Tabular,
+    ops: &[CellEditOperation],
+    ok: bool,
+) {
+    let at = Instant::now();
+    for op in ops {
+        let (row, col) = match op {
+            CellEditOperation::Update {
+                row_index,
+                col_index,
+                ..
+            } => (*row_index, Some(*col_index)),
+            CellEditOperation::InsertRow { row_index, .. } => (*row_index, None),
+            // A deleted row is gone once the save succeeds
+            CellEditOperation::DeleteRow { row_index, .. } if !ok => (*row_index, None),
+            CellEditOperation::DeleteRow { .. } => continue,
+        };
+        tabular
+            .edit_flashes
+            .retain(|f| !(f.row == row && f.col == col));
+        tabular.edit_flashes.push(EditFlash { row, col, ok, at });
+    }
+}
+
+/// Put the cells of failed updates back to their previous value and drop
+/// those updates from the pending list. Inserts and deletes stay pending.
+pub(crate) fn revert_failed_updates(tabular: &mut window_egui::Tabular, ops: &[CellEditOperation]) {
+    for op in ops.iter().rev() {
+        if let CellEditOperation::Update {
+            row_index,
+            col_index,
+            old_value,
+            ..
+        } = op
+        {
+            for rows in [&mut tabular.current_table_data, &mut tabular.all_table_data] {
+                if let Some(cell) = rows.get_mut(*row_index).and_then(|r| r.get_mut(*col_index)) {
+                    *cell = old_value.clone();
+                }
+            }
+        }
+    }
+    let state = &mut tabular.spreadsheet_state;
+    state
+        .pending_operations
+        .retain(|op| !matches!(op, CellEditOperation::Update { .. }));
+    state.is_dirty = !state.pending_operations.is_empty();
+}
+
+/// Drop faded flashes and keep repainting while any are visible.
+pub(crate) fn tick_edit_flashes(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let now = Instant::now();
+    tabular
+        .edit_flashes
+        .retain(|f| flash_strength(f.at, now).is_some());
+    if !tabular.edit_flashes.is_empty() {
+        ctx.request_repaint();
+    }
+}
+
+/// Background of cell (`row`, `col`) while it is flashing.
+pub(crate) fn edit_flash_color(
+    flashes: &[EditFlash],
+    row: usize,
+    col: usize,
+    dark: bool,
+) -> Option<egui::Color32> {
+    let flash = flashes.iter().rev().find(|f| f.covers(row, col))?;
+    let strength = flash_strength(flash.at, Instant::now())?;
+    let (r, g, b) = match (flash.ok, dark) {
+        (true, true) => (60, 200, 110),
+        (true, false) => (40, 170, 80),
+        (false, true) => (230, 70, 70),
+        (false, false) => (220, 50, 50),
+    };
+    let alpha = if dark { 110.0 } else { 90.0 };
+    Some(egui::Color32::from_rgba_unmultiplied(
+        r,
+        g,
+        b,
+        (alpha * strength) as u8,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flash_fades_out_over_the_duration() {
+        let start = Instant::now();
+        assert_eq!(flash_strength(start, start), Some(1.0));
+        let half = flash_strength(start, start + FLASH_DURATION / 2).unwrap();
+        assert!((half - 0.5).abs() < 1e-3);
+        assert_eq!(flash_strength(start, start + FLASH_DURATION), None);
+
+        let row_flash = EditFlash {
+            row: 3,
+            col: None,
+            ok: true,
+            at: start,
+        };
+        assert!(row_flash.covers(3, 7));
+        assert!(!row_flash.covers(4, 7));
+    }
+}
@@ -0,0 +1,162 @@
+//! "Save result to SQLite" dialog: writes the current result set into a new
+//! table of a new or existing SQLite file, in the background.
+
+use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
+
+use eframe::egui;
+
+use crate::export::{self, SqliteTableConflict};
+use crate::window_egui;
+
+use super::export_table_data;
+
+pub struct SqliteExportState {
+    pub path: String,
+    pub table_name: String,
+    pub on_conflict: SqliteTableConflict,
+    pub receiver: Option<Receiver<Result<(String, usize), String>>>,
+}
+
+pub(crate) fn open_sqlite_export_dialog(tabular: &mut window_egui::Tabular) {
+    if tabular.current_table_headers.is_empty() {
+        tabular.toasts.info("No result to save");
+        return;
+    }
+    let previous_path = tabular
+        .sqlite_export_state
+        .as_ref()
+        .map(|s| s.path.clone())
+        .unwrap_or_default();
+    tabular.sqlite_export_state = Some(SqliteExportState {
+        path: previous_path,
+        table_name: export::table_name_from_caption(&tabular.current_table_name),
+        on_conflict: SqliteTableConflict::Rename,
+        receiver: None,
+    });
+}
+
+fn start_export(tabular: &mut window_egui::Tabular) {
+    let rows = export_table_data(tabular);
+    let headers = super::export_headers(tabular);
+    let Some(state) = tabular.sqlite_export_state.as_mut() else {
+        return;
+    };
+    let path = PathBuf::from(state.path.trim());
+    let table = state.table_name.trim().to_string();
+    let on_conflict = state.on_conflict;
+    let (tx, rx) = std::sync::mpsc::channel();
+    state.receiver = Some(rx);
+    tabular.get_runtime().spawn(async move {
+        let result = export::write_sqlite_table(&path, &table, on_conflict, &headers, &rows).await;
+        let _ = tx.send(result);
+    });
+}
+
+pub(crate) fn render_sqlite_export_dialog(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(state) = tabular.sqlite_export_state.as_mut() else {
+        return;
+    };
+
+    if let Some(rx) = &state.receiver {
+        match rx.try_recv() {
+            Ok(result) => {
+                let path = state.path.clone();
+                tabular.sqlite_export_state = None;
+                match result {
+                    Ok((table, rows)) => tabular
+                        .toasts
+                        .success(format!("Saved {} row(s) to {} in {}", rows, table, path)),
+                    Err(e) => tabular
+                        .toasts
+                        .error(format!("Failed to save to SQLite: {e}")),
+                }
+                return;
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => ctx.request_repaint(),
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => state.receiver = None,
+        }
+    }
+
+    let running = state.receiver.is_some();
+    let mut open = true;
+    let mut start = false;
+    let mut cancel = false;
+    egui::Window::new("🗄 Save Result to SQLite")
+        .collapsible(false)
+        .resizable(false)
+        .default_width(440.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.add_enabled_ui(!running, |ui| {
+                egui::Grid::new("sqlite_export_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("File:");
+                        ui.horizontal(|ui| {
+                            ui.add(
+                                egui::TextEdit::singleline(&mut state.path).desired_width(240.0),
+                            );
+                            if ui.button("New…").clicked()
+                                && let Some(p) = rfd::FileDialog::new()
+                                    .add_filter("SQLite", &["sqlite", "db", "sqlite3"])
+                                    .set_file_name(format!("{}.sqlite", state.table_name))
+                                    .save_file()
+                            {
+                                state.path = p.display().to_string();
+                            }
+                            if ui.button("Existing…").clicked()
+                                && let Some(p) = rfd::FileDialog::new()
+                                    .add_filter("SQLite", &["sqlite", "db", "sqlite3"])
+                                    .pick_file()
+                            {
+                                state.path = p.display().to_string();
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label("Table:");
+                        ui.text_edit_singleline(&mut state.table_name);
+                        ui.end_row();
+
+                        ui.label("If it exists:");
+                        ui.horizontal(|ui| {
+                            ui.radio_value(
+                                &mut state.on_conflict,
+                                SqliteTableConflict::Rename,
+                                "Add a suffix",
+                            );
+                            ui.radio_value(
+                                &mut state.on_conflict,
+                                SqliteTableConflict::Replace,
+                                "Replace table",
+                            );
+                        });
+                        ui.end_row();
+                    });
+            });
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if running {
+                    ui.spinner();
+                    ui.label("Saving…");
+                } else {
+                    let ready =
+                        !state.path.trim().is_empty() && !state.table_name.trim().is_empty();
+                    if ui.add_enabled(ready, egui::Button::new("Save")).clicked() {
+                        start = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                }
+            });
+        });
+
+    if start {
+        start_export(tabular);
+    } else if (cancel || !open) && !running {
+        tabular.sqlite_export_state = None;
+    }
+}
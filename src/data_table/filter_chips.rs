@@ -0,0 +1,292 @@
+//! Quick filter chips for table browsing.
+//!
+//! A chip is a `column = value` condition picked from a cell ("Filter by this
+//! value") or from a column header's list of distinct values. Chips on the
+//! same column are OR-ed (`IN (...)`), chips on different columns AND-ed, and
+//! the result is AND-ed with the free-text WHERE filter before
+//! `apply_sql_filter` re-runs the browse query through pagination. Values are
+//! always emitted as escaped literals with quoted identifiers, never as raw
+//! text.
+
+use eframe::egui;
+use log::debug;
+
+use super::{apply_sql_filter, column_source, fetch_distinct_values};
+use crate::{models::enums::DatabaseType, window_egui};
+
+/// Distinct values listed in a column header menu.
+const DISTINCT_VALUE_LIMIT: usize = 100;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilterChip {
+    pub column: String,
+    /// `None` filters on `IS NULL`.
+    pub value: Option<String>,
+}
+
+impl FilterChip {
+    /// Chip for a grid cell, where the literal `NULL` marker means SQL NULL.
+    pub fn from_cell(column: &str, cell: &str) -> Self {
+        Self {
+            column: column.to_string(),
+            value: (cell != "NULL").then(|| cell.to_string()),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match &self.value {
+            Some(v) => format!("{} = {}", self.column, v),
+            None => format!("{} IS NULL", self.column),
+        }
+    }
+}
+
+/// Capped distinct values of one column; `more` when the cap was hit.
+#[derive(Clone, Debug, Default)]
+pub struct DistinctValues {
+    pub values: Vec<String>,
+    pub more: bool,
+}
+
+pub(super) fn quote_ident(name: &str, db_type: &DatabaseType) -> String {
+    match db_type {
+        DatabaseType::MySQL => format!("`{}`", name.replace('`', "``")),
+        DatabaseType::MsSQL => format!("[{}]", name.replace(']', "]]")),
+        _ => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+fn quote_literal(value: &str, db_type: &DatabaseType) -> String {
+    match db_type {
+        DatabaseType::MySQL => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''")),
+        DatabaseType::MsSQL => format!("N'{}'", value.replace('\'', "''")),
+        _ => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+/// WHERE condition for `chips`, empty when there are none.
+pub(crate) fn chips_where_clause(chips: &[FilterChip], db_type: &DatabaseType) -> String {
+    let mut columns: Vec<&str> = Vec::new();
+    for chip in chips {
+        if !columns.contains(&chip.column.as_str()) {
+            columns.push(&chip.column);
+        }
+    }
+    columns
+        .iter()
+        .map(|column| {
+            let ident = quote_ident(column, db_type);
+            let on_column = chips.iter().filter(|c| c.column == *column);
+            let has_null = on_column.clone().any(|c| c.value.is_none());
+            let literals: Vec<String> = on_column
+                .filter_map(|c| c.value.as_deref())
+                .map(|v| quote_literal(v, db_type))
+                .collect();
+            let mut parts = Vec::new();
+            match literals.len() {
+                0 => {}
+                1 => parts.push(format!("{} = {}", ident, literals[0])),
+                _ => parts.push(format!("{} IN ({})", ident, literals.join(", "))),
+            }
+            if has_null {
+                parts.push(format!("{} IS NULL", ident));
+            }
+            if parts.len() > 1 {
+                format!("({})", parts.join(" OR "))
+            } else {
+                parts.join("")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" AND ")
+}
+
+/// Free-text WHERE filter combined with the active chips.
+pub(crate) fn effective_filter(tabular: &window_egui::Tabular, db_type: &DatabaseType) -> String {
+    let text = tabular.sql_filter_text.trim();
+    let chips = chips_where_clause(&tabular.filter_chips, db_type);
+    match (text.is_empty(), chips.is_empty()) {
+        (true, _) => chips,
+        (false, true) => text.to_string(),
+        (false, false) => format!("({}) AND {}", text, chips),
+    }
+}
+
+pub(crate) fn add_filter_chip(tabular: &mut window_egui::Tabular, chip: FilterChip) {
+    if !tabular.filter_chips.contains(&chip) {
+        tabular.filter_chips.push(chip);
+        apply_sql_filter(tabular);
+    }
+}
+
+/// Drop every chip and the free-text filter, back to the unfiltered table.
+pub(crate) fn clear_all_filters(tabular: &mut window_egui::Tabular) {
+    tabular.filter_chips.clear();
+    tabular.sql_filter_text.clear();
+    apply_sql_filter(tabular);
+}
+
+/// Distinct values of result column `col_index`, fetched once per column.
+fn distinct_values_for_column(
+    tabular: &mut window_egui::Tabular,
+    col_index: usize,
+) -> Option<DistinctValues> {
+    let key = column_source(tabular, col_index)?;
+    if let Some(cached) = tabular.column_distinct_values.get(&key) {
+        return cached.clone();
+    }
+    let (connection_id, database_name, table_name, column_name) = key.clone();
+    let found = tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let pool = crate::connection::get_or_create_connection_pool(tabular, connection_id).await?;
+        match fetch_distinct_values(
+            &pool,
+            &database_name,
+            &table_name,
+            &column_name,
+            DISTINCT_VALUE_LIMIT + 1,
+        )
+        .await?
+        {
+            Ok(mut values) => {
+                let more = values.len() > DISTINCT_VALUE_LIMIT;
+                values.truncate(DISTINCT_VALUE_LIMIT);
+                Some(DistinctValues { values, more })
+            }
+            Err(e) => {
+                debug!(
+                    "Distinct values for {}.{} unavailable: {}",
+                    table_name, column_name, e
+                );
+                None
+            }
+        }
+    });
+    tabular.column_distinct_values.insert(key, found.clone());
+    found
+}
+
+/// Body of a column header's value menu; returns the chip picked, if any.
+pub(crate) fn column_value_menu(
+    tabular: &mut window_egui::Tabular,
+    ui: &mut egui::Ui,
+    col_index: usize,
+) -> Option<FilterChip> {
+    let column = tabular.current_table_headers.get(col_index)?.clone();
+    let Some(distinct) = distinct_values_for_column(tabular, col_index) else {
+        ui.label(egui::RichText::new("Values unavailable").weak());
+        return None;
+    };
+    let is_active = |value: Option<&str>| {
+        tabular
+            .filter_chips
+            .iter()
+            .any(|c| c.column == column && c.value.as_deref() == value)
+    };
+    let mut picked = None;
+    egui::ScrollArea::vertical()
+        .max_height(280.0)
+        .show(ui, |ui| {
+            if ui.selectable_label(is_active(None), "NULL").clicked() {
+                picked = Some(FilterChip {
+                    column: column.clone(),
+                    value: None,
+                });
+            }
+            for value in &distinct.values {
+                if ui.selectable_label(is_active(Some(value)), value).clicked() {
+                    picked = Some(FilterChip {
+                        column: column.clone(),
+                        value: Some(value.clone()),
+                    });
+                }
+            }
+        });
+    if distinct.more {
+        ui.label(egui::RichText::new("…more").weak())
+            .on_hover_text(format!(
+                "Only the first {} values are listed; use the WHERE filter for others",
+                DISTINCT_VALUE_LIMIT
+            ));
+    }
+    if picked.is_some() {
+        ui.close();
+    }
+    picked
+}
+
+/// Row of active chips under the WHERE filter, with per-chip remove and
+/// "Clear all".
+pub(crate) fn render_filter_chips(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
+    if tabular.filter_chips.is_empty() {
+        return;
+    }
+    let mut remove: Option<usize> = None;
+    let mut clear_all = false;
+    ui.horizontal_wrapped(|ui| {
+        ui.label(egui::RichText::new("Filters:").weak());
+        for (i, chip) in tabular.filter_chips.iter().enumerate() {
+            if ui
+                .button(format!("{}  ✕", chip.label()))
+                .on_hover_text("Remove this filter")
+                .clicked()
+            {
+                remove = Some(i);
+            }
+        }
+        if ui.small_button("Clear all").clicked() {
+            clear_all = true;
+        }
+    });
+    if clear_all {
+        clear_all_filters(tabular);
+    } else if let Some(i) = remove {
+        tabular.filter_chips.remove(i);
+        apply_sql_filter(tabular);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chip(column: &str, value: Option<&str>) -> FilterChip {
+        FilterChip {
+            column: column.to_string(),
+            value: value.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn chips_group_by_column_and_escape() {
+        let chips = vec![
+            chip("status", Some("open")),
+            chip("owner", Some("o'brien")),
+            chip("status", Some("closed")),
+            chip("status", None),
+        ];
+        assert_eq!(
+            chips_where_clause(&chips, &DatabaseType::PostgreSQL),
+            "(\"status\" IN ('open', 'closed') OR \"status\" IS NULL) AND \"owner\" = 'o''brien'"
+        );
+        assert_eq!(
+            chips_where_clause(&chips[1..2], &DatabaseType::MySQL),
+            "`owner` = 'o''brien'"
+        );
+        assert_eq!(
+            chips_where_clause(&[chip("a\\b", Some("x\\"))], &DatabaseType::MySQL),
+            "`a\\b` = 'x\\\\'"
+        );
+        assert_eq!(
+            chips_where_clause(&[chip("name", None)], &DatabaseType::MsSQL),
+            "[name] IS NULL"
+        );
+        assert_eq!(chips_where_clause(&[], &DatabaseType::SQLite), "");
+    }
+
+    #[test]
+    fn cell_null_marker_becomes_is_null() {
+        assert_eq!(FilterChip::from_cell("c", "NULL"), chip("c", None));
+        assert_eq!(FilterChip::from_cell("c", "null"), chip("c", Some("null")));
+        assert_eq!(FilterChip::from_cell("c", "NULL").label(), "c IS NULL");
+    }
+}
@@ -97,8 +97,9 @@ pub(crate) fn apply_sql_filter(tabular: &mut window_egui::Tabular) {
         .and_then(|t| t.database_name.clone())
         .unwrap_or_else(|| connection.database.clone());
 
-    // Build SQL query based on database type and filter
-    let sql_query = if tabular.sql_filter_text.trim().is_empty() {
+    // Build SQL query based on database type and filter (free text plus chips)
+    let filter = super::effective_filter(tabular, &connection.connection_type);
+    let sql_query = if filter.is_empty() {
         // No filter - get all data
         match connection.connection_type {
             models::enums::DatabaseType::MySQL => {
@@ -115,9 +116,10 @@ pub(crate) fn apply_sql_filter(tabular: &mut window_egui::Tabular) {
                     format!("SELECT * FROM \"{}\".\"{}\"", database_name, table_name)
                 }
             }
-            models::enums::DatabaseType::SQLite => {
-                format!("SELECT * FROM `{}`", table_name)
-            }
+            models::enums::DatabaseType::SQLite => format!(
+                "SELECT * FROM {}",
+                crate::driver_sqlite::qualified_table_name(Some(&database_name), &table_name)
+            ),
             models::enums::DatabaseType::MsSQL => {
                 driver_mssql::build_mssql_select_query(database_name, table_name)
                     .replace("SELECT TOP 100 *", "SELECT *")
@@ -131,12 +133,12 @@ pub(crate) fn apply_sql_filter(tabular: &mut window_egui::Tabular) {
                 if database_name.is_empty() {
                     format!(
                         "SELECT * FROM `{}` WHERE {}",
-                        table_name, tabular.sql_filter_text
+                        table_name, filter
                     )
                 } else {
                     format!(
                         "USE `{}`;\nSELECT * FROM `{}` WHERE {}",
-                        database_name, table_name, tabular.sql_filter_text
+                        database_name, table_name, filter
                     )
                 }
             }
@@ -144,31 +146,30 @@ pub(crate) fn apply_sql_filter(tabular: &mut window_egui::Tabular) {
                 if database_name.is_empty() {
                     format!(
                         "SELECT * FROM \"{}\" WHERE {}",
-                        table_name, tabular.sql_filter_text
+                        table_name, filter
                     )
                 } else {
                     format!(
                         "SELECT * FROM \"{}\".\"{}\" WHERE {}",
-                        database_name, table_name, tabular.sql_filter_text
+                        database_name, table_name, filter
                     )
                 }
             }
-            models::enums::DatabaseType::SQLite => {
-                format!(
-                    "SELECT * FROM `{}` WHERE {}",
-                    table_name, tabular.sql_filter_text
-                )
-            }
+            models::enums::DatabaseType::SQLite => format!(
+                "SELECT * FROM {} WHERE {}",
+                crate::driver_sqlite::qualified_table_name(Some(&database_name), &table_name),
+                filter
+            ),
             models::enums::DatabaseType::MsSQL => {
                 let base_query = driver_mssql::build_mssql_select_query(database_name, table_name)
                     .replace("SELECT TOP 100 *", "SELECT *");
                 if base_query.contains("WHERE") {
-                    format!("{} AND ({})", base_query, tabular.sql_filter_text)
+                    format!("{} AND ({})", base_query, filter)
                 } else {
                     format!(
                         "{} WHERE {}",
                         base_query.trim_end_matches(';'),
-                        tabular.sql_filter_text
+                        filter
                     )
                 }
             }
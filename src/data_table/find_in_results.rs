@@ -0,0 +1,292 @@
+//! Find in all results: searches the rows every open tab has loaded for a
+//! term and lists the matching cells. Only data already in memory is
+//! searched (no queries run), and the scan stops at `MAX_MATCHES` so a huge
+//! result cannot stall the window. Clicking a match opens its tab, page and
+//! cell.
+
+use eframe::egui;
+
+use super::{clear_table_selection, store_tab_pagination, update_current_page_data};
+use crate::{editor, window_egui};
+
+/// Matches listed at most; the search stops once it has this many.
+const MAX_MATCHES: usize = 500;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResultMatch {
+    pub tab: usize,
+    /// Row in the tab's loaded data: the whole result under client-side
+    /// paging, the current page otherwise.
+    pub row: usize,
+    pub column: usize,
+    pub column_name: String,
+    pub value: String,
+}
+
+#[derive(Default)]
+pub struct ResultSearchState {
+    pub term: String,
+    pub case_sensitive: bool,
+    pub matches: Vec<ResultMatch>,
+    /// The search stopped at `MAX_MATCHES`.
+    pub truncated: bool,
+    /// Term of the last search, to tell "no matches" from "not searched".
+    pub searched: Option<String>,
+}
+
+pub(crate) fn open_result_search(tabular: &mut window_egui::Tabular) {
+    if tabular.result_search.is_none() {
+        tabular.result_search = Some(ResultSearchState::default());
+    }
+}
+
+/// Matching cells of `rows`, appended to `out` until it holds `limit`.
+/// Returns false when the limit was reached.
+fn search_rows(
+    tab: usize,
+    headers: &[String],
+    rows: &[Vec<String>],
+    needle: &str,
+    case_sensitive: bool,
+    out: &mut Vec<ResultMatch>,
+    limit: usize,
+) -> bool {
+    for (row, cells) in rows.iter().enumerate() {
+        for (column, cell) in cells.iter().enumerate() {
+            let hit = if case_sensitive {
+                cell.contains(needle)
+            } else {
+                cell.to_lowercase().contains(needle)
+            };
+            if !hit {
+                continue;
+            }
+            if out.len() >= limit {
+                return false;
+            }
+            out.push(ResultMatch {
+                tab,
+                row,
+                column,
+                column_name: headers.get(column).cloned().unwrap_or_default(),
+                value: cell.clone(),
+            });
+        }
+    }
+    true
+}
+
+/// Loaded headers and rows of tab `index`. The active tab's result lives in
+/// the global grid state rather than on the tab.
+fn tab_rows(tabular: &window_egui::Tabular, index: usize) -> (&[String], &[Vec<String>]) {
+    if index == tabular.active_tab_index {
+        let rows = if tabular.all_table_data.is_empty() {
+            &tabular.current_table_data
+        } else {
+            &tabular.all_table_data
+        };
+        return (&tabular.current_table_headers, rows);
+    }
+    let tab = &tabular.query_tabs[index];
+    let rows = if tab.result_all_rows.is_empty() {
+        &tab.result_rows
+    } else {
+        &tab.result_all_rows
+    };
+    (&tab.result_headers, rows)
+}
+
+fn run_search(tabular: &mut window_egui::Tabular) {
+    let Some(state) = tabular.result_search.as_ref() else {
+        return;
+    };
+    let term = state.term.clone();
+    if term.is_empty() {
+        return;
+    }
+    let case_sensitive = state.case_sensitive;
+    let needle = if case_sensitive {
+        term.clone()
+    } else {
+        term.to_lowercase()
+    };
+    let mut matches = Vec::new();
+    let mut complete = true;
+    for index in 0..tabular.query_tabs.len() {
+        let (headers, rows) = tab_rows(tabular, index);
+        complete = search_rows(
+            index,
+            headers,
+            rows,
+            &needle,
+            case_sensitive,
+            &mut matches,
+            MAX_MATCHES,
+        );
+        if !complete {
+            break;
+        }
+    }
+    if let Some(state) = tabular.result_search.as_mut() {
+        state.matches = matches;
+        state.truncated = !complete;
+        state.searched = Some(term);
+    }
+}
+
+/// Activate the tab of `found` and select its cell, paging to it when the
+/// tab holds its whole result.
+fn reveal_match(tabular: &mut window_egui::Tabular, found: &ResultMatch) {
+    if found.tab >= tabular.query_tabs.len() {
+        return;
+    }
+    if found.tab != tabular.active_tab_index {
+        editor::switch_to_tab(tabular, found.tab);
+    }
+    clear_table_selection(tabular);
+    let mut row = found.row;
+    if !tabular.all_table_data.is_empty() && tabular.page_size > 0 {
+        let page = found.row / tabular.page_size;
+        if page != tabular.current_page {
+            tabular.current_page = page;
+            update_current_page_data(tabular);
+            store_tab_pagination(tabular);
+        }
+        row = found.row % tabular.page_size;
+    }
+    if row < tabular.current_table_data.len() {
+        tabular.selected_cell = Some((row, found.column));
+        tabular.selected_row = Some(row);
+        tabular.scroll_to_selected_cell = true;
+    }
+}
+
+pub(crate) fn render_result_search(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let tab_names: Vec<String> = tabular
+        .query_tabs
+        .iter()
+        .map(|t| t.display_name())
+        .collect();
+    let Some(state) = tabular.result_search.as_mut() else {
+        return;
+    };
+    let mut open = true;
+    let mut search = false;
+    let mut picked: Option<ResultMatch> = None;
+    egui::Window::new("🔍 Find in All Results")
+        .id(egui::Id::new("result_search_window"))
+        .open(&mut open)
+        .default_size(egui::vec2(560.0, 420.0))
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let resp = ui.add(
+                    egui::TextEdit::singleline(&mut state.term)
+                        .hint_text("Value to find in loaded results")
+                        .desired_width(300.0),
+                );
+                if state.searched.is_none() && !resp.has_focus() {
+                    resp.request_focus();
+                }
+                if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    search = true;
+                }
+                ui.checkbox(&mut state.case_sensitive, "Match case");
+                if ui
+                    .add_enabled(!state.term.is_empty(), egui::Button::new("Find"))
+                    .clicked()
+                {
+                    search = true;
+                }
+            });
+            ui.label(
+                egui::RichText::new(
+                    "Searches rows already loaded in each tab; other pages of server-paged results are not fetched.",
+                )
+                .size(11.0)
+                .weak(),
+            );
+            ui.separator();
+
+            if let Some(term) = &state.searched {
+                let summary = match (state.matches.len(), state.truncated) {
+                    (0, _) => format!("No loaded result contains \"{}\"", term),
+                    (n, true) => format!("First {} matches for \"{}\" — refine the term to see the rest", n, term),
+                    (n, false) => format!("{} match(es) for \"{}\"", n, term),
+                };
+                ui.label(summary);
+            }
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    egui::Grid::new("result_search_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .spacing([12.0, 4.0])
+                        .show(ui, |ui| {
+                            for found in &state.matches {
+                                let tab = tab_names.get(found.tab).map_or("?", String::as_str);
+                                ui.label(egui::RichText::new(tab).strong());
+                                ui.label(format!("row {}", found.row + 1));
+                                ui.label(&found.column_name);
+                                let value = super::cell_preview(&found.value, 80);
+                                if ui
+                                    .add(
+                                        egui::Label::new(egui::RichText::new(value).monospace())
+                                            .truncate()
+                                            .sense(egui::Sense::click()),
+                                    )
+                                    .on_hover_text("Show this cell")
+                                    .clicked()
+                                {
+                                    picked = Some(found.clone());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+    if !open {
+        tabular.result_search = None;
+        return;
+    }
+    if search {
+        run_search(tabular);
+    }
+    if let Some(found) = picked {
+        reveal_match(tabular, &found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_finds_cells_and_stops_at_limit() {
+        let headers = vec!["id".to_string(), "email".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "Ann@example.com".to_string()],
+            vec!["2".to_string(), "bob@example.org".to_string()],
+            vec!["3".to_string(), "ann.lee@example.com".to_string()],
+        ];
+        let mut out = Vec::new();
+        assert!(search_rows(4, &headers, &rows, "ann", false, &mut out, 10));
+        assert_eq!(
+            out.iter()
+                .map(|m| (m.tab, m.row, m.column))
+                .collect::<Vec<_>>(),
+            vec![(4, 0, 1), (4, 2, 1)]
+        );
+        assert_eq!(out[0].column_name, "email");
+
+        let mut out = Vec::new();
+        assert!(search_rows(0, &headers, &rows, "ann", true, &mut out, 10));
+        assert_eq!(out.len(), 1);
+
+        let mut out = Vec::new();
+        assert!(!search_rows(
+            0, &headers, &rows, "example", false, &mut out, 2
+        ));
+        assert_eq!(out.len(), 2);
+    }
+}
@@ -0,0 +1,339 @@
+use chrono::DateTime;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+use super::{NumberFormat, RawValue, is_numeric_type, number_argument};
+use crate::window_egui;
+
+/// A display transform for result cells, picked per column by a
+/// [`ColumnFormatRule`]. Stored values are never changed; editing and (unless
+/// the export option is on) exporting use the raw text.
+pub(crate) trait ColumnFormatter: Sync {
+    /// Stable key saved in preferences.
+    fn id(&self) -> &'static str;
+    fn label(&self) -> &'static str;
+    /// Hint for the rule's argument field; `None` when the formatter has none.
+    fn argument_hint(&self) -> Option<&'static str> {
+        None
+    }
+    /// Display text for `value`, or `None` to show it unchanged.
+    fn format(&self, value: &str, argument: &str) -> Option<String>;
+}
+
+struct EpochMillis;
+struct EpochSeconds;
+struct ByteSize;
+struct ValueLabels;
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+/// `None` when `argument` is not a valid format string (chrono reports that
+/// while writing, so it cannot go through `to_string`).
+fn format_date(date: DateTime<chrono::Utc>, argument: &str) -> Option<String> {
+    let pattern = match argument.trim() {
+        "" => DEFAULT_DATE_FORMAT,
+        custom => custom,
+    };
+    let mut out = String::new();
+    write!(out, "{}", date.format(pattern)).ok()?;
+    Some(out)
+}
+
+impl ColumnFormatter for EpochMillis {
+    fn id(&self) -> &'static str {
+        "epoch_millis"
+    }
+    fn label(&self) -> &'static str {
+        "Epoch milliseconds → date (UTC)"
+    }
+    fn argument_hint(&self) -> Option<&'static str> {
+        Some("strftime format, e.g. %Y-%m-%d")
+    }
+    fn format(&self, value: &str, argument: &str) -> Option<String> {
+        let millis = value.trim().parse::<i64>().ok()?;
+        DateTime::from_timestamp_millis(millis).and_then(|date| format_date(date, argument))
+    }
+}
+
+impl ColumnFormatter for EpochSeconds {
+    fn id(&self) -> &'static str {
+        "epoch_seconds"
+    }
+    fn label(&self) -> &'static str {
+        "Epoch seconds → date (UTC)"
+    }
+    fn argument_hint(&self) -> Option<&'static str> {
+        Some("strftime format, e.g. %Y-%m-%d")
+    }
+    fn format(&self, value: &str, argument: &str) -> Option<String> {
+        let seconds = value.trim().parse::<i64>().ok()?;
+        DateTime::from_timestamp(seconds, 0).and_then(|date| format_date(date, argument))
+    }
+}
+
+impl ColumnFormatter for ByteSize {
+    fn id(&self) -> &'static str {
+        "bytes"
+    }
+    fn label(&self) -> &'static str {
+        "Byte count (KiB, MiB, …)"
+    }
+    fn format(&self, value: &str, _argument: &str) -> Option<String> {
+        value.trim().parse::<f64>().ok().map(format_byte_size)
+    }
+}
+
+/// `1536` → `1.5 KiB`; plain bytes below 1 KiB.
+pub(crate) fn format_byte_size(bytes: f64) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+    let mut size = bytes.abs();
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    let sign = if bytes < 0.0 { "-" } else { "" };
+    if unit == 0 {
+        format!("{}{} B", sign, size)
+    } else {
+        format!("{}{:.1} {}", sign, size, UNITS[unit])
+    }
+}
+
+impl ColumnFormatter for ValueLabels {
+    fn id(&self) -> &'static str {
+        "value_labels"
+    }
+    fn label(&self) -> &'static str {
+        "Value labels"
+    }
+    fn argument_hint(&self) -> Option<&'static str> {
+        Some("1=Active, 2=Suspended, …")
+    }
+    fn format(&self, value: &str, argument: &str) -> Option<String> {
+        let value = value.trim();
+        argument.split(',').find_map(|pair| {
+            let (key, label) = pair.split_once('=')?;
+            (key.trim() == value).then(|| label.trim().to_string())
+        })
+    }
+}
+
+/// Every formatter a rule can name. New formatters implement
+/// [`ColumnFormatter`] and are listed here.
+pub(crate) static COLUMN_FORMATTERS: &[&dyn ColumnFormatter] =
+    &[&EpochMillis, &EpochSeconds, &ByteSize, &ValueLabels, &NumberFormat, &RawValue];
+
+pub(crate) fn column_formatter(id: &str) -> Option<&'static dyn ColumnFormatter> {
+    COLUMN_FORMATTERS.iter().copied().find(|f| f.id() == id)
+}
+
+/// Applies a formatter to the result columns matching `pattern`: a column
+/// name glob (`*_at_ms`), or `type:` followed by a type glob (`type:bigint`).
+/// Matching is case-insensitive; the first matching rule wins.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColumnFormatRule {
+    pub pattern: String,
+    pub formatter: String,
+    #[serde(default)]
+    pub argument: String,
+}
+
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let text = text.to_lowercase();
+    let parts: Vec<&str> = pattern.split('*').collect();
+    if parts.len() == 1 {
+        return pattern == text;
+    }
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !text.starts_with(first) || !text[first.len()..].ends_with(last) {
+        return false;
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+impl ColumnFormatRule {
+    fn matches(&self, column: &str, type_name: Option<&str>) -> bool {
+        let pattern = self.pattern.trim();
+        if pattern.is_empty() {
+            return false;
+        }
+        match pattern.strip_prefix("type:") {
+            Some(type_pattern) => type_name.is_some_and(|t| glob_matches(type_pattern.trim(), t)),
+            None => glob_matches(pattern, column),
+        }
+    }
+}
+
+/// Formatter and argument for one result column.
+pub(crate) type ResolvedFormatter = (&'static dyn ColumnFormatter, String);
+
+/// Per-column formatter picked by the rules, falling back to the number
+/// formatter for numeric columns when digit grouping is on; empty when
+/// neither applies.
+pub(crate) fn formatted_columns(tabular: &window_egui::Tabular) -> Vec<Option<ResolvedFormatter>> {
+    if tabular.column_format_rules.is_empty() && !tabular.number_format {
+        return Vec::new();
+    }
+    let types = tabular.current_column_metadata.as_ref();
+    tabular
+        .current_table_headers
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let type_name = types
+                .and_then(|meta| meta.get(i))
+                .map(|m| m.type_name.as_str());
+            tabular
+                .column_format_rules
+                .iter()
+                .find(|rule| rule.matches(column, type_name))
+                .map(|rule| (rule.formatter.as_str(), rule.argument.as_str()))
+                .or_else(|| {
+                    (tabular.number_format && type_name.is_some_and(is_numeric_type))
+                        .then_some(("number", ""))
+                })
+                .and_then(|(id, argument)| {
+                    let argument = match id {
+                        "number" => number_argument(tabular, argument),
+                        _ => argument.to_string(),
+                    };
+                    column_formatter(id).map(|f| (f, argument))
+                })
+        })
+        .collect()
+}
+
+/// `cell` as column `col_index` displays it, or `None` when no formatter
+/// applies (NULL, unparseable input, unformatted column).
+pub(crate) fn format_cell(
+    formatters: &[Option<ResolvedFormatter>],
+    col_index: usize,
+    cell: &str,
+) -> Option<String> {
+    let (formatter, argument) = formatters.get(col_index)?.as_ref()?;
+    if cell == "NULL" {
+        return None;
+    }
+    formatter.format(cell, argument)
+}
+
+/// Rule editor for Preferences → Data Grid. Returns true when a rule changed.
+pub(crate) fn render_formatter_settings(
+    tabular: &mut window_egui::Tabular,
+    ui: &mut egui::Ui,
+) -> bool {
+    let mut changed = false;
+    let mut remove = None;
+    egui::Grid::new("column_formatter_rules")
+        .num_columns(4)
+        .spacing([8.0, 4.0])
+        .show(ui, |ui| {
+            for (index, rule) in tabular.column_format_rules.iter_mut().enumerate() {
+                changed |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut rule.pattern)
+                            .desired_width(140.0)
+                            .hint_text("*_ms or type:bigint"),
+                    )
+                    .changed();
+                let selected = column_formatter(&rule.formatter);
+                egui::ComboBox::from_id_salt(("column_formatter", index))
+                    .selected_text(selected.map(|f| f.label()).unwrap_or("Choose…"))
+                    .show_ui(ui, |ui| {
+                        for formatter in COLUMN_FORMATTERS {
+                            if ui
+                                .selectable_label(
+                                    rule.formatter == formatter.id(),
+                                    formatter.label(),
+                                )
+                                .clicked()
+                            {
+                                rule.formatter = formatter.id().to_string();
+                                changed = true;
+                            }
+                        }
+                    });
+                match selected.and_then(|f| f.argument_hint()) {
+                    Some(hint) => {
+                        changed |= ui
+                            .add(
+                                egui::TextEdit::singleline(&mut rule.argument)
+                                    .desired_width(180.0)
+                                    .hint_text(hint),
+                            )
+                            .changed();
+                    }
+                    None => {
+                        ui.label("");
+                    }
+                }
+                if ui.small_button("🗑").on_hover_text("Remove rule").clicked() {
+                    remove = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+    if let Some(index) = remove {
+        tabular.column_format_rules.remove(index);
+        changed = true;
+    }
+    if ui.small_button("➕ Add formatter rule").clicked() {
+        tabular.column_format_rules.push(ColumnFormatRule {
+            formatter: COLUMN_FORMATTERS[0].id().to_string(),
+            ..Default::default()
+        });
+        changed = true;
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_formatters_render_domain_values() {
+        assert_eq!(
+            EpochMillis.format("1700000000123", "").as_deref(),
+            Some("2023-11-14 22:13:20.123")
+        );
+        assert_eq!(
+            EpochSeconds.format("1700000000", "%Y-%m-%d").as_deref(),
+            Some("2023-11-14")
+        );
+        assert_eq!(EpochSeconds.format("1700000000", "%Q"), None);
+        assert_eq!(ByteSize.format("1536", "").as_deref(), Some("1.5 KiB"));
+        assert_eq!(ByteSize.format("512", "").as_deref(), Some("512 B"));
+        assert_eq!(
+            ValueLabels.format("2", "1=Active, 2=Suspended").as_deref(),
+            Some("Suspended")
+        );
+        assert_eq!(ValueLabels.format("3", "1=Active"), None);
+        assert_eq!(ByteSize.format("n/a", ""), None);
+    }
+
+    #[test]
+    fn rules_match_column_names_and_types() {
+        let rule = |pattern: &str| ColumnFormatRule {
+            pattern: pattern.to_string(),
+            formatter: "bytes".to_string(),
+            argument: String::new(),
+        };
+        assert!(rule("*_bytes").matches("Disk_Bytes", None));
+        assert!(rule("created*ms").matches("created_at_ms", None));
+        assert!(!rule("size").matches("size_kb", None));
+        assert!(rule("type:big*").matches("anything", Some("BIGINT")));
+        assert!(!rule("type:bigint").matches("bigint", None));
+        assert!(!rule("").matches("x", None));
+    }
+}
@@ -0,0 +1,239 @@
+//! Index hints for slow queries. After a query that ran longer than the
+//! configured threshold, the columns it filters or joins on are checked
+//! against the cached indexes of their tables; a column that leads no index
+//! gets a suggestion above the grid. Nothing is ever created from here: the
+//! suggestion only opens the index dialog prefilled.
+
+use std::time::Duration;
+
+use eframe::egui;
+
+use crate::{cache_data, connection, models, query_tools, window_egui};
+
+/// Most suggestions shown for one query.
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Clone, Debug)]
+pub struct IndexSuggestion {
+    pub connection_id: i64,
+    pub database: String,
+    pub table: String,
+    pub column: String,
+}
+
+impl IndexSuggestion {
+    fn index_name(&self) -> String {
+        format!("idx_{}_{}", self.table, self.column)
+    }
+
+    fn dismiss_key(&self) -> (i64, String, String) {
+        (
+            self.connection_id,
+            self.table.to_ascii_lowercase(),
+            self.column.to_ascii_lowercase(),
+        )
+    }
+}
+
+/// Whether an index on `column` already exists: it must be the index's first
+/// column for a filter on it alone to use the index.
+fn has_leading_index(indexes: &[models::structs::IndexStructInfo], column: &str) -> bool {
+    indexes.iter().any(|index| {
+        index.columns.first().is_some_and(|c| {
+            c.trim_matches(['"', '`', '[', ']'])
+                .eq_ignore_ascii_case(column)
+        })
+    })
+}
+
+/// Recompute the active tab's index hints for a query result. Tables whose
+/// columns or indexes are not cached yet get no hint, since an empty index
+/// cache cannot tell "no indexes" from "not loaded".
+pub(crate) fn suggest_indexes(
+    tabular: &mut window_egui::Tabular,
+    message: &connection::QueryResultMessage,
+) {
+    let suggestions = if message.success
+        && tabular.index_hints
+        && message.duration >= Duration::from_millis(tabular.index_hint_min_ms)
+    {
+        find_suggestions(tabular, &message.query, message.connection_id)
+    } else {
+        Vec::new()
+    };
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.index_suggestions = suggestions;
+    }
+}
+
+fn find_suggestions(
+    tabular: &mut window_egui::Tabular,
+    query: &str,
+    connection_id: i64,
+) -> Vec<IndexSuggestion> {
+    let Some(conn) = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(connection_id))
+    else {
+        return Vec::new();
+    };
+    if !matches!(
+        conn.connection_type,
+        models::enums::DatabaseType::MySQL
+            | models::enums::DatabaseType::PostgreSQL
+            | models::enums::DatabaseType::SQLite
+            | models::enums::DatabaseType::MsSQL
+    ) {
+        return Vec::new();
+    }
+    let database = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.database_name.clone())
+        .filter(|db| !db.is_empty())
+        .unwrap_or_else(|| conn.database.clone());
+
+    let mut suggestions: Vec<IndexSuggestion> = Vec::new();
+    for filtered in query_tools::filtered_columns(query) {
+        // The one table (of the candidates) that has this column
+        let mut owners = filtered.tables.iter().filter_map(|table| {
+            cache_data::get_columns_from_cache(tabular, connection_id, &database, table)?
+                .into_iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&filtered.column))
+                .map(|(name, _)| (table.clone(), name))
+        });
+        let (Some((table, column)), None) = (owners.next(), owners.next()) else {
+            continue;
+        };
+        let suggestion = IndexSuggestion {
+            connection_id,
+            database: database.clone(),
+            table,
+            column,
+        };
+        if tabular
+            .dismissed_index_hints
+            .contains(&suggestion.dismiss_key())
+            || suggestions
+                .iter()
+                .any(|s| s.dismiss_key() == suggestion.dismiss_key())
+        {
+            continue;
+        }
+        match cache_data::get_indexes_from_cache(
+            tabular,
+            connection_id,
+            &database,
+            &suggestion.table,
+        ) {
+            Some(indexes)
+                if !indexes.is_empty() && !has_leading_index(&indexes, &suggestion.column) =>
+            {
+                suggestions.push(suggestion);
+            }
+            _ => {}
+        }
+        if suggestions.len() == MAX_SUGGESTIONS {
+            break;
+        }
+    }
+    suggestions
+}
+
+/// Open the Create Index dialog for `suggestion`. The user reviews and runs
+/// the statement from there.
+fn open_suggestion(tabular: &mut window_egui::Tabular, suggestion: &IndexSuggestion) {
+    let Some(db_type) = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(suggestion.connection_id))
+        .map(|c| c.connection_type.clone())
+    else {
+        return;
+    };
+    tabular.index_dialog = Some(models::structs::IndexDialogState {
+        mode: models::structs::IndexDialogMode::Create,
+        connection_id: suggestion.connection_id,
+        database_name: Some(suggestion.database.clone()),
+        table_name: suggestion.table.clone(),
+        existing_index_name: None,
+        index_name: suggestion.index_name(),
+        columns: suggestion.column.clone(),
+        unique: false,
+        method: None,
+        db_type,
+    });
+    tabular.show_index_dialog = true;
+}
+
+/// One-line hint above query results, one button per suggested index.
+pub(crate) fn render_index_hints(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
+    let Some(suggestions) = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .map(|t| t.index_suggestions.clone())
+        .filter(|s| !s.is_empty())
+    else {
+        return;
+    };
+    let mut open: Option<IndexSuggestion> = None;
+    let mut dismiss = false;
+    ui.horizontal_wrapped(|ui| {
+        ui.label(egui::RichText::new("💡 Filtered columns without an index:").weak());
+        for suggestion in &suggestions {
+            let label = format!("{}.{}", suggestion.table, suggestion.column);
+            if ui
+                .small_button(label)
+                .on_hover_text(format!(
+                    "CREATE INDEX {} ON {} ({})\n\nClick to review it in the index dialog.",
+                    suggestion.index_name(),
+                    suggestion.table,
+                    suggestion.column
+                ))
+                .clicked()
+            {
+                open = Some(suggestion.clone());
+            }
+        }
+        if ui
+            .small_button("✕")
+            .on_hover_text("Don't suggest these again this session")
+            .clicked()
+        {
+            dismiss = true;
+        }
+    });
+
+    if let Some(suggestion) = open {
+        open_suggestion(tabular, &suggestion);
+    }
+    if dismiss {
+        tabular
+            .dismissed_index_hints
+            .extend(suggestions.iter().map(IndexSuggestion::dismiss_key));
+        if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+            tab.index_suggestions.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_leading_index_column_counts() {
+        let index = |columns: &[&str]| models::structs::IndexStructInfo {
+            name: "i".to_string(),
+            method: None,
+            unique: false,
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        };
+        let indexes = vec![index(&["tenant_id", "status"]), index(&["`Email`"])];
+        assert!(has_leading_index(&indexes, "tenant_id"));
+        assert!(has_leading_index(&indexes, "email"));
+        assert!(!has_leading_index(&indexes, "status"));
+    }
+}
@@ -0,0 +1,139 @@
+//! Read-only index insight for Structure → Indexes: included columns and the
+//! usage figures each server keeps for free — scan counts from PostgreSQL's
+//! `pg_stat_user_indexes`, cardinality from MySQL's `SHOW INDEX` statistics.
+//! Non-unique indexes PostgreSQL never scanned are flagged for review.
+
+use crate::models::structs::IndexStructInfo;
+
+/// Method, key columns and `INCLUDE` columns from a `pg_get_indexdef` line:
+/// `CREATE INDEX i ON public.t USING btree (a, lower(b)) INCLUDE (c) WHERE …`.
+pub(crate) fn parse_pg_index_def(def: &str) -> (Option<String>, Vec<String>, Vec<String>) {
+    let Some(rest) = def.split_once(" USING ").map(|(_, rest)| rest) else {
+        return (None, Vec::new(), Vec::new());
+    };
+    let method = rest
+        .split_whitespace()
+        .next()
+        .filter(|m| !m.starts_with('('))
+        .map(str::to_string);
+    let columns = rest
+        .find('(')
+        .map(|open| parenthesised_list(&rest[open..]))
+        .unwrap_or_default();
+    let included = rest
+        .find(" INCLUDE (")
+        .map(|at| parenthesised_list(&rest[at + " INCLUDE ".len()..]))
+        .unwrap_or_default();
+    (method, columns, included)
+}
+
+/// Top-level comma-separated items of the parenthesised list `text` starts
+/// with; expressions such as `lower(b)` stay whole.
+fn parenthesised_list(text: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut depth = 0usize;
+    let mut current = String::new();
+    for c in text.chars() {
+        match c {
+            '(' => {
+                depth += 1;
+                if depth == 1 {
+                    continue;
+                }
+            }
+            ')' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    break;
+                }
+            }
+            ',' if depth == 1 => {
+                items.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(current);
+    items
+        .into_iter()
+        .map(|item| item.trim().trim_matches('"').to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Candidate for removal: PostgreSQL has never used it for a scan since its
+/// statistics were reset. Unique indexes enforce a constraint, so they stay.
+pub(crate) fn is_unused_index(index: &IndexStructInfo) -> bool {
+    !index.unique && index.scans == Some(0)
+}
+
+/// Text of the usage column; empty when the server reported nothing.
+pub(crate) fn index_usage_label(index: &IndexStructInfo) -> String {
+    let mut parts = Vec::new();
+    match index.scans {
+        Some(0) if !index.unique => parts.push("unused · 0 scans".to_string()),
+        Some(1) => parts.push("1 scan".to_string()),
+        Some(scans) => parts.push(format!("{} scans", scans)),
+        None => {}
+    }
+    if let Some(cardinality) = index.cardinality {
+        parts.push(format!("cardinality {}", cardinality));
+    }
+    parts.join(" · ")
+}
+
+/// Hover text of the usage column.
+pub(crate) fn index_usage_hint(index: &IndexStructInfo) -> Option<&'static str> {
+    if is_unused_index(index) {
+        Some(
+            "No index scans since statistics were last reset. It still costs writes and space — a candidate for removal once you have checked replicas and rare jobs.",
+        )
+    } else if index.scans.is_some() {
+        Some("Index scans counted by pg_stat_user_indexes since the last statistics reset")
+    } else if index.cardinality.is_some() {
+        Some("Estimated distinct values (SHOW INDEX); refresh with ANALYZE TABLE")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pg_index_definitions_and_usage() {
+        let (method, columns, included) = parse_pg_index_def(
+            "CREATE INDEX orders_idx ON public.orders USING btree (customer_id, lower((email)::text)) INCLUDE (total, \"Status\") WHERE (deleted_at IS NULL)",
+        );
+        assert_eq!(method.as_deref(), Some("btree"));
+        assert_eq!(columns, vec!["customer_id", "lower((email)::text)"]);
+        assert_eq!(included, vec!["total", "Status"]);
+
+        let (_, columns, included) =
+            parse_pg_index_def("CREATE UNIQUE INDEX pk ON public.t USING btree (id)");
+        assert_eq!(columns, vec!["id"]);
+        assert!(included.is_empty());
+
+        let unused = IndexStructInfo {
+            name: "orders_idx".to_string(),
+            scans: Some(0),
+            ..Default::default()
+        };
+        assert!(is_unused_index(&unused));
+        assert_eq!(index_usage_label(&unused), "unused · 0 scans");
+        let primary = IndexStructInfo {
+            unique: true,
+            ..unused.clone()
+        };
+        assert!(!is_unused_index(&primary));
+        assert_eq!(index_usage_label(&primary), "0 scans");
+        let mysql = IndexStructInfo {
+            cardinality: Some(1200),
+            ..Default::default()
+        };
+        assert_eq!(index_usage_label(&mysql), "cardinality 1200");
+    }
+}
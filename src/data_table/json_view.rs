@@ -0,0 +1,413 @@
+//! Cell value viewer with JSON support.
+//!
+//! Columns typed JSON/JSONB (from result metadata) always open as JSON; other
+//! text cells do when they hold an object or array (MySQL results without
+//! metadata, SQLite text-JSON). The viewer shows a collapsible tree next to
+//! the raw text, and any node's context menu copies its JSON path or builds a
+//! `->` / `->>` extraction expression for the current database.
+
+use eframe::egui;
+use serde_json::Value;
+
+use super::quote_ident;
+use crate::{models::enums::DatabaseType, window_egui};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JsonPathSegment {
+    Key(String),
+    Index(usize),
+}
+
+pub struct CellDetailState {
+    pub column: String,
+    /// The cell text exactly as stored.
+    pub raw: String,
+    pub json: Option<Value>,
+    pub db_type: Option<DatabaseType>,
+    pub show_raw: bool,
+}
+
+/// True for JSON column types (`JSON`, `JSONB`).
+pub(crate) fn is_json_type(type_name: &str) -> bool {
+    type_name.to_uppercase().contains("JSON")
+}
+
+/// Per-column flags marking result columns typed as JSON. Empty when column
+/// types are unknown.
+pub(crate) fn json_columns(tabular: &window_egui::Tabular) -> Vec<bool> {
+    tabular
+        .current_column_metadata
+        .as_ref()
+        .map(|meta| meta.iter().map(|m| is_json_type(&m.type_name)).collect())
+        .unwrap_or_default()
+}
+
+/// Parse a cell as JSON. Untyped cells only count when they hold an object or
+/// array, so plain numbers and words are not mistaken for JSON.
+pub(crate) fn parse_json_cell(cell: &str, typed_json: bool) -> Option<Value> {
+    let trimmed = cell.trim();
+    if trimmed.is_empty() || trimmed == "NULL" {
+        return None;
+    }
+    let looks_structured = (trimmed.starts_with('{') && trimmed.ends_with('}'))
+        || (trimmed.starts_with('[') && trimmed.ends_with(']'));
+    if !typed_json && !looks_structured {
+        return None;
+    }
+    serde_json::from_str(trimmed).ok()
+}
+
+/// SQL/JSON path of a node, e.g. `$.items[0]."unit price"`.
+pub(crate) fn json_path(path: &[JsonPathSegment]) -> String {
+    let mut out = String::from("$");
+    for segment in path {
+        match segment {
+            JsonPathSegment::Key(k)
+                if !k.is_empty()
+                    && !k.starts_with(|c: char| c.is_ascii_digit())
+                    && k.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+            {
+                out.push('.');
+                out.push_str(k);
+            }
+            JsonPathSegment::Key(k) => {
+                out.push_str(&format!(
+                    ".\"{}\"",
+                    k.replace('\\', "\\\\").replace('"', "\\\"")
+                ));
+            }
+            JsonPathSegment::Index(i) => out.push_str(&format!("[{}]", i)),
+        }
+    }
+    out
+}
+
+/// Expression extracting the node at `path` from `column`: JSON (`->`) or,
+/// with `as_text`, its text value (`->>`).
+pub(crate) fn extraction_expr(
+    column: &str,
+    path: &[JsonPathSegment],
+    db_type: &DatabaseType,
+    as_text: bool,
+) -> String {
+    let col = quote_ident(column, db_type);
+    if path.is_empty() {
+        return col;
+    }
+    let arrow = if as_text { "->>" } else { "->" };
+    let quoted_path = format!("'{}'", json_path(path).replace('\'', "''"));
+    match db_type {
+        DatabaseType::PostgreSQL => {
+            let mut out = col;
+            for (i, segment) in path.iter().enumerate() {
+                let op = if i + 1 == path.len() { arrow } else { "->" };
+                match segment {
+                    JsonPathSegment::Key(k) => {
+                        out.push_str(&format!(" {} '{}'", op, k.replace('\'', "''")))
+                    }
+                    JsonPathSegment::Index(n) => out.push_str(&format!(" {} {}", op, n)),
+                }
+            }
+            out
+        }
+        DatabaseType::MsSQL => {
+            let func = if as_text { "JSON_VALUE" } else { "JSON_QUERY" };
+            format!("{}({}, {})", func, col, quoted_path)
+        }
+        DatabaseType::MySQL => format!("{}{}{}", col, arrow, quoted_path),
+        _ => format!("{} {} {}", col, arrow, quoted_path),
+    }
+}
+
+/// Open the viewer for result cell (`row`, `col`) of the current page.
+pub(crate) fn open_cell_detail(tabular: &mut window_egui::Tabular, row: usize, col: usize) {
+    let Some(raw) = tabular
+        .current_table_data
+        .get(row)
+        .and_then(|r| r.get(col))
+        .cloned()
+    else {
+        return;
+    };
+    let column = tabular
+        .current_table_headers
+        .get(col)
+        .cloned()
+        .unwrap_or_default();
+    let typed_json = json_columns(tabular).get(col).copied().unwrap_or(false);
+    let db_type = tabular.current_connection_id.and_then(|cid| {
+        tabular
+            .connections
+            .iter()
+            .find(|c| c.id == Some(cid))
+            .map(|c| c.connection_type.clone())
+    });
+    let json = parse_json_cell(&raw, typed_json);
+    tabular.cell_detail = Some(CellDetailState {
+        column,
+        raw,
+        show_raw: json.is_none(),
+        json,
+        db_type,
+    });
+}
+
+enum NodeAction {
+    CopyPath,
+    CopyValue,
+    CopyExpr { as_text: bool },
+    InsertExpr { as_text: bool },
+}
+
+fn node_menu(
+    ui: &mut egui::Ui,
+    path: &[JsonPathSegment],
+    picked: &mut Option<(Vec<JsonPathSegment>, NodeAction)>,
+) {
+    let mut pick = |ui: &mut egui::Ui, label: &str, action: NodeAction| {
+        if ui.button(label).clicked() {
+            *picked = Some((path.to_vec(), action));
+            ui.close();
+        }
+    };
+    pick(ui, "📋 Copy JSON path", NodeAction::CopyPath);
+    pick(ui, "📋 Copy value", NodeAction::CopyValue);
+    ui.separator();
+    pick(
+        ui,
+        "📋 Copy -> expression",
+        NodeAction::CopyExpr { as_text: false },
+    );
+    pick(
+        ui,
+        "📋 Copy ->> expression",
+        NodeAction::CopyExpr { as_text: true },
+    );
+    pick(
+        ui,
+        "⬆ Insert -> into editor",
+        NodeAction::InsertExpr { as_text: false },
+    );
+    pick(
+        ui,
+        "⬆ Insert ->> into editor",
+        NodeAction::InsertExpr { as_text: true },
+    );
+}
+
+fn scalar_text(value: &Value) -> egui::RichText {
+    match value {
+        Value::String(s) => {
+            egui::RichText::new(format!("\"{}\"", s)).color(egui::Color32::from_rgb(106, 153, 85))
+        }
+        Value::Number(n) => {
+            egui::RichText::new(n.to_string()).color(egui::Color32::from_rgb(181, 137, 0))
+        }
+        Value::Bool(b) => {
+            egui::RichText::new(b.to_string()).color(egui::Color32::from_rgb(38, 139, 210))
+        }
+        Value::Null => egui::RichText::new("null").weak(),
+        _ => egui::RichText::new(value.to_string()),
+    }
+    .monospace()
+}
+
+fn json_tree(
+    ui: &mut egui::Ui,
+    label: String,
+    value: &Value,
+    path: &mut Vec<JsonPathSegment>,
+    picked: &mut Option<(Vec<JsonPathSegment>, NodeAction)>,
+) {
+    let children: Vec<(String, JsonPathSegment, &Value)> = match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| (k.clone(), JsonPathSegment::Key(k.clone()), v))
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("[{}]", i), JsonPathSegment::Index(i), v))
+            .collect(),
+        scalar => {
+            let resp = ui
+                .horizontal(|ui| {
+                    ui.label(egui::RichText::new(format!("{}:", label)).monospace());
+                    ui.label(scalar_text(scalar));
+                })
+                .response
+                .interact(egui::Sense::click());
+            resp.context_menu(|ui| node_menu(ui, path, picked));
+            return;
+        }
+    };
+    let summary = match value {
+        Value::Object(_) => format!("{}  {{{}}}", label, children.len()),
+        _ => format!("{}  [{}]", label, children.len()),
+    };
+    let header = egui::CollapsingHeader::new(egui::RichText::new(summary).monospace())
+        .id_salt(json_path(path))
+        .default_open(path.len() < 2)
+        .show(ui, |ui| {
+            for (child_label, segment, child) in children {
+                path.push(segment);
+                json_tree(ui, child_label, child, path, picked);
+                path.pop();
+            }
+        });
+    header
+        .header_response
+        .context_menu(|ui| node_menu(ui, path, picked));
+}
+
+fn insert_into_editor(tabular: &mut window_egui::Tabular, text: &str) {
+    let pos = tabular.cursor_position.min(tabular.editor.text.len());
+    tabular.editor.text.insert_str(pos, text);
+    let new_cursor = pos + text.len();
+    tabular.cursor_position = new_cursor;
+    tabular.selection_start = new_cursor;
+    tabular.selection_end = new_cursor;
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.content = tabular.editor.text.clone();
+        tab.is_modified = true;
+    }
+}
+
+pub(crate) fn render_cell_detail(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(state) = tabular.cell_detail.as_mut() else {
+        return;
+    };
+    let mut open = true;
+    let mut picked: Option<(Vec<JsonPathSegment>, NodeAction)> = None;
+    egui::Window::new(format!("🔍 {}", state.column))
+        .id(egui::Id::new("cell_detail_window"))
+        .collapsible(false)
+        .resizable(true)
+        .default_size([480.0, 420.0])
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if state.json.is_some() {
+                    ui.selectable_value(&mut state.show_raw, false, "Tree");
+                    ui.selectable_value(&mut state.show_raw, true, "Raw");
+                    ui.separator();
+                }
+                if ui.button("📋 Copy raw value").clicked() {
+                    ui.ctx().copy_text(state.raw.clone());
+                }
+                if let Some(json) = &state.json
+                    && ui.button("📋 Copy formatted").clicked()
+                {
+                    ui.ctx()
+                        .copy_text(serde_json::to_string_pretty(json).unwrap_or_default());
+                }
+            });
+            ui.separator();
+            egui::ScrollArea::both()
+                .auto_shrink([false, false])
+                .show(ui, |ui| match (&state.json, state.show_raw) {
+                    (Some(json), false) => {
+                        json_tree(ui, state.column.clone(), json, &mut Vec::new(), &mut picked);
+                    }
+                    (json, _) => {
+                        let mut text = json
+                            .as_ref()
+                            .and_then(|j| serde_json::to_string_pretty(j).ok())
+                            .unwrap_or_else(|| state.raw.clone());
+                        ui.add(
+                            egui::TextEdit::multiline(&mut text)
+                                .code_editor()
+                                .desired_width(f32::INFINITY)
+                                .interactive(false),
+                        );
+                    }
+                });
+            if state.json.is_some() && !state.show_raw {
+                ui.label(
+                    egui::RichText::new(
+                        "Right-click a node to copy its path or an extraction expression",
+                    )
+                    .small()
+                    .weak(),
+                );
+            }
+        });
+
+    if let Some((path, action)) = picked
+        && let Some(state) = tabular.cell_detail.as_ref()
+    {
+        let db_type = state.db_type.clone().unwrap_or(DatabaseType::PostgreSQL);
+        let text = match action {
+            NodeAction::CopyPath => json_path(&path),
+            NodeAction::CopyValue => {
+                let node = path.iter().try_fold(state.json.as_ref(), |v, seg| {
+                    Some(match seg {
+                        JsonPathSegment::Key(k) => v?.get(k),
+                        JsonPathSegment::Index(i) => v?.get(*i),
+                    })
+                });
+                match node.flatten() {
+                    Some(Value::String(s)) => s.clone(),
+                    Some(v) => serde_json::to_string_pretty(v).unwrap_or_default(),
+                    None => String::new(),
+                }
+            }
+            NodeAction::CopyExpr { as_text } | NodeAction::InsertExpr { as_text } => {
+                extraction_expr(&state.column, &path, &db_type, as_text)
+            }
+        };
+        if let NodeAction::InsertExpr { .. } = action {
+            insert_into_editor(tabular, &text);
+            tabular.toasts.info("Inserted into editor");
+        } else {
+            ctx.copy_text(text);
+        }
+    }
+    if !open {
+        tabular.cell_detail = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path() -> Vec<JsonPathSegment> {
+        vec![
+            JsonPathSegment::Key("items".to_string()),
+            JsonPathSegment::Index(0),
+            JsonPathSegment::Key("unit price".to_string()),
+        ]
+    }
+
+    #[test]
+    fn builds_paths_and_extraction_expressions() {
+        assert_eq!(json_path(&path()), "$.items[0].\"unit price\"");
+        assert_eq!(
+            extraction_expr("data", &path(), &DatabaseType::PostgreSQL, true),
+            "\"data\" -> 'items' -> 0 ->> 'unit price'"
+        );
+        assert_eq!(
+            extraction_expr("data", &path()[..1], &DatabaseType::MySQL, false),
+            "`data`->'$.items'"
+        );
+        assert_eq!(
+            extraction_expr("data", &path()[..2], &DatabaseType::SQLite, true),
+            "\"data\" ->> '$.items[0]'"
+        );
+        assert_eq!(
+            extraction_expr("data", &path()[..1], &DatabaseType::MsSQL, true),
+            "JSON_VALUE([data], '$.items')"
+        );
+    }
+
+    #[test]
+    fn untyped_cells_need_an_object_or_array() {
+        assert!(parse_json_cell("{\"a\": 1}", false).is_some());
+        assert!(parse_json_cell("[1, 2]", false).is_some());
+        assert!(parse_json_cell("42", false).is_none());
+        assert!(parse_json_cell("42", true).is_some());
+        assert!(parse_json_cell("{not json}", false).is_none());
+        assert!(parse_json_cell("NULL", true).is_none());
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::window_egui;
+
+/// Column widths and hidden columns the user set for a browsed table, saved
+/// in preferences and keyed by connection, database and table (see
+/// [`layout_key`]).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedColumnLayout {
+    /// Headers the widths were recorded for; a result with different columns
+    /// keeps the default widths.
+    pub headers: Vec<String>,
+    pub widths: Vec<f32>,
+    /// Names of hidden columns (see `column_visibility`).
+    #[serde(default)]
+    pub hidden: Vec<String>,
+}
+
+impl SavedColumnLayout {
+    /// Saved widths for `headers`, when the layout was recorded for exactly
+    /// those columns.
+    pub(crate) fn widths_for(&self, headers: &[String]) -> Option<Vec<f32>> {
+        (self.headers == headers && self.widths.len() == headers.len())
+            .then(|| self.widths.clone())
+    }
+}
+
+/// Key of the table shown in the data grid; `None` for query results, whose
+/// columns are not tied to one table.
+fn layout_key(tabular: &mut window_egui::Tabular) -> Option<String> {
+    if !tabular.is_table_browse_mode {
+        return None;
+    }
+    let connection_id = tabular.current_connection_id?;
+    let table = super::infer_current_table_name(tabular);
+    if table.is_empty() {
+        return None;
+    }
+    let database = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.database_name.clone())
+        .unwrap_or_default();
+    Some(format!("{}/{}/{}", connection_id, database, table))
+}
+
+/// Apply the saved widths and hidden columns for the browsed table. Returns
+/// false when no matching widths were saved. Other results keep their hidden
+/// columns only while the headers stay the same.
+pub(crate) fn restore_column_layout(tabular: &mut window_egui::Tabular) -> bool {
+    let Some(key) = layout_key(tabular) else {
+        if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index)
+            && tab.hidden_columns_headers != tabular.current_table_headers
+        {
+            tab.hidden_columns.clear();
+            tab.hidden_columns_headers.clear();
+        }
+        return false;
+    };
+    let layout = tabular.saved_column_layouts.get(&key);
+    let hidden = layout.map(|l| l.hidden.clone()).unwrap_or_default();
+    let widths = layout.and_then(|l| l.widths_for(&tabular.current_table_headers));
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.hidden_columns = hidden;
+        tab.hidden_columns_headers = tabular.current_table_headers.clone();
+    }
+    let Some(widths) = widths else {
+        return false;
+    };
+    tabular.column_widths = widths;
+    true
+}
+
+/// Save the current widths and hidden columns for the browsed table (after a
+/// resize or a column is hidden).
+pub(crate) fn remember_column_layout(tabular: &mut window_egui::Tabular) {
+    let Some(key) = layout_key(tabular) else {
+        return;
+    };
+    let layout = SavedColumnLayout {
+        headers: tabular.current_table_headers.clone(),
+        widths: tabular.column_widths.clone(),
+        hidden: tabular
+            .query_tabs
+            .get(tabular.active_tab_index)
+            .map(|t| t.hidden_columns.clone())
+            .unwrap_or_default(),
+    };
+    if tabular.saved_column_layouts.get(&key) != Some(&layout) {
+        tabular.saved_column_layouts.insert(key, layout);
+        tabular.prefs_dirty = true;
+        tabular.try_save_prefs();
+    }
+}
+
+/// Forget the browsed table's saved widths and go back to the defaults.
+pub(crate) fn reset_column_layout(tabular: &mut window_egui::Tabular) {
+    if let Some(key) = layout_key(tabular)
+        && tabular.saved_column_layouts.remove(&key).is_some()
+    {
+        tabular.prefs_dirty = true;
+        tabular.try_save_prefs();
+    }
+    super::initialize_column_widths(tabular);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widths_apply_only_to_matching_headers() {
+        let layout = SavedColumnLayout {
+            headers: vec!["id".into(), "name".into()],
+            widths: vec![80.0, 320.0],
+            hidden: Vec::new(),
+        };
+        let same = vec!["id".to_string(), "name".to_string()];
+        assert_eq!(layout.widths_for(&same), Some(vec![80.0, 320.0]));
+        let added = vec!["id".to_string(), "name".to_string(), "email".to_string()];
+        assert_eq!(layout.widths_for(&added), None);
+        let reordered = vec!["name".to_string(), "id".to_string()];
+        assert_eq!(layout.widths_for(&reordered), None);
+    }
+}
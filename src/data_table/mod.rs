@@ -1,3 +1,21 @@
+mod allowed_values;
+mod bulk_paste;
+mod cell_preview;
+mod column_stats;
+mod column_visibility;
+mod edit_flash;
+mod export_sqlite;
+mod filter_chips;
+mod find_in_results;
+mod formatters;
+mod index_advice;
+mod index_usage;
+mod json_view;
+mod layout;
+mod number_format;
+mod pg_structured;
+mod pinned_results;
+mod record_view;
 mod render_data;
 mod pagination;
 mod filter_sort;
@@ -5,10 +23,32 @@ mod structure;
 mod render_structure;
 mod selection;
 mod utils;
+mod timezone;
+mod transpose;
 
+pub(crate) use allowed_values::*;
+pub(crate) use bulk_paste::*;
+pub(crate) use cell_preview::*;
+pub(crate) use column_stats::*;
+pub(crate) use column_visibility::*;
+pub(crate) use edit_flash::*;
+pub(crate) use export_sqlite::*;
+pub(crate) use filter_chips::*;
+pub(crate) use find_in_results::*;
+pub(crate) use formatters::*;
+pub(crate) use index_advice::*;
+pub(crate) use index_usage::*;
+pub(crate) use json_view::*;
+pub(crate) use layout::*;
+pub(crate) use number_format::*;
+pub(crate) use pg_structured::*;
+pub(crate) use pinned_results::*;
+pub(crate) use record_view::*;
 pub(crate) use render_data::*;
 pub(crate) use pagination::*;
 pub(crate) use filter_sort::*;
 pub(crate) use structure::*;
 pub(crate) use render_structure::*;
 pub(crate) use selection::*;
+pub(crate) use timezone::*;
+pub(crate) use transpose::*;
@@ -0,0 +1,378 @@
+//! Thousands separators and fixed decimals for numeric columns. Runs as the
+//! `number` column formatter, applied automatically to columns whose type is
+//! numeric when the preference is on; a header-menu override pins a column
+//! to grouped or raw display through an ordinary formatter rule.
+
+use eframe::egui;
+
+use super::{ColumnFormatRule, ColumnFormatter};
+use crate::window_egui;
+
+/// Separators for grouped numbers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberStyle {
+    /// Picked from `LC_ALL` / `LC_NUMERIC` / `LANG`.
+    #[default]
+    Locale,
+    Comma,
+    Dot,
+    Space,
+    Apostrophe,
+}
+
+impl NumberStyle {
+    pub(crate) const ALL: [NumberStyle; 5] = [
+        NumberStyle::Locale,
+        NumberStyle::Comma,
+        NumberStyle::Dot,
+        NumberStyle::Space,
+        NumberStyle::Apostrophe,
+    ];
+
+    pub(crate) fn id(self) -> &'static str {
+        match self {
+            NumberStyle::Locale => "locale",
+            NumberStyle::Comma => "comma",
+            NumberStyle::Dot => "dot",
+            NumberStyle::Space => "space",
+            NumberStyle::Apostrophe => "apostrophe",
+        }
+    }
+
+    pub(crate) fn from_id(id: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|s| s.id() == id)
+            .unwrap_or_default()
+    }
+
+    /// Group and decimal separator.
+    fn separators(self) -> (char, char) {
+        match self {
+            NumberStyle::Locale => locale_separators(),
+            NumberStyle::Comma => (',', '.'),
+            NumberStyle::Dot => ('.', ','),
+            NumberStyle::Space => ('\u{202f}', ','),
+            NumberStyle::Apostrophe => ('\'', '.'),
+        }
+    }
+
+    pub(crate) fn label(self) -> String {
+        let sample = format_number("1234567.89", self.separators(), None).unwrap_or_default();
+        match self {
+            NumberStyle::Locale => format!("System locale ({})", sample),
+            _ => sample,
+        }
+    }
+}
+
+fn locale_separators() -> (char, char) {
+    let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty())
+        .unwrap_or_default();
+    let language = locale
+        .split(['_', '.', '-'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match language.as_str() {
+        "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl" => {
+            ('.', ',')
+        }
+        "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" | "uk" | "hu" | "bg" => {
+            ('\u{202f}', ',')
+        }
+        _ => (',', '.'),
+    }
+}
+
+/// Whether a column type (as reported by the driver) holds numbers.
+pub(crate) fn is_numeric_type(type_name: &str) -> bool {
+    let base = type_name
+        .split(['(', ' '])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase();
+    matches!(
+        base.as_str(),
+        "TINYINT"
+            | "SMALLINT"
+            | "MEDIUMINT"
+            | "INT"
+            | "INTEGER"
+            | "BIGINT"
+            | "INT2"
+            | "INT4"
+            | "INT8"
+            | "SERIAL"
+            | "BIGSERIAL"
+            | "DECIMAL"
+            | "NUMERIC"
+            | "NUMBER"
+            | "FLOAT"
+            | "FLOAT4"
+            | "FLOAT8"
+            | "DOUBLE"
+            | "REAL"
+            | "MONEY"
+            | "SMALLMONEY"
+    )
+}
+
+/// Per-column flags for numeric result columns (right-aligned in the grid).
+pub(crate) fn numeric_columns(tabular: &window_egui::Tabular) -> Vec<bool> {
+    tabular
+        .current_column_metadata
+        .as_ref()
+        .map(|meta| meta.iter().map(|m| is_numeric_type(&m.type_name)).collect())
+        .unwrap_or_default()
+}
+
+/// `value` with grouped integer digits and, when `decimals` is set, rounded
+/// to that many places. `None` for text that is not a plain number.
+fn format_number(
+    value: &str,
+    (group, point): (char, char),
+    decimals: Option<u32>,
+) -> Option<String> {
+    let value = value.trim();
+    let plain = match decimals {
+        Some(places) => match value.parse::<rust_decimal::Decimal>() {
+            Ok(number) => format!(
+                "{:.*}",
+                places as usize,
+                number.round_dp_with_strategy(
+                    places,
+                    rust_decimal::RoundingStrategy::MidpointAwayFromZero
+                )
+            ),
+            Err(_) => format!("{:.*}", places as usize, value.parse::<f64>().ok()?),
+        },
+        None => value.to_string(),
+    };
+    let (sign, unsigned) = match plain.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", plain.strip_prefix('+').unwrap_or(&plain)),
+    };
+    let (int_part, frac_part) = match unsigned.split_once('.') {
+        Some((i, f)) => (i, Some(f)),
+        None => (unsigned, None),
+    };
+    if int_part.is_empty()
+        || !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.is_none_or(|f| f.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+    let mut out = String::from(sign);
+    for (i, digit) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            out.push(group);
+        }
+        out.push(digit);
+    }
+    if let Some(frac) = frac_part {
+        out.push(point);
+        out.push_str(frac);
+    }
+    Some(out)
+}
+
+pub(crate) struct NumberFormat;
+pub(crate) struct RawValue;
+
+impl ColumnFormatter for NumberFormat {
+    fn id(&self) -> &'static str {
+        "number"
+    }
+    fn label(&self) -> &'static str {
+        "Number (thousands separators)"
+    }
+    fn argument_hint(&self) -> Option<&'static str> {
+        Some("decimals, e.g. 2 (blank: preference)")
+    }
+    /// `argument` is `[style:]decimals`; [`number_argument`] fills in the
+    /// preferred style, and the preferred decimals when none are given.
+    fn format(&self, value: &str, argument: &str) -> Option<String> {
+        let (style, places) = match argument.split_once(':') {
+            Some((style, places)) => (NumberStyle::from_id(style.trim()), places),
+            None => (NumberStyle::Locale, argument),
+        };
+        let places = places.trim();
+        let decimals = if places.is_empty() {
+            None
+        } else {
+            Some(places.parse().ok()?)
+        };
+        format_number(value, style.separators(), decimals)
+    }
+}
+
+impl ColumnFormatter for RawValue {
+    fn id(&self) -> &'static str {
+        "raw"
+    }
+    fn label(&self) -> &'static str {
+        "Raw value (no formatting)"
+    }
+    fn format(&self, _value: &str, _argument: &str) -> Option<String> {
+        None
+    }
+}
+
+/// Resolved argument for the number formatter from a rule's `argument`.
+pub(crate) fn number_argument(tabular: &window_egui::Tabular, argument: &str) -> String {
+    if argument.contains(':') {
+        return argument.to_string();
+    }
+    let places = match argument.trim() {
+        "" => tabular
+            .number_decimals
+            .map(|d| d.to_string())
+            .unwrap_or_default(),
+        given => given.to_string(),
+    };
+    format!("{}:{}", tabular.number_style.id(), places)
+}
+
+/// Formatter a header-menu override pinned on `column` (`number` or `raw`).
+pub(crate) fn number_override(
+    tabular: &window_egui::Tabular,
+    column: &str,
+) -> Option<&'static str> {
+    tabular
+        .column_format_rules
+        .iter()
+        .find(|r| r.pattern == column)
+        .and_then(|r| match r.formatter.as_str() {
+            "number" => Some("number"),
+            "raw" => Some("raw"),
+            _ => None,
+        })
+}
+
+/// Pin `column` to grouped (`Some("number")`) or raw (`Some("raw")`) display,
+/// or drop the override with `None`. The rule goes first so it wins.
+pub(crate) fn set_number_override(
+    tabular: &mut window_egui::Tabular,
+    column: &str,
+    formatter: Option<&str>,
+) {
+    tabular
+        .column_format_rules
+        .retain(|r| !(r.pattern == column && matches!(r.formatter.as_str(), "number" | "raw")));
+    if let Some(formatter) = formatter {
+        tabular.column_format_rules.insert(
+            0,
+            ColumnFormatRule {
+                pattern: column.to_string(),
+                formatter: formatter.to_string(),
+                argument: String::new(),
+            },
+        );
+    }
+    tabular.prefs_dirty = true;
+    tabular.try_save_prefs();
+}
+
+/// Header menu entries for the number override of `column`.
+pub(crate) fn number_override_menu(
+    tabular: &mut window_egui::Tabular,
+    ui: &mut egui::Ui,
+    column: &str,
+) {
+    let current = number_override(tabular, column);
+    ui.menu_button("🔢 Number Format", |ui| {
+        for (choice, label) in [
+            (None, "Automatic"),
+            (Some("number"), "Thousands separators"),
+            (Some("raw"), "Raw value"),
+        ] {
+            if ui.selectable_label(current == choice, label).clicked() {
+                set_number_override(tabular, column, choice);
+                ui.close();
+            }
+        }
+    });
+}
+
+/// Preferences → Data Grid controls. Returns true when something changed.
+pub(crate) fn render_number_settings(
+    tabular: &mut window_egui::Tabular,
+    ui: &mut egui::Ui,
+) -> bool {
+    let mut changed = ui
+        .checkbox(
+            &mut tabular.number_format,
+            "Group digits in numeric columns",
+        )
+        .changed();
+    ui.add_enabled_ui(tabular.number_format, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Separators:");
+            egui::ComboBox::from_id_salt("number_style")
+                .selected_text(tabular.number_style.label())
+                .show_ui(ui, |ui| {
+                    for style in NumberStyle::ALL {
+                        changed |= ui
+                            .selectable_value(&mut tabular.number_style, style, style.label())
+                            .changed();
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            let mut fixed = tabular.number_decimals.is_some();
+            if ui.checkbox(&mut fixed, "Fixed decimals:").changed() {
+                tabular.number_decimals = fixed.then_some(2);
+                changed = true;
+            }
+            if let Some(places) = tabular.number_decimals.as_mut() {
+                changed |= ui.add(egui::DragValue::new(places).range(0..=10)).changed();
+            }
+        });
+    });
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_digits_and_rounds() {
+        let comma = (',', '.');
+        assert_eq!(
+            format_number("1234567", comma, None).as_deref(),
+            Some("1,234,567")
+        );
+        assert_eq!(
+            format_number("-1234.5", comma, None).as_deref(),
+            Some("-1,234.5")
+        );
+        assert_eq!(format_number("999", comma, None).as_deref(), Some("999"));
+        assert_eq!(
+            format_number("1234.565", comma, Some(2)).as_deref(),
+            Some("1,234.57")
+        );
+        assert_eq!(
+            format_number("12", comma, Some(2)).as_deref(),
+            Some("12.00")
+        );
+        assert_eq!(
+            format_number("9876543.21", ('.', ','), None).as_deref(),
+            Some("9.876.543,21")
+        );
+        assert_eq!(format_number("12ab", comma, None), None);
+        assert_eq!(format_number("NaN", comma, None), None);
+        assert_eq!(
+            NumberFormat.format("1000", "apostrophe:").as_deref(),
+            Some("1'000")
+        );
+        assert!(is_numeric_type("BIGINT UNSIGNED"));
+        assert!(is_numeric_type("numeric(10,2)"));
+        assert!(!is_numeric_type("POINT"));
+        assert!(!is_numeric_type("INTERVAL"));
+    }
+}
@@ -9,9 +9,15 @@ pub(crate) fn render_pagination_bar(tabular: &mut window_egui::Tabular, ui: &mut
     let exec_ms = tabular
         .query_tabs
         .get(tabular.active_tab_index)
-        .and_then(|t| t.results.get(t.active_result_index))
+        .and_then(super::displayed_result)
         .map(|r| r.execution_time_ms)
         .filter(|ms| *ms > 0);
+    // Snapshot time when the displayed result came from the query result cache
+    let cached_at = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(super::displayed_result)
+        .and_then(|r| r.cached.as_ref().map(|(at, _)| *at));
 
     let bg_color = if ui.visuals().dark_mode {
         egui::Color32::from_rgb(22, 22, 26)
@@ -64,6 +70,73 @@ pub(crate) fn render_pagination_bar(tabular: &mut window_egui::Tabular, ui: &mut
                         .on_hover_text("Query execution time");
                 }
 
+                // Result cache badge with a refresh-from-server button
+                if let Some(at) = cached_at {
+                    ui.separator();
+                    let age = (chrono::Utc::now().timestamp() - at).max(0);
+                    ui.colored_label(
+                        crate::window_egui::style::theme_accent(ui.ctx()),
+                        format!("⚡ cached {} ago", crate::editor::format_cache_age(age)),
+                    )
+                    .on_hover_text("Served from the query result cache (Preferences → Performance)");
+                    if ui
+                        .small_button("⟳")
+                        .on_hover_text("Re-run on the server and replace the cached result")
+                        .clicked()
+                    {
+                        crate::editor::refresh_cached_result(tabular);
+                    }
+                }
+
+                // Active display timezone for temporal columns
+                if !tabular.current_table_headers.is_empty() {
+                    ui.separator();
+                    ui.label(
+                        egui::RichText::new(format!("🕒 {}", tabular.display_timezone.display_name()))
+                            .color(ui.visuals().weak_text_color()),
+                    )
+                    .on_hover_text("Timezone used to display TIMESTAMP/DATETIME columns (Preferences → Data Grid)");
+
+                    // Grid / record view / transposed toggles (remembered per tab)
+                    ui.separator();
+                    if ui
+                        .selectable_label(super::record_view_active(tabular), "☰ Record view")
+                        .on_hover_text("Show one record at a time as field/value pairs")
+                        .clicked()
+                    {
+                        super::toggle_record_view(tabular);
+                    }
+                    if ui
+                        .selectable_label(super::transpose_active(tabular), "⇄ Transpose")
+                        .on_hover_text("Show columns as rows and rows as columns")
+                        .clicked()
+                    {
+                        super::toggle_transpose(tabular);
+                    }
+                    let pinned_index = tabular
+                        .query_tabs
+                        .get(tabular.active_tab_index)
+                        .and_then(|t| t.active_pinned);
+                    if let Some(index) = pinned_index {
+                        if ui
+                            .button("📌 Unpin")
+                            .on_hover_text("Remove this pinned result")
+                            .clicked()
+                        {
+                            super::unpin_result(tabular, index);
+                        }
+                    } else if ui
+                        .button("📌 Pin")
+                        .on_hover_text(format!(
+                            "Keep a snapshot of this result in the result bar; later runs do not replace it (up to {} per tab)",
+                            super::MAX_PINNED_RESULTS
+                        ))
+                        .clicked()
+                    {
+                        super::pin_current_result(tabular);
+                    }
+                }
+
                 // Grid Summary Bar (Sum, Avg, Count, Min, Max for selected cells)
                 if let Some(summary) = super::selection::calculate_grid_summary(tabular) {
                     ui.separator();
@@ -102,18 +175,24 @@ pub(crate) fn render_pagination_bar(tabular: &mut window_egui::Tabular, ui: &mut
 
                 ui.separator();
 
-                // Page size selector
+                // Page size selector; a size restored from an older session stays listed
                 ui.label("Rows per page:");
-                let mut page_size_str = tabular.page_size.to_string();
-                // Batasi lebar input supaya tidak mengembang mengisi bar dan membuat gap
-                if ui
-                    .add(egui::TextEdit::singleline(&mut page_size_str).desired_width(50.0))
-                    .changed()
-                    && let Ok(new_size) = page_size_str.parse::<usize>()
-                    && new_size > 0
-                    && new_size <= 10000
-                {
-                    tabular.set_page_size(new_size);
+                let mut new_page_size = tabular.page_size;
+                egui::ComboBox::from_id_salt("result_page_size")
+                    .width(60.0)
+                    .selected_text(tabular.page_size.to_string())
+                    .show_ui(ui, |ui| {
+                        let mut choices = PAGE_SIZE_CHOICES.to_vec();
+                        if !choices.contains(&tabular.page_size) {
+                            choices.push(tabular.page_size);
+                            choices.sort_unstable();
+                        }
+                        for size in choices {
+                            ui.selectable_value(&mut new_page_size, size, size.to_string());
+                        }
+                    });
+                if new_page_size != tabular.page_size {
+                    tabular.set_page_size(new_page_size);
                 }
 
                 ui.separator();
@@ -129,36 +208,41 @@ pub(crate) fn render_pagination_bar(tabular: &mut window_egui::Tabular, ui: &mut
                 } else {
                     get_total_pages(tabular)
                 };
+                let on_last_page = tabular.current_page + 1 >= total_pages.max(1);
+                let loading = !tabular.pending_paginated_jobs.is_empty();
 
                 ui.add_enabled(
-                    has_data && tabular.current_page > 0,
+                    has_data && !loading && tabular.current_page > 0,
                     egui::Button::new("⏮ First"),
                 )
                 .clicked()
                 .then(|| go_to_page(tabular, 0));
                 ui.add_enabled(
-                    has_data && tabular.current_page > 0,
+                    has_data && !loading && tabular.current_page > 0,
                     egui::Button::new("◀ Prev"),
                 )
                 .clicked()
                 .then(|| previous_page(tabular));
+                if loading {
+                    ui.spinner();
+                }
                 ui.label(format!(
                     "Page {} of {}",
                     tabular.current_page + 1,
                     total_pages.max(1)
                 ));
                 ui.add_enabled(
-                    has_data && tabular.current_page < total_pages.saturating_sub(1),
+                    has_data && !loading && !on_last_page,
                     egui::Button::new("Next ▶"),
                 )
                 .clicked()
                 .then(|| next_page(tabular));
-                ui.add_enabled(has_data && total_pages > 1, egui::Button::new("Last ⏭"))
-                    .clicked()
-                    .then(|| {
-                        let last_page = total_pages.saturating_sub(1);
-                        go_to_page(tabular, last_page);
-                    });
+                ui.add_enabled(
+                    has_data && !loading && !on_last_page,
+                    egui::Button::new("Last ⏭"),
+                )
+                .clicked()
+                .then(|| go_to_page(tabular, total_pages.saturating_sub(1)));
 
                 ui.separator();
                 if ui.button("Clear selection").clicked() {
@@ -170,15 +254,21 @@ pub(crate) fn render_pagination_bar(tabular: &mut window_egui::Tabular, ui: &mut
                     tabular.last_clicked_column = None;
                 }
 
+                // Jump once the number is committed (Enter or focus leaves the field)
                 ui.label("Go to page:");
-                let mut page_input = (tabular.current_page + 1).to_string();
-                if ui
-                    .add_enabled(has_data, egui::TextEdit::singleline(&mut page_input).desired_width(40.0))
-                    .changed()
-                    && let Ok(page_num) = page_input.parse::<usize>()
-                    && page_num > 0
-                {
-                    go_to_page(tabular, page_num - 1);
+                let jump = ui.add_enabled(
+                    has_data && !loading,
+                    egui::TextEdit::singleline(&mut tabular.page_jump_input)
+                        .hint_text((tabular.current_page + 1).to_string())
+                        .desired_width(40.0),
+                );
+                if jump.lost_focus() {
+                    if let Some(page) = parse_page_jump(&tabular.page_jump_input, total_pages)
+                        && page != tabular.current_page
+                    {
+                        go_to_page(tabular, page);
+                    }
+                    tabular.page_jump_input.clear();
                 }
 
                 // Embed 3 view buttons directly into the right side of the datatable footer bar
@@ -310,6 +400,42 @@ pub(crate) fn render_footer_view_buttons(tabular: &mut window_egui::Tabular, ui:
             tabular.show_lint_panel = false;
         }
 
+        // Query plan toggle (query editor results only; browse mode has no statement)
+        #[cfg(feature = "query_ast")]
+        if !tabular.is_table_browse_mode {
+            let is_plan_open = tabular.query_plan_panel.open;
+            let plan_bg = if is_plan_open {
+                window_egui::style::theme_accent(ui.ctx())
+            } else if ui.visuals().dark_mode {
+                egui::Color32::from_rgb(45, 45, 50)
+            } else {
+                egui::Color32::from_rgb(225, 225, 230)
+            };
+            let plan_text_color = if is_plan_open {
+                egui::Color32::WHITE
+            } else {
+                ui.visuals().text_color()
+            };
+
+            let plan_btn = egui::Button::new(
+                egui::RichText::new("🧭 Plan")
+                    .small()
+                    .strong()
+                    .color(plan_text_color),
+            )
+            .fill(plan_bg)
+            .corner_radius(egui::CornerRadius::same(4u8))
+            .min_size(egui::vec2(0.0, button_height));
+
+            if ui
+                .add(plan_btn)
+                .on_hover_text("Show query plan for the statement under the cursor")
+                .clicked()
+            {
+                tabular.query_plan_panel.open = !tabular.query_plan_panel.open;
+            }
+        }
+
         // Explain Button (shown if EXPLAIN output present)
         let has_explain = tabular
             .query_tabs
@@ -383,6 +509,7 @@ pub(crate) fn initialize_column_widths(tabular: &mut window_egui::Tabular) {
         // Calculate initial column width based on available space
         let base_width = 180.0; // Base width per column
         tabular.column_widths = vec![base_width; num_columns];
+        super::restore_column_layout(tabular);
     } else {
         tabular.column_widths.clear();
     }
@@ -415,6 +542,25 @@ pub(crate) fn set_column_width(
     }
 }
 
+/// Page sizes offered by the footer selector.
+pub(crate) const PAGE_SIZE_CHOICES: [usize; 4] = [25, 50, 100, 500];
+
+/// Zero-based page for a 1-based page number typed by the user, clamped to
+/// the last page.
+pub(crate) fn parse_page_jump(input: &str, total_pages: usize) -> Option<usize> {
+    let page = input.trim().parse::<usize>().ok().filter(|p| *p > 0)?;
+    Some(page.min(total_pages.max(1)) - 1)
+}
+
+/// Keep the active tab's pagination state in step with the global one so
+/// switching tabs comes back to the same page.
+pub(crate) fn store_tab_pagination(tabular: &mut window_egui::Tabular) {
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.current_page = tabular.current_page;
+        tab.page_size = tabular.page_size;
+    }
+}
+
 pub(crate) fn update_current_page_data(tabular: &mut window_egui::Tabular) {
     let start_index = tabular.current_page * tabular.page_size;
     let end_index =
@@ -442,6 +588,7 @@ pub(crate) fn next_page(tabular: &mut window_egui::Tabular) {
             tabular.current_page += 1;
             tabular.execute_paginated_query();
             clear_table_selection(tabular);
+            store_tab_pagination(tabular);
         }
     } else {
         // Client-side pagination (original behavior)
@@ -450,6 +597,7 @@ pub(crate) fn next_page(tabular: &mut window_egui::Tabular) {
             tabular.current_page += 1;
             update_current_page_data(tabular);
             clear_table_selection(tabular);
+            store_tab_pagination(tabular);
         }
     }
 }
@@ -468,6 +616,7 @@ pub(crate) fn previous_page(tabular: &mut window_egui::Tabular) {
             tabular.current_page -= 1;
             tabular.execute_paginated_query();
             clear_table_selection(tabular);
+            store_tab_pagination(tabular);
         }
     } else {
         // Client-side pagination (original behavior)
@@ -475,6 +624,7 @@ pub(crate) fn previous_page(tabular: &mut window_egui::Tabular) {
             tabular.current_page -= 1;
             update_current_page_data(tabular);
             clear_table_selection(tabular);
+            store_tab_pagination(tabular);
         }
     }
 }
@@ -494,6 +644,7 @@ pub(crate) fn go_to_page(tabular: &mut window_egui::Tabular, page: usize) {
             tabular.current_page = page;
             tabular.execute_paginated_query();
             clear_table_selection(tabular);
+            store_tab_pagination(tabular);
         }
     } else {
         // Client-side pagination (original behavior)
@@ -502,6 +653,7 @@ pub(crate) fn go_to_page(tabular: &mut window_egui::Tabular, page: usize) {
             tabular.current_page = page;
             update_current_page_data(tabular);
             clear_table_selection(tabular);
+            store_tab_pagination(tabular);
         }
     }
 }
@@ -535,3 +687,17 @@ pub(crate) fn get_total_pages(tabular: &window_egui::Tabular) -> usize {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_jump_is_one_based_and_clamped() {
+        assert_eq!(parse_page_jump("1", 5), Some(0));
+        assert_eq!(parse_page_jump(" 3 ", 5), Some(2));
+        assert_eq!(parse_page_jump("99", 5), Some(4));
+        assert_eq!(parse_page_jump("2", 0), Some(0));
+        assert_eq!(parse_page_jump("0", 5), None);
+        assert_eq!(parse_page_jump("abc", 5), None);
+    }
+}
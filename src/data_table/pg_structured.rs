@@ -0,0 +1,244 @@
+//! PostgreSQL array and composite columns in the grid. Cells hold the value
+//! in PostgreSQL's literal syntax (`{a,"b c"}`, `(1,"x")`); the grid shows
+//! them as a readable list, and editing opens an element editor that writes
+//! a valid literal back. Values that don't parse cleanly (multi-dimensional
+//! arrays, explicit bounds) keep the plain text editor.
+
+use eframe::egui;
+
+use crate::{
+    driver_postgres::{format_pg_array, format_pg_record, parse_pg_array, parse_pg_record},
+    models::structs::PgValueKind,
+    spreadsheet::SpreadsheetOperations,
+    window_egui,
+};
+
+pub struct StructuredEditState {
+    pub row: usize,
+    pub col: usize,
+    pub column: String,
+    pub kind: PgValueKind,
+    /// Element (or field) text and whether it is NULL.
+    pub items: Vec<(String, bool)>,
+}
+
+/// Array / composite shape of each result column (`None` for other columns).
+pub(crate) fn structured_columns(tabular: &window_egui::Tabular) -> Vec<Option<PgValueKind>> {
+    tabular
+        .current_column_metadata
+        .as_ref()
+        .map(|meta| meta.iter().map(|m| m.pg_kind.clone()).collect())
+        .unwrap_or_default()
+}
+
+fn parse_items(kind: &PgValueKind, cell: &str) -> Option<Vec<Option<String>>> {
+    match kind {
+        PgValueKind::Array => parse_pg_array(cell),
+        PgValueKind::Composite(fields) => {
+            parse_pg_record(cell).filter(|values| values.len() == fields.len())
+        }
+    }
+}
+
+/// Grid text for an array or composite cell: `[a, b c, NULL]` or
+/// `x=1, y=NULL`. `None` leaves the raw literal.
+pub(crate) fn structured_display(kind: &PgValueKind, cell: &str) -> Option<String> {
+    let items = parse_items(kind, cell)?;
+    let shown = |v: &Option<String>| v.clone().unwrap_or_else(|| "NULL".to_string());
+    Some(match kind {
+        PgValueKind::Array => format!(
+            "[{}]",
+            items.iter().map(shown).collect::<Vec<_>>().join(", ")
+        ),
+        PgValueKind::Composite(fields) => fields
+            .iter()
+            .zip(&items)
+            .map(|(field, value)| format!("{}={}", field, shown(value)))
+            .collect::<Vec<_>>()
+            .join(", "),
+    })
+}
+
+/// Open the element editor for cell (`row`, `col`) when its column is an
+/// array or composite and the value parses. Returns whether it opened.
+pub(crate) fn open_structured_editor(
+    tabular: &mut window_egui::Tabular,
+    row: usize,
+    col: usize,
+) -> bool {
+    let Some(kind) = structured_columns(tabular).get(col).cloned().flatten() else {
+        return false;
+    };
+    let Some(cell) = tabular.current_table_data.get(row).and_then(|r| r.get(col)) else {
+        return false;
+    };
+    let items = if cell == "NULL" {
+        match &kind {
+            PgValueKind::Array => Vec::new(),
+            PgValueKind::Composite(fields) => vec![None; fields.len()],
+        }
+    } else {
+        match parse_items(&kind, cell) {
+            Some(items) => items,
+            None => return false,
+        }
+    };
+    tabular.structured_edit = Some(StructuredEditState {
+        row,
+        col,
+        column: tabular
+            .current_table_headers
+            .get(col)
+            .cloned()
+            .unwrap_or_default(),
+        kind,
+        items: items
+            .into_iter()
+            .map(|v| match v {
+                Some(v) => (v, false),
+                None => (String::new(), true),
+            })
+            .collect(),
+    });
+    true
+}
+
+/// Literal for the edited elements.
+fn edited_literal(state: &StructuredEditState) -> String {
+    let items: Vec<Option<String>> = state
+        .items
+        .iter()
+        .map(|(v, null)| (!null).then(|| v.clone()))
+        .collect();
+    match state.kind {
+        PgValueKind::Array => format_pg_array(&items),
+        PgValueKind::Composite(_) => format_pg_record(&items),
+    }
+}
+
+/// Write `text` into the cell through the regular edit path, so it becomes a
+/// pending UPDATE like any inline edit.
+fn commit_cell(tabular: &mut window_egui::Tabular, row: usize, col: usize, text: String) {
+    tabular.spreadsheet_start_cell_edit(row, col);
+    tabular.spreadsheet_state.cell_edit_text = text;
+    tabular.spreadsheet_finish_cell_edit(true);
+}
+
+pub(crate) fn render_structured_editor(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(state) = tabular.structured_edit.as_mut() else {
+        return;
+    };
+    let mut open = true;
+    let mut apply = false;
+    let mut as_text = false;
+    let mut cancel = false;
+    let title = match state.kind {
+        PgValueKind::Array => format!("🧩 {} (array)", state.column),
+        PgValueKind::Composite(_) => format!("🧩 {} (composite)", state.column),
+    };
+    egui::Window::new(title)
+        .id(egui::Id::new("structured_cell_editor"))
+        .collapsible(false)
+        .resizable(true)
+        .default_width(420.0)
+        .open(&mut open)
+        .show(ctx, |ui| {
+            let mut remove = None;
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("structured_cell_items")
+                        .num_columns(4)
+                        .spacing([8.0, 4.0])
+                        .show(ui, |ui| {
+                            let fields = match &state.kind {
+                                PgValueKind::Composite(fields) => Some(fields.clone()),
+                                PgValueKind::Array => None,
+                            };
+                            for (i, (value, null)) in state.items.iter_mut().enumerate() {
+                                let label = match &fields {
+                                    Some(fields) => fields.get(i).cloned().unwrap_or_default(),
+                                    None => format!("[{}]", i + 1),
+                                };
+                                ui.label(egui::RichText::new(label).monospace());
+                                ui.add_enabled(
+                                    !*null,
+                                    egui::TextEdit::singleline(value).desired_width(240.0),
+                                );
+                                ui.checkbox(null, "NULL");
+                                if fields.is_none()
+                                    && ui
+                                        .small_button("✕")
+                                        .on_hover_text("Remove element")
+                                        .clicked()
+                                {
+                                    remove = Some(i);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            if let Some(i) = remove {
+                state.items.remove(i);
+            }
+            if state.kind == PgValueKind::Array && ui.button("➕ Add element").clicked() {
+                state.items.push((String::new(), false));
+            }
+            ui.separator();
+            ui.label(
+                egui::RichText::new(edited_literal(state))
+                    .monospace()
+                    .weak(),
+            );
+            ui.horizontal(|ui| {
+                if ui.button("✔ Apply").clicked() {
+                    apply = true;
+                }
+                if ui
+                    .button("Edit as Text")
+                    .on_hover_text("Edit the raw literal in the cell instead")
+                    .clicked()
+                {
+                    as_text = true;
+                }
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        });
+
+    if apply {
+        if let Some(state) = tabular.structured_edit.take() {
+            let literal = edited_literal(&state);
+            commit_cell(tabular, state.row, state.col, literal);
+        }
+    } else if as_text {
+        if let Some(state) = tabular.structured_edit.take() {
+            tabular.selected_cell = Some((state.row, state.col));
+            tabular.spreadsheet_start_cell_edit(state.row, state.col);
+        }
+    } else if cancel || !open {
+        tabular.structured_edit = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structured_cells_read_as_lists() {
+        assert_eq!(
+            structured_display(&PgValueKind::Array, r#"{a,"b c",NULL}"#),
+            Some("[a, b c, NULL]".to_string())
+        );
+        let point = PgValueKind::Composite(vec!["x".into(), "y".into()]);
+        assert_eq!(
+            structured_display(&point, "(1,)"),
+            Some("x=1, y=NULL".to_string())
+        );
+        // Field count mismatch or unparsable text keeps the raw literal
+        assert_eq!(structured_display(&point, "(1,2,3)"), None);
+        assert_eq!(structured_display(&PgValueKind::Array, "{{1},{2}}"), None);
+    }
+}
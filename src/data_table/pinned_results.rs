@@ -0,0 +1,363 @@
+//! Pinned results: a snapshot of the result on screen, kept as an extra tab
+//! in the result bar so later runs in the same query tab do not replace it.
+//! A snapshot holds what was loaded (one page of a server-paged result), and
+//! each query tab keeps at most `MAX_PINNED_RESULTS` of them.
+
+use eframe::egui;
+
+use super::clear_table_selection;
+use crate::models::structs::{QueryResult, QueryTab};
+use crate::window_egui;
+
+/// Pinned results per query tab.
+pub(crate) const MAX_PINNED_RESULTS: usize = 5;
+
+#[derive(Clone, Debug)]
+pub struct PinnedResult {
+    pub label: String,
+    pub pinned_at: chrono::DateTime<chrono::Local>,
+    pub result: QueryResult,
+}
+
+/// Add `pin` unless the tab already holds the maximum.
+fn push_pin(pins: &mut Vec<PinnedResult>, pin: PinnedResult) -> Result<usize, String> {
+    if pins.len() >= MAX_PINNED_RESULTS {
+        return Err(format!(
+            "At most {} results can be pinned per tab — unpin one first",
+            MAX_PINNED_RESULTS
+        ));
+    }
+    pins.push(pin);
+    Ok(pins.len() - 1)
+}
+
+/// Pinned result on screen after pin `removed` goes away: `None` when it was
+/// the one shown, shifted down when it came after it.
+fn shown_after_unpin(shown: Option<usize>, removed: usize) -> Option<usize> {
+    match shown {
+        Some(i) if i == removed => None,
+        Some(i) if i > removed => Some(i - 1),
+        other => other,
+    }
+}
+
+/// The result on screen in `tab`: a pinned one or the selected run result.
+pub(crate) fn displayed_result(tab: &QueryTab) -> Option<&QueryResult> {
+    match tab.active_pinned {
+        Some(i) => tab.pinned_results.get(i).map(|p| &p.result),
+        None => tab.results.get(tab.active_result_index),
+    }
+}
+
+pub(crate) fn showing_pinned_result(tabular: &window_egui::Tabular) -> bool {
+    tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .is_some_and(|t| t.active_pinned.is_some())
+}
+
+/// The grid's current result as a stand-alone copy.
+fn current_view(tabular: &window_egui::Tabular) -> QueryResult {
+    let all_rows = if tabular.all_table_data.is_empty() {
+        tabular.current_table_data.clone()
+    } else {
+        tabular.all_table_data.clone()
+    };
+    let tab = tabular.query_tabs.get(tabular.active_tab_index);
+    QueryResult {
+        headers: tabular.current_table_headers.clone(),
+        rows: tabular.current_table_data.clone(),
+        total_rows: all_rows.len(),
+        all_rows,
+        table_name: tabular.current_table_name.clone(),
+        column_metadata: tabular.current_column_metadata.clone(),
+        current_page: tabular.current_page,
+        page_size: tabular.page_size,
+        query_message: tabular.query_message.clone(),
+        query_message_is_error: tabular.query_message_is_error,
+        execution_time_ms: tab
+            .and_then(displayed_result)
+            .map_or(0, |r| r.execution_time_ms),
+        explain_plan_json: None,
+        cached: None,
+    }
+}
+
+pub(crate) fn pin_current_result(tabular: &mut window_egui::Tabular) {
+    if tabular.current_table_headers.is_empty() {
+        return;
+    }
+    if showing_pinned_result(tabular) {
+        tabular.toasts.info("This result is already pinned");
+        return;
+    }
+    let mut result = current_view(tabular);
+    if tabular.use_server_pagination {
+        // The loaded page becomes the whole snapshot
+        result.current_page = 0;
+    }
+    let pinned_at = chrono::Local::now();
+    let pin = PinnedResult {
+        label: format!("📌 {}", pinned_at.format("%H:%M:%S")),
+        pinned_at,
+        result,
+    };
+    let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+        return;
+    };
+    match push_pin(&mut tab.pinned_results, pin) {
+        Ok(index) => {
+            let label = tab.pinned_results[index].label.clone();
+            tabular
+                .toasts
+                .success(format!("Pinned as {} — later runs keep it", label));
+        }
+        Err(e) => tabular.toasts.error(e),
+    }
+}
+
+/// Put `result` on screen and mirror it into the active tab's fields.
+fn show_result(tabular: &mut window_egui::Tabular, result: &QueryResult) {
+    clear_table_selection(tabular);
+    tabular.current_table_headers = result.headers.clone();
+    tabular.current_table_data = result.rows.clone();
+    tabular.all_table_data = result.all_rows.clone();
+    tabular.current_table_name = result.table_name.clone();
+    tabular.current_column_metadata = result.column_metadata.clone();
+    tabular.total_rows = result.total_rows;
+    tabular.current_page = result.current_page;
+    tabular.page_size = result.page_size;
+    tabular.query_message = result.query_message.clone();
+    tabular.query_message_is_error = result.query_message_is_error;
+    tabular.show_message_panel = true; // Always show message panel context
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.result_headers = result.headers.clone();
+        tab.result_rows = result.rows.clone();
+        tab.result_all_rows = result.all_rows.clone();
+        tab.result_table_name = result.table_name.clone();
+        tab.result_column_metadata = result.column_metadata.clone();
+        tab.query_message = result.query_message.clone();
+        tab.query_message_is_error = result.query_message_is_error;
+        tab.total_rows = result.total_rows;
+        tab.current_page = result.current_page;
+    }
+}
+
+/// Leave a pinned result. A server-paged result pages on the server again.
+fn leave_pinned(tabular: &mut window_egui::Tabular) {
+    let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+        return;
+    };
+    tab.active_pinned = None;
+    tab.live_result = None;
+    if !tab.base_query.is_empty() {
+        tabular.use_server_pagination = true;
+        tabular.current_base_query = tab.base_query.clone();
+    }
+}
+
+fn show_run_result(tabular: &mut window_egui::Tabular, index: usize) {
+    let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+        return;
+    };
+    let Some(result) = tab.results.get(index).cloned() else {
+        return;
+    };
+    tab.active_result_index = index;
+    if tab.active_pinned.is_some() {
+        leave_pinned(tabular);
+    }
+    show_result(tabular, &result);
+}
+
+/// Back to what was on screen before the first pinned result was opened.
+fn show_live_result(tabular: &mut window_egui::Tabular) {
+    let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+        return;
+    };
+    let live = tab.live_result.take();
+    leave_pinned(tabular);
+    if let Some(live) = live {
+        show_result(tabular, &live);
+    }
+}
+
+fn show_pinned_result(tabular: &mut window_egui::Tabular, index: usize) {
+    let live = current_view(tabular);
+    let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+        return;
+    };
+    let Some(pin) = tab.pinned_results.get(index) else {
+        return;
+    };
+    let result = pin.result.clone();
+    if tab.active_pinned.is_none() {
+        tab.live_result = Some(live);
+    }
+    tab.active_pinned = Some(index);
+    tabular.use_server_pagination = false;
+    show_result(tabular, &result);
+}
+
+pub(crate) fn unpin_result(tabular: &mut window_egui::Tabular, index: usize) {
+    let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+        return;
+    };
+    if index >= tab.pinned_results.len() {
+        return;
+    }
+    tab.pinned_results.remove(index);
+    if tab.active_pinned == Some(index) {
+        show_live_result(tabular);
+    } else {
+        tab.active_pinned = shown_after_unpin(tab.active_pinned, index);
+    }
+}
+
+/// Result tabs above the grid: the results of the last run (when it gave
+/// more than one) followed by the pinned results.
+pub(crate) fn render_result_tab_bar(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
+    let Some(tab) = tabular.query_tabs.get(tabular.active_tab_index) else {
+        return;
+    };
+    if tab.results.len() < 2 && tab.pinned_results.is_empty() {
+        return;
+    }
+    let run_count = if tab.results.len() > 1 {
+        tab.results.len()
+    } else {
+        0
+    };
+    let active_run = tab
+        .active_pinned
+        .is_none()
+        .then_some(tab.active_result_index);
+    let pins: Vec<(String, String)> = tab
+        .pinned_results
+        .iter()
+        .map(|p| {
+            (
+                p.label.clone(),
+                format!(
+                    "Pinned {} · {} row(s)\n{}",
+                    p.pinned_at.format("%Y-%m-%d %H:%M:%S"),
+                    p.result.all_rows.len(),
+                    p.result.table_name
+                ),
+            )
+        })
+        .collect();
+    let active_pin = tab.active_pinned;
+    let accent = crate::window_egui::style::theme_accent(ui.ctx());
+    let tab_button = |label: &str, active: bool| {
+        if active {
+            egui::Button::new(
+                egui::RichText::new(label)
+                    .strong()
+                    .color(egui::Color32::WHITE),
+            )
+            .fill(accent)
+        } else {
+            egui::Button::new(label)
+        }
+    };
+
+    let mut show_run: Option<usize> = None;
+    let mut show_latest = false;
+    let mut show_pin: Option<usize> = None;
+    let mut unpin: Option<usize> = None;
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 2.0;
+        for i in 0..run_count {
+            let label = format!("Result {}", i + 1);
+            if ui.add(tab_button(&label, active_run == Some(i))).clicked() {
+                show_run = Some(i);
+            }
+        }
+        if !pins.is_empty() {
+            if run_count > 0 {
+                ui.separator();
+            } else if ui
+                .add(tab_button("Latest", active_run.is_some()))
+                .on_hover_text("Result of the last run")
+                .clicked()
+            {
+                show_latest = true;
+            }
+            for (i, (label, hover)) in pins.iter().enumerate() {
+                let resp = ui
+                    .add(tab_button(label, active_pin == Some(i)))
+                    .on_hover_text(hover);
+                if resp.clicked() {
+                    show_pin = Some(i);
+                }
+                resp.context_menu(|ui| {
+                    if ui.button("Unpin").clicked() {
+                        unpin = Some(i);
+                        ui.close();
+                    }
+                });
+                if ui
+                    .small_button("✕")
+                    .on_hover_text("Unpin this result")
+                    .clicked()
+                {
+                    unpin = Some(i);
+                }
+            }
+        }
+    });
+    ui.separator();
+
+    if let Some(i) = unpin {
+        unpin_result(tabular, i);
+    } else if let Some(i) = show_pin {
+        show_pinned_result(tabular, i);
+    } else if let Some(i) = show_run {
+        show_run_result(tabular, i);
+    } else if show_latest && active_pin.is_some() {
+        show_live_result(tabular);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pin(label: &str) -> PinnedResult {
+        PinnedResult {
+            label: label.to_string(),
+            pinned_at: chrono::Local::now(),
+            result: QueryResult {
+                headers: vec!["id".to_string()],
+                rows: vec![vec!["1".to_string()]],
+                all_rows: vec![vec!["1".to_string()]],
+                table_name: String::new(),
+                column_metadata: None,
+                current_page: 0,
+                page_size: 100,
+                total_rows: 1,
+                query_message: String::new(),
+                query_message_is_error: false,
+                execution_time_ms: 0,
+                explain_plan_json: None,
+                cached: None,
+            },
+        }
+    }
+
+    #[test]
+    fn pins_are_bounded_and_unpinning_keeps_the_shown_one() {
+        let mut pins = Vec::new();
+        for i in 0..MAX_PINNED_RESULTS {
+            assert_eq!(push_pin(&mut pins, pin(&i.to_string())), Ok(i));
+        }
+        assert!(push_pin(&mut pins, pin("extra")).is_err());
+        assert_eq!(pins.len(), MAX_PINNED_RESULTS);
+
+        assert_eq!(shown_after_unpin(Some(2), 2), None);
+        assert_eq!(shown_after_unpin(Some(3), 1), Some(2));
+        assert_eq!(shown_after_unpin(Some(0), 1), Some(0));
+        assert_eq!(shown_after_unpin(None, 0), None);
+    }
+}
@@ -0,0 +1,159 @@
+//! Record view: the current result page shown one row at a time as a
+//! field/value form. Easier to read than the grid for single-row results and
+//! very wide rows. The choice is remembered per tab (`QueryTab::record_view`).
+
+use eframe::egui;
+
+use super::{
+    ResolvedFormatter, cell_preview, convert_for_display, format_cell, formatted_columns,
+    json_columns, open_cell_detail, parse_json_cell, temporal_columns,
+};
+use crate::{config::DisplayTimezone, window_egui};
+
+/// True when the active tab shows its result as records instead of the grid.
+pub(crate) fn record_view_active(tabular: &window_egui::Tabular) -> bool {
+    tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .is_some_and(|t| t.record_view)
+}
+
+/// Switch the active tab between grid and record view. The record view opens
+/// on the selected row; going back to the grid selects the record shown.
+pub(crate) fn toggle_record_view(tabular: &mut window_egui::Tabular) {
+    let entering = !record_view_active(tabular);
+    if entering {
+        tabular.record_view_index = tabular
+            .selected_cell
+            .map(|(row, _)| row)
+            .or(tabular.selected_row)
+            .or_else(|| tabular.selected_rows.first().copied())
+            .unwrap_or(0);
+    } else if tabular.record_view_index < tabular.current_table_data.len() {
+        tabular.selected_cell = Some((tabular.record_view_index, 0));
+        tabular.scroll_to_selected_cell = true;
+    }
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.record_view = entering;
+        if entering {
+            tab.transposed = false;
+        }
+    }
+}
+
+/// Text shown for one field: display timezone and column formatters applied,
+/// JSON pretty-printed.
+pub(super) fn field_text(
+    cell: &str,
+    col: usize,
+    temporal: &[bool],
+    json: &[bool],
+    formatters: &[Option<ResolvedFormatter>],
+    tz: DisplayTimezone,
+) -> String {
+    if let Some(value) = parse_json_cell(cell, json.get(col).copied().unwrap_or(false))
+        && let Ok(pretty) = serde_json::to_string_pretty(&value)
+    {
+        return pretty;
+    }
+    let converted = if temporal.get(col).copied().unwrap_or(false) {
+        convert_for_display(cell, tz)
+    } else {
+        None
+    };
+    format_cell(formatters, col, cell)
+        .or(converted)
+        .unwrap_or_else(|| cell.to_string())
+}
+
+pub(crate) fn render_record_view(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
+    let total = tabular.current_table_data.len();
+    if total == 0 {
+        ui.add_space(4.0);
+        ui.label(egui::RichText::new("0 rows").italics().weak());
+        return;
+    }
+    tabular.record_view_index = tabular.record_view_index.min(total - 1);
+    let index = tabular.record_view_index;
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(index > 0, egui::Button::new("◀ Prev"))
+            .clicked()
+        {
+            tabular.record_view_index -= 1;
+        }
+        ui.label(format!("Record {} of {}", index + 1, total));
+        if ui
+            .add_enabled(index + 1 < total, egui::Button::new("Next ▶"))
+            .clicked()
+        {
+            tabular.record_view_index += 1;
+        }
+    });
+    ui.separator();
+
+    let headers = tabular.current_table_headers.clone();
+    let row = tabular.current_table_data[index].clone();
+    let temporal = temporal_columns(tabular);
+    let json = json_columns(tabular);
+    let formatters = formatted_columns(tabular);
+    let tz = tabular.display_timezone;
+    let preview_chars = tabular.cell_preview_chars;
+    let mut detail_request: Option<usize> = None;
+
+    // Leave room for the pagination bar rendered below
+    let height = (ui.available_height() - 48.0).max(50.0);
+    egui::ScrollArea::vertical()
+        .id_salt("record_view_scroll")
+        .max_height(height)
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("record_view_grid")
+                .num_columns(2)
+                .striped(true)
+                .spacing([16.0, 6.0])
+                .show(ui, |ui| {
+                    for (col, name) in headers.iter().enumerate() {
+                        let cell = row.get(col).map(String::as_str).unwrap_or("");
+                        ui.label(egui::RichText::new(name).strong());
+                        let resp = if cell == "NULL" {
+                            ui.label(egui::RichText::new("NULL").italics().weak())
+                        } else {
+                            let shown = cell_preview(cell, preview_chars);
+                            let text = field_text(&shown, col, &temporal, &json, &formatters, tz);
+                            ui.add(egui::Label::new(egui::RichText::new(text).monospace()).wrap())
+                        };
+                        resp.context_menu(|ui| {
+                            if ui.button("📋 Copy Value").clicked() {
+                                ui.ctx().copy_text(cell.to_string());
+                                ui.close();
+                            }
+                            if ui.button("🔍 View Value…").clicked() {
+                                detail_request = Some(col);
+                                ui.close();
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(col) = detail_request {
+        open_cell_detail(tabular, index, col);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_text_pretty_prints_json_only() {
+        let tz = DisplayTimezone::AsStored;
+        let pretty = field_text(r#"{"a":1}"#, 0, &[], &[], &[], tz);
+        assert_eq!(pretty, "{\n  \"a\": 1\n}");
+        assert_eq!(field_text("plain text", 0, &[], &[], &[], tz), "plain text");
+        assert_eq!(field_text("42", 0, &[], &[true], &[], tz), "42");
+    }
+}
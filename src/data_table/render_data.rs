@@ -1,75 +1,114 @@
 use eframe::egui;
-use crate::{export, spreadsheet::SpreadsheetOperations, window_egui};
+use crate::{export, models, spreadsheet::SpreadsheetOperations, window_egui};
 use chrono::Timelike;
 use super::{
     initialize_column_widths, get_column_width, set_column_width,
-    refresh_current_table_data, infer_current_table_name,
+    remember_column_layout, reset_column_layout,
+    refresh_current_table_data,
     handle_row_click, handle_column_click,
     copy_selected_block_as_csv, copy_selected_rows_as_csv, copy_selected_columns_as_csv,
     copy_selected_as_sql_inserts, copy_selected_as_markdown,
     export_selected_to_sql_inserts, export_selected_to_markdown,
     apply_sql_filter, sort_table_data,
-    render_pagination_bar,
+    render_pagination_bar, temporal_columns, convert_for_display, export_table_data, open_sqlite_export_dialog,
+    allowed_values_for_column, render_filter_chips, column_value_menu, add_filter_chip,
+    FilterChip, json_columns, parse_json_cell, open_cell_detail, formatted_columns, format_cell, numeric_columns, number_override_menu,
+    is_oversized, cell_preview, view_full_button,
+    record_view_active, render_record_view, open_column_stats, edit_flash_color, tick_edit_flashes,
+    structured_columns, structured_display, open_structured_editor,
+    hidden_column_mask, set_column_hidden, export_headers, render_index_hints,
+    transpose_active, render_transposed,
 };
-use super::utils::parse_enum_values;
+
+const STATEMENT_OUTCOME_PREFIX: &str = "Statement executed successfully";
+
+/// Result-area caption for a statement that returned no result set.
+pub(crate) fn statement_outcome_caption(rows_affected: usize) -> String {
+    format!("{} ({} row(s) affected)", STATEMENT_OUTCOME_PREFIX, rows_affected)
+}
+
+/// WHERE filter, filter chips and unsaved-changes badge shown above browsed tables.
+fn render_browse_toolbar(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        // WHERE filter
+        ui.label("WHERE:");
+        let filter_response = ui.add_sized(
+            [ui.available_width() * 0.8, 25.0],
+            egui::TextEdit::singleline(&mut tabular.sql_filter_text)
+                .hint_text("column = 'value' AND col2 > 0")
+                .interactive(true),
+        );
+
+        if filter_response.has_focus() || filter_response.hovered() {
+            let visuals = ui.visuals();
+            let accent = if filter_response.has_focus() {
+                visuals.selection.stroke.color
+            } else {
+                visuals.widgets.hovered.bg_stroke.color
+            };
+            let rect = filter_response.rect.expand(2.0);
+            ui.painter().rect_stroke(
+                rect,
+                4.0,
+                egui::Stroke::new(1.6, accent),
+                egui::StrokeKind::Outside,
+            );
+        }
+
+        // Apply filter when:
+        // - Enter is pressed while the field has focus, or
+        // - The field loses focus (more forgiving than requiring `changed()`)
+        // This avoids cases where `lost_focus && changed` misses due to frame timing.
+        let enter_pressed = ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (filter_response.has_focus() && enter_pressed)
+            || filter_response.lost_focus()
+        {
+            apply_sql_filter(tabular);
+        }
+        if ui.button("❌").on_hover_text("Clear filter").clicked() {
+            tabular.sql_filter_text.clear();
+            apply_sql_filter(tabular);
+        }
+        if tabular.spreadsheet_state.is_dirty {
+            ui.separator();
+            ui.colored_label(
+                crate::window_egui::style::theme_warning(ui.ctx()),
+                "Unsaved changes (⌘S)",
+            );
+        }
+    });
+    render_filter_chips(tabular, ui);
+    ui.separator();
+}
 
 pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
+    tick_edit_flashes(tabular, ui.ctx());
+    #[cfg(feature = "query_ast")]
+    if !tabular.is_table_browse_mode {
+        tabular.render_query_plan_panel(ui);
+    }
+    if !tabular.is_table_browse_mode {
+        render_index_hints(tabular, ui);
+    }
     if !tabular.current_table_headers.is_empty() || !tabular.current_table_name.is_empty() {
         // This function now only renders DATA grid (toggle handled at higher level for table tabs)
 
-        // Show grid whenever we have headers (even if 0 rows) so user sees column structure
-        if !tabular.current_table_headers.is_empty() {
+        // Record view replaces the grid when the tab asks for it; otherwise show the
+        // grid whenever we have headers (even if 0 rows) so user sees column structure
+        if !tabular.current_table_headers.is_empty() && record_view_active(tabular) {
+            if tabular.is_table_browse_mode {
+                render_browse_toolbar(tabular, ui);
+            }
+            render_record_view(tabular, ui);
+        } else if !tabular.current_table_headers.is_empty() && transpose_active(tabular) {
+            if tabular.is_table_browse_mode {
+                render_browse_toolbar(tabular, ui);
+            }
+            render_transposed(tabular, ui);
+        } else if !tabular.current_table_headers.is_empty() {
             // Toolbar: filter + spreadsheet actions (only in table browse mode)
             if tabular.is_table_browse_mode {
-                ui.horizontal(|ui| {
-                    // WHERE filter
-                    ui.label("WHERE:");
-                    let filter_response = ui.add_sized(
-                        [ui.available_width() * 0.8, 25.0],
-                        egui::TextEdit::singleline(&mut tabular.sql_filter_text)
-                            .hint_text("column = 'value' AND col2 > 0")
-                            .interactive(true),
-                    );
-
-                    if filter_response.has_focus() || filter_response.hovered() {
-                        let visuals = ui.visuals();
-                        let accent = if filter_response.has_focus() {
-                            visuals.selection.stroke.color
-                        } else {
-                            visuals.widgets.hovered.bg_stroke.color
-                        };
-                        let rect = filter_response.rect.expand(2.0);
-                        ui.painter().rect_stroke(
-                            rect,
-                            4.0,
-                            egui::Stroke::new(1.6, accent),
-                            egui::StrokeKind::Outside,
-                        );
-                    }
-
-                    // Apply filter when:
-                    // - Enter is pressed while the field has focus, or
-                    // - The field loses focus (more forgiving than requiring `changed()`)
-                    // This avoids cases where `lost_focus && changed` misses due to frame timing.
-                    let enter_pressed = ui.input(|i| i.key_pressed(egui::Key::Enter));
-                    if (filter_response.has_focus() && enter_pressed)
-                        || filter_response.lost_focus()
-                    {
-                        apply_sql_filter(tabular);
-                    }
-                    if ui.button("❌").on_hover_text("Clear filter").clicked() {
-                        tabular.sql_filter_text.clear();
-                        apply_sql_filter(tabular);
-                    }
-                    if tabular.spreadsheet_state.is_dirty {
-                        ui.separator();
-                        ui.colored_label(
-                            crate::window_egui::style::theme_warning(ui.ctx()),
-                            "Unsaved changes (⌘S)",
-                        );
-                    }
-                });
-                ui.separator();
+                render_browse_toolbar(tabular, ui);
             }
 
             // Store sort state locally to avoid borrowing issues
@@ -84,12 +123,20 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
             // Defer actions that would mutate tabular during borrow of iter
             let mut start_edit_request: Option<(usize, usize)> = None;
             let mut cell_edit_text_update: Option<String> = None;
+            // Edit field lost focus to another widget (click/Tab elsewhere)
+            let mut cell_edit_blurred = false;
             // Defer any column width updates to avoid mut borrow in closures
             let mut deferred_width_updates: Vec<(usize, f32)> = Vec::new();
+            let mut column_resize_finished = false;
             // Defer delete-row action to avoid mutable borrow inside UI closures
             let mut delete_row_index_request: Option<usize> = None;
             let mut add_row_request: Option<usize> = None;
             let mut open_csv_import = false;
+            // Defer quick filter chips picked from a header or cell menu
+            let mut filter_chip_request: Option<FilterChip> = None;
+            let mut column_stats_request: Option<usize> = None;
+            // Defer opening the value viewer for a cell
+            let mut cell_detail_request: Option<(usize, usize)> = None;
 
             // Ensure column widths are initialized
             if tabular.column_widths.len() != headers.len() {
@@ -110,6 +157,9 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                 }
             }
 
+            // Hidden columns stay in the data but are skipped below
+            let hidden_cols = hidden_column_mask(tabular, &headers);
+
             // Tata letak: sticky header (32px) + data scroll + pagination bar.
             let avail_h = ui.available_height();
             let pagination_height_est = 44.0_f32;
@@ -125,9 +175,10 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
             );
             {
                 let total_content_w: f32 = 60.0
-                    + headers.iter().enumerate().map(|(i, _)| {
-                        get_column_width(tabular, i).max(30.0)
-                    }).sum::<f32>();
+                    + headers.iter().enumerate()
+                        .filter(|(i, _)| !hidden_cols[*i])
+                        .map(|(i, _)| get_column_width(tabular, i).max(30.0))
+                        .sum::<f32>();
                 let content_rect = egui::Rect::from_min_size(
                     egui::pos2(
                         header_alloc_rect.min.x - tabular.data_scroll_x,
@@ -186,6 +237,9 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
 
                 // Column header cells
                 for (col_index, header) in headers.iter().enumerate() {
+                    if hidden_cols[col_index] {
+                        continue;
+                    }
                     let column_width = if Some(col_index) == error_column_index {
                         if get_column_width(tabular, col_index) <= 180.0 {
                             set_column_width(tabular, col_index, 600.0);
@@ -218,7 +272,10 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                             ui.horizontal(|ui| {
                                 ui.spacing_mut().item_spacing.x = 0.0;
                                 let sort_button_width = 45.0;
-                                let text_area_width = ui.available_width() - sort_button_width;
+                                let values_button_width =
+                                    if tabular.is_table_browse_mode { 18.0 } else { 0.0 };
+                                let text_area_width =
+                                    ui.available_width() - sort_button_width - values_button_width;
                                 ui.allocate_ui_with_layout(
                                     [text_area_width, ui.available_height()].into(),
                                     egui::Layout::top_down(egui::Align::Center),
@@ -231,6 +288,16 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                         ));
                                     },
                                 );
+                                if tabular.is_table_browse_mode {
+                                    ui.menu_button("▾", |ui| {
+                                        ui.set_min_width(180.0);
+                                        if let Some(chip) = column_value_menu(tabular, ui, col_index) {
+                                            filter_chip_request = Some(chip);
+                                        }
+                                    })
+                                    .response
+                                    .on_hover_text("Filter by value");
+                                }
                                 let (is_sorted_column, is_asc) =
                                     if current_sort_column == Some(col_index) {
                                         (true, current_sort_ascending)
@@ -316,6 +383,22 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                     let modifiers = ui.input(|i| i.modifiers);
                                     col_sel_requests.push((col_index, modifiers));
                                 }
+                                header_click_resp.context_menu(|ui| {
+                                    if ui.button("📊 Column Stats…").clicked() {
+                                        column_stats_request = Some(col_index);
+                                        ui.close();
+                                    }
+                                    number_override_menu(tabular, ui, header);
+                                    ui.separator();
+                                    if ui.button("🙈 Hide Column").clicked() {
+                                        set_column_hidden(tabular, header, true);
+                                        ui.close();
+                                    }
+                                    if ui.button("☰ Manage Columns…").clicked() {
+                                        tabular.show_manage_columns = true;
+                                        ui.close();
+                                    }
+                                });
                             });
                             // Resize handle
                             let handle_x = ui.max_rect().max.x;
@@ -345,6 +428,9 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                 let new_width = column_width + resize_response.drag_delta().x;
                                 deferred_width_updates.push((col_index, new_width));
                             }
+                            if resize_response.drag_stopped() {
+                                column_resize_finished = true;
+                            }
                         },
                     );
                 }
@@ -362,46 +448,50 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
             // Defer refresh action to avoid mutable borrow inside UI closures
             let mut refresh_request_data = false;
 
-            // Virtual scroll: only render rows visible in the viewport.
-            // Previous frame's scroll offset drives row range — 1-frame lag is imperceptible.
+            // Virtual scroll: rows have a fixed height, so `show_rows` lays out only the
+            // rows intersecting the viewport while the content keeps its full height.
             const ROW_HEIGHT: f32 = 28.0;
             let total_rows = tabular.current_table_data.len();
-            let prev_scroll_y = tabular.data_scroll_y;
-            let first_row = ((prev_scroll_y / ROW_HEIGHT) as usize).saturating_sub(3);
-            let last_row = (((prev_scroll_y + data_h) / ROW_HEIGHT).ceil() as usize + 4).min(total_rows);
+
+            // Temporal columns shown in the preferred display timezone
+            let temporal_cols = temporal_columns(tabular);
+            let structured_cols = structured_columns(tabular);
+            let json_cols = json_columns(tabular);
+            let display_tz = tabular.display_timezone;
+            let formatters = formatted_columns(tabular);
+            let numeric_cols = numeric_columns(tabular);
+            let preview_chars = tabular.cell_preview_chars;
 
             // Pre-compute total content width (matches sticky header formula)
             let total_content_w: f32 = 60.0
                 + headers.iter().enumerate()
+                    .filter(|(i, _)| !hidden_cols[*i])
                     .map(|(i, _)| get_column_width(tabular, i).max(30.0))
                     .sum::<f32>();
 
-            let scroll_out = egui::ScrollArea::both()
+            let scroll_area = egui::ScrollArea::both()
                 .id_salt("table_data_scroll")
                 .horizontal_scroll_offset(tabular.data_scroll_x)
-                .auto_shrink([false, false])
-                .show(&mut scroll_child, |ui| {
+                .auto_shrink([false, false]);
+            // show_rows adds item spacing to the row pitch; rows butt against each other
+            scroll_child.spacing_mut().item_spacing = egui::vec2(0.0, 0.0);
+            let scroll_out = scroll_area
+                .show_rows(&mut scroll_child, ROW_HEIGHT, total_rows, |ui, row_range| {
                     ui.spacing_mut().item_spacing = egui::vec2(0.0, 0.0);
                     // Establish full content width so horizontal scrollbar is correct
                     ui.set_min_width(total_content_w);
+                    // Top-left of row 0 in screen space (the child ui starts at the first visible row)
+                    let content_origin =
+                        ui.max_rect().left_top() - egui::vec2(0.0, row_range.start as f32 * ROW_HEIGHT);
 
-                    // Clone data (borrow checker: tabular fields mutated inside loop)
-                    let current_table_data = tabular.current_table_data.clone();
+                    // Clone only the visible slice (borrow checker: tabular fields mutated inside loop)
+                    let visible_rows = tabular.current_table_data[row_range.clone()].to_vec();
                     let selected_rows = tabular.selected_rows.clone();
                     let selected_row = tabular.selected_row;
                     let newly_created_rows = tabular.newly_created_rows.clone();
+                    let edit_flashes = tabular.edit_flashes.clone();
 
-                    // Top spacer: allocate space for rows above the viewport
-                    if first_row > 0 {
-                        ui.add_space(first_row as f32 * ROW_HEIGHT);
-                    }
-
-                    for (row_index, row) in current_table_data
-                        .iter()
-                        .enumerate()
-                        .take(last_row)
-                        .skip(first_row)
-                    {
+                    for (row_index, row) in row_range.clone().zip(visible_rows.iter()) {
                         let is_selected_row = selected_rows.contains(&row_index)
                             || selected_row == Some(row_index);
                         let is_newly_created = newly_created_rows.contains(&row_index);
@@ -505,6 +595,28 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                     },
                                 );
                                 for (col_index, cell) in row.iter().enumerate() {
+                                    if hidden_cols.get(col_index).copied().unwrap_or(false) {
+                                        continue;
+                                    }
+                                    // Oversized values skip the display transforms and show a preview
+                                    let oversized = is_oversized(cell, preview_chars);
+                                    let converted = if oversized {
+                                        None
+                                    } else if temporal_cols.get(col_index).copied().unwrap_or(false) {
+                                        convert_for_display(cell, display_tz)
+                                    } else if let Some(Some(kind)) = structured_cols.get(col_index) {
+                                        structured_display(kind, cell)
+                                    } else {
+                                        None
+                                    };
+                                    let converted = if oversized {
+                                        None
+                                    } else {
+                                        format_cell(&formatters, col_index, cell).or(converted)
+                                    };
+                                    let preview = oversized
+                                        .then(|| cell_preview(cell, preview_chars).into_owned());
+                                    let cell = preview.as_ref().or(converted.as_ref()).unwrap_or(cell);
                                     let is_selected_cell =
                                         tabular.selected_cell == Some((row_index, col_index));
                                     let is_selected_col =
@@ -602,6 +714,14 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                                     rect.left_bottom(), rect.left_top(),
                                                 ], stroke);
                                             }
+                                            if let Some(flash) = edit_flash_color(
+                                                &edit_flashes,
+                                                row_index,
+                                                col_index,
+                                                ui.visuals().dark_mode,
+                                            ) {
+                                                ui.painter().rect_filled(rect, 0.0, flash);
+                                            }
                                             let max_chars =
                                                 ((column_width / 8.0).floor() as usize).max(10);
                                             let display_text = if cell.chars().count() > max_chars {
@@ -695,7 +815,7 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                                     ui.scope_builder(
                                                         egui::UiBuilder::new().max_rect(text_edit_rect),
                                                         |ui| {
-                                                            let valid_options = tabular.spreadsheet_state.enum_options.clone();
+                                                            let allowed = tabular.spreadsheet_state.allowed_values.clone();
                                                             // Determine if column is Date/DateTime
                                                             let mut is_date_type = false;
                                                             let mut is_datetime_type = false;
@@ -710,16 +830,16 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                                                 }
                                                             }
 
-                                                            if let Some(options) = valid_options {
-                                                                // Render ComboBox for ENUM types
+                                                            if let Some(allowed) = allowed.as_ref().filter(|a| a.is_exhaustive()) {
+                                                                // Enum / CHECK value set: pick one of the allowed values
                                                                 let mut current_val = edit_text.clone();
                                                                 let combo = egui::ComboBox::from_id_salt("enum_combo")
                                                                     .selected_text(&current_val)
                                                                     .height(200.0)
                                                                     .show_ui(ui, |ui| {
                                                                         let mut changed = false;
-                                                                        for opt in options {
-                                                                             if ui.selectable_value(&mut current_val, opt.clone(), &opt).clicked() {
+                                                                        for opt in &allowed.values {
+                                                                             if ui.selectable_value(&mut current_val, opt.clone(), opt).clicked() {
                                                                                  changed = true;
                                                                              }
                                                                         }
@@ -729,6 +849,41 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                                                 if combo.inner.unwrap_or(false) {
                                                                     edit_text = current_val;
                                                                 }
+                                                            } else if let Some(models::structs::AllowedValues {
+                                                                values,
+                                                                source: models::structs::AllowedValuesSource::ForeignKey { table, column },
+                                                            }) = &allowed
+                                                            {
+                                                                // Foreign key: free text plus a lookup of referenced values
+                                                                ui.horizontal(|ui| {
+                                                                    ui.spacing_mut().item_spacing.x = 2.0;
+                                                                    let button_w = 22.0;
+                                                                    let resp = ui.add_sized(
+                                                                        [(ui.available_width() - button_w).max(20.0), ui.available_height()],
+                                                                        egui::TextEdit::singleline(&mut edit_text),
+                                                                    );
+                                                                    if !tabular.spreadsheet_state.edit_focus_requested {
+                                                                        resp.request_focus();
+                                                                        tabular.spreadsheet_state.edit_focus_requested = true;
+                                                                    }
+                                                                    ui.menu_button("▾", |ui| {
+                                                                        ui.label(
+                                                                            egui::RichText::new(format!("{}.{}", table, column))
+                                                                                .small()
+                                                                                .weak(),
+                                                                        );
+                                                                        egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                                                                            for value in values {
+                                                                                if ui.selectable_label(*value == edit_text, value).clicked() {
+                                                                                    edit_text = value.clone();
+                                                                                    ui.close();
+                                                                                }
+                                                                            }
+                                                                        });
+                                                                    })
+                                                                    .response
+                                                                    .on_hover_text(format!("Values from {}.{}", table, column));
+                                                                });
                                                             } else if is_date_type {
                                                                 // DATE Picker
                                                                 ui.horizontal(|ui| {
@@ -801,9 +956,19 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
 
                                                                 let response = ui.add(text_edit);
 
-                                                                // Auto-focus the text edit when we start editing
-                                                                if !response.has_focus() {
+                                                                // Focus the field once when the edit starts. Re-requesting
+                                                                // focus every frame would trap the caret in the cell when
+                                                                // the user clicks or tabs to the editor.
+                                                                if !tabular.spreadsheet_state.edit_focus_requested {
                                                                     response.request_focus();
+                                                                    tabular.spreadsheet_state.edit_focus_requested = true;
+                                                                } else if response.lost_focus()
+                                                                    && !ui.input(|i| {
+                                                                        i.key_pressed(egui::Key::Enter)
+                                                                            || i.key_pressed(egui::Key::Escape)
+                                                                    })
+                                                                {
+                                                                    cell_edit_blurred = true;
                                                                 }
                                                             }
                                                         },
@@ -812,12 +977,21 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                                 // Store the updated text to apply later
                                                 cell_edit_text_update = Some(edit_text);
                                             } else {
-                                                // Show normal cell text
-                                                let text_pos = rect.left_top()
-                                                    + egui::vec2(5.0, rect.height() * 0.5);
+                                                // Show normal cell text; numbers line up on the right
+                                                let (text_pos, align) = if numeric_cols.get(col_index).copied().unwrap_or(false) {
+                                                    (
+                                                        rect.right_top() + egui::vec2(-5.0, rect.height() * 0.5),
+                                                        egui::Align2::RIGHT_CENTER,
+                                                    )
+                                                } else {
+                                                    (
+                                                        rect.left_top() + egui::vec2(5.0, rect.height() * 0.5),
+                                                        egui::Align2::LEFT_CENTER,
+                                                    )
+                                                };
                                                 ui.painter().text(
                                                     text_pos,
-                                                    egui::Align2::LEFT_CENTER,
+                                                    align,
                                                     &display_text,
                                                     egui::FontId::default(),
                                                     if is_selected_cell {
@@ -830,6 +1004,16 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                                         ui.visuals().text_color()
                                                     },
                                                 );
+                                                if oversized
+                                                    && view_full_button(
+                                                        ui,
+                                                        rect,
+                                                        egui::Id::new(("cell_full", row_index, col_index)),
+                                                        row[col_index].len(),
+                                                    )
+                                                {
+                                                    cell_detail_request = Some((row_index, col_index));
+                                                }
                                             }
                                             cell_resp.context_menu(|ui| {
                                                 ui.set_min_width(150.0);
@@ -847,7 +1031,34 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                                     }
                                                     ui.separator();
                                                     if ui.button("📋 Copy Cell Value").clicked() {
-                                                        ui.ctx().copy_text(cell.clone());
+                                                        ui.ctx().copy_text(
+                                                            converted.clone().unwrap_or_else(|| row[col_index].clone()),
+                                                        );
+                                                        ui.close();
+                                                    }
+                                                    if converted.is_some()
+                                                        && ui.button("📋 Copy Raw Value").clicked()
+                                                    {
+                                                        ui.ctx().copy_text(row[col_index].clone());
+                                                        ui.close();
+                                                    }
+                                                    let typed_json = json_cols.get(col_index).copied().unwrap_or(false);
+                                                    let view_label = if parse_json_cell(cell, typed_json).is_some() {
+                                                        "🔍 View JSON…"
+                                                    } else {
+                                                        "🔍 View Value…"
+                                                    };
+                                                    if ui.button(view_label).clicked() {
+                                                        cell_detail_request = Some((row_index, col_index));
+                                                        ui.close();
+                                                    }
+                                                    if tabular.is_table_browse_mode
+                                                        && ui.button("🔎 Filter by this value").clicked()
+                                                    {
+                                                        filter_chip_request = Some(FilterChip::from_cell(
+                                                            &headers[col_index],
+                                                            cell,
+                                                        ));
                                                         ui.close();
                                                     }
                                                     if tabular.table_sel_anchor.is_some()
@@ -925,36 +1136,121 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                                         }
                                                         ui.close();
                                                     }
-                                                    ui.separator();
-                                                    if ui.button("📄 Export to CSV").clicked() {
-                                                        export::export_to_csv(
-                                                            &tabular.all_table_data,
+                                                    if !tabular.is_table_browse_mode
+                                                        && tabular
+                                                            .query_tabs
+                                                            .get(tabular.active_tab_index)
+                                                            .is_some_and(|t| {
+                                                                t.dba_special_mode
+                                                                    == Some(models::enums::DBASpecialMode::SessionList)
+                                                            })
+                                                        && let (Some(db), Some(row_idx)) =
+                                                            (db_type.as_ref(), tabular.selected_row)
+                                                        && crate::connection::kill_session::is_session_view_query(
+                                                            db,
+                                                            &tabular.editor.text,
+                                                        )
+                                                        && let Some(cols) = crate::connection::kill_session::session_columns(
+                                                            db,
                                                             &tabular.current_table_headers,
-                                                            &tabular.current_table_name,
-                                                        );
-                                                        ui.close();
+                                                        )
+                                                        && let Some(row_data) =
+                                                            tabular.current_table_data.get(row_idx)
+                                                        && let Some(session_id) = row_data.get(cols.id)
+                                                        && crate::connection::kill_session::kill_session_statement(db, session_id)
+                                                            .is_some()
+                                                        && let Some(conn_id) = tabular.current_connection_id
+                                                    {
+                                                        ui.separator();
+                                                        if ui
+                                                            .button(format!("⛔ Kill Session {}", session_id.trim()))
+                                                            .clicked()
+                                                        {
+                                                            tabular.pending_session_kill =
+                                                                Some(crate::models::structs::SessionKillRequest {
+                                                                    connection_id: conn_id,
+                                                                    session_id: session_id.trim().to_string(),
+                                                                    query: cols
+                                                                        .query
+                                                                        .and_then(|i| row_data.get(i))
+                                                                        .filter(|q| q.as_str() != "NULL")
+                                                                        .cloned(),
+                                                                    rerun_query: tabular.editor.text.clone(),
+                                                                    error: None,
+                                                                });
+                                                            ui.close();
+                                                        }
                                                     }
+                                                    ui.separator();
+                                                    ui.menu_button("📄 Export to CSV", |ui| {
+                                                        for delimiter in export::CsvDelimiter::ALL {
+                                                            if ui.button(delimiter.label()).clicked() {
+                                                                export::export_to_csv(
+                                                                    &export_table_data(tabular),
+                                                                    &export_headers(tabular),
+                                                                    &tabular.current_table_name,
+                                                                    delimiter,
+                                                                );
+                                                                ui.close();
+                                                            }
+                                                        }
+                                                    });
                                                     if ui.button("📊 Export to XLSX").clicked() {
                                                         export::export_to_xlsx(
-                                                            &tabular.all_table_data,
-                                                            &tabular.current_table_headers,
+                                                            &export_table_data(tabular),
+                                                            &export_headers(tabular),
                                                             &tabular.current_table_name,
                                                         );
                                                         ui.close();
                                                     }
-                                                    if ui.button("🧾 Export to JSON").clicked() {
-                                                        export::export_to_json(
-                                                            &tabular.all_table_data,
-                                                            &tabular.current_table_headers,
+                                                    ui.menu_button("🧾 Export as JSON", |ui| {
+                                                        for (label, typed_numbers) in
+                                                            [("Numbers as JSON numbers", true), ("All values as strings", false)]
+                                                        {
+                                                            if ui.button(label).clicked() {
+                                                                export::export_to_json(
+                                                                    &export_table_data(tabular),
+                                                                    &export_headers(tabular),
+                                                                    &tabular.current_table_name,
+                                                                    typed_numbers,
+                                                                );
+                                                                ui.close();
+                                                            }
+                                                        }
+                                                    });
+                                                    if ui.button("📈 Export Chart Data (JSON)").clicked() {
+                                                        export::export_to_chart_json(
+                                                            &export_table_data(tabular),
+                                                            &export_headers(tabular),
                                                             &tabular.current_table_name,
                                                         );
                                                         ui.close();
                                                     }
+                                                    ui.menu_button("🌐 Export to HTML", |ui| {
+                                                        if ui.button("With caption (query + time)").clicked() {
+                                                            export::export_to_html(
+                                                                &export_table_data(tabular),
+                                                                &export_headers(tabular),
+                                                                &tabular.current_table_name,
+                                                                Some(&export_caption(tabular)),
+                                                            );
+                                                            ui.close();
+                                                        }
+                                                        if ui.button("Table only").clicked() {
+                                                            export::export_to_html(
+                                                                &export_table_data(tabular),
+                                                                &export_headers(tabular),
+                                                                &tabular.current_table_name,
+                                                                None,
+                                                            );
+                                                            ui.close();
+                                                        }
+                                                    });
                                                     if ui.button("📝 Export to Markdown").clicked()
                                                     {
                                                         export::export_to_markdown(
-                                                            &tabular.all_table_data,
-                                                            &tabular.current_table_headers,
+                                                            &export_table_data(tabular),
+                                                            &export_headers(tabular),
                                                             &tabular.current_table_name,
                                                         );
                                                         ui.close();
@@ -973,13 +1269,20 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                                             })
                                                             .map(|c| c.connection_type.clone());
                                                         export::export_to_sql_inserts(
-                                                            &tabular.all_table_data,
-                                                            &tabular.current_table_headers,
+                                                            &export_table_data(tabular),
+                                                            &export_headers(tabular),
                                                             &tabular.current_table_name,
                                                             db_type.as_ref(),
                                                         );
                                                         ui.close();
                                                     }
+                                                    if ui
+                                                        .button("🗄 Save result to SQLite…")
+                                                        .clicked()
+                                                    {
+                                                        open_sqlite_export_dialog(tabular);
+                                                        ui.close();
+                                                    }
                                                     if tabular.is_table_browse_mode
                                                         && ui.button("📥 Import CSV...").clicked()
                                                     {
@@ -1001,12 +1304,7 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                 }
                             }, // end row allocate_ui_with_layout
                         ); // end row wrapper
-                    } // end for row_index in first_row..last_row
-
-                    // Bottom spacer: allocate space for rows below the viewport
-                    if last_row < total_rows {
-                        ui.add_space((total_rows - last_row) as f32 * ROW_HEIGHT);
-                    }
+                    } // end for row_index in row_range
 
                     // Context menu on the scroll area background
                     let bg_resp = ui.interact(
@@ -1059,34 +1357,74 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                 export_selected_to_markdown(tabular);
                                 ui.close();
                             }
-                            if ui.button("📄 Export to CSV").clicked() {
-                                export::export_to_csv(
-                                    &tabular.all_table_data,
-                                    &tabular.current_table_headers,
-                                    &tabular.current_table_name,
-                                );
-                                ui.close();
-                            }
+                            ui.menu_button("📄 Export to CSV", |ui| {
+                                for delimiter in export::CsvDelimiter::ALL {
+                                    if ui.button(delimiter.label()).clicked() {
+                                        export::export_to_csv(
+                                            &export_table_data(tabular),
+                                            &export_headers(tabular),
+                                            &tabular.current_table_name,
+                                            delimiter,
+                                        );
+                                        ui.close();
+                                    }
+                                }
+                            });
                             if ui.button("📊 Export to XLSX").clicked() {
                                 export::export_to_xlsx(
-                                    &tabular.all_table_data,
-                                    &tabular.current_table_headers,
+                                    &export_table_data(tabular),
+                                    &export_headers(tabular),
                                     &tabular.current_table_name,
                                 );
                                 ui.close();
                             }
-                            if ui.button("🧾 Export to JSON").clicked() {
-                                export::export_to_json(
-                                    &tabular.all_table_data,
-                                    &tabular.current_table_headers,
+                            ui.menu_button("🧾 Export as JSON", |ui| {
+                                for (label, typed_numbers) in
+                                    [("Numbers as JSON numbers", true), ("All values as strings", false)]
+                                {
+                                    if ui.button(label).clicked() {
+                                        export::export_to_json(
+                                            &export_table_data(tabular),
+                                            &export_headers(tabular),
+                                            &tabular.current_table_name,
+                                            typed_numbers,
+                                        );
+                                        ui.close();
+                                    }
+                                }
+                            });
+                            if ui.button("📈 Export Chart Data (JSON)").clicked() {
+                                export::export_to_chart_json(
+                                    &export_table_data(tabular),
+                                    &export_headers(tabular),
                                     &tabular.current_table_name,
                                 );
                                 ui.close();
                             }
+                            ui.menu_button("🌐 Export to HTML", |ui| {
+                                if ui.button("With caption (query + time)").clicked() {
+                                    export::export_to_html(
+                                        &export_table_data(tabular),
+                                        &export_headers(tabular),
+                                        &tabular.current_table_name,
+                                        Some(&export_caption(tabular)),
+                                    );
+                                    ui.close();
+                                }
+                                if ui.button("Table only").clicked() {
+                                    export::export_to_html(
+                                        &export_table_data(tabular),
+                                        &export_headers(tabular),
+                                        &tabular.current_table_name,
+                                        None,
+                                    );
+                                    ui.close();
+                                }
+                            });
                             if ui.button("📝 Export to Markdown").clicked() {
                                 export::export_to_markdown(
-                                    &tabular.all_table_data,
-                                    &tabular.current_table_headers,
+                                    &export_table_data(tabular),
+                                    &export_headers(tabular),
                                     &tabular.current_table_name,
                                 );
                                 ui.close();
@@ -1099,19 +1437,33 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                     })
                                     .map(|c| c.connection_type.clone());
                                 export::export_to_sql_inserts(
-                                    &tabular.all_table_data,
-                                    &tabular.current_table_headers,
+                                    &export_table_data(tabular),
+                                    &export_headers(tabular),
                                     &tabular.current_table_name,
                                     db_type.as_ref(),
                                 );
                                 ui.close();
                             }
+                            if ui.button("🗄 Save result to SQLite…").clicked() {
+                                open_sqlite_export_dialog(tabular);
+                                ui.close();
+                            }
                             if tabular.is_table_browse_mode
                                 && ui.button("📥 Import CSV...").clicked()
                             {
                                 open_csv_import = true;
                                 ui.close();
                             }
+                            if ui.button("☰ Manage Columns…").clicked() {
+                                tabular.show_manage_columns = true;
+                                ui.close();
+                            }
+                            if tabular.is_table_browse_mode
+                                && ui.button("↔ Reset Column Layout").clicked()
+                            {
+                                reset_column_layout(tabular);
+                                ui.close();
+                            }
                             ui.separator();
                             if !tabular.selected_rows.is_empty()
                                 && ui.button("📋 Copy Selected Rows as CSV").clicked()
@@ -1142,15 +1494,16 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                                     .sum::<f32>();
                             let col_w = get_column_width(tabular, sel_col).max(50.0);
                             let rect = egui::Rect::from_min_size(
-                                egui::pos2(col_x, sel_row as f32 * ROW_HEIGHT),
+                                content_origin + egui::vec2(col_x, sel_row as f32 * ROW_HEIGHT),
                                 egui::vec2(col_w, ROW_HEIGHT),
                             );
                             ui.scroll_to_rect(rect, Some(egui::Align::Center));
                         }
                 });
-            // Sync scroll offsets: x for sticky header, y for next-frame virtual scroll
+            // Sync horizontal offset for the sticky header
             tabular.data_scroll_x = scroll_out.state.offset.x;
-            tabular.data_scroll_y = scroll_out.state.offset.y;
+            tabular.data_visible_rows =
+                ((scroll_out.inner_rect.height() / ROW_HEIGHT).floor() as usize).max(1);
             // Execute deferred refresh after UI borrows are released
             if refresh_request_data {
                 refresh_current_table_data(tabular);
@@ -1164,8 +1517,9 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                 let down = ui.input(|i| i.key_pressed(egui::Key::ArrowDown));
                 let up = ui.input(|i| i.key_pressed(egui::Key::ArrowUp));
 
-                // Enter/Escape behavior (commit/cancel)
-                if enter {
+                // Enter commits, Escape cancels; both leave the cell selected with
+                // keyboard focus on the grid. Losing focus elsewhere commits.
+                if enter || cell_edit_blurred {
                     // Apply in-flight text from the overlay before committing
                     if let Some(new_text) = cell_edit_text_update.take() {
                         tabular.spreadsheet_state.cell_edit_text = new_text;
@@ -1174,6 +1528,11 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                 } else if esc {
                     tabular.spreadsheet_finish_cell_edit(false);
                 }
+                if enter || esc {
+                    tabular.selected_cell = Some((erow, ecol));
+                    tabular.selected_row = Some(erow);
+                    tabular.table_recently_clicked = true;
+                }
 
                 // Arrow key navigation while editing: commit current and move edit focus
                 let mut target: Option<(usize, usize)> = None;
@@ -1287,71 +1646,35 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                 tabular.table_recently_clicked = true;
                 
                 tabular.spreadsheet_start_cell_edit(r, c);
-
-                // Fetch ENUM options if applicable
-                tabular.spreadsheet_state.enum_options = None;
-                if let Some(conn_id) = tabular.current_connection_id {
-                     // Check if we have precise metadata for this column (from query result)
-                     // This allows ENUM lookup even for complex queries or when table name isn't in the tab title
-                     let mut type_might_be_enum = false;
-                     let table_name = if let Some(meta) = &tabular.current_column_metadata
-                        && let Some(col_meta) = meta.get(c)
-                     {
-                         if let Some(t_name) = &col_meta.table_name && !t_name.is_empty() {
-                             // Check type name if available
-                             let t_type = col_meta.type_name.to_lowercase();
-                             if t_type.contains("enum") || t_type.contains("set") {
-                                 type_might_be_enum = true;
-                             }
-                             // If basic type is unknown or weird, we might want to check anyway? 
-                             // But usually sqlx gives "VARCHAR" for enums in MySQL sometimes? 
-                             // Wait, if sqlx gives "VARCHAR", then we WON'T detect it here, and we WON'T trigger cache miss.
-                             // That means we might miss ENUM dropdowns.
-                             // BUT user specifically said: "Harusnya ini muncul kalo ada kolom yang di cache belum jelas tipedatanya"
-                             // (This should appear if there is a column in cache with unclear datatype).
-                             // If sqlx says "ENUM", we MUST check cache.
-                             // If sqlx says "VARCHAR", we ignore. That seems safe for now to stop the annoyance.
-                             
-                             t_name.clone()
-                         } else {
-                             // fallback
-                             infer_current_table_name(tabular)
-                         }
-                     } else {
-                         infer_current_table_name(tabular)
-                     };
-                     
-                     // If we inferred table name but skipped metadata check (e.g. no metadata), assume we might need to check?
-                     // No, let's be conservative. If we don't know it's an ENUM, don't popup.
-                     // Exception: If we have NO metadata (e.g. browsing a table directly?), we rely on cache entirely.
-                     // In table browse mode, we usually don't have types in `current_column_metadata`? 
-                     // Actually `execute_mysql_query_job` populates it.
-                     // Let's assume `type_might_be_enum` is the gate.
-                     
-                     if !table_name.is_empty() && type_might_be_enum {
-                         let clean_table = table_name.trim_matches(|c| c == '`' || c == '"' || c == '\'');
-                         let db_name = tabular.query_tabs.get(tabular.active_tab_index)
-                                         .and_then(|t| t.database_name.clone())
-                                         .unwrap_or_default();
-                         if let (Some(cols), Some(col_name)) = (crate::cache_data::get_columns_from_cache(tabular, conn_id, &db_name, clean_table), tabular.current_table_headers.get(c)) {
-                                 if cols.is_empty() {
-                                     tabular.cache_miss_request = Some((conn_id, db_name.clone(), clean_table.to_string()));
-                                 } else {
-                                     if let Some((_, type_str)) = cols.iter().find(|(name, _)| name == col_name) {
-                                         let lower_type = type_str.to_lowercase();
-                                         if lower_type.starts_with("enum") || lower_type.starts_with("set") {
-                                              tabular.spreadsheet_state.enum_options = parse_enum_values(type_str);
-                                         }
-                                     }
-                                 }
-                         } else {
-                             tabular.cache_miss_request = Some((conn_id, db_name.clone(), clean_table.to_string()));
-                         }
-                     }
+                // Arrays and composites get the element editor instead of inline text
+                if open_structured_editor(tabular, r, c) {
+                    tabular.spreadsheet_state.editing_cell = None;
+                    tabular.spreadsheet_state.cell_edit_text.clear();
+                } else if tabular.timezone_for_edit_export
+                    && temporal_columns(tabular).get(c).copied().unwrap_or(false)
+                    && let Some(shown) = convert_for_display(
+                        &tabular.spreadsheet_state.cell_edit_text,
+                        tabular.display_timezone,
+                    )
+                {
+                    tabular.spreadsheet_state.cell_edit_text = shown;
                 }
+
+                // Offer enum / CHECK / foreign key values when the column has them
+                tabular.spreadsheet_state.allowed_values = allowed_values_for_column(tabular, c);
             }
             // (Cell edit text updates already applied above before changing edit target)
 
+            if let Some(chip) = filter_chip_request {
+                add_filter_chip(tabular, chip);
+            }
+            if let Some(col) = column_stats_request {
+                open_column_stats(tabular, col);
+            }
+            if let Some((row, col)) = cell_detail_request {
+                open_cell_detail(tabular, row, col);
+            }
+
             // Open CSV import dialog for the current table
             if open_csv_import
                 && let Some(conn_id) = tabular.current_connection_id
@@ -1388,22 +1711,13 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                             .into_iter()
                             .map(|(name, _)| name)
                             .collect();
-                        tabular.csv_import_state = Some(crate::models::structs::CsvImportState {
-                            connection_id: conn_id,
+                        tabular.csv_import_state = Some(crate::models::structs::CsvImportState::new(
+                            conn_id,
                             database_name,
                             table_name,
                             db_type,
-                            file_path: None,
-                            delimiter: ',',
-                            has_header_row: true,
-                            null_value: String::new(),
-                            preview_headers: vec![],
-                            preview_rows: vec![],
-                            table_columns: table_cols,
-                            column_mappings: vec![],
-                            status: crate::models::structs::CsvImportStatus::Idle,
-                            progress_message: String::new(),
-                        });
+                            table_cols,
+                        ));
                         tabular.show_csv_import_dialog = true;
                     }
 
@@ -1425,16 +1739,19 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
             for (ci, w) in deferred_width_updates {
                 set_column_width(tabular, ci, w);
             }
+            if column_resize_finished {
+                remember_column_layout(tabular);
+            }
 
             // Reset scroll request flag after attempting scroll inside the ScrollArea
             if tabular.scroll_to_selected_cell {
                 tabular.scroll_to_selected_cell = false;
             }
             // If there are no rows, display an explicit message under the header grid
-            // if tabular.current_table_data.is_empty() {
-            //     ui.add_space(4.0);
-            //     ui.label(egui::RichText::new("0 rows").italics().weak());
-            // }
+            if tabular.current_table_data.is_empty() && tabular.total_rows == 0 {
+                ui.add_space(4.0);
+                ui.label(egui::RichText::new("0 rows").italics().weak());
+            }
 
             // (Pagination dipindahkan & kini dirender terpisah secara universal di akhir fungsi)
         } else if tabular.current_table_name.starts_with("Failed") {
@@ -1442,6 +1759,12 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
                 egui::Color32::from_rgb(255, 0, 0),
                 &tabular.current_table_name,
             );
+        } else if tabular.current_table_name.starts_with(STATEMENT_OUTCOME_PREFIX) {
+            ui.add_space(8.0);
+            ui.label(
+                egui::RichText::new(format!("✔ {}", tabular.current_table_name))
+                    .color(egui::Color32::from_rgb(76, 175, 80)),
+            );
         } else {
             // Tampilkan header & pagination walaupun tidak ada data
             // Ambil header dari tab aktif bila current_table_headers kosong
@@ -1472,7 +1795,9 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
             }
         }
         // Pagination universal: jika belum ada header sama sekali tampilkan placeholder info sebelum bar
-        if tabular.current_table_headers.is_empty() {
+        if tabular.current_table_headers.is_empty()
+            && !tabular.current_table_name.starts_with(STATEMENT_OUTCOME_PREFIX)
+        {
             ui.label(
                 egui::RichText::new("No columns loaded yet")
                     .italics()
@@ -1491,3 +1816,17 @@ pub(crate) fn render_table_data(tabular: &mut window_egui::Tabular, ui: &mut egu
 }
 
 // Helper baru: render pagination bar (dipakai baik ada data maupun kosong)
+
+/// Caption for HTML exports: the query behind the result plus the export time.
+pub(crate) fn export_caption(tabular: &window_egui::Tabular) -> String {
+    let source = if tabular.current_base_query.trim().is_empty() {
+        tabular.current_table_name.trim()
+    } else {
+        tabular.current_base_query.trim()
+    };
+    format!(
+        "{}\nExported {}",
+        source,
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+    )
+}
@@ -1,6 +1,9 @@
 use eframe::egui;
 use crate::{models, window_egui};
-use super::{load_structure_info_for_current_table, infer_current_table_name};
+use super::{
+    index_usage_hint, index_usage_label, infer_current_table_name, is_unused_index,
+    load_structure_info_for_current_table,
+};
 
 pub(crate) fn render_structure_view(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
     let avail = ui.available_size();
@@ -110,18 +113,20 @@ pub(crate) fn render_structure_view(tabular: &mut window_egui::Tabular, ui: &mut
                             render_structure_columns_editor(tabular, ui);
                         }
                         models::structs::StructureSubView::Indexes => {
-                            // Headers: No | index_name | algorithm | unique | columns | actions
+                            // Headers: No | index_name | algorithm | unique | columns | included | usage | actions
                             let headers = [
                                 "#",
                                 "index_name",
                                 "algorithm",
                                 "unique",
                                 "columns",
+                                "included",
+                                "usage",
                                 "actions",
                             ];
                             if tabular.structure_idx_col_widths.len() != headers.len() {
                                 tabular.structure_idx_col_widths =
-                                    vec![40.0, 200.0, 120.0, 70.0, 260.0, 120.0];
+                                    vec![40.0, 200.0, 120.0, 70.0, 260.0, 160.0, 160.0, 120.0];
                             }
                             let mut widths = tabular.structure_idx_col_widths.clone();
                             for w in widths.iter_mut() {
@@ -173,6 +178,14 @@ pub(crate) fn render_structure_view(tabular: &mut window_egui::Tabular, ui: &mut
                                                 egui::FontId::proportional(13.0),
                                                 header_text_col,
                                             );
+                                            // Usage figures are live, so they are not kept in the cache
+                                            let resp = if *h == "usage" {
+                                                resp.on_hover_text(
+                                                    "Live statistics from the server; Refresh to load them when the list came from the cache",
+                                                )
+                                            } else {
+                                                resp
+                                            };
                                             let handle = egui::Rect::from_min_max(
                                                 egui::pos2(rect.max.x - 4.0, rect.min.y),
                                                 rect.max,
@@ -229,8 +242,11 @@ pub(crate) fn render_structure_view(tabular: &mut window_egui::Tabular, ui: &mut
                                                 } else {
                                                     ix.columns.join(",")
                                                 },
+                                                ix.included_columns.join(","),
+                                                index_usage_label(ix),
                                                 String::new(), // actions placeholder
                                             ];
+                                            let unused = is_unused_index(ix);
                                             // Defer selected cell border, and draw multi-selection overlay per cell
                                             let mut selected_cell_rect: Option<egui::Rect> = None;
                                             for (i, val) in values.iter().enumerate() {
@@ -303,7 +319,9 @@ pub(crate) fn render_structure_view(tabular: &mut window_egui::Tabular, ui: &mut
                                                 if is_cell_selected {
                                                     selected_cell_rect = Some(rect);
                                                 }
-                                                let txt_col = if dark {
+                                                let txt_col = if i == 6 && unused {
+                                                    ui.visuals().warn_fg_color
+                                                } else if dark {
                                                     egui::Color32::LIGHT_GRAY
                                                 } else {
                                                     egui::Color32::BLACK
@@ -315,6 +333,10 @@ pub(crate) fn render_structure_view(tabular: &mut window_egui::Tabular, ui: &mut
                                                     egui::FontId::proportional(13.0),
                                                     txt_col,
                                                 );
+                                                let resp = match index_usage_hint(ix) {
+                                                    Some(hint) if i == 6 => resp.on_hover_text(hint),
+                                                    _ => resp,
+                                                };
                                                 if resp.clicked() {
                                                     let shift = ui.input(|i| i.modifiers.shift);
                                                     tabular.structure_selected_row = Some(idx);
@@ -383,6 +405,8 @@ pub(crate) fn render_structure_view(tabular: &mut window_egui::Tabular, ui: &mut
                                                                         } else {
                                                                             row.columns.join(",")
                                                                         },
+                                                                        row.included_columns.join(","),
+                                                                        index_usage_label(row),
                                                                         String::new(),
                                                                     ];
                                                                     let mut fields: Vec<String> = Vec::new();
@@ -674,8 +698,21 @@ pub(crate) fn render_structure_view(tabular: &mut window_egui::Tabular, ui: &mut
                                                     ui.text_edit_singleline(&mut tabular.new_index_columns);
                                                 },
                                             );
+                                            // included / usage: not set when creating
+                                            for w in [widths[5], widths[6]] {
+                                                let (rect, _) = ui.allocate_exact_size(
+                                                    egui::vec2(w, row_h),
+                                                    egui::Sense::hover(),
+                                                );
+                                                ui.painter().rect_stroke(
+                                                    rect,
+                                                    0.0,
+                                                    stroke,
+                                                    egui::StrokeKind::Outside,
+                                                );
+                                            }
                                             // actions
-                                            let w_act = widths[5];
+                                            let w_act = widths[7];
                                             ui.allocate_ui_with_layout(
                                                 egui::vec2(w_act, row_h),
                                                 egui::Layout::left_to_right(egui::Align::Center),
@@ -1546,6 +1583,7 @@ fn commit_new_index(tabular: &mut window_egui::Tabular) {
             },
             unique: tabular.new_index_unique,
             columns: cols.clone(),
+            ..Default::default()
         });
     // Reset state before execution to avoid double firing on UI re-render
     tabular.adding_index = false;
@@ -362,6 +362,99 @@ pub(crate) fn export_selected_to_markdown(tabular: &window_egui::Tabular) {
     }
 }
 
+/// Keyboard movement of the active cell in the data grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum GridMove {
+    Left,
+    Right,
+    Up,
+    Down,
+    /// Home: first column of the row.
+    RowStart,
+    /// End: last column of the row.
+    RowEnd,
+    PageUp,
+    PageDown,
+    /// Ctrl+Home: first row, same column.
+    FirstRow,
+    /// Ctrl+End: last row, same column.
+    LastRow,
+}
+
+/// Cell reached from `from` by `mv`, clamped to the grid; `None` when the
+/// move would not change anything (already at the edge, empty grid).
+pub(crate) fn grid_move_target(
+    from: (usize, usize),
+    mv: GridMove,
+    rows: &[Vec<String>],
+    page_rows: usize,
+) -> Option<(usize, usize)> {
+    let (row, col) = from;
+    let last_row = rows.len().checked_sub(1)?;
+    let page = page_rows.max(1);
+    let target_row = match mv {
+        GridMove::Up => row.saturating_sub(1),
+        GridMove::Down => (row + 1).min(last_row),
+        GridMove::PageUp => row.saturating_sub(page),
+        GridMove::PageDown => (row + page).min(last_row),
+        GridMove::FirstRow => 0,
+        GridMove::LastRow => last_row,
+        _ => row.min(last_row),
+    };
+    let last_col = rows[target_row].len().checked_sub(1)?;
+    let target_col = match mv {
+        GridMove::Left => col.saturating_sub(1),
+        GridMove::Right => (col + 1).min(last_col),
+        GridMove::RowStart => 0,
+        GridMove::RowEnd => last_col,
+        _ => col.min(last_col),
+    };
+    let target = (target_row, target_col);
+    (target != from).then_some(target)
+}
+
+/// Move the active cell and keep it scrolled into view. With `extend`
+/// (Shift held) the block anchored at `table_sel_anchor` grows, and an
+/// existing row/column selection is widened to cover the moved-over range.
+/// Returns true when the active cell moved.
+pub(crate) fn move_grid_selection(
+    tabular: &mut window_egui::Tabular,
+    mv: GridMove,
+    extend: bool,
+) -> bool {
+    let Some(from) = tabular.selected_cell else {
+        return false;
+    };
+    let Some((row, col)) =
+        grid_move_target(from, mv, &tabular.current_table_data, tabular.data_visible_rows)
+    else {
+        return false;
+    };
+
+    if extend {
+        let anchor = *tabular.table_sel_anchor.get_or_insert(from);
+        if !tabular.selected_rows.is_empty() && row != from.0 {
+            let (a, b) = (anchor.0.min(row), anchor.0.max(row));
+            tabular.selected_rows.clear();
+            tabular.selected_rows.extend(a..=b);
+            tabular.last_clicked_row = Some(anchor.0);
+        }
+        if !tabular.selected_columns.is_empty() && col != from.1 {
+            let (a, b) = (anchor.1.min(col), anchor.1.max(col));
+            tabular.selected_columns.clear();
+            tabular.selected_columns.extend(a..=b);
+            tabular.last_clicked_column = Some(anchor.1);
+        }
+    } else {
+        tabular.table_sel_anchor = None;
+    }
+
+    tabular.selected_cell = Some((row, col));
+    tabular.selected_row = Some(row);
+    tabular.scroll_to_selected_cell = true;
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,6 +498,35 @@ mod tests {
         assert!(md.contains("| id | val | name |"));
         assert!(md.contains("| 1 | 10.5 | Alice |"));
     }
-}
 
+    #[test]
+    fn test_grid_move_targets() {
+        let tab = create_test_tabular();
+        let rows = &tab.current_table_data;
+        assert_eq!(grid_move_target((0, 0), GridMove::Down, rows, 10), Some((1, 0)));
+        assert_eq!(grid_move_target((0, 0), GridMove::Up, rows, 10), None);
+        assert_eq!(grid_move_target((1, 1), GridMove::RowEnd, rows, 10), Some((1, 2)));
+        assert_eq!(grid_move_target((1, 1), GridMove::RowStart, rows, 10), Some((1, 0)));
+        assert_eq!(grid_move_target((0, 2), GridMove::PageDown, rows, 10), Some((2, 2)));
+        assert_eq!(grid_move_target((2, 1), GridMove::PageUp, rows, 2), Some((0, 1)));
+        assert_eq!(grid_move_target((1, 1), GridMove::LastRow, rows, 10), Some((2, 1)));
+        assert_eq!(grid_move_target((1, 1), GridMove::FirstRow, rows, 10), Some((0, 1)));
+        assert_eq!(grid_move_target((0, 0), GridMove::Down, &[], 10), None);
+    }
 
+    #[test]
+    fn test_shift_move_extends_row_selection() {
+        let mut tab = create_test_tabular();
+        tab.selected_rows.insert(0);
+        tab.selected_cell = Some((0, 0));
+        assert!(move_grid_selection(&mut tab, GridMove::Down, true));
+        assert!(move_grid_selection(&mut tab, GridMove::Down, true));
+        assert_eq!(tab.selected_cell, Some((2, 0)));
+        assert_eq!(tab.table_sel_anchor, Some((0, 0)));
+        assert_eq!(tab.selected_rows.len(), 3);
+
+        assert!(move_grid_selection(&mut tab, GridMove::Up, false));
+        assert_eq!(tab.table_sel_anchor, None);
+        assert_eq!(tab.selected_cell, Some((1, 0)));
+    }
+}
@@ -373,6 +373,9 @@ pub fn fetch_partition_details_for_table(
 pub(crate) fn refresh_current_table_data(tabular: &mut window_egui::Tabular) {
     // Stay in browse mode so spreadsheet shortcuts remain enabled after refreshes
     tabular.is_table_browse_mode = true;
+    // Constraints may have changed too; re-read allowed values on the next edit
+    tabular.column_allowed_values.clear();
+    tabular.column_distinct_values.clear();
     if tabular.use_server_pagination && !tabular.current_base_query.is_empty() {
         tabular.current_page = 0;
         debug!("🔄 Manual refresh: server pagination first page reloaded");
@@ -415,9 +418,10 @@ pub(crate) fn refresh_current_table_data(tabular: &mut window_egui::Tabular) {
                         format!("SELECT * FROM \"{}\".\"{}\" LIMIT 100", db_name, table)
                     }
                 }
-                models::enums::DatabaseType::SQLite => {
-                    format!("SELECT * FROM `{}` LIMIT 100", table)
-                }
+                models::enums::DatabaseType::SQLite => format!(
+                    "SELECT * FROM {} LIMIT 100",
+                    crate::driver_sqlite::qualified_table_name(Some(&db_name), &table)
+                ),
                 models::enums::DatabaseType::MsSQL => {
                     driver_mssql::build_mssql_select_query(db_name.clone(), table.clone())
                 }
@@ -476,7 +480,7 @@ fn fetch_index_details_for_table(
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
                     if let Some(models::enums::DatabasePool::MySQL(mysql_pool)) = crate::connection::get_or_create_connection_pool(tabular, connection_id).await {
-                        let q = r#"SELECT INDEX_NAME, GROUP_CONCAT(COLUMN_NAME ORDER BY SEQ_IN_INDEX) AS COLS, MIN(NON_UNIQUE) AS NON_UNIQUE, GROUP_CONCAT(DISTINCT INDEX_TYPE) AS TYPES FROM INFORMATION_SCHEMA.STATISTICS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? GROUP BY INDEX_NAME ORDER BY INDEX_NAME"#;
+                        let q = r#"SELECT INDEX_NAME, GROUP_CONCAT(COLUMN_NAME ORDER BY SEQ_IN_INDEX) AS COLS, MIN(NON_UNIQUE) AS NON_UNIQUE, GROUP_CONCAT(DISTINCT INDEX_TYPE) AS TYPES, CAST(MAX(CARDINALITY) AS SIGNED) AS CARD FROM INFORMATION_SCHEMA.STATISTICS WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? GROUP BY INDEX_NAME ORDER BY INDEX_NAME"#;
                         match sqlx::query(q).bind(database_name).bind(table_name).fetch_all(mysql_pool.as_ref()).await {
                             Ok(rows) => { use sqlx::Row; rows.into_iter().map(|r| {
                                 let name: String = r.get("INDEX_NAME");
@@ -486,7 +490,9 @@ fn fetch_index_details_for_table(
                                 let columns = cols_str.unwrap_or_default().split(',').filter(|s| !s.is_empty()).map(|s| s.to_string()).collect();
                                 let unique = matches!(non_unique, Some(0));
                                 let method = types.and_then(|t| t.split(',').next().map(|m| m.trim().to_string())).filter(|s| !s.is_empty());
-                                models::structs::IndexStructInfo { name, method, unique, columns }
+                                // Cardinality of the full key: the last column's, the largest
+                                let cardinality: Option<i64> = r.try_get("CARD").ok().flatten();
+                                models::structs::IndexStructInfo { name, method, unique, columns, cardinality, ..Default::default() }
                             }).collect() }
                             Err(_) => Vec::new(),
                         }
@@ -497,15 +503,15 @@ fn fetch_index_details_for_table(
             let rt = tokio::runtime::Runtime::new().unwrap();
             rt.block_on(async {
                     if let Some(models::enums::DatabasePool::PostgreSQL(pg_pool)) = crate::connection::get_or_create_connection_pool(tabular, connection_id).await {
-                        let q = r#"SELECT idx.relname AS index_name, pg_get_indexdef(i.indexrelid) AS index_def, i.indisunique AS is_unique FROM pg_class t JOIN pg_index i ON t.oid = i.indrelid JOIN pg_class idx ON idx.oid = i.indexrelid JOIN pg_namespace n ON n.oid = t.relnamespace WHERE t.relname = $1 AND n.nspname='public' ORDER BY idx.relname"#;
+                        let q = r#"SELECT idx.relname AS index_name, pg_get_indexdef(i.indexrelid) AS index_def, i.indisunique AS is_unique, s.idx_scan AS scans FROM pg_class t JOIN pg_index i ON t.oid = i.indrelid JOIN pg_class idx ON idx.oid = i.indexrelid JOIN pg_namespace n ON n.oid = t.relnamespace LEFT JOIN pg_stat_user_indexes s ON s.indexrelid = i.indexrelid WHERE t.relname = $1 AND n.nspname='public' ORDER BY idx.relname"#;
                         match sqlx::query(q).bind(table_name).fetch_all(pg_pool.as_ref()).await {
                             Ok(rows) => { use sqlx::Row; rows.into_iter().map(|r| {
                                 let name: String = r.get("index_name");
                                 let def: String = r.get("index_def");
                                 let unique: bool = r.get("is_unique");
-                                let method = def.split(" USING ").nth(1).and_then(|rest| rest.split_whitespace().next()).and_then(|m| if m.starts_with('('){None}else{Some(m.trim_matches('(').trim_matches(')').to_string())});
-                                let columns: Vec<String> = if let Some(start) = def.rfind('(') { if let Some(end_rel) = def[start+1..].find(')') { def[start+1..start+1+end_rel].split(',').map(|s| s.trim().trim_matches('"').to_string()).filter(|s| !s.is_empty()).collect() } else { Vec::new() } } else { Vec::new() };
-                                models::structs::IndexStructInfo { name, method, unique, columns }
+                                let (method, columns, included_columns) = super::parse_pg_index_def(&def);
+                                let scans: Option<i64> = r.try_get("scans").ok().flatten();
+                                models::structs::IndexStructInfo { name, method, unique, columns, included_columns, scans, ..Default::default() }
                             }).collect() }
                             Err(_) => Vec::new(),
                         }
@@ -517,13 +523,14 @@ fn fetch_index_details_for_table(
             let port: u16 = connection.port.parse().unwrap_or(1433);
             let user = connection.username.clone();
             let pass = connection.password.clone();
+            let tls = crate::driver_mssql::MssqlTls::from_connection(connection);
             let db = database_name.to_string();
             let tbl = table_name.to_string();
             let rt_res = tokio::runtime::Runtime::new().unwrap().block_on(async move {
-                    let mut client = crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db)).await?;
+                    let mut client = crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db), tls).await?;
                     let parse = |name: &str| -> (Option<String>, String) { if let Some((s,t)) = name.split_once('.') { (Some(s.trim_matches(['[',']']).to_string()), t.trim_matches(['[',']']).to_string()) } else { (None, name.trim_matches(['[',']']).to_string()) } };
                     let (_schema_opt, table_only) = parse(&tbl);
-                    let q = format!("SELECT i.name AS index_name, i.is_unique, i.type_desc, STUFF((SELECT ','+c.name FROM sys.index_columns ic2 JOIN sys.columns c ON c.object_id=ic2.object_id AND c.column_id=ic2.column_id WHERE ic2.object_id=i.object_id AND ic2.index_id=i.index_id ORDER BY ic2.key_ordinal FOR XML PATH(''), TYPE).value('.','NVARCHAR(MAX)'),1,1,'') AS columns FROM sys.indexes i INNER JOIN sys.objects o ON o.object_id=i.object_id WHERE o.name='{}' AND i.name IS NOT NULL ORDER BY i.name", table_only.replace("'","''"));
+                    let q = format!("SELECT i.name AS index_name, i.is_unique, i.type_desc, STUFF((SELECT ','+c.name FROM sys.index_columns ic2 JOIN sys.columns c ON c.object_id=ic2.object_id AND c.column_id=ic2.column_id WHERE ic2.object_id=i.object_id AND ic2.index_id=i.index_id AND ic2.is_included_column=0 ORDER BY ic2.key_ordinal FOR XML PATH(''), TYPE).value('.','NVARCHAR(MAX)'),1,1,'') AS columns, STUFF((SELECT ','+c.name FROM sys.index_columns ic3 JOIN sys.columns c ON c.object_id=ic3.object_id AND c.column_id=ic3.column_id WHERE ic3.object_id=i.object_id AND ic3.index_id=i.index_id AND ic3.is_included_column=1 ORDER BY ic3.index_column_id FOR XML PATH(''), TYPE).value('.','NVARCHAR(MAX)'),1,1,'') AS included FROM sys.indexes i INNER JOIN sys.objects o ON o.object_id=i.object_id WHERE o.name='{}' AND i.name IS NOT NULL ORDER BY i.name", table_only.replace("'","''"));
                     let stream = client.query(&q, &[]).await.map_err(|e| e.to_string())?;
                     let mut list = Vec::new();
                     for r in stream.collect_all().await.map_err(|e| e.to_string())? {
@@ -531,8 +538,10 @@ fn fetch_index_details_for_table(
                         let is_unique: Option<bool> = r.try_get(1).ok().flatten();
                         let type_desc = r.get_string(2);
                         let cols = r.get_string(3);
+                        let included = r.get_string(4);
+                        let split = |list: Option<String>| -> Vec<String> { list.unwrap_or_default().split(',').filter(|s| !s.is_empty()).map(|s| s.to_string()).collect() };
                         if let Some(nm) = name {
-                            list.push(models::structs::IndexStructInfo { name: nm, method: type_desc, unique: is_unique.unwrap_or(false), columns: cols.unwrap_or_default().split(',').filter(|s| !s.is_empty()).map(|s| s.to_string()).collect() });
+                            list.push(models::structs::IndexStructInfo { name: nm, method: type_desc, unique: is_unique.unwrap_or(false), columns: split(cols), included_columns: split(included), ..Default::default() });
                         }
                     }
                     Ok::<_, String>(list)
@@ -546,8 +555,11 @@ fn fetch_index_details_for_table(
                     crate::connection::get_or_create_connection_pool(tabular, connection_id).await
                 {
                     use sqlx::Row;
-                    let list_query =
-                        format!("PRAGMA index_list('{}')", table_name.replace("'", "''"));
+                    let list_query = crate::driver_sqlite::schema_pragma(
+                        Some(database_name),
+                        "index_list",
+                        table_name,
+                    );
                     match sqlx::query(sqlx::AssertSqlSafe(list_query.as_str()))
                         .fetch_all(sqlite_pool.as_ref())
                         .await
@@ -558,8 +570,11 @@ fn fetch_index_details_for_table(
                                 let name_opt: Option<String> = r.try_get("name").ok().flatten();
                                 let unique_flag: Option<i64> = r.try_get("unique").ok().flatten();
                                 if let Some(nm) = name_opt {
-                                    let info_q =
-                                        format!("PRAGMA index_info('{}')", nm.replace("'", "''"));
+                                    let info_q = crate::driver_sqlite::schema_pragma(
+                                        Some(database_name),
+                                        "index_info",
+                                        &nm,
+                                    );
                                     let mut cols_vec = Vec::new();
                                     if let Ok(crows) =
                                         sqlx::query(sqlx::AssertSqlSafe(info_q.as_str())).fetch_all(sqlite_pool.as_ref()).await
@@ -577,6 +592,7 @@ fn fetch_index_details_for_table(
                                         method: None,
                                         unique: matches!(unique_flag, Some(0)),
                                         columns: cols_vec,
+                                        ..Default::default()
                                     });
                                 }
                             }
@@ -608,6 +624,7 @@ fn fetch_index_details_for_table(
                                 method: None,
                                 unique: false,
                                 columns: Vec::new(),
+                                ..Default::default()
                             })
                             .collect(),
                         Err(_) => Vec::new(),
@@ -0,0 +1,198 @@
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+
+use crate::config::DisplayTimezone;
+use crate::window_egui;
+
+// Output format used for converted values; `%.f` only prints a fraction when present.
+const DISPLAY_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+/// True for column types holding a date *and* a time of day (the only kind
+/// that can be shifted between zones; plain DATE/TIME columns are left alone).
+pub(crate) fn is_temporal_type(type_name: &str) -> bool {
+    let t = type_name.to_uppercase();
+    t.contains("TIMESTAMP") || t.contains("DATETIME")
+}
+
+/// How a stored value spelled its zone, so edits can be written back the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StoredStyle {
+    /// No offset (MySQL DATETIME, Postgres TIMESTAMP, SQLite text); treated as UTC.
+    Naive,
+    /// `2024-01-02T03:04:05+00:00`
+    Rfc3339,
+    /// `2024-01-02 03:04:05 +00:00`
+    SpacedOffset,
+}
+
+fn parse_stored(value: &str) -> Option<(DateTime<Utc>, StoredStyle)> {
+    let v = value.trim();
+    if v.is_empty() || v.eq_ignore_ascii_case("NULL") {
+        return None;
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(v) {
+        return Some((dt.with_timezone(&Utc), StoredStyle::Rfc3339));
+    }
+    for fmt in ["%Y-%m-%d %H:%M:%S%.f %:z", "%Y-%m-%d %H:%M:%S%.f%#z"] {
+        if let Ok(dt) = DateTime::parse_from_str(v, fmt) {
+            return Some((dt.with_timezone(&Utc), StoredStyle::SpacedOffset));
+        }
+    }
+    parse_naive(v).map(|naive| (naive.and_utc(), StoredStyle::Naive))
+}
+
+/// A stored date-time value as an instant; naive values count as UTC.
+pub(crate) fn parse_stored_instant(value: &str) -> Option<DateTime<Utc>> {
+    parse_stored(value).map(|(dt, _)| dt)
+}
+
+fn parse_naive(value: &str) -> Option<NaiveDateTime> {
+    ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(value.trim(), fmt).ok())
+}
+
+/// Wall-clock time of `utc` in `tz`; `None` for [`DisplayTimezone::AsStored`].
+fn to_wall_clock(utc: DateTime<Utc>, tz: DisplayTimezone) -> Option<NaiveDateTime> {
+    match tz {
+        DisplayTimezone::AsStored => None,
+        DisplayTimezone::Utc => Some(utc.naive_utc()),
+        DisplayTimezone::Local => Some(utc.with_timezone(&chrono::Local).naive_local()),
+        DisplayTimezone::Zone(z) => Some(utc.with_timezone(&z).naive_local()),
+    }
+}
+
+/// Inverse of [`to_wall_clock`]; ambiguous local times resolve to the earlier instant.
+fn from_wall_clock(naive: NaiveDateTime, tz: DisplayTimezone) -> Option<DateTime<Utc>> {
+    match tz {
+        DisplayTimezone::AsStored => None,
+        DisplayTimezone::Utc => Some(naive.and_utc()),
+        DisplayTimezone::Local => chrono::Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|d| d.with_timezone(&Utc)),
+        DisplayTimezone::Zone(z) => z
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|d| d.with_timezone(&Utc)),
+    }
+}
+
+/// Convert a stored temporal value into `tz` for display. Returns `None`
+/// when no conversion applies (as-stored mode, NULL, unparseable text).
+pub(crate) fn convert_for_display(value: &str, tz: DisplayTimezone) -> Option<String> {
+    let (utc, _) = parse_stored(value)?;
+    to_wall_clock(utc, tz).map(|wall| wall.format(DISPLAY_FORMAT).to_string())
+}
+
+/// Convert a value typed in `tz` back into the representation of `original`
+/// (the stored value it replaces). Returns `None` when `edited` is not a
+/// recognizable date-time, in which case the caller keeps the text verbatim.
+pub(crate) fn convert_from_display(
+    edited: &str,
+    original: &str,
+    tz: DisplayTimezone,
+) -> Option<String> {
+    let utc = from_wall_clock(parse_naive(edited)?, tz)?;
+    let style = parse_stored(original)
+        .map(|(_, style)| style)
+        .unwrap_or(StoredStyle::Naive);
+    Some(match style {
+        StoredStyle::Naive => utc.naive_utc().format(DISPLAY_FORMAT).to_string(),
+        StoredStyle::Rfc3339 => utc.to_rfc3339(),
+        StoredStyle::SpacedOffset => utc.format("%Y-%m-%d %H:%M:%S%.f %:z").to_string(),
+    })
+}
+
+/// Per-column flags marking which result columns get timezone conversion.
+/// Empty when conversion is off or column types are unknown.
+pub(crate) fn temporal_columns(tabular: &window_egui::Tabular) -> Vec<bool> {
+    if tabular.display_timezone == DisplayTimezone::AsStored {
+        return Vec::new();
+    }
+    tabular
+        .current_column_metadata
+        .as_ref()
+        .map(|meta| meta.iter().map(|m| is_temporal_type(&m.type_name)).collect())
+        .unwrap_or_default()
+}
+
+/// `all_table_data` as it should be exported: converted to the display
+/// timezone when the edit/export toggle is on and through the column
+/// formatters when "Export formatted values" is on, raw otherwise. Hidden
+/// columns are left out when exports skip them (see [`super::export_headers`]).
+pub(crate) fn export_table_data(tabular: &window_egui::Tabular) -> Vec<Vec<String>> {
+    let mut temporal = temporal_columns(tabular);
+    if !tabular.timezone_for_edit_export {
+        temporal.clear();
+    }
+    let formatters = if tabular.export_formatted_values {
+        super::formatted_columns(tabular)
+    } else {
+        Vec::new()
+    };
+    if !temporal.contains(&true) && formatters.iter().all(Option::is_none) {
+        return super::drop_hidden_for_export(tabular, tabular.all_table_data.clone());
+    }
+    let rows = tabular
+        .all_table_data
+        .iter()
+        .map(|row| {
+            row.iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    if let Some(formatted) = super::format_cell(&formatters, i, cell) {
+                        formatted
+                    } else if temporal.get(i).copied().unwrap_or(false) {
+                        convert_for_display(cell, tabular.display_timezone)
+                            .unwrap_or_else(|| cell.clone())
+                    } else {
+                        cell.clone()
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    super::drop_hidden_for_export(tabular, rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_naive_utc_into_named_zone() {
+        let tz = DisplayTimezone::Zone(chrono_tz::Asia::Jakarta);
+        assert_eq!(
+            convert_for_display("2024-03-01 23:30:00", tz).as_deref(),
+            Some("2024-03-02 06:30:00")
+        );
+        assert_eq!(
+            convert_for_display("2024-03-01T23:30:00+02:00", DisplayTimezone::Utc).as_deref(),
+            Some("2024-03-01 21:30:00")
+        );
+        assert_eq!(convert_for_display("NULL", tz), None);
+        assert_eq!(convert_for_display("2024-03-01 23:30:00", DisplayTimezone::AsStored), None);
+    }
+
+    #[test]
+    fn edits_round_trip_to_the_stored_style() {
+        let tz = DisplayTimezone::Zone(chrono_tz::Asia::Jakarta);
+        assert_eq!(
+            convert_from_display("2024-03-02 06:30:00", "2024-03-01 23:30:00", tz).as_deref(),
+            Some("2024-03-01 23:30:00")
+        );
+        assert_eq!(
+            convert_from_display("2024-03-02 06:30:00", "2024-01-01T00:00:00+00:00", tz).as_deref(),
+            Some("2024-03-01T23:30:00+00:00")
+        );
+        assert_eq!(convert_from_display("not a date", "2024-03-01 23:30:00", tz), None);
+    }
+
+    #[test]
+    fn only_date_time_types_are_temporal() {
+        assert!(is_temporal_type("TIMESTAMPTZ"));
+        assert!(is_temporal_type("datetime2"));
+        assert!(!is_temporal_type("DATE"));
+        assert!(!is_temporal_type("VARCHAR"));
+    }
+}
@@ -0,0 +1,166 @@
+//! Transposed view: result columns become rows and each result row a column,
+//! for comparing a few wide rows side by side. Only the rendering changes;
+//! copy and export keep working on the result as returned. The choice is
+//! remembered per tab (`QueryTab::transposed`).
+
+use eframe::egui;
+
+use super::{cell_preview, field_text, formatted_columns, hidden_column_mask, temporal_columns};
+use crate::window_egui;
+
+/// Result rows shown as columns at most; the rest of the page is left out.
+const MAX_TRANSPOSED_ROWS: usize = 50;
+
+pub(crate) fn transpose_active(tabular: &window_egui::Tabular) -> bool {
+    tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .is_some_and(|t| t.transposed)
+}
+
+/// Switch the active tab between the grid and the transposed view. The record
+/// view and the transposed view replace each other.
+pub(crate) fn toggle_transpose(tabular: &mut window_egui::Tabular) {
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.transposed = !tab.transposed;
+        if tab.transposed {
+            tab.record_view = false;
+        }
+    }
+}
+
+/// Header row and rows of the transposed table: one row per visible column,
+/// led by its name, with one cell per source row (at most `max_rows`).
+fn transposed(
+    headers: &[String],
+    rows: &[Vec<String>],
+    hidden: &[bool],
+    max_rows: usize,
+) -> (Vec<String>, Vec<(usize, Vec<String>)>) {
+    let shown = &rows[..rows.len().min(max_rows)];
+    let mut columns = vec!["Column".to_string()];
+    columns.extend((1..=shown.len()).map(|i| format!("Row {}", i)));
+    let body = headers
+        .iter()
+        .enumerate()
+        .filter(|(col, _)| !hidden.get(*col).copied().unwrap_or(false))
+        .map(|(col, name)| {
+            let mut line = vec![name.clone()];
+            line.extend(
+                shown
+                    .iter()
+                    .map(|row| row.get(col).cloned().unwrap_or_default()),
+            );
+            (col, line)
+        })
+        .collect();
+    (columns, body)
+}
+
+pub(crate) fn render_transposed(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
+    let total = tabular.current_table_data.len();
+    if total == 0 {
+        ui.add_space(4.0);
+        ui.label(
+            egui::RichText::new("0 rows — nothing to transpose")
+                .italics()
+                .weak(),
+        );
+        return;
+    }
+    let headers = tabular.current_table_headers.clone();
+    let hidden = hidden_column_mask(tabular, &headers);
+    let (columns, body) = transposed(
+        &headers,
+        &tabular.current_table_data,
+        &hidden,
+        MAX_TRANSPOSED_ROWS,
+    );
+    if total > MAX_TRANSPOSED_ROWS {
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            format!(
+                "⚠ Showing the first {} of {} rows transposed; filter the result or use the grid for the rest.",
+                MAX_TRANSPOSED_ROWS, total
+            ),
+        );
+    }
+    let temporal = temporal_columns(tabular);
+    let formatters = formatted_columns(tabular);
+    let tz = tabular.display_timezone;
+    let preview_chars = tabular.cell_preview_chars;
+    let mut copy_request: Option<String> = None;
+
+    // Leave room for the pagination bar rendered below
+    let height = (ui.available_height() - 48.0).max(50.0);
+    egui::ScrollArea::both()
+        .id_salt("transposed_scroll")
+        .max_height(height)
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("transposed_grid")
+                .num_columns(columns.len())
+                .striped(true)
+                .spacing([16.0, 6.0])
+                .show(ui, |ui| {
+                    for title in &columns {
+                        ui.label(egui::RichText::new(title).strong().weak());
+                    }
+                    ui.end_row();
+                    for (col, line) in &body {
+                        ui.label(egui::RichText::new(&line[0]).strong());
+                        for cell in &line[1..] {
+                            let resp = if cell == "NULL" {
+                                ui.label(egui::RichText::new("NULL").italics().weak())
+                            } else {
+                                let shown = cell_preview(cell, preview_chars);
+                                let text =
+                                    field_text(&shown, *col, &temporal, &[], &formatters, tz);
+                                ui.add(
+                                    egui::Label::new(egui::RichText::new(text).monospace())
+                                        .truncate(),
+                                )
+                            };
+                            resp.context_menu(|ui| {
+                                if ui.button("📋 Copy Value").clicked() {
+                                    copy_request = Some(cell.clone());
+                                    ui.close();
+                                }
+                            });
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(text) = copy_request {
+        ui.ctx().copy_text(text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_become_rows_and_rows_are_capped() {
+        let headers = vec!["id".to_string(), "secret".to_string(), "name".to_string()];
+        let rows = vec![
+            vec!["1".to_string(), "x".to_string(), "ann".to_string()],
+            vec!["2".to_string(), "y".to_string(), "bob".to_string()],
+            vec!["3".to_string(), "z".to_string(), "cy".to_string()],
+        ];
+        let (columns, body) = transposed(&headers, &rows, &[false, true, false], 2);
+        assert_eq!(columns, vec!["Column", "Row 1", "Row 2"]);
+        assert_eq!(
+            body,
+            vec![
+                (0, vec!["id".to_string(), "1".to_string(), "2".to_string()]),
+                (
+                    2,
+                    vec!["name".to_string(), "ann".to_string(), "bob".to_string()]
+                ),
+            ]
+        );
+    }
+}
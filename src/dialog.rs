@@ -1,7 +1,7 @@
 use eframe::egui;
 use log::error;
 
-use crate::{editor, models, window_egui};
+use crate::{editor, models, sidebar_query, window_egui};
 
 /// Helper function to paint cursor for TextEdit fields (fix for egui singleline cursor bug)
 fn paint_text_edit_cursor(
@@ -217,9 +217,38 @@ pub(crate) fn render_save_dialog(tabular: &mut window_egui::Tabular, ctx: &egui:
 
                     ui.add_space(10.0);
 
+                    // Optional metadata, written as `-- @name:` / `-- @desc:` / `-- @tags:`
+                    egui::Grid::new("save_query_meta_grid")
+                        .num_columns(2)
+                        .spacing([8.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.label("Name:");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut tabular.save_query_meta.name)
+                                    .hint_text("Friendly name shown in the queries tree"),
+                            );
+                            ui.end_row();
+                            ui.label("Description:");
+                            ui.add(
+                                egui::TextEdit::multiline(&mut tabular.save_query_meta.description)
+                                    .desired_rows(2),
+                            );
+                            ui.end_row();
+                            ui.label("Tags:");
+                            ui.add(
+                                egui::TextEdit::singleline(&mut tabular.save_query_tags)
+                                    .hint_text("reporting, billing"),
+                            );
+                            ui.end_row();
+                        });
+
+                    ui.add_space(10.0);
+
                     // Action buttons
                     ui.horizontal(|ui| {
                         if ui.button("Save").clicked() && !tabular.save_filename.is_empty() {
+                            tabular.save_query_meta.tags =
+                                sidebar_query::split_tags(&tabular.save_query_tags);
                             if let Err(err) = editor::save_current_tab_with_name(
                                 tabular,
                                 tabular.save_filename.clone(),
@@ -1257,164 +1286,226 @@ pub(crate) fn render_create_table_dialog(tabular: &mut window_egui::Tabular, ctx
 
 // ── CSV Import Wizard ─────────────────────────────────────────────────────────
 
-fn parse_csv_preview(
-    path: &std::path::Path,
-    delimiter: char,
-    has_header_row: bool,
-) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
-    let mut rdr = csv::ReaderBuilder::new()
-        .delimiter(delimiter as u8)
-        .has_headers(has_header_row)
-        .flexible(true)
-        .from_path(path)
-        .map_err(|e| e.to_string())?;
-
-    let headers: Vec<String> = if has_header_row {
-        rdr.headers().map_err(|e| e.to_string())?.iter().map(|s| s.to_string()).collect()
+/// Map CSV columns onto the target: by name (or position without a header
+/// row) for an existing table, one new column per CSV column otherwise.
+fn build_auto_mappings(state: &models::structs::CsvImportState) -> Vec<models::structs::CsvColumnMapping> {
+    let ncols = if state.has_header_row {
+        state.preview_headers.len()
     } else {
-        vec![]
+        state.preview_rows.iter().map(Vec::len).max().unwrap_or(0)
     };
-
-    let mut preview: Vec<Vec<String>> = Vec::new();
-    for result in rdr.records().take(5) {
-        let record = result.map_err(|e| e.to_string())?;
-        preview.push(record.iter().map(|s| s.to_string()).collect());
-    }
-    Ok((headers, preview))
-}
-
-fn parse_csv_all(
-    path: &std::path::Path,
-    delimiter: char,
-    has_header_row: bool,
-) -> Result<Vec<Vec<String>>, String> {
-    let mut rdr = csv::ReaderBuilder::new()
-        .delimiter(delimiter as u8)
-        .has_headers(has_header_row)
-        .flexible(true)
-        .from_path(path)
-        .map_err(|e| e.to_string())?;
-
-    let mut rows: Vec<Vec<String>> = Vec::new();
-    for result in rdr.records() {
-        let record = result.map_err(|e| e.to_string())?;
-        rows.push(record.iter().map(|s| s.to_string()).collect());
-    }
-    Ok(rows)
+    (0..ncols)
+        .map(|i| {
+            let csv_header = state
+                .preview_headers
+                .get(i)
+                .filter(|h| !h.trim().is_empty())
+                .map(|h| h.trim().to_string())
+                .unwrap_or_else(|| format!("col_{}", i + 1));
+            let target_column = if state.create_table {
+                csv_header.clone()
+            } else if state.has_header_row {
+                state
+                    .table_columns
+                    .iter()
+                    .find(|c| c.eq_ignore_ascii_case(&csv_header))
+                    .cloned()
+                    .unwrap_or_else(|| "__skip__".to_string())
+            } else {
+                state
+                    .table_columns
+                    .get(i)
+                    .cloned()
+                    .unwrap_or_else(|| "__skip__".to_string())
+            };
+            let values = state
+                .preview_rows
+                .iter()
+                .filter_map(|row| row.get(i))
+                .map(String::as_str);
+            models::structs::CsvColumnMapping {
+                csv_header,
+                target_column,
+                kind: crate::csv_import::ColumnKind::infer(values, &state.null_value),
+            }
+        })
+        .collect()
 }
 
-fn csv_quote_value(v: &str, null_value: &str, db_type: &crate::models::enums::DatabaseType) -> String {
-    if v == null_value || (null_value.is_empty() && v.is_empty()) {
-        return "NULL".to_string();
+/// Read the preview of `path` with the current options; `sniff` first
+/// detects the delimiter and header row.
+fn load_csv_preview(state: &mut models::structs::CsvImportState, path: std::path::PathBuf, sniff: bool) {
+    if sniff {
+        match crate::csv_import::sniff(&path) {
+            Ok((delimiter, has_header_row)) => {
+                state.delimiter = delimiter;
+                state.has_header_row = has_header_row;
+            }
+            Err(e) => {
+                state.status = models::structs::CsvImportStatus::Failed(e.clone());
+                state.progress_message = format!("Parse error: {}", e);
+                return;
+            }
+        }
     }
-    match db_type {
-        crate::models::enums::DatabaseType::MySQL => {
-            format!("'{}'", v.replace('\\', "\\\\").replace('\'', "''"))
+    match crate::csv_import::read_preview(
+        &path,
+        state.delimiter,
+        state.has_header_row,
+        crate::csv_import::PREVIEW_ROWS,
+    ) {
+        Ok((headers, preview)) => {
+            state.preview_headers = headers;
+            state.preview_rows = preview;
+            state.column_mappings = build_auto_mappings(state);
+            state.file_path = Some(path);
+            state.status = models::structs::CsvImportStatus::Idle;
+            state.progress_message = if sniff {
+                let delimiter = match state.delimiter {
+                    '\t' => "tab".to_string(),
+                    c => format!("'{}'", c),
+                };
+                let header = if state.has_header_row { "a" } else { "no" };
+                format!("Detected {} delimiter and {} header row", delimiter, header)
+            } else {
+                String::new()
+            };
+        }
+        Err(e) => {
+            state.status = models::structs::CsvImportStatus::Failed(e.clone());
+            state.progress_message = format!("Parse error: {}", e);
         }
-        _ => format!("'{}'", v.replace('\'', "''")),
     }
 }
 
-fn csv_quote_ident(s: &str, db_type: &crate::models::enums::DatabaseType) -> String {
-    match db_type {
-        crate::models::enums::DatabaseType::MySQL => format!("`{}`", s.replace('`', "``")),
-        crate::models::enums::DatabaseType::MsSQL => format!("[{}]", s.trim_matches(['[', ']'])),
-        _ => format!("\"{}\"", s.replace('"', "\"\"")),
-    }
+/// Fold the background import's progress into the dialog state.
+fn poll_csv_import(state: &mut models::structs::CsvImportState) {
+    let Some(progress) = &state.progress else {
+        return;
+    };
+    let Ok(progress) = progress.lock() else {
+        return;
+    };
+    let skipped = if progress.rows_skipped > 0 {
+        format!(", {} skipped", progress.rows_skipped)
+    } else {
+        String::new()
+    };
+    let (status, message) = match &progress.finished {
+        None => (
+            models::structs::CsvImportStatus::Importing,
+            format!("Imported {} rows{}...", progress.rows_imported, skipped),
+        ),
+        Some(models::structs::CsvImportStatus::Done(rows)) => (
+            models::structs::CsvImportStatus::Done(*rows),
+            format!("Imported {} rows{}", rows, skipped),
+        ),
+        Some(models::structs::CsvImportStatus::Cancelled(rows)) => (
+            models::structs::CsvImportStatus::Cancelled(*rows),
+            format!("Cancelled after {} rows{}", rows, skipped),
+        ),
+        Some(models::structs::CsvImportStatus::Failed(e)) => (
+            models::structs::CsvImportStatus::Failed(e.clone()),
+            format!("Import failed: {}", e),
+        ),
+        Some(other) => (other.clone(), String::new()),
+    };
+    state.status = status;
+    state.progress_message = message;
 }
 
-fn build_csv_insert_batches(
-    table_name: &str,
-    database_name: Option<&str>,
-    db_type: &crate::models::enums::DatabaseType,
-    column_mappings: &[crate::models::structs::CsvColumnMapping],
-    all_rows: &[Vec<String>],
-    null_value: &str,
-) -> Vec<String> {
-    let active_indices: Vec<(usize, &str)> = column_mappings
+/// Capture the dialog's choices as a background job.
+fn build_csv_import_job(
+    state: &models::structs::CsvImportState,
+) -> Result<crate::connection::CsvImportJob, String> {
+    let path = state.file_path.clone().ok_or("Choose a CSV file first.")?;
+    if state.table_name.trim().is_empty() {
+        return Err("Enter a table name.".to_string());
+    }
+    let columns: Vec<&models::structs::CsvColumnMapping> = state
+        .column_mappings
         .iter()
-        .enumerate()
-        .filter(|(_, m)| m.target_column != "__skip__" && !m.target_column.is_empty())
-        .map(|(i, m)| (i, m.target_column.as_str()))
+        .filter(|m| m.target_column != "__skip__" && !m.target_column.trim().is_empty())
         .collect();
-
-    if active_indices.is_empty() || all_rows.is_empty() {
-        return vec![];
+    if columns.is_empty() {
+        return Err("No data or all columns skipped.".to_string());
     }
-
-    let full_table = match (db_type, database_name) {
-        (crate::models::enums::DatabaseType::MySQL, Some(db)) => {
-            format!("{}.{}", csv_quote_ident(db, db_type), csv_quote_ident(table_name, db_type))
-        }
-        (crate::models::enums::DatabaseType::PostgreSQL, Some(schema)) => {
-            format!("{}.{}", csv_quote_ident(schema, db_type), csv_quote_ident(table_name, db_type))
-        }
-        (crate::models::enums::DatabaseType::MsSQL, Some(db)) => {
-            format!("[{}].dbo.{}", db, csv_quote_ident(table_name, db_type))
-        }
-        _ => csv_quote_ident(table_name, db_type),
-    };
-
-    let col_list: String = active_indices
-        .iter()
-        .map(|(_, col)| csv_quote_ident(col, db_type))
-        .collect::<Vec<_>>()
-        .join(", ");
-
-    let mut batches = Vec::new();
-    for chunk in all_rows.chunks(100) {
-        let rows_sql: Vec<String> = chunk
+    let table = crate::csv_import::qualified_table(
+        state.table_name.trim(),
+        state.database_name.as_deref(),
+        &state.db_type,
+    );
+    let create_table_sql = state.create_table.then(|| {
+        let definitions: Vec<(String, crate::csv_import::ColumnKind)> = columns
             .iter()
-            .map(|row| {
-                let vals: Vec<String> = active_indices
-                    .iter()
-                    .map(|(ci, _)| {
-                        let v = row.get(*ci).map(String::as_str).unwrap_or("");
-                        csv_quote_value(v, null_value, db_type)
-                    })
-                    .collect();
-                format!("({})", vals.join(", "))
-            })
+            .map(|m| (m.target_column.trim().to_string(), m.kind))
             .collect();
-
-        batches.push(format!(
-            "INSERT INTO {} ({}) VALUES\n{};",
-            full_table, col_list, rows_sql.join(",\n")
-        ));
-    }
-    batches
+        crate::csv_import::create_table_sql(&table, &definitions, &state.db_type)
+    });
+    let plan = crate::csv_import::InsertPlan {
+        table,
+        columns: state
+            .column_mappings
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.target_column != "__skip__" && !m.target_column.trim().is_empty())
+            // Existing columns get quoted strings and let the database convert.
+            .map(|(i, m)| (i, m.target_column.trim().to_string(), state.create_table.then_some(m.kind)))
+            .collect(),
+        null_value: state.null_value.clone(),
+        db_type: state.db_type.clone(),
+    };
+    Ok(crate::connection::CsvImportJob {
+        path,
+        delimiter: state.delimiter,
+        has_header_row: state.has_header_row,
+        create_table_sql,
+        plan,
+        row_error_policy: state.row_error_policy,
+    })
 }
 
 pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
     if !tabular.show_csv_import_dialog {
         return;
     }
-    if tabular.csv_import_state.is_none() {
+    let Some(state) = tabular.csv_import_state.as_mut() else {
         tabular.show_csv_import_dialog = false;
         return;
+    };
+    poll_csv_import(state);
+    let importing = state.status == models::structs::CsvImportStatus::Importing;
+    if importing {
+        ctx.request_repaint_after(std::time::Duration::from_millis(150));
     }
 
-    let table_name = tabular.csv_import_state.as_ref().unwrap().table_name.clone();
-    let title = format!("Import CSV  ->  \"{}\"", table_name);
+    let title = if state.create_table {
+        "Import CSV  ->  new table".to_string()
+    } else {
+        format!("Import CSV  ->  \"{}\"", state.table_name)
+    };
     let mut open_flag = tabular.show_csv_import_dialog;
     let mut should_close = false;
     let mut trigger_file_pick = false;
     let mut trigger_import = false;
     let mut redelimit = false;
+    let mut remap = false;
 
     egui::Window::new(&title)
+        .id(egui::Id::new("csv_import_dialog"))
         .collapsible(false)
         .resizable(true)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
-        .default_width(680.0)
+        .default_width(720.0)
         .min_width(480.0)
-        .max_height(600.0)
+        .max_height(640.0)
         .open(&mut open_flag)
         .show(ctx, |ui| {
             let state = tabular.csv_import_state.as_mut().unwrap();
             let accent = window_egui::style::theme_accent(ctx);
             let muted = window_egui::style::theme_muted_text(ctx);
 
+            ui.add_enabled_ui(!importing, |ui| {
             // ── Source file ───────────────────────────────────────────────────
             egui::Frame::group(ui.style())
                 .inner_margin(egui::Vec2::new(12.0, 10.0))
@@ -1455,6 +1546,30 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
                         .num_columns(2)
                         .spacing([12.0, 6.0])
                         .show(ui, |ui| {
+                            ui.label(egui::RichText::new("Target").color(muted));
+                            ui.horizontal(|ui| {
+                                if ui.selectable_label(!state.create_table, "Existing table").clicked()
+                                    && state.create_table
+                                {
+                                    state.create_table = false;
+                                    remap = true;
+                                }
+                                if ui.selectable_label(state.create_table, "New table").clicked()
+                                    && !state.create_table
+                                {
+                                    state.create_table = true;
+                                    remap = true;
+                                }
+                                if state.create_table {
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut state.table_name)
+                                            .desired_width(180.0)
+                                            .hint_text("new_table_name"),
+                                    );
+                                }
+                            });
+                            ui.end_row();
+
                             ui.label(egui::RichText::new("Delimiter").color(muted));
                             ui.horizontal(|ui| {
                                 for (ch, label) in [(',', "Comma"), (';', "Semicolon"), ('\t', "Tab"), ('|', "Pipe")] {
@@ -1480,10 +1595,31 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
 
                             ui.label(egui::RichText::new("NULL value").color(muted));
                             ui.horizontal(|ui| {
-                                ui.add(
-                                    egui::TextEdit::singleline(&mut state.null_value)
-                                        .desired_width(120.0)
-                                        .hint_text("leave empty to keep as string"),
+                                if ui
+                                    .add(
+                                        egui::TextEdit::singleline(&mut state.null_value)
+                                            .desired_width(120.0)
+                                            .hint_text("empty fields are NULL"),
+                                    )
+                                    .changed()
+                                    && state.file_path.is_some()
+                                {
+                                    remap = true;
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label(egui::RichText::new("On row error").color(muted));
+                            ui.horizontal(|ui| {
+                                ui.radio_value(
+                                    &mut state.row_error_policy,
+                                    models::structs::CsvRowErrorPolicy::Abort,
+                                    "Abort import",
+                                );
+                                ui.radio_value(
+                                    &mut state.row_error_policy,
+                                    models::structs::CsvRowErrorPolicy::Skip,
+                                    "Skip row and continue",
                                 );
                             });
                             ui.end_row();
@@ -1499,11 +1635,12 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
                         ui.horizontal(|ui| {
                             ui.label(egui::RichText::new("Column mapping").strong());
                             ui.add_space(4.0);
-                            ui.label(
-                                egui::RichText::new("Choose '(skip)' to ignore a column.")
-                                    .small()
-                                    .color(muted),
-                            );
+                            let hint = if state.create_table {
+                                "Name each new column and confirm its type."
+                            } else {
+                                "Choose '(skip)' to ignore a column."
+                            };
+                            ui.label(egui::RichText::new(hint).small().color(muted));
                         });
                         ui.add_space(6.0);
 
@@ -1513,14 +1650,16 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
                             .max_height(scroll_h)
                             .show(ui, |ui| {
                                 egui::Grid::new("csv_mapping_grid")
-                                    .num_columns(4)
+                                    .num_columns(5)
                                     .spacing([8.0, 4.0])
                                     .striped(true)
-                                    .min_col_width(80.0)
+                                    .min_col_width(60.0)
                                     .show(ui, |ui| {
                                         ui.label(egui::RichText::new("CSV column").strong().small().color(muted));
                                         ui.label(egui::RichText::new("").small());
-                                        ui.label(egui::RichText::new("Table column").strong().small().color(muted));
+                                        let target = if state.create_table { "New column" } else { "Table column" };
+                                        ui.label(egui::RichText::new(target).strong().small().color(muted));
+                                        ui.label(egui::RichText::new("Type").strong().small().color(muted));
                                         ui.label(egui::RichText::new("Sample value").strong().small().color(muted));
                                         ui.end_row();
 
@@ -1531,20 +1670,42 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
                                                     .small(),
                                             );
                                             ui.label(egui::RichText::new("->").color(muted).small());
-                                            let mut sel = mapping.target_column.clone();
-                                            let display = if sel == "__skip__" { "(skip)" } else { sel.as_str() };
-                                            let combo_color = if sel == "__skip__" { muted } else { ui.visuals().text_color() };
-                                            egui::ComboBox::from_id_salt(egui::Id::new(("csv_map", i)))
-                                                .selected_text(egui::RichText::new(display).color(combo_color))
-                                                .width(170.0)
-                                                .show_ui(ui, |ui| {
-                                                    ui.selectable_value(&mut sel, "__skip__".to_string(),
-                                                        egui::RichText::new("(skip)").color(muted));
-                                                    for col in &state.table_columns {
-                                                        ui.selectable_value(&mut sel, col.clone(), col.as_str());
-                                                    }
-                                                });
-                                            mapping.target_column = sel;
+                                            if state.create_table {
+                                                ui.add(
+                                                    egui::TextEdit::singleline(&mut mapping.target_column)
+                                                        .desired_width(170.0)
+                                                        .hint_text("(skip)"),
+                                                );
+                                                egui::ComboBox::from_id_salt(egui::Id::new(("csv_kind", i)))
+                                                    .selected_text(mapping.kind.label())
+                                                    .width(110.0)
+                                                    .show_ui(ui, |ui| {
+                                                        for kind in crate::csv_import::ColumnKind::ALL {
+                                                            ui.selectable_value(&mut mapping.kind, kind, kind.label());
+                                                        }
+                                                    });
+                                            } else {
+                                                let mut sel = mapping.target_column.clone();
+                                                let display = if sel == "__skip__" { "(skip)" } else { sel.as_str() };
+                                                let combo_color = if sel == "__skip__" { muted } else { ui.visuals().text_color() };
+                                                egui::ComboBox::from_id_salt(egui::Id::new(("csv_map", i)))
+                                                    .selected_text(egui::RichText::new(display).color(combo_color))
+                                                    .width(170.0)
+                                                    .show_ui(ui, |ui| {
+                                                        ui.selectable_value(&mut sel, "__skip__".to_string(),
+                                                            egui::RichText::new("(skip)").color(muted));
+                                                        for col in &state.table_columns {
+                                                            ui.selectable_value(&mut sel, col.clone(), col.as_str());
+                                                        }
+                                                    });
+                                                mapping.target_column = sel;
+                                                ui.label(
+                                                    egui::RichText::new(mapping.kind.label())
+                                                        .small()
+                                                        .color(muted),
+                                                )
+                                                .on_hover_text("Detected from the preview rows");
+                                            }
 
                                             let preview_val = state.preview_rows.first()
                                                 .and_then(|r| r.get(i))
@@ -1566,8 +1727,9 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
                 ui.add_space(4.0);
 
                 // ── Data preview ──────────────────────────────────────────────
+                let shown = state.preview_rows.len().min(10);
                 ui.collapsing(
-                    egui::RichText::new(format!("Data preview  ({} rows shown)", state.preview_rows.len())).small(),
+                    egui::RichText::new(format!("Data preview  ({} rows shown)", shown)).small(),
                     |ui| {
                         egui::ScrollArea::horizontal()
                             .id_salt("csv_preview_hscroll")
@@ -1580,7 +1742,7 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
                                             ui.label(egui::RichText::new(h).strong().small().monospace());
                                         }
                                         if !state.preview_headers.is_empty() { ui.end_row(); }
-                                        for row in &state.preview_rows {
+                                        for row in state.preview_rows.iter().take(shown) {
                                             for cell in row {
                                                 ui.label(egui::RichText::new(cell).small());
                                             }
@@ -1592,6 +1754,39 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
                 );
                 ui.add_space(4.0);
             }
+            });
+
+            // ── Progress + row errors ─────────────────────────────────────────
+            if let Some(progress) = &state.progress
+                && let Ok(progress) = progress.lock()
+            {
+                if importing {
+                    ui.add(
+                        egui::ProgressBar::new(progress.fraction)
+                            .show_percentage()
+                            .desired_height(14.0),
+                    );
+                }
+                if !progress.row_errors.is_empty() {
+                    ui.collapsing(
+                        egui::RichText::new(format!("Rejected rows ({})", progress.rows_skipped))
+                            .small()
+                            .color(window_egui::style::theme_danger(ui.ctx())),
+                        |ui| {
+                            egui::ScrollArea::vertical()
+                                .id_salt("csv_row_errors")
+                                .max_height(120.0)
+                                .show(ui, |ui| {
+                                    for (line, error) in &progress.row_errors {
+                                        ui.label(
+                                            egui::RichText::new(format!("Line {}: {}", line, error)).small(),
+                                        );
+                                    }
+                                });
+                        },
+                    );
+                }
+            }
 
             // ── Status bar + buttons ──────────────────────────────────────────
             ui.separator();
@@ -1600,12 +1795,14 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
                 // Status on the left
                 if !state.progress_message.is_empty() {
                     let (icon, color) = match &state.status {
-                        crate::models::structs::CsvImportStatus::Failed(_) =>
+                        models::structs::CsvImportStatus::Failed(_) =>
                             ("x ", window_egui::style::theme_danger(ui.ctx())),
-                        crate::models::structs::CsvImportStatus::Done(_) =>
+                        models::structs::CsvImportStatus::Done(_) =>
                             ("ok ", window_egui::style::theme_success(ui.ctx())),
-                        crate::models::structs::CsvImportStatus::Importing =>
+                        models::structs::CsvImportStatus::Importing =>
                             ("... ", window_egui::style::theme_accent(ui.ctx())),
+                        models::structs::CsvImportStatus::Cancelled(_) =>
+                            ("", window_egui::style::theme_warning(ui.ctx())),
                         _ => ("", muted),
                     };
                     ui.label(
@@ -1621,14 +1818,23 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
                         should_close = true;
                     }
                     ui.add_space(4.0);
-                    let can_import = state.file_path.is_some()
-                        && !state.column_mappings.is_empty()
-                        && state.status != crate::models::structs::CsvImportStatus::Importing;
-                    let import_btn = egui::Button::new(
-                        egui::RichText::new("  Import  ").strong()
-                    );
-                    if ui.add_enabled(can_import, import_btn).clicked() {
-                        trigger_import = true;
+                    if importing {
+                        let stopping = state.cancel.load(std::sync::atomic::Ordering::Relaxed);
+                        if ui
+                            .add_enabled(!stopping, egui::Button::new("  Cancel import  "))
+                            .on_hover_text("Stops after the current batch; committed rows stay")
+                            .clicked()
+                        {
+                            state.cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+                        }
+                    } else {
+                        let can_import = state.file_path.is_some() && !state.column_mappings.is_empty();
+                        let import_btn = egui::Button::new(
+                            egui::RichText::new("  Import  ").strong()
+                        );
+                        if ui.add_enabled(can_import, import_btn).clicked() {
+                            trigger_import = true;
+                        }
                     }
                 });
             });
@@ -1638,156 +1844,154 @@ pub(crate) fn render_csv_import_dialog(tabular: &mut window_egui::Tabular, ctx:
     // ── File pick (outside closure) ────────────────────────────────────────
     if redelimit {
         let state = tabular.csv_import_state.as_mut().unwrap();
-        let path = state.file_path.clone().unwrap();
-        let delim = state.delimiter;
-        let has_hdr = state.has_header_row;
-        if let Ok((headers, preview)) = parse_csv_preview(&path, delim, has_hdr) {
-            let table_cols = state.table_columns.clone();
-            let mappings = build_auto_mappings(&headers, &preview, has_hdr, &table_cols);
-            state.preview_headers = headers;
-            state.preview_rows = preview;
-            state.column_mappings = mappings;
+        if let Some(path) = state.file_path.clone() {
+            load_csv_preview(state, path, false);
         }
+    } else if remap {
+        let state = tabular.csv_import_state.as_mut().unwrap();
+        state.column_mappings = build_auto_mappings(state);
     }
 
     if trigger_file_pick
         && let Some(path) = rfd::FileDialog::new()
             .add_filter("CSV / TSV", &["csv", "tsv", "txt"])
             .pick_file()
-        {
-            let state = tabular.csv_import_state.as_mut().unwrap();
-            let delim = state.delimiter;
-            let has_hdr = state.has_header_row;
-            match parse_csv_preview(&path, delim, has_hdr) {
-                Ok((headers, preview)) => {
-                    let table_cols = state.table_columns.clone();
-                    let mappings = build_auto_mappings(&headers, &preview, has_hdr, &table_cols);
-                    state.preview_headers = headers;
-                    state.preview_rows = preview;
-                    state.column_mappings = mappings;
-                    state.file_path = Some(path);
-                    state.status = crate::models::structs::CsvImportStatus::Idle;
-                    state.progress_message = String::new();
-                }
-                Err(e) => {
-                    state.status = crate::models::structs::CsvImportStatus::Failed(e.clone());
-                    state.progress_message = format!("Parse error: {}", e);
-                }
-            }
-        }
+    {
+        load_csv_preview(tabular.csv_import_state.as_mut().unwrap(), path, true);
+    }
 
     if trigger_import {
         let state = tabular.csv_import_state.as_ref().unwrap();
-        let path = state.file_path.clone().unwrap();
-        let delim = state.delimiter;
-        let has_hdr = state.has_header_row;
-        let null_value = state.null_value.clone();
-        let table_name2 = state.table_name.clone();
-        let database_name = state.database_name.clone();
-        let db_type = state.db_type.clone();
-        let mappings = state.column_mappings.clone();
         let connection_id = state.connection_id;
-
-        match parse_csv_all(&path, delim, has_hdr) {
-            Ok(all_rows) => {
-                let total_rows = all_rows.len();
-                let batches = build_csv_insert_batches(
-                    &table_name2,
-                    database_name.as_deref(),
-                    &db_type,
-                    &mappings,
-                    &all_rows,
-                    &null_value,
-                );
-                if batches.is_empty() {
-                    let state = tabular.csv_import_state.as_mut().unwrap();
-                    state.status = crate::models::structs::CsvImportStatus::Failed("No data or all columns skipped.".into());
-                    state.progress_message = "No data or all columns skipped.".into();
-                } else {
-                    let batch_count = batches.len();
-                    let mut jobs = Vec::new();
-                    let mut all_ok = true;
-                    for (i, sql) in batches.into_iter().enumerate() {
-                        let job_id = tabular.next_query_job_id;
-                        tabular.next_query_job_id = tabular.next_query_job_id.wrapping_add(1);
-                        match crate::connection::prepare_query_job(tabular, connection_id, sql, job_id) {
-                            Ok(job) => {
-                                let preview = format!("CSV import batch {}/{}", i + 1, batch_count);
-                                tabular.active_query_jobs.insert(
-                                    job_id,
-                                    crate::connection::QueryJobStatus {
-                                        job_id,
-                                        connection_id,
-                                        query_preview: preview,
-                                        started_at: std::time::Instant::now(),
-                                        completed: false,
-                                    },
-                                );
-                                jobs.push(job);
-                            }
-                            Err(e) => {
-                                let state = tabular.csv_import_state.as_mut().unwrap();
-                                state.status = crate::models::structs::CsvImportStatus::Failed(format!("{:?}", e));
-                                state.progress_message = format!("Failed to prepare batch: {:?}", e);
-                                all_ok = false;
-                                break;
-                            }
-                        }
-                    }
-                    if all_ok && !jobs.is_empty() {
-                        let sender = tabular.query_result_sender.clone();
-                        match crate::connection::spawn_query_job_batch(tabular, jobs, sender) {
-                            Ok(_) => {
-                                let state = tabular.csv_import_state.as_mut().unwrap();
-                                state.status = crate::models::structs::CsvImportStatus::Importing;
-                                state.progress_message = format!(
-                                    "Importing {} rows in {} batch(es) of up to 100...",
-                                    total_rows, batch_count
-                                );
-                            }
-                            Err(e) => {
-                                let state = tabular.csv_import_state.as_mut().unwrap();
-                                state.status = crate::models::structs::CsvImportStatus::Failed(format!("{:?}", e));
-                                state.progress_message = format!("Failed to start import: {:?}", e);
-                            }
-                        }
-                    }
-                }
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let started = build_csv_import_job(state).and_then(|job| {
+            crate::connection::spawn_csv_import(tabular, connection_id, job, cancel.clone())
+        });
+        let state = tabular.csv_import_state.as_mut().unwrap();
+        match started {
+            Ok(progress) => {
+                state.progress = Some(progress);
+                state.cancel = cancel;
+                state.status = models::structs::CsvImportStatus::Importing;
+                state.progress_message = "Starting import...".to_string();
             }
             Err(e) => {
-                let state = tabular.csv_import_state.as_mut().unwrap();
-                state.status = crate::models::structs::CsvImportStatus::Failed(e.clone());
-                state.progress_message = format!("Failed to read CSV: {}", e);
+                state.status = models::structs::CsvImportStatus::Failed(e.clone());
+                state.progress_message = e;
             }
         }
     }
 
     if !open_flag || should_close {
+        // Closing does not wait for a running import; it stops after the
+        // current batch.
+        if let Some(state) = &tabular.csv_import_state {
+            state.cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
         tabular.show_csv_import_dialog = false;
         tabular.csv_import_state = None;
     }
 }
 
-fn build_auto_mappings(
-    headers: &[String],
-    preview: &[Vec<String>],
-    has_header_row: bool,
-    table_cols: &[String],
-) -> Vec<crate::models::structs::CsvColumnMapping> {
-    if has_header_row {
-        headers.iter().map(|h| {
-            let target = table_cols.iter()
-                .find(|c| c.to_lowercase() == h.to_lowercase())
-                .cloned()
-                .unwrap_or_else(|| "__skip__".to_string());
-            crate::models::structs::CsvColumnMapping { csv_header: h.clone(), target_column: target }
-        }).collect()
-    } else {
-        let ncols = preview.first().map(|r| r.len()).unwrap_or(0);
-        (0..ncols).map(|i| {
-            let target = table_cols.get(i).cloned().unwrap_or_else(|| "__skip__".to_string());
-            crate::models::structs::CsvColumnMapping { csv_header: format!("col_{}", i + 1), target_column: target }
-        }).collect()
+// ── Routine Call ──────────────────────────────────────────────────────────────
+
+pub(crate) fn render_routine_call_dialog(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(state) = tabular.routine_call_state.as_mut() else {
+        return;
+    };
+    crate::connection::poll_routine_signature(state);
+    let loading = state.signature_receiver.is_some();
+    if loading {
+        ctx.request_repaint_after(std::time::Duration::from_millis(100));
+    }
+
+    let verb = match state.kind {
+        models::structs::RoutineKind::Procedure => "Call procedure",
+        models::structs::RoutineKind::Function => "Call function",
+    };
+    let mut open = true;
+    let mut call_clicked = false;
+    let mut cancel_clicked = false;
+
+    egui::Window::new(format!("{} {}", verb, state.routine_name))
+        .id(egui::Id::new("routine_call_dialog"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .default_width(480.0)
+        .open(&mut open)
+        .show(ctx, |ui| {
+            let muted = window_egui::style::theme_muted_text(ctx);
+            if loading {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Loading parameters...");
+                });
+            } else if let Some(error) = &state.error {
+                ui.label(
+                    egui::RichText::new(format!("Could not read the signature: {}", error))
+                        .color(window_egui::style::theme_danger(ctx)),
+                );
+            } else if state.parameters.is_empty() {
+                ui.label(egui::RichText::new("This routine takes no parameters.").color(muted));
+            } else {
+                egui::Grid::new("routine_call_grid")
+                    .num_columns(3)
+                    .spacing([10.0, 6.0])
+                    .show(ui, |ui| {
+                        for (parameter, value) in state.parameters.iter().zip(state.values.iter_mut()) {
+                            ui.label(egui::RichText::new(&parameter.name).monospace().strong());
+                            ui.label(
+                                egui::RichText::new(format!(
+                                    "{} {}",
+                                    parameter.mode.label(),
+                                    parameter.data_type
+                                ))
+                                .small()
+                                .color(muted),
+                            );
+                            if parameter.mode.takes_value() {
+                                ui.add(
+                                    egui::TextEdit::singleline(value)
+                                        .hint_text("NULL")
+                                        .desired_width(220.0),
+                                );
+                            } else {
+                                ui.label(egui::RichText::new("returned").small().italics().color(muted));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(4.0);
+                ui.label(
+                    egui::RichText::new("Values are bound as parameters; leave a field empty for NULL.")
+                        .small()
+                        .color(muted),
+                );
+            }
+
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let ready = !loading && state.error.is_none();
+                    if ui
+                        .add_enabled(ready, egui::Button::new(egui::RichText::new("▶ Call").strong()))
+                        .clicked()
+                    {
+                        call_clicked = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel_clicked = true;
+                    }
+                });
+            });
+        });
+
+    if call_clicked && let Some(state) = tabular.routine_call_state.take() {
+        crate::connection::spawn_routine_call(tabular, &state);
+    } else if cancel_clicked || !open {
+        tabular.routine_call_state = None;
     }
 }
 
@@ -1798,13 +2002,14 @@ pub(crate) fn render_parameter_dialog(tabular: &mut window_egui::Tabular, ctx: &
 
     let mut execute_clicked = false;
     let mut cancel_clicked = false;
+    let mut open = true;
 
     egui::Window::new("Parameter Bindings Required")
         .collapsible(false)
         .resizable(false)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .default_width(450.0)
-        .open(&mut tabular.show_parameter_dialog)
+        .open(&mut open)
         .show(ctx, |ui| {
             ui.vertical(|ui| {
                 ui.label(
@@ -1812,6 +2017,8 @@ pub(crate) fn render_parameter_dialog(tabular: &mut window_egui::Tabular, ctx: &
                         .strong(),
                 );
                 ui.add_space(8.0);
+                crate::query_presets::render_preset_editor(tabular, ui);
+                ui.add_space(8.0);
 
                 egui::Grid::new("parameter_input_grid")
                     .num_columns(2)
@@ -1852,13 +2059,85 @@ pub(crate) fn render_parameter_dialog(tabular: &mut window_egui::Tabular, ctx: &
             });
         });
 
-    if cancel_clicked {
+    if cancel_clicked || !open {
         tabular.show_parameter_dialog = false;
     } else if execute_clicked {
         tabular.show_parameter_dialog = false;
         let query = tabular.parameter_dialog_query.clone();
-        let substituted = editor::substitute_query_parameters(&query, &tabular.parameter_inputs);
-        editor::execute_query_bypass_checks(tabular, substituted);
+        let inputs = tabular.parameter_inputs.clone();
+        editor::execute_query_with_params(tabular, query, inputs);
+    }
+}
+
+/// Non-modal panel with a value field for every placeholder in the editor.
+/// Values are kept on the tab; running with all of them filled skips the
+/// parameter dialog.
+pub(crate) fn render_parameters_panel(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    if !tabular.show_parameters_panel {
+        return;
+    }
+
+    let params = editor::extract_query_parameters(&tabular.editor.text);
+    let mut run_clicked = false;
+    let mut open = true;
+
+    egui::Window::new("Query Parameters")
+        .collapsible(true)
+        .resizable(true)
+        .default_width(360.0)
+        .open(&mut open)
+        .show(ctx, |ui| {
+            let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+                return;
+            };
+            if params.is_empty() {
+                ui.label(
+                    egui::RichText::new("No :name, @name or $n placeholders in this query")
+                        .weak(),
+                );
+                return;
+            }
+            for name in &params {
+                if !tab.param_values.iter().any(|(n, _)| n == name) {
+                    tab.param_values.push((name.clone(), String::new()));
+                }
+            }
+            egui::Grid::new("parameters_panel_grid")
+                .num_columns(2)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    for (name, value) in tab
+                        .param_values
+                        .iter_mut()
+                        .filter(|(n, _)| params.contains(n))
+                    {
+                        ui.label(egui::RichText::new(name.as_str()).monospace().strong());
+                        ui.add(
+                            egui::TextEdit::singleline(value)
+                                .hint_text("NULL, 42, true, text…")
+                                .desired_width(220.0),
+                        );
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(6.0);
+            ui.label(
+                egui::RichText::new(
+                    "Values are bound by the driver, not pasted into the SQL. Quote a value ('42') to send it as text.",
+                )
+                .small()
+                .weak(),
+            );
+            ui.add_space(6.0);
+            if ui.button("▶ Run").clicked() {
+                run_clicked = true;
+            }
+        });
+
+    if !open {
+        tabular.show_parameters_panel = false;
+    } else if run_clicked {
+        editor::execute_query(tabular);
     }
 }
 
@@ -1867,8 +2146,47 @@ pub(crate) fn render_unsafe_dml_dialog(tabular: &mut window_egui::Tabular, ctx:
         return;
     }
 
+    editor::poll_dry_run_count(tabular);
+    let threshold = tabular.destructive_confirm_over_rows;
+    let counted = match &tabular.unsafe_dml_count {
+        Some(editor::DryRunCount::Done(rows)) => Some(*rows),
+        Some(editor::DryRunCount::Running(_)) => {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            None
+        }
+        _ => None,
+    };
+    // Under the configured threshold the statement runs without asking.
+    if threshold > 0 && counted.is_some_and(|rows| rows <= threshold) {
+        tabular.show_unsafe_dml_dialog = false;
+        tabular.unsafe_dml_count = None;
+        let query = tabular.unsafe_dml_query.clone();
+        editor::execute_query_bypass_checks(tabular, query);
+        return;
+    }
+
     let mut confirm_clicked = false;
     let mut cancel_clicked = false;
+    let mut count_clicked = false;
+
+    let (headline, detail) = match (tabular.unsafe_dml_type.as_str(), tabular.unsafe_dml_filtered) {
+        ("DROP", _) => (
+            "Perintah DROP ini akan menghapus objek secara permanen!".to_string(),
+            "Objek beserta seluruh datanya tidak dapat dikembalikan. Apakah Anda yakin ingin melanjutkan?",
+        ),
+        ("TRUNCATE", _) => (
+            "Perintah TRUNCATE ini akan mengosongkan seluruh tabel!".to_string(),
+            "Mengeksekusi perintah ini akan menghapus SELURUH baris data pada tabel target. Apakah Anda yakin ingin melanjutkan?",
+        ),
+        (kind, true) => (
+            format!("Perintah {} ini akan mengubah lebih dari {} baris!", kind, threshold),
+            "Periksa jumlah baris terdampak sebelum melanjutkan. Apakah Anda yakin ingin melanjutkan?",
+        ),
+        (kind, false) => (
+            format!("Perintah {} ini TIDAK memiliki klausa WHERE!", kind),
+            "Mengeksekusi perintah ini akan mengubah atau menghapus SELURUH baris data pada tabel target. Apakah Anda yakin ingin melanjutkan?",
+        ),
+    };
 
     egui::Window::new("⚠️ Peringatan: Query Berbahaya (Unsafe DML)")
         .collapsible(false)
@@ -1879,28 +2197,57 @@ pub(crate) fn render_unsafe_dml_dialog(tabular: &mut window_egui::Tabular, ctx:
         .show(ctx, |ui| {
             ui.vertical(|ui| {
                 ui.label(
-                    egui::RichText::new(format!(
-                        "Perintah {} ini TIDAK memiliki klausa WHERE!",
-                        tabular.unsafe_dml_type
-                    ))
-                    .color(window_egui::style::theme_danger(ctx))
-                    .strong()
-                    .size(15.0),
+                    egui::RichText::new(headline)
+                        .color(window_egui::style::theme_danger(ctx))
+                        .strong()
+                        .size(15.0),
                 );
                 ui.add_space(6.0);
-                ui.label(
-                    "Mengeksekusi perintah ini akan mengubah atau menghapus SELURUH baris data pada tabel target. Apakah Anda yakin ingin melanjutkan?",
-                );
+                ui.label(detail);
                 ui.add_space(8.0);
 
                 ui.group(|ui| {
-                    ui.label(
-                        egui::RichText::new(&tabular.unsafe_dml_query)
-                            .monospace()
-                            .size(12.0),
-                    );
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            ui.label(
+                                egui::RichText::new(&tabular.unsafe_dml_query)
+                                    .monospace()
+                                    .size(12.0),
+                            );
+                        });
                 });
 
+                if tabular.unsafe_dml_count_sql.is_some() {
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| match &tabular.unsafe_dml_count {
+                        None => {
+                            if ui.button("Hitung Baris Terdampak").clicked() {
+                                count_clicked = true;
+                            }
+                        }
+                        Some(editor::DryRunCount::Running(_)) => {
+                            ui.spinner();
+                            ui.label("Menghitung baris terdampak…");
+                        }
+                        Some(editor::DryRunCount::Done(rows)) => {
+                            ui.label(
+                                egui::RichText::new(format!("Perkiraan baris terdampak: {}", rows))
+                                    .strong(),
+                            );
+                        }
+                        Some(editor::DryRunCount::Failed(error)) => {
+                            ui.colored_label(
+                                window_egui::style::theme_danger(ctx),
+                                format!("Gagal menghitung baris: {}", error),
+                            );
+                            if ui.small_button("Coba Lagi").clicked() {
+                                count_clicked = true;
+                            }
+                        }
+                    });
+                }
+
                 ui.add_space(14.0);
                 ui.separator();
                 ui.add_space(8.0);
@@ -1925,12 +2272,118 @@ pub(crate) fn render_unsafe_dml_dialog(tabular: &mut window_egui::Tabular, ctx:
             });
         });
 
-    if cancel_clicked {
+    if count_clicked {
+        editor::start_dry_run_count(tabular);
+    }
+    if cancel_clicked || !tabular.show_unsafe_dml_dialog {
         tabular.show_unsafe_dml_dialog = false;
+        tabular.unsafe_dml_count = None;
     } else if confirm_clicked {
         tabular.show_unsafe_dml_dialog = false;
+        tabular.unsafe_dml_count = None;
         let query = tabular.unsafe_dml_query.clone();
         editor::execute_query_bypass_checks(tabular, query);
     }
 }
 
+pub(crate) fn render_session_kill_dialog(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(request) = tabular.pending_session_kill.as_ref() else {
+        return;
+    };
+
+    let mut open = true;
+    let mut confirm_clicked = false;
+    let mut cancel_clicked = false;
+
+    egui::Window::new("⛔ Kill Session")
+        .collapsible(false)
+        .resizable(true)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .default_width(480.0)
+        .open(&mut open)
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(format!("Terminate session {}?", request.session_id))
+                    .color(window_egui::style::theme_danger(ctx))
+                    .strong()
+                    .size(15.0),
+            );
+            ui.add_space(6.0);
+            ui.label("The session's connection is closed and any open transaction is rolled back.");
+            ui.add_space(8.0);
+
+            match request.query.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+                Some(query) => {
+                    ui.group(|ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(200.0)
+                            .show(ui, |ui| {
+                                ui.label(egui::RichText::new(query).monospace().size(12.0));
+                            });
+                    });
+                }
+                None => {
+                    ui.label(
+                        egui::RichText::new("No statement is currently running in this session.")
+                            .size(11.0)
+                            .color(egui::Color32::from_gray(120)),
+                    );
+                }
+            }
+
+            if let Some(error) = &request.error {
+                ui.add_space(8.0);
+                ui.colored_label(window_egui::style::theme_danger(ctx), error);
+            }
+
+            ui.add_space(14.0);
+            ui.separator();
+            ui.add_space(8.0);
+
+            ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .button(
+                            egui::RichText::new("Kill Session")
+                                .strong()
+                                .color(egui::Color32::WHITE),
+                        )
+                        .clicked()
+                    {
+                        confirm_clicked = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel_clicked = true;
+                    }
+                });
+            });
+        });
+
+    if cancel_clicked || !open {
+        tabular.pending_session_kill = None;
+    } else if confirm_clicked {
+        let Some(request) = tabular.pending_session_kill.take() else {
+            return;
+        };
+        match crate::connection::kill_session::kill_session(
+            tabular,
+            request.connection_id,
+            &request.session_id,
+        ) {
+            Ok(()) => {
+                tabular
+                    .toasts
+                    .success(format!("Session {} terminated", request.session_id));
+                editor::execute_query_bypass_checks(tabular, request.rerun_query);
+            }
+            Err(error) => {
+                // Keep the dialog open so the reason stays next to the session it concerns.
+                tabular.pending_session_kill = Some(models::structs::SessionKillRequest {
+                    error: Some(error),
+                    ..request
+                });
+            }
+        }
+    }
+}
+
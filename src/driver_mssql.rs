@@ -2,20 +2,48 @@
 use crate::models;
 use crate::window_egui; // for Tabular type
 
-use mssql_client::{Client, Config, Credentials, Ready, SqlValue};
+use mssql_client::{Client, Config, Credentials, Ready, SqlValue, ToSql};
 
 // MsSQL connectivity is provided by mssql-client (praxiomlabs rust-mssql-driver)
 // with pooling from mssql-driver-pool. The helpers below centralize config,
 // connection, and dynamic value-to-string conversion for the whole app.
 
-/// Build a Config for a direct (non-pooled) MsSQL connection.
-/// Mirrors the app-wide defaults: SQL auth + trusted server certificate.
+/// TLS options for a MsSQL connection, as chosen in the connection dialog.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct MssqlTls {
+    /// Encrypt the whole session, not only the login packet.
+    pub encrypt: bool,
+    /// Accept self-signed or otherwise unverifiable server certificates.
+    pub trust_server_certificate: bool,
+}
+
+impl Default for MssqlTls {
+    fn default() -> Self {
+        Self {
+            encrypt: true,
+            trust_server_certificate: true,
+        }
+    }
+}
+
+impl MssqlTls {
+    pub(crate) fn from_connection(connection: &models::structs::ConnectionConfig) -> Self {
+        Self {
+            encrypt: connection.mssql_encrypt,
+            trust_server_certificate: connection.mssql_trust_server_certificate,
+        }
+    }
+}
+
+/// Build a Config for a MsSQL connection (pooled or direct).
+/// SQL auth with the connection's encryption / certificate trust settings.
 pub(crate) fn mssql_config(
     host: &str,
     port: u16,
     username: &str,
     password: &str,
     database: Option<&str>,
+    tls: MssqlTls,
 ) -> Config {
     let mut config = Config::new()
         .host(host)
@@ -25,7 +53,8 @@ pub(crate) fn mssql_config(
             password.to_string(),
         ))
         .connect_timeout(std::time::Duration::from_secs(10))
-        .trust_server_certificate(true);
+        .encrypt(tls.encrypt)
+        .trust_server_certificate(tls.trust_server_certificate);
     if let Some(db) = database
         && !db.is_empty()
     {
@@ -41,8 +70,9 @@ pub(crate) async fn connect_mssql(
     username: &str,
     password: &str,
     database: Option<&str>,
+    tls: MssqlTls,
 ) -> Result<Client<Ready>, String> {
-    Client::connect(mssql_config(host, port, username, password, database))
+    Client::connect(mssql_config(host, port, username, password, database, tls))
         .await
         .map_err(|e| e.to_string())
 }
@@ -51,7 +81,8 @@ pub(crate) async fn connect_mssql(
 pub(crate) fn sql_value_to_string(value: &SqlValue) -> String {
     match value {
         SqlValue::Null => "NULL".to_string(),
-        SqlValue::Bool(v) => v.to_string(),
+        // BIT columns display as 1/0, matching SSMS and the T-SQL literals users type
+        SqlValue::Bool(v) => if *v { "1" } else { "0" }.to_string(),
         SqlValue::TinyInt(v) => v.to_string(),
         SqlValue::SmallInt(v) => v.to_string(),
         SqlValue::Int(v) => v.to_string(),
@@ -97,6 +128,215 @@ pub(crate) async fn pooled_query(
     stream.collect_all().await.map_err(|e| e.to_string())
 }
 
+/// CHECK definitions and single-column foreign key target of one column.
+/// `table_name` may be schema-qualified (`dbo.orders`, `[dbo].[orders]`).
+pub(crate) async fn fetch_mssql_column_constraints(
+    pool: &mssql_driver_pool::Pool,
+    table_name: &str,
+    column_name: &str,
+) -> Result<models::structs::ColumnConstraints, String> {
+    let table = table_name.replace('\'', "''");
+    let column = column_name.replace('\'', "''");
+    let checks = pooled_query(
+        pool,
+        &format!(
+            "SELECT cc.definition FROM sys.check_constraints cc \
+             LEFT JOIN sys.columns c ON c.object_id = cc.parent_object_id AND c.column_id = cc.parent_column_id \
+             WHERE cc.parent_object_id = OBJECT_ID(N'{table}') AND (c.name = N'{column}' OR cc.parent_column_id = 0)"
+        ),
+    )
+    .await?;
+    let refs = pooled_query(
+        pool,
+        &format!(
+            "SELECT TOP 1 QUOTENAME(OBJECT_SCHEMA_NAME(fkc.referenced_object_id)) + '.' + QUOTENAME(OBJECT_NAME(fkc.referenced_object_id)), rc.name \
+             FROM sys.foreign_key_columns fkc \
+             JOIN sys.columns pc ON pc.object_id = fkc.parent_object_id AND pc.column_id = fkc.parent_column_id \
+             JOIN sys.columns rc ON rc.object_id = fkc.referenced_object_id AND rc.column_id = fkc.referenced_column_id \
+             WHERE fkc.parent_object_id = OBJECT_ID(N'{table}') AND pc.name = N'{column}' \
+               AND NOT EXISTS (SELECT 1 FROM sys.foreign_key_columns o \
+                               WHERE o.constraint_object_id = fkc.constraint_object_id AND o.constraint_column_id > 1)"
+        ),
+    )
+    .await?;
+    Ok(models::structs::ColumnConstraints {
+        check_clauses: checks.iter().filter_map(|r| r.get_string(0)).collect(),
+        references: refs
+            .first()
+            .and_then(|r| Some((r.get_string(0)?, r.get_string(1)?))),
+        ..Default::default()
+    })
+}
+
+/// Up to `limit` distinct non-null values of `table.column`, sorted.
+/// `table_name` is used verbatim (already quoted by the constraint lookup).
+pub(crate) async fn fetch_mssql_distinct_values(
+    pool: &mssql_driver_pool::Pool,
+    table_name: &str,
+    column_name: &str,
+    limit: usize,
+) -> Result<Vec<String>, String> {
+    let col = format!("[{}]", column_name.replace(']', "]]"));
+    let rows = pooled_query(
+        pool,
+        &format!(
+            "SELECT DISTINCT TOP {limit} CAST({col} AS NVARCHAR(4000)) AS v FROM {table_name} \
+             WHERE {col} IS NOT NULL ORDER BY v"
+        ),
+    )
+    .await?;
+    Ok(rows.iter().filter_map(|r| r.get_string(0)).collect())
+}
+
+/// Parameters of a procedure or function (`object_name` may be
+/// database-qualified) and whether a function is table-valued. SQL Server
+/// does not distinguish OUT from INOUT, so output parameters are INOUT.
+pub(crate) async fn fetch_mssql_routine_signature(
+    pool: &mssql_driver_pool::Pool,
+    database_name: &str,
+    object_name: &str,
+) -> Result<models::structs::RoutineSignature, String> {
+    let catalog = if database_name.is_empty() {
+        String::new()
+    } else {
+        format!("[{}].", database_name.replace(']', "]]"))
+    };
+    // OBJECT_ID resolves in the connection's current database unless the
+    // name carries the tab's database too
+    let object = object_in_catalog(&catalog, object_name).replace('\'', "''");
+    let rows = pooled_query(
+        pool,
+        &format!(
+            "SELECT p.name, \
+             CASE WHEN t.name IN ('varchar', 'char', 'varbinary', 'binary') \
+                    THEN t.name + '(' + CASE WHEN p.max_length = -1 THEN 'max' ELSE CAST(p.max_length AS NVARCHAR(10)) END + ')' \
+                  WHEN t.name IN ('nvarchar', 'nchar') \
+                    THEN t.name + '(' + CASE WHEN p.max_length = -1 THEN 'max' ELSE CAST(p.max_length / 2 AS NVARCHAR(10)) END + ')' \
+                  WHEN t.name IN ('decimal', 'numeric') \
+                    THEN t.name + '(' + CAST(p.precision AS NVARCHAR(10)) + ',' + CAST(p.scale AS NVARCHAR(10)) + ')' \
+                  WHEN t.name IN ('datetime2', 'time', 'datetimeoffset') \
+                    THEN t.name + '(' + CAST(p.scale AS NVARCHAR(10)) + ')' \
+                  ELSE t.name END, \
+             CAST(p.is_output AS NVARCHAR(1)) \
+             FROM {catalog}sys.parameters p JOIN {catalog}sys.types t ON t.user_type_id = p.user_type_id \
+             WHERE p.object_id = OBJECT_ID(N'{object}') AND p.parameter_id > 0 \
+             ORDER BY p.parameter_id"
+        ),
+    )
+    .await?;
+    let kind = pooled_query(
+        pool,
+        &format!("SELECT type FROM {catalog}sys.objects WHERE object_id = OBJECT_ID(N'{object}')"),
+    )
+    .await?;
+    let returns_table = kind
+        .first()
+        .and_then(|r| r.get_string(0))
+        .is_some_and(|t| matches!(t.trim(), "IF" | "TF"));
+    let parameters = rows
+        .iter()
+        .map(|r| models::structs::RoutineParameter {
+            name: r.get_string(0).unwrap_or_default(),
+            data_type: r.get_string(1).unwrap_or_default(),
+            mode: if r.get_string(2).as_deref() == Some("1") {
+                models::structs::ParameterMode::InOut
+            } else {
+                models::structs::ParameterMode::In
+            },
+        })
+        .collect();
+    Ok((parameters, returns_table))
+}
+
+/// `object` (`name`, `schema.name` or already `db.schema.name`) qualified
+/// with `catalog` (`[db].`, or empty for the current database).
+fn object_in_catalog(catalog: &str, object: &str) -> String {
+    let mut parts = 1;
+    let mut bracketed = false;
+    for ch in object.chars() {
+        match ch {
+            '[' => bracketed = true,
+            ']' => bracketed = false,
+            '.' if !bracketed => parts += 1,
+            _ => {}
+        }
+    }
+    match (catalog.is_empty(), parts) {
+        (true, _) | (false, 3..) => object.to_string(),
+        (false, 2) => format!("{}{}", catalog, object),
+        (false, _) => format!("{}.{}", catalog, object),
+    }
+}
+
+/// Run a routine call batch with its values bound as `@p1..@pN`. Returns
+/// the last result set with columns (the OUTPUT values when there are any).
+pub(crate) async fn call_mssql_routine(
+    pool: &mssql_driver_pool::Pool,
+    sql: &str,
+    values: &[Option<String>],
+) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+    let client = conn
+        .client_mut()
+        .ok_or_else(|| "MsSQL pooled connection unavailable".to_string())?;
+    let params: Vec<&(dyn mssql_client::ToSql + Sync)> = values
+        .iter()
+        .map(|v| v as &(dyn mssql_client::ToSql + Sync))
+        .collect();
+    let mut stream = client
+        .query_multiple(sql, &params)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut result = (Vec::new(), Vec::new());
+    loop {
+        let headers: Vec<String> = stream
+            .columns()
+            .map(|cols| cols.iter().map(|c| c.name.clone()).collect())
+            .unwrap_or_default();
+        if !headers.is_empty() {
+            let mut rows = Vec::new();
+            while let Some(row) = stream.next_row().await.map_err(|e| e.to_string())? {
+                rows.push(row_values_to_strings(&row));
+            }
+            result = (headers, rows);
+        }
+        if !stream.next_result().await.map_err(|e| e.to_string())? {
+            break;
+        }
+    }
+    Ok(result)
+}
+
+/// Row count of the heap / clustered index and reserved size, from the
+/// catalog views.
+pub(crate) async fn fetch_mssql_table_stats(
+    pool: &mssql_driver_pool::Pool,
+    table_name: &str,
+) -> Result<models::structs::TableStats, String> {
+    let object = table_name.replace('\'', "''");
+    let rows = pooled_query(
+        pool,
+        &format!(
+            "SELECT CAST((SELECT SUM(rows) FROM sys.partitions \
+             WHERE object_id = OBJECT_ID(N'{object}') AND index_id IN (0, 1)) AS NVARCHAR(40)), \
+             CAST((SELECT SUM(a.total_pages) * 8192 FROM sys.partitions p \
+             JOIN sys.allocation_units a ON a.container_id = p.partition_id \
+             WHERE p.object_id = OBJECT_ID(N'{object}')) AS NVARCHAR(40))"
+        ),
+    )
+    .await?;
+    let cell = |i: usize| {
+        rows.first()
+            .and_then(|r| r.get_string(i))
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    Ok(models::structs::TableStats {
+        rows: cell(0),
+        rows_capped: false,
+        bytes: cell(1),
+    })
+}
+
 pub(crate) async fn fetch_mssql_data(
     _connection_id: i64,
     _pool: std::sync::Arc<mssql_driver_pool::Pool>,
@@ -325,9 +565,83 @@ pub(crate) async fn execute_query(
     run_query(client, query).await
 }
 
+/// Like `execute_query`, with `params` bound to the `@p1`, `@p2`, ...
+/// placeholders of `query` through sp_executesql.
+pub(crate) async fn execute_bound_query(
+    pool: std::sync::Arc<mssql_driver_pool::Pool>,
+    query: &str,
+    params: &[crate::query_tools::ParamValue],
+) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+    let client = conn
+        .client_mut()
+        .ok_or_else(|| "MsSQL pooled connection unavailable".to_string())?;
+    run_bound_query(client, query, params).await
+}
+
+/// Run `query` (no result set expected) with `params` bound to its `@p1`,
+/// `@p2`, ... placeholders, returning the number of rows it affected.
+pub(crate) async fn execute_statement(
+    pool: std::sync::Arc<mssql_driver_pool::Pool>,
+    query: &str,
+    params: &[crate::query_tools::ParamValue],
+) -> Result<u64, String> {
+    let mut conn = pool.get().await.map_err(|e| e.to_string())?;
+    let client = conn
+        .client_mut()
+        .ok_or_else(|| "MsSQL pooled connection unavailable".to_string())?;
+    let values = param_values_to_sql(params);
+    let refs: Vec<&(dyn ToSql + Sync)> = values
+        .iter()
+        .map(|v| v.as_ref() as &(dyn ToSql + Sync))
+        .collect();
+    client.execute(query, &refs).await.map_err(|e| e.to_string())
+}
+
+/// The parameters panel's values as SQL Server parameters, in bind order.
+pub(crate) fn param_values_to_sql(
+    params: &[crate::query_tools::ParamValue],
+) -> Vec<Box<dyn ToSql + Send + Sync>> {
+    use crate::query_tools::ParamValue;
+    params
+        .iter()
+        .map(|value| -> Box<dyn ToSql + Send + Sync> {
+            match value {
+                ParamValue::Null => Box::new(None::<String>),
+                ParamValue::Bool(b) => Box::new(*b),
+                ParamValue::Int(n) => Box::new(*n),
+                ParamValue::Float(f) => Box::new(*f),
+                ParamValue::Text(t) => Box::new(t.clone()),
+            }
+        })
+        .collect()
+}
+
+/// Like `run_query`, with `params` bound to the `@p1`, `@p2`, ... placeholders.
+pub(crate) async fn run_bound_query(
+    client: &mut Client<Ready>,
+    query: &str,
+    params: &[crate::query_tools::ParamValue],
+) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    let values = param_values_to_sql(params);
+    let refs: Vec<&(dyn ToSql + Sync)> = values
+        .iter()
+        .map(|v| v.as_ref() as &(dyn ToSql + Sync))
+        .collect();
+    run_query_with_params(client, query, &refs).await
+}
+
 pub(crate) async fn run_query(
     client: &mut Client<Ready>,
     query: &str,
+) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
+    run_query_with_params(client, query, &[]).await
+}
+
+async fn run_query_with_params(
+    client: &mut Client<Ready>,
+    query: &str,
+    params: &[&(dyn ToSql + Sync)],
 ) -> Result<(Vec<String>, Vec<Vec<String>>), String> {
     let mut headers: Vec<String> = Vec::new();
     let mut data: Vec<Vec<String>> = Vec::new();
@@ -336,7 +650,7 @@ pub(crate) async fn run_query(
     // previous tiberius stream, headers follow the latest result set with
     // columns while rows accumulate across result sets.
     let mut stream = client
-        .query_multiple(query, &[])
+        .query_multiple(query, params)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -434,6 +748,36 @@ pub(crate) fn sanitize_mssql_select_for_pagination(select_part: &str) -> String
     result
 }
 
+/// Wrap a SELECT in OFFSET/FETCH paging. T-SQL only accepts OFFSET after an
+/// ORDER BY, so `ORDER BY 1` is injected when missing; TOP is stripped because
+/// it cannot be combined with OFFSET. A leading `USE [db];` stays in front and
+/// queries that already page themselves are returned unchanged.
+pub(crate) fn build_mssql_paginated_query(base_query: &str, offset: usize, limit: usize) -> String {
+    let trimmed = base_query.trim();
+    let upper = trimmed.to_uppercase();
+    if upper.contains(" FETCH NEXT ") || upper.contains(" FETCH FIRST ") {
+        return trimmed.to_string();
+    }
+
+    // Separate a USE ...; prefix so pagination applies only to the SELECT part
+    let (prefix, body) = match upper.find(";") {
+        Some(semi) if upper.starts_with("USE ") => {
+            (format!("{}\n", &trimmed[..=semi]), trimmed[semi + 1..].trim())
+        }
+        _ => (String::new(), trimmed),
+    };
+
+    let mut select_part = sanitize_mssql_select_for_pagination(body.trim_end_matches(';').trim_end());
+    if !select_part.to_lowercase().contains("order by") {
+        select_part.push_str(" ORDER BY 1");
+    }
+    let effective_limit = if limit == 0 { 100 } else { limit };
+    format!(
+        "{}{} OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
+        prefix, select_part, offset, effective_limit
+    )
+}
+
 // Helper: build MsSQL SELECT ensuring database context and proper quoting.
 // db_name: selected database (can be empty -> fallback to object-provided or omit USE)
 // raw_name: could be formats: table, [schema].[object], schema.object, [db].[schema].[object], db.schema.object
@@ -524,3 +868,47 @@ pub(crate) fn build_mssql_select_query(db_name: String, raw_name: String) -> Str
         format!("USE [{}];\nSELECT TOP 100 * FROM {};", database_part, fq)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sql_values_render_like_ssms() {
+        assert_eq!(sql_value_to_string(&SqlValue::Bool(true)), "1");
+        assert_eq!(sql_value_to_string(&SqlValue::Bool(false)), "0");
+        assert_eq!(sql_value_to_string(&SqlValue::String("Zoë".into())), "Zoë");
+        let dt = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+            .unwrap()
+            .and_hms_micro_opt(13, 5, 9, 123_400)
+            .unwrap();
+        assert_eq!(
+            sql_value_to_string(&SqlValue::DateTime(dt)),
+            "2024-02-29 13:05:09.123400"
+        );
+        assert_eq!(sql_value_to_string(&SqlValue::Null), "NULL");
+    }
+
+    #[test]
+    fn paginated_query_uses_offset_fetch() {
+        assert_eq!(
+            build_mssql_paginated_query("SELECT TOP 10000 * FROM [dbo].[users];", 200, 100),
+            "SELECT * FROM [dbo].[users] ORDER BY 1 OFFSET 200 ROWS FETCH NEXT 100 ROWS ONLY"
+        );
+        assert_eq!(
+            build_mssql_paginated_query("USE [shop];\nSELECT id FROM orders ORDER BY id DESC", 0, 50),
+            "USE [shop];\nSELECT id FROM orders ORDER BY id DESC OFFSET 0 ROWS FETCH NEXT 50 ROWS ONLY"
+        );
+        let already = "SELECT id FROM t ORDER BY id OFFSET 5 ROWS FETCH NEXT 5 ROWS ONLY";
+        assert_eq!(build_mssql_paginated_query(already, 0, 50), already);
+    }
+
+    #[test]
+    fn routine_lookups_name_the_tab_database() {
+        assert_eq!(object_in_catalog("[shop].", "dbo.restock"), "[shop].dbo.restock");
+        assert_eq!(object_in_catalog("[shop].", "restock"), "[shop]..restock");
+        assert_eq!(object_in_catalog("[shop].", "[a.b].[c]"), "[shop].[a.b].[c]");
+        assert_eq!(object_in_catalog("[shop].", "other.dbo.restock"), "other.dbo.restock");
+        assert_eq!(object_in_catalog("", "dbo.restock"), "dbo.restock");
+    }
+}
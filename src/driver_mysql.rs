@@ -174,231 +174,248 @@ fn bytes_to_string_or_marker(bytes: Vec<u8>) -> String {
 pub(crate) fn convert_mysql_rows_to_table_data(
     rows: Vec<sqlx::mysql::MySqlRow>,
 ) -> Vec<Vec<String>> {
-    use sqlx::{Column, Row, TypeInfo};
-
-    let mut table_data = Vec::new();
-
-    for row in &rows {
-        let mut row_data = Vec::new();
-        let columns = row.columns();
-
-        for (idx, column) in columns.iter().enumerate() {
-            let column_name = column.name();
-            let type_info = column.type_info();
-            let type_name = type_info.name();
-            let t = type_name.to_ascii_uppercase(); // case-insensitive match
+    rows.iter().map(convert_mysql_row).collect()
+}
 
-            let value_str = match t.as_str() {
-                // Integer types
-                "TINYINT" => match row.try_get::<Option<i8>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
-                    Ok(None) => "NULL".to_string(),
-                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                },
-                "SMALLINT" => match row.try_get::<Option<i16>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
-                    Ok(None) => "NULL".to_string(),
-                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                },
-                "MEDIUMINT" | "INT" | "INTEGER" => match row.try_get::<Option<i32>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
-                    Ok(None) => "NULL".to_string(),
-                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                },
-                "BIGINT" => match row.try_get::<Option<i64>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
-                    Ok(None) => "NULL".to_string(),
-                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                },
+/// One MySQL row as display strings.
+pub(crate) fn convert_mysql_row(row: &sqlx::mysql::MySqlRow) -> Vec<String> {
+    use sqlx::{Column, Row, TypeInfo};
 
-                // Unsigned integer types
-                "TINYINT UNSIGNED" => match row.try_get::<Option<u8>, _>(idx) {
+    let mut row_data = Vec::new();
+    let columns = row.columns();
+
+    for (idx, column) in columns.iter().enumerate() {
+        let column_name = column.name();
+        let type_info = column.type_info();
+        let type_name = type_info.name();
+        let t = type_name.to_ascii_uppercase(); // case-insensitive match
+
+        let value_str = match t.as_str() {
+            // Integer types
+            "TINYINT" => match row.try_get::<Option<i8>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
+            },
+            "SMALLINT" => match row.try_get::<Option<i16>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
+            },
+            "MEDIUMINT" | "INT" | "INTEGER" => match row.try_get::<Option<i32>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
+            },
+            "BIGINT" => match row.try_get::<Option<i64>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
+            },
+
+            // Unsigned integer types
+            "TINYINT UNSIGNED" => match row.try_get::<Option<u8>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
+            },
+            "SMALLINT UNSIGNED" => match row.try_get::<Option<u16>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
+            },
+            "MEDIUMINT UNSIGNED" | "INT UNSIGNED" | "INTEGER UNSIGNED" => {
+                match row.try_get::<Option<u32>, _>(idx) {
                     Ok(Some(val)) => val.to_string(),
                     Ok(None) => "NULL".to_string(),
                     Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                },
-                "SMALLINT UNSIGNED" => match row.try_get::<Option<u16>, _>(idx) {
+                }
+            }
+            "BIGINT UNSIGNED" => {
+                // Prefer u64 for BIGINT UNSIGNED
+                match row.try_get::<Option<u64>, _>(idx) {
                     Ok(Some(val)) => val.to_string(),
                     Ok(None) => "NULL".to_string(),
-                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                },
-                "MEDIUMINT UNSIGNED" | "INT UNSIGNED" | "INTEGER UNSIGNED" => {
-                    match row.try_get::<Option<u32>, _>(idx) {
-                        Ok(Some(val)) => val.to_string(),
-                        Ok(None) => "NULL".to_string(),
-                        Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                    }
-                }
-                "BIGINT UNSIGNED" => {
-                    // Prefer u64 for BIGINT UNSIGNED
-                    match row.try_get::<Option<u64>, _>(idx) {
-                        Ok(Some(val)) => val.to_string(),
-                        Ok(None) => "NULL".to_string(),
-                        Err(er) => {
-                            debug!(
-                                "BIGINT UNSIGNED conversion error for column '{}'",
-                                column_name
-                            );
-                            error!("Error: {:?}", er);
-                            // Try signed as a fallback (if fits) before string fallback
-                            match row.try_get::<Option<i64>, _>(idx) {
-                                Ok(Some(val)) => val.to_string(),
-                                Ok(None) => "NULL".to_string(),
-                                Err(_) => {
-                                    get_value_as_string_fallback_idx(row, idx, column_name, &t)
-                                }
+                    Err(er) => {
+                        debug!(
+                            "BIGINT UNSIGNED conversion error for column '{}'",
+                            column_name
+                        );
+                        error!("Error: {:?}", er);
+                        // Try signed as a fallback (if fits) before string fallback
+                        match row.try_get::<Option<i64>, _>(idx) {
+                            Ok(Some(val)) => val.to_string(),
+                            Ok(None) => "NULL".to_string(),
+                            Err(_) => {
+                                get_value_as_string_fallback_idx(row, idx, column_name, &t)
                             }
                         }
                     }
                 }
+            }
 
-                // Floating point types
-                ,
-                "FLOAT" => match row.try_get::<Option<f32>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
-                    Ok(None) => "NULL".to_string(),
-                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                },
-                "DOUBLE" | "REAL" => match row.try_get::<Option<f64>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
-                    Ok(None) => "NULL".to_string(),
-                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                },
+            // Floating point types
+            ,
+            "FLOAT" => match row.try_get::<Option<f32>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
+            },
+            "DOUBLE" | "REAL" => match row.try_get::<Option<f64>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
+            },
+
+            // Decimal types - use rust_decimal for proper handling
+            "DECIMAL" | "NUMERIC" | "NEWDECIMAL" => {
+                if let Ok(Some(val)) = row.try_get::<Option<rust_decimal::Decimal>, _>(idx) {
+                    val.to_string()
+                } else if let Ok(val) = row.try_get::<rust_decimal::Decimal, _>(idx) {
+                    val.to_string()
+                } else if let Ok(Some(val)) = row.try_get::<Option<String>, _>(idx) {
+                    val
+                } else if let Ok(val) = row.try_get::<String, _>(idx) {
+                    val
+                } else if let Ok(Some(val)) = row.try_get::<Option<f64>, _>(idx) {
+                    val.to_string()
+                } else if let Ok(val) = row.try_get::<f64, _>(idx) {
+                    val.to_string()
+                } else {
+                    get_value_as_string_fallback_idx(row, idx, column_name, &t)
+                }
+            }
 
-                // Decimal types - use rust_decimal for proper handling
-                "DECIMAL" | "NUMERIC" | "NEWDECIMAL" => {
-                    if let Ok(Some(val)) = row.try_get::<Option<rust_decimal::Decimal>, _>(idx) {
-                        val.to_string()
-                    } else if let Ok(val) = row.try_get::<rust_decimal::Decimal, _>(idx) {
-                        val.to_string()
-                    } else if let Ok(Some(val)) = row.try_get::<Option<String>, _>(idx) {
-                        val
-                    } else if let Ok(val) = row.try_get::<String, _>(idx) {
-                        val
-                    } else if let Ok(Some(val)) = row.try_get::<Option<f64>, _>(idx) {
-                        val.to_string()
-                    } else if let Ok(val) = row.try_get::<f64, _>(idx) {
-                        val.to_string()
+            // String types
+            ,
+            "VARCHAR" | "CHAR" | "TEXT" | "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" | "ENUM"
+            | "SET" | "VAR_STRING" | "STRING" => match row.try_get::<Option<String>, _>(idx) {
+                Ok(Some(val)) => val,
+                Ok(None) => "NULL".to_string(),
+                Err(_) => {
+                    // Some drivers may expose these as bytes, try to decode
+                    if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
+                        bytes_to_string_or_marker(bytes)
+                    } else if let Ok(bytes) = row.try_get::<Vec<u8>, _>(idx) {
+                        bytes_to_string_or_marker(bytes)
                     } else {
                         get_value_as_string_fallback_idx(row, idx, column_name, &t)
                     }
                 }
+            },
 
-                // String types
-                ,
-                "VARCHAR" | "CHAR" | "TEXT" | "TINYTEXT" | "MEDIUMTEXT" | "LONGTEXT" | "ENUM"
-                | "SET" | "VAR_STRING" | "STRING" => match row.try_get::<Option<String>, _>(idx) {
-                    Ok(Some(val)) => val,
+            // Binary types
+            "BINARY" | "VARBINARY" | "BLOB" | "TINYBLOB" | "MEDIUMBLOB" | "LONGBLOB" => {
+                match row.try_get::<Option<Vec<u8>>, _>(idx) {
+                    Ok(Some(val)) => bytes_to_string_or_marker(val),
                     Ok(None) => "NULL".to_string(),
-                    Err(_) => {
-                        // Some drivers may expose these as bytes, try to decode
-                        if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
-                            bytes_to_string_or_marker(bytes)
-                        } else if let Ok(bytes) = row.try_get::<Vec<u8>, _>(idx) {
-                            bytes_to_string_or_marker(bytes)
-                        } else {
-                            get_value_as_string_fallback_idx(row, idx, column_name, &t)
-                        }
-                    }
-                },
-
-                // Binary types
-                "BINARY" | "VARBINARY" | "BLOB" | "TINYBLOB" | "MEDIUMBLOB" | "LONGBLOB" => {
-                    match row.try_get::<Option<Vec<u8>>, _>(idx) {
-                        Ok(Some(val)) => bytes_to_string_or_marker(val),
-                        Ok(None) => "NULL".to_string(),
-                        Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                    }
+                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
                 }
+            }
 
-                // Bit type (format as integer or bit-string)
-                "BIT" => {
-                    if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
-                        use std::fmt::Write as _;
-                        let mut s = String::with_capacity(bytes.len() * 8 + 2);
-                        s.push_str("0b");
-                        for b in bytes {
-                            let _ = write!(&mut s, "{:08b}", b);
-                        }
-                        s
-                    } else if let Ok(bytes) = row.try_get::<Vec<u8>, _>(idx) {
-                        use std::fmt::Write as _;
-                        let mut s = String::with_capacity(bytes.len() * 8 + 2);
-                        s.push_str("0b");
-                        for b in bytes {
-                            let _ = write!(&mut s, "{:08b}", b);
-                        }
-                        s
-                    } else if let Ok(Some(val)) = row.try_get::<Option<u64>, _>(idx) {
-                        format!("0b{:b}", val)
-                    } else {
-                        get_value_as_string_fallback_idx(row, idx, column_name, &t)
+            // Bit type (format as integer or bit-string)
+            "BIT" => {
+                if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
+                    use std::fmt::Write as _;
+                    let mut s = String::with_capacity(bytes.len() * 8 + 2);
+                    s.push_str("0b");
+                    for b in bytes {
+                        let _ = write!(&mut s, "{:08b}", b);
                     }
+                    s
+                } else if let Ok(bytes) = row.try_get::<Vec<u8>, _>(idx) {
+                    use std::fmt::Write as _;
+                    let mut s = String::with_capacity(bytes.len() * 8 + 2);
+                    s.push_str("0b");
+                    for b in bytes {
+                        let _ = write!(&mut s, "{:08b}", b);
+                    }
+                    s
+                } else if let Ok(Some(val)) = row.try_get::<Option<u64>, _>(idx) {
+                    format!("0b{:b}", val)
+                } else {
+                    get_value_as_string_fallback_idx(row, idx, column_name, &t)
                 }
+            }
 
-                // Date and time types
-                "DATE" => match row.try_get::<Option<chrono::NaiveDate>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
+            // Date and time types
+            "DATE" => match row.try_get::<Option<chrono::NaiveDate>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => match row.try_get::<Option<String>, _>(idx) {
+                    Ok(Some(val)) => val,
                     Ok(None) => "NULL".to_string(),
-                    Err(_) => match row.try_get::<Option<String>, _>(idx) {
-                        Ok(Some(val)) => val,
-                        Ok(None) => "NULL".to_string(),
-                        Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                    },
+                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
                 },
-                "TIME" => match row.try_get::<Option<chrono::NaiveTime>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
+            },
+            "TIME" => match row.try_get::<Option<chrono::NaiveTime>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => match row.try_get::<Option<String>, _>(idx) {
+                    Ok(Some(val)) => val,
                     Ok(None) => "NULL".to_string(),
-                    Err(_) => match row.try_get::<Option<String>, _>(idx) {
-                        Ok(Some(val)) => val,
-                        Ok(None) => "NULL".to_string(),
-                        Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                    },
+                    Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
                 },
-                "DATETIME" | "TIMESTAMP" => {
-                    // Try chrono::NaiveDateTime first
-                    if let Ok(Some(val)) = row.try_get::<Option<chrono::NaiveDateTime>, _>(idx) {
-                        val.to_string()
-                    } else if let Ok(Some(val)) =
-                        row.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>(idx)
-                    {
-                        val.to_rfc3339()
-                    } else if let Ok(Some(val)) = row.try_get::<Option<String>, _>(idx) {
-                        val
-                    } else if let Ok(val) = row.try_get::<String, _>(idx) {
-                        val
-                    } else if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
-                        bytes_to_string_or_marker(bytes)
-                    } else if let Ok(bytes) = row.try_get::<Vec<u8>, _>(idx) {
-                        bytes_to_string_or_marker(bytes)
-                    } else {
-                        get_value_as_string_fallback_idx(row, idx, column_name, &t)
-                    }
+            },
+            "DATETIME" | "TIMESTAMP" => {
+                // Try chrono::NaiveDateTime first
+                if let Ok(Some(val)) = row.try_get::<Option<chrono::NaiveDateTime>, _>(idx) {
+                    val.to_string()
+                } else if let Ok(Some(val)) =
+                    row.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>(idx)
+                {
+                    val.to_rfc3339()
+                } else if let Ok(Some(val)) = row.try_get::<Option<String>, _>(idx) {
+                    val
+                } else if let Ok(val) = row.try_get::<String, _>(idx) {
+                    val
+                } else if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
+                    bytes_to_string_or_marker(bytes)
+                } else if let Ok(bytes) = row.try_get::<Vec<u8>, _>(idx) {
+                    bytes_to_string_or_marker(bytes)
+                } else {
+                    get_value_as_string_fallback_idx(row, idx, column_name, &t)
                 }
-                "YEAR" => match row.try_get::<Option<i16>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
+            }
+            "YEAR" => match row.try_get::<Option<i16>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
+            },
+
+            // Boolean type
+            "BOOLEAN" | "BOOL" => match row.try_get::<Option<bool>, _>(idx) {
+                Ok(Some(val)) => val.to_string(),
+                Ok(None) => "NULL".to_string(),
+                Err(_) => match row.try_get::<Option<i8>, _>(idx) {
+                    Ok(Some(val)) => (val != 0).to_string(),
                     Ok(None) => "NULL".to_string(),
                     Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
                 },
+            },
+
+            // JSON type
+            "JSON" => {
+                if let Ok(Some(val)) = row.try_get::<Option<String>, _>(idx) {
+                    val
+                } else if let Ok(val) = row.try_get::<String, _>(idx) {
+                    val
+                } else if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
+                    bytes_to_string_or_marker(bytes)
+                } else if let Ok(bytes) = row.try_get::<Vec<u8>, _>(idx) {
+                    bytes_to_string_or_marker(bytes)
+                } else {
+                    get_value_as_string_fallback_idx(row, idx, column_name, &t)
+                }
+            }
 
-                // Boolean type
-                "BOOLEAN" | "BOOL" => match row.try_get::<Option<bool>, _>(idx) {
-                    Ok(Some(val)) => val.to_string(),
-                    Ok(None) => "NULL".to_string(),
-                    Err(_) => match row.try_get::<Option<i8>, _>(idx) {
-                        Ok(Some(val)) => (val != 0).to_string(),
-                        Ok(None) => "NULL".to_string(),
-                        Err(_) => get_value_as_string_fallback_idx(row, idx, column_name, &t),
-                    },
-                },
-
-                // JSON type
-                "JSON" => {
-                    if let Ok(Some(val)) = row.try_get::<Option<String>, _>(idx) {
-                        val
-                    } else if let Ok(val) = row.try_get::<String, _>(idx) {
-                        val
-                    } else if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
+            // Default
+            _ => match row.try_get::<Option<String>, _>(idx) {
+                Ok(Some(val)) => val,
+                Ok(None) => "NULL".to_string(),
+                Err(_) => {
+                    // If not directly convertible to String, try bytes -> text
+                    if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
                         bytes_to_string_or_marker(bytes)
                     } else if let Ok(bytes) = row.try_get::<Vec<u8>, _>(idx) {
                         bytes_to_string_or_marker(bytes)
@@ -406,30 +423,12 @@ pub(crate) fn convert_mysql_rows_to_table_data(
                         get_value_as_string_fallback_idx(row, idx, column_name, &t)
                     }
                 }
+            },
+        };
 
-                // Default
-                _ => match row.try_get::<Option<String>, _>(idx) {
-                    Ok(Some(val)) => val,
-                    Ok(None) => "NULL".to_string(),
-                    Err(_) => {
-                        // If not directly convertible to String, try bytes -> text
-                        if let Ok(Some(bytes)) = row.try_get::<Option<Vec<u8>>, _>(idx) {
-                            bytes_to_string_or_marker(bytes)
-                        } else if let Ok(bytes) = row.try_get::<Vec<u8>, _>(idx) {
-                            bytes_to_string_or_marker(bytes)
-                        } else {
-                            get_value_as_string_fallback_idx(row, idx, column_name, &t)
-                        }
-                    }
-                },
-            };
-
-            row_data.push(value_str);
-        }
-        table_data.push(row_data);
+        row_data.push(value_str);
     }
-
-    table_data
+    row_data
 }
 
 #[allow(dead_code)]
@@ -891,6 +890,178 @@ pub(crate) async fn fetch_mysql_columns(
     Ok(columns_map)
 }
 
+/// Enum definition, CHECK clauses and foreign key target of one column.
+/// CHECK constraints need MySQL 8.0.16+; older servers just report none.
+pub(crate) async fn fetch_mysql_column_constraints(
+    pool: &MySqlPool,
+    database_name: &str,
+    table_name: &str,
+    column_name: &str,
+) -> Result<models::structs::ColumnConstraints, sqlx::Error> {
+    let mut out = models::structs::ColumnConstraints {
+        type_definition: sqlx::query_scalar::<_, String>(
+            "SELECT CAST(COLUMN_TYPE AS CHAR) FROM INFORMATION_SCHEMA.COLUMNS \
+             WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND COLUMN_NAME = ?",
+        )
+        .bind(database_name)
+        .bind(table_name)
+        .bind(column_name)
+        .fetch_optional(pool)
+        .await?,
+        ..Default::default()
+    };
+
+    let checks = sqlx::query_scalar::<_, String>(
+        "SELECT CAST(cc.CHECK_CLAUSE AS CHAR) \
+         FROM INFORMATION_SCHEMA.TABLE_CONSTRAINTS tc \
+         JOIN INFORMATION_SCHEMA.CHECK_CONSTRAINTS cc \
+           ON cc.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA AND cc.CONSTRAINT_NAME = tc.CONSTRAINT_NAME \
+         WHERE tc.TABLE_SCHEMA = ? AND tc.TABLE_NAME = ? AND tc.CONSTRAINT_TYPE = 'CHECK'",
+    )
+    .bind(database_name)
+    .bind(table_name)
+    .fetch_all(pool)
+    .await;
+    match checks {
+        // CHECK_CLAUSE shows literals with backslash-escaped quotes
+        Ok(clauses) => {
+            out.check_clauses = clauses.into_iter().map(|c| c.replace("\\'", "'")).collect()
+        }
+        Err(e) => debug!("MySQL CHECK constraints unavailable: {}", e),
+    }
+
+    out.references = sqlx::query_as::<_, (String, String)>(
+        "SELECT CAST(REFERENCED_TABLE_NAME AS CHAR), CAST(REFERENCED_COLUMN_NAME AS CHAR) \
+         FROM INFORMATION_SCHEMA.KEY_COLUMN_USAGE \
+         WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ? AND COLUMN_NAME = ? \
+           AND REFERENCED_TABLE_NAME IS NOT NULL LIMIT 1",
+    )
+    .bind(database_name)
+    .bind(table_name)
+    .bind(column_name)
+    .fetch_optional(pool)
+    .await?;
+    Ok(out)
+}
+
+/// Up to `limit` distinct non-null values of `table.column`, sorted.
+pub(crate) async fn fetch_mysql_distinct_values(
+    pool: &MySqlPool,
+    database_name: &str,
+    table_name: &str,
+    column_name: &str,
+    limit: usize,
+) -> Result<Vec<String>, sqlx::Error> {
+    let quote = |s: &str| format!("`{}`", s.replace('`', "``"));
+    let sql = format!(
+        "SELECT DISTINCT CAST({col} AS CHAR) FROM {db}.{tbl} WHERE {col} IS NOT NULL ORDER BY 1 LIMIT {limit}",
+        col = quote(column_name),
+        db = quote(database_name),
+        tbl = quote(table_name),
+    );
+    sqlx::query_scalar::<_, String>(sqlx::AssertSqlSafe(sql))
+        .fetch_all(pool)
+        .await
+}
+
+/// Parameters of a stored procedure or function, in declaration order.
+pub(crate) async fn fetch_mysql_routine_parameters(
+    pool: &MySqlPool,
+    database_name: &str,
+    routine_name: &str,
+    kind: models::structs::RoutineKind,
+) -> Result<Vec<models::structs::RoutineParameter>, sqlx::Error> {
+    let routine_type = match kind {
+        models::structs::RoutineKind::Procedure => "PROCEDURE",
+        models::structs::RoutineKind::Function => "FUNCTION",
+    };
+    let rows: Vec<(Option<String>, Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT CAST(PARAMETER_NAME AS CHAR), CAST(DTD_IDENTIFIER AS CHAR), CAST(PARAMETER_MODE AS CHAR) \
+         FROM information_schema.PARAMETERS \
+         WHERE SPECIFIC_SCHEMA = ? AND SPECIFIC_NAME = ? AND ROUTINE_TYPE = ? AND ORDINAL_POSITION > 0 \
+         ORDER BY ORDINAL_POSITION",
+    )
+    .bind(database_name)
+    .bind(routine_name)
+    .bind(routine_type)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(name, data_type, mode)| models::structs::RoutineParameter {
+            name: name.unwrap_or_default(),
+            data_type: data_type.unwrap_or_default(),
+            mode: match mode.as_deref() {
+                Some("OUT") => models::structs::ParameterMode::Out,
+                Some("INOUT") => models::structs::ParameterMode::InOut,
+                _ => models::structs::ParameterMode::In,
+            },
+        })
+        .collect())
+}
+
+/// Run the statements of a routine call in order on one connection, so
+/// session variables set for OUT parameters survive until they are read.
+/// Returns the last result set with rows, read through every result set a
+/// CALL produces, and the rows affected along the way.
+pub(crate) async fn call_mysql_routine(
+    pool: &MySqlPool,
+    statements: &[(String, Vec<Option<String>>)],
+) -> Result<(Vec<String>, Vec<Vec<String>>, u64), sqlx::Error> {
+    use futures_util::TryStreamExt;
+    use sqlx::Executor;
+    use sqlx::Either;
+
+    let mut conn = pool.acquire().await?;
+    let mut result = (Vec::new(), Vec::new());
+    let mut affected = 0;
+    for (sql, values) in statements {
+        let mut query = sqlx::query(sqlx::AssertSqlSafe(sql.as_str()));
+        for value in values {
+            query = query.bind(value.clone());
+        }
+        let mut set: Vec<sqlx::mysql::MySqlRow> = Vec::new();
+        let mut stream = (&mut *conn).fetch_many(query);
+        while let Some(item) = stream.try_next().await? {
+            match item {
+                Either::Left(done) => {
+                    affected += done.rows_affected();
+                    if let Some(first) = set.first() {
+                        let headers =
+                            first.columns().iter().map(|c| c.name().to_string()).collect();
+                        let rows = convert_mysql_rows_to_table_data(std::mem::take(&mut set));
+                        result = (headers, rows);
+                    }
+                }
+                Either::Right(row) => set.push(row),
+            }
+        }
+    }
+    Ok((result.0, result.1, affected))
+}
+
+/// Row estimate and data + index size from `information_schema.TABLES`.
+pub(crate) async fn fetch_mysql_table_stats(
+    pool: &MySqlPool,
+    database_name: &str,
+    table_name: &str,
+) -> Result<models::structs::TableStats, sqlx::Error> {
+    let row: Option<(Option<u64>, Option<u64>)> = sqlx::query_as(
+        "SELECT CAST(TABLE_ROWS AS UNSIGNED), CAST(DATA_LENGTH + INDEX_LENGTH AS UNSIGNED) \
+         FROM information_schema.TABLES WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?",
+    )
+    .bind(database_name)
+    .bind(table_name)
+    .fetch_optional(pool)
+    .await?;
+    let (rows, bytes) = row.unwrap_or_default();
+    Ok(models::structs::TableStats {
+        rows,
+        rows_capped: false,
+        bytes,
+    })
+}
+
 // Check if the connection is a replica (slave)
 pub(crate) async fn check_replication_status(
     pool: &sqlx::MySqlPool,
@@ -1,5 +1,10 @@
-use sqlx::postgres::PgPoolOptions;
-use sqlx::{PgPool, Row, SqlitePool};
+use sqlx::postgres::types::PgRecordDecoder;
+use sqlx::postgres::{
+    PgConnectOptions, PgListener, PgPoolOptions, PgRow, PgTypeInfo, PgTypeKind,
+};
+use sqlx::{Column, PgPool, Row, SqlitePool, TypeInfo, ValueRef};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
 
 use crate::{models, window_egui};
 
@@ -243,6 +248,173 @@ pub(crate) async fn fetch_postgres_columns(
     Ok(map)
 }
 
+/// Enum labels (also through a domain), CHECK clauses (table and domain) and
+/// foreign key target of one column. `table_name` may be schema-qualified.
+pub(crate) async fn fetch_postgres_column_constraints(
+    pool: &PgPool,
+    table_name: &str,
+    column_name: &str,
+) -> Result<models::structs::ColumnConstraints, sqlx::Error> {
+    let enum_values = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT e.enumlabel::text
+        FROM pg_attribute a
+        JOIN pg_type t ON t.oid = a.atttypid
+        JOIN pg_enum e ON e.enumtypid = CASE WHEN t.typtype = 'd' THEN t.typbasetype ELSE t.oid END
+        WHERE a.attrelid = to_regclass($1) AND a.attname = $2
+        ORDER BY e.enumsortorder
+    "#,
+    )
+    .bind(table_name)
+    .bind(column_name)
+    .fetch_all(pool)
+    .await?;
+
+    let check_clauses = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT pg_get_constraintdef(c.oid)
+        FROM pg_constraint c
+        JOIN pg_attribute a ON a.attrelid = c.conrelid AND a.attnum = ANY (c.conkey)
+        WHERE c.conrelid = to_regclass($1) AND c.contype = 'c' AND a.attname = $2
+        UNION ALL
+        SELECT pg_get_constraintdef(c.oid)
+        FROM pg_attribute a
+        JOIN pg_constraint c ON c.contypid = a.atttypid AND c.contype = 'c'
+        WHERE a.attrelid = to_regclass($1) AND a.attname = $2
+    "#,
+    )
+    .bind(table_name)
+    .bind(column_name)
+    .fetch_all(pool)
+    .await?;
+
+    let references = sqlx::query_as::<_, (String, String)>(
+        r#"
+        SELECT c.confrelid::regclass::text, fa.attname::text
+        FROM pg_constraint c
+        JOIN pg_attribute a ON a.attrelid = c.conrelid AND a.attnum = c.conkey[1]
+        JOIN pg_attribute fa ON fa.attrelid = c.confrelid AND fa.attnum = c.confkey[1]
+        WHERE c.conrelid = to_regclass($1) AND c.contype = 'f'
+          AND array_length(c.conkey, 1) = 1 AND a.attname = $2
+        LIMIT 1
+    "#,
+    )
+    .bind(table_name)
+    .bind(column_name)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(models::structs::ColumnConstraints {
+        type_definition: None,
+        enum_values,
+        check_clauses,
+        references,
+    })
+}
+
+/// Up to `limit` distinct non-null values of `table.column`, sorted.
+/// `table_name` is used verbatim, as returned by `regclass::text`.
+pub(crate) async fn fetch_postgres_distinct_values(
+    pool: &PgPool,
+    table_name: &str,
+    column_name: &str,
+    limit: usize,
+) -> Result<Vec<String>, sqlx::Error> {
+    let col = format!("\"{}\"", column_name.replace('"', "\"\""));
+    let sql = format!(
+        "SELECT DISTINCT {col}::text FROM {table_name} WHERE {col} IS NOT NULL ORDER BY 1 LIMIT {limit}"
+    );
+    sqlx::query_scalar::<_, String>(sqlx::AssertSqlSafe(sql))
+        .fetch_all(pool)
+        .await
+}
+
+/// Parameters of a function or procedure from `pg_proc`, in declaration
+/// order. Overloads are not distinguished; the oldest one is used.
+pub(crate) async fn fetch_postgres_routine_parameters(
+    pool: &PgPool,
+    schema: &str,
+    routine_name: &str,
+) -> Result<Vec<models::structs::RoutineParameter>, sqlx::Error> {
+    let rows: Vec<(String, String, String)> = sqlx::query_as(
+        "SELECT COALESCE(p.proargnames[a.n::int], ''), format_type(a.typ, NULL), \
+                COALESCE(p.proargmodes[a.n::int]::text, 'i') \
+         FROM pg_proc p \
+         CROSS JOIN LATERAL unnest(COALESCE(p.proallargtypes, p.proargtypes::oid[])) WITH ORDINALITY AS a(typ, n) \
+         WHERE p.oid = (SELECT p2.oid FROM pg_proc p2 JOIN pg_namespace ns ON ns.oid = p2.pronamespace \
+                        WHERE ns.nspname = $1 AND p2.proname = $2 ORDER BY p2.oid LIMIT 1) \
+         ORDER BY a.n",
+    )
+    .bind(schema)
+    .bind(routine_name)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows
+        .into_iter()
+        .map(|(name, data_type, mode)| models::structs::RoutineParameter {
+            name,
+            data_type,
+            // 't' marks a RETURNS TABLE column, which is output only.
+            mode: match mode.as_str() {
+                "o" | "t" => models::structs::ParameterMode::Out,
+                "b" => models::structs::ParameterMode::InOut,
+                _ => models::structs::ParameterMode::In,
+            },
+        })
+        .collect())
+}
+
+/// Run a routine call with its values bound as text (the statement casts
+/// them to the parameter types) and collect the rows it returns.
+pub(crate) async fn call_postgres_routine(
+    pool: &PgPool,
+    sql: &str,
+    values: &[Option<String>],
+) -> Result<(Vec<String>, Vec<Vec<String>>), sqlx::Error> {
+    let mut query = sqlx::query(sqlx::AssertSqlSafe(sql));
+    for value in values {
+        query = query.bind(value.clone());
+    }
+    let rows = query.fetch_all(pool).await?;
+    let headers = rows
+        .first()
+        .map(|r| r.columns().iter().map(|c| c.name().to_string()).collect())
+        .unwrap_or_default();
+    let data = rows
+        .iter()
+        .map(|row| (0..row.len()).map(|i| pg_cell_to_string(row, i)).collect())
+        .collect();
+    Ok((headers, data))
+}
+
+/// Planner row estimate (`reltuples`, unknown before the first ANALYZE) and
+/// total relation size including indexes and TOAST.
+pub(crate) async fn fetch_postgres_table_stats(
+    pool: &PgPool,
+    table_name: &str,
+) -> Result<models::structs::TableStats, sqlx::Error> {
+    let quote = |s: &str| format!("\"{}\"", s.replace('"', "\"\""));
+    let regclass = match table_name.split_once('.') {
+        Some((schema, table)) => format!("{}.{}", quote(schema), quote(table)),
+        None => quote(table_name),
+    };
+    let row: Option<(f64, i64)> = sqlx::query_as(
+        "SELECT c.reltuples::float8, pg_total_relation_size(c.oid)::int8 \
+         FROM pg_class c WHERE c.oid = to_regclass($1)",
+    )
+    .bind(regclass)
+    .fetch_optional(pool)
+    .await?;
+    Ok(match row {
+        Some((reltuples, bytes)) => models::structs::TableStats {
+            rows: (reltuples >= 0.0).then_some(reltuples as u64),
+            rows_capped: false,
+            bytes: u64::try_from(bytes).ok(),
+        },
+        None => models::structs::TableStats::default(),
+    })
+}
+
 // Fetch tables/views from a PostgreSQL database (schema: public)
 pub(crate) fn fetch_tables_from_postgres_connection(
     tabular: &mut window_egui::Tabular,
@@ -273,6 +445,8 @@ pub(crate) fn fetch_tables_from_postgres_connection(
               let sql = match table_type {
                      "table" => "SELECT table_name FROM information_schema.tables WHERE table_schema = 'public' AND table_type = 'BASE TABLE' ORDER BY table_name",
                      "view" => "SELECT table_name FROM information_schema.views WHERE table_schema = 'public' ORDER BY table_name",
+                     "procedure" => "SELECT DISTINCT routine_name::text FROM information_schema.routines WHERE routine_schema = 'public' AND routine_type = 'PROCEDURE' ORDER BY 1",
+                     "function" => "SELECT DISTINCT routine_name::text FROM information_schema.routines WHERE routine_schema = 'public' AND routine_type = 'FUNCTION' ORDER BY 1",
                      _ => return None,
               };
 
@@ -289,3 +463,457 @@ pub(crate) fn fetch_tables_from_postgres_connection(
               }
        })
 }
+
+/// Grid text for column `idx` of a PostgreSQL row. JSON/JSONB values come
+/// back as compact JSON text rather than `[unsupported]`.
+pub(crate) fn pg_cell_to_string(row: &PgRow, idx: usize) -> String {
+    match row.try_get::<Option<String>, _>(idx) {
+        Ok(Some(v)) => v,
+        Ok(None) => "NULL".to_string(),
+        Err(_) => {
+            if let Ok(val) = row.try_get::<i64, _>(idx) {
+                val.to_string()
+            } else if let Ok(val) = row.try_get::<f64, _>(idx) {
+                val.to_string()
+            } else if let Ok(val) = row.try_get::<bool, _>(idx) {
+                val.to_string()
+            } else if let Ok(val) = row.try_get::<Option<serde_json::Value>, _>(idx) {
+                val.map(|v| v.to_string()).unwrap_or_else(|| "NULL".to_string())
+            } else if let Some(literal) = pg_structured_to_string(row, idx) {
+                literal
+            } else {
+                "[unsupported]".to_string()
+            }
+        }
+    }
+}
+
+fn array_literal<T: ToString>(items: Vec<Option<T>>) -> String {
+    format_pg_array(
+        &items
+            .into_iter()
+            .map(|v| v.map(|v| v.to_string()))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Text of one field of a composite value; `None` for field types the grid
+/// cannot show, in which case the whole value is left unsupported.
+fn record_field_to_string(
+    decoder: &mut PgRecordDecoder<'_>,
+    ty: &PgTypeInfo,
+) -> Option<Option<String>> {
+    fn text<T: ToString>(v: Option<T>) -> Option<String> {
+        v.map(|v| v.to_string())
+    }
+    Some(match ty.name() {
+        "TEXT" | "VARCHAR" | "BPCHAR" | "NAME" | "CHAR" => {
+            decoder.try_decode::<Option<String>>().ok()?
+        }
+        "INT2" => text(decoder.try_decode::<Option<i16>>().ok()?),
+        "INT4" => text(decoder.try_decode::<Option<i32>>().ok()?),
+        "INT8" => text(decoder.try_decode::<Option<i64>>().ok()?),
+        "FLOAT4" => text(decoder.try_decode::<Option<f32>>().ok()?),
+        "FLOAT8" => text(decoder.try_decode::<Option<f64>>().ok()?),
+        "BOOL" => decoder
+            .try_decode::<Option<bool>>()
+            .ok()?
+            .map(|b| if b { "t" } else { "f" }.to_string()),
+        "NUMERIC" => text(decoder.try_decode::<Option<rust_decimal::Decimal>>().ok()?),
+        "DATE" => text(decoder.try_decode::<Option<chrono::NaiveDate>>().ok()?),
+        "TIMESTAMP" => text(decoder.try_decode::<Option<chrono::NaiveDateTime>>().ok()?),
+        "TIMESTAMPTZ" => text(
+            decoder
+                .try_decode::<Option<chrono::DateTime<chrono::Utc>>>()
+                .ok()?,
+        ),
+        _ => return None,
+    })
+}
+
+/// Array and composite values, rendered in PostgreSQL's own literal syntax
+/// (`{a,"b c"}`, `(1,"x y")`) so an edited value can be written back as is.
+/// Only one-dimensional arrays of common element types are decoded.
+fn pg_structured_to_string(row: &PgRow, idx: usize) -> Option<String> {
+    let ty = row.columns().get(idx)?.type_info().clone();
+    match ty.kind() {
+        PgTypeKind::Array(_) => {
+            macro_rules! try_array {
+                ($($t:ty),*) => {$(
+                    if let Ok(v) = row.try_get::<Option<Vec<Option<$t>>>, _>(idx) {
+                        return Some(v.map_or_else(|| "NULL".to_string(), array_literal));
+                    }
+                )*};
+            }
+            try_array!(String, i16, i32, i64, f32, f64, rust_decimal::Decimal);
+            let bools = row.try_get::<Option<Vec<Option<bool>>>, _>(idx).ok()?;
+            Some(bools.map_or_else(
+                || "NULL".to_string(),
+                |items| {
+                    array_literal(
+                        items
+                            .into_iter()
+                            .map(|b| b.map(|b| if b { "t" } else { "f" }))
+                            .collect(),
+                    )
+                },
+            ))
+        }
+        PgTypeKind::Composite(fields) => {
+            let raw = row.try_get_raw(idx).ok()?;
+            if raw.is_null() {
+                return Some("NULL".to_string());
+            }
+            let mut decoder = PgRecordDecoder::new(raw).ok()?;
+            let values = fields
+                .iter()
+                .map(|(_, field_ty)| record_field_to_string(&mut decoder, field_ty))
+                .collect::<Option<Vec<_>>>()?;
+            Some(format_pg_record(&values))
+        }
+        _ => None,
+    }
+}
+
+/// Array or composite shape of a column type, for the grid's element editor.
+pub(crate) fn pg_value_kind(ty: &PgTypeInfo) -> Option<models::structs::PgValueKind> {
+    match ty.kind() {
+        PgTypeKind::Array(_) => Some(models::structs::PgValueKind::Array),
+        PgTypeKind::Composite(fields) => Some(models::structs::PgValueKind::Composite(
+            fields.iter().map(|(name, _)| name.clone()).collect(),
+        )),
+        _ => None,
+    }
+}
+
+/// Whether an array element or record field has to be double-quoted.
+fn needs_quotes(value: &str, specials: &[char]) -> bool {
+    value.is_empty()
+        || value.eq_ignore_ascii_case("NULL")
+        || value
+            .chars()
+            .any(|c| c.is_whitespace() || c == '"' || c == '\\' || specials.contains(&c))
+}
+
+fn quote_element(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// One-dimensional array literal, e.g. `{a,NULL,"b c"}`.
+pub(crate) fn format_pg_array(items: &[Option<String>]) -> String {
+    let parts: Vec<String> = items
+        .iter()
+        .map(|item| match item {
+            None => "NULL".to_string(),
+            Some(v) if needs_quotes(v, &['{', '}', ',']) => quote_element(v),
+            Some(v) => v.clone(),
+        })
+        .collect();
+    format!("{{{}}}", parts.join(","))
+}
+
+/// Composite literal, e.g. `(1,,"b c")`; an empty field is NULL.
+pub(crate) fn format_pg_record(fields: &[Option<String>]) -> String {
+    let parts: Vec<String> = fields
+        .iter()
+        .map(|field| match field {
+            None => String::new(),
+            Some(v) if needs_quotes(v, &['(', ')', ',']) => quote_element(v),
+            Some(v) => v.clone(),
+        })
+        .collect();
+    format!("({})", parts.join(","))
+}
+
+/// Split the inside of an array or record literal into elements. `None` for
+/// anything not understood (nested arrays, stray quotes).
+fn split_literal(inner: &str, array: bool) -> Option<Vec<Option<String>>> {
+    let chars: Vec<char> = inner.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+    loop {
+        let mut value = String::new();
+        let mut quoted = false;
+        while i < chars.len() && chars[i] != ',' {
+            match chars[i] {
+                '"' => {
+                    quoted = true;
+                    i += 1;
+                    loop {
+                        match chars.get(i)? {
+                            '\\' => {
+                                value.push(*chars.get(i + 1)?);
+                                i += 2;
+                            }
+                            // Records may also escape a quote by doubling it
+                            '"' if !array && chars.get(i + 1) == Some(&'"') => {
+                                value.push('"');
+                                i += 2;
+                            }
+                            '"' => {
+                                i += 1;
+                                break;
+                            }
+                            c => {
+                                value.push(*c);
+                                i += 1;
+                            }
+                        }
+                    }
+                }
+                '{' | '}' if array => return None,
+                '\\' => {
+                    value.push(*chars.get(i + 1)?);
+                    i += 2;
+                }
+                c => {
+                    value.push(c);
+                    i += 1;
+                }
+            }
+        }
+        let item = if quoted {
+            Some(value)
+        } else if array {
+            let trimmed = value.trim();
+            (!trimmed.eq_ignore_ascii_case("NULL")).then(|| trimmed.to_string())
+        } else {
+            (!value.is_empty()).then_some(value)
+        };
+        out.push(item);
+        if i >= chars.len() {
+            return Some(out);
+        }
+        i += 1;
+    }
+}
+
+/// Elements of a one-dimensional array literal; `None` for multi-dimensional
+/// arrays, arrays with explicit bounds (`[0:1]={...}`) and malformed text.
+pub(crate) fn parse_pg_array(text: &str) -> Option<Vec<Option<String>>> {
+    let inner = text.trim().strip_prefix('{')?.strip_suffix('}')?;
+    if inner.trim().is_empty() {
+        return Some(Vec::new());
+    }
+    split_literal(inner, true)
+}
+
+/// Fields of a composite literal.
+pub(crate) fn parse_pg_record(text: &str) -> Option<Vec<Option<String>>> {
+    let inner = text.trim().strip_prefix('(')?.strip_suffix(')')?;
+    split_literal(inner, false)
+}
+
+/// Column names and type names (`JSONB`, `INT4`, ...) of a result row, so the
+/// grid can treat JSON columns specially. `table_name` is only set when the
+/// statement reads from a single table.
+pub(crate) fn pg_column_metadata(
+    row: &PgRow,
+    table_name: Option<&str>,
+) -> Vec<models::structs::ColumnMetadata> {
+    row.columns()
+        .iter()
+        .map(|col| models::structs::ColumnMetadata {
+            name: col.name().to_string(),
+            type_name: col.type_info().name().to_string(),
+            table_name: table_name.map(str::to_string),
+            original_name: Some(col.name().to_string()),
+            is_primary_key: false,
+            pg_kind: pg_value_kind(col.type_info()),
+        })
+        .collect()
+}
+
+/// Split the panel's channel field ("orders, audit jobs") into distinct names.
+pub(crate) fn parse_listen_channels(input: &str) -> Vec<String> {
+    let mut channels: Vec<String> = Vec::new();
+    for name in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        let name = name.trim();
+        if !name.is_empty() && !channels.iter().any(|c| c == name) {
+            channels.push(name.to_string());
+        }
+    }
+    channels
+}
+
+/// Connect options for a dedicated LISTEN session, resolving SSH tunnels the
+/// same way the query pool does.
+pub(crate) fn listen_connect_options(
+    connection: &models::structs::ConnectionConfig,
+) -> Result<PgConnectOptions, String> {
+    let (host, port) = crate::connection::resolve_connection_target(connection)?;
+    let mut options = PgConnectOptions::new()
+        .host(&host)
+        .port(port.parse().unwrap_or(5432))
+        .username(&connection.username)
+        .password(&connection.password);
+    if !connection.database.is_empty() {
+        options = options.database(&connection.database);
+    }
+    Ok(options)
+}
+
+/// Hold a LISTEN connection open on its own thread and forward every NOTIFY
+/// to `sender` until `stop` is set or the receiver goes away. The connection
+/// is separate from the query pool; when it drops, sqlx reconnects and
+/// re-subscribes to all channels before the next receive.
+pub(crate) fn spawn_notify_listener(
+    options: PgConnectOptions,
+    channels: Vec<String>,
+    sender: mpsc::Sender<models::enums::PgListenMessage>,
+    stop: Arc<AtomicBool>,
+) {
+    use models::enums::PgListenMessage;
+
+    std::thread::spawn(move || {
+        let rt = match tokio::runtime::Runtime::new() {
+            Ok(rt) => rt,
+            Err(e) => {
+                let _ = sender.send(PgListenMessage::Status(format!("Runtime error: {}", e)));
+                return;
+            }
+        };
+        rt.block_on(async move {
+            let poll = std::time::Duration::from_millis(250);
+            let retry = std::time::Duration::from_secs(3);
+
+            // Sleep in short slices so Stop takes effect quickly.
+            let wait_unless_stopped = |total: std::time::Duration| {
+                let stop = stop.clone();
+                async move {
+                    let mut waited = std::time::Duration::ZERO;
+                    while waited < total && !stop.load(Ordering::Relaxed) {
+                        tokio::time::sleep(poll).await;
+                        waited += poll;
+                    }
+                }
+            };
+
+            // Initial connect, retried until it succeeds or the user stops.
+            let (_pool, mut listener) = loop {
+                if stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                let attempt = async {
+                    let pool = PgPoolOptions::new()
+                        .max_connections(1)
+                        .acquire_timeout(std::time::Duration::from_secs(10))
+                        .connect_with(options.clone())
+                        .await?;
+                    let mut listener = PgListener::connect_with(&pool).await?;
+                    listener
+                        .listen_all(channels.iter().map(String::as_str))
+                        .await?;
+                    Ok::<_, sqlx::Error>((pool, listener))
+                };
+                match attempt.await {
+                    Ok(pair) => break pair,
+                    Err(e) => {
+                        if sender
+                            .send(PgListenMessage::Status(format!(
+                                "Connect failed: {} — retrying",
+                                e
+                            )))
+                            .is_err()
+                        {
+                            return;
+                        }
+                        wait_unless_stopped(retry).await;
+                    }
+                }
+            };
+            let _ = sender.send(PgListenMessage::Status(format!(
+                "Listening on {}",
+                channels.join(", ")
+            )));
+
+            loop {
+                // Keep the receive future alive across stop checks; dropping it
+                // mid-read could lose a notification.
+                let outcome = {
+                    let recv = listener.try_recv();
+                    tokio::pin!(recv);
+                    loop {
+                        tokio::select! {
+                            r = &mut recv => break Some(r),
+                            _ = tokio::time::sleep(poll) => {
+                                if stop.load(Ordering::Relaxed) {
+                                    break None;
+                                }
+                            }
+                        }
+                    }
+                };
+                let message = match outcome {
+                    None => break,
+                    Some(Ok(Some(n))) => PgListenMessage::Notification(models::structs::PgNotifyEvent {
+                        received_at: chrono::Local::now(),
+                        channel: n.channel().to_string(),
+                        payload: n.payload().to_string(),
+                    }),
+                    Some(Ok(None)) => PgListenMessage::Status(format!(
+                        "Connection dropped — reconnected, listening on {}",
+                        channels.join(", ")
+                    )),
+                    Some(Err(e)) => {
+                        let status = PgListenMessage::Status(format!("Listen error: {} — retrying", e));
+                        if sender.send(status).is_err() {
+                            break;
+                        }
+                        wait_unless_stopped(retry).await;
+                        continue;
+                    }
+                };
+                if sender.send(message).is_err() {
+                    break;
+                }
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listen_channels_are_split_and_deduplicated() {
+        assert_eq!(
+            parse_listen_channels(" orders, audit  jobs,orders ,, "),
+            vec!["orders", "audit", "jobs"]
+        );
+        assert!(parse_listen_channels(" , ").is_empty());
+    }
+
+    #[test]
+    fn array_and_record_literals_round_trip() {
+        let items = vec![
+            Some("plain".to_string()),
+            None,
+            Some("NULL".to_string()),
+            Some(String::new()),
+            Some(r#"a, "b" {c}\"#.to_string()),
+        ];
+        let literal = format_pg_array(&items);
+        assert_eq!(literal, r#"{plain,NULL,"NULL","","a, \"b\" {c}\\"}"#);
+        assert_eq!(parse_pg_array(&literal), Some(items));
+        assert_eq!(parse_pg_array("{}"), Some(Vec::new()));
+        assert_eq!(
+            parse_pg_array("{1, 2 ,null}"),
+            Some(vec![Some("1".into()), Some("2".into()), None])
+        );
+        // Multi-dimensional arrays and explicit bounds fall back to raw text
+        assert_eq!(parse_pg_array("{{1,2},{3,4}}"), None);
+        assert_eq!(parse_pg_array("[0:1]={1,2}"), None);
+        assert_eq!(parse_pg_array(r#"{"open}"#), None);
+
+        let fields = vec![Some("1".to_string()), None, Some("x (y)".to_string())];
+        let literal = format_pg_record(&fields);
+        assert_eq!(literal, r#"(1,,"x (y)")"#);
+        assert_eq!(parse_pg_record(&literal), Some(fields));
+        assert_eq!(
+            parse_pg_record(r#"(a,"say ""hi""")"#),
+            Some(vec![Some("a".into()), Some(r#"say "hi""#.into())])
+        );
+    }
+}
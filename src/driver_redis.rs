@@ -22,12 +22,139 @@ fn default_redis_keyspace(connection: &models::structs::ConnectionConfig) -> Str
     }
 }
 
-fn format_ttl_label(ttl: i64) -> String {
+/// `TTL` reply as shown in the browser: "in 2h 5m", "No expiry", "Expired".
+pub(crate) fn format_ttl_label(ttl: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
     match ttl {
         -2 => "Expired".to_string(),
-        -1 => "No limit".to_string(),
-        value if value >= 0 => format!("{} s", value),
-        _ => "Unknown".to_string(),
+        -1 => "No expiry".to_string(),
+        value if value < 0 => "Unknown".to_string(),
+        value if value < MINUTE => format!("in {}s", value),
+        value if value < HOUR => format!("in {}m", value / MINUTE),
+        value if value < DAY => match (value % HOUR) / MINUTE {
+            0 => format!("in {}h", value / HOUR),
+            minutes => format!("in {}h {}m", value / HOUR, minutes),
+        },
+        value => match (value % DAY) / HOUR {
+            0 => format!("in {}d", value / DAY),
+            hours => format!("in {}d {}h", value / DAY, hours),
+        },
+    }
+}
+
+/// Browser list entry for a scanned key. `ttl` is its `TTL` reply when it was
+/// fetched; otherwise the list shows the keyspace ("Cluster" for a cluster).
+pub(crate) fn browser_key_entry(
+    key_name: String,
+    key_type: String,
+    ttl: Option<i64>,
+    keyspace_label: &str,
+) -> models::structs::RedisBrowserKeyEntry {
+    let ttl_label = match ttl {
+        Some(ttl) => format_ttl_label(ttl),
+        None if keyspace_label == REDIS_CLUSTER_KEYSPACE => "Cluster".to_string(),
+        None => keyspace_label.to_string(),
+    };
+    models::structs::RedisBrowserKeyEntry {
+        key_name,
+        key_type,
+        ttl_label,
+        size_label: "-".to_string(),
+        ttl_seconds: ttl,
+    }
+}
+
+/// `TTL` of each of `keys` on a standalone keyspace, pipelined in batches.
+/// Keys whose batch failed are left out.
+pub(crate) async fn fetch_key_ttls(
+    connection: &models::structs::ConnectionConfig,
+    database_name: &str,
+    keys: &[String],
+) -> std::collections::HashMap<String, i64> {
+    let mut ttls = std::collections::HashMap::new();
+    if keys.is_empty() || database_name == REDIS_CLUSTER_KEYSPACE {
+        return ttls;
+    }
+    let mut conn = match create_redis_manager_for_target(connection, database_name, None).await {
+        Ok(conn) => conn,
+        Err(error) => {
+            warn!("[redis_ttl] failed creating manager for {}: {}", database_name, error);
+            return ttls;
+        }
+    };
+    for batch in keys.chunks(200) {
+        let mut pipe = redis::pipe();
+        for key in batch {
+            pipe.cmd("TTL").arg(key);
+        }
+        match pipe.query_async::<Vec<i64>>(&mut conn).await {
+            Ok(values) => ttls.extend(batch.iter().cloned().zip(values)),
+            Err(error) => warn!("[redis_ttl] TTL pipeline failed: {}", error),
+        }
+    }
+    ttls
+}
+
+/// Run a confirmed key mutation from the browser. Returns the message to show.
+pub(crate) fn apply_redis_key_edit(
+    tabular: &mut window_egui::Tabular,
+    connection_id: i64,
+    database_name: &str,
+    key_name: &str,
+    edit: &models::structs::RedisKeyEdit,
+) -> Result<String, String> {
+    use models::structs::RedisKeyEdit;
+
+    let connection = tabular
+        .connections
+        .iter()
+        .find(|candidate| candidate.id == Some(connection_id))
+        .cloned()
+        .ok_or_else(|| format!("Redis connection {} not found", connection_id))?;
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|error| format!("Failed to create runtime for Redis command: {}", error))?;
+    let seconds;
+    let (command, args): (&str, Vec<&str>) = match edit {
+        RedisKeyEdit::Expire { seconds: value } => {
+            seconds = value.to_string();
+            ("EXPIRE", vec![seconds.as_str()])
+        }
+        RedisKeyEdit::Persist => ("PERSIST", Vec::new()),
+        RedisKeyEdit::Rename { new_name } => ("RENAMENX", vec![new_name.as_str()]),
+        RedisKeyEdit::Delete => ("DEL", Vec::new()),
+    };
+    let reply = runtime.block_on(retry_on_moved_i64_command(
+        &connection,
+        database_name,
+        key_name,
+        command,
+        &args,
+    ))?;
+    cache_data::remove_redis_browser_preview_from_cache(
+        tabular,
+        connection_id,
+        database_name,
+        key_name,
+    );
+    match (edit, reply) {
+        (RedisKeyEdit::Expire { seconds }, 1) => Ok(format!(
+            "{} expires {}",
+            key_name,
+            format_ttl_label(*seconds as i64)
+        )),
+        (RedisKeyEdit::Persist, 1) => Ok(format!("{} no longer expires", key_name)),
+        (RedisKeyEdit::Persist, _) => Ok(format!("{} had no expiry", key_name)),
+        (RedisKeyEdit::Rename { new_name }, 1) => {
+            Ok(format!("Renamed {} to {}", key_name, new_name))
+        }
+        (RedisKeyEdit::Rename { new_name }, _) => Err(format!(
+            "Key {} already exists; nothing was renamed",
+            new_name
+        )),
+        (RedisKeyEdit::Delete, 1) => Ok(format!("Deleted {}", key_name)),
+        _ => Err(format!("Key {} no longer exists", key_name)),
     }
 }
 
@@ -473,15 +600,9 @@ pub(crate) fn load_redis_browser_state(
                 keyspace_label: keyspace_label.clone(),
                 keys: key_pairs
                     .into_iter()
-                    .map(|(key_name, key_type)| models::structs::RedisBrowserKeyEntry {
-                        key_name,
-                        key_type,
-                        ttl_label: if is_cluster {
-                            "Cluster".to_string()
-                        } else {
-                            keyspace_label.clone()
-                        },
-                        size_label: "-".to_string(),
+                    .map(|(key_name, key_type)| {
+                        let keyspace = if is_cluster { REDIS_CLUSTER_KEYSPACE } else { &keyspace_label };
+                        browser_key_entry(key_name, key_type, None, keyspace)
                     })
                     .collect(),
                 status_text: if is_cluster {
@@ -625,23 +746,13 @@ pub(crate) fn load_cached_redis_browser_state(
 
     let key_pairs = cache_data::get_redis_browser_keys_from_cache(tabular, connection_id, &keyspace_label)?;
     let key_count = key_pairs.len();
-    let is_cluster = keyspace_label == REDIS_CLUSTER_KEYSPACE;
 
     Some(models::structs::RedisBrowserState {
         available_keyspaces: cached_databases.clone(),
         keyspace_label: keyspace_label.clone(),
         keys: key_pairs
             .into_iter()
-            .map(|(key_name, key_type)| models::structs::RedisBrowserKeyEntry {
-                key_name,
-                key_type,
-                ttl_label: if is_cluster {
-                    "Cluster".to_string()
-                } else {
-                    keyspace_label.clone()
-                },
-                size_label: "-".to_string(),
-            })
+            .map(|(key_name, key_type)| browser_key_entry(key_name, key_type, None, &keyspace_label))
             .collect(),
         status_text: format!("Cached Redis browser · {} keys", key_count),
         ..Default::default()
@@ -991,6 +1102,7 @@ pub(crate) async fn load_redis_connection_config(
                 COALESCE(ssh_auth_method, 'key') AS ssh_auth_method, \
                 COALESCE(ssh_private_key, '') AS ssh_private_key, \
                 COALESCE(ssh_password, '') AS ssh_password, \
+                COALESCE(ssh_key_passphrase, '') AS ssh_key_passphrase, \
                 COALESCE(ssh_accept_unknown_host_keys, 0) AS ssh_accept_unknown_host_keys \
          FROM connections WHERE id = ?",
     )
@@ -1001,7 +1113,7 @@ pub(crate) async fn load_redis_connection_config(
 
     let id = row.try_get::<i64, _>("id").ok()?;
 
-    Some(models::structs::ConnectionConfig {
+    let mut connection = models::structs::ConnectionConfig {
         id: Some(id),
         name: row.try_get::<String, _>("name").unwrap_or_default(),
         host: row.try_get::<String, _>("host").unwrap_or_default(),
@@ -1034,10 +1146,26 @@ pub(crate) async fn load_redis_connection_config(
             &crate::secrets::connection_secret_name(id, "ssh_password"),
             &row.try_get::<String, _>("ssh_password").unwrap_or_default(),
         ),
+        ssh_key_passphrase: crate::secrets::resolve_readonly(
+            &crate::secrets::connection_secret_name(id, "ssh_key_passphrase"),
+            &row.try_get::<String, _>("ssh_key_passphrase").unwrap_or_default(),
+        ),
         ssh_accept_unknown_host_keys: row.try_get::<i64, _>("ssh_accept_unknown_host_keys").unwrap_or(0) != 0,
+        mssql_encrypt: true,
+        mssql_trust_server_certificate: true,
         custom_views: Vec::new(),
+        sqlite_attachments: Vec::new(),
         replication_master_id: None,
-    })
+        default_schema: String::new(),
+        last_used_database: None,
+        use_credential_file: false,
+        label: String::new(),
+        color: String::new(),
+        proxy: models::structs::ProxyConfig::default(),
+        templates: None,
+    };
+    crate::connection::resolve_loaded_connection(&mut connection);
+    Some(connection)
 }
 
 pub(crate) async fn fetch_cluster_keys_with_types(
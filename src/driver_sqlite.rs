@@ -1,18 +1,178 @@
 use log::debug;
+use sqlx::sqlite::SqlitePoolOptions;
 use sqlx::{Row, SqlitePool};
 
 use crate::{connection, models, window_egui};
 
-pub async fn fetch_data(connection_id: i64, pool: &SqlitePool, cache_pool: &SqlitePool) -> bool {
-    // For SQLite, we typically work with the main database, but we can get table info
-    if let Ok(rows) = sqlx::query(
-        "SELECT name FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%'",
+/// Attached schemas are addressed by name; `main` (or no schema) is the
+/// connection's own file and stays unqualified.
+fn attached_schema(schema: Option<&str>) -> Option<&str> {
+    schema.filter(|s| !s.is_empty() && !s.eq_ignore_ascii_case("main"))
+}
+
+/// Quote a table for browse queries, prefixing attached schemas: `db`.`table`.
+pub(crate) fn qualified_table_name(schema: Option<&str>, table: &str) -> String {
+    let quote = |name: &str| format!("`{}`", name.replace('`', "``"));
+    match attached_schema(schema) {
+        Some(s) => format!("{}.{}", quote(s), quote(table)),
+        None => quote(table),
+    }
+}
+
+/// `PRAGMA [schema.]name('table')`, targeting the attached file when needed.
+pub(crate) fn schema_pragma(schema: Option<&str>, pragma: &str, table: &str) -> String {
+    let target = attached_schema(schema)
+        .map(|s| format!("\"{}\".", s.replace('"', "\"\"")))
+        .unwrap_or_default();
+    format!("PRAGMA {}{}('{}')", target, pragma, table.replace('\'', "''"))
+}
+
+/// `sqlite_master` of the given schema.
+pub(crate) fn schema_master(schema: Option<&str>) -> String {
+    match attached_schema(schema) {
+        Some(s) => format!("\"{}\".sqlite_master", s.replace('"', "\"\"")),
+        None => "sqlite_master".to_string(),
+    }
+}
+
+pub(crate) fn attach_statement(attachment: &models::structs::SqliteAttachment) -> String {
+    format!(
+        "ATTACH DATABASE '{}' AS \"{}\"",
+        attachment.path.replace('\'', "''"),
+        attachment.schema.replace('"', "\"\"")
     )
-    .fetch_all(pool)
-    .await
-    {
-        // Cache the main database
-        let db_name = "main";
+}
+
+/// Schema names shown as database nodes: `main` followed by every attachment.
+pub(crate) fn schema_names(connection: &models::structs::ConnectionConfig) -> Vec<String> {
+    std::iter::once("main".to_string())
+        .chain(
+            connection
+                .sqlite_attachments
+                .iter()
+                .filter(|a| !a.schema.trim().is_empty() && !a.path.trim().is_empty())
+                .map(|a| a.schema.trim().to_string()),
+        )
+        .collect()
+}
+
+/// Why the attachment list cannot be used as-is, for the connection dialog.
+pub(crate) fn attachment_problem(
+    attachments: &[models::structs::SqliteAttachment],
+) -> Option<String> {
+    let mut seen = std::collections::HashSet::new();
+    for attachment in attachments {
+        let schema = attachment.schema.trim();
+        if schema.is_empty() {
+            return Some("Every attached file needs a schema name".to_string());
+        }
+        if schema.eq_ignore_ascii_case("main") || schema.eq_ignore_ascii_case("temp") {
+            return Some(format!("'{}' is reserved by SQLite", schema));
+        }
+        if !seen.insert(schema.to_lowercase()) {
+            return Some(format!("Schema '{}' is used twice", schema));
+        }
+        if attachment.path.trim().is_empty() {
+            return Some(format!("'{}' has no file path", schema));
+        }
+    }
+    None
+}
+
+/// Run ATTACH for each configured file on every new pooled connection, so
+/// cross-file queries work regardless of which connection serves them.
+pub(crate) fn with_attachments(
+    options: SqlitePoolOptions,
+    connection: &models::structs::ConnectionConfig,
+) -> SqlitePoolOptions {
+    let statements: Vec<String> = connection
+        .sqlite_attachments
+        .iter()
+        .filter(|a| !a.schema.trim().is_empty() && !a.path.trim().is_empty())
+        .map(attach_statement)
+        .collect();
+    if statements.is_empty() {
+        return options;
+    }
+    options.after_connect(move |conn, _meta| {
+        let statements = statements.clone();
+        Box::pin(async move {
+            for statement in statements {
+                if let Err(e) = sqlx::query(sqlx::AssertSqlSafe(statement.as_str()))
+                    .execute(&mut *conn)
+                    .await
+                {
+                    debug!("SQLite attach failed ({}): {}", statement, e);
+                }
+            }
+            Ok(())
+        })
+    })
+}
+
+/// Folder nodes (Tables, Views) for one SQLite schema.
+fn schema_folders(connection_id: i64, schema: &str) -> Vec<models::structs::TreeNode> {
+    let mut tables_folder =
+        models::structs::TreeNode::new("Tables".to_string(), models::enums::NodeType::TablesFolder);
+    tables_folder.connection_id = Some(connection_id);
+    tables_folder.database_name = Some(schema.to_string());
+    tables_folder.is_loaded = false;
+
+    let mut views_folder =
+        models::structs::TreeNode::new("Views".to_string(), models::enums::NodeType::ViewsFolder);
+    views_folder.connection_id = Some(connection_id);
+    views_folder.database_name = Some(schema.to_string());
+    views_folder.is_loaded = false;
+
+    vec![tables_folder, views_folder]
+}
+
+/// Children of a SQLite connection node. A single file keeps the flat
+/// Tables/Views layout; with attachments every schema gets a database node.
+pub(crate) fn structure_children(
+    connection_id: i64,
+    connection: &models::structs::ConnectionConfig,
+) -> Vec<models::structs::TreeNode> {
+    let schemas = schema_names(connection);
+    if schemas.len() == 1 {
+        return schema_folders(connection_id, "main");
+    }
+    schemas
+        .into_iter()
+        .map(|schema| {
+            let mut db_node =
+                models::structs::TreeNode::new(schema.clone(), models::enums::NodeType::Database);
+            db_node.connection_id = Some(connection_id);
+            db_node.database_name = Some(schema.clone());
+            db_node.children = schema_folders(connection_id, &schema);
+            db_node.is_loaded = true;
+            db_node
+        })
+        .collect()
+}
+
+pub async fn fetch_data(connection_id: i64, pool: &SqlitePool, cache_pool: &SqlitePool) -> bool {
+    // `main` plus any ATTACHed files; `temp` is per-connection scratch space
+    let schemas: Vec<String> = match sqlx::query("PRAGMA database_list").fetch_all(pool).await {
+        Ok(rows) => rows
+            .iter()
+            .filter_map(|r| r.try_get::<String, _>("name").ok())
+            .filter(|name| name != "temp")
+            .collect(),
+        Err(_) => return false,
+    };
+
+    for db_name in &schemas {
+        let tables_query = format!(
+            "SELECT name FROM {} WHERE type='table' AND name NOT LIKE 'sqlite_%'",
+            schema_master(Some(db_name))
+        );
+        let Ok(rows) = sqlx::query(sqlx::AssertSqlSafe(tables_query.as_str())).fetch_all(pool).await
+        else {
+            continue;
+        };
+
+        // Cache the database (schema)
         let _ = sqlx::query(
             "INSERT OR REPLACE INTO database_cache (connection_id, database_name) VALUES (?, ?)",
         )
@@ -34,7 +194,7 @@ pub async fn fetch_data(connection_id: i64, pool: &SqlitePool, cache_pool: &Sqli
                             .await;
 
                 // Fetch columns for this table
-                let col_query = format!("PRAGMA table_info({})", table_name);
+                let col_query = schema_pragma(Some(db_name), "table_info", &table_name);
                 if let Ok(col_rows) = sqlx::query(sqlx::AssertSqlSafe(col_query.as_str())).fetch_all(pool).await {
                     for col_row in col_rows {
                         if let (Ok(col_name), Ok(col_type)) = (
@@ -56,10 +216,8 @@ pub async fn fetch_data(connection_id: i64, pool: &SqlitePool, cache_pool: &Sqli
                 }
             }
         }
-        true
-    } else {
-        false
     }
+    true
 }
 
 // Helper function to convert SQLite rows to Vec<Vec<String>> with proper type checking
@@ -219,46 +377,35 @@ pub(crate) fn convert_sqlite_rows_to_table_data(
 
 pub(crate) fn load_sqlite_structure(
     connection_id: i64,
-    _connection: &models::structs::ConnectionConfig,
+    connection: &models::structs::ConnectionConfig,
     node: &mut models::structs::TreeNode,
 ) {
-    // Create basic structure for SQLite
-    let mut main_children = Vec::new();
+    let mut children = structure_children(connection_id, connection);
 
-    // Tables folder
-    let mut tables_folder =
-        models::structs::TreeNode::new("Tables".to_string(), models::enums::NodeType::TablesFolder);
-    tables_folder.connection_id = Some(connection_id);
-    tables_folder.database_name = Some("main".to_string());
-    tables_folder.is_loaded = false;
-
-    // Add a loading indicator
-    let loading_node = models::structs::TreeNode::new(
-        "Loading tables...".to_string(),
-        models::enums::NodeType::Table,
-    );
-    tables_folder.children.push(loading_node);
-
-    main_children.push(tables_folder);
-
-    // Views folder
-    let mut views_folder =
-        models::structs::TreeNode::new("Views".to_string(), models::enums::NodeType::ViewsFolder);
-    views_folder.connection_id = Some(connection_id);
-    views_folder.database_name = Some("main".to_string());
-    views_folder.is_loaded = false;
-    main_children.push(views_folder);
+    // Add a loading indicator to the first Tables folder of a single-file layout
+    if let Some(tables_folder) = children
+        .iter_mut()
+        .find(|c| c.node_type == models::enums::NodeType::TablesFolder)
+    {
+        tables_folder.children.push(models::structs::TreeNode::new(
+            "Loading tables...".to_string(),
+            models::enums::NodeType::Table,
+        ));
+    }
 
-    node.children = main_children;
+    node.children = children;
 }
 
 /// Fetch FK constraints across all user tables via PRAGMA foreign_key_list.
 pub(crate) async fn fetch_sqlite_foreign_keys(
     pool: &SqlitePool,
+    schema: &str,
 ) -> Result<Vec<models::structs::ForeignKey>, sqlx::Error> {
-    let tables: Vec<String> = sqlx::query_as::<_, (String,)>(
-        "SELECT name FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%'",
-    )
+    let tables_query = format!(
+        "SELECT name FROM {} WHERE type='table' AND name NOT LIKE 'sqlite_%'",
+        schema_master(Some(schema))
+    );
+    let tables: Vec<String> = sqlx::query_as::<_, (String,)>(sqlx::AssertSqlSafe(tables_query.as_str()))
     .fetch_all(pool)
     .await?
     .into_iter()
@@ -267,7 +414,7 @@ pub(crate) async fn fetch_sqlite_foreign_keys(
 
     let mut keys = Vec::new();
     for table in tables {
-        let pragma = format!("PRAGMA foreign_key_list('{}')", table.replace('\'', "''"));
+        let pragma = schema_pragma(Some(schema), "foreign_key_list", &table);
         if let Ok(rows) = sqlx::query(sqlx::AssertSqlSafe(pragma.as_str())).fetch_all(pool).await {
             for row in rows {
                 let referenced_table: String = row.try_get("table").unwrap_or_default();
@@ -287,13 +434,144 @@ pub(crate) async fn fetch_sqlite_foreign_keys(
     Ok(keys)
 }
 
+/// CHECK clauses and foreign key target of one column. SQLite keeps CHECK
+/// constraints only in the CREATE TABLE text, so every CHECK in it is returned
+/// and the caller keeps those that constrain the column.
+pub(crate) async fn fetch_sqlite_column_constraints(
+    pool: &SqlitePool,
+    schema: &str,
+    table_name: &str,
+    column_name: &str,
+) -> Result<models::structs::ColumnConstraints, sqlx::Error> {
+    let create_query = format!(
+        "SELECT sql FROM {} WHERE type='table' AND name = ?",
+        schema_master(Some(schema))
+    );
+    let create_sql: Option<String> = sqlx::query_scalar(sqlx::AssertSqlSafe(create_query.as_str()))
+        .bind(table_name)
+        .fetch_optional(pool)
+        .await?;
+
+    let mut references = None;
+    let pragma = schema_pragma(Some(schema), "foreign_key_list", table_name);
+    for row in sqlx::query(sqlx::AssertSqlSafe(pragma.as_str())).fetch_all(pool).await? {
+        let from_col: String = row.try_get("from").unwrap_or_default();
+        if from_col.eq_ignore_ascii_case(column_name) {
+            let referenced_table: String = row.try_get("table").unwrap_or_default();
+            // `to` is NULL when the FK targets the referenced table's primary key
+            let to_col: Option<String> = row.try_get("to").unwrap_or_default();
+            references = Some((referenced_table, to_col.unwrap_or_else(|| "rowid".to_string())));
+            break;
+        }
+    }
+
+    Ok(models::structs::ColumnConstraints {
+        check_clauses: create_sql.as_deref().map(check_clauses_in).unwrap_or_default(),
+        references,
+        ..Default::default()
+    })
+}
+
+/// Bodies of every `CHECK (...)` in a CREATE TABLE statement.
+fn check_clauses_in(create_sql: &str) -> Vec<String> {
+    let upper = create_sql.to_ascii_uppercase();
+    let bytes = create_sql.as_bytes();
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(rel) = upper[from..].find("CHECK") {
+        let kw = from + rel;
+        from = kw + 5;
+        let Some(open) = create_sql[from..].find(|c: char| !c.is_whitespace()).map(|o| from + o)
+        else {
+            break;
+        };
+        if bytes[open] != b'(' {
+            continue;
+        }
+        let mut depth = 0;
+        let mut in_str = false;
+        for (i, &b) in bytes.iter().enumerate().skip(open) {
+            match b {
+                b'\'' => in_str = !in_str,
+                b'(' if !in_str => depth += 1,
+                b')' if !in_str => {
+                    depth -= 1;
+                    if depth == 0 {
+                        out.push(create_sql[open + 1..i].trim().to_string());
+                        from = i + 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    out
+}
+
+/// Up to `limit` distinct non-null values of `table.column`, sorted.
+pub(crate) async fn fetch_sqlite_distinct_values(
+    pool: &SqlitePool,
+    schema: &str,
+    table_name: &str,
+    column_name: &str,
+    limit: usize,
+) -> Result<Vec<String>, sqlx::Error> {
+    let col = format!("\"{}\"", column_name.replace('"', "\"\""));
+    let sql = format!(
+        "SELECT DISTINCT CAST({col} AS TEXT) FROM {} WHERE {col} IS NOT NULL ORDER BY 1 LIMIT {limit}",
+        qualified_table_name(Some(schema), table_name)
+    );
+    sqlx::query_scalar::<_, String>(sqlx::AssertSqlSafe(sql))
+        .fetch_all(pool)
+        .await
+}
+
+/// Tables are counted up to this many rows; beyond it the tooltip shows a
+/// lower bound instead of scanning the whole table.
+const TABLE_STATS_COUNT_CAP: u64 = 100_000;
+
+/// Exact row count up to [`TABLE_STATS_COUNT_CAP`], and the table's size
+/// from `dbstat` when SQLite was built with it.
+pub(crate) async fn fetch_sqlite_table_stats(
+    pool: &SqlitePool,
+    schema: &str,
+    table_name: &str,
+) -> Result<models::structs::TableStats, sqlx::Error> {
+    let sql = format!(
+        "SELECT COUNT(*) FROM (SELECT 1 FROM {} LIMIT {})",
+        qualified_table_name(Some(schema), table_name),
+        TABLE_STATS_COUNT_CAP + 1
+    );
+    let counted: i64 = sqlx::query_scalar(sqlx::AssertSqlSafe(sql))
+        .fetch_one(pool)
+        .await?;
+    let counted = u64::try_from(counted).unwrap_or(0);
+    let bytes: Option<i64> =
+        sqlx::query_scalar("SELECT SUM(pgsize) FROM dbstat WHERE name = ?1 AND schema = ?2")
+            .bind(table_name)
+            .bind(if schema.is_empty() { "main" } else { schema })
+            .fetch_one(pool)
+            .await
+            .ok()
+            .flatten();
+    Ok(models::structs::TableStats {
+        rows: Some(counted.min(TABLE_STATS_COUNT_CAP)),
+        rows_capped: counted > TABLE_STATS_COUNT_CAP,
+        bytes: bytes.and_then(|b| u64::try_from(b).ok()),
+    })
+}
+
 /// Fetch all columns for every user table: table_name → [col1, col2, …]
 pub(crate) async fn fetch_sqlite_columns(
     pool: &SqlitePool,
+    schema: &str,
 ) -> Result<std::collections::HashMap<String, Vec<String>>, sqlx::Error> {
-    let tables: Vec<String> = sqlx::query_as::<_, (String,)>(
-        "SELECT name FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%'",
-    )
+    let tables_query = format!(
+        "SELECT name FROM {} WHERE type='table' AND name NOT LIKE 'sqlite_%'",
+        schema_master(Some(schema))
+    );
+    let tables: Vec<String> = sqlx::query_as::<_, (String,)>(sqlx::AssertSqlSafe(tables_query.as_str()))
     .fetch_all(pool)
     .await?
     .into_iter()
@@ -302,7 +580,7 @@ pub(crate) async fn fetch_sqlite_columns(
 
     let mut map: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
     for table in tables {
-        let pragma = format!("PRAGMA table_info('{}')", table.replace('\'', "''"));
+        let pragma = schema_pragma(Some(schema), "table_info", &table);
         if let Ok(rows) = sqlx::query(sqlx::AssertSqlSafe(pragma.as_str())).fetch_all(pool).await {
             for row in rows {
                 let col: String = row.try_get("name").unwrap_or_default();
@@ -316,8 +594,10 @@ pub(crate) async fn fetch_sqlite_columns(
 pub(crate) fn fetch_tables_from_sqlite_connection(
     tabular: &mut window_egui::Tabular,
     connection_id: i64,
+    database_name: &str,
     table_type: &str,
 ) -> Option<Vec<String>> {
+    let master = schema_master(Some(database_name));
     // Create a new runtime for the database query
     let rt = tokio::runtime::Runtime::new().ok()?;
 
@@ -328,15 +608,15 @@ pub(crate) fn fetch_tables_from_sqlite_connection(
        match pool {
               models::enums::DatabasePool::SQLite(sqlite_pool) => {
               let query = match table_type {
-                     "table" => "SELECT name FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%'",
-                     "view" => "SELECT name FROM sqlite_master WHERE type='view'",
+                     "table" => format!("SELECT name FROM {} WHERE type='table' AND name NOT LIKE 'sqlite_%'", master),
+                     "view" => format!("SELECT name FROM {} WHERE type='view'", master),
                      _ => {
                      debug!("Unsupported table type for SQLite: {}", table_type);
                      return None;
                      }
               };
 
-              let result = sqlx::query_as::<_, (String,)>(query)
+              let result = sqlx::query_as::<_, (String,)>(sqlx::AssertSqlSafe(query.as_str()))
                      .fetch_all(sqlite_pool.as_ref())
                      .await;
 
@@ -358,3 +638,78 @@ pub(crate) fn fetch_tables_from_sqlite_connection(
        }
        })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use models::structs::SqliteAttachment;
+
+    fn attachment(schema: &str, path: &str) -> SqliteAttachment {
+        SqliteAttachment {
+            schema: schema.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn attached_schemas_are_qualified() {
+        assert_eq!(qualified_table_name(Some("main"), "users"), "`users`");
+        assert_eq!(qualified_table_name(None, "users"), "`users`");
+        assert_eq!(qualified_table_name(Some("archive"), "users"), "`archive`.`users`");
+        assert_eq!(
+            schema_pragma(Some("archive"), "table_info", "o'rders"),
+            "PRAGMA \"archive\".table_info('o''rders')"
+        );
+        assert_eq!(schema_pragma(Some("main"), "index_list", "t"), "PRAGMA index_list('t')");
+        assert_eq!(
+            attach_statement(&attachment("archive", "/tmp/it's.db")),
+            "ATTACH DATABASE '/tmp/it''s.db' AS \"archive\""
+        );
+    }
+
+    #[test]
+    fn attachment_list_is_validated() {
+        assert_eq!(attachment_problem(&[attachment("archive", "/a.db")]), None);
+        assert!(attachment_problem(&[attachment("main", "/a.db")]).is_some());
+        assert!(attachment_problem(&[attachment("a", "/a.db"), attachment("A", "/b.db")]).is_some());
+        assert!(attachment_problem(&[attachment("a", " ")]).is_some());
+
+        let connection = models::structs::ConnectionConfig {
+            sqlite_attachments: vec![attachment("archive", "/a.db"), attachment("", "")],
+            ..Default::default()
+        };
+        assert_eq!(schema_names(&connection), vec!["main", "archive"]);
+    }
+
+    #[test]
+    fn pooled_connections_see_attached_files() {
+        let dir = std::env::temp_dir().join(format!("tabular-attach-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let main_path = dir.join("main.db");
+        let other_path = dir.join("other.db");
+        let connection = models::structs::ConnectionConfig {
+            host: main_path.to_string_lossy().to_string(),
+            connection_type: models::enums::DatabaseType::SQLite,
+            sqlite_attachments: vec![attachment("other", &other_path.to_string_lossy())],
+            ..Default::default()
+        };
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let count: i64 = rt.block_on(async {
+            let pool = with_attachments(SqlitePoolOptions::new().max_connections(2), &connection)
+                .connect_with(
+                    sqlx::sqlite::SqliteConnectOptions::new()
+                        .filename(&connection.host)
+                        .create_if_missing(true),
+                )
+                .await
+                .unwrap();
+            sqlx::query("CREATE TABLE other.items (id INTEGER)").execute(&pool).await.unwrap();
+            sqlx::query("INSERT INTO other.items VALUES (1), (2)").execute(&pool).await.unwrap();
+            let sql = format!("SELECT COUNT(*) FROM {}", qualified_table_name(Some("other"), "items"));
+            sqlx::query_scalar(sqlx::AssertSqlSafe(sql.as_str())).fetch_one(&pool).await.unwrap()
+        });
+        assert_eq!(count, 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -11,6 +11,7 @@ use crate::{
     sidebar_history, sidebar_query, window_egui,
 };
 use crate::spreadsheet::SpreadsheetOperations;
+use crate::window_egui::keybindings::Action;
 use std::borrow::Cow;
 use std::time::Instant;
 
@@ -19,11 +20,12 @@ pub(crate) fn create_new_tab(
     tabular: &mut window_egui::Tabular,
     title: String,
     content: String,
-) -> usize {
+) -> u64 {
     let tab_id = tabular.next_tab_id;
     tabular.next_tab_id += 1;
 
     let new_tab = models::structs::QueryTab {
+        id: tab_id,
         title,
         content: content.clone(),
         file_path: None,
@@ -42,7 +44,7 @@ pub(crate) fn create_new_tab(
         active_result_index: 0,
         is_table_browse_mode: false,
         current_page: 0,
-        page_size: 500, // default page size aligns with global default
+        page_size: tabular.default_page_size,
         total_rows: 0,
         base_query: String::new(), // Empty base query initially
         dba_special_mode: None,
@@ -57,6 +59,17 @@ pub(crate) fn create_new_tab(
         tx_mode: false,
         tx_active: false,
         session: None,
+        split_ratio: None,
+        record_view: false,
+        transposed: false,
+        bookmarks: Vec::new(),
+        hidden_columns: Vec::new(),
+        hidden_columns_headers: Vec::new(),
+        index_suggestions: Vec::new(),
+        pinned_results: Vec::new(),
+        active_pinned: None,
+        live_result: None,
+        param_values: Vec::new(),
     };
 
     tabular.query_tabs.push(new_tab);
@@ -65,6 +78,7 @@ pub(crate) fn create_new_tab(
 
     // Update editor with new tab content
     tabular.editor.set_text(content.clone());
+    tabular.bookmark_snapshot = None;
     tabular.highlight_cache.clear();
     tabular.last_highlight_hash = None;
     tabular.sql_semantic_snapshot = None;
@@ -74,6 +88,8 @@ pub(crate) fn create_new_tab(
     tabular.all_table_data.clear();
     tabular.current_table_name.clear();
     tabular.total_rows = 0;
+    tabular.current_page = 0;
+    tabular.page_size = tabular.default_page_size;
     tabular.is_table_browse_mode = false;
     tabular.current_object_ddl = None;
 
@@ -86,29 +102,29 @@ pub(crate) fn create_new_tab_with_connection(
     title: String,
     content: String,
     connection_id: Option<i64>,
-) -> usize {
-    let tab_id = create_new_tab(tabular, title, content);
-    if let Some(active_tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
-        active_tab.connection_id = connection_id;
-        // Keep global current_connection_id in sync with the newly created tab
-        tabular.current_connection_id = connection_id;
-        // New tabs have no database selected by default here; leave as-is
-    }
-    tab_id
+) -> u64 {
+    create_new_tab_with_connection_and_database(tabular, title, content, connection_id, None)
 }
 
-// Convenience: create a new query tab and pre-assign connection + database context
+// Convenience: create a new query tab and pre-assign connection + database context.
+// Without an explicit database the tab starts in the connection's default one.
 pub(crate) fn create_new_tab_with_connection_and_database(
     tabular: &mut window_egui::Tabular,
     title: String,
     content: String,
     connection_id: Option<i64>,
     database_name: Option<String>,
-) -> usize {
+) -> u64 {
     let tab_id = create_new_tab(tabular, title, content);
+    let connection = connection_id
+        .and_then(|cid| tabular.connections.iter().find(|c| c.id == Some(cid)));
+    let database_name =
+        database_name.or_else(|| connection.and_then(crate::connection::default_tab_database));
+    let schema_name = connection.and_then(crate::connection::default_tab_schema);
     if let Some(active_tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
         active_tab.connection_id = connection_id;
-        active_tab.database_name = database_name.clone();
+        active_tab.database_name = database_name;
+        active_tab.schema_name = schema_name;
         // Sync global state with the tab's assigned connection
         tabular.current_connection_id = connection_id;
     }
@@ -116,6 +132,7 @@ pub(crate) fn create_new_tab_with_connection_and_database(
 }
 
 pub(crate) fn close_tab(tabular: &mut window_egui::Tabular, tab_index: usize) {
+    tabular.bookmark_snapshot = None;
     if tabular.query_tabs.len() <= 1 {
         // Don't close the last tab, just clear it
         if let Some(tab) = tabular.query_tabs.get_mut(0) {
@@ -139,6 +156,7 @@ pub(crate) fn close_tab(tabular: &mut window_egui::Tabular, tab_index: usize) {
             tab.has_executed_query = false;
             tab.dba_special_mode = None;
             tab.object_ddl = None;
+            tab.bookmarks.clear();
         }
         tabular.editor.set_text(String::new());
         tabular.highlight_cache.clear();
@@ -203,47 +221,89 @@ pub(crate) fn find_tab_for_target(
     })
 }
 
+/// Moves the editor text and the displayed result state back into the active tab,
+/// leaving the global result state empty for whichever tab is shown next.
+fn stash_active_tab_state(tabular: &mut window_egui::Tabular) {
+    if let Some(current_tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        if current_tab.content != tabular.editor.text {
+            current_tab.content = tabular.editor.text.clone();
+            current_tab.is_modified = true;
+        }
+        // Persist current global result state into the tab before switching (swap to avoid clones)
+        std::mem::swap(
+            &mut current_tab.result_headers,
+            &mut tabular.current_table_headers,
+        );
+        std::mem::swap(
+            &mut current_tab.result_rows,
+            &mut tabular.current_table_data,
+        );
+        std::mem::swap(
+            &mut current_tab.result_all_rows,
+            &mut tabular.all_table_data,
+        );
+        std::mem::swap(
+            &mut current_tab.result_table_name,
+            &mut tabular.current_table_name,
+        );
+        current_tab.is_table_browse_mode = tabular.is_table_browse_mode;
+        current_tab.current_page = tabular.current_page;
+        current_tab.page_size = tabular.page_size;
+        current_tab.total_rows = tabular.total_rows;
+        std::mem::swap(&mut current_tab.base_query, &mut tabular.current_base_query);
+        debug!(
+            "💾 Saving tab {} state (swap): base_query='{}'",
+            tabular.active_tab_index, current_tab.base_query
+        );
+        std::mem::swap(&mut current_tab.object_ddl, &mut tabular.current_object_ddl);
+        // Save query message state
+        current_tab.query_message = tabular.query_message.clone();
+        current_tab.query_message_is_error = tabular.query_message_is_error;
+        // dba_special_mode already resides on current_tab; no action required here
+    }
+}
+
+/// Opens a copy of the active tab next to the others: same editor text and connection
+/// binding, but no result set.
+pub(crate) fn duplicate_active_tab(tabular: &mut window_egui::Tabular) {
+    let Some(tab) = tabular.query_tabs.get(tabular.active_tab_index) else {
+        return;
+    };
+    let title = format!("{} (copy)", tab.title);
+    let connection_id = tab.connection_id;
+    let database_name = tab.database_name.clone();
+    let schema_name = tab.schema_name.clone();
+    let content = tabular.editor.text.clone();
+    let source = tabular.active_tab_index;
+    stash_active_tab_state(tabular);
+    create_new_tab_with_connection_and_database(
+        tabular,
+        title,
+        content,
+        connection_id,
+        database_name,
+    );
+    tabular.current_base_query.clear();
+    tabular.query_message.clear();
+    tabular.query_message_is_error = false;
+    // The copy opens right after its source rather than at the end
+    if let Some(mut new_tab) = tabular.query_tabs.pop() {
+        new_tab.schema_name = schema_name;
+        tabular.query_tabs.insert(source + 1, new_tab);
+        tabular.active_tab_index = source + 1;
+    }
+}
+
 pub(crate) fn switch_to_tab(tabular: &mut window_egui::Tabular, tab_index: usize) {
     let mut need_connect: Option<i64> = None;
+    // Commit an in-progress cell edit before its grid state is swapped out
+    if tabular.spreadsheet_state.editing_cell.is_some() {
+        tabular.spreadsheet_finish_cell_edit(true);
+        tabular.table_recently_clicked = false;
+    }
     if tab_index < tabular.query_tabs.len() {
-        // Save current tab content
-        if let Some(current_tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
-            if current_tab.content != tabular.editor.text {
-                current_tab.content = tabular.editor.text.clone();
-                current_tab.is_modified = true;
-            }
-            // Persist current global result state into the tab before switching (swap to avoid clones)
-            std::mem::swap(
-                &mut current_tab.result_headers,
-                &mut tabular.current_table_headers,
-            );
-            std::mem::swap(
-                &mut current_tab.result_rows,
-                &mut tabular.current_table_data,
-            );
-            std::mem::swap(
-                &mut current_tab.result_all_rows,
-                &mut tabular.all_table_data,
-            );
-            std::mem::swap(
-                &mut current_tab.result_table_name,
-                &mut tabular.current_table_name,
-            );
-            current_tab.is_table_browse_mode = tabular.is_table_browse_mode;
-            current_tab.current_page = tabular.current_page;
-            current_tab.page_size = tabular.page_size;
-            current_tab.total_rows = tabular.total_rows;
-            std::mem::swap(&mut current_tab.base_query, &mut tabular.current_base_query);
-            debug!(
-                "💾 Saving tab {} state (swap): base_query='{}'",
-                tabular.active_tab_index, current_tab.base_query
-            );
-            std::mem::swap(&mut current_tab.object_ddl, &mut tabular.current_object_ddl);
-            // Save query message state
-            current_tab.query_message = tabular.query_message.clone();
-            current_tab.query_message_is_error = tabular.query_message_is_error;
-            // dba_special_mode already resides on current_tab; no action required here
-        }
+        tabular.bookmark_snapshot = None;
+        stash_active_tab_state(tabular);
 
         // Switch to new tab
         tabular.active_tab_index = tab_index;
@@ -314,6 +374,34 @@ pub(crate) fn switch_to_tab(tabular: &mut window_egui::Tabular, tab_index: usize
     }
 }
 
+/// Current SQL text of tab `tab_index`; the active tab's live buffer lives in
+/// `tabular.editor` rather than `tab.content`.
+pub(crate) fn tab_text(tabular: &window_egui::Tabular, tab_index: usize) -> String {
+    if tab_index == tabular.active_tab_index {
+        tabular.editor.text.clone()
+    } else {
+        tabular
+            .query_tabs
+            .get(tab_index)
+            .map(|t| t.content.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Open the side-by-side diff view comparing the active tab with `other`
+/// (or the next tab when `None`).
+pub(crate) fn open_tab_compare(tabular: &mut window_egui::Tabular, other: Option<usize>) {
+    if tabular.query_tabs.len() < 2 {
+        tabular.toasts.info("Open a second query tab to compare with");
+        return;
+    }
+    let left = tabular.active_tab_index;
+    let right = other
+        .filter(|i| *i < tabular.query_tabs.len() && *i != left)
+        .unwrap_or((left + 1) % tabular.query_tabs.len());
+    tabular.tab_compare_state = Some(models::structs::TabCompareState::new(left, right));
+}
+
 pub(crate) fn save_current_tab(tabular: &mut window_egui::Tabular) -> Result<(), String> {
     // HTTP API tabs: save the HTTP client state to disk instead of showing SQL save dialog
     if let Some(tab) = tabular.query_tabs.get(tabular.active_tab_index)
@@ -443,6 +531,10 @@ pub(crate) fn save_current_tab(tabular: &mut window_egui::Tabular) -> Result<(),
                     .to_string();
             }
 
+            // Offer the metadata the text already declares
+            tabular.save_query_meta = sidebar_query::parse_saved_query_meta(&tabular.editor.text);
+            tabular.save_query_tags = tabular.save_query_meta.tags.join(", ");
+
             tabular.show_save_dialog = true;
             Ok(())
         }
@@ -457,8 +549,13 @@ pub(crate) fn save_current_tab_with_name(
 ) -> Result<(), String> {
     // Keep editor content as-is when saving (no auto-format on save)
     if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        // Write the metadata from the save dialog as the leading comment block
+        let body = sidebar_query::with_saved_query_meta(&tabular.editor.text, &tabular.save_query_meta);
+        if body != tabular.editor.text {
+            tabular.editor.set_text(body.clone());
+        }
         // Mirror header injection as in save_current_tab
-        let mut final_content = tabular.editor.text.clone();
+        let mut final_content = body;
         let (conn_meta, db_meta) = (tab.connection_id, tab.database_name.clone());
         let mut header_lines: Vec<String> = Vec::new();
         if conn_meta.is_some() || db_meta.is_some() {
@@ -752,91 +849,34 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
     let mut request_scroll_to_cursor = false;
     let mut inserted_newline_this_frame = false;
     let editor_id = ui.make_persistent_id("sql_editor");
+    editor_autocomplete::poll_autocomplete(tabular, ui.ctx());
+    if std::mem::take(&mut tabular.editor_focus_request) {
+        ui.memory_mut(|m| m.request_focus(editor_id));
+        tabular.editor_focus_boost_frames = tabular.editor_focus_boost_frames.max(6);
+    }
 
-    // Shortcut: Format SQL (Cmd/Ctrl + Shift + F)
-    let mut trigger_format_sql = false;
-    ui.input(|i| {
-        // Accept platform command (command on macOS, control elsewhere)
-        if (i.modifiers.mac_cmd || i.modifiers.command)
-            && i.modifiers.shift
-            && i.key_pressed(egui::Key::F)
-        {
-            trigger_format_sql = true;
-        }
-    });
-    if trigger_format_sql {
-        // Consume the key event so TextEdit doesn't see it
-        ui.ctx().input_mut(|ri| {
-            ri.events.retain(|e| {
-                !matches!(
-                    e,
-                    egui::Event::Key {
-                        key: egui::Key::F,
-                        pressed: true,
-                        ..
-                    }
-                )
-            });
-        });
+    // Vim mode consumes Normal/Visual/Command keys before any other handling sees them
+    crate::editor_vim::handle_vim_input(tabular, ui, editor_id);
+
+    // Shortcut: Format SQL (default Cmd/Ctrl + Shift + F)
+    // Consuming the key event keeps TextEdit from seeing it
+    if tabular.keybindings.consume(ui.ctx(), Action::FormatSql) {
         reformat_current_sql(tabular, ui);
         request_scroll_to_cursor = true;
         // Early repaint for snappy UX
         ui.ctx().request_repaint();
     }
     
-    // Shortcut: Toggle Comment (Cmd/Ctrl + /)
-    let mut trigger_toggle_comment = false;
-    ui.input(|i| {
-        if (i.modifiers.mac_cmd || i.modifiers.command)
-            && !i.modifiers.shift
-            && i.key_pressed(egui::Key::Slash)
-        {
-            trigger_toggle_comment = true;
-        }
-    });
-    if trigger_toggle_comment {
-        // Consume the key event so TextEdit doesn't see it
-        ui.ctx().input_mut(|ri| {
-            ri.events.retain(|e| {
-                !matches!(
-                    e,
-                    egui::Event::Key {
-                        key: egui::Key::Slash,
-                        pressed: true,
-                        ..
-                    }
-                )
-            });
-        });
+    // Shortcut: Toggle Comment (default Cmd/Ctrl + /)
+    if tabular.keybindings.consume(ui.ctx(), Action::ToggleComment) {
         toggle_line_comment(tabular);
         request_scroll_to_cursor = true;
         // Early repaint for snappy UX
         ui.ctx().request_repaint();
     }
 
-    // Shortcut: Toggle AI Panel (Cmd/Ctrl + Shift + A)
-    let mut trigger_toggle_ai = false;
-    ui.input(|i| {
-        if (i.modifiers.mac_cmd || i.modifiers.command)
-            && i.modifiers.shift
-            && i.key_pressed(egui::Key::A)
-        {
-            trigger_toggle_ai = true;
-        }
-    });
-    if trigger_toggle_ai {
-        ui.ctx().input_mut(|ri| {
-            ri.events.retain(|e| {
-                !matches!(
-                    e,
-                    egui::Event::Key {
-                        key: egui::Key::A,
-                        pressed: true,
-                        ..
-                    }
-                )
-            });
-        });
+    // Shortcut: Toggle AI Panel (default Cmd/Ctrl + Shift + A)
+    if tabular.keybindings.consume(ui.ctx(), Action::ToggleAiPanel) {
         tabular.show_ai_panel = !tabular.show_ai_panel;
         if tabular.show_ai_panel && tabular.ai_input.is_empty() {
             // Pre-fill the AI prompt with selected text or the whole editor content (capped)
@@ -856,30 +896,26 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
         ui.ctx().request_repaint();
     }
 
-    // Shortcut: Go to DDL / Declaration (F12 or Cmd/Ctrl + B)
-    let mut trigger_goto_def = false;
-    ui.input(|i| {
-        let cmd_or_ctrl = i.modifiers.mac_cmd || i.modifiers.command || i.modifiers.ctrl;
-        if i.key_pressed(egui::Key::F12) || (cmd_or_ctrl && i.key_pressed(egui::Key::B)) {
-            trigger_goto_def = true;
-        }
-    });
-    if trigger_goto_def {
-        ui.ctx().input_mut(|ri| {
-            ri.events.retain(|e| {
-                !matches!(
-                    e,
-                    egui::Event::Key {
-                        key: egui::Key::F12 | egui::Key::B,
-                        pressed: true,
-                        ..
-                    }
-                )
-            });
-        });
+    // Shortcut: Go to DDL / Declaration (default F12 or Cmd/Ctrl + B)
+    let trigger_goto_def = tabular.keybindings.consume(ui.ctx(), Action::GoToDefinition);
+    if tabular.keybindings.consume(ui.ctx(), Action::JumpToDeclaration) || trigger_goto_def {
         jump_to_definition_at_cursor(tabular);
         ui.ctx().request_repaint();
     }
+
+    // Shortcuts: bookmarks (default Cmd/Ctrl + F2 toggles, Shift + F2 / Cmd/Ctrl + Shift + F2 cycle)
+    if tabular.keybindings.consume(ui.ctx(), Action::ToggleBookmark) {
+        crate::editor_bookmarks::toggle_bookmark(tabular);
+    }
+    if tabular.keybindings.consume(ui.ctx(), Action::NextBookmark) {
+        crate::editor_bookmarks::cycle_bookmark(tabular, true);
+    }
+    if tabular.keybindings.consume(ui.ctx(), Action::PreviousBookmark) {
+        crate::editor_bookmarks::cycle_bookmark(tabular, false);
+    }
+    if std::mem::take(&mut tabular.editor_scroll_to_cursor) {
+        request_scroll_to_cursor = true;
+    }
     
     // Find & Replace panel
     if tabular.advanced_editor.show_find_replace {
@@ -1017,7 +1053,7 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
     let autocomplete_was_visible_at_start = tabular.show_autocomplete;
 
     // ----- Handle autocomplete key interception and pre-acceptance BEFORE building TextEdit -----
-    let mut enter_pressed_pre = ui.input(|i| i.key_pressed(egui::Key::Enter));
+    let enter_pressed_pre = ui.input(|i| i.key_pressed(egui::Key::Enter));
     let mut raw_tab = false;
     // VSCode-like navigation/action flags
     let mut multi_nav_left = false;
@@ -1118,11 +1154,14 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
     }
     // Defer actual accept application until after TextEdit is rendered to avoid borrow conflicts
     let mut defer_accept_autocomplete = false;
+    // Enter accepted the popup; `enter_newline_after_accept` also re-inserts the newline
+    let mut enter_accepts_pre = false;
+    let mut enter_newline_after_accept = false;
     if tabular.show_autocomplete {
+        let enter_action = editor_autocomplete::enter_action(tabular);
         ui.ctx().input_mut(|ri| {
             // Drain & filter events: buang ArrowUp/ArrowDown pressed supaya TextEdit tidak memproses
             let mut kept = Vec::with_capacity(ri.events.len());
-            let mut _enter_consumed = false; // renamed to silence unused warning
             for ev in ri.events.drain(..) {
                 match ev {
                     egui::Event::Key {
@@ -1148,39 +1187,26 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
                         key: egui::Key::Enter,
                         pressed: true,
                         ..
-                    } => {
-                        // Heuristic: accept on Enter if user navigated OR
-                        //  - there is only one suggestion OR
-                        //  - selected suggestion extends current prefix (case-insensitive)
-                        let mut should_accept = tabular.autocomplete_navigated;
-
-                        if !should_accept {
-                            let sugg_count = tabular.autocomplete_suggestions.len();
-                            if sugg_count == 1 {
-                                should_accept = true;
-                            } else {
-                                let prefix = tabular.autocomplete_prefix.clone();
-                                if let Some(sugg) = tabular
-                                    .autocomplete_suggestions
-                                    .get(tabular.selected_autocomplete_index)
-                                    && !prefix.is_empty()
-                                {
-                                    let p = prefix.to_lowercase();
-                                    let s = sugg.to_lowercase();
-                                    if s.starts_with(&p) {
-                                        should_accept = true;
-                                    }
-                                }
-                            }
+                    } => match enter_action {
+                        editor_autocomplete::EnterAction::Accept => enter_accepts_pre = true,
+                        editor_autocomplete::EnterAction::AcceptAndNewline => {
+                            enter_accepts_pre = true;
+                            enter_newline_after_accept = true;
                         }
-
-                        if should_accept {
-                            enter_pressed_pre = true; // we'll accept suggestion
-                            _enter_consumed = true;
-                        } else {
+                        editor_autocomplete::EnterAction::Newline => {
                             // don't consume: let TextEdit insert newline
+                            editor_autocomplete::dismiss(tabular);
                             kept.push(e);
                         }
+                    },
+                    // Escape only closes the popup; TextEdit must not see it or it
+                    // drops focus and the next keystroke goes nowhere
+                    egui::Event::Key {
+                        key: egui::Key::Escape,
+                        pressed: true,
+                        ..
+                    } => {
+                        editor_autocomplete::dismiss(tabular);
                     }
                     // Jangan hilangkan release events agar repeat logic internal tidak stuck; hanya pressed yang kita konsumsi
                     other @ egui::Event::Key {
@@ -2630,7 +2656,7 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
     let accept_via_tab_pre = tab_pressed_pre && tabular.show_autocomplete;
     // Only accept via Enter if popup shown AND acceptance criteria met
     // Only intercept Enter for autocomplete when popup is visible AND there are suggestions
-    let accept_via_enter_pre = enter_pressed_pre
+    let accept_via_enter_pre = enter_accepts_pre
         && tabular.show_autocomplete
         && !tabular.autocomplete_suggestions.is_empty();
     if accept_via_tab_pre || accept_via_enter_pre {
@@ -2681,10 +2707,30 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
 
     // Simple layouter with cached highlighting; honor Word Wrap by adjusting max_width
     let word_wrap = tabular.advanced_editor.word_wrap;
+    let highlight_context = crate::syntax_ts::HighlightContext {
+        lang,
+        theme: tabular.advanced_editor.theme,
+        dark,
+        db_type: tabular
+            .query_tabs
+            .get(tabular.active_tab_index)
+            .and_then(|t| t.connection_id)
+            .and_then(|cid| tabular.connections.iter().find(|c| c.id == Some(cid)))
+            .map(|c| c.connection_type.clone()),
+    };
+    // Bracket pair at the caret, painted over the cached highlighting
+    let bracket_match = if tabular.selection_start == tabular.selection_end {
+        crate::editor_brackets::match_at(&tabular.editor.text, tabular.cursor_position)
+    } else {
+        None
+    };
     // Capture a mutable handle to the highlight cache for this frame to avoid recomputing
     let cache = &mut tabular.highlight_cache;
     let mut layouter = move |ui: &egui::Ui, text: &dyn egui::TextBuffer, wrap_width: f32| {
-        let mut job = crate::syntax_ts::highlight_text_cached(text.as_str(), lang, dark, cache);
+        let mut job = cache.highlight(text.as_str(), &highlight_context);
+        if let Some(found) = bracket_match {
+            crate::editor_brackets::highlight(&mut job, found, dark);
+        }
         job.wrap.max_width = if word_wrap { wrap_width } else { f32::INFINITY };
         ui.fonts_mut(|f| f.layout_job(job))
     };
@@ -2757,11 +2803,14 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
         cursor_range,
         ..
     } = text_output;
+    crate::editor_vim::paint_mode_indicator(tabular, ui, editor_rect);
 
-    // Fix: Check response immediately after widget interaction
+    // Clicking or tabbing into the editor ends an in-progress cell edit so the
+    // caret isn't left behind in the grid; the typed value is kept.
     if response.gained_focus() && tabular.spreadsheet_state.editing_cell.is_some() {
-        log::debug!("⎋ Editor gained focus (click) while cell editing -> Cancelling cell edit");
-        tabular.spreadsheet_finish_cell_edit(false);
+        log::debug!("⎋ Editor gained focus while cell editing -> committing cell edit");
+        tabular.spreadsheet_finish_cell_edit(true);
+        tabular.table_recently_clicked = false;
     }
 
     let cursor_range_after = cursor_range;
@@ -2783,25 +2832,51 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
         ui.ctx().request_repaint();
     }
 
-    // Cmd+Click / Ctrl+Click Go to DDL / Declaration
+    // Cmd+Click / Ctrl+Click Go to DDL / Declaration, and reveal it in the sidebar
     if response.clicked()
         && ui.input(|i| i.modifiers.command || i.modifiers.ctrl || i.modifiers.mac_cmd)
     {
         jump_to_definition_at_cursor(tabular);
+        reveal_definition(tabular, false);
     }
 
     // Right-click Context Menu on SQL Editor
     response.context_menu(|ui| {
-        if ui.button("🔍 Go to DDL / Structure (F12 / Cmd+B)").clicked() {
+        let keys = tabular.keybindings.clone();
+        if ui
+            .button(format!(
+                "🔍 Go to DDL / Structure ({} / {})",
+                keys.hint(Action::GoToDefinition),
+                keys.hint(Action::JumpToDeclaration)
+            ))
+            .clicked()
+        {
             jump_to_definition_at_cursor(tabular);
             ui.close();
         }
         ui.separator();
-        if ui.button("⚡ Execute Statement (Cmd+Enter)").clicked() {
+        if ui
+            .button(format!("⚡ Execute Statement ({})", keys.hint(Action::RunQuery)))
+            .clicked()
+        {
             execute_selected_command(tabular);
             ui.close();
         }
-        if ui.button("🧹 Format SQL (Cmd+Shift+F)").clicked() {
+        if ui
+            .button(format!("🔖 Toggle Bookmark ({})", keys.hint(Action::ToggleBookmark)))
+            .clicked()
+        {
+            crate::editor_bookmarks::toggle_bookmark(tabular);
+            ui.close();
+        }
+        if ui.button("🌐 Run on Multiple Connections…").clicked() {
+            connection::open_fleet_run(tabular);
+            ui.close();
+        }
+        if ui
+            .button(format!("🧹 Format SQL ({})", keys.hint(Action::FormatSql)))
+            .clicked()
+        {
             reformat_current_sql(tabular, ui);
             ui.close();
         }
@@ -2811,6 +2886,8 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
     // We purposely do NOT collapse selection on double-click frame (handled via did_double_click checks above),
     // to avoid wiping the freshly formed selection.
 
+    tabular.editor_has_focus = response.has_focus();
+
     // VSCode-like: highlight logic
     if response.has_focus() {
         let text = &tabular.editor.text;
@@ -2963,6 +3040,11 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
     // Apply deferred autocomplete acceptance after TextEdit borrow is released
     if defer_accept_autocomplete {
         crate::editor_autocomplete::accept_current_suggestion(tabular);
+        if enter_newline_after_accept {
+            let cur = tabular.cursor_position.min(tabular.editor.text.len());
+            tabular.editor.apply_single_replace(cur..cur, "\n");
+            tabular.cursor_position = cur + 1;
+        }
         let clamped = tabular.cursor_position.min(tabular.editor.text.len());
         tabular.pending_cursor_set = Some(clamped);
         // Keep focus on editor so Tab/Enter doesn't move focus
@@ -3085,6 +3167,7 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
 
     // Handle multi-cursor typing - apply changes to all cursors
     // Multi-selection typing compensations handled later in response.changed() branch now.
+    crate::editor_bookmarks::sync_bookmarks(tabular);
     if tabular.advanced_editor.show_line_numbers
         && let Some(gutter_rect) =
             ui.data(|d| d.get_temp::<egui::Rect>(egui::Id::new("gutter_rect")))
@@ -3099,15 +3182,29 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
             egui::vec2(gutter_rect.width(), editor_height),
         );
         painter.rect_filled(final_rect, 0.0, ui.visuals().faint_bg_color);
+        let bookmarked: Vec<usize> = tabular
+            .query_tabs
+            .get(tabular.active_tab_index)
+            .map(|t| t.bookmarks.iter().map(|b| b.line).collect())
+            .unwrap_or_default();
 
         // Render line numbers aligned with actual galley rows
         let mut line_num = 1;
+        let mut line_first_row = true;
         for row in &galley.rows {
             // Use galley_pos to get the actual vertical position of each row
             let y = galley_pos.y + row.rect().min.y;
             
             // Only render if within visible gutter area
             if y >= final_rect.top() && y <= final_rect.bottom() + 20.0 {
+                if line_first_row && bookmarked.contains(&(line_num - 1)) {
+                    crate::editor_bookmarks::paint_gutter_marker(
+                        painter,
+                        final_rect,
+                        y,
+                        row.rect().height(),
+                    );
+                }
                 painter.text(
                     egui::pos2(final_rect.right() - 8.0, y + 1.5),
                     egui::Align2::RIGHT_TOP,
@@ -3119,6 +3216,7 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
             
             // Increment line number after rendering each row that ends with newline
             // This ensures wrapped lines show the same line number
+            line_first_row = row.ends_with_newline;
             if row.ends_with_newline {
                 line_num += 1;
                 // Stop if we've rendered all lines
@@ -3728,6 +3826,7 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
             } else if is_text_changed {
                 // Hide popup on deletion or newline
                 tabular.show_autocomplete = false;
+                tabular.autocomplete_pending = false;
             }
         }
 
@@ -3789,6 +3888,7 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
         && (input.modifiers.command || input.modifiers.ctrl || input.modifiers.mac_cmd)
     {
         tabular.show_autocomplete = false;
+        tabular.autocomplete_pending = false;
     }
     if input.key_pressed(egui::Key::Space) && (input.modifiers.ctrl || input.modifiers.command) {
         editor_autocomplete::trigger_manual(tabular);
@@ -3847,29 +3947,8 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
         }
         let mut accepted = false;
         if input.key_pressed(egui::Key::Enter) && !accept_via_enter_pre {
-            // Apply same heuristic as pre-render
-            let mut should_accept = tabular.autocomplete_navigated;
-            if !should_accept {
-                let sugg_count = tabular.autocomplete_suggestions.len();
-                if sugg_count == 1 {
-                    should_accept = true;
-                } else {
-                    let prefix = tabular.autocomplete_prefix.clone();
-                    if let Some(sugg) = tabular
-                        .autocomplete_suggestions
-                        .get(tabular.selected_autocomplete_index)
-                        && !prefix.is_empty()
-                    {
-                        let p = prefix.to_lowercase();
-                        let s = sugg.to_lowercase();
-                        if s.starts_with(&p) {
-                            should_accept = true;
-                        }
-                    }
-                }
-            }
-
-            if should_accept {
+            // Apply same rule as pre-render
+            if editor_autocomplete::enter_action(tabular) == editor_autocomplete::EnterAction::Accept {
                 editor_autocomplete::accept_current_suggestion(tabular);
                 accepted = true;
             }
@@ -3925,7 +4004,7 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
             request_scroll_to_cursor = true;
         }
         if input.key_pressed(egui::Key::Escape) {
-            tabular.show_autocomplete = false;
+            editor_autocomplete::dismiss(tabular);
         }
     }
 
@@ -3936,27 +4015,28 @@ pub(crate) fn render_advanced_editor(tabular: &mut window_egui::Tabular, ui: &mu
     if moved_lr && !accept_via_tab_pre && !accept_via_enter_pre {
         // Hide autocomplete when navigating with arrows to avoid stale position
         tabular.show_autocomplete = false;
+        tabular.autocomplete_pending = false;
         tabular.suppress_editor_arrow_once = false;
     }
 
     // Render autocomplete popup positioned under cursor
     if tabular.show_autocomplete && !tabular.autocomplete_suggestions.is_empty() {
-        // Use O(log n) binary-search via offset_to_line_col instead of O(n) char scan
+        // Anchor on the laid-out caret so scrolling and wrapping are accounted for
         let cursor = tabular.cursor_position.min(tabular.editor.text.len());
-        let (line_no, col_bytes) = tabular.editor.offset_to_line_col(cursor);
-        let column = col_bytes; // byte offset within line (good enough for monospace approximation)
-        let char_w = 8.0_f32; // heuristic monospace width
-        let line_h = ui.text_style_height(&egui::TextStyle::Monospace);
-        let editor_rect = response.rect; // basic TextEdit rect
-        let mut pos = egui::pos2(
-            editor_rect.left() + (column as f32) * char_w,
-            editor_rect.top() + 4.0 + (line_no as f32) * line_h,
-        );
-        // Clamp horizontally inside editor area
-        if pos.x > editor_rect.right() - 150.0 {
-            pos.x = editor_rect.right() - 150.0;
-        }
-        editor_autocomplete::render_autocomplete(tabular, ui, pos);
+        let char_idx = to_char_index(&tabular.editor.text, cursor);
+        let caret = galley
+            .pos_from_cursor(CCursor::new(char_idx))
+            .translate(galley_pos.to_vec2());
+        // Keep the anchor inside the visible editor area
+        let visible = text_clip_rect.intersect(response.rect);
+        let x = caret
+            .left()
+            .clamp(visible.left(), (visible.right() - 150.0).max(visible.left()));
+        let y = caret
+            .top()
+            .clamp(visible.top(), (visible.bottom() - caret.height()).max(visible.top()));
+        let caret = caret.translate(egui::vec2(x - caret.left(), y - caret.top()));
+        editor_autocomplete::render_autocomplete(tabular, ui, caret);
     }
 
     if autocomplete_was_visible_at_start && !tabular.show_autocomplete {
@@ -4696,31 +4776,183 @@ pub(crate) fn open_command_palette(tabular: &mut window_egui::Tabular) {
     tabular.show_theme_selector = false;
     tabular.command_palette_selected_index = 0;
 
-    // Initialize command palette items with shortcut hints
+    // Initialize command palette items with the current shortcut bindings as hints
+    let with_hint = |name: &str, action: Action| {
+        format!("{:<30} {}", name, tabular.keybindings.hint(action))
+    };
     tabular.command_palette_items = vec![
-        "Query: Run                    ⌘ Enter".to_string(),
-        "Query: Format SQL             ⌘ Shift+F".to_string(),
-        "Query: Explain                ⌘ Shift+E".to_string(),
-        "Query: New Tab                ⌘T".to_string(),
-        "Query: Close Tab              ⌘W".to_string(),
-        "Query: Save Tab               ⌘S".to_string(),
-        "Editor: Go to Definition      F12".to_string(),
-        "Editor: Rename Symbol         F2".to_string(),
-        "Editor: Toggle Find & Replace ⌘F".to_string(),
+        with_hint("Query: Run", Action::RunQuery),
+        with_hint("Query: Format SQL", Action::FormatSql),
+        with_hint("Query: Explain", Action::ExplainQuery),
+        "Query: Expand SELECT *".to_string(),
+        with_hint("Query: New Tab", Action::NewTab),
+        "Query: Duplicate Current Tab".to_string(),
+        with_hint("Query: Close Tab", Action::CloseTab),
+        with_hint("Query: Save Tab", Action::SaveTab),
+        "Query: Run on Multiple Connections...".to_string(),
+        "Query: Toggle Safe Mode".to_string(),
+        "Query: Toggle Parameters Panel".to_string(),
+        "Query: Toggle Run in Transaction".to_string(),
+        "Query: Compare Tab With...".to_string(),
+        "Query: Recover Unsaved Buffers".to_string(),
+        with_hint("Editor: Go to Definition", Action::GoToDefinition),
+        with_hint("Editor: Rename Symbol", Action::RenameSymbol),
+        with_hint("Editor: Toggle Bookmark", Action::ToggleBookmark),
+        with_hint("Editor: Next Bookmark", Action::NextBookmark),
+        with_hint("Editor: Previous Bookmark", Action::PreviousBookmark),
+        "Editor: Toggle Find & Replace".to_string(),
         "Editor: Toggle Word Wrap".to_string(),
         "Editor: Toggle Line Numbers".to_string(),
+        "Editor: Paste as IN List...".to_string(),
         "Data: Export CSV".to_string(),
         "Data: Export JSON".to_string(),
+        "Data: Export Chart Data (JSON)".to_string(),
+        "Data: Export HTML".to_string(),
         "Data: Export SQL Inserts".to_string(),
         "Data: Export Markdown".to_string(),
+        "Data: Save Result to SQLite".to_string(),
         "Data: Import CSV".to_string(),
-        "Transaction: Begin / Toggle   ⌘ Shift+T".to_string(),
+        "Data: Clear Result Cache".to_string(),
+        "Data: Find in All Results...".to_string(),
+        "Data: Pin Current Result".to_string(),
+        with_hint("Transaction: Begin / Toggle", Action::ToggleTransaction),
         "Transaction: Commit".to_string(),
         "Transaction: Rollback".to_string(),
-        "View: Refresh                 ⌘R".to_string(),
+        with_hint("View: Refresh", Action::Refresh),
+        with_hint("View: Toggle Sidebar", Action::ToggleSidebar),
+        with_hint("View: Go to Table...", Action::GoToTable),
+        "View: PostgreSQL Notifications (LISTEN)".to_string(),
         "Preferences: Color Theme".to_string(),
-        "Preferences: Settings         ⌘,".to_string(),
+        "Workspace: Switch Profile...".to_string(),
+        with_hint("Preferences: Settings", Action::OpenSettings),
     ];
+    let text_commands = crate::editor_lines::TextCommand::PALETTE;
+    tabular
+        .command_palette_items
+        .extend(text_commands.iter().map(|(name, _)| name.to_string()));
+    #[cfg(feature = "query_ast")]
+    for (name, _) in DIALECT_TARGETS {
+        tabular
+            .command_palette_items
+            .push(format!("Query: Convert to {}", name));
+    }
+}
+
+/// Targets offered by the "Query: Convert to ..." commands.
+#[cfg(feature = "query_ast")]
+const DIALECT_TARGETS: [(&str, models::enums::DatabaseType); 4] = [
+    ("MySQL", models::enums::DatabaseType::MySQL),
+    ("PostgreSQL", models::enums::DatabaseType::PostgreSQL),
+    ("SQLite", models::enums::DatabaseType::SQLite),
+    ("SQL Server", models::enums::DatabaseType::MsSQL),
+];
+
+/// Re-emit the selected statement (or the one under the cursor) for `target`
+/// and open the result in a new tab. The active tab's connection type decides
+/// how the source is parsed; untranslatable parts are listed as comments.
+#[cfg(feature = "query_ast")]
+pub(crate) fn convert_query_to_dialect(
+    tabular: &mut window_egui::Tabular,
+    target_name: &str,
+    target: &models::enums::DatabaseType,
+) {
+    let sql = if tabular.selected_text.trim().is_empty() {
+        extract_query_from_cursor(tabular)
+    } else {
+        tabular.selected_text.clone()
+    };
+    if sql.trim().is_empty() {
+        tabular.toasts.info("Convert: place the cursor in a SELECT statement");
+        return;
+    }
+    let source = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.connection_id)
+        .and_then(|id| tabular.connections.iter().find(|c| c.id == Some(id)))
+        .map(|c| c.connection_type.clone());
+    match crate::query_ast::convert::convert_dialect(&sql, source.as_ref(), target) {
+        Ok(conversion) => {
+            let title = format!("Converted to {}", target_name);
+            create_new_tab(tabular, title, conversion.annotated_sql());
+            if !conversion.notes.is_empty() {
+                tabular.toasts.info(format!(
+                    "Converted with {} note(s); see the comments at the top",
+                    conversion.notes.len()
+                ));
+            }
+        }
+        Err(e) => {
+            tabular.toasts.error(format!("Convert to {}: {}", target_name, e));
+        }
+    }
+}
+
+/// Replace the bare `*` of the SELECT under the cursor with its explicit
+/// column list. Columns come from the cache and are fetched first when a
+/// table has not been cached yet; the rest of the statement is untouched.
+pub(crate) fn expand_select_star(tabular: &mut window_egui::Tabular) {
+    let text = tabular.editor.text.clone();
+    let cursor = tabular.cursor_position.min(text.len());
+    let statements = split_sql_statements_with_spans(&text);
+    let Some((offset, statement)) = statements
+        .iter()
+        .find(|(start, end, _)| cursor >= *start && cursor <= *end)
+        .or(statements.last())
+        .map(|(start, _, stmt)| (*start, *stmt))
+    else {
+        return;
+    };
+    let Some(found) = query_tools::find_select_star(statement) else {
+        tabular
+            .toasts
+            .info("Expand SELECT *: no SELECT * from plain tables at the cursor");
+        return;
+    };
+    let Some((connection_id, database)) = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|tab| tab.connection_id.map(|id| (id, tab.database_name.clone().unwrap_or_default())))
+    else {
+        tabular.toasts.info("Expand SELECT *: select a connection for this tab first");
+        return;
+    };
+    let db_type = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(connection_id))
+        .map(|c| c.connection_type.clone())
+        .unwrap_or(models::enums::DatabaseType::PostgreSQL);
+
+    let mut sources = Vec::new();
+    for source in found.sources {
+        // An explicit request retries tables autocomplete gave up on.
+        tabular
+            .autocomplete_cols_warmed
+            .remove(&(connection_id, source.table.to_ascii_lowercase()));
+        match crate::editor_autocomplete_new::table_columns(tabular, connection_id, &database, &source.table)
+            .filter(|columns| !columns.is_empty())
+        {
+            Some(columns) => sources.push((source, columns)),
+            None => {
+                tabular.toasts.error(format!(
+                    "Expand SELECT *: columns of '{}' are not available",
+                    source.table
+                ));
+                return;
+            }
+        }
+    }
+
+    let replacement = query_tools::expanded_column_list(&sources, &db_type);
+    let star = offset + found.star.start..offset + found.star.end;
+    tabular.editor.apply_single_replace(star.clone(), &replacement);
+    tabular.cursor_position = star.start + replacement.len();
+    tabular
+        .multi_selection
+        .set_primary_range(tabular.cursor_position, tabular.cursor_position);
+    tabular.pending_cursor_set = Some(tabular.cursor_position);
+    tabular.editor_focus_boost_frames = tabular.editor_focus_boost_frames.max(4);
 }
 
 pub(crate) fn navigate_command_palette(tabular: &mut window_egui::Tabular, direction: i32) {
@@ -4920,21 +5152,75 @@ pub(crate) fn execute_command(tabular: &mut window_egui::Tabular, command: &str)
         "Query: New Tab" => {
             create_new_tab(tabular, String::new(), String::new());
         }
+        "Query: Duplicate Current Tab" => {
+            duplicate_active_tab(tabular);
+        }
         "Query: Close Tab" => {
             if !tabular.query_tabs.is_empty() {
                 let idx = tabular.active_tab_index;
-                close_tab(tabular, idx);
+                crate::unsaved_changes::request_close_tab(tabular, idx);
             }
         }
         "Query: Save Tab" => {
             let _ = save_current_tab(tabular);
         }
+        "Query: Run on Multiple Connections..." => {
+            connection::open_fleet_run(tabular);
+        }
+        "Query: Toggle Parameters Panel" => {
+            tabular.show_parameters_panel = !tabular.show_parameters_panel;
+        }
+        "Query: Toggle Run in Transaction" => {
+            tabular.run_in_transaction = !tabular.run_in_transaction;
+            tabular.prefs_dirty = true;
+            tabular.try_save_prefs();
+            tabular.toasts.info(if tabular.run_in_transaction {
+                "Scripts now run in one transaction, rolled back on any error"
+            } else {
+                "Run in transaction off"
+            });
+        }
+        "Query: Toggle Safe Mode" => {
+            tabular.safe_mode = !tabular.safe_mode;
+            tabular.prefs_dirty = true;
+            tabular.try_save_prefs();
+            if tabular.safe_mode {
+                tabular.toasts.info(format!(
+                    "Safe mode on: data changes roll back after {}s unless kept",
+                    tabular.safe_mode_window_secs
+                ));
+            } else {
+                tabular.toasts.info("Safe mode off");
+            }
+        }
+        "Query: Compare Tab With..." => {
+            open_tab_compare(tabular, None);
+        }
+        "Query: Expand SELECT *" => {
+            expand_select_star(tabular);
+        }
+        #[cfg(feature = "query_ast")]
+        k if k.starts_with("Query: Convert to ") => {
+            let name = &k["Query: Convert to ".len()..];
+            if let Some((name, target)) = DIALECT_TARGETS.iter().find(|(n, _)| *n == name) {
+                convert_query_to_dialect(tabular, name, target);
+            }
+        }
         "Editor: Go to Definition" => {
             go_to_definition(tabular);
         }
         "Editor: Rename Symbol" => {
             begin_rename_symbol(tabular);
         }
+        "Editor: Toggle Bookmark" => {
+            crate::editor_bookmarks::toggle_bookmark(tabular);
+        }
+        "Editor: Next Bookmark" => {
+            crate::editor_bookmarks::cycle_bookmark(tabular, true);
+        }
+        "Editor: Previous Bookmark" => {
+            crate::editor_bookmarks::cycle_bookmark(tabular, false);
+        }
         "Editor: Toggle Find & Replace" => {
             tabular.advanced_editor.show_find_replace = !tabular.advanced_editor.show_find_replace;
         }
@@ -4944,38 +5230,96 @@ pub(crate) fn execute_command(tabular: &mut window_egui::Tabular, command: &str)
         "Editor: Toggle Line Numbers" => {
             tabular.advanced_editor.show_line_numbers = !tabular.advanced_editor.show_line_numbers;
         }
+        "Editor: Paste as IN List..." => {
+            crate::editor_in_list::open_in_list_dialog(tabular);
+        }
+        k if let Some((_, command)) = crate::editor_lines::TextCommand::PALETTE
+            .iter()
+            .find(|(name, _)| *name == k) =>
+        {
+            crate::editor_lines::apply_text_command(tabular, *command);
+        }
+        "Data: Find in All Results..." => {
+            crate::data_table::open_result_search(tabular);
+        }
+        "Data: Pin Current Result" => {
+            crate::data_table::pin_current_result(tabular);
+        }
         "Data: Export CSV" => {
             crate::export::export_to_csv(
-                &tabular.all_table_data,
-                &tabular.current_table_headers,
+                &crate::data_table::export_table_data(tabular),
+                &crate::data_table::export_headers(tabular),
                 &tabular.current_table_name,
+                crate::export::CsvDelimiter::Comma,
             );
         }
         "Data: Export JSON" => {
             crate::export::export_to_json(
-                &tabular.all_table_data,
-                &tabular.current_table_headers,
+                &crate::data_table::export_table_data(tabular),
+                &crate::data_table::export_headers(tabular),
+                &tabular.current_table_name,
+                true,
+            );
+        }
+        "Data: Export Chart Data (JSON)" => {
+            crate::export::export_to_chart_json(
+                &crate::data_table::export_table_data(tabular),
+                &crate::data_table::export_headers(tabular),
+                &tabular.current_table_name,
+            );
+        }
+        "Data: Export HTML" => {
+            crate::export::export_to_html(
+                &crate::data_table::export_table_data(tabular),
+                &crate::data_table::export_headers(tabular),
                 &tabular.current_table_name,
+                Some(&crate::data_table::export_caption(tabular)),
             );
         }
+        "Workspace: Switch Profile..." => {
+            crate::workspace_profile::open_window(tabular);
+        }
+        "Query: Recover Unsaved Buffers" => {
+            crate::recovery::open_browser(tabular);
+        }
+        "View: PostgreSQL Notifications (LISTEN)" => {
+            if tabular.pg_listen.connection_id.is_none() {
+                tabular.pg_listen.connection_id = tabular.current_connection_id.filter(|id| {
+                    tabular.connections.iter().any(|c| {
+                        c.id == Some(*id)
+                            && c.connection_type == models::enums::DatabaseType::PostgreSQL
+                    })
+                });
+            }
+            tabular.show_pg_listen_panel = true;
+        }
+        "Data: Clear Result Cache" => {
+            let removed = crate::cache_data::invalidate_query_result_cache(tabular, None, None);
+            tabular
+                .toasts
+                .info(format!("Cleared {} cached result(s)", removed));
+        }
         "Data: Export SQL Inserts" => {
             let db_type = tabular.current_connection_id
                 .and_then(|id| tabular.connections.iter().find(|c| c.id == Some(id)))
                 .map(|c| c.connection_type.clone());
             crate::export::export_to_sql_inserts(
-                &tabular.all_table_data,
-                &tabular.current_table_headers,
+                &crate::data_table::export_table_data(tabular),
+                &crate::data_table::export_headers(tabular),
                 &tabular.current_table_name,
                 db_type.as_ref(),
             );
         }
         "Data: Export Markdown" => {
             crate::export::export_to_markdown(
-                &tabular.all_table_data,
-                &tabular.current_table_headers,
+                &crate::data_table::export_table_data(tabular),
+                &crate::data_table::export_headers(tabular),
                 &tabular.current_table_name,
             );
         }
+        "Data: Save Result to SQLite" => {
+            crate::data_table::open_sqlite_export_dialog(tabular);
+        }
         "Data: Import CSV" => {
             if let Some(conn_id) = tabular.current_connection_id {
                 let db_type = tabular.connections.iter()
@@ -4983,22 +5327,13 @@ pub(crate) fn execute_command(tabular: &mut window_egui::Tabular, command: &str)
                     .map(|c| c.connection_type.clone())
                     .unwrap_or(crate::models::enums::DatabaseType::MySQL);
                 tabular.show_csv_import_dialog = true;
-                tabular.csv_import_state = Some(crate::models::structs::CsvImportState {
-                    connection_id: conn_id,
-                    database_name: None,
-                    table_name: tabular.current_table_name.clone(),
+                tabular.csv_import_state = Some(crate::models::structs::CsvImportState::new(
+                    conn_id,
+                    None,
+                    tabular.current_table_name.clone(),
                     db_type,
-                    file_path: None,
-                    delimiter: ',',
-                    has_header_row: true,
-                    null_value: String::new(),
-                    preview_headers: Vec::new(),
-                    preview_rows: Vec::new(),
-                    table_columns: tabular.current_table_headers.clone(),
-                    column_mappings: Vec::new(),
-                    status: crate::models::structs::CsvImportStatus::Idle,
-                    progress_message: String::new(),
-                });
+                    tabular.current_table_headers.clone(),
+                ));
             }
         }
         "Transaction: Begin / Toggle" => {
@@ -5012,9 +5347,15 @@ pub(crate) fn execute_command(tabular: &mut window_egui::Tabular, command: &str)
         "Transaction: Rollback" => {
             send_session_tx_command(tabular, false);
         }
+        "View: Toggle Sidebar" => {
+            tabular.toggle_sidebar();
+        }
         "View: Refresh" => {
             crate::data_table::refresh_current_table_data(tabular);
         }
+        "View: Go to Table..." => {
+            window_egui::table_palette::open_table_palette(tabular);
+        }
         "Preferences: Color Theme" => {
             tabular.request_theme_selector = true;
             tabular.theme_selector_selected_index = 0;
@@ -5028,8 +5369,8 @@ pub(crate) fn execute_command(tabular: &mut window_egui::Tabular, command: &str)
     }
 }
 
-/// Extract the identifier word at `pos` in `text` (SQL identifier chars: alphanumeric + _).
-fn word_at_cursor(text: &str, pos: usize) -> Option<&str> {
+/// Byte bounds of the identifier word at `pos` in `text` (SQL identifier chars: alphanumeric + _).
+fn word_bounds_at(text: &str, pos: usize) -> Option<(usize, usize)> {
     let bytes = text.as_bytes();
     let len = bytes.len();
     if len == 0 || pos > len {
@@ -5043,73 +5384,200 @@ fn word_at_cursor(text: &str, pos: usize) -> Option<&str> {
     }
     let start = (0..=p).rev().take_while(|&i| is_ident(bytes[i])).last()?;
     let end = (p..len).take_while(|&i| is_ident(bytes[i])).last().map(|i| i + 1).unwrap_or(p + 1);
-    Some(&text[start..end])
+    Some((start, end))
 }
 
-/// Find first tree node whose name (case-insensitive) matches `name` and is a table/view.
-fn find_table_in_tree<'a>(
-    nodes: &'a [crate::models::structs::TreeNode],
-    name: &str,
-) -> Option<&'a crate::models::structs::TreeNode> {
-    use crate::models::enums::NodeType;
-    for node in nodes {
-        if matches!(node.node_type, NodeType::Table | NodeType::View)
-            && node.name.to_lowercase() == name.to_lowercase()
-        {
-            return Some(node);
-        }
-        if let Some(found) = find_table_in_tree(&node.children, name) {
-            return Some(found);
-        }
-    }
-    None
+/// Extract the identifier word at `pos` in `text`.
+fn word_at_cursor(text: &str, pos: usize) -> Option<&str> {
+    word_bounds_at(text, pos).map(|(start, end)| &text[start..end])
 }
 
-/// Go-to-definition: finds the word under the editor cursor and navigates the sidebar
-/// to the matching table/view. Shows a toast if nothing is found.
-pub(crate) fn go_to_definition(tabular: &mut window_egui::Tabular) {
+/// The identifier at `pos`, the `qualifier.` in front of it if any, and
+/// whether a `.` follows it (it qualifies something itself, e.g. an alias).
+fn qualified_word_at(text: &str, pos: usize) -> Option<(Option<&str>, &str, bool)> {
+    let (start, end) = word_bounds_at(text, pos)?;
+    let qualifier = start
+        .checked_sub(1)
+        .filter(|&dot| text.as_bytes()[dot] == b'.')
+        .and_then(|dot| word_bounds_at(text, dot.checked_sub(1)?))
+        .map(|(s, e)| &text[s..e]);
+    Some((qualifier, &text[start..end], text[end..].starts_with('.')))
+}
+
+/// Schema objects the identifier under the cursor can refer to on the active
+/// connection: a table/view (or an alias of one), else a column of the table
+/// its qualifier names or of a table the statement uses. Returns the
+/// identifier with the matches, or why nothing could be looked up.
+fn definition_targets(
+    tabular: &window_egui::Tabular,
+) -> Result<(String, Vec<models::structs::DefinitionTarget>), &'static str> {
+    use models::{enums::NodeType, structs::DefinitionTarget};
+    let text = &tabular.editor.text;
     let cursor = tabular.cursor_position;
-    let text = tabular.editor.text.clone();
-    let word = match word_at_cursor(&text, cursor) {
-        Some(w) => w.to_string(),
-        None => {
-            tabular.toasts.info("Go to definition: no identifier at cursor");
-            return;
-        }
+    let (qualifier, word, is_qualifier) =
+        qualified_word_at(text, cursor).ok_or("no identifier at cursor")?;
+    let cid = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.connection_id)
+        .or(tabular.current_connection_id)
+        .ok_or("no active connection")?;
+    let aliases = crate::editor_autocomplete_new::collect_alias_map(text);
+    let unalias = |name: &str| {
+        aliases
+            .get(&name.to_ascii_lowercase())
+            .cloned()
+            .unwrap_or_else(|| name.to_string())
+    };
+    let tables = |name: &str| -> Vec<DefinitionTarget> {
+        crate::cache_data::find_cached_tables(tabular, cid, name)
+            .into_iter()
+            .map(|(database_name, table, table_type)| DefinitionTarget {
+                connection_id: cid,
+                database_name,
+                table,
+                node_type: if table_type == "view" { NodeType::View } else { NodeType::Table },
+                column: None,
+            })
+            .collect()
     };
 
-    // Search sidebar tree for a matching table/view
-    let tree = tabular.items_tree.clone();
-    if let Some(node) = find_table_in_tree(&tree, &word) {
-        // Navigate: set the connection from the node's context
-        if let Some(conn_id) = node.connection_id {
-            tabular.current_connection_id = Some(conn_id);
-        }
-        tabular.current_table_name = node.name.clone();
-        // Expand the tree to reveal the node
-        expand_tree_to_table(&mut tabular.items_tree, &word);
-        tabular.toasts.info(format!("Go to definition: navigated to '{}'", word));
-    } else {
-        tabular.toasts.info(format!("Go to definition: '{}' not found in schema", word));
+    // A table, or the alias in front of a column
+    if qualifier.is_none() || is_qualifier {
+        let found = tables(&unalias(word));
+        if !found.is_empty() {
+            return Ok((word.to_string(), found));
+        }
+    }
+    // A column of the qualifying table, else of any table the statement uses
+    let owners = match qualifier {
+        Some(q) => vec![unalias(q)],
+        None => crate::editor_autocomplete_new::tables_near_cursor(text, cursor),
+    };
+    let columns: Vec<DefinitionTarget> = crate::cache_data::find_cached_column_tables(tabular, cid, word)
+        .into_iter()
+        .filter(|(_, table, _)| owners.iter().any(|o| o.eq_ignore_ascii_case(table)))
+        .map(|(database_name, table, column)| DefinitionTarget {
+            connection_id: cid,
+            database_name,
+            table,
+            node_type: NodeType::Table,
+            column: Some(column),
+        })
+        .collect();
+    if !columns.is_empty() {
+        return Ok((word.to_string(), columns));
+    }
+    // `database.table` / `schema.table`
+    let Some(q) = qualifier else {
+        return Ok((word.to_string(), Vec::new()));
+    };
+    let mut found = tables(word);
+    if found.iter().any(|t| t.database_name.eq_ignore_ascii_case(q)) {
+        found.retain(|t| t.database_name.eq_ignore_ascii_case(q));
     }
+    Ok((word.to_string(), found))
 }
 
-/// Recursively expand tree nodes until a Table/View named `target` is found.
-fn expand_tree_to_table(nodes: &mut [crate::models::structs::TreeNode], target: &str) -> bool {
-    use crate::models::enums::NodeType;
-    for node in nodes.iter_mut() {
-        if matches!(node.node_type, NodeType::Table | NodeType::View)
-            && node.name.to_lowercase() == target.to_lowercase()
-        {
-            node.is_expanded = true;
-            return true;
-        }
-        if expand_tree_to_table(&mut node.children, target) {
-            node.is_expanded = true;
-            return true;
+/// Expand the sidebar tree to a go-to-definition target and scroll to it.
+pub(crate) fn reveal_definition_target(
+    tabular: &mut window_egui::Tabular,
+    target: &models::structs::DefinitionTarget,
+) {
+    tabular.selected_menu = "Database".to_string();
+    tabular.sidebar_collapsed = false;
+    tabular.reveal_object_in_tree(
+        target.connection_id,
+        Some(&target.database_name),
+        &target.table,
+        &target.node_type,
+        target.column.as_deref(),
+    );
+}
+
+/// Go-to-definition: reveals the table, view or column under the editor cursor
+/// in the sidebar tree. A name found in several databases opens a picker.
+pub(crate) fn go_to_definition(tabular: &mut window_egui::Tabular) {
+    reveal_definition(tabular, true);
+}
+
+/// Resolve and reveal the definition under the cursor. `report` toasts misses;
+/// Cmd+Click passes false since it also fires on keywords and plain words.
+fn reveal_definition(tabular: &mut window_egui::Tabular, report: bool) {
+    match definition_targets(tabular) {
+        Err(reason) => {
+            if report {
+                tabular.toasts.info(format!("Go to definition: {}", reason));
+            }
         }
+        Ok((word, targets)) => match targets.as_slice() {
+            [] => {
+                if report {
+                    tabular.toasts.info(format!("Go to definition: '{}' not found in schema", word));
+                }
+            }
+            [target] => {
+                reveal_definition_target(tabular, target);
+                if report {
+                    tabular.toasts.info(format!("Go to definition: revealed '{}'", target.label()));
+                }
+            }
+            _ => tabular.definition_picker = targets,
+        },
+    }
+}
+
+/// Picker for a go-to-definition name that exists in several databases.
+pub(crate) fn render_definition_picker(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let mut picked = None;
+    let mut cancel = ctx.input(|i| i.key_pressed(egui::Key::Escape));
+
+    egui::Area::new(egui::Id::new("definition_picker"))
+        .fixed_pos(egui::pos2(
+            ctx.content_rect().center().x - 160.0,
+            ctx.content_rect().center().y - 100.0,
+        ))
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            egui::Frame::default()
+                .fill(ui.style().visuals.window_fill)
+                .stroke(ui.style().visuals.window_stroke)
+                .shadow(egui::epaint::Shadow::default())
+                .inner_margin(egui::Margin::same(12))
+                .show(ui, |ui| {
+                    ui.set_min_width(320.0);
+                    ui.label(egui::RichText::new("Go to definition").strong());
+                    ui.add_space(6.0);
+                    egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                        for (i, target) in tabular.definition_picker.iter().enumerate() {
+                            let icon = if target.column.is_some() {
+                                "•"
+                            } else if target.node_type == models::enums::NodeType::View {
+                                "👁"
+                            } else {
+                                "📋"
+                            };
+                            if ui
+                                .selectable_label(false, format!("{} {}", icon, target.label()))
+                                .clicked()
+                            {
+                                picked = Some(i);
+                            }
+                        }
+                    });
+                    ui.add_space(6.0);
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+        });
+
+    if let Some(i) = picked {
+        let targets = std::mem::take(&mut tabular.definition_picker);
+        reveal_definition_target(tabular, &targets[i]);
+    } else if cancel {
+        tabular.definition_picker.clear();
     }
-    false
 }
 
 /// Begin rename: extract word at cursor and open the rename dialog.
@@ -5508,6 +5976,7 @@ pub(crate) fn execute_query(tabular: &mut window_egui::Tabular) {
 
 fn execute_query_internal(tabular: &mut window_egui::Tabular, mut query: String) {
     query = query.trim().to_string();
+    let preset = tabular.pending_parameter_preset.take();
 
     // Reset pagination state for each fresh execution; we will re-enable if heuristics say so.
     tabular.use_server_pagination = false;
@@ -5547,15 +6016,30 @@ fn execute_query_internal(tabular: &mut window_egui::Tabular, mut query: String)
         return;
     }
 
-    // Safety Guard: Check for unsafe UPDATE or DELETE without WHERE clause
+    // Safety Guard: confirm DELETE/UPDATE without WHERE, DROP and TRUNCATE.
+    // With a row threshold, DELETE/UPDATE are counted first and only
+    // confirmed when they would touch more rows (see render_unsafe_dml_dialog).
     if !tabular.show_unsafe_dml_dialog {
-        if let Some(kind) = is_unsafe_dml_query(&query) {
-            tabular.show_unsafe_dml_dialog = true;
-            tabular.unsafe_dml_query = query;
-            tabular.unsafe_dml_type = kind.to_string();
-            tabular.query_execution_in_progress = false;
-            tabular.extend_query_icon_hold();
-            return;
+        let threshold = tabular.destructive_confirm_over_rows;
+        if let Some((kind, statements)) = destructive_statement(&query, threshold > 0) {
+            let filtered = matches!(kind, "DELETE" | "UPDATE")
+                && statements.iter().all(|s| is_unsafe_dml_query(s).is_none());
+            let count_sql = dry_run_count_sql(&statements);
+            // Filtered statements we cannot estimate run as before.
+            if !filtered || count_sql.is_some() {
+                tabular.show_unsafe_dml_dialog = true;
+                tabular.unsafe_dml_query = query;
+                tabular.unsafe_dml_type = kind.to_string();
+                tabular.unsafe_dml_filtered = filtered;
+                tabular.unsafe_dml_count_sql = count_sql;
+                tabular.unsafe_dml_count = None;
+                if threshold > 0 {
+                    start_dry_run_count(tabular);
+                }
+                tabular.query_execution_in_progress = false;
+                tabular.extend_query_icon_hold();
+                return;
+            }
         }
     }
 
@@ -5563,9 +6047,35 @@ fn execute_query_internal(tabular: &mut window_egui::Tabular, mut query: String)
     if !tabular.show_parameter_dialog {
         let params = extract_query_parameters(&query);
         if !params.is_empty() {
+            let mut inputs = crate::query_presets::preset_inputs(preset.as_ref(), &params);
+            if preset.is_none() {
+                let remembered = tabular
+                    .query_tabs
+                    .get(tabular.active_tab_index)
+                    .map(|t| t.param_values.clone())
+                    .unwrap_or_default();
+                for (name, value) in &mut inputs {
+                    if let Some((_, last)) = remembered.iter().find(|(n, _)| n == name) {
+                        *value = last.clone();
+                    }
+                }
+            }
+            // A preset, or the open parameters panel, with a value for every
+            // parameter runs without asking
+            if (preset.is_some() || tabular.show_parameters_panel)
+                && inputs.iter().all(|(_, value)| !value.trim().is_empty())
+            {
+                execute_query_with_params(tabular, query, inputs);
+                return;
+            }
             tabular.show_parameter_dialog = true;
             tabular.parameter_dialog_query = query;
-            tabular.parameter_inputs = params.into_iter().map(|p| (p, String::new())).collect();
+            tabular.parameter_inputs = inputs;
+            tabular.parameter_dialog_file = tabular
+                .query_tabs
+                .get(tabular.active_tab_index)
+                .and_then(|t| t.file_path.clone());
+            tabular.parameter_preset_name = preset.map(|p| p.name).unwrap_or_default();
             tabular.query_execution_in_progress = false;
             tabular.extend_query_icon_hold();
             return;
@@ -5576,6 +6086,16 @@ fn execute_query_internal(tabular: &mut window_egui::Tabular, mut query: String)
 }
 
 pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, query: String) {
+    execute_query_with_inputs(tabular, query, Vec::new());
+}
+
+/// Run `query` without the safety checks, binding `inputs` (placeholder name,
+/// raw value) to its placeholders. The values belong to this run only.
+fn execute_query_with_inputs(
+    tabular: &mut window_egui::Tabular,
+    query: String,
+    inputs: Vec<(String, String)>,
+) {
     let query = query.trim().to_string();
     if query.is_empty() {
         return;
@@ -5612,6 +6132,9 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
         if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
             tab.results.clear();
             tab.active_result_index = 0;
+            // Pinned results stay; the new run replaces what is on screen
+            tab.active_pinned = None;
+            tab.live_result = None;
         }
 
         // Split the script into statements (quote/comment aware). '#' starts
@@ -5645,6 +6168,7 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
             tabular.pool_wait_in_progress = true;
             tabular.pool_wait_connection_id = Some(connection_id);
             tabular.pool_wait_query = query.clone();
+            tabular.pool_wait_params = inputs;
             tabular.pool_wait_started_at = Some(std::time::Instant::now());
             // Friendly status message; keep current data intact
             tabular.current_table_name = "Connecting… waiting for pool".to_string();
@@ -5666,6 +6190,7 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
             tabular.pool_wait_in_progress = true;
             tabular.pool_wait_connection_id = Some(connection_id);
             tabular.pool_wait_query = query.clone();
+            tabular.pool_wait_params = inputs;
             tabular.pool_wait_started_at = Some(std::time::Instant::now());
             tabular.current_table_name = "Connecting… waiting for pool".to_string();
             return;
@@ -5689,17 +6214,28 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
                 .map(|c| crate::connection::session::supports_transactions(&c.connection_type))
                 .unwrap_or(false);
             if supported {
-                execute_statements_in_session(tabular, connection_id, statements);
+                execute_statements_in_session(tabular, connection_id, statements, &inputs);
                 return;
             }
         }
 
+        // Safe mode: data changes wait for "Keep changes" before committing.
+        if crate::connection::safe_mode::try_start_safe_change(
+            tabular,
+            connection_id,
+            &statements,
+            &inputs,
+        ) {
+            return;
+        }
+
         if statements.len() == 1 {
             let stmt = statements.remove(0);
 
             // Auto-enable server-side pagination when the query does not specify
-            // LIMIT/TOP/OFFSET/FETCH. Only applicable to a single statement.
-            if connection::should_enable_auto_pagination(&stmt) {
+            // LIMIT/TOP/OFFSET/FETCH. Only applicable to a single statement, and
+            // not to bound parameters, whose placeholders the paging rewrite may move.
+            if inputs.is_empty() && connection::should_enable_auto_pagination(&stmt) {
                 let base_query = stmt.trim().trim_end_matches(';').to_string();
 
                 tabular.use_server_pagination = true;
@@ -5718,11 +6254,41 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
                 return;
             }
 
+            // Opt-in result cache: serve read-only statements from a fresh snapshot,
+            // or remember the job so its result is stored when it arrives.
+            let cache_target = if tabular.result_cache_enabled
+                && inputs.is_empty()
+                && crate::cache_data::is_result_cacheable(&stmt)
+            {
+                let database_name = tabular
+                    .query_tabs
+                    .get(tabular.active_tab_index)
+                    .and_then(|t| t.database_name.clone())
+                    .unwrap_or_default();
+                if let Some((headers, rows, cached_at)) = crate::cache_data::get_query_result_from_cache(
+                    tabular,
+                    connection_id,
+                    &database_name,
+                    &stmt,
+                ) {
+                    serve_cached_result(tabular, connection_id, stmt, headers, rows, cached_at);
+                    return;
+                }
+                Some((connection_id, database_name, stmt.clone()))
+            } else {
+                None
+            };
+
             let job_id = tabular.next_query_job_id;
             tabular.next_query_job_id = tabular.next_query_job_id.wrapping_add(1);
+            if let Some(target) = cache_target {
+                tabular.result_cache_jobs.insert(job_id, target);
+            }
+            remember_result_target(tabular, job_id);
 
-            match connection::prepare_query_job(tabular, connection_id, stmt.clone(), job_id) {
-                Ok(job) => {
+            match connection::prepare_bound_query_job(tabular, connection_id, stmt.clone(), job_id, &inputs) {
+                Ok(mut job) => {
+                    tabular.attach_query_stream(&mut job);
                     let status = connection::QueryJobStatus {
                         job_id,
                         connection_id,
@@ -5740,6 +6306,7 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
                         }
                         Err(err) => {
                             tabular.active_query_jobs.remove(&job_id);
+                            tabular.query_streams.remove(&job_id);
                             debug!("Failed to spawn async job: {:?}", err);
                         }
                     }
@@ -5749,6 +6316,12 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
                 }
             }
         } else {
+            if tabular.run_in_transaction
+                && execute_statements_in_transaction(tabular, connection_id, &statements, &inputs)
+            {
+                return;
+            }
+
             // Sequential batch: prepare every statement first, then run them
             // in order on ONE background task so script-like input behaves
             // like a script (no races between pool connections). Results
@@ -5762,7 +6335,13 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
                 let job_id = tabular.next_query_job_id;
                 tabular.next_query_job_id = tabular.next_query_job_id.wrapping_add(1);
 
-                match connection::prepare_query_job(tabular, connection_id, stmt.clone(), job_id) {
+                match connection::prepare_bound_query_job(
+                    tabular,
+                    connection_id,
+                    stmt.clone(),
+                    job_id,
+                    &inputs,
+                ) {
                     Ok(job) => {
                         let preview: String = stmt.chars().take(72).collect();
                         let status = connection::QueryJobStatus {
@@ -5787,6 +6366,18 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
                 return;
             }
 
+            if tabular.batch_results_in_tabs {
+                let targets: Vec<(u64, String)> = jobs
+                    .iter()
+                    .map(|job| (job.job_id, job.options.query.clone()))
+                    .collect();
+                open_batch_result_tabs(tabular, &targets);
+            } else {
+                for job_id in &job_ids {
+                    remember_result_target(tabular, *job_id);
+                }
+            }
+
             match connection::spawn_query_job_batch(tabular, jobs, tabular.query_result_sender.clone())
             {
                 Ok(handle) => {
@@ -5811,12 +6402,263 @@ pub(crate) fn execute_query_bypass_checks(tabular: &mut window_egui::Tabular, qu
     }
 }
 
+/// Tab a query job reports to, recorded at dispatch so the result lands in
+/// the tab that ran it even when another tab is in front by the time it
+/// finishes (or, in "A new tab per SELECT" mode, in the statement's own tab).
+#[derive(Clone, Debug)]
+pub struct ResultTarget {
+    /// [`models::structs::QueryTab::id`], so a tab closed, moved or renamed
+    /// meanwhile is never mistaken for the target.
+    pub tab_id: u64,
+}
+
+/// Tab title for a batch statement: its first words on one line.
+pub(crate) fn batch_tab_title(statement: &str) -> String {
+    const MAX_CHARS: usize = 28;
+    let one_line = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+    if one_line.chars().count() > MAX_CHARS {
+        format!("{}…", one_line.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        one_line
+    }
+}
+
+/// Open one tab per row-returning statement of a batch, on the current tab's
+/// connection, database and schema, and route each job's result to its tab.
+/// Other statements keep reporting to the current tab. The first new tab is
+/// brought to the front.
+fn open_batch_result_tabs(tabular: &mut window_egui::Tabular, jobs: &[(u64, String)]) {
+    let origin = tabular.active_tab_index;
+    let Some(origin_tab) = tabular.query_tabs.get_mut(origin) else {
+        return;
+    };
+    if origin_tab.content != tabular.editor.text {
+        origin_tab.content = tabular.editor.text.clone();
+        origin_tab.is_modified = true;
+    }
+    let connection_id = origin_tab.connection_id;
+    let database_name = origin_tab.database_name.clone();
+    let schema_name = origin_tab.schema_name.clone();
+    let origin_id = origin_tab.id;
+
+    let mut first_new = None;
+    for (job_id, statement) in jobs {
+        let target = if connection::statement_returns_rows(statement) {
+            let tab_id = create_new_tab_with_connection_and_database(
+                tabular,
+                batch_tab_title(statement),
+                statement.clone(),
+                connection_id,
+                database_name.clone(),
+            );
+            let index = tabular.active_tab_index;
+            if let Some(tab) = tabular.query_tabs.get_mut(index) {
+                tab.schema_name = schema_name.clone();
+                tab.result_table_name = "Running query…".to_string();
+            }
+            first_new.get_or_insert(index);
+            ResultTarget { tab_id }
+        } else {
+            ResultTarget { tab_id: origin_id }
+        };
+        tabular.result_targets.insert(*job_id, target);
+    }
+    // Creating tabs activated the last one; show the first instead
+    if let Some(index) = first_new
+        && index != tabular.active_tab_index
+    {
+        switch_to_tab(tabular, index);
+    }
+}
+
+/// Record that `job_id` reports to the active tab.
+pub(crate) fn remember_result_target(tabular: &mut window_egui::Tabular, job_id: u64) {
+    if let Some(tab) = tabular.query_tabs.get(tabular.active_tab_index) {
+        let target = ResultTarget { tab_id: tab.id };
+        tabular.result_targets.insert(job_id, target);
+    }
+}
+
+/// True while a query job dispatched from tab `index` is still running.
+pub(crate) fn tab_has_running_query(tabular: &window_egui::Tabular, index: usize) -> bool {
+    tabular.result_targets.iter().any(|(job_id, target)| {
+        tabular.active_query_jobs.contains_key(job_id)
+            && result_target_index(tabular, target) == Some(index)
+    })
+}
+
+/// Current index of a result target tab, if it is still open.
+pub(crate) fn result_target_index(
+    tabular: &window_egui::Tabular,
+    target: &ResultTarget,
+) -> Option<usize> {
+    tabular.query_tabs.iter().position(|t| t.id == target.tab_id)
+}
+
+/// Store a result in a tab that is not in front, so switching to it shows
+/// the result as if it had run there.
+pub(crate) fn store_result_in_tab(
+    tabular: &mut window_egui::Tabular,
+    tab_index: usize,
+    message: &connection::QueryResultMessage,
+    status: String,
+) {
+    let page_size = tabular.page_size.max(1);
+    let Some(tab) = tabular.query_tabs.get_mut(tab_index) else {
+        return;
+    };
+    let total_rows = message.rows.len();
+    let caption = if message.success && !message.produced_result_set {
+        data_table::statement_outcome_caption(message.affected_rows.unwrap_or(0))
+    } else if total_rows == 0 {
+        "Query returned 0 rows".to_string()
+    } else {
+        format!(
+            "Query Results ({} total rows, showing page 1 of {})",
+            total_rows,
+            total_rows.div_ceil(page_size)
+        )
+    };
+    tab.has_executed_query = true;
+    tab.results.push(models::structs::QueryResult {
+        headers: message.headers.clone(),
+        rows: message.rows.clone(),
+        all_rows: message.rows.clone(),
+        table_name: format!("Result {}", tab.results.len() + 1),
+        current_page: 0,
+        page_size,
+        total_rows,
+        query_message: status.clone(),
+        query_message_is_error: !message.success,
+        execution_time_ms: message.duration.as_millis(),
+        column_metadata: message.column_metadata.clone(),
+        explain_plan_json: None,
+        cached: None,
+    });
+    tab.query_message = status;
+    tab.query_message_is_error = !message.success;
+    if tab.results.len() == 1 {
+        tab.active_result_index = 0;
+        tab.result_headers = message.headers.clone();
+        tab.result_rows = message.rows.iter().take(page_size).cloned().collect();
+        tab.result_all_rows = message.rows.clone();
+        tab.result_column_metadata = message.column_metadata.clone();
+        tab.result_table_name = caption;
+        tab.total_rows = total_rows;
+        tab.current_page = 0;
+        tab.page_size = page_size;
+        tab.is_table_browse_mode = false;
+        tab.base_query.clear();
+    }
+}
+
+/// Store a page of a server-paginated result in a tab that is not in front.
+pub(crate) fn store_page_in_tab(
+    tabular: &mut window_egui::Tabular,
+    tab_index: usize,
+    message: &connection::QueryResultMessage,
+) {
+    let Some(tab) = tabular.query_tabs.get_mut(tab_index) else {
+        return;
+    };
+    if !message.headers.is_empty() {
+        tab.result_headers = message.headers.clone();
+    }
+    tab.result_rows = message.rows.clone();
+    tab.result_all_rows = message.rows.clone();
+    tab.result_table_name = if message.rows.is_empty() {
+        format!("Query Results (page {} empty)", tab.current_page.saturating_add(1))
+    } else {
+        format!(
+            "Query Results (page {} showing {} rows)",
+            tab.current_page.saturating_add(1),
+            message.rows.len()
+        )
+    };
+    tab.has_executed_query = true;
+}
+
+/// Show a result from the query result cache as if it had just run, then
+/// label it with its age so the grid can show the "cached" badge.
+fn serve_cached_result(
+    tabular: &mut window_egui::Tabular,
+    connection_id: i64,
+    query: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    cached_at: i64,
+) {
+    let job_id = tabular.next_query_job_id;
+    tabular.next_query_job_id = tabular.next_query_job_id.wrapping_add(1);
+    let row_count = rows.len();
+    tabular.handle_query_result_message(connection::QueryResultMessage {
+        job_id,
+        connection_id,
+        success: true,
+        headers,
+        rows,
+        error: None,
+        duration: std::time::Duration::ZERO,
+        query: query.clone(),
+        dba_special_mode: None,
+        ast_debug_sql: None,
+        ast_headers: None,
+        affected_rows: None,
+        produced_result_set: true,
+        column_metadata: None,
+    });
+
+    let age_secs = (chrono::Utc::now().timestamp() - cached_at).max(0);
+    tabular.query_message = format!(
+        "Served from result cache (cached {} ago) • {} row(s)",
+        format_cache_age(age_secs),
+        row_count
+    );
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.query_message = tabular.query_message.clone();
+        if let Some(result) = tab.results.last_mut() {
+            result.cached = Some((cached_at, query));
+            result.query_message = tabular.query_message.clone();
+        }
+    }
+}
+
+/// Compact age for cache badges: "42s", "5m", "3h".
+pub(crate) fn format_cache_age(secs: i64) -> String {
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s => format!("{}h", s / 3600),
+    }
+}
+
+/// Drop the active result's cache entry and re-run its statement against the
+/// server; the fresh result replaces the snapshot.
+pub(crate) fn refresh_cached_result(tabular: &mut window_egui::Tabular) {
+    let Some(tab) = tabular.query_tabs.get(tabular.active_tab_index) else {
+        return;
+    };
+    let Some((_, sql)) = tab.results.get(tab.active_result_index).and_then(|r| r.cached.clone()) else {
+        return;
+    };
+    if let Some(connection_id) = tab.connection_id {
+        let database_name = tab.database_name.clone().unwrap_or_default();
+        crate::cache_data::invalidate_query_result_cache(
+            tabular,
+            Some(connection_id),
+            Some((&database_name, &sql)),
+        );
+    }
+    execute_query_bypass_checks(tabular, sql);
+}
+
 /// Send statements to the active tab's dedicated session connection
 /// (manual-commit mode), creating or replacing the session as needed.
 fn execute_statements_in_session(
     tabular: &mut window_egui::Tabular,
     connection_id: i64,
     statements: Vec<String>,
+    inputs: &[(String, String)],
 ) {
     let database_name = tabular
         .query_tabs
@@ -5879,6 +6721,7 @@ fn execute_statements_in_session(
         if !session.send(crate::connection::session::SessionCommand::Execute {
             job_id,
             sql: stmt,
+            inputs: inputs.to_vec(),
         }) {
             tabular.active_query_jobs.remove(&job_id);
             tabular.error_message =
@@ -5899,6 +6742,90 @@ fn execute_statements_in_session(
     };
 }
 
+/// "Run in transaction": the statements of a script run on one connection
+/// between BEGIN and COMMIT and are rolled back together when one fails.
+/// Returns false, leaving the script to the regular batch, when the engine
+/// has no transactions (Redis, MongoDB) or the script manages its own
+/// (BEGIN, START TRANSACTION, COMMIT, ROLLBACK).
+fn execute_statements_in_transaction(
+    tabular: &mut window_egui::Tabular,
+    connection_id: i64,
+    statements: &[String],
+    inputs: &[(String, String)],
+) -> bool {
+    let Some(connection_type) = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(connection_id))
+        .map(|c| c.connection_type.clone())
+        .filter(crate::connection::session::supports_transactions)
+    else {
+        return false;
+    };
+    // MySQL commits before DDL, so such a script could not be rolled back as a whole
+    let implicit_commit = connection_type == models::enums::DatabaseType::MySQL
+        && statements
+            .iter()
+            .any(|s| crate::connection::sql::commits_implicitly_on_mysql(s));
+    if implicit_commit
+        || statements
+            .iter()
+            .any(|s| crate::connection::sql::is_transaction_control(s))
+    {
+        return false;
+    }
+
+    let total = statements.len();
+    let mut jobs = Vec::with_capacity(total);
+    for (idx, stmt) in statements.iter().enumerate() {
+        let job_id = tabular.next_query_job_id;
+        tabular.next_query_job_id = tabular.next_query_job_id.wrapping_add(1);
+        let preview: String = stmt.chars().take(72).collect();
+        let status = connection::QueryJobStatus {
+            job_id,
+            connection_id,
+            query_preview: format!("[tx {}/{}] {}", idx + 1, total, preview),
+            started_at: Instant::now(),
+            completed: false,
+        };
+        tabular.active_query_jobs.insert(job_id, status);
+        jobs.push((job_id, stmt.clone()));
+    }
+    if tabular.batch_results_in_tabs {
+        open_batch_result_tabs(tabular, &jobs);
+    } else {
+        for (job_id, _) in &jobs {
+            remember_result_target(tabular, *job_id);
+        }
+    }
+
+    let database_name = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.database_name.clone());
+    let job_ids: Vec<u64> = jobs.iter().map(|(job_id, _)| *job_id).collect();
+    match crate::connection::session::spawn_transaction_batch(
+        tabular,
+        connection_id,
+        database_name,
+        jobs,
+        inputs.to_vec(),
+    ) {
+        Ok(()) => {
+            tabular.current_table_name = format!("Running {} queries in a transaction…", total);
+        }
+        Err(e) => {
+            for job_id in &job_ids {
+                tabular.active_query_jobs.remove(job_id);
+            }
+            tabular.error_message = e;
+            tabular.show_error_message = true;
+            tabular.query_execution_in_progress = false;
+        }
+    }
+    true
+}
+
 /// Send COMMIT or ROLLBACK to the active tab's session (manual-commit mode).
 pub(crate) fn send_session_tx_command(tabular: &mut window_egui::Tabular, commit: bool) {
     let Some(session) = tabular
@@ -5944,6 +6871,8 @@ pub(crate) fn process_query_result(
 ) {
     if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
         tab.has_executed_query = true;
+        tab.active_pinned = None;
+        tab.live_result = None;
     }
 
     if let Some((headers, data)) = result {
@@ -5962,7 +6891,7 @@ pub(crate) fn process_query_result(
         data_table::update_pagination_data(tabular, data);
 
         if tabular.total_rows == 0 {
-            tabular.current_table_name = "Query executed successfully (no results)".to_string();
+            tabular.current_table_name = "Query returned 0 rows".to_string();
         } else {
             tabular.current_table_name = format!(
                 "Query Results ({} total rows, showing page {} of {})",
@@ -6016,7 +6945,11 @@ pub(crate) fn process_query_result(
 
         // Save query to history hanya jika bukan hasil error
         if !is_error_result {
-            sidebar_history::save_query_to_history(tabular, query, connection_id);
+            let database_name = tabular
+                .query_tabs
+                .get(tabular.active_tab_index)
+                .and_then(|t| t.database_name.clone());
+            sidebar_history::save_query_to_history(tabular, query, connection_id, database_name);
         } else {
             debug!("Skip saving to history karena hasil error");
         }
@@ -6223,186 +7156,271 @@ pub(crate) fn split_sql_statements_with_spans(text: &str) -> Vec<(usize, usize,
     result
 }
 
+/// Distinct parameter placeholders of `sql`, in first-use order.
 pub(crate) fn extract_query_parameters(sql: &str) -> Vec<String> {
-    let mut params = Vec::new();
-    let bytes = sql.as_bytes();
-    let len = bytes.len();
-    let mut i = 0;
-
-    let mut in_single_quote = false;
-    let mut in_double_quote = false;
-    let mut in_backtick = false;
-    let mut in_line_comment = false;
-    let mut in_block_comment = false;
-
-    while i < len {
-        let b = bytes[i];
-        let next_b = if i + 1 < len { Some(bytes[i + 1]) } else { None };
-
-        if in_line_comment {
-            if b == b'\n' {
-                in_line_comment = false;
-            }
-            i += 1;
-            continue;
-        }
-
-        if in_block_comment {
-            if b == b'*' && next_b == Some(b'/') {
-                in_block_comment = false;
-                i += 2;
-                continue;
-            }
-            i += 1;
-            continue;
+    let mut params: Vec<String> = Vec::new();
+    for placeholder in query_tools::find_placeholders(sql) {
+        if !params.contains(&placeholder.name) {
+            params.push(placeholder.name);
         }
+    }
+    params
+}
 
-        if in_single_quote {
-            if b == b'\\' {
-                i += 2;
-                continue;
-            } else if b == b'\'' {
-                if next_b == Some(b'\'') {
-                    i += 2;
-                    continue;
-                }
-                in_single_quote = false;
+/// Run `query` with the parameter values in `inputs` and remember them on the
+/// active tab. SQL connections bind the values natively, on every execution
+/// path; Redis and MongoDB get them written in as quoted literals instead.
+pub(crate) fn execute_query_with_params(
+    tabular: &mut window_egui::Tabular,
+    query: String,
+    inputs: Vec<(String, String)>,
+) {
+    let tab = tabular.query_tabs.get_mut(tabular.active_tab_index);
+    let connection_id = tab.as_ref().and_then(|t| t.connection_id);
+    if let Some(tab) = tab {
+        for (name, value) in &inputs {
+            match tab.param_values.iter_mut().find(|(n, _)| n == name) {
+                Some(existing) => existing.1 = value.clone(),
+                None => tab.param_values.push((name.clone(), value.clone())),
             }
-            i += 1;
-            continue;
         }
-
-        if in_double_quote {
-            if b == b'\\' {
-                i += 2;
-                continue;
-            } else if b == b'"' {
-                in_double_quote = false;
-            }
-            i += 1;
-            continue;
+    }
+    let target = connection_id
+        .and_then(|cid| tabular.connections.iter().find(|c| c.id == Some(cid)))
+        .map(|c| query_tools::ParamTarget::for_database(&c.connection_type));
+    match target {
+        Some(target) if target.binds_natively() => {
+            execute_query_with_inputs(tabular, query, inputs);
         }
-
-        if in_backtick {
-            if b == b'`' {
-                in_backtick = false;
+        _ => {
+            let target = target
+                .filter(|t| !t.binds_natively())
+                .unwrap_or(query_tools::ParamTarget::SqlLiteral);
+            match query_tools::substitute_params(&query, &inputs, target) {
+                Ok(substituted) => execute_query_bypass_checks(tabular, substituted),
+                Err(e) => tabular.toasts.error(e),
             }
-            i += 1;
-            continue;
         }
+    }
+}
 
-        if b == b'-' && next_b == Some(b'-') {
-            in_line_comment = true;
-            i += 2;
-            continue;
-        }
-        if b == b'#' {
-            in_line_comment = true;
-            i += 1;
-            continue;
-        }
-        if b == b'/' && next_b == Some(b'*') {
-            in_block_comment = true;
-            i += 2;
-            continue;
-        }
+pub(crate) fn is_unsafe_dml_query(sql: &str) -> Option<&'static str> {
+    let clean_sql = split_sql_statements_with_spans(sql);
+    let stmt = clean_sql.first().map(|(_, _, s)| *s).unwrap_or(sql);
+    let trimmed = stmt.trim();
+    let upper = trimmed.to_ascii_uppercase();
 
-        if b == b'\'' {
-            in_single_quote = true;
-            i += 1;
-            continue;
-        }
-        if b == b'"' {
-            in_double_quote = true;
-            i += 1;
-            continue;
+    if upper.starts_with("DELETE") {
+        if !upper.split_whitespace().any(|w| w == "WHERE" || w.starts_with("WHERE;")) {
+            return Some("DELETE");
         }
-        if b == b'`' {
-            in_backtick = true;
-            i += 1;
-            continue;
+    } else if upper.starts_with("UPDATE") {
+        if !upper.split_whitespace().any(|w| w == "WHERE" || w.starts_with("WHERE;")) {
+            return Some("UPDATE");
         }
+    }
+    None
+}
 
-        if b == b':' && next_b == Some(b':') {
-            i += 2;
-            continue;
+/// Statements of `sql` that need confirmation before they run, with their
+/// kind: the first DELETE/UPDATE without WHERE, DROP or TRUNCATE on its own,
+/// otherwise (with `include_filtered_dml`) every other DELETE/UPDATE, kind
+/// taken from the first.
+pub(crate) fn destructive_statement(
+    sql: &str,
+    include_filtered_dml: bool,
+) -> Option<(&'static str, Vec<String>)> {
+    let mut filtered_kind = None;
+    let mut filtered_dml = Vec::new();
+    for (_, _, stmt) in split_sql_statements_with_spans(sql) {
+        let stmt_owned = stmt.trim().trim_end_matches(';').trim_end().to_string();
+        if let Some(kind) = is_unsafe_dml_query(stmt) {
+            return Some((kind, vec![stmt_owned]));
+        }
+        let upper = stmt.trim_start().to_ascii_uppercase();
+        let kind = match upper.split_whitespace().next() {
+            Some("DROP") => return Some(("DROP", vec![stmt_owned])),
+            Some("TRUNCATE") => return Some(("TRUNCATE", vec![stmt_owned])),
+            Some("DELETE") => "DELETE",
+            Some("UPDATE") => "UPDATE",
+            _ => continue,
+        };
+        if include_filtered_dml {
+            filtered_kind.get_or_insert(kind);
+            filtered_dml.push(stmt_owned);
         }
+    }
+    filtered_kind.map(|kind| (kind, filtered_dml))
+}
 
-        if b == b':' && next_b.map_or(false, |c| c.is_ascii_alphabetic() || c == b'_') {
-            let start = i;
-            i += 1;
-            while i < len && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
-                i += 1;
-            }
-            let param_name = &sql[start..i];
-            if !params.contains(&param_name.to_string()) {
-                params.push(param_name.to_string());
+/// Byte offset and upper-cased text of each word of `sql` outside quotes,
+/// bracketed identifiers and parentheses.
+fn top_level_words(sql: &str) -> Vec<(usize, String)> {
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut word_start: Option<usize> = None;
+    for (i, c) in sql.char_indices() {
+        if let Some(close) = quote {
+            if c == close {
+                quote = None;
             }
             continue;
         }
-
-        if b == b'$' && next_b.map_or(false, |c| c.is_ascii_digit()) {
-            let start = i;
-            i += 1;
-            while i < len && bytes[i].is_ascii_digit() {
-                i += 1;
-            }
-            let param_name = &sql[start..i];
-            if !params.contains(&param_name.to_string()) {
-                params.push(param_name.to_string());
+        if c.is_alphanumeric() || c == '_' {
+            if word_start.is_none() && depth == 0 {
+                word_start = Some(i);
             }
             continue;
         }
-
-        if b == b'?' {
-            let param_name = format!("? (Param {})", params.len() + 1);
-            if !params.contains(&param_name) {
-                params.push(param_name);
-            }
-            i += 1;
-            continue;
+        if let Some(start) = word_start.take() {
+            words.push((start, sql[start..i].to_ascii_uppercase()));
         }
-
-        i += 1;
+        match c {
+            '\'' | '"' | '`' => quote = Some(c),
+            '[' => quote = Some(']'),
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    if let Some(start) = word_start {
+        words.push((start, sql[start..].to_ascii_uppercase()));
     }
+    words
+}
 
-    params
+/// One query counting the rows every statement of `statements` would
+/// touch, summed. Statements [`statement_count_sql`] cannot estimate are
+/// left out; `None` when none can be.
+pub(crate) fn dry_run_count_sql(statements: &[String]) -> Option<String> {
+    let counts: Vec<String> = statements
+        .iter()
+        .filter_map(|statement| statement_count_sql(statement))
+        .collect();
+    match counts.as_slice() {
+        [] => None,
+        [single] => Some(single.clone()),
+        several => Some(format!(
+            "SELECT {}",
+            several
+                .iter()
+                .map(|count| format!("({})", count))
+                .collect::<Vec<_>>()
+                .join(" + ")
+        )),
+    }
 }
 
-pub(crate) fn substitute_query_parameters(query: &str, params: &[(String, String)]) -> String {
-    let mut result = query.to_string();
-    for (param_name, value) in params {
-        let val_trimmed = value.trim();
-        if val_trimmed.is_empty() {
-            continue;
-        }
-        if param_name.starts_with('?') {
-            if let Some(pos) = result.find('?') {
-                result.replace_range(pos..pos + 1, val_trimmed);
+/// `SELECT COUNT(*)` over the rows a DELETE or UPDATE would touch: same
+/// table and WHERE, or the whole table. `None` for other statements and for
+/// forms whose target rows a plain count cannot express (joins via USING or
+/// UPDATE … FROM, OUTPUT, statement modifiers).
+pub(crate) fn statement_count_sql(statement: &str) -> Option<String> {
+    let stmt = statement.trim().trim_end_matches(';').trim_end();
+    let words = top_level_words(stmt);
+    let position = |keyword: &str| {
+        words
+            .iter()
+            .find(|(_, w)| w == keyword)
+            .map(|(pos, _)| *pos)
+    };
+    if words
+        .iter()
+        .any(|(_, w)| matches!(w.as_str(), "USING" | "OUTPUT"))
+    {
+        return None;
+    }
+    // RETURNING / ORDER BY / LIMIT shape the output, not the rows touched.
+    let end = words
+        .iter()
+        .find(|(_, w)| matches!(w.as_str(), "RETURNING" | "ORDER" | "LIMIT"))
+        .map(|(pos, _)| *pos)
+        .unwrap_or(stmt.len());
+    let where_pos = position("WHERE").filter(|pos| *pos < end);
+
+    let (first, second) = (words.first()?, words.get(1)?);
+    let table = match first.1.as_str() {
+        "DELETE" if second.1 == "FROM" => {
+            let start = second.0 + "FROM".len();
+            stmt.get(start..where_pos.unwrap_or(end))?
+        }
+        "UPDATE" => {
+            let set_pos = position("SET")?;
+            let from_after_set = words
+                .iter()
+                .any(|(pos, w)| *pos > set_pos && w == "FROM");
+            if from_after_set || matches!(second.1.as_str(), "OR" | "LOW_PRIORITY" | "IGNORE" | "TOP") {
+                return None;
             }
-        } else {
-            result = result.replace(param_name, val_trimmed);
+            stmt.get(first.0 + "UPDATE".len()..set_pos)?
         }
+        _ => return None,
+    };
+    let table = table.trim();
+    if table.is_empty() {
+        return None;
     }
-    result
+    Some(match where_pos {
+        Some(pos) => format!("SELECT COUNT(*) FROM {} {}", table, stmt[pos..end].trim()),
+        None => format!("SELECT COUNT(*) FROM {}", table),
+    })
 }
 
-pub(crate) fn is_unsafe_dml_query(sql: &str) -> Option<&'static str> {
-    let clean_sql = split_sql_statements_with_spans(sql);
-    let stmt = clean_sql.first().map(|(_, _, s)| *s).unwrap_or(sql);
-    let trimmed = stmt.trim();
-    let upper = trimmed.to_ascii_uppercase();
+/// Progress of the dry-run count shown in the destructive statement dialog.
+pub enum DryRunCount {
+    Running(std::sync::mpsc::Receiver<connection::QueryResultMessage>),
+    Done(u64),
+    Failed(String),
+}
 
-    if upper.starts_with("DELETE") {
-        if !upper.split_whitespace().any(|w| w == "WHERE" || w.starts_with("WHERE;")) {
-            return Some("DELETE");
+/// Start counting the rows the statement awaiting confirmation would touch.
+pub(crate) fn start_dry_run_count(tabular: &mut window_egui::Tabular) {
+    let Some(count_sql) = tabular.unsafe_dml_count_sql.clone() else {
+        return;
+    };
+    let Some(connection_id) = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.connection_id)
+    else {
+        tabular.unsafe_dml_count = Some(DryRunCount::Failed("No connection selected".to_string()));
+        return;
+    };
+    tabular.unsafe_dml_count = Some(match connection::spawn_side_query(tabular, connection_id, count_sql) {
+        Ok(receiver) => DryRunCount::Running(receiver),
+        Err(connection::types::QueryPreparationError::PoolUnavailable) => {
+            DryRunCount::Failed("Connection is not ready yet, try again".to_string())
         }
-    } else if upper.starts_with("UPDATE") {
-        if !upper.split_whitespace().any(|w| w == "WHERE" || w.starts_with("WHERE;")) {
-            return Some("UPDATE");
+        Err(err) => DryRunCount::Failed(format!("{:?}", err)),
+    });
+}
+
+/// Pick up a finished dry-run count.
+pub(crate) fn poll_dry_run_count(tabular: &mut window_egui::Tabular) {
+    let Some(DryRunCount::Running(receiver)) = &tabular.unsafe_dml_count else {
+        return;
+    };
+    let message = match receiver.try_recv() {
+        Ok(message) => message,
+        Err(std::sync::mpsc::TryRecvError::Empty) => return,
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+            tabular.unsafe_dml_count = Some(DryRunCount::Failed("Count was aborted".to_string()));
+            return;
         }
-    }
-    None
+    };
+    let count = if message.success {
+        message
+            .rows
+            .first()
+            .and_then(|row| row.first())
+            .and_then(|cell| cell.trim().parse::<u64>().ok())
+            .map(DryRunCount::Done)
+            .unwrap_or_else(|| DryRunCount::Failed("Unexpected count result".to_string()))
+    } else {
+        DryRunCount::Failed(message.error.unwrap_or_else(|| "Count failed".to_string()))
+    };
+    tabular.unsafe_dml_count = Some(count);
 }
 
 pub(crate) fn close_tabs_for_file(tabular: &mut window_egui::Tabular, file_path: &str) {
@@ -6574,6 +7592,16 @@ mod tests {
         assert_eq!(stmt2, "SELECT * FROM orders;");
     }
 
+    #[test]
+    fn test_qualified_word_at() {
+        let sql = "SELECT u.email FROM shop.users u";
+        assert_eq!(qualified_word_at(sql, 10), Some((Some("u"), "email", false)));
+        // The alias itself is followed by a dot
+        assert_eq!(qualified_word_at(sql, 7), Some((None, "u", true)));
+        assert_eq!(qualified_word_at(sql, 27), Some((Some("shop"), "users", false)));
+        assert_eq!(qualified_word_at(sql, 6), None);
+    }
+
     #[test]
     fn test_extract_query_parameters() {
         let sql = "SELECT * FROM users WHERE status = :status AND id = $1 AND name = ?;";
@@ -6588,5 +7616,171 @@ mod tests {
         assert_eq!(is_unsafe_dml_query("DELETE FROM users WHERE id = 1;"), None);
         assert_eq!(is_unsafe_dml_query("UPDATE users SET status = 'a' WHERE id = 1;"), None);
     }
-}
 
+    #[test]
+    fn batch_tab_title_is_single_line_and_truncated() {
+        assert_eq!(batch_tab_title("SELECT *\n  FROM users"), "SELECT * FROM users");
+        let long = batch_tab_title("SELECT id, name, email, created_at FROM users");
+        assert!(long.ends_with('…'));
+        assert_eq!(long.chars().count(), 29);
+    }
+
+    #[test]
+    fn dry_run_count_keeps_table_and_where() {
+        assert_eq!(
+            statement_count_sql("DELETE FROM users;").as_deref(),
+            Some("SELECT COUNT(*) FROM users")
+        );
+        assert_eq!(
+            statement_count_sql("UPDATE users SET note = 'WHERE x' WHERE id > 5 RETURNING id")
+                .as_deref(),
+            Some("SELECT COUNT(*) FROM users WHERE id > 5")
+        );
+        assert_eq!(
+            statement_count_sql("DELETE FROM t WHERE id IN (SELECT id FROM u LIMIT 3) LIMIT 10")
+                .as_deref(),
+            Some("SELECT COUNT(*) FROM t WHERE id IN (SELECT id FROM u LIMIT 3)")
+        );
+        assert_eq!(statement_count_sql("UPDATE a SET x = b.x FROM b WHERE a.id = b.id"), None);
+        assert_eq!(statement_count_sql("DROP TABLE users"), None);
+    }
+
+    #[test]
+    fn destructive_statement_prefers_unfiltered_statements() {
+        assert_eq!(
+            destructive_statement("SELECT 1; TRUNCATE logs;", false),
+            Some(("TRUNCATE", vec!["TRUNCATE logs".to_string()]))
+        );
+        assert_eq!(destructive_statement("DELETE FROM t WHERE id = 1", false), None);
+        assert_eq!(
+            destructive_statement("DELETE FROM t WHERE id = 1; DROP TABLE x", true),
+            Some(("DROP", vec!["DROP TABLE x".to_string()]))
+        );
+        assert_eq!(
+            destructive_statement("DELETE FROM t WHERE id = 1", true),
+            Some(("DELETE", vec!["DELETE FROM t WHERE id = 1".to_string()]))
+        );
+        // Every filtered statement of the batch is checked, not just the first
+        assert_eq!(
+            destructive_statement("DELETE FROM t WHERE id = 1; UPDATE u SET x = 1 WHERE y > 0", true),
+            Some((
+                "DELETE",
+                vec![
+                    "DELETE FROM t WHERE id = 1".to_string(),
+                    "UPDATE u SET x = 1 WHERE y > 0".to_string()
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn dry_run_count_sums_every_statement() {
+        let statements = [
+            "DELETE FROM t WHERE id = 1".to_string(),
+            "UPDATE a SET x = b.x FROM b WHERE a.id = b.id".to_string(),
+            "UPDATE u SET x = 1 WHERE y > 0".to_string(),
+        ];
+        assert_eq!(
+            dry_run_count_sql(&statements).as_deref(),
+            Some("SELECT (SELECT COUNT(*) FROM t WHERE id = 1) + (SELECT COUNT(*) FROM u WHERE y > 0)")
+        );
+        assert_eq!(dry_run_count_sql(&statements[1..2]), None);
+    }
+
+    #[test]
+    fn test_duplicate_active_tab_keeps_text_and_connection_only() {
+        let mut tabular = window_egui::Tabular::default();
+        create_new_tab_with_connection_and_database(
+            &mut tabular,
+            "Orders".to_string(),
+            String::new(),
+            Some(7),
+            Some("shop".to_string()),
+        );
+        tabular.editor.set_text("SELECT * FROM orders".to_string());
+        tabular.current_table_headers = vec!["id".to_string()];
+        tabular.current_table_data = vec![vec!["1".to_string()]];
+        tabular.query_message = "1 row".to_string();
+        let source = tabular.active_tab_index;
+
+        duplicate_active_tab(&mut tabular);
+
+        assert_eq!(tabular.active_tab_index, source + 1);
+        assert!(tabular.query_message.is_empty());
+        let copy = &tabular.query_tabs[tabular.active_tab_index];
+        assert_eq!(copy.title, "Orders (copy)");
+        assert_eq!(copy.content, "SELECT * FROM orders");
+        assert_eq!(copy.connection_id, Some(7));
+        assert_eq!(copy.database_name.as_deref(), Some("shop"));
+        assert!(tabular.current_table_headers.is_empty());
+        assert!(tabular.current_table_data.is_empty());
+        // The source tab keeps its results for when the user switches back
+        assert_eq!(tabular.query_tabs[source].result_rows.len(), 1);
+    }
+
+    #[test]
+    fn test_duplicate_active_tab_opens_next_to_its_source() {
+        let mut tabular = window_egui::Tabular::default();
+        create_new_tab(&mut tabular, "First".to_string(), String::new());
+        let first = tabular.active_tab_index;
+        create_new_tab(&mut tabular, "Second".to_string(), String::new());
+        switch_to_tab(&mut tabular, first);
+
+        duplicate_active_tab(&mut tabular);
+
+        assert_eq!(tabular.active_tab_index, first + 1);
+        assert_eq!(tabular.query_tabs[first + 1].title, "First (copy)");
+        assert_eq!(tabular.query_tabs[first + 2].title, "Second");
+    }
+
+    #[test]
+    fn test_result_reaches_its_tab_after_tabs_close_and_move() {
+        let mut tabular = window_egui::Tabular::default();
+        let untitled = || "Untitled Query".to_string();
+        create_new_tab(&mut tabular, untitled(), String::new());
+        let first = tabular.active_tab_index;
+        create_new_tab(&mut tabular, untitled(), "SELECT 1".to_string());
+        remember_result_target(&mut tabular, 7);
+        create_new_tab(&mut tabular, untitled(), String::new());
+        tabular.active_query_jobs.insert(
+            7,
+            connection::QueryJobStatus {
+                job_id: 7,
+                connection_id: 1,
+                query_preview: "SELECT 1".to_string(),
+                started_at: Instant::now(),
+                completed: false,
+            },
+        );
+
+        // The last tab closes, then a copy of the first lands before the target
+        close_tab(&mut tabular, first + 2);
+        switch_to_tab(&mut tabular, first);
+        duplicate_active_tab(&mut tabular);
+        let target = first + 2;
+        assert_eq!(tabular.query_tabs[target].content, "SELECT 1");
+        assert!(tab_has_running_query(&tabular, target));
+        assert!(!tab_has_running_query(&tabular, first + 1));
+
+        tabular.handle_query_result_message(connection::QueryResultMessage {
+            job_id: 7,
+            connection_id: 1,
+            success: false,
+            headers: vec!["Error".to_string()],
+            rows: vec![vec!["boom".to_string()]],
+            error: Some("boom".to_string()),
+            duration: std::time::Duration::ZERO,
+            query: "SELECT 1".to_string(),
+            dba_special_mode: None,
+            ast_debug_sql: None,
+            ast_headers: None,
+            affected_rows: None,
+            produced_result_set: false,
+            column_metadata: None,
+        });
+        assert!(tabular.query_tabs[target].query_message_is_error);
+        assert_eq!(tabular.query_tabs[target].results.len(), 1);
+        assert!(tabular.query_tabs[first + 1].results.is_empty());
+        assert!(tabular.query_tabs[first].results.is_empty());
+    }
+}
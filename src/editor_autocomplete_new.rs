@@ -132,8 +132,13 @@ fn collect_table_hits(sql: &str) -> Vec<(usize, String)> {
             && (i == 0 || !is_word_char(bytes[i - 1]))
             && (i + 4 >= bytes.len() || !is_word_char(bytes[i + 4]))
         {
-            if let Some((pos, name)) = parse_table_name(sql, i + 4) {
-                hits.push((pos, name));
+            // `FROM a x, b y` lists several tables
+            let mut next = Some(i + 4);
+            while let Some(at) = next.take() {
+                if let Some((pos, name)) = parse_table_name(sql, at) {
+                    hits.push((pos, name));
+                    next = next_listed_table(sql, pos);
+                }
             }
             i += 4;
             continue;
@@ -153,7 +158,118 @@ fn collect_table_hits(sql: &str) -> Vec<(usize, String)> {
     hits
 }
 
-fn tables_near_cursor(sql: &str, cursor: usize) -> Vec<String> {
+/// After a FROM-list entry starting at `pos` (`table [AS] [alias]`), the
+/// position right after a following comma, if any.
+fn next_listed_table(sql: &str, pos: usize) -> Option<usize> {
+    let bytes = sql.as_bytes();
+    let skip_ws = |mut j: usize| {
+        while j < bytes.len() && bytes[j].is_ascii_whitespace() {
+            j += 1;
+        }
+        j
+    };
+    let skip_word = |mut j: usize| {
+        while j < bytes.len() && is_word_char(bytes[j]) {
+            j += 1;
+        }
+        j
+    };
+    let mut j = pos;
+    while j < bytes.len()
+        && (is_word_char(bytes[j]) || matches!(bytes[j], b'.' | b'"' | b'`' | b'[' | b']'))
+    {
+        j += 1;
+    }
+    j = skip_ws(j);
+    if j < bytes.len() && is_word_char(bytes[j]) {
+        let end = skip_word(j);
+        let word = sql[j..end].to_ascii_uppercase();
+        if word == "AS" {
+            j = skip_ws(end);
+            j = skip_ws(skip_word(j));
+        } else if !SQL_KEYWORDS.contains(&word.as_str()) {
+            j = skip_ws(end);
+        }
+    }
+    (j < bytes.len() && bytes[j] == b',').then_some(j + 1)
+}
+
+/// Merge the columns of several tables (`(display name, columns)` in query
+/// order). A name found in one table stays bare; a name found in more than
+/// one is offered as `display.column` for each of them.
+fn merge_scope_columns(per_table: &[(String, Vec<String>)]) -> Vec<String> {
+    let mut owners: std::collections::HashMap<String, HashSet<&str>> =
+        std::collections::HashMap::new();
+    for (display, cols) in per_table {
+        for c in cols {
+            owners
+                .entry(c.to_ascii_lowercase())
+                .or_default()
+                .insert(display.as_str());
+        }
+    }
+    let mut out = Vec::new();
+    for (display, cols) in per_table {
+        for c in cols {
+            let candidate = if owners[&c.to_ascii_lowercase()].len() > 1 {
+                format!("{}.{}", display, c)
+            } else {
+                c.clone()
+            };
+            if !out.contains(&candidate) {
+                out.push(candidate);
+            }
+        }
+    }
+    out
+}
+
+/// Lowercase real table name → the name the query refers to it by (its alias
+/// when it has one).
+fn scope_display_names(
+    tables_in_scope: &[String],
+    alias_map: &std::collections::HashMap<String, String>,
+) -> std::collections::HashMap<String, String> {
+    let mut real_to_display = std::collections::HashMap::new();
+    for t in tables_in_scope {
+        real_to_display
+            .entry(t.to_ascii_lowercase())
+            .or_insert(t.clone());
+    }
+    for (alias, real) in alias_map {
+        let real_lower = real.to_ascii_lowercase();
+        if alias != &real_lower {
+            real_to_display.insert(real_lower, alias.clone());
+        }
+    }
+    real_to_display
+}
+
+/// Columns of every table in the statement's FROM / JOIN clauses, qualified
+/// by alias where a name is ambiguous (see [`merge_scope_columns`]).
+fn scope_columns(
+    app: &mut Tabular,
+    cid: i64,
+    db: &str,
+    text: &str,
+    tables_in_scope: &[String],
+) -> Vec<String> {
+    let real_to_display = scope_display_names(tables_in_scope, &collect_alias_map(text));
+    let per_table: Vec<(String, Vec<String>)> = tables_in_scope
+        .iter()
+        .filter_map(|t| {
+            let cols = table_columns(app, cid, db, t)?;
+            let display = real_to_display
+                .get(&t.to_ascii_lowercase())
+                .cloned()
+                .unwrap_or_else(|| t.clone());
+            Some((display, cols))
+        })
+        .collect();
+    merge_scope_columns(&per_table)
+}
+
+pub(crate) fn tables_near_cursor(sql: &str, cursor: usize) -> Vec<String> {
     let hits = collect_table_hits(sql);
     if hits.is_empty() {
         return Vec::new();
@@ -189,6 +305,25 @@ fn tables_near_cursor(sql: &str, cursor: usize) -> Vec<String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_enter_action_for() {
+        // Partially typed: Enter completes
+        assert_eq!(enter_action_for("sel", "SELECT", None, false, 3), EnterAction::Accept);
+        // Fully typed keyword: fix the case and keep the newline
+        assert_eq!(
+            enter_action_for("select", "SELECT", None, false, 3),
+            EnterAction::AcceptAndNewline
+        );
+        // Snippets expand even when their label is fully typed
+        assert_eq!(
+            enter_action_for("sel", "sel", Some("SELECT * FROM "), false, 3),
+            EnterAction::Accept
+        );
+        // Unrelated highlighted item and no navigation: plain newline
+        assert_eq!(enter_action_for("", "users", None, false, 3), EnterAction::Newline);
+        assert_eq!(enter_action_for("", "users", None, true, 3), EnterAction::Accept);
+    }
+
     #[test]
     fn test_find_statement_bounds() {
         let sql = "SELECT * FROM t1; SELECT * FROM t2 WHERE id = 1; INSERT INTO t3 VALUES(1)";
@@ -259,6 +394,23 @@ mod tests {
         assert_eq!(tables2, vec!["orders"]);
     }
 
+    #[test]
+    fn test_scope_columns_cover_from_lists_and_joins() {
+        let sql = "SELECT * FROM users u, teams AS t JOIN orders o ON o.user_id = u.id WHERE ";
+        let tables = tables_near_cursor(sql, sql.len());
+        assert_eq!(tables.len(), 3);
+        for t in ["users", "teams", "orders"] {
+            assert!(tables.iter().any(|x| x == t), "{t} in {tables:?}");
+        }
+        assert_eq!(tables_near_cursor("SELECT * FROM users WHERE a, b", 30), vec!["users"]);
+
+        let merged = merge_scope_columns(&[
+            ("u".to_string(), vec!["id".to_string(), "email".to_string()]),
+            ("o".to_string(), vec!["id".to_string(), "user_id".to_string()]),
+        ]);
+        assert_eq!(merged, vec!["u.id", "email", "o.id", "user_id"]);
+    }
+
     #[test]
     fn test_context_relevance_prefers_clause_specific_candidates() {
         assert!(context_relevance_score(SqlContext::AfterSelect, "customer_id", "cu")
@@ -268,6 +420,14 @@ mod tests {
         assert!(context_relevance_score(SqlContext::AfterJoinOn, "orders.id = users.id", "o")
             > context_relevance_score(SqlContext::AfterJoinOn, "orders", "o"));
     }
+    #[test]
+    fn test_prefix_changed_detects_same_length_edits() {
+        assert!(!prefix_changed(true, 3, "sel", "sel"));
+        // Same length, different text: the shown list is stale
+        assert!(prefix_changed(true, 3, "sel", "set"));
+        assert!(prefix_changed(true, 3, "sel", "sele"));
+        assert!(prefix_changed(false, 3, "sel", "sel"));
+    }
 }
 
 fn extract_tables(sql: &str) -> Vec<String> {
@@ -378,7 +538,7 @@ fn detect_ctx(sql: &str, cursor: usize) -> SqlContext {
 /// better match. An exact case-insensitive prefix wins big; matches landing on
 /// word boundaries (start, after `_`/`.`, or a CamelCase hump) score higher.
 /// An empty `pref` matches everything with score 0.
-fn fuzzy_match(pref: &str, cand: &str) -> Option<i32> {
+pub(crate) fn fuzzy_match(pref: &str, cand: &str) -> Option<i32> {
     let p: Vec<char> = pref
         .chars()
         .filter(|c| !c.is_whitespace())
@@ -487,105 +647,112 @@ fn get_cached_columns(
     }
     let mut out = Vec::new();
     for t in tables {
-        let key = (cid, t.to_ascii_lowercase());
-
-        // 1) In-memory fast path: once a table's columns are resolved they live
-        // here for the session, so suggestions never vanish on a later cache
-        // miss and we don't re-run blocking lookups on every keystroke.
-        if let Some(cols) = app.autocomplete_cols_mem.get(&key) {
-            for c in cols {
-                if !out.contains(c) {
-                    out.push(c.clone());
-                }
+        for c in table_columns(app, cid, db, &t).unwrap_or_default() {
+            if !out.contains(&c) {
+                out.push(c);
             }
-            continue;
         }
+    }
+    out.sort_unstable();
+    if out.is_empty() { None } else { Some(out) }
+}
 
-        // 2) SQLite cache: db-scoped first, then any-database (editor tabs
-        // aren't always pinned to the table's real database).
-        let mut cols = get_columns_from_cache(app, cid, db, &t)
-            .filter(|c| !c.is_empty())
-            .or_else(|| crate::cache_data::get_columns_for_connection_any_db(app, cid, &t));
-
-        // 3) Lazy warm: columns are only cached when a table is expanded in the
-        // tree. If still nothing, fetch them live once and persist, so column
-        // autocomplete works without manually opening each table first.
-        if cols.is_none() && !app.autocomplete_cols_warmed.contains(&key) {
-            // Build an ordered list of databases to try. Prefer the database the
-            // table is actually cached under (the editor tab's `database_name`
-            // is often empty or stale), then the tab's db, then every database
-            // known for this connection.
-            let mut cand_dbs: Vec<String> = Vec::new();
-            let push_db = |d: String, v: &mut Vec<String>| {
-                if !d.is_empty() && !v.iter().any(|e| e.eq_ignore_ascii_case(&d)) {
-                    v.push(d);
-                }
-            };
-            if let Some(d) = crate::cache_data::get_table_database_from_cache(app, cid, &t) {
-                push_db(d, &mut cand_dbs);
+/// Column names of `t` in their cached (ordinal) order, fetched live and
+/// persisted the first time they are not cached.
+pub(crate) fn table_columns(app: &mut Tabular, cid: i64, db: &str, t: &str) -> Option<Vec<String>> {
+    let key = (cid, t.to_ascii_lowercase());
+
+    // 1) In-memory fast path: once a table's columns are resolved they live
+    // here for the session, so suggestions never vanish on a later cache
+    // miss and we don't re-run blocking lookups on every keystroke.
+    if let Some(cols) = app.autocomplete_cols_mem.get(&key) {
+        return Some(cols.clone());
+    }
+
+    // 2) SQLite cache: db-scoped first, then any-database (editor tabs
+    // aren't always pinned to the table's real database).
+    let cols = get_columns_from_cache(app, cid, db, t)
+        .filter(|c| !c.is_empty())
+        .or_else(|| crate::cache_data::get_columns_for_connection_any_db(app, cid, t));
+
+    // 3) Lazy warm: columns are only cached when a table is expanded in the
+    // tree. If still nothing, fetch them live once and persist, so column
+    // autocomplete works without manually opening each table first.
+    if cols.is_none() && !app.autocomplete_cols_warmed.contains(&key) {
+        // Build an ordered list of databases to try. Prefer the database the
+        // table is actually cached under (the editor tab's `database_name`
+        // is often empty or stale), then the tab's db, then every database
+        // known for this connection.
+        let mut cand_dbs: Vec<String> = Vec::new();
+        let push_db = |d: String, v: &mut Vec<String>| {
+            if !d.is_empty() && !v.iter().any(|e| e.eq_ignore_ascii_case(&d)) {
+                v.push(d);
             }
-            push_db(db.to_string(), &mut cand_dbs);
-            if let Some(dbs) = app.database_cache.get(&cid).cloned() {
-                for d in dbs {
-                    push_db(d, &mut cand_dbs);
-                }
+        };
+        if let Some(d) = crate::cache_data::get_table_database_from_cache(app, cid, t) {
+            push_db(d, &mut cand_dbs);
+        }
+        push_db(db.to_string(), &mut cand_dbs);
+        if let Some(dbs) = app.database_cache.get(&cid).cloned() {
+            for d in dbs {
+                push_db(d, &mut cand_dbs);
             }
+        }
 
-            // Fallback: query the SQLite database_cache directly. This covers the
-            // common case where the user opens the editor before expanding the tree —
-            // database_cache (in-memory) is empty but the SQLite table was populated
-            // when the connection was first established.
-            if cand_dbs.is_empty()
-                && let (Some(pool), Some(rt)) = (app.db_pool.as_ref().cloned(), app.runtime.clone()) {
-                    let fut = async {
-                        sqlx::query_as::<_, (String,)>(
-                            "SELECT DISTINCT database_name FROM database_cache WHERE connection_id = ? AND database_name != '' LIMIT 10",
-                        )
-                        .bind(cid)
-                        .fetch_all(pool.as_ref())
-                        .await
-                    };
-                    if let Ok(rows) = rt.block_on(fut) {
-                        for (d,) in rows {
-                            push_db(d, &mut cand_dbs);
-                        }
-                    }
-                }
-
-            // Only attempt the live fetch (and blacklist on miss) when we have at
-            // least one candidate database. If cand_dbs is still empty the user
-            // hasn't connected yet — skip blacklisting so we retry next keystroke.
-            if !cand_dbs.is_empty() {
-                if let Some(conn) = app.connections.iter().find(|c| c.id == Some(cid)).cloned() {
-                    for table_db in &cand_dbs {
-                        if let Some(fetched) =
-                            crate::connection::fetch_columns_from_database(cid, table_db, &t, &conn)
-                            && !fetched.is_empty()
-                        {
-                            crate::cache_data::save_columns_to_cache(app, cid, table_db, &t, &fetched);
-                            cols = Some(fetched);
-                            break;
-                        }
+        // Fallback: query the SQLite database_cache directly. This covers the
+        // common case where the user opens the editor before expanding the tree —
+        // database_cache (in-memory) is empty but the SQLite table was populated
+        // when the connection was first established.
+        if cand_dbs.is_empty()
+            && let (Some(pool), Some(rt)) = (app.db_pool.as_ref().cloned(), app.runtime.clone()) {
+                let fut = async {
+                    sqlx::query_as::<_, (String,)>(
+                        "SELECT DISTINCT database_name FROM database_cache WHERE connection_id = ? AND database_name != '' LIMIT 10",
+                    )
+                    .bind(cid)
+                    .fetch_all(pool.as_ref())
+                    .await
+                };
+                if let Ok(rows) = rt.block_on(fut) {
+                    for (d,) in rows {
+                        push_db(d, &mut cand_dbs);
                     }
                 }
-                // Blacklist only after a genuine fetch attempt (success or definitive miss).
-                app.autocomplete_cols_warmed.insert(key.clone());
             }
-        }
 
-        // Promote whatever we resolved into the in-memory map and the output.
-        if let Some(cols) = cols {
-            let names: Vec<String> = cols.into_iter().map(|(c, _)| c).collect();
-            for c in &names {
-                if !out.contains(c) {
-                    out.push(c.clone());
+        // Only attempt the live fetch (and blacklist on miss) when we have at
+        // least one candidate database. If cand_dbs is still empty the user
+        // hasn't connected yet — skip blacklisting so we retry next keystroke.
+        // The fetch runs off the UI thread, once per table; `poll_autocomplete`
+        // stores the columns and refreshes the popup when they arrive.
+        if !cand_dbs.is_empty() && !app.autocomplete_cols_inflight.contains(&key) {
+            match app.connections.iter().find(|c| c.id == Some(cid)).cloned() {
+                Some(conn) => {
+                    app.autocomplete_cols_inflight.insert(key.clone());
+                    let sender = app.autocomplete_cols_sender.clone();
+                    let table = t.to_string();
+                    std::thread::spawn(move || {
+                        let found = cand_dbs.into_iter().find_map(|table_db| {
+                            crate::connection::fetch_columns_from_database(
+                                cid, &table_db, &table, &conn,
+                            )
+                            .filter(|c| !c.is_empty())
+                            .map(|c| (table_db, c))
+                        });
+                        let _ = sender.send((cid, table, found));
+                    });
+                }
+                None => {
+                    app.autocomplete_cols_warmed.insert(key.clone());
                 }
             }
-            app.autocomplete_cols_mem.insert(key, names);
         }
     }
-    out.sort_unstable();
-    if out.is_empty() { None } else { Some(out) }
+
+    // Promote whatever we resolved into the in-memory map.
+    let names: Vec<String> = cols?.into_iter().map(|(c, _)| c).collect();
+    app.autocomplete_cols_mem.insert(key, names.clone());
+    Some(names)
 }
 fn add_keywords(out: &mut Vec<String>, pref: &str) {
     // With no prefix yet (e.g. right after `FROM `), don't flood the popup with
@@ -636,7 +803,7 @@ fn collect_loaded_fks(app: &mut Tabular) -> Vec<crate::models::structs::ForeignK
 /// Parse alias → real-table-name mappings from a SQL string.
 /// Handles `FROM table alias`, `FROM table AS alias`, `JOIN table alias`, etc.
 /// Returns `HashMap<alias_lowercase, real_table_name_as_written>`.
-fn collect_alias_map(sql: &str) -> std::collections::HashMap<String, String> {
+pub(crate) fn collect_alias_map(sql: &str) -> std::collections::HashMap<String, String> {
     let bytes = sql.as_bytes();
     let lower = sql.to_ascii_lowercase();
     let lb = lower.as_bytes();
@@ -1090,10 +1257,8 @@ fn build_suggestions(
     match ctx {
         SqlContext::AfterSelect => {
             add_keywords(&mut out, &pl);
-            if let Some(cid) = conn_id
-                && let Some(cols) = get_cached_columns(app, cid, &db, tables_in_scope.clone())
-            {
-                for c in cols {
+            if let Some(cid) = conn_id {
+                for c in scope_columns(app, cid, &db, text, &tables_in_scope) {
                     if fuzzy_match(&pl, &c).is_some() {
                         out.push(c);
                     }
@@ -1168,10 +1333,8 @@ fn build_suggestions(
         }
         SqlContext::AfterWhere => {
             add_keywords(&mut out, &pl);
-            if let Some(cid) = conn_id
-                && let Some(cols) = get_cached_columns(app, cid, &db, tables_in_scope.clone())
-            {
-                for c in cols {
+            if let Some(cid) = conn_id {
+                for c in scope_columns(app, cid, &db, text, &tables_in_scope) {
                     if fuzzy_match(&pl, &c).is_some() {
                         out.push(c);
                     }
@@ -1182,20 +1345,7 @@ fn build_suggestions(
             add_keywords(&mut out, &pl);
             if let Some(cid) = conn_id {
                 let alias_map = collect_alias_map(text);
-                // Build real_lower → display_name map
-                let mut real_to_display: std::collections::HashMap<String, String> =
-                    std::collections::HashMap::new();
-                for t in &tables_in_scope {
-                    real_to_display
-                        .entry(t.to_ascii_lowercase())
-                        .or_insert(t.clone());
-                }
-                for (alias, real) in &alias_map {
-                    let real_lower = real.to_ascii_lowercase();
-                    if alias != &real_lower {
-                        real_to_display.insert(real_lower, alias.clone());
-                    }
-                }
+                let real_to_display = scope_display_names(&tables_in_scope, &alias_map);
                 // Suggest qualified `alias.column` names for all tables in scope
                 for table in &tables_in_scope {
                     let display = real_to_display
@@ -1233,11 +1383,9 @@ fn build_suggestions(
                         }
                     }
                 }
-                if let Some(cols) = get_cached_columns(app, cid, &db, tables_in_scope.clone()) {
-                    for c in cols {
-                        if fuzzy_match(&pl, &c).is_some() {
-                            out.push(c);
-                        }
+                for c in scope_columns(app, cid, &db, text, &tables_in_scope) {
+                    if fuzzy_match(&pl, &c).is_some() {
+                        out.push(c);
                     }
                 }
             } else {
@@ -1264,16 +1412,65 @@ fn build_suggestions(
     scored.into_iter().map(|(_, s)| s).collect()
 }
 
+/// Columns of a table fetched in the background for autocomplete: connection,
+/// table, and the database they were found in with their (name, type) pairs.
+pub type FetchedColumns = (i64, String, Option<(String, Vec<(String, String)>)>);
+
+/// True when the suggestions shown were not built for `pref`.
+fn prefix_changed(showing: bool, last_len: usize, last_prefix: &str, pref: &str) -> bool {
+    !showing || last_len != pref.len() || last_prefix != pref
+}
+
+/// Schedule a suggestion rebuild after an edit. Each edit restarts the
+/// debounce window, so a burst of keystrokes costs one rebuild against the
+/// text as it is when typing pauses (see `poll_autocomplete`).
 pub fn update_autocomplete(app: &mut Tabular) {
-    // Throttle autocomplete updates to avoid heavy work on every keystroke
-    let now = std::time::Instant::now();
-    if let Some(last) = app.autocomplete_last_update {
-        let elapsed = now.saturating_duration_since(last);
-        if elapsed < std::time::Duration::from_millis(app.autocomplete_debounce_ms) {
-            return;
+    app.autocomplete_last_update = Some(std::time::Instant::now());
+    app.autocomplete_pending = true;
+}
+
+/// Per-frame driver: stores columns fetched in the background and runs the
+/// scheduled rebuild once the debounce window has passed. Rebuilds always
+/// read the current buffer, so a late fetch never brings back suggestions
+/// for an older prefix.
+pub fn poll_autocomplete(app: &mut Tabular, ctx: &egui::Context) {
+    let mut columns_arrived = false;
+    while let Ok((cid, table, found)) = app.autocomplete_cols_receiver.try_recv() {
+        let key = (cid, table.to_ascii_lowercase());
+        app.autocomplete_cols_inflight.remove(&key);
+        app.autocomplete_cols_warmed.insert(key.clone());
+        if let Some((table_db, cols)) = found {
+            crate::cache_data::save_columns_to_cache(app, cid, &table_db, &table, &cols);
+            app.autocomplete_cols_mem
+                .insert(key, cols.into_iter().map(|(c, _)| c).collect());
+            columns_arrived = true;
         }
     }
-    app.autocomplete_last_update = Some(now);
+    if columns_arrived && app.show_autocomplete && !app.autocomplete_pending {
+        rebuild_autocomplete(app, true);
+        ctx.request_repaint();
+    }
+
+    if !app.autocomplete_pending {
+        return;
+    }
+    let debounce = std::time::Duration::from_millis(app.autocomplete_debounce_ms);
+    let elapsed = app
+        .autocomplete_last_update
+        .map(|t| t.elapsed())
+        .unwrap_or(debounce);
+    if elapsed < debounce {
+        ctx.request_repaint_after(debounce - elapsed);
+        return;
+    }
+    app.autocomplete_pending = false;
+    rebuild_autocomplete(app, columns_arrived);
+    ctx.request_repaint();
+}
+
+/// Rebuild suggestions for the prefix at the cursor. Skipped when they were
+/// already built for this prefix, unless `force` (new column metadata).
+fn rebuild_autocomplete(app: &mut Tabular, force: bool) {
     // Clone editor text first to avoid immutable + mutable borrow overlap
     let editor_text = app.editor.text.clone();
     let cursor = app.cursor_position.min(editor_text.len());
@@ -1342,18 +1539,16 @@ pub fn update_autocomplete(app: &mut Tabular) {
         return;
     }
 
-    // Only rebuild if prefix length changed (avoid redundant calls)
-    if app.last_autocomplete_trigger_len == pref.len()
-        && app.show_autocomplete
-        && app.autocomplete_prefix == pref
-    {
-        // Suggestions already up-to-date for this prefix
-        return;
-    }
-
+    // Only rebuild when the prefix changed (avoid redundant calls)
+    let changed = prefix_changed(
+        app.show_autocomplete,
+        app.last_autocomplete_trigger_len,
+        &app.autocomplete_prefix,
+        &pref,
+    );
     app.autocomplete_prefix = pref.clone();
 
-    if app.last_autocomplete_trigger_len != pref.len() || !app.show_autocomplete {
+    if force || changed {
         let context = detect_ctx(&editor_text, cursor);
         let suggestions = build_suggestions(app, &editor_text, cursor, &pref, context);
         if suggestions.is_empty() {
@@ -1526,6 +1721,7 @@ pub fn update_autocomplete(app: &mut Tabular) {
             app.autocomplete_notes = notes;
             app.autocomplete_payloads = payloads;
             app.selected_autocomplete_index = 0;
+            app.autocomplete_navigated = false;
         }
         app.last_autocomplete_trigger_len = pref.len();
     }
@@ -1558,6 +1754,8 @@ pub fn accept_current_suggestion(app: &mut Tabular) {
         app.autocomplete_protection_frames = app.autocomplete_protection_frames.max(8);
         app.editor_focus_boost_frames = app.editor_focus_boost_frames.max(6);
         app.show_autocomplete = false;
+        app.autocomplete_pending = false;
+        app.autocomplete_navigated = false;
         app.autocomplete_suggestions.clear();
         app.autocomplete_kinds.clear();
         app.autocomplete_notes.clear();
@@ -1565,6 +1763,64 @@ pub fn accept_current_suggestion(app: &mut Tabular) {
     }
 }
 
+/// What Enter does while the autocomplete popup is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnterAction {
+    /// Replace the prefix with the highlighted suggestion.
+    Accept,
+    /// The suggestion is already typed out: accept it (normalising case) and
+    /// still insert the newline the user asked for.
+    AcceptAndNewline,
+    /// Close the popup and let the editor insert a newline.
+    Newline,
+}
+
+pub fn enter_action(app: &Tabular) -> EnterAction {
+    let idx = app.selected_autocomplete_index;
+    match app.autocomplete_suggestions.get(idx) {
+        Some(suggestion) => enter_action_for(
+            &app.autocomplete_prefix,
+            suggestion,
+            app.autocomplete_payloads.get(idx).and_then(|p| p.as_deref()),
+            app.autocomplete_navigated,
+            app.autocomplete_suggestions.len(),
+        ),
+        None => EnterAction::Newline,
+    }
+}
+
+fn enter_action_for(
+    prefix: &str,
+    suggestion: &str,
+    payload: Option<&str>,
+    navigated: bool,
+    count: usize,
+) -> EnterAction {
+    let expands = payload.is_some_and(|p| p != suggestion);
+    if !expands && !prefix.is_empty() && prefix.eq_ignore_ascii_case(suggestion) {
+        return EnterAction::AcceptAndNewline;
+    }
+    if navigated
+        || count == 1
+        || (!prefix.is_empty()
+            && suggestion
+                .to_lowercase()
+                .starts_with(&prefix.to_lowercase()))
+    {
+        EnterAction::Accept
+    } else {
+        EnterAction::Newline
+    }
+}
+
+/// Close the popup without touching the buffer.
+pub fn dismiss(app: &mut Tabular) {
+    app.show_autocomplete = false;
+    app.autocomplete_pending = false;
+    app.autocomplete_navigated = false;
+    app.autocomplete_hover_index = None;
+}
+
 pub fn navigate(app: &mut Tabular, delta: i32) {
     if !app.show_autocomplete || app.autocomplete_suggestions.is_empty() {
         return;
@@ -1577,12 +1833,18 @@ pub fn navigate(app: &mut Tabular, delta: i32) {
     } else {
         app.selected_autocomplete_index -= 1;
     }
+    app.autocomplete_scroll_to_selected = true;
 }
 
-pub fn render_autocomplete(app: &mut Tabular, ui: &mut egui::Ui, pos: egui::Pos2) {
+/// Draw the popup next to `caret` (screen-space caret rect), flipping above the
+/// line when there is no room below, plus a documentation pane for the
+/// hovered or highlighted suggestion.
+pub fn render_autocomplete(app: &mut Tabular, ui: &mut egui::Ui, caret: egui::Rect) {
     if !app.show_autocomplete || app.autocomplete_suggestions.is_empty() {
         return;
     }
+    let scroll_to_selected = std::mem::take(&mut app.autocomplete_scroll_to_selected);
+    app.autocomplete_hover_index = None;
     let screen = ui.ctx().content_rect();
     let font_id = egui::TextStyle::Monospace.resolve(ui.style());
     let small_font_id = egui::TextStyle::Small.resolve(ui.style());
@@ -1645,20 +1907,21 @@ pub fn render_autocomplete(app: &mut Tabular, ui: &mut egui::Ui, pos: egui::Pos2
         desired_h = desired_cap;
     }
     let margin = 8.0;
-    let space_below = (screen.bottom() - pos.y - margin).max(0.0);
-    let space_above = (pos.y - screen.top() - margin).max(0.0);
+    let space_below = (screen.bottom() - caret.bottom() - margin).max(0.0);
+    let space_above = (caret.top() - screen.top() - margin).max(0.0);
     let show_above = space_below + 2.0 < desired_h && space_above > space_below;
     let max_h = desired_h.min(if show_above { space_above } else { space_below });
-    let mut popup_pos = pos;
+    let mut popup_pos = egui::pos2(caret.left(), caret.bottom() + 2.0);
     if show_above {
-        popup_pos.y = (pos.y - max_h).max(screen.top());
+        popup_pos.y = (caret.top() - 2.0 - max_h).max(screen.top());
     }
     if popup_pos.x + popup_w > screen.right() {
-        popup_pos.x = (screen.right() - popup_w).max(screen.left());
+        popup_pos.x = screen.right() - popup_w;
     }
-    
+    popup_pos.x = popup_pos.x.max(screen.left());
+
     // nice shadow and generic window styles
-    egui::Area::new(egui::Id::new("autocomplete_popup"))
+    let popup_rect = egui::Area::new(egui::Id::new("autocomplete_popup"))
         .fixed_pos(popup_pos)
         .order(egui::Order::Foreground)
         .show(ui.ctx(), |ui| {
@@ -1739,6 +2002,12 @@ pub fn render_autocomplete(app: &mut Tabular, ui: &mut egui::Ui, pos: egui::Pos2
                                 let available_width = ui.available_width();
                                 let (rect, response) = ui.allocate_exact_size(egui::vec2(available_width, row_height), egui::Sense::click());
                                 
+                                if response.hovered() {
+                                    app.autocomplete_hover_index = Some(i);
+                                }
+                                if selected && scroll_to_selected {
+                                    response.scroll_to_me(None);
+                                }
                                 if ui.is_rect_visible(rect) {
                                     if selected {
                                         let sel_color = if ui.visuals().dark_mode {
@@ -1820,11 +2089,89 @@ pub fn render_autocomplete(app: &mut Tabular, ui: &mut egui::Ui, pos: egui::Pos2
                             }
                         });
                 });
+        })
+        .response
+        .rect;
+
+    if app.show_autocomplete {
+        render_documentation_pane(app, ui.ctx(), popup_rect, screen);
+    }
+}
+
+/// Side pane describing the hovered suggestion, falling back to the one
+/// highlighted from the keyboard.
+fn render_documentation_pane(
+    app: &Tabular,
+    ctx: &egui::Context,
+    popup_rect: egui::Rect,
+    screen: egui::Rect,
+) {
+    let idx = app
+        .autocomplete_hover_index
+        .unwrap_or(app.selected_autocomplete_index);
+    let Some(label) = app.autocomplete_suggestions.get(idx) else {
+        return;
+    };
+    let note = app.autocomplete_notes.get(idx).and_then(|n| n.as_deref());
+    let payload = app
+        .autocomplete_payloads
+        .get(idx)
+        .and_then(|p| p.as_deref())
+        .filter(|p| p != label);
+    if note.is_none() && payload.is_none() {
+        return;
+    }
+    let kind = app.autocomplete_kinds.get(idx).map(|k| match k {
+        crate::models::enums::AutocompleteKind::Table => "Table",
+        crate::models::enums::AutocompleteKind::Column => "Column",
+        crate::models::enums::AutocompleteKind::Syntax => "Keyword",
+        crate::models::enums::AutocompleteKind::Snippet => "Snippet",
+        crate::models::enums::AutocompleteKind::Parameter => "Parameter",
+    });
+
+    let pane_w = 260.0;
+    let gap = 4.0;
+    let x = if popup_rect.right() + gap + pane_w <= screen.right() {
+        popup_rect.right() + gap
+    } else {
+        (popup_rect.left() - gap - pane_w).max(screen.left())
+    };
+    egui::Area::new(egui::Id::new("autocomplete_doc_pane"))
+        .fixed_pos(egui::pos2(x, popup_rect.top()))
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .show(ctx, |ui| {
+            let (bg_fill, stroke_color) = if ui.visuals().dark_mode {
+                (egui::Color32::from_rgb(30, 30, 35), egui::Color32::from_rgb(100, 100, 110))
+            } else {
+                (egui::Color32::from_rgb(250, 250, 250), egui::Color32::from_rgb(180, 180, 190))
+            };
+            egui::Frame::new()
+                .fill(bg_fill)
+                .stroke(egui::Stroke::new(1.0, stroke_color))
+                .inner_margin(egui::Margin::same(8))
+                .show(ui, |ui| {
+                    ui.set_width(pane_w - 16.0);
+                    ui.label(egui::RichText::new(label).monospace().strong());
+                    if let Some(kind) = kind {
+                        ui.label(egui::RichText::new(kind).small().weak());
+                    }
+                    if let Some(note) = note {
+                        ui.add_space(4.0);
+                        ui.add(egui::Label::new(note).wrap());
+                    }
+                    if let Some(payload) = payload {
+                        ui.add_space(4.0);
+                        ui.label(egui::RichText::new("Inserts:").small().weak());
+                        ui.add(egui::Label::new(egui::RichText::new(payload).monospace()).wrap());
+                    }
+                });
         });
 }
 
 pub fn trigger_manual(app: &mut Tabular) {
-    update_autocomplete(app);
+    app.autocomplete_pending = false;
+    rebuild_autocomplete(app, true);
     if app.autocomplete_prefix.is_empty() {
         app.autocomplete_suggestions = SQL_KEYWORDS.iter().map(|s| s.to_string()).collect();
         app.autocomplete_suggestions.sort_unstable();
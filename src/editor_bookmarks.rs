@@ -0,0 +1,288 @@
+//! Named bookmarks on editor lines, kept per tab and saved with the workspace
+//! profile. Bookmarks follow their line as text is inserted or deleted above
+//! them; a bookmark whose whole line is deleted goes away with it.
+
+use eframe::egui;
+
+use crate::{models::structs::EditorBookmark, window_egui};
+
+/// Longest label taken from the bookmarked line.
+const LABEL_CHARS: usize = 40;
+
+/// Default label: the line's text without a leading `--`, or `Line N`.
+fn default_label(line_text: &str, line: usize) -> String {
+    let text = line_text.trim();
+    let text = text.strip_prefix("--").unwrap_or(text).trim();
+    if text.is_empty() {
+        return format!("Line {}", line + 1);
+    }
+    let mut label: String = text.chars().take(LABEL_CHARS).collect();
+    if text.chars().count() > LABEL_CHARS {
+        label.push('…');
+    }
+    label
+}
+
+/// Move bookmarks from their lines in `old` to the same lines in `new`. The
+/// edit is taken to be the span between the common prefix and suffix.
+pub(crate) fn shift_bookmarks(bookmarks: &mut Vec<EditorBookmark>, old: &str, new: &str) {
+    let (a, b) = (old.as_bytes(), new.as_bytes());
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let newlines = |s: &[u8]| s.iter().filter(|&&c| c == b'\n').count();
+    let removed = newlines(&a[prefix..a.len() - suffix]);
+    let inserted = newlines(&b[prefix..b.len() - suffix]);
+    if removed == 0 && inserted == 0 {
+        return;
+    }
+    let start = newlines(&a[..prefix]);
+    // An edit starting at column 0 leaves nothing of the start line in place
+    let at_line_start = prefix == 0 || a[prefix - 1] == b'\n';
+    bookmarks.retain_mut(|bookmark| {
+        let line = bookmark.line;
+        if line < start || (line == start && !at_line_start) {
+            return true;
+        }
+        if line < start + removed {
+            // The whole line was deleted
+            return false;
+        }
+        bookmark.line = line + inserted - removed;
+        true
+    });
+}
+
+/// Keep the active tab's bookmarks in step with edits made since the last
+/// call. Only tabs with bookmarks keep a copy of the text to compare against.
+pub(crate) fn sync_bookmarks(tabular: &mut window_egui::Tabular) {
+    let index = tabular.active_tab_index;
+    let Some(tab) = tabular.query_tabs.get_mut(index) else {
+        return;
+    };
+    if tab.bookmarks.is_empty() {
+        tabular.bookmark_snapshot = None;
+        return;
+    }
+    if let Some((snapshot_index, text)) = &tabular.bookmark_snapshot
+        && *snapshot_index == index
+    {
+        if *text == tabular.editor.text {
+            return;
+        }
+        shift_bookmarks(&mut tab.bookmarks, text, &tabular.editor.text);
+        let last = tabular.editor.line_count().saturating_sub(1);
+        for bookmark in tab.bookmarks.iter_mut() {
+            bookmark.line = bookmark.line.min(last);
+        }
+        tab.bookmarks.sort_by_key(|b| b.line);
+        tab.bookmarks.dedup_by_key(|b| b.line);
+    }
+    tabular.bookmark_snapshot = Some((index, tabular.editor.text.clone()));
+}
+
+fn caret_line(tabular: &window_egui::Tabular) -> usize {
+    let caret = tabular.cursor_position.min(tabular.editor.text.len());
+    tabular.editor.offset_to_line_col(caret).0
+}
+
+/// Set or clear the bookmark on the caret's line.
+pub(crate) fn toggle_bookmark(tabular: &mut window_egui::Tabular) {
+    sync_bookmarks(tabular);
+    let line = caret_line(tabular);
+    let start = tabular.editor.line_start(line);
+    let end = tabular.editor.text[start..]
+        .find('\n')
+        .map_or(tabular.editor.text.len(), |p| start + p);
+    let label = default_label(&tabular.editor.text[start..end], line);
+    let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+        return;
+    };
+    match tab.bookmarks.iter().position(|b| b.line == line) {
+        Some(i) => {
+            tab.bookmarks.remove(i);
+        }
+        None => {
+            tab.bookmarks.push(EditorBookmark { line, label });
+            tab.bookmarks.sort_by_key(|b| b.line);
+        }
+    }
+    tabular.bookmark_snapshot = Some((tabular.active_tab_index, tabular.editor.text.clone()));
+}
+
+/// Move the caret to the start of bookmarked `line` and scroll it into view.
+pub(crate) fn jump_to_line(tabular: &mut window_egui::Tabular, line: usize) {
+    let offset = tabular.editor.line_start(line);
+    tabular.cursor_position = offset;
+    tabular.selection_start = offset;
+    tabular.selection_end = offset;
+    tabular.selected_text.clear();
+    tabular.pending_cursor_set = Some(offset);
+    tabular.editor_focus_request = true;
+    tabular.editor_scroll_to_cursor = true;
+}
+
+/// Jump to the next bookmark after the caret (or the previous one before
+/// it), wrapping around the buffer.
+pub(crate) fn cycle_bookmark(tabular: &mut window_egui::Tabular, forward: bool) {
+    sync_bookmarks(tabular);
+    let current = caret_line(tabular);
+    let Some(lines) = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .map(|t| t.bookmarks.iter().map(|b| b.line).collect::<Vec<_>>())
+    else {
+        return;
+    };
+    let target = if forward {
+        lines.iter().find(|&&l| l > current).or(lines.first())
+    } else {
+        lines.iter().rev().find(|&&l| l < current).or(lines.last())
+    };
+    match target {
+        Some(&line) => jump_to_line(tabular, line),
+        None => tabular.toasts.info("No bookmarks in this tab"),
+    }
+}
+
+/// Paint a marker in the gutter for a bookmarked line whose first row sits
+/// at `y`.
+pub(crate) fn paint_gutter_marker(
+    painter: &egui::Painter,
+    gutter: egui::Rect,
+    y: f32,
+    row_height: f32,
+) {
+    let rect = egui::Rect::from_min_size(
+        egui::pos2(gutter.left() + 3.0, y + row_height * 0.2),
+        egui::vec2(5.0, row_height * 0.6),
+    );
+    painter.rect_filled(rect, 1.0, egui::Color32::from_rgb(80, 150, 255));
+}
+
+/// Bookmark dropdown for the editor's action cluster: jump to, rename or
+/// remove a bookmark.
+pub(crate) fn render_bookmark_menu(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
+    let count = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .map_or(0, |t| t.bookmarks.len());
+    if count == 0 {
+        return;
+    }
+    let mut jump = None;
+    let mut remove = None;
+    ui.menu_button(format!("🔖 {}", count), |ui| {
+        let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) else {
+            return;
+        };
+        egui::Grid::new("editor_bookmarks_menu")
+            .num_columns(3)
+            .spacing([6.0, 2.0])
+            .show(ui, |ui| {
+                for (i, bookmark) in tab.bookmarks.iter_mut().enumerate() {
+                    if ui
+                        .button(format!("{:>4}", bookmark.line + 1))
+                        .on_hover_text("Jump to this line")
+                        .clicked()
+                    {
+                        jump = Some(bookmark.line);
+                    }
+                    ui.add(egui::TextEdit::singleline(&mut bookmark.label).desired_width(200.0));
+                    if ui
+                        .small_button("✕")
+                        .on_hover_text("Remove bookmark")
+                        .clicked()
+                    {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+        if remove.is_some() || jump.is_some() {
+            ui.close();
+        }
+    })
+    .response
+    .on_hover_text(format!(
+        "Bookmarks ({} toggles, {} / {} jump)",
+        tabular
+            .keybindings
+            .hint(window_egui::keybindings::Action::ToggleBookmark),
+        tabular
+            .keybindings
+            .hint(window_egui::keybindings::Action::NextBookmark),
+        tabular
+            .keybindings
+            .hint(window_egui::keybindings::Action::PreviousBookmark),
+    ));
+    if let Some(i) = remove
+        && let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index)
+        && i < tab.bookmarks.len()
+    {
+        tab.bookmarks.remove(i);
+    }
+    if let Some(line) = jump {
+        jump_to_line(tabular, line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marks(lines: &[usize]) -> Vec<EditorBookmark> {
+        lines
+            .iter()
+            .map(|&line| EditorBookmark {
+                line,
+                label: String::new(),
+            })
+            .collect()
+    }
+
+    fn lines(bookmarks: &[EditorBookmark]) -> Vec<usize> {
+        bookmarks.iter().map(|b| b.line).collect()
+    }
+
+    #[test]
+    fn bookmarks_follow_inserted_and_deleted_lines() {
+        let old = "a\nb\nc\nd\n";
+        // Two lines inserted above line 1
+        let mut bookmarks = marks(&[0, 1, 3]);
+        shift_bookmarks(&mut bookmarks, old, "a\nx\ny\nb\nc\nd\n");
+        assert_eq!(lines(&bookmarks), vec![0, 3, 5]);
+
+        // Line "b" deleted: its bookmark goes, the ones below move up
+        let mut bookmarks = marks(&[0, 1, 3]);
+        shift_bookmarks(&mut bookmarks, old, "a\nc\nd\n");
+        assert_eq!(lines(&bookmarks), vec![0, 2]);
+
+        // Enter at column 0 pushes the bookmarked line down; Enter at its end does not
+        let mut bookmarks = marks(&[1]);
+        shift_bookmarks(&mut bookmarks, "a\nbb\nc", "a\n\nbb\nc");
+        assert_eq!(lines(&bookmarks), vec![2]);
+        let mut bookmarks = marks(&[1]);
+        shift_bookmarks(&mut bookmarks, "a\nbb\nc", "a\nbb\n\nc");
+        assert_eq!(lines(&bookmarks), vec![1]);
+
+        // Typing within a line moves nothing
+        let mut bookmarks = marks(&[1, 2]);
+        shift_bookmarks(&mut bookmarks, old, "a\nbé!\nc\nd\n");
+        assert_eq!(lines(&bookmarks), vec![1, 2]);
+    }
+
+    #[test]
+    fn labels_come_from_the_line() {
+        assert_eq!(
+            default_label("  -- Section 2: users", 4),
+            "Section 2: users"
+        );
+        assert_eq!(default_label("   ", 4), "Line 5");
+        assert!(default_label(&"x".repeat(60), 0).ends_with('…'));
+    }
+}
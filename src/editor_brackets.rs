@@ -0,0 +1,217 @@
+//! Bracket matching for the SQL editor: the bracket next to the caret and its
+//! partner, ignoring brackets inside string literals, quoted identifiers and
+//! comments. The pair is painted onto the highlighted layout job.
+
+use eframe::egui::{Color32, text::LayoutJob};
+use std::ops::Range;
+
+/// Result of matching the bracket next to the caret (byte offsets).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BracketMatch {
+    /// The bracket at the caret and its partner.
+    Matched(usize, usize),
+    /// The bracket at the caret has no partner, or a bracket of the wrong
+    /// kind closes it.
+    Unbalanced(usize),
+}
+
+/// The partner of a bracket character and whether it is an opener.
+fn partner(c: u8) -> Option<(u8, bool)> {
+    match c {
+        b'(' => Some((b')', true)),
+        b'[' => Some((b']', true)),
+        b'{' => Some((b'}', true)),
+        b')' => Some((b'(', false)),
+        b']' => Some((b'[', false)),
+        b'}' => Some((b'{', false)),
+        _ => None,
+    }
+}
+
+/// Byte offsets of every bracket outside strings, quoted identifiers and
+/// comments. All delimiters are ASCII, so scanning bytes is UTF-8 safe.
+fn code_brackets(text: &str) -> Vec<usize> {
+    let bytes = text.as_bytes();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = text[i + 2..].find("*/").map_or(bytes.len(), |p| i + p + 4);
+            }
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() {
+                    if bytes[i] == quote {
+                        // A doubled quote is an escaped quote
+                        if bytes.get(i + 1) == Some(&quote) {
+                            i += 2;
+                            continue;
+                        }
+                        break;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            c => {
+                if partner(c).is_some() {
+                    out.push(i);
+                }
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Match the bracket right after the caret, or else the one right before it.
+/// Returns `None` when the caret is not next to a bracket in code.
+pub fn match_at(text: &str, caret: usize) -> Option<BracketMatch> {
+    let bytes = text.as_bytes();
+    let caret = caret.min(bytes.len());
+    let is_bracket = |p: usize| bytes.get(p).is_some_and(|&c| partner(c).is_some());
+    // Cheap check first so most frames skip the scan
+    if !is_bracket(caret) && !caret.checked_sub(1).is_some_and(is_bracket) {
+        return None;
+    }
+    let brackets = code_brackets(text);
+    let idx = [Some(caret), caret.checked_sub(1)]
+        .into_iter()
+        .flatten()
+        .find_map(|p| brackets.binary_search(&p).ok())?;
+    let pos = brackets[idx];
+    let (want, opening) = partner(bytes[pos])?;
+    // Scan outward: forward from an opener, backward from a closer
+    let outward: Vec<usize> = if opening {
+        brackets[idx + 1..].to_vec()
+    } else {
+        brackets[..idx].iter().rev().copied().collect()
+    };
+    let mut nested = Vec::new();
+    for p in outward {
+        let c = bytes[p];
+        let (_, c_opening) = partner(c)?;
+        if c_opening == opening {
+            nested.push(c);
+            continue;
+        }
+        match nested.pop() {
+            Some(inner) if partner(inner).map(|(w, _)| w) == Some(c) => {}
+            Some(_) => return Some(BracketMatch::Unbalanced(pos)),
+            None if c == want => return Some(BracketMatch::Matched(pos, p)),
+            None => return Some(BracketMatch::Unbalanced(pos)),
+        }
+    }
+    Some(BracketMatch::Unbalanced(pos))
+}
+
+/// Give `range` of `job` a background, splitting sections as needed.
+fn paint(job: &mut LayoutJob, range: Range<usize>, background: Color32) {
+    let mut sections = Vec::with_capacity(job.sections.len() + 2);
+    for section in job.sections.drain(..) {
+        let r = section.byte_range.start.0..section.byte_range.end.0;
+        if r.end <= range.start || r.start >= range.end {
+            sections.push(section);
+            continue;
+        }
+        let cuts = [
+            r.start,
+            range.start.max(r.start),
+            range.end.min(r.end),
+            r.end,
+        ];
+        for (k, w) in cuts.windows(2).enumerate() {
+            if w[0] >= w[1] {
+                continue;
+            }
+            let mut piece = section.clone();
+            piece.byte_range = w[0].into()..w[1].into();
+            if w[0] != r.start {
+                piece.leading_space = 0.0;
+            }
+            if k == 1 {
+                piece.format.background = background;
+            }
+            sections.push(piece);
+        }
+    }
+    job.sections = sections;
+}
+
+/// Paint a bracket match onto the editor's layout job. Offsets that no longer
+/// point at a bracket (the text changed since matching) are skipped.
+pub fn highlight(job: &mut LayoutJob, found: BracketMatch, dark: bool) {
+    let (positions, background) = match found {
+        BracketMatch::Matched(a, b) => (
+            vec![a, b],
+            if dark {
+                Color32::from_rgba_unmultiplied(120, 160, 255, 70)
+            } else {
+                Color32::from_rgba_unmultiplied(60, 110, 220, 55)
+            },
+        ),
+        BracketMatch::Unbalanced(a) => (
+            vec![a],
+            Color32::from_rgba_unmultiplied(230, 60, 60, if dark { 90 } else { 70 }),
+        ),
+    };
+    for p in positions {
+        if job
+            .text
+            .as_bytes()
+            .get(p)
+            .is_some_and(|&c| partner(c).is_some())
+        {
+            paint(job, p..p + 1, background);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_nested_and_unbalanced() {
+        let sql = "SELECT COUNT(*) FROM (SELECT id FROM t) x";
+        // Caret before the subquery's opener, and after its closer
+        assert_eq!(match_at(sql, 21), Some(BracketMatch::Matched(21, 38)));
+        assert_eq!(match_at(sql, 39), Some(BracketMatch::Matched(38, 21)));
+        assert_eq!(match_at(sql, 12), Some(BracketMatch::Matched(12, 14)));
+        assert_eq!(match_at(sql, 3), None);
+
+        assert_eq!(
+            match_at("SELECT (1 + 2", 7),
+            Some(BracketMatch::Unbalanced(7))
+        );
+        assert_eq!(
+            match_at("SELECT 1 + 2)", 12),
+            Some(BracketMatch::Unbalanced(12))
+        );
+        // Wrong kind of closer
+        assert_eq!(match_at("([)]", 0), Some(BracketMatch::Unbalanced(0)));
+        assert_eq!(match_at("{[()]}", 0), Some(BracketMatch::Matched(0, 5)));
+    }
+
+    #[test]
+    fn test_match_ignores_strings_and_comments() {
+        let sql = "SELECT concat(')', \"a(b\") -- )\nFROM t";
+        assert_eq!(match_at(sql, 13), Some(BracketMatch::Matched(13, 24)));
+        assert_eq!(
+            match_at("f('it''s (', x)", 1),
+            Some(BracketMatch::Matched(1, 14))
+        );
+        assert_eq!(
+            match_at("f(/* ) */ 1)", 1),
+            Some(BracketMatch::Matched(1, 11))
+        );
+        // A bracket inside a string is not matched at all
+        assert_eq!(match_at("SELECT '(' ", 8), None);
+    }
+}
@@ -0,0 +1,217 @@
+//! Line-based diff of two editor buffers (Myers O(ND) algorithm).
+//! Used by the "Compare tab with..." view; purely textual, no DB access.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffOp {
+    Equal,
+    /// Line only present on the left side.
+    Delete,
+    /// Line only present on the right side.
+    Insert,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    /// 0-based line index in the left buffer (None for inserts).
+    pub left: Option<usize>,
+    /// 0-based line index in the right buffer (None for deletes).
+    pub right: Option<usize>,
+    pub text: String,
+}
+
+/// One visual row of the side-by-side view. A removed block followed by an
+/// added block is paired row by row so modified lines sit next to each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SideBySideRow {
+    pub left: Option<(usize, String)>,
+    pub right: Option<(usize, String)>,
+    pub changed: bool,
+}
+
+/// Diff `left` against `right` line by line, returning the edit script in order.
+pub fn diff_lines(left: &str, right: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = left.lines().collect();
+    let b: Vec<&str> = right.lines().collect();
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let max = (n + m) as usize;
+    let offset = max as isize;
+
+    // Forward pass: record the furthest-reaching x per diagonal for each d.
+    let mut v = vec![0isize; 2 * max + 2];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    'outer: for d in 0..=max as isize {
+        trace.push(v.clone());
+        let mut k = -d;
+        while k <= d {
+            let idx = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                break 'outer;
+            }
+            k += 2;
+        }
+    }
+
+    // Backtrack through the recorded frontiers to rebuild the script.
+    let mut out = Vec::with_capacity(a.len().max(b.len()));
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[(k - 1 + offset) as usize] < v[(k + 1 + offset) as usize]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[(prev_k + offset) as usize];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            out.push(DiffLine {
+                op: DiffOp::Equal,
+                left: Some(x as usize),
+                right: Some(y as usize),
+                text: a[x as usize].to_string(),
+            });
+        }
+        if d > 0 {
+            if x == prev_x {
+                y -= 1;
+                out.push(DiffLine {
+                    op: DiffOp::Insert,
+                    left: None,
+                    right: Some(y as usize),
+                    text: b[y as usize].to_string(),
+                });
+            } else {
+                x -= 1;
+                out.push(DiffLine {
+                    op: DiffOp::Delete,
+                    left: Some(x as usize),
+                    right: None,
+                    text: a[x as usize].to_string(),
+                });
+            }
+        }
+    }
+    out.reverse();
+    out
+}
+
+/// Arrange an edit script into side-by-side rows.
+pub fn side_by_side(script: &[DiffLine]) -> Vec<SideBySideRow> {
+    let mut rows = Vec::with_capacity(script.len());
+    let mut i = 0;
+    while i < script.len() {
+        let line = &script[i];
+        if line.op == DiffOp::Equal {
+            rows.push(SideBySideRow {
+                left: line.left.map(|l| (l, line.text.clone())),
+                right: line.right.map(|r| (r, line.text.clone())),
+                changed: false,
+            });
+            i += 1;
+            continue;
+        }
+        let mut deletes = Vec::new();
+        let mut inserts = Vec::new();
+        while i < script.len() && script[i].op != DiffOp::Equal {
+            let l = &script[i];
+            match l.op {
+                DiffOp::Delete => deletes.push((l.left.unwrap_or(0), l.text.clone())),
+                DiffOp::Insert => inserts.push((l.right.unwrap_or(0), l.text.clone())),
+                DiffOp::Equal => {}
+            }
+            i += 1;
+        }
+        let len = deletes.len().max(inserts.len());
+        let mut deletes = deletes.into_iter();
+        let mut inserts = inserts.into_iter();
+        for _ in 0..len {
+            rows.push(SideBySideRow {
+                left: deletes.next(),
+                right: inserts.next(),
+                changed: true,
+            });
+        }
+    }
+    rows
+}
+
+/// Unified ("merged") rendering of the script with `-`/`+`/space prefixes.
+pub fn unified_text(script: &[DiffLine]) -> String {
+    let mut out = String::new();
+    for line in script {
+        let prefix = match line.op {
+            DiffOp::Equal => ' ',
+            DiffOp::Delete => '-',
+            DiffOp::Insert => '+',
+        };
+        out.push(prefix);
+        out.push_str(&line.text);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ops(script: &[DiffLine]) -> String {
+        script
+            .iter()
+            .map(|l| match l.op {
+                DiffOp::Equal => '=',
+                DiffOp::Delete => '-',
+                DiffOp::Insert => '+',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identical_and_empty_inputs() {
+        let script = diff_lines("select 1\nfrom t", "select 1\nfrom t");
+        assert_eq!(ops(&script), "==");
+        assert!(diff_lines("", "").is_empty());
+        assert_eq!(ops(&diff_lines("", "a\nb")), "++");
+        assert_eq!(ops(&diff_lines("a\nb", "")), "--");
+    }
+
+    #[test]
+    fn minimal_script_for_changed_line() {
+        let left = "SELECT id\nFROM users\nWHERE active = 1\nORDER BY id";
+        let right = "SELECT id, name\nFROM users\nWHERE active = 1\nLIMIT 10\nORDER BY id";
+        let script = diff_lines(left, right);
+        assert_eq!(ops(&script), "-+==+=");
+        assert_eq!(
+            unified_text(&script),
+            "-SELECT id\n+SELECT id, name\n FROM users\n WHERE active = 1\n+LIMIT 10\n ORDER BY id\n"
+        );
+    }
+
+    #[test]
+    fn side_by_side_pairs_replacements() {
+        let script = diff_lines("a\nb\nc", "a\nx\ny\nc");
+        let rows = side_by_side(&script);
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows[1].left, Some((1, "b".to_string())));
+        assert_eq!(rows[1].right, Some((1, "x".to_string())));
+        assert!(rows[2].changed && rows[2].left.is_none());
+        assert_eq!(rows[3].left, Some((2, "c".to_string())));
+        assert_eq!(rows[3].right, Some((3, "c".to_string())));
+    }
+}
@@ -0,0 +1,251 @@
+//! "Paste as IN List": turns a column of values copied from a spreadsheet (or a
+//! comma-separated line) into `IN ('a', 'b', 'c')` at the caret. Values are
+//! trimmed, blank lines dropped, and strings quoted with `'` doubled.
+
+use eframe::egui;
+
+use crate::{editor_autocomplete, window_egui};
+
+/// State of the dialog; the options outlive it for the session.
+#[derive(Clone, Debug)]
+pub struct InListDialog {
+    pub open: bool,
+    pub source: String,
+    /// Leave values unquoted. Set from the pasted values, then up to the user.
+    pub numeric: bool,
+    pub remove_duplicates: bool,
+    /// Values per line before breaking; 0 keeps the list on one line.
+    pub wrap_after: usize,
+    /// Ask the platform for the clipboard once the input has focus.
+    pub paste_requested: bool,
+}
+
+impl Default for InListDialog {
+    fn default() -> Self {
+        Self {
+            open: false,
+            source: String::new(),
+            numeric: false,
+            remove_duplicates: true,
+            wrap_after: 10,
+            paste_requested: false,
+        }
+    }
+}
+
+/// Values from pasted text: one per line or comma/tab separated, trimmed, with
+/// surrounding quotes from an earlier list removed.
+fn parse_values(source: &str, remove_duplicates: bool) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    source
+        .split(['\n', '\r', ',', '\t'])
+        .map(str::trim)
+        .map(|value| {
+            ['\'', '"']
+                .iter()
+                .find_map(|q| value.strip_prefix(*q)?.strip_suffix(*q))
+                .unwrap_or(value)
+        })
+        .filter(|value| !value.is_empty())
+        .filter(|value| !remove_duplicates || seen.insert(*value))
+        .map(str::to_string)
+        .collect()
+}
+
+fn is_number(value: &str) -> bool {
+    value.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// The parenthesised list, `wrap_after` values per line (0: one line). The
+/// caller indents continuation lines to match the caret's line.
+fn format_in_list(values: &[String], numeric: bool, wrap_after: usize) -> String {
+    let items: Vec<String> = values
+        .iter()
+        .map(|value| {
+            if numeric {
+                value.clone()
+            } else {
+                format!("'{}'", value.replace('\'', "''"))
+            }
+        })
+        .collect();
+    if wrap_after == 0 || items.len() <= wrap_after {
+        return format!("({})", items.join(", "));
+    }
+    let lines: Vec<String> = items
+        .chunks(wrap_after)
+        .map(|chunk| format!("    {}", chunk.join(", ")))
+        .collect();
+    format!("(\n{}\n)", lines.join(",\n"))
+}
+
+/// What to insert at `caret`: the bare items after an open `(`, the
+/// parenthesised list after `IN`, otherwise `IN (...)`.
+fn insertion_for(text: &str, caret: usize, list: &str) -> String {
+    let before = text[..caret].trim_end();
+    if before.ends_with('(') {
+        let inner = &list[1..list.len() - 1];
+        return inner.to_string();
+    }
+    let last_word = before
+        .rsplit(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default();
+    let space = if text[..caret].ends_with(char::is_whitespace) || caret == 0 {
+        ""
+    } else {
+        " "
+    };
+    if last_word.eq_ignore_ascii_case("in") {
+        format!("{}{}", space, list)
+    } else {
+        format!("{}IN {}", space, list)
+    }
+}
+
+pub(crate) fn open_in_list_dialog(tabular: &mut window_egui::Tabular) {
+    let dialog = &mut tabular.in_list_dialog;
+    dialog.open = true;
+    dialog.source.clear();
+    dialog.paste_requested = true;
+}
+
+fn insert_in_list(tabular: &mut window_egui::Tabular, list: &str) {
+    let text = &tabular.editor.text;
+    let start = tabular
+        .selection_start
+        .min(tabular.selection_end)
+        .min(text.len());
+    let end = tabular
+        .selection_start
+        .max(tabular.selection_end)
+        .min(text.len());
+    let (start, end) = if start < end {
+        (start, end)
+    } else {
+        let caret = tabular.cursor_position.min(text.len());
+        (caret, caret)
+    };
+    let line_start = text[..start].rfind('\n').map_or(0, |p| p + 1);
+    let indent: String = text[line_start..start]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect();
+    let list = list.replace('\n', &format!("\n{}", indent));
+    let insertion = insertion_for(text, start, &list);
+
+    tabular.editor.apply_single_replace(start..end, &insertion);
+    let caret = start + insertion.len();
+    tabular.cursor_position = caret;
+    tabular.selection_start = caret;
+    tabular.selection_end = caret;
+    tabular.selected_text.clear();
+    tabular.multi_selection.set_primary_range(caret, caret);
+    tabular.pending_cursor_set = Some(caret);
+    tabular.editor_focus_boost_frames = tabular.editor_focus_boost_frames.max(4);
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.content = tabular.editor.text.clone();
+        tab.is_modified = true;
+    } else {
+        tabular.editor.mark_text_modified();
+    }
+    editor_autocomplete::update_autocomplete(tabular);
+}
+
+pub(crate) fn render_in_list_dialog(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let mut open = true;
+    let mut insert: Option<String> = None;
+    let dialog = &mut tabular.in_list_dialog;
+    egui::Window::new("Paste as IN List")
+        .open(&mut open)
+        .collapsible(false)
+        .default_size(egui::vec2(460.0, 380.0))
+        .show(ctx, |ui| {
+            ui.label("Values, one per line or comma separated:");
+            let resp = ui.add(
+                egui::TextEdit::multiline(&mut dialog.source)
+                    .desired_rows(8)
+                    .desired_width(f32::INFINITY)
+                    .code_editor()
+                    .hint_text("Paste a column from a spreadsheet (⌘V / Ctrl+V)"),
+            );
+            if dialog.paste_requested {
+                if resp.has_focus() {
+                    dialog.paste_requested = false;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::RequestPaste);
+                } else {
+                    resp.request_focus();
+                }
+            }
+            let values = parse_values(&dialog.source, dialog.remove_duplicates);
+            if resp.changed() {
+                dialog.numeric = !values.is_empty() && values.iter().all(|v| is_number(v));
+            }
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut dialog.numeric, "Numbers (no quotes)");
+                ui.checkbox(&mut dialog.remove_duplicates, "Remove duplicates");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Break lines after");
+                ui.add(egui::DragValue::new(&mut dialog.wrap_after).range(0..=1000));
+                ui.label("values (0: one line)");
+            });
+            ui.separator();
+
+            let list = format_in_list(&values, dialog.numeric, dialog.wrap_after);
+            ui.label(
+                egui::RichText::new(format!("{} value(s)", values.len()))
+                    .weak()
+                    .size(11.0),
+            );
+            egui::ScrollArea::vertical()
+                .max_height(120.0)
+                .show(ui, |ui| {
+                    ui.add(egui::Label::new(egui::RichText::new(&list).monospace()).wrap());
+                });
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(!values.is_empty(), egui::Button::new("Insert at Caret"))
+                    .clicked()
+                {
+                    insert = Some(list.clone());
+                }
+                if ui.button("Cancel").clicked() {
+                    dialog.open = false;
+                }
+            });
+        });
+    if !open {
+        tabular.in_list_dialog.open = false;
+    }
+    if let Some(list) = insert {
+        tabular.in_list_dialog.open = false;
+        insert_in_list(tabular, &list);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pasted_values_become_in_list() {
+        let values = parse_values("  a \n\nO'Brien\r\n'a'\n\tb, c\n", true);
+        assert_eq!(values, vec!["a", "O'Brien", "b", "c"]);
+        assert_eq!(
+            format_in_list(&values, false, 0),
+            "('a', 'O''Brien', 'b', 'c')"
+        );
+        let ids = parse_values("1\n2\n3\n2", false);
+        assert!(ids.iter().all(|v| is_number(v)));
+        assert_eq!(format_in_list(&ids, true, 0), "(1, 2, 3, 2)");
+        assert_eq!(format_in_list(&ids, true, 3), "(\n    1, 2, 3,\n    2\n)");
+
+        let list = "(1, 2)";
+        assert_eq!(insertion_for("WHERE id ", 9, list), "IN (1, 2)");
+        assert_eq!(insertion_for("WHERE id in", 11, list), " (1, 2)");
+        assert_eq!(insertion_for("WHERE id IN (", 13, list), "1, 2");
+    }
+}
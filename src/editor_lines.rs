@@ -0,0 +1,160 @@
+//! Text commands from the palette: change the case of the selection, sort the
+//! selected lines and drop duplicate lines. They work on plain text, without
+//! regard to SQL, and act on the whole buffer when nothing is selected.
+
+use std::ops::Range;
+
+use crate::{editor_autocomplete, window_egui};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TextCommand {
+    Uppercase,
+    Lowercase,
+    SortLines { ignore_case: bool },
+    RemoveDuplicateLines,
+}
+
+impl TextCommand {
+    /// Palette entries and the command each one runs.
+    pub(crate) const PALETTE: [(&'static str, TextCommand); 5] = [
+        ("Editor: Transform to Uppercase", TextCommand::Uppercase),
+        ("Editor: Transform to Lowercase", TextCommand::Lowercase),
+        ("Editor: Sort Lines", TextCommand::SortLines { ignore_case: false }),
+        (
+            "Editor: Sort Lines (Ignore Case)",
+            TextCommand::SortLines { ignore_case: true },
+        ),
+        ("Editor: Remove Duplicate Lines", TextCommand::RemoveDuplicateLines),
+    ];
+
+    fn works_on_lines(self) -> bool {
+        matches!(
+            self,
+            TextCommand::SortLines { .. } | TextCommand::RemoveDuplicateLines
+        )
+    }
+
+    fn transform(self, text: &str) -> String {
+        match self {
+            TextCommand::Uppercase => text.to_uppercase(),
+            TextCommand::Lowercase => text.to_lowercase(),
+            TextCommand::SortLines { ignore_case } => sort_lines(text, ignore_case),
+            TextCommand::RemoveDuplicateLines => remove_duplicate_lines(text),
+        }
+    }
+}
+
+/// `block` split into lines, and whether it ended with a newline.
+fn split_lines(block: &str) -> (Vec<&str>, bool) {
+    match block.strip_suffix('\n') {
+        Some(body) => (body.split('\n').collect(), true),
+        None => (block.split('\n').collect(), false),
+    }
+}
+
+fn join_lines(lines: &[&str], trailing_newline: bool) -> String {
+    let mut out = lines.join("\n");
+    if trailing_newline {
+        out.push('\n');
+    }
+    out
+}
+
+/// Lines of `block` in order; equal lines keep their relative order.
+fn sort_lines(block: &str, ignore_case: bool) -> String {
+    let (mut lines, trailing_newline) = split_lines(block);
+    if ignore_case {
+        lines.sort_by_cached_key(|line| line.to_lowercase());
+    } else {
+        lines.sort();
+    }
+    join_lines(&lines, trailing_newline)
+}
+
+/// Lines of `block` with repeats of an earlier line removed.
+fn remove_duplicate_lines(block: &str) -> String {
+    let (lines, trailing_newline) = split_lines(block);
+    let mut seen = std::collections::HashSet::new();
+    let kept: Vec<&str> = lines.into_iter().filter(|line| seen.insert(*line)).collect();
+    join_lines(&kept, trailing_newline)
+}
+
+/// Whole lines touched by `start..end`. A selection ending right after a
+/// newline does not take in the next line.
+fn line_range(text: &str, start: usize, end: usize) -> Range<usize> {
+    let first = text[..start].rfind('\n').map_or(0, |p| p + 1);
+    let last = if end > start && text.as_bytes()[end - 1] == b'\n' {
+        end
+    } else {
+        text[end..].find('\n').map_or(text.len(), |p| end + p)
+    };
+    first..last
+}
+
+/// Run `command` on the selection (grown to whole lines for line commands),
+/// or on the whole buffer, and leave the changed text selected.
+pub(crate) fn apply_text_command(tabular: &mut window_egui::Tabular, command: TextCommand) {
+    let text = &tabular.editor.text;
+    let start = tabular.selection_start.min(text.len());
+    let end = tabular.selection_end.min(text.len());
+    let range = if start < end && text.is_char_boundary(start) && text.is_char_boundary(end) {
+        if command.works_on_lines() {
+            line_range(text, start, end)
+        } else {
+            start..end
+        }
+    } else {
+        0..text.len()
+    };
+    let replacement = command.transform(&text[range.clone()]);
+    if replacement == text[range.clone()] {
+        tabular.toasts.info("Nothing to change");
+        return;
+    }
+
+    tabular.editor.apply_single_replace(range.clone(), &replacement);
+    let new_end = range.start + replacement.len();
+    tabular.selection_start = range.start;
+    tabular.selection_end = new_end;
+    tabular.selected_text = replacement;
+    tabular.cursor_position = new_end;
+    tabular.multi_selection.set_primary_range(range.start, new_end);
+    tabular.pending_cursor_set = Some(new_end);
+    tabular.editor_focus_boost_frames = tabular.editor_focus_boost_frames.max(4);
+
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.content = tabular.editor.text.clone();
+        tab.is_modified = true;
+    } else {
+        tabular.editor.mark_text_modified();
+    }
+    editor_autocomplete::update_autocomplete(tabular);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_commands_transform_selected_lines() {
+        let block = "b\nA\na\nb\nB\n";
+        assert_eq!(sort_lines(block, false), "A\nB\na\nb\nb\n");
+        // Stable: "A" stays ahead of "a", "b" ahead of "B"
+        assert_eq!(sort_lines(block, true), "A\na\nb\nb\nB\n");
+        assert_eq!(remove_duplicate_lines(block), "b\nA\na\nB\n");
+        assert_eq!(remove_duplicate_lines("1,\n2,\n1,"), "1,\n2,");
+        assert_eq!(
+            TextCommand::Uppercase.transform("select straße"),
+            "SELECT STRASSE"
+        );
+        assert_eq!(TextCommand::Lowercase.transform("IN ('A', 'B')"), "in ('a', 'b')");
+
+        let text = "head\nzeta\nalpha\ntail";
+        // Partial selection inside "zeta" through "al" grows to both lines
+        let range = line_range(text, 6, 13);
+        assert_eq!(&text[range], "zeta\nalpha");
+        // Selection ending after a newline stops at that line
+        let range = line_range(text, 5, 10);
+        assert_eq!(&text[range], "zeta\n");
+    }
+}
@@ -0,0 +1,587 @@
+//! Vim-style modal editing for the query editor (Preferences → Editor → Vim mode).
+//!
+//! [`VimState::handle_key`] is a small state machine over the buffer text and a
+//! byte caret. [`handle_vim_input`] runs before the TextEdit is built: it takes
+//! the keys the widget would otherwise insert and applies the resulting edits
+//! through `EditorBuffer`, so undo history and line caches stay in sync. Insert
+//! mode leaves typing, multi-cursor editing and autocomplete untouched.
+
+use eframe::egui;
+use std::ops::Range;
+
+use crate::editor_state_adapter::EditorStateAdapter;
+use crate::window_egui::Tabular;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VimMode {
+    #[default]
+    Normal,
+    Insert,
+    Visual,
+    Command,
+}
+
+impl VimMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            VimMode::Normal => "NORMAL",
+            VimMode::Insert => "INSERT",
+            VimMode::Visual => "VISUAL",
+            VimMode::Command => "COMMAND",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimKey {
+    Char(char),
+    Escape,
+    Enter,
+    Backspace,
+}
+
+/// `:` command line actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimCommand {
+    Write,
+    Run,
+    Quit,
+    WriteQuit,
+}
+
+/// Result of one key: an optional edit against the text the key was applied
+/// to, and where the caret (and visual selection) ends up afterwards.
+#[derive(Debug, Default, PartialEq)]
+pub struct VimOutcome {
+    pub edit: Option<(Range<usize>, String)>,
+    pub cursor: usize,
+    /// Visual selection as a byte range in the post-edit text.
+    pub selection: Option<Range<usize>>,
+    pub command: Option<VimCommand>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct VimState {
+    pub mode: VimMode,
+    pub command_line: String,
+    pending: Option<char>,
+    register: String,
+    linewise: bool,
+    anchor: usize,
+}
+
+fn line_start(text: &str, pos: usize) -> usize {
+    text[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0)
+}
+
+fn line_end(text: &str, pos: usize) -> usize {
+    text[pos..].find('\n').map(|i| pos + i).unwrap_or(text.len())
+}
+
+fn next_char(text: &str, pos: usize) -> usize {
+    text[pos..].chars().next().map(|c| pos + c.len_utf8()).unwrap_or(pos)
+}
+
+fn prev_char(text: &str, pos: usize) -> usize {
+    text[..pos].chars().next_back().map(|c| pos - c.len_utf8()).unwrap_or(pos)
+}
+
+/// Normal mode keeps the caret on a character, never past the end of a line.
+fn clamp_normal(text: &str, pos: usize) -> usize {
+    let (ls, le) = (line_start(text, pos), line_end(text, pos));
+    if pos >= le && le > ls { prev_char(text, le) } else { pos }
+}
+
+fn char_class(c: char) -> u8 {
+    if c.is_whitespace() {
+        0
+    } else if c.is_alphanumeric() || c == '_' {
+        1
+    } else {
+        2
+    }
+}
+
+fn word_forward(text: &str, pos: usize) -> usize {
+    let mut chars = text[pos..].char_indices().map(|(i, c)| (pos + i, c)).peekable();
+    if let Some(&(_, first)) = chars.peek() {
+        let class = char_class(first);
+        if class != 0 {
+            while chars.next_if(|&(_, c)| char_class(c) == class).is_some() {}
+        }
+    }
+    while chars.next_if(|&(_, c)| c.is_whitespace()).is_some() {}
+    chars.peek().map(|&(i, _)| i).unwrap_or(text.len())
+}
+
+fn word_backward(text: &str, pos: usize) -> usize {
+    let mut chars = text[..pos].char_indices().rev().peekable();
+    while chars.next_if(|&(_, c)| c.is_whitespace()).is_some() {}
+    let Some(&(last, first)) = chars.peek() else {
+        return 0;
+    };
+    let mut start = last;
+    let class = char_class(first);
+    while let Some((i, _)) = chars.next_if(|&(_, c)| char_class(c) == class) {
+        start = i;
+    }
+    start
+}
+
+/// Move `pos` one line up (`down == false`) or down, keeping the column.
+fn vertical(text: &str, pos: usize, down: bool) -> usize {
+    let ls = line_start(text, pos);
+    let col = text[ls..pos].chars().count();
+    let target_ls = if down {
+        let le = line_end(text, pos);
+        if le >= text.len() {
+            return pos;
+        }
+        le + 1
+    } else {
+        if ls == 0 {
+            return pos;
+        }
+        line_start(text, ls - 1)
+    };
+    let target_le = line_end(text, target_ls);
+    text[target_ls..target_le]
+        .char_indices()
+        .nth(col)
+        .map(|(i, _)| target_ls + i)
+        .unwrap_or(target_le)
+}
+
+fn motion(text: &str, pos: usize, c: char) -> Option<usize> {
+    Some(match c {
+        'h' => {
+            if pos > line_start(text, pos) { prev_char(text, pos) } else { pos }
+        }
+        'l' => {
+            let next = next_char(text, pos);
+            if next < line_end(text, pos) { next } else { pos }
+        }
+        'j' => vertical(text, pos, true),
+        'k' => vertical(text, pos, false),
+        'w' => word_forward(text, pos),
+        'b' => word_backward(text, pos),
+        '0' => line_start(text, pos),
+        '$' => line_end(text, pos),
+        'G' => line_start(text, text.len()),
+        _ => return None,
+    })
+}
+
+fn parse_command(line: &str) -> Result<VimCommand, String> {
+    match line.trim() {
+        "w" => Ok(VimCommand::Write),
+        "r" | "run" => Ok(VimCommand::Run),
+        "q" => Ok(VimCommand::Quit),
+        "wq" | "x" => Ok(VimCommand::WriteQuit),
+        other => Err(format!("Not an editor command: {}", other)),
+    }
+}
+
+impl VimState {
+    /// Apply `key` with the caret at byte offset `cursor` of `text`.
+    pub fn handle_key(&mut self, text: &str, cursor: usize, key: VimKey) -> VimOutcome {
+        let mut cursor = cursor.min(text.len());
+        while !text.is_char_boundary(cursor) {
+            cursor -= 1;
+        }
+        let mut out = VimOutcome {
+            cursor,
+            ..Default::default()
+        };
+        match self.mode {
+            VimMode::Insert => {
+                if key == VimKey::Escape {
+                    self.mode = VimMode::Normal;
+                    if cursor > line_start(text, cursor) {
+                        out.cursor = prev_char(text, cursor);
+                    }
+                }
+            }
+            VimMode::Command => match key {
+                VimKey::Char(c) => self.command_line.push(c),
+                VimKey::Backspace => {
+                    if self.command_line.pop().is_none() {
+                        self.mode = VimMode::Normal;
+                    }
+                }
+                VimKey::Escape => {
+                    self.command_line.clear();
+                    self.mode = VimMode::Normal;
+                }
+                VimKey::Enter => {
+                    match parse_command(&std::mem::take(&mut self.command_line)) {
+                        Ok(cmd) => out.command = Some(cmd),
+                        Err(e) => out.error = Some(e),
+                    }
+                    self.mode = VimMode::Normal;
+                }
+            },
+            VimMode::Normal => self.normal_key(text, cursor, key, &mut out),
+            VimMode::Visual => self.visual_key(text, cursor, key, &mut out),
+        }
+        out
+    }
+
+    fn normal_key(&mut self, text: &str, cursor: usize, key: VimKey, out: &mut VimOutcome) {
+        let VimKey::Char(c) = key else {
+            self.pending = None;
+            return;
+        };
+        let ls = line_start(text, cursor);
+        let le = line_end(text, cursor);
+        if let Some(op) = self.pending.take() {
+            match (op, c) {
+                ('d', 'd') => {
+                    if text.is_empty() {
+                        return;
+                    }
+                    self.register = format!("{}\n", &text[ls..le]);
+                    self.linewise = true;
+                    let (range, caret) = if le < text.len() {
+                        (ls..le + 1, ls)
+                    } else if ls > 0 {
+                        (ls - 1..le, line_start(text, ls - 1))
+                    } else {
+                        (ls..le, 0)
+                    };
+                    out.edit = Some((range, String::new()));
+                    out.cursor = caret;
+                }
+                ('y', 'y') => {
+                    self.register = format!("{}\n", &text[ls..le]);
+                    self.linewise = true;
+                }
+                ('g', 'g') => out.cursor = 0,
+                _ => {}
+            }
+            return;
+        }
+        if let Some(pos) = motion(text, cursor, c) {
+            out.cursor = clamp_normal(text, pos);
+            return;
+        }
+        match c {
+            'i' => self.mode = VimMode::Insert,
+            'a' => {
+                self.mode = VimMode::Insert;
+                out.cursor = if cursor < le { next_char(text, cursor) } else { cursor };
+            }
+            'I' => {
+                self.mode = VimMode::Insert;
+                out.cursor = ls + (text[ls..le].len() - text[ls..le].trim_start().len());
+            }
+            'A' => {
+                self.mode = VimMode::Insert;
+                out.cursor = le;
+            }
+            'o' => {
+                self.mode = VimMode::Insert;
+                out.edit = Some((le..le, "\n".to_string()));
+                out.cursor = le + 1;
+            }
+            'O' => {
+                self.mode = VimMode::Insert;
+                out.edit = Some((ls..ls, "\n".to_string()));
+                out.cursor = ls;
+            }
+            'x' if cursor < le => {
+                let end = next_char(text, cursor);
+                self.register = text[cursor..end].to_string();
+                self.linewise = false;
+                out.edit = Some((cursor..end, String::new()));
+                let rest = format!("{}{}", &text[..cursor], &text[end..]);
+                out.cursor = clamp_normal(&rest, cursor);
+            }
+            'p' | 'P' if !self.register.is_empty() => {
+                let before = c == 'P';
+                if self.linewise {
+                    let (at, insert) = if before {
+                        (ls, self.register.clone())
+                    } else if le < text.len() {
+                        (le + 1, self.register.clone())
+                    } else {
+                        (le, format!("\n{}", self.register.trim_end_matches('\n')))
+                    };
+                    out.cursor = if at == le && !before { le + 1 } else { at };
+                    out.edit = Some((at..at, insert));
+                } else {
+                    let at = if before || cursor >= le { cursor } else { next_char(text, cursor) };
+                    let last = self.register.chars().next_back().map_or(0, char::len_utf8);
+                    out.cursor = at + self.register.len() - last;
+                    out.edit = Some((at..at, self.register.clone()));
+                }
+            }
+            'v' => {
+                self.mode = VimMode::Visual;
+                self.anchor = cursor;
+                out.selection = Some(cursor..next_char(text, cursor));
+            }
+            ':' => {
+                self.mode = VimMode::Command;
+                self.command_line.clear();
+            }
+            'd' | 'y' | 'g' => self.pending = Some(c),
+            _ => {}
+        }
+    }
+
+    fn visual_key(&mut self, text: &str, cursor: usize, key: VimKey, out: &mut VimOutcome) {
+        let anchor = self.anchor.min(text.len());
+        let selection = |head: usize| {
+            let (lo, hi) = (anchor.min(head), anchor.max(head));
+            lo..next_char(text, hi)
+        };
+        let c = match key {
+            VimKey::Char(c) => c,
+            VimKey::Escape => {
+                self.mode = VimMode::Normal;
+                return;
+            }
+            _ => {
+                out.selection = Some(selection(cursor));
+                return;
+            }
+        };
+        if let Some(pos) = motion(text, cursor, c) {
+            out.cursor = clamp_normal(text, pos);
+            out.selection = Some(selection(out.cursor));
+            return;
+        }
+        match c {
+            'y' | 'd' | 'x' => {
+                let range = selection(cursor);
+                self.register = text[range.clone()].to_string();
+                self.linewise = false;
+                self.mode = VimMode::Normal;
+                out.cursor = range.start;
+                if c != 'y' {
+                    let rest = format!("{}{}", &text[..range.start], &text[range.end..]);
+                    out.cursor = clamp_normal(&rest, range.start);
+                    out.edit = Some((range, String::new()));
+                }
+            }
+            'v' => self.mode = VimMode::Normal,
+            _ => out.selection = Some(selection(cursor)),
+        }
+    }
+}
+
+/// Feed this frame's keys through vim mode. Called before the TextEdit is built
+/// so the widget never sees keys consumed by Normal/Visual/Command mode.
+pub(crate) fn handle_vim_input(tabular: &mut Tabular, ui: &egui::Ui, editor_id: egui::Id) {
+    if !tabular.advanced_editor.vim_mode || !ui.memory(|m| m.has_focus(editor_id)) {
+        return;
+    }
+    let insert = tabular.vim.mode == VimMode::Insert;
+    let mut keys: Vec<VimKey> = Vec::new();
+    ui.ctx().input_mut(|ri| {
+        ri.events.retain(|ev| match ev {
+            egui::Event::Key {
+                key,
+                pressed,
+                modifiers,
+                ..
+            } if !(modifiers.command || modifiers.ctrl || modifiers.alt) => {
+                let mapped = match key {
+                    egui::Key::Escape => Some(VimKey::Escape),
+                    egui::Key::Enter => Some(VimKey::Enter),
+                    egui::Key::Backspace => Some(VimKey::Backspace),
+                    _ => None,
+                };
+                if insert {
+                    // Only Escape leaves Insert mode; everything else is typed as usual
+                    if mapped == Some(VimKey::Escape) {
+                        if *pressed {
+                            keys.push(VimKey::Escape);
+                        }
+                        return false;
+                    }
+                    return true;
+                }
+                if *pressed && let Some(k) = mapped {
+                    keys.push(k);
+                }
+                // Arrow keys, Home/End etc. keep moving the caret natively
+                mapped.is_none() && !matches!(key, egui::Key::Tab | egui::Key::Delete)
+                    && !is_text_key(*key)
+            }
+            egui::Event::Text(t) if !insert => {
+                keys.extend(t.chars().map(VimKey::Char));
+                false
+            }
+            _ => true,
+        });
+    });
+    if tabular.vim.mode != VimMode::Insert {
+        tabular.show_autocomplete = false;
+        tabular.autocomplete_pending = false;
+    }
+    if keys.is_empty() {
+        return;
+    }
+
+    let text_len = tabular.editor.text.len();
+    let mut cursor = EditorStateAdapter::get_range(ui.ctx(), editor_id)
+        .map(|r| {
+            let text = &tabular.editor.text;
+            text.char_indices().nth(r.primary).map(|(b, _)| b).unwrap_or(text.len())
+        })
+        .unwrap_or(tabular.cursor_position.min(text_len));
+    let mut selection = None;
+    let mut edited = false;
+    let mut commands = Vec::new();
+    for key in keys {
+        let was_insert = tabular.vim.mode == VimMode::Insert;
+        let out = tabular.vim.handle_key(&tabular.editor.text, cursor, key);
+        if let Some((range, replacement)) = out.edit {
+            tabular.editor.apply_single_replace(range, &replacement);
+            edited = true;
+        }
+        if was_insert && tabular.vim.mode != VimMode::Insert {
+            // Extra carets only make sense while typing
+            tabular.multi_selection.clear();
+            tabular.show_autocomplete = false;
+            tabular.autocomplete_pending = false;
+        }
+        if let Some(e) = out.error {
+            tabular.toasts.error(e);
+        }
+        commands.extend(out.command);
+        cursor = out.cursor;
+        selection = out.selection;
+    }
+
+    let text = &tabular.editor.text;
+    let to_ci = |b: usize| text[..b.min(text.len())].chars().count();
+    let (start, end) = selection
+        .map(|r| (r.start, r.end))
+        .unwrap_or((cursor, cursor));
+    tabular.cursor_position = cursor;
+    tabular.selection_start = start;
+    tabular.selection_end = end;
+    tabular.selected_text = text.get(start..end).unwrap_or_default().to_string();
+    tabular.pending_cursor_set = None;
+    if start < end {
+        let primary = if cursor == start { to_ci(start) } else { to_ci(end) };
+        EditorStateAdapter::set_selection(ui.ctx(), editor_id, to_ci(start), to_ci(end), primary);
+    } else {
+        EditorStateAdapter::set_single(ui.ctx(), editor_id, to_ci(cursor));
+    }
+    if edited && let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.content = tabular.editor.text.clone();
+        tab.is_modified = true;
+    }
+    for cmd in commands {
+        match cmd {
+            VimCommand::Write => {
+                let _ = crate::editor::save_current_tab(tabular);
+            }
+            VimCommand::Run => crate::editor::execute_query(tabular),
+            VimCommand::Quit => {
+                crate::unsaved_changes::request_close_tab(tabular, tabular.active_tab_index)
+            }
+            VimCommand::WriteQuit => {
+                // A new file opens the Save Query dialog; the tab stays until it is saved
+                if crate::editor::save_current_tab(tabular).is_ok() && !tabular.show_save_dialog {
+                    crate::editor::close_tab(tabular, tabular.active_tab_index);
+                }
+            }
+        }
+    }
+    ui.ctx().request_repaint();
+}
+
+fn is_text_key(key: egui::Key) -> bool {
+    let name = key.name();
+    name.chars().count() == 1 || key == egui::Key::Space
+}
+
+/// Mode badge (and pending `:` command) in the editor's bottom-right corner.
+pub(crate) fn paint_mode_indicator(tabular: &Tabular, ui: &egui::Ui, rect: egui::Rect) {
+    if !tabular.advanced_editor.vim_mode {
+        return;
+    }
+    let label = match tabular.vim.mode {
+        VimMode::Command => format!(":{}▏", tabular.vim.command_line),
+        mode => format!("-- {} --", mode.label()),
+    };
+    let color = match tabular.vim.mode {
+        VimMode::Insert => egui::Color32::from_rgb(80, 170, 90),
+        VimMode::Visual => egui::Color32::from_rgb(200, 140, 40),
+        _ => egui::Color32::from_gray(140),
+    };
+    ui.painter().text(
+        rect.right_bottom() - egui::vec2(8.0, 4.0),
+        egui::Align2::RIGHT_BOTTOM,
+        label,
+        egui::FontId::monospace(11.0),
+        color,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(state: &mut VimState, text: &mut String, cursor: &mut usize, keys: &str) {
+        for c in keys.chars() {
+            let key = match c {
+                '\x1b' => VimKey::Escape,
+                '\n' if state.mode == VimMode::Command => VimKey::Enter,
+                c => VimKey::Char(c),
+            };
+            let out = state.handle_key(text, *cursor, key);
+            if let Some((range, s)) = out.edit {
+                text.replace_range(range, &s);
+            }
+            *cursor = out.cursor;
+        }
+    }
+
+    #[test]
+    fn motions_and_line_operations() {
+        let mut state = VimState::default();
+        let mut text = "select a,\n  b_col from t\nwhere x = 1".to_string();
+        let mut cursor = 0;
+        run(&mut state, &mut text, &mut cursor, "w");
+        assert_eq!(&text[cursor..cursor + 1], "a");
+        run(&mut state, &mut text, &mut cursor, "jb$");
+        assert_eq!(cursor, text.find("t\n").unwrap());
+        run(&mut state, &mut text, &mut cursor, "ddp");
+        assert_eq!(text, "select a,\nwhere x = 1\n  b_col from t");
+        run(&mut state, &mut text, &mut cursor, "ggyyP");
+        assert_eq!(text, "select a,\nselect a,\nwhere x = 1\n  b_col from t");
+        run(&mut state, &mut text, &mut cursor, "Gx");
+        assert_eq!(text.lines().last(), Some(" b_col from t"));
+        run(&mut state, &mut text, &mut cursor, "ggvwd");
+        assert_eq!(text.lines().next(), Some(",")); // "select a" removed
+        assert_eq!(state.mode, VimMode::Normal);
+    }
+
+    #[test]
+    fn insert_escape_and_command_line() {
+        let mut state = VimState::default();
+        let mut text = "ab".to_string();
+        let mut cursor = 0;
+        run(&mut state, &mut text, &mut cursor, "A");
+        assert_eq!((state.mode, cursor), (VimMode::Insert, 2));
+        run(&mut state, &mut text, &mut cursor, "\x1b");
+        assert_eq!((state.mode, cursor), (VimMode::Normal, 1));
+        run(&mut state, &mut text, &mut cursor, ":run");
+        assert_eq!(state.mode, VimMode::Command);
+        assert_eq!(
+            state.handle_key(&text, cursor, VimKey::Enter).command,
+            Some(VimCommand::Run)
+        );
+        run(&mut state, &mut text, &mut cursor, ":nope");
+        let out = state.handle_key(&text, cursor, VimKey::Enter);
+        assert!(out.error.is_some() && out.command.is_none());
+        assert_eq!(state.mode, VimMode::Normal);
+    }
+}
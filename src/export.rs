@@ -3,18 +3,59 @@ use std::path::Path;
 
 use crate::models::enums::DatabaseType;
 
+/// Field separator for CSV exports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsvDelimiter {
+    Comma,
+    Semicolon,
+    Tab,
+}
+
+impl CsvDelimiter {
+    pub const ALL: [CsvDelimiter; 3] = [Self::Comma, Self::Semicolon, Self::Tab];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Comma => "Comma (,)",
+            Self::Semicolon => "Semicolon (;)",
+            Self::Tab => "Tab",
+        }
+    }
+
+    fn byte(self) -> u8 {
+        match self {
+            Self::Comma => b',',
+            Self::Semicolon => b';',
+            Self::Tab => b'\t',
+        }
+    }
+
+    /// Save dialog filter name and file extension.
+    fn file_type(self) -> (&'static str, &'static str) {
+        match self {
+            Self::Tab => ("TSV files", "tsv"),
+            Self::Comma | Self::Semicolon => ("CSV files", "csv"),
+        }
+    }
+}
+
 pub fn export_to_csv(
     all_table_data: &[Vec<String>],
     current_table_headers: &[String],
     current_table_name: &str,
+    delimiter: CsvDelimiter,
 ) {
     // Use rfd to open save dialog
+    let (filter, extension) = delimiter.file_type();
     let file_dialog = rfd::FileDialog::new()
-        .add_filter("CSV files", &["csv"])
-        .set_file_name(format!("{}.csv", current_table_name.replace(' ', "_")));
+        .add_filter(filter, &[extension])
+        .set_file_name(format!("{}.{}", current_table_name.replace(' ', "_"), extension));
 
     if let Some(path) = file_dialog.save_file() {
-        match write_csv_file(&path, all_table_data, current_table_headers) {
+        match build_csv(all_table_data, current_table_headers, delimiter)
+            .map_err(|e| e.to_string())
+            .and_then(|csv| std::fs::write(&path, csv).map_err(|e| e.to_string()))
+        {
             Ok(_) => debug!(
                 "✓ Successfully exported {} rows to CSV: {:?}",
                 all_table_data.len(),
@@ -27,24 +68,23 @@ pub fn export_to_csv(
     }
 }
 
-fn write_csv_file(
-    path: &Path,
+/// RFC 4180 CSV: fields containing the delimiter, a quote, CR or LF are
+/// wrapped in double quotes with inner quotes doubled.
+pub fn build_csv(
     all_table_data: &[Vec<String>],
-    current_table_headers: &[String],
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut writer = csv::Writer::from_path(path)?;
-
-    // Write headers
-    writer.write_record(current_table_headers)?;
-
-    // Write data rows
-    for row in all_table_data.iter() {
+    headers: &[String],
+    delimiter: CsvDelimiter,
+) -> Result<String, csv::Error> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter.byte())
+        .quote_style(csv::QuoteStyle::Necessary)
+        .from_writer(Vec::new());
+    writer.write_record(headers)?;
+    for row in all_table_data {
         writer.write_record(row)?;
     }
-
-    writer.flush()?;
-
-    Ok(())
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
 }
 
 pub fn export_to_xlsx(
@@ -111,6 +151,7 @@ pub fn export_to_json(
     all_table_data: &[Vec<String>],
     current_table_headers: &[String],
     current_table_name: &str,
+    typed_numbers: bool,
 ) {
     let file_dialog = rfd::FileDialog::new()
         .add_filter("JSON files", &["json"])
@@ -119,7 +160,7 @@ pub fn export_to_json(
     if let Some(path) = file_dialog.save_file() {
         match std::fs::write(
             &path,
-            build_json(all_table_data, current_table_headers),
+            export_json(current_table_headers, all_table_data, typed_numbers),
         ) {
             Ok(_) => debug!(
                 "✓ Successfully exported {} rows to JSON: {:?}",
@@ -131,20 +172,18 @@ pub fn export_to_json(
     }
 }
 
-fn build_json(all_table_data: &[Vec<String>], headers: &[String]) -> String {
-    let rows: Vec<serde_json::Value> = all_table_data
+/// Pretty JSON array with one object per row, keyed by header. NULL cells
+/// become `null`; numeric-looking cells become numbers when `typed_numbers`
+/// is set and stay strings otherwise.
+pub fn export_json(headers: &[String], rows: &[Vec<String>], typed_numbers: bool) -> String {
+    let rows: Vec<serde_json::Value> = rows
         .iter()
         .map(|row| {
             let mut obj = serde_json::Map::new();
             for (i, header) in headers.iter().enumerate() {
                 let cell = row.get(i).map(String::as_str).unwrap_or("");
-                // Grid semantics: the literal NULL marker means SQL NULL.
-                let value = if cell.eq_ignore_ascii_case("null") {
-                    serde_json::Value::Null
-                } else if let Ok(n) = cell.parse::<i64>() {
-                    serde_json::Value::from(n)
-                } else if let Ok(f) = cell.parse::<f64>() {
-                    serde_json::Value::from(f)
+                let value = if typed_numbers || cell.eq_ignore_ascii_case("null") {
+                    json_cell_value(cell)
                 } else {
                     serde_json::Value::from(cell)
                 };
@@ -157,6 +196,139 @@ fn build_json(all_table_data: &[Vec<String>], headers: &[String]) -> String {
         .unwrap_or_else(|_| "[]".to_string())
 }
 
+fn json_cell_value(cell: &str) -> serde_json::Value {
+    // Grid semantics: the literal NULL marker means SQL NULL.
+    if cell.eq_ignore_ascii_case("null") {
+        serde_json::Value::Null
+    } else if let Ok(n) = cell.parse::<i64>() {
+        serde_json::Value::from(n)
+    } else if let Ok(f) = cell.parse::<f64>() {
+        serde_json::Value::from(f)
+    } else {
+        serde_json::Value::from(cell)
+    }
+}
+
+pub fn export_to_chart_json(
+    all_table_data: &[Vec<String>],
+    current_table_headers: &[String],
+    current_table_name: &str,
+) {
+    let file_dialog = rfd::FileDialog::new()
+        .add_filter("JSON files", &["json"])
+        .set_file_name(format!("{}_chart.json", current_table_name.replace(' ', "_")));
+
+    if let Some(path) = file_dialog.save_file() {
+        match std::fs::write(&path, build_chart_json(all_table_data, current_table_headers)) {
+            Ok(_) => debug!(
+                "✓ Successfully exported {} rows as chart JSON: {:?}",
+                all_table_data.len(),
+                path
+            ),
+            Err(e) => debug!("❌ Failed to export chart JSON: {}", e),
+        }
+    }
+}
+
+/// Column-oriented JSON for charting libraries:
+/// `{"columns": ["a", "b"], "data": {"a": [..], "b": [..]}}`.
+pub fn build_chart_json(all_table_data: &[Vec<String>], headers: &[String]) -> String {
+    let mut data = serde_json::Map::new();
+    for (i, header) in headers.iter().enumerate() {
+        let values: Vec<serde_json::Value> = all_table_data
+            .iter()
+            .map(|row| json_cell_value(row.get(i).map(String::as_str).unwrap_or("")))
+            .collect();
+        data.insert(header.clone(), serde_json::Value::Array(values));
+    }
+    let doc = serde_json::json!({
+        "columns": headers,
+        "data": serde_json::Value::Object(data),
+    });
+    serde_json::to_string_pretty(&doc).unwrap_or_else(|_| "{}".to_string())
+}
+
+pub fn export_to_html(
+    all_table_data: &[Vec<String>],
+    current_table_headers: &[String],
+    current_table_name: &str,
+    caption: Option<&str>,
+) {
+    let file_dialog = rfd::FileDialog::new()
+        .add_filter("HTML files", &["html", "htm"])
+        .set_file_name(format!("{}.html", current_table_name.replace(' ', "_")));
+
+    if let Some(path) = file_dialog.save_file() {
+        match std::fs::write(&path, build_html(all_table_data, current_table_headers, caption)) {
+            Ok(_) => debug!(
+                "✓ Successfully exported {} rows to HTML: {:?}",
+                all_table_data.len(),
+                path
+            ),
+            Err(e) => debug!("❌ Failed to export HTML: {}", e),
+        }
+    }
+}
+
+pub fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Self-contained HTML table with inline styles (header row, zebra striping)
+/// so it survives being pasted into documents and mail clients.
+pub fn build_html(
+    all_table_data: &[Vec<String>],
+    headers: &[String],
+    caption: Option<&str>,
+) -> String {
+    const TABLE_STYLE: &str = "border-collapse:collapse;font-family:-apple-system,Segoe UI,Helvetica,Arial,sans-serif;font-size:13px";
+    const CAPTION_STYLE: &str = "caption-side:top;text-align:left;padding:4px 0;color:#555;white-space:pre-wrap";
+    const TH_STYLE: &str = "background:#2f3640;color:#fff;text-align:left;padding:6px 10px;border:1px solid #ccc";
+    const TD_STYLE: &str = "padding:4px 10px;border:1px solid #ddd;vertical-align:top";
+    const STRIPE_BG: &str = "#f4f6f8";
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"></head>\n<body>\n");
+    out.push_str(&format!("<table style=\"{}\">\n", TABLE_STYLE));
+    if let Some(caption) = caption.filter(|c| !c.trim().is_empty()) {
+        out.push_str(&format!(
+            "<caption style=\"{}\">{}</caption>\n",
+            CAPTION_STYLE,
+            escape_html(caption)
+        ));
+    }
+    out.push_str("<thead><tr>");
+    for h in headers {
+        out.push_str(&format!("<th style=\"{}\">{}</th>", TH_STYLE, escape_html(h)));
+    }
+    out.push_str("</tr></thead>\n<tbody>\n");
+    for (idx, row) in all_table_data.iter().enumerate() {
+        if idx % 2 == 1 {
+            out.push_str(&format!("<tr style=\"background:{}\">", STRIPE_BG));
+        } else {
+            out.push_str("<tr>");
+        }
+        for i in 0..headers.len() {
+            let cell = row.get(i).map(String::as_str).unwrap_or("");
+            out.push_str(&format!("<td style=\"{}\">{}</td>", TD_STYLE, escape_html(cell)));
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+    out
+}
+
 pub fn export_to_markdown(
     all_table_data: &[Vec<String>],
     current_table_headers: &[String],
@@ -223,24 +395,29 @@ pub fn export_to_sql_inserts(
     }
 }
 
-pub fn build_sql_inserts(
-    all_table_data: &[Vec<String>],
-    headers: &[String],
-    table_caption: &str,
-    db_type: Option<&DatabaseType>,
-) -> String {
-    // Captions look like "Table: users" or a free-form query title.
+/// Table name for exports: captions look like "Table: users" or a free-form
+/// query title.
+pub fn table_name_from_caption(table_caption: &str) -> String {
     let table_name = table_caption
         .trim()
         .strip_prefix("Table:")
         .map(str::trim)
         .unwrap_or(table_caption.trim())
         .replace(' ', "_");
-    let table_name = if table_name.is_empty() {
+    if table_name.is_empty() {
         "exported_table".to_string()
     } else {
         table_name
-    };
+    }
+}
+
+pub fn build_sql_inserts(
+    all_table_data: &[Vec<String>],
+    headers: &[String],
+    table_caption: &str,
+    db_type: Option<&DatabaseType>,
+) -> String {
+    let table_name = table_name_from_caption(table_caption);
 
     let quote_ident = |s: &str| -> String {
         match db_type {
@@ -291,6 +468,159 @@ pub fn build_sql_inserts(
     out
 }
 
+/// What to do when the SQLite file already has a table with the chosen name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SqliteTableConflict {
+    /// Create `name_2`, `name_3`, ... instead.
+    Rename,
+    /// Drop the existing table first.
+    Replace,
+}
+
+/// SQLite column affinity that fits every non-NULL value of a column. A
+/// numeric affinity is only picked when each value reads back as the same
+/// text ("007", "1.50" or "1e3" keep the column TEXT).
+pub fn infer_sqlite_affinity<'a>(values: impl Iterator<Item = &'a str>) -> &'static str {
+    let mut affinity = None;
+    for v in values.filter(|v| !is_null_cell(v)) {
+        let this = if v.parse::<i64>().is_ok_and(|n| n.to_string() == v) {
+            "INTEGER"
+        } else if v
+            .parse::<f64>()
+            .is_ok_and(|f| f.is_finite() && f.to_string() == v)
+        {
+            "REAL"
+        } else {
+            return "TEXT";
+        };
+        affinity = match (affinity, this) {
+            (None, t) | (Some("INTEGER"), t) => Some(t),
+            (Some(a), _) => Some(a),
+        };
+    }
+    affinity.unwrap_or("TEXT")
+}
+
+/// Grid semantics: the literal NULL marker (and an empty cell) means SQL NULL.
+fn is_null_cell(v: &str) -> bool {
+    v.is_empty() || v.eq_ignore_ascii_case("null")
+}
+
+/// `wanted`, or `wanted_2`, `wanted_3`, ... when taken (case-insensitively).
+pub fn unique_name(existing: &[String], wanted: &str) -> String {
+    let taken = |n: &str| existing.iter().any(|e| e.eq_ignore_ascii_case(n));
+    if !taken(wanted) {
+        return wanted.to_string();
+    }
+    (2..)
+        .map(|i| format!("{}_{}", wanted, i))
+        .find(|n| !taken(n))
+        .unwrap()
+}
+
+/// Write `rows` into table `table_name` of the SQLite file at `path`, creating
+/// the file when missing. All inserts run in one transaction, in multi-row
+/// batches. Returns the table name actually used and the number of rows.
+pub async fn write_sqlite_table(
+    path: &Path,
+    table_name: &str,
+    on_conflict: SqliteTableConflict,
+    headers: &[String],
+    rows: &[Vec<String>],
+) -> Result<(String, usize), String> {
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+    let quote = |s: &str| format!("\"{}\"", s.replace('"', "\"\""));
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(
+            SqliteConnectOptions::new()
+                .filename(path)
+                .create_if_missing(true),
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let existing: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type IN ('table', 'view')")
+            .fetch_all(&pool)
+            .await
+            .map_err(|e| e.to_string())?;
+    let table = match on_conflict {
+        SqliteTableConflict::Rename => unique_name(&existing, table_name),
+        SqliteTableConflict::Replace => table_name.to_string(),
+    };
+
+    // Result sets may repeat or omit column names (joins, expressions)
+    let mut columns: Vec<String> = Vec::with_capacity(headers.len());
+    for (i, h) in headers.iter().enumerate() {
+        let base = if h.trim().is_empty() {
+            format!("column_{}", i + 1)
+        } else {
+            h.trim().to_string()
+        };
+        columns.push(unique_name(&columns, &base));
+    }
+    let affinities: Vec<&str> = (0..columns.len())
+        .map(|i| infer_sqlite_affinity(rows.iter().map(|r| r.get(i).map_or("", String::as_str))))
+        .collect();
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    if on_conflict == SqliteTableConflict::Replace {
+        let drop = format!("DROP TABLE IF EXISTS {}", quote(&table));
+        sqlx::query(sqlx::AssertSqlSafe(drop))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    let create = format!(
+        "CREATE TABLE {} ({})",
+        quote(&table),
+        columns
+            .iter()
+            .zip(&affinities)
+            .map(|(c, a)| format!("{} {}", quote(c), a))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    sqlx::query(sqlx::AssertSqlSafe(create))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !columns.is_empty() {
+        // Stay under SQLite's default limit of 999 bound variables per statement
+        let per_batch = (999 / columns.len()).max(1);
+        let row_placeholders = format!("({})", vec!["?"; columns.len()].join(", "));
+        for chunk in rows.chunks(per_batch) {
+            let sql = format!(
+                "INSERT INTO {} VALUES {}",
+                quote(&table),
+                vec![row_placeholders.as_str(); chunk.len()].join(", ")
+            );
+            let mut query = sqlx::query(sqlx::AssertSqlSafe(sql));
+            for row in chunk {
+                for (i, affinity) in affinities.iter().enumerate() {
+                    let cell = row.get(i).map_or("", String::as_str);
+                    query = if is_null_cell(cell) {
+                        query.bind(None::<String>)
+                    } else {
+                        match *affinity {
+                            "INTEGER" => query.bind(cell.parse::<i64>().ok()),
+                            "REAL" => query.bind(cell.parse::<f64>().ok()),
+                            _ => query.bind(cell.to_string()),
+                        }
+                    };
+                }
+            }
+            query.execute(&mut *tx).await.map_err(|e| e.to_string())?;
+        }
+    }
+    tx.commit().await.map_err(|e| e.to_string())?;
+    pool.close().await;
+    Ok((table, rows.len()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,6 +638,87 @@ mod tests {
         assert!(sql.contains("('2', NULL)"));
     }
 
+    #[test]
+    fn sqlite_affinity_and_unique_names() {
+        assert_eq!(infer_sqlite_affinity(["1", "NULL", "-3"].into_iter()), "INTEGER");
+        assert_eq!(infer_sqlite_affinity(["1", "2.5", ""].into_iter()), "REAL");
+        assert_eq!(infer_sqlite_affinity(["1", "abc"].into_iter()), "TEXT");
+        assert_eq!(infer_sqlite_affinity(["NULL"].into_iter()), "TEXT");
+        // Leading zeros, signs and other spellings would not survive a number
+        assert_eq!(infer_sqlite_affinity(["1", "007"].into_iter()), "TEXT");
+        assert_eq!(infer_sqlite_affinity(["+5"].into_iter()), "TEXT");
+        assert_eq!(infer_sqlite_affinity(["2.5", "1.50"].into_iter()), "TEXT");
+        assert_eq!(infer_sqlite_affinity(["1e3"].into_iter()), "TEXT");
+        let existing = vec!["Orders".to_string(), "orders_2".to_string()];
+        assert_eq!(unique_name(&existing, "orders"), "orders_3");
+        assert_eq!(unique_name(&existing, "users"), "users");
+    }
+
+    #[test]
+    fn sqlite_export_creates_and_renames_tables() {
+        let dir = std::env::temp_dir().join(format!("tabular-sqlite-export-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("out.sqlite");
+        let headers = vec!["id".to_string(), "id".to_string(), "price".to_string()];
+        let rows: Vec<Vec<String>> = (0..700)
+            .map(|i| vec![i.to_string(), "x".to_string(), format!("{}.5", i)])
+            .chain(std::iter::once(vec!["NULL".to_string(), "y".to_string(), "".to_string()]))
+            .collect();
+
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let (first, n) = rt
+            .block_on(write_sqlite_table(&path, "t", SqliteTableConflict::Rename, &headers, &rows))
+            .unwrap();
+        assert_eq!((first.as_str(), n), ("t", 701));
+        let (second, _) = rt
+            .block_on(write_sqlite_table(&path, "t", SqliteTableConflict::Rename, &headers, &rows))
+            .unwrap();
+        assert_eq!(second, "t_2");
+        let (replaced, _) = rt
+            .block_on(write_sqlite_table(&path, "t", SqliteTableConflict::Replace, &headers, &rows[..1]))
+            .unwrap();
+        assert_eq!(replaced, "t");
+
+        let (count, sum, typeof_id): (i64, f64, String) = rt.block_on(async {
+            let pool = sqlx::SqlitePool::connect(&format!("sqlite://{}", path.display())).await.unwrap();
+            let count = sqlx::query_scalar("SELECT COUNT(*) FROM t_2").fetch_one(&pool).await.unwrap();
+            let sum = sqlx::query_scalar("SELECT SUM(price) FROM t_2").fetch_one(&pool).await.unwrap();
+            let ty = sqlx::query_scalar("SELECT typeof(id) FROM t LIMIT 1").fetch_one(&pool).await.unwrap();
+            let cols: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info('t')")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+            assert_eq!(cols, vec!["id", "id_2", "price"]);
+            (count, sum, ty)
+        });
+        assert_eq!(count, 701);
+        assert_eq!(sum, (0..700).map(|i| i as f64 + 0.5).sum::<f64>());
+        assert_eq!(typeof_id, "integer");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn csv_quotes_delimiters_quotes_and_newlines() {
+        let headers = vec!["id".to_string(), "note".to_string()];
+        let data = vec![
+            vec!["1".to_string(), "he said \"hi\",\nbye".to_string()],
+            vec!["2".to_string(), "a;b\tc".to_string()],
+        ];
+        assert_eq!(
+            build_csv(&data, &headers, CsvDelimiter::Comma).unwrap(),
+            "id,note\n1,\"he said \"\"hi\"\",\nbye\"\n2,a;b\tc\n"
+        );
+        assert_eq!(
+            build_csv(&data[1..], &headers, CsvDelimiter::Semicolon).unwrap(),
+            "id;note\n2;\"a;b\tc\"\n"
+        );
+        assert_eq!(
+            build_csv(&data[1..], &headers, CsvDelimiter::Tab).unwrap(),
+            "id\tnote\n2\t\"a;b\tc\"\n"
+        );
+    }
+
     #[test]
     fn markdown_escapes_pipes() {
         let data = vec![vec!["a|b".to_string()]];
@@ -320,10 +731,59 @@ mod tests {
     fn json_nulls_and_numbers() {
         let data = vec![vec!["NULL".to_string(), "42".to_string(), "x".to_string()]];
         let headers = vec!["a".to_string(), "b".to_string(), "c".to_string()];
-        let json = build_json(&data, &headers);
+        let json = export_json(&headers, &data, true);
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert!(parsed[0]["a"].is_null());
         assert_eq!(parsed[0]["b"], 42);
         assert_eq!(parsed[0]["c"], "x");
+
+        let parsed: serde_json::Value =
+            serde_json::from_str(&export_json(&headers, &data, false)).unwrap();
+        assert!(parsed[0]["a"].is_null());
+        assert_eq!(parsed[0]["b"], "42");
+    }
+
+    #[test]
+    fn json_of_an_empty_result_is_an_empty_array() {
+        assert_eq!(export_json(&["id".to_string()], &[], true), "[]");
+    }
+
+    #[test]
+    fn html_escapes_special_characters() {
+        let data = vec![
+            vec!["<script>alert('x')</script>".to_string(), "a & b".to_string()],
+            vec!["\"quoted\"".to_string(), "plain".to_string()],
+        ];
+        let headers = vec!["<b>col</b>".to_string(), "other".to_string()];
+        let html = build_html(&data, &headers, Some("SELECT * FROM t WHERE a < 5"));
+        assert!(html.contains("&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;"));
+        assert!(html.contains("a &amp; b"));
+        assert!(html.contains("&quot;quoted&quot;"));
+        assert!(html.contains("&lt;b&gt;col&lt;/b&gt;"));
+        assert!(html.contains("WHERE a &lt; 5</caption>"));
+        assert!(!html.contains("<script>"));
+        // Second data row carries the zebra stripe.
+        assert_eq!(html.matches("<tr style=\"background:").count(), 1);
+    }
+
+    #[test]
+    fn html_caption_is_optional() {
+        let html = build_html(&[], &["id".to_string()], None);
+        assert!(!html.contains("<caption"));
+        assert_eq!(escape_html("plain text"), "plain text");
+    }
+
+    #[test]
+    fn chart_json_is_column_oriented() {
+        let data = vec![
+            vec!["jan".to_string(), "10".to_string()],
+            vec!["feb".to_string(), "NULL".to_string()],
+        ];
+        let headers = vec!["month".to_string(), "sales".to_string()];
+        let parsed: serde_json::Value =
+            serde_json::from_str(&build_chart_json(&data, &headers)).unwrap();
+        assert_eq!(parsed["columns"], serde_json::json!(["month", "sales"]));
+        assert_eq!(parsed["data"]["month"], serde_json::json!(["jan", "feb"]));
+        assert_eq!(parsed["data"]["sales"], serde_json::json!([10, null]));
     }
 }
@@ -0,0 +1,273 @@
+//! Files dropped onto the window: `.sql` files open as query tabs, SQLite
+//! database files open through a connection pointed at them. An existing
+//! connection to the same file is reused; otherwise the user picks between
+//! saving a new connection and opening the file for this session only.
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use log::debug;
+
+use crate::{editor, models, sidebar_database, sidebar_query, window_egui};
+
+const SQLITE_EXTENSIONS: [&str; 4] = ["sqlite", "sqlite3", "db", "db3"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DroppedFile {
+    Query(PathBuf),
+    Sqlite(PathBuf),
+    Unsupported(PathBuf),
+}
+
+/// Route a dropped path by its extension.
+pub(crate) fn classify(path: &Path) -> DroppedFile {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+        .unwrap_or_default();
+    if extension == "sql" {
+        DroppedFile::Query(path.to_path_buf())
+    } else if SQLITE_EXTENSIONS.contains(&extension.as_str()) {
+        DroppedFile::Sqlite(path.to_path_buf())
+    } else {
+        DroppedFile::Unsupported(path.to_path_buf())
+    }
+}
+
+/// Path a SQLite connection opens (`host`, with or without the URL scheme).
+fn sqlite_file(connection: &models::structs::ConnectionConfig) -> &str {
+    let host = connection.host.trim();
+    host.strip_prefix("sqlite:").unwrap_or(host)
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Open this frame's dropped files and shade the window while files hover
+/// over it.
+pub(crate) fn handle_dropped_files(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let (hovering, dropped) = ctx.input(|i| {
+        (
+            !i.raw.hovered_files.is_empty(),
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|f| f.path.clone())
+                .collect::<Vec<_>>(),
+        )
+    });
+    if hovering {
+        paint_drop_hint(ctx);
+    }
+    for path in dropped {
+        match classify(&path) {
+            DroppedFile::Query(path) => {
+                let path = path.to_string_lossy().to_string();
+                debug!("📥 Opening dropped query file '{}'", path);
+                if let Err(err) = sidebar_query::open_query_file(tabular, &path) {
+                    tabular.toasts.error(err);
+                }
+            }
+            DroppedFile::Sqlite(path) => {
+                let existing = tabular
+                    .connections
+                    .iter()
+                    .find(|c| {
+                        c.connection_type == models::enums::DatabaseType::SQLite
+                            && same_file(Path::new(sqlite_file(c)), &path)
+                    })
+                    .and_then(|c| c.id);
+                match existing {
+                    Some(connection_id) => open_connection(tabular, connection_id),
+                    None if !tabular.pending_sqlite_drops.contains(&path) => {
+                        tabular.pending_sqlite_drops.push(path)
+                    }
+                    None => {}
+                }
+            }
+            DroppedFile::Unsupported(path) => {
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                tabular.toasts.warning(format!(
+                    "Can't open {}: drop a .sql file or a SQLite database",
+                    name
+                ));
+            }
+        }
+    }
+}
+
+fn paint_drop_hint(ctx: &egui::Context) {
+    let screen = ctx.content_rect();
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("file_drop_hint"),
+    ));
+    painter.rect_filled(screen, 0.0, egui::Color32::from_black_alpha(140));
+    painter.text(
+        screen.center(),
+        egui::Align2::CENTER_CENTER,
+        "Drop a .sql file to open it, or a SQLite database to connect",
+        egui::FontId::proportional(18.0),
+        egui::Color32::WHITE,
+    );
+}
+
+/// Expand the connection in the tree and open a query tab on it.
+fn open_connection(tabular: &mut window_egui::Tabular, connection_id: i64) {
+    let name = tabular
+        .get_connection_name(connection_id)
+        .unwrap_or_else(|| format!("Connection {}", connection_id));
+    tabular.selected_menu = "Database".to_string();
+    tabular.reveal_connection_in_tree(connection_id);
+    editor::create_new_tab_with_connection(
+        tabular,
+        format!("Query - {}", name),
+        String::new(),
+        Some(connection_id),
+    );
+}
+
+/// Id for a connection open for this session only. These count down from
+/// -1 so they never take an id the connections table hands out later.
+fn session_connection_id(connections: &[models::structs::ConnectionConfig]) -> i64 {
+    connections
+        .iter()
+        .filter_map(|c| c.id)
+        .min()
+        .unwrap_or(0)
+        .min(0)
+        - 1
+}
+
+/// Add a SQLite connection for `path`. A saved connection is written to the
+/// connection store; otherwise it only lives until the next restart.
+fn add_sqlite_connection(tabular: &mut window_egui::Tabular, path: &Path, save: bool) {
+    let connection = models::structs::ConnectionConfig {
+        name: path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "SQLite".to_string()),
+        host: path.to_string_lossy().to_string(),
+        port: String::new(),
+        connection_type: models::enums::DatabaseType::SQLite,
+        ..Default::default()
+    };
+    let mut connection_id = None;
+    if save && sidebar_database::save_connection_to_database(tabular, &connection) {
+        // Reloading rebuilds the tree with the new connection in it
+        sidebar_database::load_connections(tabular);
+        connection_id = tabular
+            .connections
+            .iter()
+            .rev()
+            .find(|c| {
+                c.connection_type == models::enums::DatabaseType::SQLite
+                    && c.host == connection.host
+            })
+            .and_then(|c| c.id);
+        if let Some(id) = connection_id {
+            tabular.maybe_auto_sync_connection(id);
+        }
+    }
+    let connection_id = match connection_id {
+        Some(id) => id,
+        None => {
+            if save {
+                tabular
+                    .toasts
+                    .warning("Could not save the connection; it is open for this session only");
+            }
+            let id = session_connection_id(&tabular.connections);
+            let connection = models::structs::ConnectionConfig {
+                id: Some(id),
+                ..connection
+            };
+            tabular.connections.push(connection.clone());
+            sidebar_database::add_connection_to_tree(tabular, &connection);
+            id
+        }
+    };
+    open_connection(tabular, connection_id);
+}
+
+/// Ask how to open the first dropped SQLite file that has no connection yet.
+pub(crate) fn render_sqlite_drop_prompt(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some(path) = tabular.pending_sqlite_drops.first().cloned() else {
+        return;
+    };
+    let mut choice: Option<bool> = None;
+    let mut dismissed = false;
+    egui::Window::new("Open SQLite Database")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("No connection uses this file yet:");
+            ui.label(
+                egui::RichText::new(path.to_string_lossy())
+                    .monospace()
+                    .weak(),
+            );
+            ui.add_space(6.0);
+            ui.label("Save a connection for it, or open it for this session only?");
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                if ui.button("💾 Save Connection").clicked() {
+                    choice = Some(true);
+                }
+                if ui.button("Open Once").clicked() {
+                    choice = Some(false);
+                }
+                if ui.button("Cancel").clicked() {
+                    dismissed = true;
+                }
+            });
+        });
+    if choice.is_some() || dismissed {
+        tabular.pending_sqlite_drops.remove(0);
+    }
+    if let Some(save) = choice {
+        add_sqlite_connection(tabular, &path, save);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropped_files_route_by_extension() {
+        let query = Path::new("/tmp/report.SQL");
+        assert_eq!(classify(query), DroppedFile::Query(query.to_path_buf()));
+        for name in ["app.db", "data.sqlite", "x.sqlite3", "y.db3"] {
+            assert!(matches!(classify(Path::new(name)), DroppedFile::Sqlite(_)));
+        }
+        assert!(matches!(
+            classify(Path::new("notes.txt")),
+            DroppedFile::Unsupported(_)
+        ));
+        assert!(matches!(
+            classify(Path::new("Makefile")),
+            DroppedFile::Unsupported(_)
+        ));
+    }
+
+    #[test]
+    fn session_connections_never_take_a_saved_id() {
+        let with_id = |id| models::structs::ConnectionConfig {
+            id: Some(id),
+            ..Default::default()
+        };
+        assert_eq!(session_connection_id(&[]), -1);
+        assert_eq!(session_connection_id(&[with_id(3), with_id(7)]), -1);
+        assert_eq!(session_connection_id(&[with_id(3), with_id(-1)]), -2);
+    }
+}
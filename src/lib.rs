@@ -5,6 +5,7 @@ pub mod ai_assistant;
 pub mod cache_data;
 pub mod config;
 pub mod connection;
+pub mod csv_import;
 pub mod curl_import;
 pub mod data_table;
 pub mod dialog;
@@ -19,14 +20,23 @@ pub mod driver_sqlite;
 pub mod editor;
 pub mod editor_autocomplete;
 pub mod editor_autocomplete_new; // temporary clean implementation backing the shim
+pub mod editor_bookmarks;
+pub mod editor_brackets;
 pub mod editor_buffer;
+pub mod editor_diff;
+pub mod editor_in_list;
+pub mod editor_lines;
 pub mod editor_selection;
 pub mod editor_state_adapter;
+pub mod editor_vim;
 pub mod export;
+pub mod file_drop;
 pub mod http_client;
 pub mod models;
 pub mod modules;
+pub mod query_presets;
 pub mod query_tools;
+pub mod recovery;
 pub mod redis_browser;
 pub mod secrets;
 pub mod safety_guard;
@@ -36,6 +46,9 @@ pub mod sidebar_history;
 pub mod sidebar_query;
 pub mod spreadsheet;
 pub mod ssh_tunnel;
+pub mod proxy_tunnel;
+pub mod unsaved_changes;
+pub mod workspace_profile;
 // Unified syntax / parsing module (legacy highlighter + optional tree-sitter parsing)
 #[cfg(feature = "query_ast")]
 pub mod query_ast;
@@ -98,6 +111,7 @@ pub fn run() -> Result<(), eframe::Error> {
         Box::new(move |_cc| {
             let mut app = window_egui::Tabular::new();
             app.set_initial_prefs(initial_prefs);
+            workspace_profile::restore_on_startup(&mut app);
             Ok(Box::new(app))
         }),
     )
@@ -62,6 +62,8 @@ pub enum NodeType {
 pub enum DBASpecialMode {
     ReplicationStatus,
     MasterStatus,
+    /// "Processes" / "Blocked Query": rows are live server sessions.
+    SessionList,
 }
 
 #[derive(Debug, Clone)]
@@ -69,7 +71,9 @@ pub enum BackgroundTask {
     RefreshConnection {
         connection_id: i64,
     },
-    CheckForUpdates,
+    CheckForUpdates {
+        channel: crate::self_update::UpdateChannel,
+    },
     StartPrefetch {
         connection_id: i64,
         show_progress: bool, // Whether to show progress in UI
@@ -96,6 +100,13 @@ pub enum BackgroundTask {
     },
 }
 
+/// Messages from a PostgreSQL LISTEN thread to the NOTIFY panel.
+#[derive(Debug, Clone)]
+pub enum PgListenMessage {
+    Notification(crate::models::structs::PgNotifyEvent),
+    Status(String),
+}
+
 // Infrequent mpsc channel message (one per background task completion), so the
 // size disparity between variants is not a hot-path concern.
 #[allow(clippy::large_enum_variant)]
@@ -105,6 +116,11 @@ pub enum BackgroundResult {
         connection_id: i64,
         success: bool,
     },
+    // Periodic progress while a RefreshConnection task is filling the cache
+    RefreshProgress {
+        connection_id: i64,
+        progress: crate::models::structs::SchemaRefreshProgress,
+    },
     UpdateCheckComplete {
         result: Result<crate::self_update::UpdateInfo, String>,
     },
@@ -138,6 +154,8 @@ pub enum BackgroundResult {
         database_name: String,
         search_text: String,
         keys: Vec<(String, String)>,
+        /// `TTL` replies for `keys`, where they could be read.
+        ttls: std::collections::HashMap<String, i64>,
     },
 }
 
@@ -237,6 +255,25 @@ impl SshAuthMethod {
     }
 }
 
+/// Protocol of a proxy in front of a database (see proxy_tunnel).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum ProxyKind {
+    #[default]
+    None,
+    Socks5,
+    Http,
+}
+
+impl ProxyKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProxyKind::None => "None",
+            ProxyKind::Socks5 => "SOCKS5",
+            ProxyKind::Http => "HTTP CONNECT",
+        }
+    }
+}
+
 // Enum untuk berbagai jenis database pool - sqlx pools are already thread-safe
 #[derive(Clone)]
 pub enum DatabasePool {
@@ -230,6 +230,21 @@ pub struct RedisBrowserKeyEntry {
     pub key_type: String,
     pub ttl_label: String,
     pub size_label: String,
+    /// `TTL` reply when it was fetched: seconds left, -1 for no expiry.
+    pub ttl_seconds: Option<i64>,
+}
+
+/// A key mutation from the Redis browser, awaiting confirmation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RedisKeyEdit {
+    /// `EXPIRE key seconds`
+    Expire { seconds: u64 },
+    /// `PERSIST key`
+    Persist,
+    /// `RENAMENX key new_name`
+    Rename { new_name: String },
+    /// `DEL key`
+    Delete,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -260,6 +275,8 @@ pub struct RedisBrowserState {
     pub preview: Option<RedisBrowserPreview>,
     pub last_error: Option<String>,
     pub status_text: String,
+    /// Key and mutation shown in the confirmation bar, if any.
+    pub key_edit: Option<(String, RedisKeyEdit)>,
 }
 
 #[derive(Clone)]
@@ -324,6 +341,42 @@ impl TreeNode {
     }
 }
 
+/// Raw constraint metadata of one column as reported by a driver; turned into
+/// [`AllowedValues`] by the grid editor.
+#[derive(Clone, Debug, Default)]
+pub struct ColumnConstraints {
+    /// Full type definition when the server spells enums inline (MySQL `enum('a','b')`).
+    pub type_definition: Option<String>,
+    /// Labels of a native enum type (PostgreSQL), in declaration order.
+    pub enum_values: Vec<String>,
+    /// CHECK expressions that mention the column (table or domain constraints).
+    pub check_clauses: Vec<String>,
+    /// Single-column foreign key target: (referenced table, referenced column).
+    pub references: Option<(String, String)>,
+}
+
+/// Values a column accepts, offered as a dropdown when editing a cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllowedValues {
+    pub values: Vec<String>,
+    pub source: AllowedValuesSource,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AllowedValuesSource {
+    Enum,
+    Check,
+    /// Sample of referenced values; the user may still type any value.
+    ForeignKey { table: String, column: String },
+}
+
+impl AllowedValues {
+    /// Enum and CHECK value sets are exhaustive; foreign key lookups are only hints.
+    pub fn is_exhaustive(&self) -> bool {
+        !matches!(self.source, AllowedValuesSource::ForeignKey { .. })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ForeignKey {
     pub constraint_name: String,
@@ -339,6 +392,25 @@ pub struct CustomView {
     pub query: String,
 }
 
+/// Proxy a connection's TCP traffic goes through. The password may be a
+/// `${VAR}` placeholder, resolved when the forwarder starts.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyConfig {
+    pub kind: models::enums::ProxyKind,
+    pub host: String,
+    pub port: String,
+    pub username: String,
+    pub password: String,
+}
+
+/// An extra database file ATTACHed to a SQLite connection under `schema`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SqliteAttachment {
+    pub schema: String,
+    pub path: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DiagramGroup {
     pub id: String,
@@ -428,6 +500,14 @@ impl Default for DiagramState {
     }
 }
 
+/// PostgreSQL column types the grid edits element by element.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PgValueKind {
+    Array,
+    /// Composite (row) type with its field names in order.
+    Composite(Vec<String>),
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ColumnMetadata {
     pub name: String,
@@ -435,6 +515,8 @@ pub struct ColumnMetadata {
     pub table_name: Option<String>, // Source table name if available
     pub original_name: Option<String>, // Original column name if aliased
     pub is_primary_key: bool,
+    #[serde(default)]
+    pub pg_kind: Option<PgValueKind>, // Array / composite columns (PostgreSQL only)
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -451,10 +533,22 @@ pub struct QueryResult {
     pub query_message_is_error: bool,
     pub execution_time_ms: u128,
     pub explain_plan_json: Option<String>,
+    /// (snapshot unix time, statement) when served from the query result cache.
+    #[serde(default)]
+    pub cached: Option<(i64, String)>,
+}
+
+/// A named bookmark on a line (0-based) of a query tab.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EditorBookmark {
+    pub line: usize,
+    pub label: String,
 }
 
 #[derive(Clone, Debug)]
 pub struct QueryTab {
+    /// Unique for the session; unlike the index or title it never changes.
+    pub id: u64,
     pub title: String,
     pub content: String,
     pub file_path: Option<String>,
@@ -500,9 +594,81 @@ pub struct QueryTab {
     pub tx_mode: bool,
     pub tx_active: bool,
     pub session: Option<crate::connection::session::SessionHandle>,
+
+    // Editor share of the editor/result split; None = preference default
+    pub split_ratio: Option<f32>,
+    // Show the result one record at a time instead of the grid
+    pub record_view: bool,
+    // Result shown transposed (columns as rows) — see data_table::transpose
+    pub transposed: bool,
+    // Named line bookmarks, sorted by line — see editor_bookmarks.rs
+    pub bookmarks: Vec<EditorBookmark>,
+    // Result columns hidden from the grid, by name, and the headers of the
+    // result they were hidden in — see data_table::column_visibility
+    pub hidden_columns: Vec<String>,
+    pub hidden_columns_headers: Vec<String>,
+    // Index hints for the last slow query — see data_table::index_advice
+    pub index_suggestions: Vec<crate::data_table::IndexSuggestion>,
+    // Results kept across later runs, and the one on screen — see data_table::pinned_results
+    pub pinned_results: Vec<crate::data_table::PinnedResult>,
+    pub active_pinned: Option<usize>,
+    // Result on screen before a pinned one was opened, restored when leaving it
+    pub live_result: Option<QueryResult>,
+    // Last values given to the query's parameters — see query_tools::params
+    pub param_values: Vec<(String, String)>,
+}
+
+impl QueryTab {
+    /// Name shown on the tab: the file name for tabs backed by a query file,
+    /// the title otherwise.
+    pub fn display_name(&self) -> String {
+        if self.http_client_state.is_none()
+            && self.redis_browser_state.is_none()
+            && let Some(name) = self
+                .file_path
+                .as_deref()
+                .and_then(|p| std::path::Path::new(p).file_name())
+        {
+            return name.to_string_lossy().into_owned();
+        }
+        self.title.clone()
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Metadata a saved query declares in its leading comment block
+/// (`-- @name:`, `-- @desc:`, `-- @tags:`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SavedQueryMeta {
+    pub name: String,
+    pub description: String,
+    pub tags: Vec<String>,
+}
+
+impl SavedQueryMeta {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty() && self.description.is_empty() && self.tags.is_empty()
+    }
+
+    /// True when every term matches (is a substring of) one of the tags,
+    /// ignoring case.
+    pub fn matches_tags(&self, terms: &[String]) -> bool {
+        terms.iter().all(|term| {
+            self.tags
+                .iter()
+                .any(|tag| tag.to_lowercase().contains(&term.to_lowercase()))
+        })
+    }
+}
+
+/// Named parameter values for a saved query, kept next to its file (see
+/// query_presets.rs). `values` pairs a placeholder (`:start_date`) with its value.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParameterPreset {
+    pub name: String,
+    pub values: Vec<(String, String)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum EditorColorTheme {
     GithubDark,
     GithubLight,
@@ -521,6 +687,8 @@ pub struct AdvancedEditor {
     #[allow(dead_code)]
     pub show_whitespace: bool,
     pub word_wrap: bool,
+    // Modal (vim-style) key handling, see editor_vim
+    pub vim_mode: bool,
     // Number of visible rows the editor should aim to display; set dynamically to fill height
     pub desired_rows: usize,
     pub find_text: String,
@@ -540,6 +708,7 @@ impl Default for AdvancedEditor {
             auto_indent: true,
             show_whitespace: false,
             word_wrap: false,
+            vim_mode: false,
             desired_rows: 25,
             find_text: String::new(),
             replace_text: String::new(),
@@ -557,6 +726,10 @@ pub struct HistoryItem {
     pub connection_id: i64,
     pub connection_name: String,
     pub executed_at: String,
+    /// Database the statement ran in; `None` for entries recorded before it
+    /// was stored.
+    #[serde(default)]
+    pub database_name: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -577,11 +750,77 @@ pub struct ConnectionConfig {
     pub ssh_auth_method: models::enums::SshAuthMethod,
     pub ssh_private_key: String,
     pub ssh_password: String,
+    /// Passphrase of an encrypted `ssh_private_key`; empty for plain keys.
+    #[serde(default)]
+    pub ssh_key_passphrase: String,
     pub ssh_accept_unknown_host_keys: bool,
+    /// MsSQL only: encrypt the whole TDS session, not just the login.
+    #[serde(default = "default_mssql_tls_flag")]
+    pub mssql_encrypt: bool,
+    /// MsSQL only: accept self-signed / unverifiable server certificates.
+    #[serde(default = "default_mssql_tls_flag")]
+    pub mssql_trust_server_certificate: bool,
     #[serde(default)]
     pub custom_views: Vec<CustomView>,
+    /// SQLite only: additional files attached to every pooled connection.
+    #[serde(default)]
+    pub sqlite_attachments: Vec<SqliteAttachment>,
     #[serde(default)]
     pub replication_master_id: Option<i64>,
+    /// Schema new PostgreSQL tabs start in (their search_path).
+    #[serde(default)]
+    pub default_schema: String,
+    /// Database last picked in a tab; new tabs start there over `database`.
+    #[serde(default)]
+    pub last_used_database: Option<String>,
+    /// PostgreSQL / MySQL: read the password from `~/.pgpass` / `~/.my.cnf`
+    /// when connections load instead of storing it (see connection::credential_files).
+    #[serde(default)]
+    pub use_credential_file: bool,
+    /// Short environment label shown on tabs and above the editor, e.g. "PROD".
+    #[serde(default)]
+    pub label: String,
+    /// Tag color as `#RRGGBB`, empty for none (see connection::tag).
+    #[serde(default)]
+    pub color: String,
+    /// Proxy of this connection; when off, the global proxy applies.
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+    /// Fields as saved, when loading resolved `${VAR}` placeholders or a
+    /// credential file into them (see connection::resolve_loaded_connection).
+    #[serde(skip)]
+    pub templates: Option<ConnectionTemplates>,
+}
+
+/// Connection fields as the user wrote them, before load-time resolution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionTemplates {
+    pub host: String,
+    pub port: String,
+    pub username: String,
+    pub password: String,
+    pub database: String,
+    /// Why resolving failed, e.g. an unset environment variable.
+    pub error: Option<String>,
+}
+
+impl ConnectionConfig {
+    /// Copy to save or edit: resolved fields go back to their templates.
+    pub fn for_storage(&self) -> ConnectionConfig {
+        let mut stored = self.clone();
+        if let Some(templates) = stored.templates.take() {
+            stored.host = templates.host;
+            stored.port = templates.port;
+            stored.username = templates.username;
+            stored.password = templates.password;
+            stored.database = templates.database;
+        }
+        stored
+    }
+}
+
+fn default_mssql_tls_flag() -> bool {
+    true
 }
 
 impl Default for ConnectionConfig {
@@ -603,9 +842,70 @@ impl Default for ConnectionConfig {
             ssh_auth_method: models::enums::SshAuthMethod::Key,
             ssh_private_key: String::new(),
             ssh_password: String::new(),
+            ssh_key_passphrase: String::new(),
             ssh_accept_unknown_host_keys: false,
+            mssql_encrypt: true,
+            mssql_trust_server_certificate: true,
             custom_views: Vec::new(),
+            sqlite_attachments: Vec::new(),
             replication_master_id: None,
+            default_schema: String::new(),
+            last_used_database: None,
+            use_credential_file: false,
+            label: String::new(),
+            color: String::new(),
+            proxy: ProxyConfig::default(),
+            templates: None,
+        }
+    }
+}
+
+/// A table, view or column the editor's go-to-definition resolved to, revealed
+/// in the sidebar tree. Also the tree node the sidebar scrolls to next.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefinitionTarget {
+    pub connection_id: i64,
+    pub database_name: String,
+    pub table: String,
+    pub node_type: NodeType,
+    pub column: Option<String>,
+}
+
+impl DefinitionTarget {
+    /// `database.table` or `database.table.column`, for the picker and toasts.
+    pub fn label(&self) -> String {
+        let mut label = format!("{}.{}", self.database_name, self.table);
+        if let Some(column) = &self.column {
+            label.push('.');
+            label.push_str(column);
+        }
+        label
+    }
+
+    /// Whether `node` is the tree row for this target: the table/view node, or
+    /// the column under the table's Columns folder.
+    pub fn matches_node(&self, node: &TreeNode) -> bool {
+        if node.connection_id != Some(self.connection_id)
+            || node
+                .database_name
+                .as_deref()
+                .is_some_and(|db| !db.eq_ignore_ascii_case(&self.database_name))
+        {
+            return false;
+        }
+        let table = node.table_name.as_deref().unwrap_or(&node.name);
+        match (&self.column, &node.node_type) {
+            (None, NodeType::Table | NodeType::View) => table.eq_ignore_ascii_case(&self.table),
+            (Some(column), NodeType::Column) => {
+                // Column rows read "name (type)"
+                let name = node.name.split(" (").next().unwrap_or(&node.name);
+                name.eq_ignore_ascii_case(column)
+                    && node
+                        .table_name
+                        .as_deref()
+                        .is_some_and(|t| t.eq_ignore_ascii_case(&self.table))
+            }
+            _ => false,
         }
     }
 }
@@ -669,6 +969,9 @@ pub struct IndexStructInfo {
     pub method: Option<String>, // algorithm / type (btree, hash, etc.)
     pub unique: bool,
     pub columns: Vec<String>,
+    pub included_columns: Vec<String>, // INCLUDE columns (PostgreSQL, SQL Server)
+    pub scans: Option<i64>,            // index scans since the stats reset (PostgreSQL)
+    pub cardinality: Option<i64>,      // estimated distinct values (MySQL)
 }
 
 // Simplified partition info shown in Structure -> Partitions
@@ -725,6 +1028,12 @@ pub struct SpreadsheetOperation {
     pub primary_key_values: std::collections::HashMap<String, String>,
 }
 
+/// Grid save running in a transaction: the edits it saves and its outcome.
+pub type SpreadsheetSave = (
+    Vec<CellEditOperation>,
+    std::sync::mpsc::Receiver<Result<(), String>>,
+);
+
 #[derive(Clone, Debug, Default)]
 pub struct SpreadsheetState {
     pub editing_cell: Option<(usize, usize)>, // (row, col) being edited
@@ -732,7 +1041,8 @@ pub struct SpreadsheetState {
     pub pending_operations: Vec<CellEditOperation>, // Unsaved changes
     pub is_dirty: bool,                       // Whether there are unsaved changes
     pub primary_key_columns: Vec<String>,     // Primary key column names for generating SQL
-    pub enum_options: Option<Vec<String>>,    // If editing an ENUM, available options
+    pub allowed_values: Option<AllowedValues>, // Known value set of the column being edited
+    pub edit_focus_requested: bool,           // Edit field already grabbed focus for this edit
 }
 
 #[derive(Clone, Debug)]
@@ -936,13 +1246,40 @@ pub enum CsvImportStatus {
     Idle,
     Importing,
     Done(usize),
+    /// Stopped by the user after this many rows were committed.
+    Cancelled(usize),
     Failed(String),
 }
 
+/// What a CSV import does when a row is rejected by the database.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CsvRowErrorPolicy {
+    /// Roll back the current batch and stop; earlier batches stay committed.
+    #[default]
+    Abort,
+    /// Leave the row out, record the error, and carry on.
+    Skip,
+}
+
+/// Running totals of a background CSV import, shared with the dialog.
+#[derive(Debug, Default)]
+pub struct CsvImportProgress {
+    pub rows_imported: usize,
+    pub rows_skipped: usize,
+    /// Share of the file read so far.
+    pub fraction: f32,
+    /// `(line, error)` of rejected rows, capped.
+    pub row_errors: Vec<(u64, String)>,
+    /// Set once the import has stopped.
+    pub finished: Option<CsvImportStatus>,
+}
+
 #[derive(Clone, Debug)]
 pub struct CsvColumnMapping {
     pub csv_header: String,
     pub target_column: String, // "__skip__" = skip this column
+    /// Inferred from the preview rows; the column type for a new table.
+    pub kind: crate::csv_import::ColumnKind,
 }
 
 #[derive(Clone, Debug)]
@@ -951,6 +1288,8 @@ pub struct CsvImportState {
     pub database_name: Option<String>,
     pub table_name: String,
     pub db_type: crate::models::enums::DatabaseType,
+    /// Create `table_name` from the mapped columns before importing.
+    pub create_table: bool,
     pub file_path: Option<std::path::PathBuf>,
     pub delimiter: char,
     pub has_header_row: bool,
@@ -959,8 +1298,107 @@ pub struct CsvImportState {
     pub preview_rows: Vec<Vec<String>>,
     pub table_columns: Vec<String>,
     pub column_mappings: Vec<CsvColumnMapping>,
+    pub row_error_policy: CsvRowErrorPolicy,
     pub status: CsvImportStatus,
     pub progress_message: String,
+    pub progress: Option<Arc<Mutex<CsvImportProgress>>>,
+    pub cancel: Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl CsvImportState {
+    /// Import into `table_name` (whose known columns are `table_columns`), or
+    /// into a new table when there is no target table yet.
+    pub fn new(
+        connection_id: i64,
+        database_name: Option<String>,
+        table_name: String,
+        db_type: crate::models::enums::DatabaseType,
+        table_columns: Vec<String>,
+    ) -> Self {
+        Self {
+            connection_id,
+            database_name,
+            create_table: table_name.trim().is_empty(),
+            table_name,
+            db_type,
+            file_path: None,
+            delimiter: ',',
+            has_header_row: true,
+            null_value: String::new(),
+            preview_headers: Vec::new(),
+            preview_rows: Vec::new(),
+            table_columns,
+            column_mappings: Vec::new(),
+            row_error_policy: CsvRowErrorPolicy::default(),
+            status: CsvImportStatus::Idle,
+            progress_message: String::new(),
+            progress: None,
+            cancel: Arc::default(),
+        }
+    }
+}
+
+// ── Routine Call ──────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoutineKind {
+    Procedure,
+    Function,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParameterMode {
+    In,
+    Out,
+    InOut,
+}
+
+impl ParameterMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            ParameterMode::In => "IN",
+            ParameterMode::Out => "OUT",
+            ParameterMode::InOut => "INOUT",
+        }
+    }
+
+    /// Whether the caller supplies a value for the parameter.
+    pub fn takes_value(self) -> bool {
+        self != ParameterMode::Out
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoutineParameter {
+    pub name: String,
+    /// Declared type as the database prints it (`varchar(20)`, `integer`).
+    pub data_type: String,
+    pub mode: ParameterMode,
+}
+
+/// Parameter signature of a routine: its parameters and, for SQL Server
+/// functions, whether it is table-valued.
+pub type RoutineSignature = (Vec<RoutineParameter>, bool);
+
+/// State of the "Call routine" dialog opened from a procedure or function
+/// node in the sidebar.
+pub struct RoutineCallState {
+    pub connection_id: i64,
+    pub database_name: Option<String>,
+    /// Name as shown in the tree.
+    pub routine_name: String,
+    /// Fully qualified, quoted name used in the call.
+    pub qualified_name: String,
+    pub kind: RoutineKind,
+    pub db_type: crate::models::enums::DatabaseType,
+    pub parameters: Vec<RoutineParameter>,
+    /// One input per parameter; empty means NULL.
+    pub values: Vec<String>,
+    /// SQL Server table-valued function, selected FROM rather than called.
+    pub returns_table: bool,
+    pub error: Option<String>,
+    /// Pending signature lookup.
+    pub signature_receiver: Option<mpsc::Receiver<Result<RoutineSignature, String>>>,
 }
 
 // ── Schema Diff ───────────────────────────────────────────────────────────────
@@ -1040,6 +1478,118 @@ impl SchemaDiffState {
     }
 }
 
+/// One NOTIFY payload received by the LISTEN panel.
+#[derive(Clone, Debug)]
+pub struct PgNotifyEvent {
+    pub received_at: chrono::DateTime<chrono::Local>,
+    pub channel: String,
+    pub payload: String,
+}
+
+/// State of the PostgreSQL LISTEN/NOTIFY panel. The listener runs on its own
+/// thread and connection; `stop_flag` ends it, `receiver` feeds the UI.
+#[derive(Default)]
+pub struct PgListenState {
+    pub connection_id: Option<i64>,
+    pub channels_input: String,
+    pub events: Vec<PgNotifyEvent>,
+    pub status: String,
+    pub receiver: Option<mpsc::Receiver<models::enums::PgListenMessage>>,
+    pub stop_flag: Option<Arc<std::sync::atomic::AtomicBool>>,
+}
+
+impl PgListenState {
+    /// Cap on retained notifications; the oldest are dropped first.
+    pub const MAX_EVENTS: usize = 2000;
+
+    pub fn is_listening(&self) -> bool {
+        self.stop_flag.is_some()
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(flag) = self.stop_flag.take() {
+            flag.store(true, std::sync::atomic::Ordering::Relaxed);
+        }
+        self.receiver = None;
+    }
+}
+
+/// State of the read-only "Compare tab with..." diff view.
+#[derive(Clone, Debug)]
+pub struct TabCompareState {
+    pub left_tab: usize,
+    pub right_tab: usize,
+    /// Cached diff, recomputed when either buffer changes.
+    pub rows: Vec<crate::editor_diff::SideBySideRow>,
+    pub unified: String,
+    pub added: usize,
+    pub removed: usize,
+    /// Buffers the cache was computed from.
+    pub source: Option<(String, String)>,
+}
+
+impl TabCompareState {
+    pub fn new(left_tab: usize, right_tab: usize) -> Self {
+        Self {
+            left_tab,
+            right_tab,
+            rows: Vec::new(),
+            unified: String::new(),
+            added: 0,
+            removed: 0,
+            source: None,
+        }
+    }
+
+    /// Recompute the diff if `left`/`right` differ from the cached source.
+    pub fn refresh(&mut self, left: &str, right: &str) {
+        if let Some((l, r)) = &self.source
+            && l == left
+            && r == right
+        {
+            return;
+        }
+        let script = crate::editor_diff::diff_lines(left, right);
+        self.added = script.iter().filter(|l| l.op == crate::editor_diff::DiffOp::Insert).count();
+        self.removed = script.iter().filter(|l| l.op == crate::editor_diff::DiffOp::Delete).count();
+        self.rows = crate::editor_diff::side_by_side(&script);
+        self.unified = crate::editor_diff::unified_text(&script);
+        self.source = Some((left.to_string(), right.to_string()));
+    }
+}
+
+/// Progress of a background schema refresh, sampled from the cache as it fills.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SchemaRefreshProgress {
+    /// Databases discovered so far (rows in `database_cache`).
+    pub databases_total: usize,
+    /// Databases whose tables have been cached.
+    pub databases_done: usize,
+    pub tables_cached: usize,
+}
+
+impl SchemaRefreshProgress {
+    /// Completed fraction in 0..=1, or None while no database is known yet.
+    pub fn fraction(&self) -> Option<f32> {
+        (self.databases_total > 0)
+            .then(|| (self.databases_done.min(self.databases_total) as f32) / self.databases_total as f32)
+    }
+
+    /// Short sidebar label, e.g. "42% · 5/12 db · 830 tables".
+    pub fn label(&self) -> String {
+        match self.fraction() {
+            Some(fraction) => format!(
+                "{:.0}% · {}/{} db · {} tables",
+                fraction * 100.0,
+                self.databases_done.min(self.databases_total),
+                self.databases_total,
+                self.tables_cached
+            ),
+            None => "Syncing…".to_string(),
+        }
+    }
+}
+
 mod serde_color {
     use serde::{Deserialize, Deserializer, Serializer};
     use eframe::egui::Color32;
@@ -1142,3 +1692,102 @@ mod serde_option_pos2 {
         Ok(opt.map(|arr| Pos2::new(arr[0], arr[1])))
     }
 }
+
+/// A pending "kill session" action from a DBA view, awaiting confirmation.
+#[derive(Clone, Debug)]
+pub struct SessionKillRequest {
+    pub connection_id: i64,
+    pub session_id: String,
+    /// Statement the session is running, if the view exposes it.
+    pub query: Option<String>,
+    /// Query to re-run afterwards so the view reflects the kill.
+    pub rerun_query: String,
+    pub error: Option<String>,
+}
+
+/// Approximate size of one table, shown in the tree tooltip. `None` fields
+/// are estimates the driver could not provide.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableStats {
+    pub rows: Option<u64>,
+    /// `rows` is a lower bound: counting stopped there.
+    pub rows_capped: bool,
+    pub bytes: Option<u64>,
+}
+
+/// Tree tooltip state of one table's [`TableStats`].
+#[derive(Clone, Debug)]
+pub enum TableStatsEntry {
+    /// Hovered; the next frame starts the fetch.
+    Requested,
+    Loading,
+    Ready(TableStats, std::time::Instant),
+}
+
+/// `(connection_id, database, table)` → stats. Shared with fetch tasks.
+pub type TableStatsCache =
+    Arc<Mutex<std::collections::HashMap<(i64, String, String), TableStatsEntry>>>;
+
+/// What a successful connection test learned about the server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionProbe {
+    /// Round trip of the version query on the open connection.
+    pub latency: std::time::Duration,
+    pub server_version: String,
+}
+
+impl ConnectionProbe {
+    /// `12 ms round trip · PostgreSQL 16.2 on x86_64…`, first line of the
+    /// version only.
+    pub fn summary(&self) -> String {
+        let version = self.server_version.lines().next().unwrap_or("").trim();
+        let latency_ms = self.latency.as_secs_f64() * 1000.0;
+        let latency = if latency_ms < 10.0 {
+            format!("{:.1} ms", latency_ms)
+        } else {
+            format!("{:.0} ms", latency_ms)
+        };
+        if version.is_empty() {
+            format!("{} round trip", latency)
+        } else {
+            format!("{} round trip · {}", latency, version)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_refresh_progress_label() {
+        let unknown = SchemaRefreshProgress::default();
+        assert_eq!(unknown.fraction(), None);
+        assert_eq!(unknown.label(), "Syncing…");
+
+        let partial = SchemaRefreshProgress {
+            databases_total: 4,
+            databases_done: 1,
+            tables_cached: 37,
+        };
+        assert_eq!(partial.fraction(), Some(0.25));
+        assert_eq!(partial.label(), "25% · 1/4 db · 37 tables");
+    }
+
+    #[test]
+    fn connection_probe_summary_keeps_first_version_line() {
+        let probe = ConnectionProbe {
+            latency: std::time::Duration::from_micros(4_250),
+            server_version: "Microsoft SQL Server 2022 (RTM)\n\tCopyright".to_string(),
+        };
+        assert_eq!(
+            probe.summary(),
+            "4.2 ms round trip · Microsoft SQL Server 2022 (RTM)"
+        );
+        let slow = ConnectionProbe {
+            latency: std::time::Duration::from_millis(183),
+            server_version: String::new(),
+        };
+        assert_eq!(slow.summary(), "183 ms round trip");
+    }
+}
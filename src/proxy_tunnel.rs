@@ -0,0 +1,675 @@
+//! Database connections through a SOCKS5 or HTTP CONNECT proxy.
+//!
+//! Drivers only take a host and port, so, like the SSH tunnel, a listener on
+//! 127.0.0.1 forwards every accepted socket through the proxy to the database.
+//! One handshake runs before the listener is handed out, so an unreachable
+//! proxy, rejected proxy credentials and an unreachable database each surface
+//! as their own error instead of a generic connection failure.
+
+use crate::models::{
+    self,
+    enums::{DatabaseType, ProxyKind},
+    structs::ProxyConfig,
+};
+use log::debug;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Shutdown, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum ProxyError {
+    #[error("Proxy {proxy} is unreachable: {reason}")]
+    ProxyUnreachable { proxy: String, reason: String },
+    #[error("Proxy authentication failed at {proxy}: {reason}")]
+    AuthFailed { proxy: String, reason: String },
+    #[error("Proxy {proxy} could not reach {target}: {reason}")]
+    TargetUnreachable {
+        proxy: String,
+        target: String,
+        reason: String,
+    },
+    #[error("Unexpected reply from proxy {proxy}: {reason}")]
+    Protocol { proxy: String, reason: String },
+}
+
+/// Proxy and destination of one forwarder, with the password resolved.
+#[derive(Clone, Debug)]
+struct Route {
+    kind: ProxyKind,
+    proxy_host: String,
+    proxy_port: u16,
+    username: String,
+    password: String,
+    target_host: String,
+    target_port: u16,
+}
+
+impl Route {
+    fn proxy(&self) -> String {
+        host_port(&self.proxy_host, self.proxy_port)
+    }
+
+    fn target(&self) -> String {
+        host_port(&self.target_host, self.target_port)
+    }
+
+    /// Everything identifying the route except the password, so it can be
+    /// used as a registry key and logged.
+    fn fingerprint(&self) -> String {
+        format!(
+            "{:?}|{}|{}|{}",
+            self.kind,
+            self.proxy(),
+            self.username,
+            self.target()
+        )
+    }
+
+    fn protocol(&self, reason: impl Into<String>) -> ProxyError {
+        ProxyError::Protocol {
+            proxy: self.proxy(),
+            reason: reason.into(),
+        }
+    }
+
+    fn auth_failed(&self, reason: impl Into<String>) -> ProxyError {
+        ProxyError::AuthFailed {
+            proxy: self.proxy(),
+            reason: reason.into(),
+        }
+    }
+
+    fn target_unreachable(&self, reason: impl Into<String>) -> ProxyError {
+        ProxyError::TargetUnreachable {
+            proxy: self.proxy(),
+            target: self.target(),
+            reason: reason.into(),
+        }
+    }
+}
+
+struct Forwarder {
+    fingerprint: String,
+    password: String,
+    local_port: u16,
+    stop: Arc<AtomicBool>,
+}
+
+impl Forwarder {
+    fn serves(&self, route: &Route) -> bool {
+        self.fingerprint == route.fingerprint() && self.password == route.password
+    }
+
+    fn terminate(self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(("127.0.0.1", self.local_port));
+    }
+}
+
+static FORWARDERS: Lazy<Mutex<HashMap<String, Forwarder>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Proxy from Preferences, used by connections without one of their own.
+static GLOBAL_PROXY: Lazy<Mutex<ProxyConfig>> = Lazy::new(|| Mutex::new(ProxyConfig::default()));
+
+pub fn set_global_proxy(proxy: &ProxyConfig) {
+    if let Ok(mut global) = GLOBAL_PROXY.lock() {
+        *global = proxy.clone();
+    }
+}
+
+/// Secret-store name of the global proxy's password.
+const GLOBAL_PROXY_SECRET: &str = "pref:global_proxy_password";
+
+/// `proxy` serialized for the database, its password moved into the secret
+/// store under `secret_name` so the JSON only holds the sentinel.
+pub(crate) fn proxy_json_for_storage(proxy: &ProxyConfig, secret_name: &str) -> String {
+    let mut stored = proxy.clone();
+    stored.password = crate::secrets::store_or_keep(secret_name, &proxy.password);
+    serde_json::to_string(&stored).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Proxy settings read from disk with the password resolved. With `migrate`,
+/// a legacy plaintext password is moved into the secret store and the JSON
+/// to write back is returned alongside.
+pub(crate) fn proxy_from_storage(
+    json: &str,
+    secret_name: &str,
+    migrate: bool,
+) -> (ProxyConfig, Option<String>) {
+    let mut proxy: ProxyConfig = serde_json::from_str(json).unwrap_or_default();
+    if !migrate {
+        proxy.password = crate::secrets::resolve_readonly(secret_name, &proxy.password);
+        return (proxy, None);
+    }
+    let (password, rewrite) = crate::secrets::resolve_stored(secret_name, &proxy.password);
+    let rewrite = rewrite.map(|stored| {
+        let mut sanitized = proxy.clone();
+        sanitized.password = stored;
+        serde_json::to_string(&sanitized).unwrap_or_else(|_| "{}".to_string())
+    });
+    proxy.password = password;
+    (proxy, rewrite)
+}
+
+/// The `global_proxy` preference as persisted (see [`proxy_json_for_storage`]).
+pub(crate) fn global_proxy_for_storage(json: &str) -> String {
+    if json.trim().is_empty() {
+        return String::new();
+    }
+    let proxy: ProxyConfig = serde_json::from_str(json).unwrap_or_default();
+    proxy_json_for_storage(&proxy, GLOBAL_PROXY_SECRET)
+}
+
+/// The `global_proxy` preference read from disk with its password resolved,
+/// plus the value to write back when a plaintext password was migrated.
+pub(crate) fn global_proxy_from_storage(json: &str) -> (String, Option<String>) {
+    if json.trim().is_empty() {
+        return (String::new(), None);
+    }
+    let (proxy, rewrite) = proxy_from_storage(json, GLOBAL_PROXY_SECRET, true);
+    (
+        serde_json::to_string(&proxy).unwrap_or_else(|_| json.to_string()),
+        rewrite,
+    )
+}
+
+fn host_port(host: &str, port: u16) -> String {
+    if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+fn is_local_host(host: &str) -> bool {
+    let host = host.trim().trim_start_matches('[').trim_end_matches(']');
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Proxy `connection` goes through: its own, else the global one unless the
+/// database runs on this machine. SQLite files and HTTP APIs never use one.
+pub fn proxy_for(connection: &models::structs::ConnectionConfig) -> Option<ProxyConfig> {
+    if matches!(
+        connection.connection_type,
+        DatabaseType::SQLite | DatabaseType::ApiHttp
+    ) {
+        return None;
+    }
+    if connection.proxy.kind != ProxyKind::None {
+        return Some(connection.proxy.clone());
+    }
+    if is_local_host(&connection.host) {
+        return None;
+    }
+    let global = GLOBAL_PROXY.lock().ok()?.clone();
+    (global.kind != ProxyKind::None).then_some(global)
+}
+
+fn route_for(
+    connection: &models::structs::ConnectionConfig,
+    proxy: &ProxyConfig,
+) -> Result<Route, String> {
+    if proxy.host.trim().is_empty() {
+        return Err("Proxy host cannot be empty".to_string());
+    }
+    let proxy_port = proxy
+        .port
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| "Proxy port must be a valid number".to_string())?;
+    let target_port = connection
+        .port
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| "Database port must be a valid number when using a proxy".to_string())?;
+    let password = crate::connection::pool::resolve_env_placeholders(&proxy.password)
+        .map_err(|e| format!("Proxy password: {}", e))?;
+    Ok(Route {
+        kind: proxy.kind,
+        proxy_host: proxy.host.trim().to_string(),
+        proxy_port,
+        username: proxy.username.trim().to_string(),
+        password,
+        target_host: connection.host.trim().to_string(),
+        target_port,
+    })
+}
+
+/// Local port forwarding to `connection`'s database through `proxy`.
+pub fn ensure_forwarder(
+    connection: &models::structs::ConnectionConfig,
+    proxy: &ProxyConfig,
+) -> Result<u16, String> {
+    let route = route_for(connection, proxy)?;
+    let fingerprint = route.fingerprint();
+    let key = match connection.id {
+        Some(id) => format!("id:{id}"),
+        None => format!("tmp:{fingerprint}"),
+    };
+    {
+        let mut registry = FORWARDERS
+            .lock()
+            .map_err(|_| "Failed to lock proxy registry".to_string())?;
+        if let Some(existing) = registry.get(&key) {
+            if existing.serves(&route) {
+                return Ok(existing.local_port);
+            }
+            if let Some(stale) = registry.remove(&key) {
+                stale.terminate();
+            }
+        }
+    }
+
+    // Check the route now so the error says which hop failed. The registry
+    // stays unlocked meanwhile: the handshake can take the full timeout.
+    open_through_proxy(&route).map_err(|e| e.to_string())?;
+
+    let listener = TcpListener::bind(("127.0.0.1", 0))
+        .map_err(|e| format!("Failed to allocate local port: {e}"))?;
+    let local_port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to allocate local port: {e}"))?
+        .port();
+    let stop = Arc::new(AtomicBool::new(false));
+    let forwarder = Forwarder {
+        fingerprint,
+        password: route.password.clone(),
+        local_port,
+        stop: stop.clone(),
+    };
+    std::thread::Builder::new()
+        .name(format!("proxy-{}", route.target()))
+        .spawn(move || serve(listener, route, stop))
+        .map_err(|e| format!("Failed to start proxy forwarder: {e}"))?;
+
+    let mut registry = FORWARDERS
+        .lock()
+        .map_err(|_| "Failed to lock proxy registry".to_string())?;
+    // Another caller may have opened the same route while this one shook hands
+    if let Some(existing) = registry.get(&key)
+        && existing.fingerprint == forwarder.fingerprint
+        && existing.password == forwarder.password
+    {
+        let port = existing.local_port;
+        forwarder.terminate();
+        return Ok(port);
+    }
+    debug!("Proxy forwarder for key {} on port {}", key, local_port);
+    if let Some(stale) = registry.insert(key, forwarder) {
+        stale.terminate();
+    }
+    Ok(local_port)
+}
+
+pub fn shutdown_by_id(connection_id: i64) {
+    let key = format!("id:{connection_id}");
+    let Ok(mut registry) = FORWARDERS.lock() else {
+        return;
+    };
+    if let Some(forwarder) = registry.remove(&key) {
+        debug!("Shutting down proxy forwarder for key {}", key);
+        forwarder.terminate();
+    }
+}
+
+/// Proxy rows of a settings grid. Returns whether anything changed.
+pub(crate) fn render_proxy_fields(
+    ui: &mut eframe::egui::Ui,
+    proxy: &mut ProxyConfig,
+    id_salt: &str,
+) -> bool {
+    use eframe::egui;
+
+    let before = proxy.clone();
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(proxy.kind.label())
+        .show_ui(ui, |ui| {
+            for kind in [ProxyKind::None, ProxyKind::Socks5, ProxyKind::Http] {
+                ui.selectable_value(&mut proxy.kind, kind, kind.label());
+            }
+        });
+    ui.end_row();
+
+    if proxy.kind != ProxyKind::None {
+        ui.label("Proxy Host:");
+        ui.text_edit_singleline(&mut proxy.host);
+        ui.end_row();
+
+        ui.label("Proxy Port:");
+        ui.add(egui::TextEdit::singleline(&mut proxy.port).hint_text(
+            if proxy.kind == ProxyKind::Socks5 {
+                "1080"
+            } else {
+                "3128"
+            },
+        ));
+        ui.end_row();
+
+        ui.label("Proxy Username:");
+        ui.add(egui::TextEdit::singleline(&mut proxy.username).hint_text("optional"));
+        ui.end_row();
+
+        ui.label("Proxy Password:");
+        ui.add(egui::TextEdit::singleline(&mut proxy.password).password(true))
+            .on_hover_text("Use ${ENV_VAR} to read it from the environment");
+        ui.end_row();
+    }
+    *proxy != before
+}
+
+fn serve(listener: TcpListener, route: Route, stop: Arc<AtomicBool>) {
+    for incoming in listener.incoming() {
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+        let Ok(client) = incoming else {
+            continue;
+        };
+        let route = route.clone();
+        std::thread::spawn(move || match open_through_proxy(&route) {
+            Ok(upstream) => pipe(client, upstream),
+            Err(e) => {
+                debug!("Proxied connection to {} failed: {}", route.target(), e);
+                let _ = client.shutdown(Shutdown::Both);
+            }
+        });
+    }
+}
+
+fn pipe(client: TcpStream, upstream: TcpStream) {
+    let (Ok(client_read), Ok(upstream_write)) = (client.try_clone(), upstream.try_clone()) else {
+        return;
+    };
+    let outbound = std::thread::spawn(move || copy_then_close(client_read, upstream_write));
+    copy_then_close(upstream, client);
+    let _ = outbound.join();
+}
+
+fn copy_then_close(mut from: TcpStream, mut to: TcpStream) {
+    let _ = std::io::copy(&mut from, &mut to);
+    let _ = to.shutdown(Shutdown::Write);
+}
+
+fn open_through_proxy(route: &Route) -> Result<TcpStream, ProxyError> {
+    let unreachable = |reason: String| ProxyError::ProxyUnreachable {
+        proxy: route.proxy(),
+        reason,
+    };
+    let addr = (route.proxy_host.as_str(), route.proxy_port)
+        .to_socket_addrs()
+        .map_err(|e| unreachable(e.to_string()))?
+        .next()
+        .ok_or_else(|| unreachable("host name did not resolve".to_string()))?;
+    let mut stream = TcpStream::connect_timeout(&addr, HANDSHAKE_TIMEOUT)
+        .map_err(|e| unreachable(e.to_string()))?;
+    let _ = stream.set_nodelay(true);
+    let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+    match route.kind {
+        ProxyKind::Socks5 => socks5_connect(&mut stream, route)?,
+        ProxyKind::Http => http_connect(&mut stream, route)?,
+        ProxyKind::None => {}
+    }
+    let _ = stream.set_read_timeout(None);
+    Ok(stream)
+}
+
+/// SOCKS5 CONNECT request for `host:port` (RFC 1928).
+fn socks5_connect_request(host: &str, port: u16) -> Result<Vec<u8>, String> {
+    let mut request = vec![5, 1, 0];
+    match host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(1);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(4);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            let len = u8::try_from(host.len()).map_err(|_| "host name is too long".to_string())?;
+            request.push(3);
+            request.push(len);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    Ok(request)
+}
+
+fn socks5_connect(stream: &mut TcpStream, route: &Route) -> Result<(), ProxyError> {
+    let io = |e: std::io::Error| route.protocol(e.to_string());
+    let with_auth = !route.username.is_empty();
+    let greeting: &[u8] = if with_auth { &[5, 2, 0, 2] } else { &[5, 1, 0] };
+    stream.write_all(greeting).map_err(io)?;
+    let mut choice = [0u8; 2];
+    stream.read_exact(&mut choice).map_err(io)?;
+    if choice[0] != 5 {
+        return Err(route.protocol("not a SOCKS5 proxy"));
+    }
+    match choice[1] {
+        0 => {}
+        2 if with_auth => {
+            // Username/password sub-negotiation (RFC 1929)
+            let (user, pass) = (route.username.as_bytes(), route.password.as_bytes());
+            let (Ok(user_len), Ok(pass_len)) = (u8::try_from(user.len()), u8::try_from(pass.len()))
+            else {
+                return Err(route.auth_failed("username and password must be at most 255 bytes"));
+            };
+            let mut auth = vec![1, user_len];
+            auth.extend_from_slice(user);
+            auth.push(pass_len);
+            auth.extend_from_slice(pass);
+            stream.write_all(&auth).map_err(io)?;
+            let mut status = [0u8; 2];
+            stream.read_exact(&mut status).map_err(io)?;
+            if status[1] != 0 {
+                return Err(route.auth_failed("username or password rejected"));
+            }
+        }
+        0xFF if with_auth => {
+            return Err(route.auth_failed("no offered authentication method was accepted"));
+        }
+        0xFF => return Err(route.auth_failed("the proxy requires a username and password")),
+        method => {
+            return Err(route.protocol(format!("unsupported authentication method {method}")));
+        }
+    }
+
+    let request = socks5_connect_request(&route.target_host, route.target_port)
+        .map_err(|e| route.protocol(e))?;
+    stream.write_all(&request).map_err(io)?;
+    let mut head = [0u8; 4];
+    stream.read_exact(&mut head).map_err(io)?;
+    let reason = match head[1] {
+        0 => None,
+        1 => Some("general SOCKS server failure"),
+        2 => Some("connection not allowed by the proxy's rules"),
+        3 => Some("network unreachable"),
+        4 => Some("host unreachable"),
+        5 => Some("connection refused"),
+        6 => Some("TTL expired"),
+        7 => return Err(route.protocol("CONNECT is not supported")),
+        8 => return Err(route.protocol("address type not supported")),
+        code => return Err(route.protocol(format!("reply code {code}"))),
+    };
+    if let Some(reason) = reason {
+        return Err(route.target_unreachable(reason));
+    }
+    // Skip the bound address and port
+    let addr_len = match head[3] {
+        1 => 4,
+        4 => 16,
+        3 => {
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len).map_err(io)?;
+            len[0] as usize
+        }
+        other => return Err(route.protocol(format!("address type {other}"))),
+    };
+    let mut bound = vec![0u8; addr_len + 2];
+    stream.read_exact(&mut bound).map_err(io)?;
+    Ok(())
+}
+
+fn base64(input: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(input.len().div_ceil(3) * 4);
+    for chunk in input.chunks(3) {
+        let bytes = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(TABLE[(n >> (18 - 6 * i)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Outcome of an HTTP CONNECT from the proxy's status line.
+fn http_connect_status(status_line: &str, route: &Route) -> Result<(), ProxyError> {
+    let code = status_line
+        .strip_prefix("HTTP/")
+        .and_then(|rest| rest.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| route.protocol("not an HTTP proxy"))?;
+    match code {
+        200..=299 => Ok(()),
+        407 if route.username.is_empty() => {
+            Err(route.auth_failed("the proxy requires a username and password"))
+        }
+        407 => Err(route.auth_failed("username or password rejected")),
+        403 | 502 | 503 | 504 => Err(route.target_unreachable(status_line.trim())),
+        _ => Err(route.protocol(status_line.trim())),
+    }
+}
+
+fn http_connect(stream: &mut TcpStream, route: &Route) -> Result<(), ProxyError> {
+    let io = |e: std::io::Error| route.protocol(e.to_string());
+    let target = route.target();
+    let mut request = format!("CONNECT {target} HTTP/1.1\r\nHost: {target}\r\n");
+    if !route.username.is_empty() {
+        let credentials = format!("{}:{}", route.username, route.password);
+        request.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            base64(credentials.as_bytes())
+        ));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).map_err(io)?;
+
+    // Read the response head byte by byte: what follows belongs to the database
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() > 16 * 1024 {
+            return Err(route.protocol("response header too long"));
+        }
+        stream.read_exact(&mut byte).map_err(io)?;
+        head.push(byte[0]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    http_connect_status(head.lines().next().unwrap_or_default(), route)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(kind: ProxyKind, proxy_port: u16, username: &str) -> Route {
+        Route {
+            kind,
+            proxy_host: "127.0.0.1".to_string(),
+            proxy_port,
+            username: username.to_string(),
+            password: "secret".to_string(),
+            target_host: "db.internal".to_string(),
+            target_port: 5432,
+        }
+    }
+
+    #[test]
+    fn handshakes_tell_auth_failures_from_unreachable_targets() {
+        assert_eq!(
+            socks5_connect_request("db.internal", 5432).unwrap(),
+            [&[5, 1, 0, 3, 11][..], b"db.internal", &[0x15, 0x38]].concat()
+        );
+        assert_eq!(
+            socks5_connect_request("10.0.0.2", 3306).unwrap(),
+            vec![5, 1, 0, 1, 10, 0, 0, 2, 0x0C, 0xEA]
+        );
+        assert_eq!(base64(b"user:pass"), "dXNlcjpwYXNz");
+        assert_eq!(base64(b"ab"), "YWI=");
+
+        let http = route(ProxyKind::Http, 3128, "user");
+        assert!(http_connect_status("HTTP/1.1 200 Connection established", &http).is_ok());
+        assert!(matches!(
+            http_connect_status("HTTP/1.1 407 Proxy Authentication Required", &http),
+            Err(ProxyError::AuthFailed { .. })
+        ));
+        assert!(matches!(
+            http_connect_status("HTTP/1.1 502 Bad Gateway", &http),
+            Err(ProxyError::TargetUnreachable { .. })
+        ));
+        assert!(matches!(
+            http_connect_status("SSH-2.0-OpenSSH", &http),
+            Err(ProxyError::Protocol { .. })
+        ));
+
+        // A SOCKS5 proxy that rejects the credentials
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut client, _) = listener.accept().unwrap();
+            let mut greeting = [0u8; 4];
+            client.read_exact(&mut greeting).unwrap();
+            client.write_all(&[5, 2]).unwrap();
+            let mut auth = [0u8; 1 + 1 + 4 + 1 + 6];
+            client.read_exact(&mut auth).unwrap();
+            client.write_all(&[1, 1]).unwrap();
+        });
+        let err = open_through_proxy(&route(ProxyKind::Socks5, port, "user")).unwrap_err();
+        assert!(matches!(err, ProxyError::AuthFailed { .. }), "{err}");
+        server.join().unwrap();
+
+        assert!(is_local_host("localhost") && is_local_host("[::1]"));
+        assert!(!is_local_host("db.internal"));
+    }
+
+    #[test]
+    fn forwarder_keys_never_carry_the_password() {
+        let first = route(ProxyKind::Socks5, 1080, "user");
+        let mut changed = first.clone();
+        changed.password = "rotated".to_string();
+        assert!(!first.fingerprint().contains("secret"));
+        assert_eq!(first.fingerprint(), changed.fingerprint());
+
+        let forwarder = Forwarder {
+            fingerprint: first.fingerprint(),
+            password: first.password.clone(),
+            local_port: 0,
+            stop: Arc::new(AtomicBool::new(false)),
+        };
+        assert!(forwarder.serves(&first));
+        assert!(!forwarder.serves(&changed));
+    }
+}
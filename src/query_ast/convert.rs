@@ -0,0 +1,266 @@
+//! Dialect conversion: parse a SELECT written for one database and re-emit it
+//! for another through the logical plan.
+//!
+//! The emitter already handles identifier quoting (only where the target needs
+//! it, unless asked to quote everything), LIMIT / TOP / FETCH and boolean
+//! literals per dialect. Embedded SQL (CTE bodies, derived tables,
+//! subqueries) is converted recursively; whatever the plan keeps as raw text
+//! is copied as written and reported in [`DialectConversion::notes`].
+
+use super::{
+    emitter::{
+        dialect::{IdentQuoting, SqlDialect, get_dialect, unquote_ident},
+        emit_sql_with_quoting,
+    },
+    errors::QueryAstError,
+    logical::{Expr, JoinKind, LogicalQueryPlan},
+    parser::parse_select_for_dialect,
+};
+use crate::models::enums::DatabaseType;
+
+#[derive(Debug, Clone)]
+pub struct DialectConversion {
+    pub sql: String,
+    /// Constructs that could not be translated faithfully, one line each.
+    pub notes: Vec<String>,
+}
+
+impl DialectConversion {
+    /// Converted SQL preceded by the notes as `--` comments.
+    pub fn annotated_sql(&self) -> String {
+        let mut out = String::new();
+        for note in &self.notes {
+            out.push_str("-- NOTE: ");
+            out.push_str(note);
+            out.push('\n');
+        }
+        out.push_str(&self.sql);
+        out
+    }
+}
+
+/// Convert `raw` from `source` syntax (generic SQL when unknown) to `target`.
+pub fn convert_dialect(
+    raw: &str,
+    source: Option<&DatabaseType>,
+    target: &DatabaseType,
+) -> Result<DialectConversion, QueryAstError> {
+    convert_dialect_with_quoting(raw, source, target, IdentQuoting::default())
+}
+
+/// [`convert_dialect`] with the identifier quoting chosen by the caller.
+pub fn convert_dialect_with_quoting(
+    raw: &str,
+    source: Option<&DatabaseType>,
+    target: &DatabaseType,
+    quoting: IdentQuoting,
+) -> Result<DialectConversion, QueryAstError> {
+    if !matches!(
+        target,
+        DatabaseType::MySQL | DatabaseType::PostgreSQL | DatabaseType::SQLite | DatabaseType::MsSQL
+    ) {
+        return Err(QueryAstError::Unsupported("target is not a SQL database"));
+    }
+    let raw = raw.trim().trim_end_matches(';').trim_end();
+    let mut plan = parse_select_for_dialect(raw, source)?;
+    let mut cx = Converter {
+        source,
+        target,
+        dialect: get_dialect(target),
+        quoting,
+        notes: Vec::new(),
+    };
+    cx.plan(&mut plan);
+    let sql = emit_sql_with_quoting(&plan, target, quoting)?;
+    let mut notes = Vec::new();
+    for n in cx.notes {
+        if !notes.contains(&n) {
+            notes.push(n);
+        }
+    }
+    Ok(DialectConversion { sql, notes })
+}
+
+struct Converter<'a> {
+    source: Option<&'a DatabaseType>,
+    target: &'a DatabaseType,
+    dialect: Box<dyn SqlDialect>,
+    quoting: IdentQuoting,
+    notes: Vec<String>,
+}
+
+impl Converter<'_> {
+    fn target_name(&self) -> &'static str {
+        match self.target {
+            DatabaseType::MySQL => "MySQL",
+            DatabaseType::PostgreSQL => "PostgreSQL",
+            DatabaseType::SQLite => "SQLite",
+            _ => "SQL Server",
+        }
+    }
+
+    /// Convert a nested statement, keeping it as written when it cannot be parsed.
+    fn embedded(&mut self, sql: &mut String, what: &str) {
+        match convert_dialect_with_quoting(sql, self.source, self.target, self.quoting) {
+            Ok(inner) => {
+                self.notes.extend(inner.notes);
+                *sql = inner.sql;
+            }
+            Err(e) => self.notes.push(format!("{} kept as written ({})", what, e)),
+        }
+    }
+
+    /// Re-quote a name the emitter writes verbatim (CTE names and columns).
+    fn requote(&self, name: &mut String) {
+        if unquote_ident(name) != *name {
+            *name = self.dialect.emit_ident(name, self.quoting);
+        }
+    }
+
+    fn plan(&mut self, plan: &mut LogicalQueryPlan) {
+        use LogicalQueryPlan as L;
+        match plan {
+            L::Projection { exprs, input } => {
+                exprs.iter_mut().for_each(|e| self.expr(e));
+                self.plan(input);
+            }
+            L::Filter { predicate, input } | L::Having { predicate, input } => {
+                self.expr(predicate);
+                self.plan(input);
+            }
+            L::Sort { items, input } => {
+                items.iter_mut().for_each(|s| self.expr(&mut s.expr));
+                self.plan(input);
+            }
+            L::Group { group_exprs, input } => {
+                group_exprs.iter_mut().for_each(|e| self.expr(e));
+                self.plan(input);
+            }
+            L::Limit { offset, input, .. } => {
+                if *offset > 0
+                    && *self.target == DatabaseType::MsSQL
+                    && !matches!(**input, L::Sort { .. })
+                {
+                    self.notes.push(
+                        "SQL Server only allows OFFSET ... FETCH after an ORDER BY".to_string(),
+                    );
+                }
+                self.plan(input);
+            }
+            L::Distinct { on, input } => {
+                on.iter_mut().for_each(|e| self.expr(e));
+                self.plan(input);
+            }
+            L::Join {
+                left,
+                right,
+                on,
+                kind,
+            } => {
+                if *kind == JoinKind::Full && !self.dialect.supports_full_join() {
+                    self.notes
+                        .push(format!("{} has no FULL JOIN", self.target_name()));
+                }
+                if let Some(on) = on {
+                    self.expr(on);
+                }
+                self.plan(left);
+                self.plan(right);
+            }
+            L::With { ctes, input, .. } => {
+                for cte in ctes.iter_mut() {
+                    let what = format!("CTE {}", cte.name);
+                    self.embedded(&mut cte.sql, &what);
+                    self.requote(&mut cte.name);
+                    cte.columns.iter_mut().for_each(|c| self.requote(c));
+                }
+                self.plan(input);
+            }
+            L::SetOp { left, right, .. } => {
+                self.plan(left);
+                self.plan(right);
+            }
+            L::SubqueryScan { sql, alias, .. } => {
+                let what = format!("derived table {}", alias);
+                self.embedded(sql, &what);
+            }
+            L::TableScan { .. } => {}
+        }
+    }
+
+    fn expr(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Raw(r) => self.notes.push(format!("kept as written: {}", r)),
+            Expr::Subquery { sql, .. } => self.embedded(sql, "subquery"),
+            Expr::BinaryOp { left, right, .. } => {
+                self.expr(left);
+                self.expr(right);
+            }
+            Expr::FuncCall { args, .. } => args.iter_mut().for_each(|a| self.expr(a)),
+            Expr::Alias { expr, .. } | Expr::Not(expr) | Expr::IsNull { expr, .. } => {
+                self.expr(expr)
+            }
+            Expr::Like { expr, pattern, .. } => {
+                self.expr(expr);
+                self.expr(pattern);
+            }
+            Expr::InList { expr, list, .. } => {
+                self.expr(expr);
+                list.iter_mut().for_each(|e| self.expr(e));
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.expr(expr);
+                self.expr(low);
+                self.expr(high);
+            }
+            Expr::Case {
+                operand,
+                when_then,
+                else_expr,
+            } => {
+                if let Some(o) = operand {
+                    self.expr(o);
+                }
+                for (w, t) in when_then.iter_mut() {
+                    self.expr(w);
+                    self.expr(t);
+                }
+                if let Some(e) = else_expr {
+                    self.expr(e);
+                }
+            }
+            Expr::WindowFunc {
+                name,
+                args,
+                partition_by,
+                order_by,
+                frame,
+            } => {
+                if !self.dialect.supports_window_functions() {
+                    self.notes.push(format!(
+                        "{} has no window functions ({} OVER ...)",
+                        self.target_name(),
+                        name
+                    ));
+                }
+                if frame.take().is_some() {
+                    self.notes.push(format!(
+                        "window frame of {} dropped; re-add it by hand",
+                        name
+                    ));
+                }
+                args.iter_mut().for_each(|a| self.expr(a));
+                partition_by.iter_mut().for_each(|e| self.expr(e));
+                order_by.iter_mut().for_each(|(e, _)| self.expr(e));
+            }
+            Expr::Column(_)
+            | Expr::StringLiteral(_)
+            | Expr::Number(_)
+            | Expr::Star
+            | Expr::Null
+            | Expr::Boolean(_) => {}
+        }
+    }
+}
@@ -6,13 +6,237 @@
 use super::super::errors::QueryAstError;
 use crate::models::enums::DatabaseType;
 
+/// Bare name of an identifier that may already carry any dialect's quoting
+/// (`"a""b"`, `` `a` ``, `[a]`), so re-quoting for another dialect is safe.
+pub fn unquote_ident(ident: &str) -> String {
+    let trimmed = ident.trim();
+    for (open, close) in [('"', '"'), ('`', '`'), ('[', ']')] {
+        if trimmed.len() >= 2 && trimmed.starts_with(open) && trimmed.ends_with(close) {
+            let inner = &trimmed[1..trimmed.len() - 1];
+            let doubled = format!("{close}{close}");
+            return inner.replace(&doubled, &close.to_string());
+        }
+    }
+    trimmed.to_string()
+}
+
+/// Parts of `name` separated by `sep` outside any dialect's quotes, so
+/// `[dbo].[a.b]` splits on the first dot only.
+pub fn split_outside_quotes(name: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut closing: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in name.char_indices() {
+        match closing {
+            Some(close) if c == close => closing = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '`' => closing = Some(c),
+                '[' => closing = Some(']'),
+                _ if c == sep => {
+                    parts.push(&name[start..i]);
+                    start = i + c.len_utf8();
+                }
+                _ => {}
+            },
+        }
+    }
+    parts.push(&name[start..]);
+    parts
+}
+
+/// How the emitter writes identifiers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdentQuoting {
+    /// Quote only names that need it (see [`SqlDialect::needs_quoting`]).
+    #[default]
+    WhenNeeded,
+    /// Quote every identifier.
+    Always,
+}
+
+/// Words reserved by standard SQL, for dialects without a list of their own.
+#[rustfmt::skip]
+const SQL_RESERVED: &[&str] = &[
+    "ALL", "ALTER", "AND", "ANY", "AS", "ASC", "BETWEEN", "BY", "CASE", "CAST", "CHECK",
+    "COLUMN", "CONSTRAINT", "CREATE", "CROSS", "CURRENT_DATE", "CURRENT_TIME",
+    "CURRENT_TIMESTAMP", "CURRENT_USER", "DEFAULT", "DELETE", "DESC", "DISTINCT", "DROP",
+    "ELSE", "END", "EXCEPT", "EXISTS", "FALSE", "FETCH", "FOR", "FOREIGN", "FROM", "FULL",
+    "GRANT", "GROUP", "HAVING", "IN", "INNER", "INSERT", "INTERSECT", "INTO", "IS", "JOIN",
+    "LEFT", "LIKE", "LIMIT", "NATURAL", "NOT", "NULL", "OFFSET", "ON", "OR", "ORDER",
+    "OUTER", "PRIMARY", "REFERENCES", "RIGHT", "SELECT", "SET", "SOME", "TABLE", "THEN",
+    "TO", "TRUE", "UNION", "UNIQUE", "UPDATE", "USER", "USING", "VALUES", "WHEN", "WHERE",
+    "WITH",
+];
+
+/// MySQL 8.0 reserved words (the ones marked (R) in the manual).
+#[rustfmt::skip]
+const MYSQL_RESERVED: &[&str] = &[
+    "ACCESSIBLE", "ADD", "ALL", "ALTER", "ANALYZE", "AND", "AS", "ASC", "ASENSITIVE",
+    "BEFORE", "BETWEEN", "BIGINT", "BINARY", "BLOB", "BOTH", "BY", "CALL", "CASCADE", "CASE",
+    "CHANGE", "CHAR", "CHARACTER", "CHECK", "COLLATE", "COLUMN", "CONDITION", "CONSTRAINT",
+    "CONTINUE", "CONVERT", "CREATE", "CROSS", "CUBE", "CUME_DIST", "CURRENT_DATE",
+    "CURRENT_TIME", "CURRENT_TIMESTAMP", "CURRENT_USER", "CURSOR", "DATABASE", "DATABASES",
+    "DAY_HOUR", "DAY_MICROSECOND", "DAY_MINUTE", "DAY_SECOND", "DEC", "DECIMAL", "DECLARE",
+    "DEFAULT", "DELAYED", "DELETE", "DENSE_RANK", "DESC", "DESCRIBE", "DETERMINISTIC",
+    "DISTINCT", "DISTINCTROW", "DIV", "DOUBLE", "DROP", "DUAL", "EACH", "ELSE", "ELSEIF",
+    "EMPTY", "ENCLOSED", "ESCAPED", "EXCEPT", "EXISTS", "EXIT", "EXPLAIN", "FALSE", "FETCH",
+    "FIRST_VALUE", "FLOAT", "FLOAT4", "FLOAT8", "FOR", "FORCE", "FOREIGN", "FROM",
+    "FULLTEXT", "FUNCTION", "GENERATED", "GET", "GRANT", "GROUP", "GROUPING", "GROUPS",
+    "HAVING", "HIGH_PRIORITY", "HOUR_MICROSECOND", "HOUR_MINUTE", "HOUR_SECOND", "IF",
+    "IGNORE", "IN", "INDEX", "INFILE", "INNER", "INOUT", "INSENSITIVE", "INSERT", "INT",
+    "INT1", "INT2", "INT3", "INT4", "INT8", "INTEGER", "INTERSECT", "INTERVAL", "INTO",
+    "IO_AFTER_GTIDS", "IO_BEFORE_GTIDS", "IS", "ITERATE", "JOIN", "JSON_TABLE", "KEY",
+    "KEYS", "KILL", "LAG", "LAST_VALUE", "LATERAL", "LEAD", "LEADING", "LEAVE", "LEFT",
+    "LIKE", "LIMIT", "LINEAR", "LINES", "LOAD", "LOCALTIME", "LOCALTIMESTAMP", "LOCK",
+    "LONG", "LONGBLOB", "LONGTEXT", "LOOP", "LOW_PRIORITY", "MASTER_BIND",
+    "MASTER_SSL_VERIFY_SERVER_CERT", "MATCH", "MAXVALUE", "MEDIUMBLOB", "MEDIUMINT",
+    "MEDIUMTEXT", "MIDDLEINT", "MINUTE_MICROSECOND", "MINUTE_SECOND", "MOD", "MODIFIES",
+    "NATURAL", "NOT", "NO_WRITE_TO_BINLOG", "NTH_VALUE", "NTILE", "NULL", "NUMERIC", "OF",
+    "ON", "OPTIMIZE", "OPTIMIZER_COSTS", "OPTION", "OPTIONALLY", "OR", "ORDER", "OUT",
+    "OUTER", "OUTFILE", "OVER", "PARTITION", "PERCENT_RANK", "PRECISION", "PRIMARY",
+    "PROCEDURE", "PURGE", "RANGE", "RANK", "READ", "READS", "READ_WRITE", "REAL",
+    "RECURSIVE", "REFERENCES", "REGEXP", "RELEASE", "RENAME", "REPEAT", "REPLACE",
+    "REQUIRE", "RESIGNAL", "RESTRICT", "RETURN", "REVOKE", "RIGHT", "RLIKE", "ROW", "ROWS",
+    "ROW_NUMBER", "SCHEMA", "SCHEMAS", "SECOND_MICROSECOND", "SELECT", "SENSITIVE",
+    "SEPARATOR", "SET", "SHOW", "SIGNAL", "SMALLINT", "SPATIAL", "SPECIFIC", "SQL",
+    "SQLEXCEPTION", "SQLSTATE", "SQLWARNING", "SQL_BIG_RESULT", "SQL_CALC_FOUND_ROWS",
+    "SQL_SMALL_RESULT", "SSL", "STARTING", "STORED", "STRAIGHT_JOIN", "SYSTEM", "TABLE",
+    "TERMINATED", "THEN", "TINYBLOB", "TINYINT", "TINYTEXT", "TO", "TRAILING", "TRIGGER",
+    "TRUE", "UNDO", "UNION", "UNIQUE", "UNLOCK", "UNSIGNED", "UPDATE", "USAGE", "USE",
+    "USING", "UTC_DATE", "UTC_TIME", "UTC_TIMESTAMP", "VALUES", "VARBINARY", "VARCHAR",
+    "VARCHARACTER", "VARYING", "VIRTUAL", "WHEN", "WHERE", "WHILE", "WINDOW", "WITH",
+    "WRITE", "XOR", "YEAR_MONTH", "ZEROFILL",
+];
+
+/// PostgreSQL reserved key words, including those that may only be used as
+/// function or type names.
+#[rustfmt::skip]
+const POSTGRES_RESERVED: &[&str] = &[
+    "ALL", "ANALYSE", "ANALYZE", "AND", "ANY", "ARRAY", "AS", "ASC", "ASYMMETRIC",
+    "AUTHORIZATION", "BINARY", "BOTH", "CASE", "CAST", "CHECK", "COLLATE", "COLLATION",
+    "COLUMN", "CONCURRENTLY", "CONSTRAINT", "CREATE", "CROSS", "CURRENT_CATALOG",
+    "CURRENT_DATE", "CURRENT_ROLE", "CURRENT_SCHEMA", "CURRENT_TIME", "CURRENT_TIMESTAMP",
+    "CURRENT_USER", "DEFAULT", "DEFERRABLE", "DESC", "DISTINCT", "DO", "ELSE", "END",
+    "EXCEPT", "FALSE", "FETCH", "FOR", "FOREIGN", "FREEZE", "FROM", "FULL", "GRANT",
+    "GROUP", "HAVING", "ILIKE", "IN", "INITIALLY", "INNER", "INTERSECT", "INTO", "IS",
+    "ISNULL", "JOIN", "LATERAL", "LEADING", "LEFT", "LIKE", "LIMIT", "LOCALTIME",
+    "LOCALTIMESTAMP", "NATURAL", "NOT", "NOTNULL", "NULL", "OFFSET", "ON", "ONLY", "OR",
+    "ORDER", "OUTER", "OVERLAPS", "PLACING", "PRIMARY", "REFERENCES", "RETURNING", "RIGHT",
+    "SELECT", "SESSION_USER", "SIMILAR", "SOME", "SYMMETRIC", "SYSTEM_USER", "TABLE",
+    "TABLESAMPLE", "THEN", "TO", "TRAILING", "TRUE", "UNION", "UNIQUE", "USER", "USING",
+    "VARIADIC", "VERBOSE", "WHEN", "WHERE", "WINDOW", "WITH",
+];
+
+/// SQLite keywords. SQLite accepts many of them as bare names, but which ones
+/// depends on the context, so all of them are quoted.
+#[rustfmt::skip]
+const SQLITE_RESERVED: &[&str] = &[
+    "ABORT", "ACTION", "ADD", "AFTER", "ALL", "ALTER", "ALWAYS", "ANALYZE", "AND", "AS",
+    "ASC", "ATTACH", "AUTOINCREMENT", "BEFORE", "BEGIN", "BETWEEN", "BY", "CASCADE", "CASE",
+    "CAST", "CHECK", "COLLATE", "COLUMN", "COMMIT", "CONFLICT", "CONSTRAINT", "CREATE",
+    "CROSS", "CURRENT", "CURRENT_DATE", "CURRENT_TIME", "CURRENT_TIMESTAMP", "DATABASE",
+    "DEFAULT", "DEFERRABLE", "DEFERRED", "DELETE", "DESC", "DETACH", "DISTINCT", "DO",
+    "DROP", "EACH", "ELSE", "END", "ESCAPE", "EXCEPT", "EXCLUDE", "EXCLUSIVE", "EXISTS",
+    "EXPLAIN", "FAIL", "FILTER", "FIRST", "FOLLOWING", "FOR", "FOREIGN", "FROM", "FULL",
+    "GENERATED", "GLOB", "GROUP", "GROUPS", "HAVING", "IF", "IGNORE", "IMMEDIATE", "IN",
+    "INDEX", "INDEXED", "INITIALLY", "INNER", "INSERT", "INSTEAD", "INTERSECT", "INTO", "IS",
+    "ISNULL", "JOIN", "KEY", "LAST", "LEFT", "LIKE", "LIMIT", "MATCH", "MATERIALIZED",
+    "NATURAL", "NO", "NOT", "NOTHING", "NOTNULL", "NULL", "NULLS", "OF", "OFFSET", "ON",
+    "OR", "ORDER", "OTHERS", "OUTER", "OVER", "PARTITION", "PLAN", "PRAGMA", "PRECEDING",
+    "PRIMARY", "QUERY", "RAISE", "RANGE", "RECURSIVE", "REFERENCES", "REGEXP", "REINDEX",
+    "RELEASE", "RENAME", "REPLACE", "RESTRICT", "RETURNING", "RIGHT", "ROLLBACK", "ROW",
+    "ROWS", "SAVEPOINT", "SELECT", "SET", "TABLE", "TEMP", "TEMPORARY", "THEN", "TIES", "TO",
+    "TRANSACTION", "TRIGGER", "UNBOUNDED", "UNION", "UNIQUE", "UPDATE", "USING", "VACUUM",
+    "VALUES", "VIEW", "VIRTUAL", "WHEN", "WHERE", "WINDOW", "WITH", "WITHOUT",
+];
+
+/// Transact-SQL reserved keywords.
+#[rustfmt::skip]
+const MSSQL_RESERVED: &[&str] = &[
+    "ADD", "ALL", "ALTER", "AND", "ANY", "AS", "ASC", "AUTHORIZATION", "BACKUP", "BEGIN",
+    "BETWEEN", "BREAK", "BROWSE", "BULK", "BY", "CASCADE", "CASE", "CHECK", "CHECKPOINT",
+    "CLOSE", "CLUSTERED", "COALESCE", "COLLATE", "COLUMN", "COMMIT", "COMPUTE", "CONSTRAINT",
+    "CONTAINS", "CONTAINSTABLE", "CONTINUE", "CONVERT", "CREATE", "CROSS", "CURRENT",
+    "CURRENT_DATE", "CURRENT_TIME", "CURRENT_TIMESTAMP", "CURRENT_USER", "CURSOR",
+    "DATABASE", "DBCC", "DEALLOCATE", "DECLARE", "DEFAULT", "DELETE", "DENY", "DESC", "DISK",
+    "DISTINCT", "DISTRIBUTED", "DOUBLE", "DROP", "DUMP", "ELSE", "END", "ERRLVL", "ESCAPE",
+    "EXCEPT", "EXEC", "EXECUTE", "EXISTS", "EXIT", "EXTERNAL", "FETCH", "FILE", "FILLFACTOR",
+    "FOR", "FOREIGN", "FREETEXT", "FREETEXTTABLE", "FROM", "FULL", "FUNCTION", "GOTO",
+    "GRANT", "GROUP", "HAVING", "HOLDLOCK", "IDENTITY", "IDENTITYCOL", "IDENTITY_INSERT",
+    "IF", "IN", "INDEX", "INNER", "INSERT", "INTERSECT", "INTO", "IS", "JOIN", "KEY", "KILL",
+    "LEFT", "LIKE", "LINENO", "LOAD", "MERGE", "NATIONAL", "NOCHECK", "NONCLUSTERED", "NOT",
+    "NULL", "NULLIF", "OF", "OFF", "OFFSETS", "ON", "OPEN", "OPENDATASOURCE", "OPENQUERY",
+    "OPENROWSET", "OPENXML", "OPTION", "OR", "ORDER", "OUTER", "OVER", "PERCENT", "PIVOT",
+    "PLAN", "PRECISION", "PRIMARY", "PRINT", "PROC", "PROCEDURE", "PUBLIC", "RAISERROR",
+    "READ", "READTEXT", "RECONFIGURE", "REFERENCES", "REPLICATION", "RESTORE", "RESTRICT",
+    "RETURN", "REVERT", "REVOKE", "RIGHT", "ROLLBACK", "ROWCOUNT", "ROWGUIDCOL", "RULE",
+    "SAVE", "SCHEMA", "SECURITYAUDIT", "SELECT", "SEMANTICKEYPHRASETABLE",
+    "SEMANTICSIMILARITYDETAILSTABLE", "SEMANTICSIMILARITYTABLE", "SESSION_USER", "SET",
+    "SETUSER", "SHUTDOWN", "SOME", "STATISTICS", "SYSTEM_USER", "TABLE", "TABLESAMPLE",
+    "TEXTSIZE", "THEN", "TO", "TOP", "TRAN", "TRANSACTION", "TRIGGER", "TRUNCATE",
+    "TRY_CONVERT", "TSEQUAL", "UNION", "UNIQUE", "UNPIVOT", "UPDATE", "UPDATETEXT", "USE",
+    "USER", "VALUES", "VARYING", "VIEW", "WAITFOR", "WHEN", "WHERE", "WHILE", "WITH",
+    "WRITETEXT",
+];
+
 /// Trait for database-specific SQL dialect
 pub trait SqlDialect: Send + Sync {
     /// Get the database type
     fn db_type(&self) -> DatabaseType;
 
-    /// Quote an identifier (table/column name)
-    fn quote_ident(&self, ident: &str) -> String;
+    /// Opening and closing identifier quote; MsSQL's brackets differ.
+    fn quote_chars(&self) -> (char, char) {
+        ('"', '"')
+    }
+
+    /// Quote one identifier part (table/column name). Existing quoting of any
+    /// dialect is stripped first, and the closing quote is doubled inside.
+    fn quote_ident(&self, ident: &str) -> String {
+        let (open, close) = self.quote_chars();
+        let escaped = unquote_ident(ident).replace(close, &format!("{close}{close}"));
+        format!("{open}{escaped}{close}")
+    }
+
+    /// Reserved words of this dialect, upper case and sorted.
+    fn reserved_words(&self) -> &'static [&'static str] {
+        SQL_RESERVED
+    }
+
+    /// Whether the server folds unquoted names to lower case, so a name with
+    /// upper-case letters only survives quoted (PostgreSQL).
+    fn folds_to_lower_case(&self) -> bool {
+        false
+    }
+
+    /// Whether `ident` has to be quoted to mean the same name: it is a
+    /// reserved word, is not a plain `[A-Za-z_][A-Za-z0-9_]*` name, or has
+    /// upper-case letters the server would fold away.
+    fn needs_quoting(&self, ident: &str) -> bool {
+        let bare = unquote_ident(ident);
+        let mut chars = bare.chars();
+        let plain = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !plain {
+            return true;
+        }
+        if self.folds_to_lower_case() && bare.chars().any(|c| c.is_ascii_uppercase()) {
+            return true;
+        }
+        let upper = bare.to_ascii_uppercase();
+        self.reserved_words().binary_search(&upper.as_str()).is_ok()
+    }
+
+    /// Write an identifier, quoted when `quoting` or the name asks for it.
+    fn emit_ident(&self, ident: &str, quoting: IdentQuoting) -> String {
+        if quoting == IdentQuoting::Always || self.needs_quoting(ident) {
+            self.quote_ident(ident)
+        } else {
+            unquote_ident(ident)
+        }
+    }
 
     /// Quote a string literal
     fn quote_string(&self, s: &str) -> String {
@@ -67,6 +291,43 @@ pub trait SqlDialect: Send + Sync {
         !matches!(self.db_type(), DatabaseType::MySQL | DatabaseType::SQLite)
     }
 
+    /// `SELECT DISTINCT ON (...)` is a PostgreSQL extension.
+    fn supports_distinct_on(&self) -> bool {
+        matches!(self.db_type(), DatabaseType::PostgreSQL)
+    }
+
+    /// Keyword opening a WITH clause. SQL Server has no RECURSIVE keyword: a
+    /// CTE that references itself is recursive implicitly.
+    fn emit_with(&self, recursive: bool) -> &'static str {
+        if recursive && !matches!(self.db_type(), DatabaseType::MsSQL) {
+            "WITH RECURSIVE"
+        } else {
+            "WITH"
+        }
+    }
+
+    /// Keyword for a set operation.
+    ///
+    /// Dialect notes: MySQL only understands INTERSECT / EXCEPT from 8.0.31 on,
+    /// and SQLite and SQL Server have no INTERSECT ALL / EXCEPT ALL (the parser
+    /// rejects those, so only the distinct forms reach the emitter).
+    fn emit_set_op(&self, op: &super::super::logical::SetOpKind) -> &'static str {
+        use super::super::logical::SetOpKind;
+        match op {
+            SetOpKind::Union => "UNION",
+            SetOpKind::UnionAll => "UNION ALL",
+            SetOpKind::Intersect => "INTERSECT",
+            SetOpKind::Except => "EXCEPT",
+        }
+    }
+
+    /// Whether set operation operands may be parenthesized, i.e.
+    /// `(SELECT ..) UNION (SELECT ..)`. SQLite rejects that form: its compound
+    /// selects are flat and evaluated strictly left to right.
+    fn parenthesize_set_operands(&self) -> bool {
+        !matches!(self.db_type(), DatabaseType::SQLite)
+    }
+
     /// Emit a cast expression (database-specific syntax)
     fn emit_cast(&self, expr: &str, target_type: &str) -> String {
         format!("CAST({} AS {})", expr, target_type)
@@ -117,13 +378,12 @@ impl SqlDialect for MySqlDialect {
         DatabaseType::MySQL
     }
 
-    fn quote_ident(&self, ident: &str) -> String {
-        let trimmed = ident.trim();
-        // If already backtick-quoted, return as-is to avoid double quoting like ```name```.
-        if trimmed.len() >= 2 && trimmed.starts_with('`') && trimmed.ends_with('`') {
-            return trimmed.to_string();
-        }
-        format!("`{}`", trimmed.replace('`', "``"))
+    fn quote_chars(&self) -> (char, char) {
+        ('`', '`')
+    }
+
+    fn reserved_words(&self) -> &'static [&'static str] {
+        MYSQL_RESERVED
     }
 
     fn quote_string(&self, s: &str) -> String {
@@ -142,12 +402,12 @@ impl SqlDialect for PostgresDialect {
         DatabaseType::PostgreSQL
     }
 
-    fn quote_ident(&self, ident: &str) -> String {
-        let trimmed = ident.trim();
-        if trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"') {
-            return trimmed.to_string();
-        }
-        format!("\"{}\"", trimmed.replace('"', "\"\""))
+    fn reserved_words(&self) -> &'static [&'static str] {
+        POSTGRES_RESERVED
+    }
+
+    fn folds_to_lower_case(&self) -> bool {
+        true
     }
 }
 
@@ -159,12 +419,8 @@ impl SqlDialect for SqliteDialect {
         DatabaseType::SQLite
     }
 
-    fn quote_ident(&self, ident: &str) -> String {
-        let trimmed = ident.trim();
-        if trimmed.len() >= 2 && trimmed.starts_with('`') && trimmed.ends_with('`') {
-            return trimmed.to_string();
-        }
-        format!("`{}`", trimmed.replace('`', "``"))
+    fn reserved_words(&self) -> &'static [&'static str] {
+        SQLITE_RESERVED
     }
 
     fn supports_window_functions(&self) -> bool {
@@ -180,12 +436,12 @@ impl SqlDialect for MssqlDialect {
         DatabaseType::MsSQL
     }
 
-    fn quote_ident(&self, ident: &str) -> String {
-        let trimmed = ident.trim();
-        if trimmed.len() >= 2 && trimmed.starts_with('[') && trimmed.ends_with(']') {
-            return trimmed.to_string();
-        }
-        format!("[{}]", trimmed.replace(']', "]]"))
+    fn quote_chars(&self) -> (char, char) {
+        ('[', ']')
+    }
+
+    fn reserved_words(&self) -> &'static [&'static str] {
+        MSSQL_RESERVED
     }
 
     fn emit_limit(&self, limit: u64, offset: u64) -> String {
@@ -211,14 +467,6 @@ impl SqlDialect for MongoDialect {
         DatabaseType::MongoDB
     }
 
-    fn quote_ident(&self, ident: &str) -> String {
-        let trimmed = ident.trim();
-        if trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"') {
-            return trimmed.to_string();
-        }
-        format!("\"{}\"", trimmed.replace('"', "\"\""))
-    }
-
     fn supports_window_functions(&self) -> bool {
         false
     }
@@ -236,14 +484,6 @@ impl SqlDialect for RedisDialect {
         DatabaseType::Redis
     }
 
-    fn quote_ident(&self, ident: &str) -> String {
-        let trimmed = ident.trim();
-        if trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"') {
-            return trimmed.to_string();
-        }
-        format!("\"{}\"", trimmed.replace('"', "\"\""))
-    }
-
     fn supports_window_functions(&self) -> bool {
         false
     }
@@ -5,38 +5,144 @@ use super::{
 use crate::models::enums::DatabaseType;
 
 pub mod dialect;
-use dialect::{SqlDialect, get_dialect};
+use dialect::{IdentQuoting, SqlDialect, get_dialect, split_outside_quotes};
 
+/// Emit `plan` for `db_type`, quoting only the identifiers that need it.
 pub fn emit_sql(plan: &LogicalQueryPlan, db_type: &DatabaseType) -> Result<String, QueryAstError> {
+    emit_sql_with_quoting(plan, db_type, IdentQuoting::default())
+}
+
+/// [`emit_sql`] with the identifier quoting chosen by the caller.
+pub fn emit_sql_with_quoting(
+    plan: &LogicalQueryPlan,
+    db_type: &DatabaseType,
+    quoting: IdentQuoting,
+) -> Result<String, QueryAstError> {
     // If top-level is With and still has CTEs, emit a proper WITH clause wrapping emitted SELECT.
-    if let LogicalQueryPlan::With { ctes, input } = plan
+    if let LogicalQueryPlan::With {
+        ctes,
+        recursive,
+        input,
+    } = plan
         && !ctes.is_empty()
     {
         let mut parts = Vec::new();
-        for (name, sql) in ctes {
+        for cte in ctes {
             // Ensure subquery body does not end with semicolon
-            let body = sql.trim().trim_end_matches(';');
-            parts.push(format!("{} AS ({})", name, body));
+            let body = cte.sql.trim().trim_end_matches(';');
+            if cte.columns.is_empty() {
+                parts.push(format!("{} AS ({})", cte.name, body));
+            } else {
+                parts.push(format!(
+                    "{} ({}) AS ({})",
+                    cte.name,
+                    cte.columns.join(", "),
+                    body
+                ));
+            }
         }
-        let rendered_inner = emit_sql(input, db_type)?; // recursive (will flatten below)
-        return Ok(format!("WITH {} {}", parts.join(", "), rendered_inner));
+        let rendered_inner = emit_sql_with_quoting(input, db_type, quoting)?; // recursive (will flatten below)
+        let keyword = get_dialect(db_type).emit_with(*recursive);
+        return Ok(format!(
+            "{} {} {}",
+            keyword,
+            parts.join(", "),
+            rendered_inner
+        ));
     }
+    let dialect = get_dialect(db_type);
     // If top-level is a SetOp, emit recursively (each side may itself contain WITH already handled above)
     if let LogicalQueryPlan::SetOp { left, right, op } = plan {
-        let left_sql = emit_sql(left, db_type)?;
-        let right_sql = emit_sql(right, db_type)?;
-        let op_str = match op {
-            super::logical::SetOpKind::Union => "UNION",
-            super::logical::SetOpKind::UnionAll => "UNION ALL",
-        };
-        return Ok(format!("({}) {} ({})", left_sql, op_str, right_sql));
+        let left_sql = emit_set_operand(left, false, dialect.as_ref(), db_type, quoting)?;
+        let right_sql = emit_set_operand(right, true, dialect.as_ref(), db_type, quoting)?;
+        return Ok(format!(
+            "{} {} {}",
+            left_sql,
+            dialect.emit_set_op(op),
+            right_sql
+        ));
+    }
+    // ORDER BY / LIMIT over a compound: select from it as a derived table, which
+    // also gives engines without LIMIT (SQL Server) a place for TOP.
+    if contains_set_op_below(plan) {
+        let lifted = lift_set_op(plan, db_type, quoting)?;
+        return emit_sql_with_quoting(&lifted, db_type, quoting);
     }
     let flat = flatten_plan(plan);
-    let dialect = get_dialect(db_type);
-    let mut emitter = FlatEmitter { dialect };
+    let mut emitter = FlatEmitter { dialect, quoting };
     emitter.emit(&flat)
 }
 
+fn emit_set_operand(
+    operand: &LogicalQueryPlan,
+    is_right: bool,
+    dialect: &dyn SqlDialect,
+    db_type: &DatabaseType,
+    quoting: IdentQuoting,
+) -> Result<String, QueryAstError> {
+    let sql = emit_sql_with_quoting(operand, db_type, quoting)?;
+    if dialect.parenthesize_set_operands() {
+        return Ok(format!("({})", sql));
+    }
+    // Flat compound (SQLite): a left-nested chain reads the same without
+    // parentheses; anything else would change meaning or fail to parse.
+    let flat_ok = match operand {
+        LogicalQueryPlan::SetOp { .. } => !is_right,
+        other => !contains_sort_or_limit(other),
+    };
+    Ok(if flat_ok {
+        sql
+    } else {
+        format!("SELECT * FROM ({})", sql)
+    })
+}
+
+fn contains_sort_or_limit(plan: &LogicalQueryPlan) -> bool {
+    match plan {
+        LogicalQueryPlan::Sort { .. } | LogicalQueryPlan::Limit { .. } => true,
+        LogicalQueryPlan::Projection { input, .. }
+        | LogicalQueryPlan::Distinct { input, .. }
+        | LogicalQueryPlan::With { input, .. } => contains_sort_or_limit(input),
+        _ => false,
+    }
+}
+
+/// A SetOp under the Sort/Limit/Projection/Distinct chain at the top of `plan`.
+fn contains_set_op_below(plan: &LogicalQueryPlan) -> bool {
+    match plan {
+        LogicalQueryPlan::SetOp { .. } => true,
+        LogicalQueryPlan::Sort { input, .. }
+        | LogicalQueryPlan::Limit { input, .. }
+        | LogicalQueryPlan::Projection { input, .. }
+        | LogicalQueryPlan::Distinct { input, .. } => contains_set_op_below(input),
+        _ => false,
+    }
+}
+
+/// Replace the SetOp found by [`contains_set_op_below`] with a derived table.
+fn lift_set_op(
+    plan: &LogicalQueryPlan,
+    db_type: &DatabaseType,
+    quoting: IdentQuoting,
+) -> Result<LogicalQueryPlan, QueryAstError> {
+    let mut lifted = plan.clone();
+    let mut node = &mut lifted;
+    loop {
+        match node {
+            LogicalQueryPlan::SetOp { .. } => {
+                let sql = emit_sql_with_quoting(node, db_type, quoting)?;
+                *node = LogicalQueryPlan::subquery_scan(sql, "set_op");
+                return Ok(lifted);
+            }
+            LogicalQueryPlan::Sort { input, .. }
+            | LogicalQueryPlan::Limit { input, .. }
+            | LogicalQueryPlan::Projection { input, .. }
+            | LogicalQueryPlan::Distinct { input, .. } => node = input,
+            _ => return Ok(lifted),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 struct FlatSelect {
     table: Option<String>,
@@ -47,8 +153,9 @@ struct FlatSelect {
     limit: Option<u64>,
     offset: Option<u64>,
     distinct: bool,
+    distinct_on: Vec<Expr>,
     group_exprs: Vec<Expr>,
-    join: Option<(super::logical::JoinKind, String, Option<Expr>)>, // (kind, right_table, on expr)
+    joins: Vec<(super::logical::JoinKind, String, Option<Expr>)>, // (kind, right_table, on expr) in FROM order
     having: Option<Expr>,
 }
 
@@ -68,8 +175,9 @@ fn flatten_plan(plan: &LogicalQueryPlan) -> FlatSelect {
                 acc.projection = exprs.clone();
                 rec(input, acc);
             }
-            LogicalQueryPlan::Distinct { input } => {
+            LogicalQueryPlan::Distinct { on, input } => {
                 acc.distinct = true;
+                acc.distinct_on = on.clone();
                 rec(input, acc);
             }
             LogicalQueryPlan::Filter { predicate, input } => {
@@ -99,8 +207,8 @@ fn flatten_plan(plan: &LogicalQueryPlan) -> FlatSelect {
                 on,
                 kind,
             } => {
-                // assume left eventually becomes main table, right is simple table scan
-                // Extract right table name if direct TableScan
+                // Joins nest on the left spine (the last JOIN is outermost); right is a
+                // simple table scan. Extract right table name if direct TableScan
                 let right_table = match &**right {
                     LogicalQueryPlan::TableScan { table, alias } => match alias {
                         Some(a) => format!("{} {}", table, a),
@@ -108,7 +216,7 @@ fn flatten_plan(plan: &LogicalQueryPlan) -> FlatSelect {
                     },
                     _ => "sub".into(),
                 };
-                acc.join = Some((*kind, right_table, on.clone()));
+                acc.joins.push((*kind, right_table, on.clone()));
                 rec(left, acc);
             }
             LogicalQueryPlan::Having { predicate, input } => {
@@ -124,11 +232,14 @@ fn flatten_plan(plan: &LogicalQueryPlan) -> FlatSelect {
     }
     let mut flat = FlatSelect::default();
     rec(plan, &mut flat);
+    // Collected outermost first; emit them in the order they were written
+    flat.joins.reverse();
     flat
 }
 
 struct FlatEmitter {
     dialect: Box<dyn SqlDialect>,
+    quoting: IdentQuoting,
 }
 
 impl FlatEmitter {
@@ -147,7 +258,27 @@ impl FlatEmitter {
         } else {
             self.quote_table(&flat.table.clone().unwrap_or_else(|| "DUAL".to_string()))
         };
-        let mut sql = if flat.distinct {
+        let mut sql = if !flat.distinct_on.is_empty() {
+            if !self.dialect.supports_distinct_on() {
+                return Err(QueryAstError::DatabaseFeatureUnsupported {
+                    db_type: format!("{:?}", self.dialect.db_type()),
+                    feature: "DISTINCT ON".to_string(),
+                });
+            }
+            let on = flat
+                .distinct_on
+                .iter()
+                .map(|e| self.emit_expr(e))
+                .collect::<Result<Vec<_>, _>>()?
+                .join(", ");
+            format!(
+                "SELECT {} ON ({}) {} FROM {}",
+                self.dialect.emit_distinct(),
+                on,
+                proj_sql,
+                from_clause
+            )
+        } else if flat.distinct {
             format!(
                 "SELECT {} {} FROM {}",
                 self.dialect.emit_distinct(),
@@ -157,7 +288,7 @@ impl FlatEmitter {
         } else {
             format!("SELECT {} FROM {}", proj_sql, from_clause)
         };
-        if let Some((kind, right_table, on)) = &flat.join {
+        for (kind, right_table, on) in &flat.joins {
             let join_kw = self.dialect.emit_join_kind(kind);
             sql.push_str(&format!(" {} {}", join_kw, self.quote_table(right_table)));
             if let Some(on_expr) = on {
@@ -293,6 +424,19 @@ impl FlatEmitter {
                     format!("{} IN ({})", self.emit_expr(expr)?, items)
                 }
             }
+            // Portable across every SQL dialect, so emitted as is
+            Expr::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => format!(
+                "{} {}BETWEEN {} AND {}",
+                self.emit_expr(expr)?,
+                if *negated { "NOT " } else { "" },
+                self.emit_expr(low)?,
+                self.emit_expr(high)?
+            ),
             Expr::Case {
                 operand,
                 when_then,
@@ -371,22 +515,33 @@ impl FlatEmitter {
         })
     }
 
+    /// Quote each part of a possibly qualified name (`schema.table.column`).
     fn emit_column(&self, col: &str) -> String {
-        if col.contains('.') {
-            col.split('.')
-                .map(|p| self.quote_ident(p))
-                .collect::<Vec<_>>()
-                .join(".")
-        } else {
-            self.quote_ident(col)
-        }
+        split_outside_quotes(col, '.')
+            .into_iter()
+            .map(|p| self.quote_ident(p))
+            .collect::<Vec<_>>()
+            .join(".")
     }
 
+    /// A qualified table name, followed by its alias when there is one.
     fn quote_table(&self, t: &str) -> String {
-        self.emit_column(t)
+        split_outside_quotes(t.trim(), ' ')
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .enumerate()
+            .map(|(i, p)| {
+                if i == 0 {
+                    self.emit_column(p)
+                } else {
+                    self.quote_ident(p)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 
     fn quote_ident(&self, ident: &str) -> String {
-        self.dialect.quote_ident(ident)
+        self.dialect.emit_ident(ident, self.quoting)
     }
 }
@@ -37,6 +37,12 @@ pub enum Expr {
         list: Vec<Expr>,
         negated: bool,
     },
+    Between {
+        expr: Box<Expr>,
+        low: Box<Expr>,
+        high: Box<Expr>,
+        negated: bool,
+    },
     Case {
         operand: Option<Box<Expr>>,
         when_then: Vec<(Expr, Expr)>,
@@ -67,7 +73,9 @@ pub enum LogicalQueryPlan {
         exprs: Vec<Expr>,
         input: Box<LogicalQueryPlan>,
     },
+    /// `DISTINCT`, or PostgreSQL's `DISTINCT ON (on...)` when `on` is set.
     Distinct {
+        on: Vec<Expr>,
         input: Box<LogicalQueryPlan>,
     },
     Filter {
@@ -97,12 +105,13 @@ pub enum LogicalQueryPlan {
         predicate: Expr,
         input: Box<LogicalQueryPlan>,
     },
-    // WITH ctes as (sql) ... <input>
+    // WITH [RECURSIVE] name [(cols)] AS (sql), ... <input>
     With {
-        ctes: Vec<(String, String)>,
+        ctes: Vec<Cte>,
+        recursive: bool,
         input: Box<LogicalQueryPlan>,
-    }, // (name, sql)
-    // Set operations (currently only UNION / UNION ALL implemented)
+    },
+    // Set operations (UNION [ALL], INTERSECT, EXCEPT); chains nest left-associatively
     SetOp {
         left: Box<LogicalQueryPlan>,
         right: Box<LogicalQueryPlan>,
@@ -119,6 +128,15 @@ pub enum LogicalQueryPlan {
     },
 }
 
+/// One `name [(columns)] AS (sql)` entry of a WITH clause; `sql` is the body
+/// without the surrounding parentheses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cte {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub sql: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum JoinKind {
     Inner,
@@ -131,6 +149,8 @@ pub enum JoinKind {
 pub enum SetOpKind {
     Union,
     UnionAll,
+    Intersect,
+    Except,
 }
 
 impl LogicalQueryPlan {
@@ -7,6 +7,8 @@
 #[cfg(feature = "query_ast")]
 pub mod ast;
 #[cfg(feature = "query_ast")]
+pub mod convert;
+#[cfg(feature = "query_ast")]
 pub mod emitter;
 #[cfg(feature = "query_ast")]
 pub mod errors;
@@ -40,7 +42,7 @@ pub fn compile_single_select(
     pagination: Option<(u64, u64)>, // (page, page_size)
     inject_auto_limit: bool,
 ) -> Result<(String, Vec<String>), QueryAstError> {
-    use emitter::emit_sql;
+    use emitter::{dialect::IdentQuoting, emit_sql_with_quoting};
     use parser::parse_single_select_to_plan;
     use plan_cache::PlanCache;
     use rewrite::{Pagination, apply_basic_rewrites};
@@ -97,6 +99,17 @@ pub fn compile_single_select(
                     hash_expr(i, h);
                 }
             }
+            E::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => {
+                (*negated as u8).hash(h);
+                hash_expr(expr, h);
+                hash_expr(low, h);
+                hash_expr(high, h);
+            }
             E::Case {
                 operand,
                 when_then,
@@ -156,7 +169,12 @@ pub fn compile_single_select(
                 }
                 hash_plan(input, h);
             }
-            L::Distinct { input } => hash_plan(input, h),
+            L::Distinct { on, input } => {
+                for e in on {
+                    hash_expr(e, h);
+                }
+                hash_plan(input, h);
+            }
             L::Filter { predicate, input } => {
                 hash_expr(predicate, h);
                 hash_plan(input, h);
@@ -200,10 +218,16 @@ pub fn compile_single_select(
                 hash_expr(predicate, h);
                 hash_plan(input, h);
             }
-            L::With { ctes, input } => {
-                for (n, s) in ctes {
-                    n.to_ascii_lowercase().hash(h);
-                    s.to_ascii_lowercase().hash(h);
+            L::With {
+                ctes,
+                recursive,
+                input,
+            } => {
+                recursive.hash(h);
+                for cte in ctes {
+                    cte.name.to_ascii_lowercase().hash(h);
+                    cte.columns.hash(h);
+                    cte.sql.to_ascii_lowercase().hash(h);
                 }
                 hash_plan(input, h);
             }
@@ -230,19 +254,6 @@ pub fn compile_single_select(
         }
     }
 
-    fn canonicalize_space(s: &str) -> String {
-        let mut out = s.trim().trim_end_matches(';').to_string();
-        out = out.split_whitespace().collect::<Vec<_>>().join(" ");
-        out
-    }
-    // Basic structural fingerprint: lowercase, remove redundant spaces and quote style agnostic
-    fn structural_fingerprint(s: &str) -> u64 {
-        let norm = canonicalize_space(&s.to_ascii_lowercase().replace(['`', '[', ']'], "\""));
-        let mut hasher = DefaultHasher::new();
-        norm.hash(&mut hasher);
-        hasher.finish()
-    }
-    // let _canon = canonicalize_space(raw); // reserved for future debugging
     let fp_struct = structural_fingerprint(raw);
     // We'll compute precise logical hash after parsing; initial quick key for early hit
     let pre_key = format!(
@@ -253,56 +264,9 @@ pub fn compile_single_select(
         return Ok((entry.sql, entry.headers));
     }
 
-    let mut working_sql = raw.to_string();
-    // Very simple CTE inlining (Phase A): if WITH cte AS (sub) SELECT ... ; only support single simple CTE referenced once
-    if working_sql
-        .trim_start()
-        .to_ascii_lowercase()
-        .starts_with("with ")
-        && let Some(as_pos) = working_sql.to_ascii_lowercase().find(" as (")
-    {
-        // Extract cte name
-        let with_body = &working_sql[4..as_pos];
-        let cte_name = with_body.trim().trim_matches(|c: char| c == ',');
-        let subquery_start = as_pos + 5; // len(" as (")
-        // Find the matching closing paren, respecting nesting (e.g. function calls
-        // like `now()` inside the CTE body) instead of grabbing the first `)`.
-        let mut depth = 1i32;
-        let mut close_paren_abs = None;
-        for (i, b) in working_sql.as_bytes()[subquery_start..].iter().enumerate() {
-            match b {
-                b'(' => depth += 1,
-                b')' => {
-                    depth -= 1;
-                    if depth == 0 {
-                        close_paren_abs = Some(subquery_start + i);
-                        break;
-                    }
-                }
-                _ => {}
-            }
-        }
-        if let Some(close_paren_abs) = close_paren_abs {
-            let subquery_sql = working_sql[subquery_start..close_paren_abs].trim();
-            // Only inline trivially simple CTE bodies; complex ones are kept as-is
-            // so the parser builds a With node and the WITH clause passes through.
-            if rewrite::is_simple_cte_body(subquery_sql) {
-                // Remaining after )
-                let after = &working_sql[close_paren_abs + 1..];
-                // Replace first occurrence of cte_name in FROM with (subquery) alias
-                let lowered_after = after.to_ascii_lowercase();
-                if lowered_after.contains(&format!(" {} ", cte_name.to_ascii_lowercase())) {
-                    let replaced_after =
-                        after.replacen(cte_name, &format!("({})", subquery_sql), 1);
-                    working_sql = replaced_after;
-                } else {
-                    // If not referenced, fallback to original SELECT after CTE list (strip WITH ...)
-                    working_sql = after.to_string();
-                }
-            }
-        }
-    }
-    let mut plan = parse_single_select_to_plan(&working_sql)?;
+    // WITH clauses are parsed into a With node; single-use simple CTEs are inlined by
+    // rewrite::inline_single_use_ctes, everything else is emitted as-is.
+    let mut plan = parse_single_select_to_plan(raw)?;
     // Compute precise hash of plan shape + expressions
     let mut hasher = DefaultHasher::new();
     hash_plan(&plan, &mut hasher);
@@ -324,10 +288,11 @@ pub fn compile_single_select(
     if let logical::LogicalQueryPlan::With { ctes, .. } = &plan
         && !ctes.is_empty()
     {
-        remaining_ctes = Some(ctes.iter().map(|(n, _)| n.clone()).collect());
+        remaining_ctes = Some(ctes.iter().map(|c| c.name.clone()).collect());
     }
     let headers = infer_headers_from_plan(&plan);
-    let sql = emit_sql(&plan, db_type)?;
+    // SQL sent to the server keeps every identifier quoted
+    let sql = emit_sql_with_quoting(&plan, db_type, IdentQuoting::Always)?;
     // (Optionally we could store remaining_ctes inside PlanEntry in future)
     let entry = plan_cache::PlanEntry {
         plan: std::sync::Arc::new(plan),
@@ -345,6 +310,55 @@ pub fn compile_single_select(
     Ok((sql, headers))
 }
 
+/// Lowercase, collapse whitespace and unify identifier quotes outside string
+/// literals; literal text is kept verbatim so `'A'` and `'a'` stay distinct.
+fn canonicalize_space(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.trim().trim_end_matches(';').chars().peekable();
+    let mut pending_space = false;
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            pending_space = !out.is_empty();
+            continue;
+        }
+        if pending_space {
+            out.push(' ');
+            pending_space = false;
+        }
+        match c {
+            '\'' => {
+                out.push(c);
+                while let Some(ch) = chars.next() {
+                    out.push(ch);
+                    if ch == '\\' {
+                        out.extend(chars.next());
+                    } else if ch == '\'' {
+                        match chars.next_if_eq(&'\'') {
+                            Some(quote) => out.push(quote),
+                            None => break,
+                        }
+                    }
+                }
+            }
+            '`' | '[' | ']' => out.push('"'),
+            _ => out.push(c.to_ascii_lowercase()),
+        }
+    }
+    out
+}
+
+/// Basic structural fingerprint: case, spacing and quote style agnostic
+/// outside string literals. Also keys the query result cache
+/// (`cache_data::save_query_result_to_cache`).
+pub fn structural_fingerprint(s: &str) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    let norm = canonicalize_space(s);
+    let mut hasher = DefaultHasher::new();
+    norm.hash(&mut hasher);
+    hasher.finish()
+}
+
 type StoreDebugType = Option<(u64, String, Option<Vec<String>>)>;
 
 thread_local! { static STORE_DEBUG: std::cell::RefCell<StoreDebugType> = const { std::cell::RefCell::new(None) }; }
@@ -430,6 +444,17 @@ pub fn plan_structural_hash(
                     hash_expr(i, h);
                 }
             }
+            E::Between {
+                expr,
+                low,
+                high,
+                negated,
+            } => {
+                negated.hash(h);
+                hash_expr(expr, h);
+                hash_expr(low, h);
+                hash_expr(high, h);
+            }
             E::Case {
                 operand,
                 when_then,
@@ -487,7 +512,7 @@ pub fn plan_structural_hash(
                 }
                 hash_plan(input, h);
             }
-            L::Distinct { input }
+            L::Distinct { input, .. }
             | L::Group { input, .. }
             | L::Filter { input, .. }
             | L::Sort { input, .. }
@@ -556,8 +581,12 @@ pub fn debug_plan(raw: &str, db_type: &DatabaseType) -> Result<String, QueryAstE
                 out.push_str(&format!("{}Projection {:?}\n", pad, exprs.len()));
                 fmt(input, indent + 1, out);
             }
-            L::Distinct { input } => {
-                out.push_str(&format!("{}Distinct\n", pad));
+            L::Distinct { on, input } => {
+                if on.is_empty() {
+                    out.push_str(&format!("{}Distinct\n", pad));
+                } else {
+                    out.push_str(&format!("{}DistinctOn {:?}\n", pad, on));
+                }
                 fmt(input, indent + 1, out);
             }
             L::Filter { predicate, input } => {
@@ -589,11 +618,16 @@ pub fn debug_plan(raw: &str, db_type: &DatabaseType) -> Result<String, QueryAstE
                 out.push_str(&format!("{}Having {:?}\n", pad, predicate));
                 fmt(input, indent + 1, out);
             }
-            L::With { ctes, input } => {
+            L::With {
+                ctes,
+                recursive,
+                input,
+            } => {
                 out.push_str(&format!(
-                    "{}With ctes={:?}\n",
+                    "{}With{} ctes={:?}\n",
                     pad,
-                    ctes.iter().map(|(n, _)| n).collect::<Vec<_>>()
+                    if *recursive { " recursive" } else { "" },
+                    ctes.iter().map(|c| &c.name).collect::<Vec<_>>()
                 ));
                 fmt(input, indent + 1, out);
             }
@@ -624,7 +658,7 @@ pub fn plan_metrics(raw: &str) -> Result<(usize, usize, usize, usize, usize), Qu
             | L::Filter { input, .. }
             | L::Sort { input, .. }
             | L::Limit { input, .. }
-            | L::Distinct { input }
+            | L::Distinct { input, .. }
             | L::Group { input, .. }
             | L::Having { input, .. }
             | L::With { input, .. } => walk(input, depth + 1, stats),
@@ -685,6 +719,13 @@ pub fn plan_metrics(raw: &str) -> Result<(usize, usize, usize, usize, usize), Qu
                 count_expr(expr, subs, correlated, wins);
                 count_expr(pattern, subs, correlated, wins);
             }
+            E::Between {
+                expr, low, high, ..
+            } => {
+                count_expr(expr, subs, correlated, wins);
+                count_expr(low, subs, correlated, wins);
+                count_expr(high, subs, correlated, wins);
+            }
             E::Not(inner) => count_expr(inner, subs, correlated, wins),
             E::IsNull { expr, .. } => count_expr(expr, subs, correlated, wins),
             _ => {}
@@ -701,7 +742,7 @@ pub fn plan_metrics(raw: &str) -> Result<(usize, usize, usize, usize, usize), Qu
             L::Filter { input, .. }
             | L::Sort { input, .. }
             | L::Limit { input, .. }
-            | L::Distinct { input }
+            | L::Distinct { input, .. }
             | L::Group { input, .. }
             | L::Having { input, .. }
             | L::With { input, .. } => collect_projection(input, out),
@@ -722,6 +763,49 @@ pub fn plan_metrics(raw: &str) -> Result<(usize, usize, usize, usize, usize), Qu
     Ok(stats)
 }
 
+/// Everything the "Show query plan" panel displays for one statement.
+#[cfg(feature = "query_ast")]
+#[derive(Debug, Clone)]
+pub struct PlanInspection {
+    /// Indented logical plan as parsed, before rewrites (see [`debug_plan`]).
+    pub plan: String,
+    /// (nodes, depth, subqueries_total, subqueries_correlated, windows), see [`plan_metrics`].
+    pub metrics: (usize, usize, usize, usize, usize),
+    /// Rewrite rules that fired, in application order.
+    pub rules: Vec<String>,
+    pub structural_hash: u64,
+    /// SQL that would be sent to the server after rewrites.
+    pub emitted_sql: String,
+}
+
+/// Parse, rewrite and emit `raw` without touching the plan cache, so the
+/// reported rewrite rules always belong to this statement.
+#[cfg(feature = "query_ast")]
+pub fn inspect_plan(
+    raw: &str,
+    db_type: &DatabaseType,
+    inject_auto_limit: bool,
+) -> Result<PlanInspection, QueryAstError> {
+    let plan_text = debug_plan(raw, db_type)?;
+    let metrics = plan_metrics(raw)?;
+    let (structural_hash, _) = plan_structural_hash(raw, db_type, None, inject_auto_limit)?;
+    let mut plan = parser::parse_single_select_to_plan(raw)?;
+    rewrite::apply_basic_rewrites(&mut plan, inject_auto_limit, None)?;
+    let rules = rewrite::take_last_rules();
+    let emitted_sql = emitter::emit_sql_with_quoting(
+        &plan,
+        db_type,
+        emitter::dialect::IdentQuoting::Always,
+    )?;
+    Ok(PlanInspection {
+        plan: plan_text,
+        metrics,
+        rules,
+        structural_hash,
+        emitted_sql,
+    })
+}
+
 #[cfg(feature = "query_ast")]
 fn infer_headers_from_plan(plan: &LogicalQueryPlan) -> Vec<String> {
     use logical::Expr as E;
@@ -732,7 +816,7 @@ fn infer_headers_from_plan(plan: &LogicalQueryPlan) -> Vec<String> {
             L::Filter { input, .. }
             | L::Sort { input, .. }
             | L::Limit { input, .. }
-            | L::Distinct { input }
+            | L::Distinct { input, .. }
             | L::Group { input, .. }
             | L::Having { input, .. }
             | L::With { input, .. } => find_projection(input),
@@ -756,6 +840,7 @@ fn infer_headers_from_plan(plan: &LogicalQueryPlan) -> Vec<String> {
                 E::IsNull { .. } => out.push("is_null".to_string()),
                 E::Like { .. } => out.push("like".to_string()),
                 E::InList { .. } => out.push("in_list".to_string()),
+                E::Between { .. } => out.push("between".to_string()),
                 E::Case { .. } => out.push("case".to_string()),
                 E::BinaryOp { .. } | E::Raw(_) => out.push("expr".to_string()),
                 E::Subquery { .. } => out.push("subquery".to_string()),
@@ -1,85 +1,122 @@
 use super::{
     errors::QueryAstError,
-    logical::{Expr, JoinKind, LogicalQueryPlan, SetOpKind, SortItem},
+    logical::{Cte, Expr, JoinKind, LogicalQueryPlan, SetOpKind, SortItem},
 };
+use crate::models::enums::DatabaseType;
 use sqlparser::ast as sq;
-use sqlparser::dialect::GenericDialect;
+use sqlparser::dialect::{
+    Dialect, GenericDialect, MsSqlDialect, MySqlDialect, PostgreSqlDialect, SQLiteDialect,
+};
 use sqlparser::parser::Parser;
 use std::collections::HashSet;
 
 pub fn parse_single_select_to_plan(sql: &str) -> Result<LogicalQueryPlan, QueryAstError> {
-    let dialect = GenericDialect {};
-    let ast = Parser::parse_sql(&dialect, sql).map_err(|e| QueryAstError::Parse(e.to_string()))?;
+    parse_with_dialect(sql, &GenericDialect {})
+}
+
+/// Like [`parse_single_select_to_plan`], but accepting the syntax of `source`
+/// (e.g. `[bracketed]` identifiers for SQL Server), for dialect conversion.
+pub fn parse_select_for_dialect(
+    sql: &str,
+    source: Option<&DatabaseType>,
+) -> Result<LogicalQueryPlan, QueryAstError> {
+    match source {
+        Some(DatabaseType::MySQL) => parse_with_dialect(sql, &MySqlDialect {}),
+        Some(DatabaseType::PostgreSQL) => parse_with_dialect(sql, &PostgreSqlDialect {}),
+        Some(DatabaseType::SQLite) => parse_with_dialect(sql, &SQLiteDialect {}),
+        Some(DatabaseType::MsSQL) => parse_with_dialect(sql, &MsSqlDialect {}),
+        _ => parse_with_dialect(sql, &GenericDialect {}),
+    }
+}
+
+fn parse_with_dialect(sql: &str, dialect: &dyn Dialect) -> Result<LogicalQueryPlan, QueryAstError> {
+    let ast = Parser::parse_sql(dialect, sql).map_err(|e| QueryAstError::Parse(e.to_string()))?;
     if ast.len() != 1 {
         return Err(QueryAstError::Unsupported("multi-statement"));
     }
     match &ast[0] {
-        sq::Statement::Query(q) => convert_query(q, sql),
+        sq::Statement::Query(q) => convert_query(q),
         _ => Err(QueryAstError::Unsupported("not a SELECT")),
     }
 }
 
-fn convert_query(q: &sq::Query, raw_sql: &str) -> Result<LogicalQueryPlan, QueryAstError> {
-    let mut plan = convert_set_expr(&q.body, q, raw_sql)?;
+fn convert_query(q: &sq::Query) -> Result<LogicalQueryPlan, QueryAstError> {
+    let mut plan = convert_set_expr(&q.body, Some(q))?;
     if let Some(with) = &q.with
         && !with.cte_tables.is_empty()
     {
-        let mut ctes = Vec::new();
-        for c in &with.cte_tables {
-            ctes.push((c.alias.name.value.clone(), c.query.to_string()));
-        }
+        let ctes = with
+            .cte_tables
+            .iter()
+            .map(|c| Cte {
+                name: c.alias.name.to_string(),
+                columns: c
+                    .alias
+                    .columns
+                    .iter()
+                    .map(|col| col.name.to_string())
+                    .collect(),
+                sql: c.query.to_string(),
+            })
+            .collect();
         plan = LogicalQueryPlan::With {
             ctes,
+            recursive: with.recursive,
             input: Box::new(plan),
         };
     }
     Ok(plan)
 }
 
+/// `outer_q` carries the ORDER BY / LIMIT written after `se`; set operation
+/// operands get `None` so a trailing ORDER BY / LIMIT applies to the whole
+/// compound instead of being copied into every branch.
 fn convert_set_expr(
     se: &sq::SetExpr,
-    outer_q: &sq::Query,
-    raw_sql: &str,
+    outer_q: Option<&sq::Query>,
 ) -> Result<LogicalQueryPlan, QueryAstError> {
     match se {
-        sq::SetExpr::Select(sel) => convert_select(sel, outer_q, raw_sql),
-        // Newer sqlparser versions expose set ops via SetOperation { op, left, right, set_quantifier, .. }
+        sq::SetExpr::Select(sel) => convert_select(sel, outer_q),
+        // sqlparser already nests chains left-associatively (INTERSECT binding tighter),
+        // respecting parentheses, so `a UNION b EXCEPT c` arrives as ((a UNION b) EXCEPT c).
         sq::SetExpr::SetOperation {
             op,
             left,
             right,
             set_quantifier,
-            ..
         } => {
-            let left_plan = convert_set_expr(left, outer_q, raw_sql)?;
-            let right_plan = convert_set_expr(right, outer_q, raw_sql)?;
+            let left_plan = convert_set_expr(left, None)?;
+            let right_plan = convert_set_expr(right, None)?;
             use sq::SetOperator;
+            let all = matches!(set_quantifier, sq::SetQuantifier::All);
             let op_kind = match op {
-                SetOperator::Union => {
-                    if matches!(set_quantifier, sq::SetQuantifier::All) {
-                        SetOpKind::UnionAll
-                    } else {
-                        SetOpKind::Union
-                    }
+                SetOperator::Union if all => SetOpKind::UnionAll,
+                SetOperator::Union => SetOpKind::Union,
+                SetOperator::Intersect | SetOperator::Except | SetOperator::Minus if all => {
+                    return Err(QueryAstError::Unsupported("INTERSECT/EXCEPT ALL"));
                 }
-                _ => return Err(QueryAstError::Unsupported("only UNION supported")),
+                SetOperator::Intersect => SetOpKind::Intersect,
+                SetOperator::Except | SetOperator::Minus => SetOpKind::Except,
             };
-            Ok(LogicalQueryPlan::SetOp {
+            let plan = LogicalQueryPlan::SetOp {
                 left: Box::new(left_plan),
                 right: Box::new(right_plan),
                 op: op_kind,
-            })
+            };
+            match outer_q {
+                Some(q) => apply_order_limit(plan, q),
+                None => Ok(plan),
+            }
         }
-        // Some variants (Query) wrap another Query (e.g. parentheses); unwrap recursively
-        sq::SetExpr::Query(q2) => convert_set_expr(&q2.body, q2, raw_sql),
+        // Parenthesized operand: its own ORDER BY / LIMIT / WITH stay inside it
+        sq::SetExpr::Query(q2) => convert_query(q2),
         _ => Err(QueryAstError::Unsupported("unsupported set expr variant")),
     }
 }
 
 fn convert_select(
     sel: &sq::Select,
-    q: &sq::Query,
-    raw_sql: &str,
+    q: Option<&sq::Query>,
 ) -> Result<LogicalQueryPlan, QueryAstError> {
     if sel.from.is_empty() {
         return Err(QueryAstError::Unsupported("missing FROM"));
@@ -131,14 +168,15 @@ fn convert_select(
             _ => return Err(QueryAstError::Unsupported("complex join rel")),
         };
         let kind = match join.join_operator {
-            sq::JoinOperator::Inner(_) => JoinKind::Inner,
+            sq::JoinOperator::Join(_) | sq::JoinOperator::Inner(_) => JoinKind::Inner,
             sq::JoinOperator::Left(_) | sq::JoinOperator::LeftOuter(_) => JoinKind::Left,
             sq::JoinOperator::Right(_) | sq::JoinOperator::RightOuter(_) => JoinKind::Right,
             sq::JoinOperator::FullOuter(_) => JoinKind::Full,
             _ => JoinKind::Inner,
         };
         let on_expr = match &join.join_operator {
-            sq::JoinOperator::Inner(cond)
+            sq::JoinOperator::Join(cond)
+            | sq::JoinOperator::Inner(cond)
             | sq::JoinOperator::Left(cond)
             | sq::JoinOperator::LeftOuter(cond)
             | sq::JoinOperator::Right(cond)
@@ -165,10 +203,10 @@ fn convert_select(
         };
     }
 
-    // GROUP BY (primary attempt via AST)
+    // GROUP BY
     use sqlparser::ast::GroupByExpr;
     let mut group_added = false;
-    if let GroupByExpr::Expressions(_mod, list) = &sel.group_by
+    if let GroupByExpr::Expressions(list, _modifiers) = &sel.group_by
         && !list.is_empty()
     {
         let mut gexprs = Vec::new();
@@ -190,36 +228,12 @@ fn convert_select(
         };
         group_added = true;
     }
-    // Fallback heuristic: if parser gave us no group node but raw SQL contains GROUP BY
-    if !group_added {
-        let lower = raw_sql.to_ascii_lowercase();
-        if let Some(gpos) = lower.find("group by ") {
-            // slice after 'group by '
-            let start = gpos + "group by ".len();
-            // find earliest of having/order/limit/end
-            let tail = &lower[start..];
-            let mut end_rel = tail.len();
-            for kw in [" having ", " order by ", " limit ", ";"] {
-                if let Some(idx) = tail.find(kw)
-                    && idx < end_rel
-                {
-                    end_rel = idx;
-                }
-            }
-            let original_slice = &raw_sql[start..start + end_rel];
-            let candidates: Vec<Expr> = original_slice
-                .split(',')
-                .map(|s| s.trim())
-                .filter(|s| !s.is_empty())
-                .map(|s| Expr::Raw(s.to_string()))
-                .collect();
-            if !candidates.is_empty() {
-                plan = LogicalQueryPlan::Group {
-                    group_exprs: candidates,
-                    input: Box::new(plan),
-                };
-            }
-        }
+    // GROUP BY ALL has no expression list; keep it verbatim
+    if !group_added && let GroupByExpr::All(_) = &sel.group_by {
+        plan = LogicalQueryPlan::Group {
+            group_exprs: vec![Expr::Raw("ALL".to_string())],
+            input: Box::new(plan),
+        };
     }
 
     // HAVING
@@ -286,13 +300,60 @@ fn convert_select(
         input: Box::new(plan),
     };
 
-    // DISTINCT
-    if sel.distinct.is_some() {
-        plan = LogicalQueryPlan::Distinct {
+    // DISTINCT / DISTINCT ON (...); an explicit ALL keeps every row
+    match &sel.distinct {
+        Some(sq::Distinct::Distinct) => {
+            plan = LogicalQueryPlan::Distinct {
+                on: Vec::new(),
+                input: Box::new(plan),
+            };
+        }
+        Some(sq::Distinct::On(exprs)) => {
+            plan = LogicalQueryPlan::Distinct {
+                on: exprs.iter().map(convert_expr).collect(),
+                input: Box::new(plan),
+            };
+        }
+        Some(sq::Distinct::All) | None => {}
+    }
+
+    if let Some(q) = q {
+        plan = apply_order_limit(plan, q)?;
+    }
+    // SQL Server `SELECT TOP n`: same as LIMIT n unless PERCENT / WITH TIES
+    if let Some(top) = &sel.top
+        && !matches!(plan, LogicalQueryPlan::Limit { .. })
+    {
+        if top.percent || top.with_ties {
+            return Err(QueryAstError::Unsupported("TOP PERCENT / WITH TIES"));
+        }
+        let n = match &top.quantity {
+            Some(sq::TopQuantity::Constant(n)) => Some(*n),
+            Some(sq::TopQuantity::Expr(e)) => literal_u64(Some(e)),
+            None => None,
+        };
+        let Some(limit) = n else {
+            return Err(QueryAstError::Unsupported("non-constant TOP"));
+        };
+        plan = LogicalQueryPlan::Limit {
+            limit,
+            offset: 0,
             input: Box::new(plan),
         };
     }
 
+    // Final accurate correlation marking: traverse expressions finding subquery columns referencing outer aliases.
+    let mut outer_aliases = HashSet::new();
+    collect_table_aliases(&plan, &mut outer_aliases);
+    mark_correlated(&mut plan, &outer_aliases);
+    Ok(plan)
+}
+
+/// Wrap `plan` in the Sort / Limit nodes for `q`'s ORDER BY and LIMIT/OFFSET.
+fn apply_order_limit(
+    mut plan: LogicalQueryPlan,
+    q: &sq::Query,
+) -> Result<LogicalQueryPlan, QueryAstError> {
     // ORDER BY
     if let Some(ob) = &q.order_by
         && let sq::OrderByKind::Expressions(exprs) = &ob.kind
@@ -320,11 +381,6 @@ fn convert_select(
             input: Box::new(plan),
         };
     }
-
-    // Final accurate correlation marking: traverse expressions finding subquery columns referencing outer aliases.
-    let mut outer_aliases = HashSet::new();
-    collect_table_aliases(&plan, &mut outer_aliases);
-    mark_correlated(&mut plan, &outer_aliases);
     Ok(plan)
 }
 
@@ -333,26 +389,30 @@ fn extract_limit_offset(q: &sq::Query) -> Result<(Option<u64>, Option<u64>), Que
         Some(sq::LimitClause::LimitOffset { limit, offset, .. }) => {
             (limit.as_ref(), offset.as_ref().map(|o| &o.value))
         }
-        Some(sq::LimitClause::OffsetCommaLimit { offset, limit }) => {
-            (Some(limit), Some(offset))
-        }
+        Some(sq::LimitClause::OffsetCommaLimit { offset, limit }) => (Some(limit), Some(offset)),
         None => (None, None),
     };
-    let limit = match limit_expr {
-        Some(sq::Expr::Value(v)) => match &v.value {
-            sq::Value::Number(n, _) => n.parse().ok(),
-            _ => None,
-        },
-        _ => None,
+    // `FETCH FIRST|NEXT n ROWS ONLY` is the standard spelling of LIMIT
+    let fetch = match &q.fetch {
+        Some(f) if f.percent || f.with_ties => {
+            return Err(QueryAstError::Unsupported("FETCH PERCENT / WITH TIES"));
+        }
+        Some(f) => f.quantity.as_ref(),
+        None => None,
     };
-    let offset = match offset_expr {
+    let limit = literal_u64(limit_expr.or(fetch));
+    let offset = literal_u64(offset_expr);
+    Ok((limit, offset))
+}
+
+fn literal_u64(e: Option<&sq::Expr>) -> Option<u64> {
+    match e {
         Some(sq::Expr::Value(v)) => match &v.value {
             sq::Value::Number(n, _) => n.parse().ok(),
             _ => None,
         },
         _ => None,
-    };
-    Ok((limit, offset))
+    }
 }
 
 fn convert_expr(e: &sq::Expr) -> Expr {
@@ -361,6 +421,20 @@ fn convert_expr(e: &sq::Expr) -> Expr {
             sql: sub.to_string(),
             correlated: false,
         },
+        // `x [NOT] IN (SELECT ...)`: keep the subquery as a node so it is
+        // marked for correlation and converted like any other subquery
+        sq::Expr::InSubquery {
+            expr,
+            subquery,
+            negated,
+        } => Expr::BinaryOp {
+            left: Box::new(convert_expr(expr)),
+            op: if *negated { "NOT IN" } else { "IN" }.to_string(),
+            right: Box::new(Expr::Subquery {
+                sql: subquery.to_string(),
+                correlated: false,
+            }),
+        },
         sq::Expr::Identifier(id) => Expr::Column(id.to_string()),
         sq::Expr::CompoundIdentifier(parts) => Expr::Column(
             parts
@@ -478,6 +552,17 @@ fn convert_expr(e: &sq::Expr) -> Expr {
                 negated: *negated,
             }
         }
+        sq::Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => Expr::Between {
+            expr: Box::new(convert_expr(expr)),
+            low: Box::new(convert_expr(low)),
+            high: Box::new(convert_expr(high)),
+            negated: *negated,
+        },
         sq::Expr::Case {
             operand,
             conditions,
@@ -523,7 +608,7 @@ fn collect_table_aliases(plan: &LogicalQueryPlan, out: &mut HashSet<String>) {
         | LogicalQueryPlan::Filter { input, .. }
         | LogicalQueryPlan::Sort { input, .. }
         | LogicalQueryPlan::Limit { input, .. }
-        | LogicalQueryPlan::Distinct { input }
+        | LogicalQueryPlan::Distinct { input, .. }
         | LogicalQueryPlan::Group { input, .. }
         | LogicalQueryPlan::Having { input, .. }
         | LogicalQueryPlan::With { input, .. } => collect_table_aliases(input, out),
@@ -551,7 +636,7 @@ fn mark_correlated(plan: &mut LogicalQueryPlan, outer: &HashSet<String>) {
         | LogicalQueryPlan::Filter { input, .. }
         | LogicalQueryPlan::Sort { input, .. }
         | LogicalQueryPlan::Limit { input, .. }
-        | LogicalQueryPlan::Distinct { input }
+        | LogicalQueryPlan::Distinct { input, .. }
         | LogicalQueryPlan::Group { input, .. }
         | LogicalQueryPlan::Having { input, .. }
         | LogicalQueryPlan::With { input, .. } => mark_correlated(input, outer),
@@ -119,13 +119,15 @@ fn has_limit(plan: &LogicalQueryPlan) -> bool {
         LogicalQueryPlan::Projection { input, .. }
         | LogicalQueryPlan::Filter { input, .. }
         | LogicalQueryPlan::Sort { input, .. }
-        | LogicalQueryPlan::Distinct { input }
+        | LogicalQueryPlan::Distinct { input, .. }
         | LogicalQueryPlan::Group { input, .. }
         | LogicalQueryPlan::Having { input, .. }
         | LogicalQueryPlan::With { input, .. } => has_limit(input),
-        LogicalQueryPlan::Join { left, right, .. }
-        | LogicalQueryPlan::SetOp { left, right, .. } => has_limit(left) || has_limit(right),
-        LogicalQueryPlan::TableScan { .. } | LogicalQueryPlan::SubqueryScan { .. } => false,
+        LogicalQueryPlan::Join { left, right, .. } => has_limit(left) || has_limit(right),
+        // A LIMIT inside one operand does not bound the compound's result
+        LogicalQueryPlan::SetOp { .. }
+        | LogicalQueryPlan::TableScan { .. }
+        | LogicalQueryPlan::SubqueryScan { .. } => false,
     }
 }
 
@@ -146,7 +148,7 @@ fn replace_or_add_limit_record(plan: &mut LogicalQueryPlan, limit: u64, offset:
         LogicalQueryPlan::Projection { input, .. }
         | LogicalQueryPlan::Filter { input, .. }
         | LogicalQueryPlan::Sort { input, .. }
-        | LogicalQueryPlan::Distinct { input }
+        | LogicalQueryPlan::Distinct { input, .. }
         | LogicalQueryPlan::Group { input, .. }
         | LogicalQueryPlan::Having { input, .. }
         | LogicalQueryPlan::With { input, .. } => {
@@ -155,10 +157,10 @@ fn replace_or_add_limit_record(plan: &mut LogicalQueryPlan, limit: u64, offset:
         LogicalQueryPlan::Join { left, .. } => {
             changed |= replace_or_add_limit_record(left, limit, offset);
         }
-        LogicalQueryPlan::SetOp { left, .. } => {
-            changed |= replace_or_add_limit_record(left, limit, offset);
-        }
-        LogicalQueryPlan::TableScan { .. } | LogicalQueryPlan::SubqueryScan { .. } => {
+        // Page the compound as a whole, never one of its operands
+        LogicalQueryPlan::SetOp { .. }
+        | LogicalQueryPlan::TableScan { .. }
+        | LogicalQueryPlan::SubqueryScan { .. } => {
             let new = LogicalQueryPlan::Limit {
                 limit,
                 offset,
@@ -256,7 +258,13 @@ fn projection_prune(plan: &mut LogicalQueryPlan) -> bool {
             L::Projection { input, .. } => {
                 collect_needed(input, needed);
             }
-            L::Distinct { input } | L::With { input, .. } => collect_needed(input, needed),
+            L::Distinct { on, input } => {
+                for e in on {
+                    collect_expr_cols(e, needed);
+                }
+                collect_needed(input, needed);
+            }
+            L::With { input, .. } => collect_needed(input, needed),
             L::Join {
                 left, right, on, ..
             } => {
@@ -304,6 +312,13 @@ fn projection_prune(plan: &mut LogicalQueryPlan) -> bool {
                     collect_expr_cols(l, out);
                 }
             }
+            Between {
+                expr, low, high, ..
+            } => {
+                collect_expr_cols(expr, out);
+                collect_expr_cols(low, out);
+                collect_expr_cols(high, out);
+            }
             Case {
                 operand,
                 when_then,
@@ -393,7 +408,7 @@ fn projection_prune(plan: &mut LogicalQueryPlan) -> bool {
             L::Filter { input, .. }
             | L::Sort { input, .. }
             | L::Limit { input, .. }
-            | L::Distinct { input }
+            | L::Distinct { input, .. }
             | L::Group { input, .. }
             | L::Having { input, .. }
             | L::With { input, .. } => recurse(input, changed, needed_parent, is_root),
@@ -439,7 +454,7 @@ fn merge_consecutive_filters(plan: &mut LogicalQueryPlan) -> bool {
         L::Projection { input, .. }
         | L::Sort { input, .. }
         | L::Limit { input, .. }
-        | L::Distinct { input }
+        | L::Distinct { input, .. }
         | L::Group { input, .. }
         | L::Having { input, .. }
         | L::With { input, .. } => {
@@ -484,7 +499,7 @@ fn remove_redundant_projection(plan: &mut LogicalQueryPlan) -> bool {
         L::Filter { input, .. }
         | L::Sort { input, .. }
         | L::Limit { input, .. }
-        | L::Distinct { input }
+        | L::Distinct { input, .. }
         | L::Group { input, .. }
         | L::Having { input, .. }
         | L::With { input, .. } => {
@@ -510,7 +525,7 @@ fn try_pushdown_limit_into_subquery(plan: &mut LogicalQueryPlan) -> bool {
             input,
         } if *offset == 0 => {
             if let L::Projection { input: inner2, .. }
-            | L::Distinct { input: inner2 }
+            | L::Distinct { input: inner2, .. }
             | L::Sort { input: inner2, .. } = &mut **input
             {
                 // Recurse first
@@ -534,7 +549,7 @@ fn try_pushdown_limit_into_subquery(plan: &mut LogicalQueryPlan) -> bool {
         L::Projection { input, .. }
         | L::Filter { input, .. }
         | L::Sort { input, .. }
-        | L::Distinct { input }
+        | L::Distinct { input, .. }
         | L::Group { input, .. }
         | L::Having { input, .. }
         | L::With { input, .. } => {
@@ -544,12 +559,11 @@ fn try_pushdown_limit_into_subquery(plan: &mut LogicalQueryPlan) -> bool {
             changed |= try_pushdown_limit_into_subquery(left);
             changed |= try_pushdown_limit_into_subquery(right);
         }
+        L::SetOp { left, right, .. } => {
+            changed |= try_pushdown_limit_into_subquery(left);
+            changed |= try_pushdown_limit_into_subquery(right);
+        }
         L::TableScan { .. } | L::SubqueryScan { .. } => {}
-        L::SetOp {
-            left: _left,
-            right: _right,
-            op: _op,
-        } => todo!(),
     }
     changed
 }
@@ -578,7 +592,7 @@ fn collect_aliases(plan: &LogicalQueryPlan, out: &mut Vec<String>) {
         | LogicalQueryPlan::Filter { input, .. }
         | LogicalQueryPlan::Sort { input, .. }
         | LogicalQueryPlan::Limit { input, .. }
-        | LogicalQueryPlan::Distinct { input }
+        | LogicalQueryPlan::Distinct { input, .. }
         | LogicalQueryPlan::Group { input, .. }
         | LogicalQueryPlan::Having { input, .. }
         | LogicalQueryPlan::With { input, .. } => collect_aliases(input, out),
@@ -603,7 +617,7 @@ fn annotate(plan: &mut LogicalQueryPlan, aliases: &[String], changed: &mut bool)
         | LogicalQueryPlan::Filter { input, .. }
         | LogicalQueryPlan::Sort { input, .. }
         | LogicalQueryPlan::Limit { input, .. }
-        | LogicalQueryPlan::Distinct { input }
+        | LogicalQueryPlan::Distinct { input, .. }
         | LogicalQueryPlan::Group { input, .. }
         | LogicalQueryPlan::Having { input, .. }
         | LogicalQueryPlan::With { input, .. } => annotate(input, aliases, changed),
@@ -736,7 +750,8 @@ fn collect_ident_roots_query(
             collect_ident_roots_factor(&fw.relation, out);
             for j in &fw.joins {
                 match &j.join_operator {
-                    sq::JoinOperator::Inner(constraint)
+                    sq::JoinOperator::Join(constraint)
+                    | sq::JoinOperator::Inner(constraint)
                     | sq::JoinOperator::Left(constraint)
                     | sq::JoinOperator::LeftOuter(constraint)
                     | sq::JoinOperator::Right(constraint)
@@ -903,17 +918,32 @@ fn inline_single_use_ctes(plan: &mut LogicalQueryPlan) -> bool {
     use LogicalQueryPlan as L;
     let mut changed = false;
     match plan {
-        L::With { ctes, input } => {
-            if ctes.is_empty() {
+        L::With {
+            ctes,
+            recursive,
+            input,
+        } => {
+            // Recursive CTEs cannot become derived tables
+            if ctes.is_empty() || *recursive {
                 return false;
             }
             // Build lowercase names
-            let names: Vec<String> = ctes.iter().map(|(n, _)| n.to_ascii_lowercase()).collect();
+            let names: Vec<String> = ctes.iter().map(|c| c.name.to_ascii_lowercase()).collect();
             let mut counts = vec![0usize; names.len()];
             count_cte_refs(input, &names, &mut counts);
             let mut to_inline = Vec::new();
             for (idx, c) in counts.iter().enumerate() {
-                if *c == 1 && is_simple_cte_body(&ctes[idx].1) {
+                // Keep CTEs that other CTE bodies depend on, and column-list CTEs
+                // whose renames a plain derived table would lose
+                let used_by_other_cte = ctes
+                    .iter()
+                    .enumerate()
+                    .any(|(j, other)| j != idx && mentions_name(&other.sql, &names[idx]));
+                if *c == 1
+                    && !used_by_other_cte
+                    && ctes[idx].columns.is_empty()
+                    && is_simple_cte_body(&ctes[idx].sql)
+                {
                     to_inline.push(idx);
                 }
             }
@@ -921,9 +951,8 @@ fn inline_single_use_ctes(plan: &mut LogicalQueryPlan) -> bool {
                 // Apply replacements inside subtree
                 for idx in to_inline.iter().rev() {
                     // reverse so index stable when removing
-                    let (name, sql) = ctes[*idx].clone();
-                    inline_cte_in_subtree(input, &name, &sql);
-                    ctes.remove(*idx);
+                    let cte = ctes.remove(*idx);
+                    inline_cte_in_subtree(input, &cte.name, &cte.sql);
                     changed = true;
                 }
                 if ctes.is_empty() {
@@ -943,7 +972,7 @@ fn inline_single_use_ctes(plan: &mut LogicalQueryPlan) -> bool {
         | L::Filter { input, .. }
         | L::Sort { input, .. }
         | L::Limit { input, .. }
-        | L::Distinct { input }
+        | L::Distinct { input, .. }
         | L::Group { input, .. }
         | L::Having { input, .. } => {
             changed |= inline_single_use_ctes(input);
@@ -957,6 +986,13 @@ fn inline_single_use_ctes(plan: &mut LogicalQueryPlan) -> bool {
     changed
 }
 
+/// Whole-token, case-insensitive check for `lower_name` inside `sql`.
+fn mentions_name(sql: &str, lower_name: &str) -> bool {
+    sql.to_ascii_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+        .any(|tok| tok == lower_name)
+}
+
 fn count_cte_refs(plan: &LogicalQueryPlan, names: &[String], counts: &mut [usize]) {
     match plan {
         LogicalQueryPlan::TableScan { table, alias } => {
@@ -968,12 +1004,8 @@ fn count_cte_refs(plan: &LogicalQueryPlan, names: &[String], counts: &mut [usize
             }
         }
         LogicalQueryPlan::SubqueryScan { sql, .. } => {
-            let lower = sql.to_ascii_lowercase();
             for (i, n) in names.iter().enumerate() {
-                if lower
-                    .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
-                    .any(|tok| tok == n)
-                {
+                if mentions_name(sql, n) {
                     counts[i] += 1;
                 }
             }
@@ -982,7 +1014,7 @@ fn count_cte_refs(plan: &LogicalQueryPlan, names: &[String], counts: &mut [usize
         | LogicalQueryPlan::Filter { input, .. }
         | LogicalQueryPlan::Sort { input, .. }
         | LogicalQueryPlan::Limit { input, .. }
-        | LogicalQueryPlan::Distinct { input }
+        | LogicalQueryPlan::Distinct { input, .. }
         | LogicalQueryPlan::Group { input, .. }
         | LogicalQueryPlan::Having { input, .. }
         | LogicalQueryPlan::With { input, .. } => count_cte_refs(input, names, counts),
@@ -1023,7 +1055,7 @@ fn inline_cte_in_subtree(plan: &mut LogicalQueryPlan, name: &str, sql: &str) {
         | LogicalQueryPlan::Filter { input, .. }
         | LogicalQueryPlan::Sort { input, .. }
         | LogicalQueryPlan::Limit { input, .. }
-        | LogicalQueryPlan::Distinct { input }
+        | LogicalQueryPlan::Distinct { input, .. }
         | LogicalQueryPlan::Group { input, .. }
         | LogicalQueryPlan::Having { input, .. }
         | LogicalQueryPlan::With { input, .. } => inline_cte_in_subtree(input, name, sql),
@@ -0,0 +1,230 @@
+//! Named parameter presets for saved queries ("last 7 days", "prod tenant").
+//! A query's presets live in `<query>.presets.json` next to its `.sql` file
+//! and move or disappear with it. Picking a preset in the top bar runs the
+//! query with its values; the parameter dialog fills, saves and deletes them.
+
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::{editor, models::structs::ParameterPreset, window_egui};
+
+fn presets_path(query_file: &Path) -> PathBuf {
+    query_file.with_extension("presets.json")
+}
+
+fn load_presets(query_file: &str) -> Vec<ParameterPreset> {
+    std::fs::read_to_string(presets_path(Path::new(query_file)))
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn save_presets(query_file: &str, presets: &[ParameterPreset]) -> Result<(), String> {
+    let path = presets_path(Path::new(query_file));
+    if presets.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        };
+    }
+    let json = serde_json::to_string_pretty(presets).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("Cannot write {}: {}", path.display(), e))
+}
+
+/// Presets of `query_file`, read from disk the first time they are asked for.
+pub(crate) fn presets_for<'a>(
+    tabular: &'a mut window_egui::Tabular,
+    query_file: &str,
+) -> &'a [ParameterPreset] {
+    tabular
+        .parameter_presets
+        .entry(query_file.to_string())
+        .or_insert_with(|| load_presets(query_file))
+}
+
+/// Carry the presets along when a query file moves to `to`.
+pub(crate) fn move_presets(tabular: &mut window_egui::Tabular, from: &str, to: &Path) {
+    tabular.parameter_presets.remove(from);
+    let source = presets_path(Path::new(from));
+    if source.exists() {
+        let _ = std::fs::rename(source, presets_path(to));
+    }
+}
+
+pub(crate) fn remove_presets(tabular: &mut window_egui::Tabular, query_file: &str) {
+    tabular.parameter_presets.remove(query_file);
+    let _ = save_presets(query_file, &[]);
+}
+
+/// Dialog inputs for `params`, filled from `preset` where it has a value.
+pub(crate) fn preset_inputs(
+    preset: Option<&ParameterPreset>,
+    params: &[String],
+) -> Vec<(String, String)> {
+    params
+        .iter()
+        .map(|param| {
+            let value = preset
+                .and_then(|p| p.values.iter().find(|(name, _)| name == param))
+                .map(|(_, value)| value.clone())
+                .unwrap_or_default();
+            (param.clone(), value)
+        })
+        .collect()
+}
+
+/// Store the dialog's current inputs under `name`, replacing a preset of the
+/// same name.
+fn upsert_preset(presets: &mut Vec<ParameterPreset>, name: &str, inputs: &[(String, String)]) {
+    let preset = ParameterPreset {
+        name: name.to_string(),
+        values: inputs.to_vec(),
+    };
+    match presets.iter_mut().find(|p| p.name == name) {
+        Some(existing) => *existing = preset,
+        None => presets.push(preset),
+    }
+}
+
+/// Presets dropdown for the top bar, shown when the active tab is a saved
+/// query with parameters and presets. Picking one runs the query with it.
+pub(crate) fn render_preset_menu(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) -> bool {
+    let Some(file) = tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.file_path.clone())
+    else {
+        return false;
+    };
+    let presets = presets_for(tabular, &file).to_vec();
+    if presets.is_empty()
+        || tabular
+            .query_tabs
+            .get(tabular.active_tab_index)
+            .is_none_or(|t| editor::extract_query_parameters(&t.content).is_empty())
+    {
+        return false;
+    }
+    let mut picked: Option<ParameterPreset> = None;
+    ui.menu_button("🔖 Presets", |ui| {
+        for preset in &presets {
+            let summary = preset
+                .values
+                .iter()
+                .map(|(name, value)| format!("{} = {}", name, value))
+                .collect::<Vec<_>>()
+                .join("\n");
+            if ui.button(&preset.name).on_hover_text(summary).clicked() {
+                picked = Some(preset.clone());
+                ui.close();
+            }
+        }
+    })
+    .response
+    .on_hover_text("Run with saved parameter values");
+    if let Some(preset) = picked {
+        tabular.pending_parameter_preset = Some(preset);
+        editor::execute_query(tabular);
+    }
+    true
+}
+
+/// Preset row of the parameter dialog: fill the inputs from a preset, save
+/// them under a name, or delete the named preset.
+pub(crate) fn render_preset_editor(tabular: &mut window_egui::Tabular, ui: &mut egui::Ui) {
+    let Some(file) = tabular.parameter_dialog_file.clone() else {
+        return;
+    };
+    let presets = presets_for(tabular, &file).to_vec();
+    let mut changed: Option<Vec<ParameterPreset>> = None;
+    ui.horizontal(|ui| {
+        ui.label("Preset:");
+        egui::ComboBox::from_id_salt("parameter_preset_pick")
+            .width(140.0)
+            .selected_text(if presets.is_empty() {
+                "(none saved)"
+            } else {
+                "Fill from…"
+            })
+            .show_ui(ui, |ui| {
+                for preset in &presets {
+                    if ui.selectable_label(false, &preset.name).clicked() {
+                        let params: Vec<String> = tabular
+                            .parameter_inputs
+                            .iter()
+                            .map(|(name, _)| name.clone())
+                            .collect();
+                        tabular.parameter_inputs = preset_inputs(Some(preset), &params);
+                        tabular.parameter_preset_name = preset.name.clone();
+                    }
+                }
+            });
+        ui.add(
+            egui::TextEdit::singleline(&mut tabular.parameter_preset_name)
+                .hint_text("Preset name")
+                .desired_width(120.0),
+        );
+        let name = tabular.parameter_preset_name.trim().to_string();
+        let exists = presets.iter().any(|p| p.name == name);
+        if ui
+            .add_enabled(!name.is_empty(), egui::Button::new("💾 Save"))
+            .on_hover_text("Save the values below as a preset of this query")
+            .clicked()
+        {
+            let mut updated = presets.clone();
+            upsert_preset(&mut updated, &name, &tabular.parameter_inputs);
+            changed = Some(updated);
+        }
+        if ui
+            .add_enabled(exists, egui::Button::new("🗑"))
+            .on_hover_text("Delete this preset")
+            .clicked()
+        {
+            changed = Some(presets.iter().filter(|p| p.name != name).cloned().collect());
+        }
+    });
+    if let Some(updated) = changed {
+        if let Err(e) = save_presets(&file, &updated) {
+            log::debug!("Failed to save parameter presets: {}", e);
+            return;
+        }
+        tabular.parameter_presets.insert(file, updated);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_fill_known_parameters_and_replace_by_name() {
+        let mut presets = Vec::new();
+        upsert_preset(
+            &mut presets,
+            "last week",
+            &[(":from".to_string(), "'2026-10-11'".to_string())],
+        );
+        upsert_preset(
+            &mut presets,
+            "last week",
+            &[
+                (":from".to_string(), "'2026-10-10'".to_string()),
+                (":tenant".to_string(), "7".to_string()),
+            ],
+        );
+        assert_eq!(presets.len(), 1);
+        let params = vec![":from".to_string(), ":to".to_string()];
+        assert_eq!(
+            preset_inputs(presets.first(), &params),
+            vec![
+                (":from".to_string(), "'2026-10-10'".to_string()),
+                (":to".to_string(), String::new()),
+            ]
+        );
+        assert_eq!(
+            presets_path(Path::new("/q/reports/weekly.sql")),
+            PathBuf::from("/q/reports/weekly.presets.json")
+        );
+    }
+}
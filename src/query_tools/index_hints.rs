@@ -0,0 +1,239 @@
+use super::select_star::{CLAUSE_END_WORDS, Token, TokenKind, from_sources, tokenize, unquote};
+
+/// A column a statement compares in its WHERE clause or a JOIN condition.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilteredColumn {
+    /// Unquoted names of the tables the column may belong to: one when it is
+    /// qualified (or the statement reads a single table), every FROM table
+    /// otherwise.
+    pub tables: Vec<String>,
+    pub column: String,
+}
+
+/// Words that end a JOIN condition.
+const CONDITION_END_WORDS: &[&str] = &[
+    "JOIN",
+    "INNER",
+    "LEFT",
+    "RIGHT",
+    "FULL",
+    "CROSS",
+    "NATURAL",
+    "STRAIGHT_JOIN",
+    "USING",
+];
+
+/// Words inside a condition that are never column names.
+const CONDITION_KEYWORDS: &[&str] = &[
+    "AND",
+    "OR",
+    "NOT",
+    "NULL",
+    "TRUE",
+    "FALSE",
+    "IS",
+    "IN",
+    "LIKE",
+    "ILIKE",
+    "BETWEEN",
+    "EXISTS",
+    "ANY",
+    "ALL",
+    "SOME",
+    "CASE",
+    "WHEN",
+    "THEN",
+    "ELSE",
+    "END",
+    "ESCAPE",
+    "COLLATE",
+    "INTERVAL",
+    "DATE",
+    "TIMESTAMP",
+    "CURRENT_DATE",
+    "CURRENT_TIMESTAMP",
+];
+
+/// Words that compare the column in front of them.
+const COMPARISON_WORDS: &[&str] = &["IN", "LIKE", "ILIKE", "BETWEEN", "IS", "NOT"];
+
+/// Columns compared in the top-level WHERE clause and JOIN ... ON conditions
+/// of `sql`, in order of appearance. Subqueries are skipped, and so is a
+/// statement whose FROM clause is not made of plain tables.
+pub fn filtered_columns(sql: &str) -> Vec<FilteredColumn> {
+    let tokens = tokenize(sql);
+    let text = |t: &Token| &sql[t.range.clone()];
+    let upper = |t: &Token| (t.kind == TokenKind::Word).then(|| text(t).to_ascii_uppercase());
+    let Some(from) = tokens
+        .iter()
+        .position(|t| t.depth == 0 && upper(t).as_deref() == Some("FROM"))
+    else {
+        return Vec::new();
+    };
+    let Some(sources) = from_sources(sql, &tokens, from) else {
+        return Vec::new();
+    };
+
+    let is_comparison_punct =
+        |t: &Token| matches!(t.kind, TokenKind::Punct(b'=' | b'<' | b'>' | b'!'));
+    let mut found: Vec<FilteredColumn> = Vec::new();
+    let mut in_condition = false;
+    let mut i = from + 1;
+    while let Some(token) = tokens.get(i) {
+        if token.depth == 0 {
+            let word = upper(token);
+            if token.kind == TokenKind::Punct(b';') {
+                break;
+            }
+            match word.as_deref() {
+                Some("WHERE" | "ON") => {
+                    in_condition = true;
+                    i += 1;
+                    continue;
+                }
+                Some(w) if CLAUSE_END_WORDS.contains(&w) => in_condition = false,
+                // `LEFT JOIN`, but not `LEFT(name, 3)`
+                Some(w)
+                    if CONDITION_END_WORDS.contains(&w)
+                        && !tokens
+                            .get(i + 1)
+                            .is_some_and(|t| t.kind == TokenKind::Punct(b'(')) =>
+                {
+                    in_condition = false;
+                }
+                _ if token.kind == TokenKind::Punct(b',') => in_condition = false,
+                _ => {}
+            }
+        }
+        if !in_condition {
+            i += 1;
+            continue;
+        }
+        // `(SELECT ...)`: its columns belong to other tables
+        if token.kind == TokenKind::Punct(b'(')
+            && tokens
+                .get(i + 1)
+                .and_then(upper)
+                .is_some_and(|w| w == "SELECT" || w == "WITH")
+        {
+            let depth = token.depth;
+            i += 1;
+            while tokens
+                .get(i)
+                .is_some_and(|t| !(t.depth == depth && t.kind == TokenKind::Punct(b')')))
+            {
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+        if !matches!(token.kind, TokenKind::Word | TokenKind::QuotedIdent) {
+            i += 1;
+            continue;
+        }
+
+        // qualifier.column (or schema.table.column)
+        let start = i;
+        let mut segments = vec![token];
+        while tokens
+            .get(i + 1)
+            .is_some_and(|t| t.kind == TokenKind::Punct(b'.'))
+            && let Some(next) = tokens
+                .get(i + 2)
+                .filter(|t| matches!(t.kind, TokenKind::Word | TokenKind::QuotedIdent))
+        {
+            segments.push(next);
+            i += 2;
+        }
+        let next = tokens.get(i + 1);
+        i += 1;
+
+        let first = segments[0];
+        let plain_word = first.kind == TokenKind::Word && segments.len() == 1;
+        if plain_word
+            && (CONDITION_KEYWORDS.contains(&text(first).to_ascii_uppercase().as_str())
+                || text(first).starts_with(|c: char| c.is_ascii_digit() || c == '$'))
+        {
+            continue;
+        }
+        // Function calls and bind parameters (`:id`, `@id`)
+        if next.is_some_and(|t| t.kind == TokenKind::Punct(b'('))
+            || start
+                .checked_sub(1)
+                .and_then(|p| tokens.get(p))
+                .is_some_and(|t| matches!(t.kind, TokenKind::Punct(b':' | b'@')))
+        {
+            continue;
+        }
+        let compared_after = next.is_some_and(|t| {
+            is_comparison_punct(t)
+                || upper(t).is_some_and(|w| COMPARISON_WORDS.contains(&w.as_str()))
+        });
+        let compared_before = start
+            .checked_sub(1)
+            .and_then(|p| tokens.get(p))
+            .is_some_and(is_comparison_punct);
+        if !(compared_after || compared_before) {
+            continue;
+        }
+
+        let column = unquote(text(segments[segments.len() - 1])).to_string();
+        let tables: Vec<String> = if segments.len() >= 2 {
+            let qualifier = unquote(text(segments[segments.len() - 2]));
+            match sources.iter().find(|s| {
+                unquote(&s.qualifier).eq_ignore_ascii_case(qualifier)
+                    || s.table.eq_ignore_ascii_case(qualifier)
+            }) {
+                Some(source) => vec![source.table.clone()],
+                None => continue,
+            }
+        } else {
+            sources.iter().map(|s| s.table.clone()).collect()
+        };
+        if !found
+            .iter()
+            .any(|f| f.tables == tables && f.column.eq_ignore_ascii_case(&column))
+        {
+            found.push(FilteredColumn { tables, column });
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn col(tables: &[&str], column: &str) -> FilteredColumn {
+        FilteredColumn {
+            tables: tables.iter().map(|t| t.to_string()).collect(),
+            column: column.to_string(),
+        }
+    }
+
+    #[test]
+    fn finds_where_and_join_columns() {
+        let sql = "SELECT o.id, c.name FROM orders o \
+                   JOIN \"customers\" AS c ON c.id = o.customer_id \
+                   WHERE o.status IN ('open', 'held') AND lower(c.email) = :email \
+                   AND o.total > 10 AND o.id NOT IN (SELECT order_id FROM refunds WHERE reason = 'x') \
+                   ORDER BY o.created_at";
+        assert_eq!(
+            filtered_columns(sql),
+            vec![
+                col(&["customers"], "id"),
+                col(&["orders"], "customer_id"),
+                col(&["orders"], "status"),
+                col(&["orders"], "total"),
+                col(&["orders"], "id"),
+            ]
+        );
+        // Unqualified columns may come from any FROM table
+        assert_eq!(
+            filtered_columns("select * from a, b where x = 1"),
+            vec![col(&["a", "b"], "x")]
+        );
+        assert!(filtered_columns("SELECT * FROM (SELECT 1) t WHERE t.x = 1").is_empty());
+        assert!(filtered_columns("UPDATE t SET a = 1").is_empty());
+    }
+}
@@ -1,6 +1,14 @@
 use sqlformat::{FormatOptions, Indent};
 use std::ops::Range;
 
+mod index_hints;
+mod params;
+mod select_star;
+
+pub use index_hints::*;
+pub use params::*;
+pub use select_star::*;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum LintSeverity {
     Info,
@@ -183,6 +191,146 @@ fn find_case_insensitive(haystack: &str, needle: &str) -> Option<usize> {
     hay_lower.find(&needle_lower)
 }
 
+/// Byte offset of `keyword` (upper case, single-spaced) in `upper` as a whole
+/// word; any run of whitespace matches a space inside the keyword.
+fn find_keyword(upper: &str, keyword: &str) -> Option<usize> {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let words: Vec<&str> = keyword.split(' ').collect();
+    let mut from = 0;
+    while let Some(pos) = upper[from..].find(words[0]).map(|p| p + from) {
+        from = pos + words[0].len();
+        if upper[..pos].chars().next_back().is_some_and(is_word) {
+            continue;
+        }
+        let mut end = from;
+        let mut matched = true;
+        for word in &words[1..] {
+            let rest = &upper[end..];
+            let gap = rest.len() - rest.trim_start().len();
+            if gap == 0 || !rest[gap..].starts_with(word) {
+                matched = false;
+                break;
+            }
+            end += gap + word.len();
+        }
+        if matched && !upper[end..].chars().next().is_some_and(is_word) {
+            return Some(pos);
+        }
+    }
+    None
+}
+
+/// Aggregate call such as `COUNT(` or `SUM (`.
+fn has_aggregate(upper: &str) -> bool {
+    ["COUNT", "SUM", "AVG", "MIN", "MAX"].iter().any(|f| {
+        find_keyword(upper, f).is_some_and(|pos| upper[pos + f.len()..].trim_start().starts_with('('))
+    })
+}
+
+/// A top-level SELECT from a table with no row bound and no aggregate may
+/// scan and return a whole table.
+fn lint_missing_limit(upper: &str) -> Option<LintMessage> {
+    if !upper.starts_with("SELECT") || find_keyword(upper, "FROM").is_none() {
+        return None;
+    }
+    let bounded = ["LIMIT", "TOP", "FETCH FIRST", "GROUP BY"]
+        .iter()
+        .any(|kw| find_keyword(upper, kw).is_some());
+    if bounded || has_aggregate(upper) {
+        return None;
+    }
+    Some(LintMessage {
+        severity: LintSeverity::Info,
+        message: "SELECT without LIMIT may scan and return the whole table.".to_string(),
+        span: Some(0.."SELECT".len()),
+        hint: Some("Add a LIMIT (or TOP / FETCH FIRST) while exploring data.".to_string()),
+    })
+}
+
+/// JOINs with neither ON nor USING pair every row of both sides. CROSS and
+/// NATURAL joins say so on purpose and are skipped. Parentheses are followed
+/// so a subquery's clauses do not end the join it sits in.
+fn lint_cartesian_joins(upper: &str) -> Vec<LintMessage> {
+    let bytes = upper.as_bytes();
+    // Every word with its parenthesis depth; `;` ends a statement. Quoted
+    // text and comments are skipped.
+    let mut words: Vec<(&str, usize, i32)> = Vec::new();
+    let mut depth = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                // A doubled quote closes and reopens, which skips the same text
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    i += 1;
+                }
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = upper[i + 2..].find("*/").map_or(bytes.len(), |end| i + 2 + end + 1);
+            }
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            b';' => words.push((";", i, depth)),
+            b if b.is_ascii_alphanumeric() || b == b'_' => {
+                let start = i;
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                words.push((&upper[start..i], start, depth));
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let ends_join = |w: &str| {
+        matches!(
+            w,
+            "JOIN" | "WHERE" | "GROUP" | "ORDER" | "HAVING" | "LIMIT" | "UNION" | "EXCEPT"
+                | "INTERSECT" | "WINDOW" | ";"
+        )
+    };
+    let mut messages = Vec::new();
+    for (idx, &(word, start, depth)) in words.iter().enumerate() {
+        if word != "JOIN" {
+            continue;
+        }
+        let explicit = words[..idx]
+            .iter()
+            .rev()
+            .map(|(w, _, _)| *w)
+            .find(|w| !matches!(*w, "INNER" | "LEFT" | "RIGHT" | "FULL" | "OUTER"))
+            .is_some_and(|w| matches!(w, "CROSS" | "NATURAL"));
+        if explicit {
+            continue;
+        }
+        let has_condition = words[idx + 1..]
+            .iter()
+            .take_while(|(w, _, d)| *d > depth || (*d == depth && !ends_join(w)))
+            .any(|(w, _, d)| *d == depth && matches!(*w, "ON" | "USING"));
+        if !has_condition {
+            messages.push(LintMessage {
+                severity: LintSeverity::Warning,
+                message: "JOIN without ON or USING returns every pairing of rows (cartesian product)."
+                    .to_string(),
+                span: Some(start..start + "JOIN".len()),
+                hint: Some(
+                    "Add an ON condition, or write CROSS JOIN if every pairing is intended."
+                        .to_string(),
+                ),
+            });
+        }
+    }
+    messages
+}
+
 pub fn lint_sql(sql: &str) -> Vec<LintMessage> {
     let mut messages = Vec::new();
     let trimmed = sql.trim();
@@ -195,7 +343,9 @@ pub fn lint_sql(sql: &str) -> Vec<LintMessage> {
             severity: LintSeverity::Warning,
             message: "Avoid SELECT * to minimize payload and leverage indexes.".to_string(),
             span: Some(idx..idx + "SELECT *".len()),
-            hint: Some("Enumerate the columns you actually need.".to_string()),
+            hint: Some(
+                "Enumerate the columns you actually need (Query: Expand SELECT *).".to_string(),
+            ),
         });
     }
 
@@ -227,55 +377,30 @@ pub fn lint_sql(sql: &str) -> Vec<LintMessage> {
             hint: Some("Consider DROP TABLE IF EXISTS ...".to_string()),
         });
     }
+    messages.extend(lint_missing_limit(&upper));
+    messages.extend(lint_cartesian_joins(&upper));
 
+    // Spans were found in the trimmed text; point them into `sql`
+    let lead = sql.len() - sql.trim_start().len();
+    for span in messages.iter_mut().filter_map(|m| m.span.as_mut()) {
+        *span = span.start + lead..span.end + lead;
+    }
     messages
 }
 
+/// Format `sql` with the app's sqlformat options. Returns `None` for blank
+/// input or when formatting would not change anything.
 pub fn format_sql(sql: &str) -> Option<String> {
     let trimmed = sql.trim();
     if trimmed.is_empty() {
         return None;
     }
 
-    let mut tokens = trimmed.split_whitespace().peekable();
-    let mut lines: Vec<String> = Vec::new();
-    let mut current = String::new();
-
-    while let Some(tok) = tokens.next() {
-        let mut token = tok.to_string();
-        let mut upper = token.to_ascii_uppercase();
-        if matches!(
-            upper.as_str(),
-            "LEFT" | "RIGHT" | "INNER" | "OUTER" | "FULL" | "CROSS"
-        ) && let Some(next) = tokens.peek()
-            && next.eq_ignore_ascii_case("JOIN")
-        {
-            let join = tokens.next().unwrap();
-            token = format!("{} {}", tok, join);
-            upper = "JOIN".to_string();
-        }
-
-        let break_before = matches!(
-            upper.as_str(),
-            "SELECT" | "FROM" | "WHERE" | "GROUP" | "ORDER" | "HAVING" | "LIMIT" | "JOIN"
-        );
-
-        if break_before && !current.is_empty() {
-            lines.push(current.trim_end().to_string());
-            current.clear();
-        }
-
-        if !current.is_empty() {
-            current.push(' ');
-        }
-        current.push_str(&token);
-    }
-
-    if !current.trim().is_empty() {
-        lines.push(current.trim_end().to_string());
-    }
-
-    let formatted = lines.join("\n").trim().to_string();
+    let formatted = sqlformat::format(
+        trimmed,
+        &sqlformat::QueryParams::None,
+        &default_sqlformat_options(),
+    );
     if formatted == trimmed {
         None
     } else {
@@ -297,3 +422,98 @@ pub fn default_sqlformat_options() -> FormatOptions<'static> {
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing_limit(sql: &str) -> Option<LintMessage> {
+        lint_sql(sql)
+            .into_iter()
+            .find(|m| m.message.contains("without LIMIT"))
+    }
+
+    #[test]
+    fn missing_limit_is_flagged_on_the_select_keyword_unless_bounded() {
+        let sql = "  select id, name from users where active = 1";
+        let msg = missing_limit(sql).expect("flagged");
+        assert_eq!(msg.severity, LintSeverity::Info);
+        assert!(msg.hint.is_some_and(|h| h.contains("LIMIT")));
+        assert_eq!(&sql[msg.span.unwrap()], "select");
+
+        for bounded in [
+            "SELECT id FROM users LIMIT 10",
+            "select top 5 id from users",
+            "select id from users order by id fetch  first 10 rows only",
+            "select count(*) from users",
+            "select max (id) from users",
+            "select status from users group\nby status",
+            "select 1",
+            "update users set a = 1 where id = 2",
+        ] {
+            assert!(missing_limit(bounded).is_none(), "{bounded}");
+        }
+        // Keywords only count as whole words
+        assert!(missing_limit("select credit_limit, stop_at, discount from orders").is_some());
+    }
+
+    fn cartesian_joins(sql: &str) -> Vec<LintMessage> {
+        lint_sql(sql)
+            .into_iter()
+            .filter(|m| m.message.contains("cartesian"))
+            .collect()
+    }
+
+    #[test]
+    fn join_without_on_is_flagged_on_the_join_keyword() {
+        let sql = " select o.id, c.name from orders o\n  left outer\n join customers c\nwhere o.total > 10";
+        let messages = cartesian_joins(sql);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].severity, LintSeverity::Warning);
+        assert!(messages[0].hint.as_ref().is_some_and(|h| h.contains("ON")));
+        assert_eq!(&sql[messages[0].span.clone().unwrap()], "join");
+
+        // The second JOIN lacks ON even though the first has one
+        let chained = "SELECT * FROM a JOIN b ON a.id = b.a_id INNER JOIN c WHERE c.x = 1";
+        assert_eq!(cartesian_joins(chained).len(), 1);
+    }
+
+    #[test]
+    fn cross_natural_using_and_subquery_joins_are_not_flagged() {
+        for sql in [
+            "SELECT * FROM sizes CROSS JOIN colors",
+            "SELECT * FROM a NATURAL LEFT JOIN b",
+            "SELECT * FROM orders JOIN customers USING (customer_id)",
+            "SELECT * FROM a JOIN (SELECT id FROM b WHERE b.x = 1) s\nON s.id = a.id",
+            "SELECT * FROM a JOIN b ON a.id = b.id; SELECT 1",
+            // Quoted text and comments are not SQL
+            "SELECT * FROM a JOIN b ON a.note = 'x JOIN y' AND a.id = b.id",
+            "SELECT * FROM a JOIN b -- old: JOIN c\nON a.id = b.id",
+            "SELECT * FROM a /* ( JOIN c */ JOIN b ON a.id = b.id",
+            "SELECT * FROM a JOIN b ON a.tag = ')' AND a.id = b.id",
+        ] {
+            assert!(cartesian_joins(sql).is_empty(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn format_sql_indents_subqueries_and_ctes() {
+        let subquery = "select a, b from (select id as a, name as b from users where active = 1) t where a > 2";
+        assert_eq!(
+            format_sql(subquery).unwrap(),
+            "SELECT a, b\nFROM (\n      SELECT id AS a, name AS b\n      FROM users\n      WHERE active = 1\n) t\nWHERE a > 2"
+        );
+
+        let cte = "with recent as (select id from orders where created_at > now()) \
+                   select * from recent r join users u on u.id = r.id";
+        assert_eq!(
+            format_sql(cte).unwrap(),
+            "WITH\nrecent AS (\n      SELECT id\n      FROM orders\n      WHERE created_at > NOW())\n\
+             SELECT *\nFROM recent r\nJOIN users u ON u.id = r.id"
+        );
+
+        assert_eq!(format_sql("select 1; select 2").unwrap(), "SELECT 1;\n\nSELECT 2");
+        assert_eq!(format_sql("SELECT 1"), None);
+        assert_eq!(format_sql("  \n "), None);
+    }
+}
@@ -0,0 +1,540 @@
+use crate::models::enums::DatabaseType;
+use std::ops::Range;
+
+/// A `:name`, `@name`, `$n` or `?` placeholder outside strings and comments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placeholder {
+    pub span: Range<usize>,
+    /// Name as the parameters panel lists it; `?` placeholders are numbered.
+    pub name: String,
+}
+
+/// Placeholders of `sql` in text order. `@name`s the script declares or
+/// assigns itself are variables rather than parameters and are left out,
+/// everywhere they appear: `DECLARE @a INT, @b INT`, `SET @x`, `@x :=`,
+/// `SELECT @x = col`, `INTO @a, @b` and the parameters of a
+/// `CREATE PROCEDURE` / `CREATE FUNCTION` signature. Named arguments of
+/// `EXEC p @id = …` are skipped where they appear.
+pub fn find_placeholders(sql: &str) -> Vec<Placeholder> {
+    let bytes = sql.as_bytes();
+    let len = bytes.len();
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut found: Vec<Placeholder> = Vec::new();
+    let mut distinct: Vec<String> = Vec::new();
+    let mut variables: Vec<String> = Vec::new();
+    let mut scope = VariableScope::default();
+    let mut i = 0;
+
+    while i < len {
+        let b = bytes[i];
+        let next_b = bytes.get(i + 1).copied();
+        let prev_b = i.checked_sub(1).map(|p| bytes[p]);
+
+        match b {
+            b'-' if next_b == Some(b'-') => {
+                i = sql[i..].find('\n').map_or(len, |p| i + p);
+                continue;
+            }
+            b'#' => {
+                i = sql[i..].find('\n').map_or(len, |p| i + p);
+                continue;
+            }
+            b'/' if next_b == Some(b'*') => {
+                i = sql[i + 2..].find("*/").map_or(len, |p| i + 2 + p + 2);
+                continue;
+            }
+            b'\'' | b'"' | b'`' => {
+                i = skip_quoted(bytes, i);
+                scope.last = Token::Other;
+                continue;
+            }
+            _ => {}
+        }
+
+        let start = i;
+        let name = if b == b':' && next_b == Some(b':') {
+            i += 2;
+            scope.last = Token::Other;
+            continue;
+        } else if (b == b':' || b == b'@')
+            && next_b.is_some_and(|c| c.is_ascii_alphabetic() || c == b'_')
+            && !prev_b.is_some_and(|p| is_ident(p) || p == b'@')
+        {
+            i += 1;
+            while i < len && is_ident(bytes[i]) {
+                i += 1;
+            }
+            if b == b'@' {
+                let after = sql[i..].trim_start();
+                let assigned = after.starts_with('=') && !after.starts_with("==");
+                let usage = if after.starts_with(":=") {
+                    VariableUse::Variable
+                } else {
+                    scope.classify(assigned)
+                };
+                scope.last = Token::Other;
+                match usage {
+                    VariableUse::Variable => {
+                        variables.push(sql[start..i].to_string());
+                        continue;
+                    }
+                    VariableUse::NamedArgument => continue,
+                    VariableUse::Parameter => {}
+                }
+            }
+            sql[start..i].to_string()
+        } else if b == b'$' && next_b.is_some_and(|c| c.is_ascii_digit()) {
+            i += 1;
+            while i < len && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            sql[start..i].to_string()
+        } else if b == b'?' {
+            i += 1;
+            format!("? (Param {})", distinct.len() + 1)
+        } else if b.is_ascii_alphabetic() || b == b'_' {
+            while i < len && (is_ident(bytes[i]) || bytes[i] == b'$') {
+                i += 1;
+            }
+            scope.word(&sql[start..i].to_ascii_uppercase());
+            continue;
+        } else {
+            i += 1;
+            if !b.is_ascii_whitespace() {
+                scope.punct(b);
+            }
+            continue;
+        };
+
+        if !distinct.contains(&name) {
+            distinct.push(name.clone());
+        }
+        found.push(Placeholder {
+            span: start..i,
+            name,
+        });
+        scope.last = Token::Other;
+    }
+
+    found.retain(|p| !variables.contains(&p.name));
+    found
+}
+
+/// Index just past the string or quoted identifier opening at `start`.
+fn skip_quoted(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if quote != b'`' => i += 2,
+            b if b == quote => {
+                if bytes.get(i + 1) == Some(&quote) {
+                    i += 2;
+                } else {
+                    return i + 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Words that start a new statement and so end a `DECLARE` list in T-SQL
+/// scripts without semicolons.
+const STATEMENT_KEYWORDS: &[&str] = &[
+    "SELECT", "INSERT", "UPDATE", "DELETE", "MERGE", "SET", "IF", "WHILE", "BEGIN", "END",
+    "EXEC", "EXECUTE", "PRINT", "RETURN", "OPEN", "FETCH", "CLOSE", "DEALLOCATE", "WITH",
+];
+
+#[derive(Clone, Debug, Default, PartialEq)]
+enum Token {
+    #[default]
+    Other,
+    Word(String),
+    Punct(u8),
+}
+
+/// List whose items are names the script defines.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum VariableList {
+    #[default]
+    None,
+    /// `DECLARE @a INT, @b INT`
+    Declare,
+    /// `SELECT … INTO @a, @b`, `FETCH … INTO @a, @b`
+    Into,
+    /// `CREATE PROCEDURE p @id INT, …` / `CREATE FUNCTION f(@id INT)`
+    Signature,
+}
+
+enum VariableUse {
+    Variable,
+    NamedArgument,
+    Parameter,
+}
+
+/// What the scanner has seen of the current statement, enough to tell the
+/// script's own `@variables` from parameters.
+#[derive(Default)]
+struct VariableScope {
+    last: Token,
+    depth: usize,
+    list: VariableList,
+    list_depth: usize,
+    routine_header: bool,
+    exec: bool,
+}
+
+impl VariableScope {
+    fn word(&mut self, word: &str) {
+        match word {
+            "DECLARE" => self.open(VariableList::Declare),
+            "INTO" => self.open(VariableList::Into),
+            "CREATE" | "ALTER" => self.routine_header = true,
+            "PROC" | "PROCEDURE" | "FUNCTION" if self.routine_header => {
+                self.open(VariableList::Signature)
+            }
+            _ if self.depth <= self.list_depth => match self.list {
+                VariableList::Into => self.list = VariableList::None,
+                VariableList::Declare if STATEMENT_KEYWORDS.contains(&word) => {
+                    self.list = VariableList::None
+                }
+                VariableList::Signature if matches!(word, "AS" | "RETURNS" | "BEGIN") => {
+                    self.list = VariableList::None;
+                    self.routine_header = false;
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        if matches!(word, "EXEC" | "EXECUTE") {
+            self.exec = true;
+        }
+        self.last = Token::Word(word.to_string());
+    }
+
+    fn punct(&mut self, b: u8) {
+        match b {
+            b'(' => self.depth += 1,
+            b')' => self.depth = self.depth.saturating_sub(1),
+            b';' => *self = VariableScope::default(),
+            _ => {}
+        }
+        self.last = Token::Punct(b);
+    }
+
+    fn open(&mut self, list: VariableList) {
+        self.list = list;
+        self.list_depth = self.depth;
+    }
+
+    /// Classify the `@name` at the current position; `assigned` when `=`
+    /// follows it.
+    fn classify(&self, assigned: bool) -> VariableUse {
+        let after_word = |w: &str| self.last == Token::Word(w.to_string());
+        let after_comma = self.last == Token::Punct(b',');
+        let list_item = self.depth == self.list_depth
+            && match self.list {
+                VariableList::Declare => after_comma || after_word("DECLARE"),
+                VariableList::Into => after_comma || after_word("INTO"),
+                _ => false,
+            };
+        let signature = self.list == VariableList::Signature
+            && self.depth >= self.list_depth
+            && self.last != Token::Punct(b'=');
+        if list_item || signature || after_word("SET") {
+            VariableUse::Variable
+        } else if assigned && self.exec && !matches!(self.last, Token::Punct(b'=')) {
+            VariableUse::NamedArgument
+        } else if assigned && (after_comma || after_word("SELECT")) {
+            VariableUse::Variable
+        } else {
+            VariableUse::Parameter
+        }
+    }
+}
+
+/// A parameter value as typed, bound with the type it reads as: `NULL`,
+/// `true`/`false` and numbers are typed, anything else is text. Single quotes
+/// around a value force text, so `'42'` is the string `42`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamValue {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl ParamValue {
+    pub fn parse(raw: &str) -> Self {
+        let trimmed = raw.trim();
+        if trimmed.len() >= 2 && trimmed.starts_with('\'') && trimmed.ends_with('\'') {
+            return ParamValue::Text(trimmed[1..trimmed.len() - 1].replace("''", "'"));
+        }
+        if trimmed.eq_ignore_ascii_case("null") {
+            ParamValue::Null
+        } else if trimmed.eq_ignore_ascii_case("true") {
+            ParamValue::Bool(true)
+        } else if trimmed.eq_ignore_ascii_case("false") {
+            ParamValue::Bool(false)
+        } else if let Ok(n) = trimmed.parse::<i64>() {
+            ParamValue::Int(n)
+        } else if let Some(f) = trimmed.parse::<f64>().ok().filter(|f| {
+            f.is_finite() && trimmed.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.')
+        }) {
+            ParamValue::Float(f)
+        } else {
+            ParamValue::Text(raw.to_string())
+        }
+    }
+
+    /// The value as text, for drivers that bind everything as strings.
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            ParamValue::Null => None,
+            ParamValue::Bool(b) => Some(b.to_string()),
+            ParamValue::Int(n) => Some(n.to_string()),
+            ParamValue::Float(f) => Some(f.to_string()),
+            ParamValue::Text(s) => Some(s.clone()),
+        }
+    }
+}
+
+/// How the placeholders of a query reach its database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamTarget {
+    /// Bound natively, placeholders rewritten as `$1`, `$2`, ... (PostgreSQL).
+    Numbered,
+    /// Bound natively, one `?` per occurrence (MySQL, SQLite).
+    Positional,
+    /// Bound natively, placeholders rewritten as `@p1`, `@p2`, ... (SQL Server).
+    AtNumbered,
+    /// Written into the text as SQL literals, for paths without binding.
+    SqlLiteral,
+    /// Written into a whitespace-separated Redis command.
+    RedisArgument,
+    /// Written into a MongoDB command as JSON values.
+    JsonLiteral,
+}
+
+impl ParamTarget {
+    pub fn for_database(db_type: &DatabaseType) -> Self {
+        match db_type {
+            DatabaseType::PostgreSQL => ParamTarget::Numbered,
+            DatabaseType::MySQL | DatabaseType::SQLite => ParamTarget::Positional,
+            DatabaseType::MsSQL => ParamTarget::AtNumbered,
+            DatabaseType::Redis => ParamTarget::RedisArgument,
+            DatabaseType::MongoDB => ParamTarget::JsonLiteral,
+            DatabaseType::ApiHttp => ParamTarget::SqlLiteral,
+        }
+    }
+
+    pub fn binds_natively(self) -> bool {
+        matches!(
+            self,
+            ParamTarget::Numbered | ParamTarget::Positional | ParamTarget::AtNumbered
+        )
+    }
+}
+
+/// A statement with its placeholders rewritten for native binding and the
+/// values to bind, in bind order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundQuery {
+    pub sql: String,
+    pub values: Vec<ParamValue>,
+}
+
+fn input_value(inputs: &[(String, String)], name: &str) -> ParamValue {
+    inputs
+        .iter()
+        .find(|(n, _)| n == name)
+        .map_or(ParamValue::Null, |(_, v)| ParamValue::parse(v))
+}
+
+/// Rewrite the placeholders of `sql` into the native form of `target` and
+/// collect the values from `inputs` (name, raw value). Numbered targets give
+/// a repeated name a single slot; positional ones bind once per occurrence.
+/// Placeholders without an input bind NULL.
+pub fn bind_query(sql: &str, inputs: &[(String, String)], target: ParamTarget) -> BoundQuery {
+    let mut out = String::with_capacity(sql.len());
+    let mut values = Vec::new();
+    let mut slots: Vec<String> = Vec::new();
+    let mut last = 0;
+    for placeholder in find_placeholders(sql) {
+        out.push_str(&sql[last..placeholder.span.start]);
+        last = placeholder.span.end;
+        if target == ParamTarget::Positional {
+            out.push('?');
+            values.push(input_value(inputs, &placeholder.name));
+            continue;
+        }
+        let slot = match slots.iter().position(|s| *s == placeholder.name) {
+            Some(pos) => pos + 1,
+            None => {
+                values.push(input_value(inputs, &placeholder.name));
+                slots.push(placeholder.name.clone());
+                slots.len()
+            }
+        };
+        let prefix = if target == ParamTarget::AtNumbered { "@p" } else { "$" };
+        out.push_str(&format!("{}{}", prefix, slot));
+    }
+    out.push_str(&sql[last..]);
+    BoundQuery { sql: out, values }
+}
+
+/// Wrap each `$n` of a bound PostgreSQL statement in a cast to the type the
+/// server inferred for it, so values bound as text still compare as numbers,
+/// dates and so on.
+pub fn cast_numbered_params(sql: &str, types: &[String]) -> String {
+    let mut out = String::with_capacity(sql.len());
+    let mut last = 0;
+    for placeholder in find_placeholders(sql) {
+        let Some(ty) = placeholder.name[1..]
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| types.get(n.wrapping_sub(1)))
+            .filter(|ty| !ty.is_empty())
+        else {
+            continue;
+        };
+        out.push_str(&sql[last..placeholder.span.start]);
+        out.push_str(&format!("CAST({} AS {})", placeholder.name, ty));
+        last = placeholder.span.end;
+    }
+    out.push_str(&sql[last..]);
+    out
+}
+
+/// Write the values from `inputs` into `sql` as literals of `target`, for
+/// queries that cannot be bound natively. Values are quoted so they cannot
+/// change the statement; Redis arguments cannot be quoted, so a value that
+/// would split into several arguments is refused.
+pub fn substitute_params(
+    sql: &str,
+    inputs: &[(String, String)],
+    target: ParamTarget,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(sql.len());
+    let mut last = 0;
+    for placeholder in find_placeholders(sql) {
+        let value = input_value(inputs, &placeholder.name);
+        let literal = match (target, value) {
+            (ParamTarget::RedisArgument, value) => {
+                let text = value.as_text().unwrap_or_default();
+                if text.is_empty() || text.chars().any(char::is_whitespace) {
+                    return Err(format!(
+                        "{} must be a single word to be used in a Redis command",
+                        placeholder.name
+                    ));
+                }
+                text
+            }
+            (ParamTarget::JsonLiteral, ParamValue::Text(s)) => {
+                serde_json::Value::String(s).to_string()
+            }
+            (ParamTarget::JsonLiteral, ParamValue::Null) => "null".to_string(),
+            (_, ParamValue::Text(s)) => format!("'{}'", s.replace('\'', "''")),
+            (_, ParamValue::Null) => "NULL".to_string(),
+            (_, value) => value.as_text().unwrap_or_default(),
+        };
+        out.push_str(&sql[last..placeholder.span.start]);
+        out.push_str(&literal);
+        last = placeholder.span.end;
+    }
+    out.push_str(&sql[last..]);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(n, v)| (n.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn placeholders_skip_literals_casts_and_session_variables() {
+        let sql = "DECLARE @limit INT; SELECT ':skip', a::int FROM t \
+                   WHERE id = :id AND owner = @owner AND n < @limit AND k = $2 -- :note\n\
+                   AND key = 'user:1' AND u = :id";
+        let names: Vec<String> = find_placeholders(sql).into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec![":id", "@owner", "$2", ":id"]);
+    }
+
+    #[test]
+    fn placeholders_skip_tsql_declarations_assignments_and_signatures() {
+        let names = |sql: &str| -> Vec<String> {
+            find_placeholders(sql).into_iter().map(|p| p.name).collect()
+        };
+        assert_eq!(
+            names("DECLARE @a INT = (SELECT MAX(id) FROM t), @b INT\nSELECT @a, @b, @c"),
+            vec!["@c"]
+        );
+        assert_eq!(names("SELECT @x = name, @y = id FROM t WHERE id = @id"), vec!["@id"]);
+        assert_eq!(
+            names("FETCH NEXT FROM c INTO @a, @b; INSERT INTO t (a) VALUES (@a, @v)"),
+            vec!["@v"]
+        );
+        assert_eq!(
+            names("CREATE PROCEDURE p @id INT, @name NVARCHAR(50) = NULL AS SELECT * FROM t WHERE id = @id AND owner = @name"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            names("CREATE FUNCTION f(@n INT) RETURNS INT AS BEGIN RETURN @n * @factor END"),
+            vec!["@factor"]
+        );
+        assert_eq!(names("EXEC p @id = @id, @mode = 2"), vec!["@id"]);
+        assert_eq!(names("SELECT * FROM t WHERE @flag = 1 AND (@r := @r + 1) < 5"), vec!["@flag"]);
+    }
+
+    #[test]
+    fn bind_query_rewrites_per_driver() {
+        let sql = "SELECT * FROM t WHERE a = :a AND b = @b AND c = :a";
+        let values = inputs(&[(":a", "7"), ("@b", "O'Brien")]);
+
+        let pg = bind_query(sql, &values, ParamTarget::Numbered);
+        assert_eq!(pg.sql, "SELECT * FROM t WHERE a = $1 AND b = $2 AND c = $1");
+        assert_eq!(
+            pg.values,
+            vec![ParamValue::Int(7), ParamValue::Text("O'Brien".to_string())]
+        );
+
+        let mysql = bind_query(sql, &values, ParamTarget::Positional);
+        assert_eq!(mysql.sql, "SELECT * FROM t WHERE a = ? AND b = ? AND c = ?");
+        assert_eq!(mysql.values.len(), 3);
+
+        let mssql = bind_query(sql, &values, ParamTarget::AtNumbered);
+        assert_eq!(mssql.sql, "SELECT * FROM t WHERE a = @p1 AND b = @p2 AND c = @p1");
+
+        assert_eq!(
+            cast_numbered_params(&pg.sql, &["INT4".to_string(), "TEXT".to_string()]),
+            "SELECT * FROM t WHERE a = CAST($1 AS INT4) AND b = CAST($2 AS TEXT) AND c = CAST($1 AS INT4)"
+        );
+    }
+
+    #[test]
+    fn substitution_quotes_values_and_refuses_split_redis_arguments() {
+        let values = inputs(&[(":name", "x' OR '1'='1"), (":n", "null")]);
+        assert_eq!(
+            substitute_params("SELECT :name, :n", &values, ParamTarget::SqlLiteral).unwrap(),
+            "SELECT 'x'' OR ''1''=''1', NULL"
+        );
+        assert_eq!(
+            substitute_params("GET :key", &inputs(&[(":key", "user:1")]), ParamTarget::RedisArgument)
+                .unwrap(),
+            "GET user:1"
+        );
+        assert!(
+            substitute_params("GET :key", &inputs(&[(":key", "a\r\nFLUSHALL")]), ParamTarget::RedisArgument)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,451 @@
+use crate::models::enums::DatabaseType;
+use std::ops::Range;
+
+/// A table the `*` of a SELECT draws columns from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StarSource {
+    /// Unquoted table name, without schema, for the column lookup.
+    pub table: String,
+    /// How the statement refers to the table: its alias, or the name as written.
+    pub qualifier: String,
+}
+
+/// The bare `*` of a SELECT list and the tables of its FROM clause.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectStar {
+    pub star: Range<usize>,
+    pub sources: Vec<StarSource>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TokenKind {
+    Word,
+    QuotedIdent,
+    Literal,
+    Punct(u8),
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Token {
+    pub(super) kind: TokenKind,
+    pub(super) range: Range<usize>,
+    pub(super) depth: usize,
+}
+
+/// Words that end a table reference instead of aliasing it.
+const NON_ALIAS_WORDS: &[&str] = &[
+    "JOIN",
+    "INNER",
+    "LEFT",
+    "RIGHT",
+    "FULL",
+    "CROSS",
+    "OUTER",
+    "NATURAL",
+    "STRAIGHT_JOIN",
+    "ON",
+    "USING",
+    "WHERE",
+    "GROUP",
+    "ORDER",
+    "HAVING",
+    "LIMIT",
+    "OFFSET",
+    "FETCH",
+    "UNION",
+    "EXCEPT",
+    "INTERSECT",
+    "WINDOW",
+    "FOR",
+    "WITH",
+    "LATERAL",
+];
+
+/// Words that end the FROM clause.
+pub(super) const CLAUSE_END_WORDS: &[&str] = &[
+    "WHERE",
+    "GROUP",
+    "ORDER",
+    "HAVING",
+    "LIMIT",
+    "OFFSET",
+    "FETCH",
+    "UNION",
+    "EXCEPT",
+    "INTERSECT",
+    "WINDOW",
+    "FOR",
+];
+
+/// Words that force quoting when used as a column name.
+const RESERVED_WORDS: &[&str] = &[
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "CHECK",
+    "COLUMN",
+    "CREATE",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "END",
+    "FROM",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INDEX",
+    "INSERT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LIKE",
+    "LIMIT",
+    "NOT",
+    "NULL",
+    "ON",
+    "OR",
+    "ORDER",
+    "PRIMARY",
+    "REFERENCES",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "THEN",
+    "TO",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USER",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WITH",
+];
+
+pub(super) fn tokenize(sql: &str) -> Vec<Token> {
+    let bytes = sql.as_bytes();
+    let len = bytes.len();
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < len {
+        let b = bytes[i];
+        let next = bytes.get(i + 1).copied();
+        if b.is_ascii_whitespace() {
+            i += 1;
+        } else if (b == b'-' && next == Some(b'-')) || b == b'#' {
+            while i < len && bytes[i] != b'\n' {
+                i += 1;
+            }
+        } else if b == b'/' && next == Some(b'*') {
+            i = sql[i + 2..].find("*/").map_or(len, |p| i + 2 + p + 2);
+        } else if matches!(b, b'\'' | b'"' | b'`' | b'[') {
+            let close = if b == b'[' { b']' } else { b };
+            let start = i;
+            i += 1;
+            while i < len {
+                if bytes[i] == close {
+                    // A doubled closing character is an escaped one.
+                    if bytes.get(i + 1) == Some(&close) && close != b']' {
+                        i += 2;
+                        continue;
+                    }
+                    break;
+                }
+                i += 1;
+            }
+            i = (i + 1).min(len);
+            let kind = if b == b'\'' {
+                TokenKind::Literal
+            } else {
+                TokenKind::QuotedIdent
+            };
+            tokens.push(Token {
+                kind,
+                range: start..i,
+                depth,
+            });
+        } else if b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80 {
+            let start = i;
+            while i < len
+                && (bytes[i].is_ascii_alphanumeric()
+                    || matches!(bytes[i], b'_' | b'$')
+                    || bytes[i] >= 0x80)
+            {
+                i += 1;
+            }
+            tokens.push(Token {
+                kind: TokenKind::Word,
+                range: start..i,
+                depth,
+            });
+        } else {
+            if b == b')' {
+                depth = depth.saturating_sub(1);
+            }
+            tokens.push(Token {
+                kind: TokenKind::Punct(b),
+                range: i..i + 1,
+                depth,
+            });
+            if b == b'(' {
+                depth += 1;
+            }
+            i += 1;
+        }
+    }
+    tokens
+}
+
+pub(super) fn unquote(ident: &str) -> &str {
+    let bytes = ident.as_bytes();
+    match (bytes.first(), bytes.last()) {
+        (Some(b'"'), Some(b'"')) | (Some(b'`'), Some(b'`')) | (Some(b'['), Some(b']'))
+            if ident.len() >= 2 =>
+        {
+            &ident[1..ident.len() - 1]
+        }
+        _ => ident,
+    }
+}
+
+/// Tables of the FROM clause whose `FROM` keyword is `tokens[from]`. `None`
+/// when the clause has a derived table or function instead of a table name.
+pub(super) fn from_sources(sql: &str, tokens: &[Token], from: usize) -> Option<Vec<StarSource>> {
+    let text = |t: &Token| &sql[t.range.clone()];
+    let is_word =
+        |t: &Token, word: &str| t.kind == TokenKind::Word && text(t).eq_ignore_ascii_case(word);
+    let mut sources = Vec::new();
+    let mut expect_table = true;
+    let mut j = from + 1;
+    while let Some(token) = tokens.get(j) {
+        if token.depth > 0 {
+            j += 1;
+            continue;
+        }
+        let word = (token.kind == TokenKind::Word).then(|| text(token).to_ascii_uppercase());
+        if token.kind == TokenKind::Punct(b';')
+            || word
+                .as_deref()
+                .is_some_and(|w| CLAUSE_END_WORDS.contains(&w))
+        {
+            break;
+        }
+        if token.kind == TokenKind::Punct(b',')
+            || word.as_deref() == Some("JOIN")
+            || word.as_deref() == Some("STRAIGHT_JOIN")
+        {
+            expect_table = true;
+            j += 1;
+            continue;
+        }
+        if !expect_table {
+            j += 1;
+            continue;
+        }
+        if !matches!(token.kind, TokenKind::Word | TokenKind::QuotedIdent)
+            || word.as_deref() == Some("LATERAL")
+        {
+            return None;
+        }
+        // schema.table: the last segment names the table.
+        let mut last = token;
+        while tokens
+            .get(j + 1)
+            .is_some_and(|t| t.kind == TokenKind::Punct(b'.'))
+            && let Some(next) = tokens.get(j + 2)
+        {
+            last = next;
+            j += 2;
+        }
+        if tokens
+            .get(j + 1)
+            .is_some_and(|t| t.kind == TokenKind::Punct(b'(') && t.depth == 0)
+        {
+            return None;
+        }
+        j += 1;
+        if tokens.get(j).is_some_and(|t| is_word(t, "AS")) {
+            j += 1;
+        }
+        let alias = tokens.get(j).filter(|t| {
+            t.depth == 0
+                && (t.kind == TokenKind::QuotedIdent
+                    || (t.kind == TokenKind::Word
+                        && !NON_ALIAS_WORDS.contains(&text(t).to_ascii_uppercase().as_str())))
+        });
+        if alias.is_some() {
+            j += 1;
+        }
+        sources.push(StarSource {
+            table: unquote(text(last)).to_string(),
+            qualifier: text(alias.unwrap_or(last)).to_string(),
+        });
+        expect_table = false;
+    }
+    (!sources.is_empty()).then_some(sources)
+}
+
+/// Locate the bare `*` in the top-level SELECT list of `sql` and the tables
+/// it expands to. `None` when there is no such `*`, or when the FROM clause
+/// has a derived table or function whose columns cannot be looked up.
+pub fn find_select_star(sql: &str) -> Option<SelectStar> {
+    let tokens = tokenize(sql);
+    let text = |t: &Token| &sql[t.range.clone()];
+    let is_word =
+        |t: &Token, word: &str| t.kind == TokenKind::Word && text(t).eq_ignore_ascii_case(word);
+
+    let select = tokens
+        .iter()
+        .position(|t| t.depth == 0 && is_word(t, "SELECT"))?;
+    let mut i = select + 1;
+    while tokens
+        .get(i)
+        .is_some_and(|t| is_word(t, "DISTINCT") || is_word(t, "ALL"))
+    {
+        i += 1;
+    }
+    if tokens.get(i).is_some_and(|t| is_word(t, "TOP")) {
+        // `TOP n` or `TOP (n)`
+        i += 1;
+        if tokens
+            .get(i)
+            .is_some_and(|t| t.kind == TokenKind::Punct(b'('))
+        {
+            while tokens
+                .get(i)
+                .is_some_and(|t| t.depth > 0 || t.kind == TokenKind::Punct(b'('))
+            {
+                i += 1;
+            }
+        }
+        i += 1;
+    }
+    let star = tokens.get(i).filter(|t| t.kind == TokenKind::Punct(b'*'))?;
+    let after = tokens.get(i + 1)?;
+    if !(is_word(after, "FROM") || after.kind == TokenKind::Punct(b',')) {
+        return None;
+    }
+
+    let from = i
+        + 1
+        + tokens[i + 1..]
+            .iter()
+            .position(|t| t.depth == 0 && is_word(t, "FROM"))?;
+    let sources = from_sources(sql, &tokens, from)?;
+    Some(SelectStar {
+        star: star.range.clone(),
+        sources,
+    })
+}
+
+/// `ident` quoted for `db_type` when it is not a plain identifier, is a
+/// reserved word, or (PostgreSQL) would be case-folded.
+pub fn quote_column_if_needed(ident: &str, db_type: &DatabaseType) -> String {
+    let plain = ident
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && ident.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let folded = matches!(db_type, DatabaseType::PostgreSQL)
+        && ident.chars().any(|c| c.is_ascii_uppercase());
+    let reserved = RESERVED_WORDS.contains(&ident.to_ascii_uppercase().as_str());
+    if plain && !folded && !reserved {
+        return ident.to_string();
+    }
+    match db_type {
+        DatabaseType::MySQL => format!("`{}`", ident.replace('`', "``")),
+        DatabaseType::MsSQL => format!("[{}]", ident.replace(']', "]]")),
+        _ => format!("\"{}\"", ident.replace('"', "\"\"")),
+    }
+}
+
+/// Comma-separated replacement for the `*`. Columns are qualified by each
+/// source's alias when the query reads from more than one table.
+pub fn expanded_column_list(
+    sources: &[(StarSource, Vec<String>)],
+    db_type: &DatabaseType,
+) -> String {
+    let qualify = sources.len() > 1;
+    sources
+        .iter()
+        .flat_map(|(source, columns)| {
+            columns.iter().map(move |column| {
+                let column = quote_column_if_needed(column, db_type);
+                if qualify {
+                    format!("{}.{}", source.qualifier, column)
+                } else {
+                    column
+                }
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(table: &str, qualifier: &str) -> StarSource {
+        StarSource {
+            table: table.to_string(),
+            qualifier: qualifier.to_string(),
+        }
+    }
+
+    #[test]
+    fn finds_star_and_aliased_sources() {
+        let sql = "SELECT DISTINCT *\nFROM public.users AS u\n  LEFT JOIN \"Orders\" o ON o.user_id = u.id, items\nWHERE u.id IN (SELECT 1 FROM x)";
+        let found = find_select_star(sql).unwrap();
+        assert_eq!(&sql[found.star.clone()], "*");
+        assert_eq!(found.star.start, 16);
+        assert_eq!(
+            found.sources,
+            vec![
+                source("users", "u"),
+                source("Orders", "o"),
+                source("items", "items"),
+            ]
+        );
+        let top = find_select_star("SELECT TOP (10) * FROM [dbo].[Log] ORDER BY id").unwrap();
+        assert_eq!(top.sources, vec![source("Log", "[Log]")]);
+
+        assert!(find_select_star("SELECT id, name FROM users").is_none());
+        assert!(find_select_star("SELECT COUNT(*) FROM users").is_none());
+        assert!(find_select_star("SELECT * FROM (SELECT 1) sub").is_none());
+        assert!(find_select_star("SELECT * FROM generate_series(1, 3)").is_none());
+    }
+
+    #[test]
+    fn expansion_quotes_and_qualifies() {
+        let single = [(
+            source("users", "users"),
+            vec!["id".to_string(), "order".to_string()],
+        )];
+        assert_eq!(
+            expanded_column_list(&single, &DatabaseType::MySQL),
+            "id, `order`"
+        );
+        let joined = [
+            (
+                source("users", "u"),
+                vec!["id".to_string(), "Name".to_string()],
+            ),
+            (source("orders", "o"), vec!["total amount".to_string()]),
+        ];
+        assert_eq!(
+            expanded_column_list(&joined, &DatabaseType::PostgreSQL),
+            "u.id, u.\"Name\", o.\"total amount\""
+        );
+    }
+}
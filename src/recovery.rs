@@ -0,0 +1,361 @@
+//! Crash recovery for unsaved editor buffers.
+//!
+//! Every few seconds the modified query tabs are mirrored into
+//! `<app data>/recovery/<session>-<tab>.json`; only buffers whose content
+//! changed since the last pass are written. A clean exit removes this
+//! session's files and stamps the `clean_shutdown` marker, so anything left
+//! behind that is newer than the marker comes from a crashed session.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{DateTime, Local};
+use eframe::egui;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::{directory, editor, models, window_egui};
+
+/// Minimum gap between two recovery passes; doubles as the edit debounce.
+const WRITE_INTERVAL: Duration = Duration::from_secs(3);
+const CLEAN_SHUTDOWN_MARKER: &str = "clean_shutdown";
+
+/// Snapshot of one tab as stored on disk.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecoveryEntry {
+    pub title: String,
+    pub content: String,
+    #[serde(default)]
+    pub file_path: Option<String>,
+    #[serde(default)]
+    pub connection_id: Option<i64>,
+    #[serde(default)]
+    pub database_name: Option<String>,
+    pub saved_at: DateTime<Local>,
+}
+
+/// A recovery file found on disk, for the restore dialog.
+#[derive(Clone, Debug)]
+pub struct RecoveryFile {
+    pub path: PathBuf,
+    pub entry: RecoveryEntry,
+    /// Written after the last clean shutdown, i.e. left behind by a crash.
+    pub from_crash: bool,
+}
+
+/// Per-session writer state kept on `Tabular`.
+pub struct RecoveryWriter {
+    session: String,
+    /// Content hash last written for each tab slot.
+    written: HashMap<usize, u64>,
+    last_pass: Instant,
+}
+
+impl Default for RecoveryWriter {
+    fn default() -> Self {
+        Self {
+            session: Local::now().format("%Y%m%d%H%M%S%3f").to_string(),
+            written: HashMap::new(),
+            last_pass: Instant::now(),
+        }
+    }
+}
+
+pub(crate) fn recovery_dir() -> PathBuf {
+    directory::get_app_data_dir().join("recovery")
+}
+
+fn content_hash(entry: &RecoveryEntry) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    entry.title.hash(&mut hasher);
+    entry.content.hash(&mut hasher);
+    entry.file_path.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn session_file(dir: &Path, session: &str, slot: usize) -> PathBuf {
+    dir.join(format!("{}-{}.json", session, slot))
+}
+
+/// Tabs worth protecting: edited, non-empty plain query buffers.
+fn snapshot(tab: &models::structs::QueryTab) -> Option<RecoveryEntry> {
+    let special = tab.diagram_state.is_some()
+        || tab.http_client_state.is_some()
+        || tab.redis_browser_state.is_some();
+    if special || !tab.is_modified || tab.content.trim().is_empty() {
+        return None;
+    }
+    Some(RecoveryEntry {
+        title: tab.title.clone(),
+        content: tab.content.clone(),
+        file_path: tab.file_path.clone(),
+        connection_id: tab.connection_id,
+        database_name: tab.database_name.clone(),
+        saved_at: Local::now(),
+    })
+}
+
+impl RecoveryWriter {
+    /// Mirror per-tab snapshots (indexed by tab slot) into `dir`, writing only
+    /// changed buffers and removing files for tabs that were saved or closed.
+    pub(crate) fn sync(&mut self, dir: &Path, snapshots: Vec<Option<RecoveryEntry>>) {
+        let mut live = HashMap::new();
+        for (slot, entry) in snapshots.into_iter().enumerate() {
+            let Some(entry) = entry else { continue };
+            let hash = content_hash(&entry);
+            live.insert(slot, hash);
+            if self.written.get(&slot) == Some(&hash) {
+                continue;
+            }
+            let Ok(json) = serde_json::to_string(&entry) else { continue };
+            if std::fs::create_dir_all(dir).is_ok()
+                && std::fs::write(session_file(dir, &self.session, slot), json).is_ok()
+            {
+                self.written.insert(slot, hash);
+            }
+        }
+        self.written.retain(|slot, _| {
+            let keep = live.contains_key(slot);
+            if !keep {
+                let _ = std::fs::remove_file(session_file(dir, &self.session, *slot));
+            }
+            keep
+        });
+    }
+
+    /// Remove this session's files and record a clean shutdown.
+    pub(crate) fn finish(&mut self, dir: &Path) {
+        for slot in self.written.drain().map(|(slot, _)| slot) {
+            let _ = std::fs::remove_file(session_file(dir, &self.session, slot));
+        }
+        if std::fs::create_dir_all(dir).is_ok() {
+            let _ = std::fs::write(dir.join(CLEAN_SHUTDOWN_MARKER), Local::now().to_rfc3339());
+        }
+    }
+}
+
+/// Run a recovery pass if the debounce interval has elapsed.
+pub(crate) fn tick(tabular: &mut window_egui::Tabular) {
+    if tabular.recovery_writer.last_pass.elapsed() < WRITE_INTERVAL {
+        return;
+    }
+    tabular.recovery_writer.last_pass = Instant::now();
+    let snapshots = tabular.query_tabs.iter().map(snapshot).collect();
+    tabular.recovery_writer.sync(&recovery_dir(), snapshots);
+}
+
+/// Recovery files left in `dir`, newest first. Files of the running session
+/// are skipped since their tabs are still open.
+pub(crate) fn list_recovery_files(dir: &Path, current_session: Option<&str>) -> Vec<RecoveryFile> {
+    let clean_at = std::fs::metadata(dir.join(CLEAN_SHUTDOWN_MARKER))
+        .and_then(|m| m.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<RecoveryFile> = read_dir
+        .flatten()
+        .filter_map(|e| {
+            let path = e.path();
+            let name = path.file_name()?.to_str()?;
+            if !name.ends_with(".json")
+                || current_session.is_some_and(|s| name.starts_with(&format!("{}-", s)))
+            {
+                return None;
+            }
+            let modified = e.metadata().and_then(|m| m.modified()).ok()?;
+            let entry: RecoveryEntry =
+                serde_json::from_str(&std::fs::read_to_string(&path).ok()?).ok()?;
+            Some(RecoveryFile {
+                path,
+                entry,
+                from_crash: modified > clean_at,
+            })
+        })
+        .collect();
+    files.sort_by_key(|f| std::cmp::Reverse(f.entry.saved_at));
+    files
+}
+
+/// Called once at startup: offer recovery when a crashed session left files.
+pub(crate) fn check_on_startup(tabular: &mut window_egui::Tabular) {
+    let files = list_recovery_files(&recovery_dir(), None);
+    if files.iter().any(|f| f.from_crash) {
+        debug!("♻️ Found {} recovery file(s) from a previous session", files.len());
+        tabular.recovery_files = files;
+        tabular.show_recovery_dialog = true;
+    }
+}
+
+/// Open the recovery browser from the command palette.
+pub(crate) fn open_browser(tabular: &mut window_egui::Tabular) {
+    tabular.recovery_files =
+        list_recovery_files(&recovery_dir(), Some(&tabular.recovery_writer.session));
+    tabular.show_recovery_dialog = true;
+}
+
+/// Reopen a recovered buffer as a modified tab and drop its file.
+fn restore(tabular: &mut window_egui::Tabular, file: &RecoveryFile) {
+    let entry = &file.entry;
+    editor::create_new_tab_with_connection_and_database(
+        tabular,
+        entry.title.clone(),
+        entry.content.clone(),
+        entry.connection_id,
+        entry.database_name.clone(),
+    );
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index) {
+        tab.file_path = entry.file_path.clone();
+        tab.is_modified = true;
+    }
+    let _ = std::fs::remove_file(&file.path);
+}
+
+pub(crate) fn render_recovery_dialog(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    if !tabular.show_recovery_dialog {
+        return;
+    }
+    let mut open = true;
+    let mut restore_idx: Option<usize> = None;
+    let mut discard_idx: Option<usize> = None;
+    let mut restore_all = false;
+    let mut discard_all = false;
+
+    egui::Window::new("♻️ Recover Unsaved Queries")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(520.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .open(&mut open)
+        .show(ctx, |ui| {
+            if tabular.recovery_files.is_empty() {
+                ui.label("No recovery files.");
+                return;
+            }
+            if tabular.recovery_files.iter().any(|f| f.from_crash) {
+                ui.label("Tabular did not shut down cleanly. These buffers had unsaved changes:");
+            } else {
+                ui.label("Recovery files kept from earlier sessions:");
+            }
+            ui.add_space(6.0);
+            egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                egui::Grid::new("recovery_files_grid")
+                    .striped(true)
+                    .num_columns(3)
+                    .show(ui, |ui| {
+                        for (idx, file) in tabular.recovery_files.iter().enumerate() {
+                            ui.vertical(|ui| {
+                                ui.horizontal(|ui| {
+                                    ui.strong(&file.entry.title);
+                                    if !file.from_crash {
+                                        ui.label(
+                                            egui::RichText::new("stale")
+                                                .size(11.0)
+                                                .color(egui::Color32::from_gray(120)),
+                                        );
+                                    }
+                                });
+                                let preview: String = file
+                                    .entry
+                                    .content
+                                    .lines()
+                                    .find(|l| !l.trim().is_empty())
+                                    .unwrap_or_default()
+                                    .chars()
+                                    .take(60)
+                                    .collect();
+                                ui.label(
+                                    egui::RichText::new(preview)
+                                        .monospace()
+                                        .size(11.0)
+                                        .color(egui::Color32::from_gray(120)),
+                                );
+                            });
+                            ui.label(file.entry.saved_at.format("%Y-%m-%d %H:%M:%S").to_string());
+                            ui.horizontal(|ui| {
+                                if ui.button("Restore").clicked() {
+                                    restore_idx = Some(idx);
+                                }
+                                if ui.button("🗑").on_hover_text("Discard").clicked() {
+                                    discard_idx = Some(idx);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.add_space(8.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Restore All").clicked() {
+                    restore_all = true;
+                }
+                if ui.button("Discard All").clicked() {
+                    discard_all = true;
+                }
+            });
+        });
+
+    if restore_all {
+        for file in std::mem::take(&mut tabular.recovery_files) {
+            restore(tabular, &file);
+        }
+    } else if discard_all {
+        for file in std::mem::take(&mut tabular.recovery_files) {
+            let _ = std::fs::remove_file(&file.path);
+        }
+    } else if let Some(idx) = restore_idx {
+        let file = tabular.recovery_files.remove(idx);
+        restore(tabular, &file);
+    } else if let Some(idx) = discard_idx {
+        let file = tabular.recovery_files.remove(idx);
+        let _ = std::fs::remove_file(&file.path);
+    }
+
+    if !open || restore_all || discard_all {
+        tabular.show_recovery_dialog = false;
+        tabular.recovery_files.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(content: &str, modified: bool) -> Option<RecoveryEntry> {
+        modified.then(|| RecoveryEntry {
+            title: "Untitled Query".to_string(),
+            content: content.to_string(),
+            file_path: None,
+            connection_id: None,
+            database_name: None,
+            saved_at: Local::now(),
+        })
+    }
+
+    #[test]
+    fn writes_changed_buffers_and_cleans_up_on_exit() {
+        let dir = std::env::temp_dir().join(format!("tabular-recovery-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut writer = RecoveryWriter::default();
+
+        writer.sync(&dir, vec![tab("SELECT 1", true), tab("SELECT 2", false)]);
+        let files = list_recovery_files(&dir, None);
+        assert_eq!(files.len(), 1);
+        assert!(files[0].from_crash);
+        assert_eq!(files[0].entry.content, "SELECT 1");
+        assert!(list_recovery_files(&dir, Some(&writer.session)).is_empty());
+
+        // Saving the tab drops its recovery file.
+        writer.sync(&dir, vec![tab("SELECT 1", false)]);
+        assert!(list_recovery_files(&dir, None).is_empty());
+
+        writer.sync(&dir, vec![tab("SELECT 3", true)]);
+        writer.finish(&dir);
+        assert!(list_recovery_files(&dir, None).is_empty());
+        assert!(dir.join(CLEAN_SHUTDOWN_MARKER).exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,9 +1,7 @@
-use std::collections::HashMap;
-
 use eframe::egui;
 
 use crate::models::structs::{
-    RedisBrowserState, RedisBrowserTypeFilter,
+    RedisBrowserState, RedisBrowserTypeFilter, RedisKeyEdit,
 };
 
 #[derive(Clone, Debug)]
@@ -12,6 +10,7 @@ pub enum RedisBrowserAction {
     SelectKeyspace { database_name: String },
     SelectKey { key_name: String, key_type: String },
     SearchServer { search_text: String },
+    EditKey { key_name: String, edit: RedisKeyEdit },
 }
 
 fn display_key_type(key_type: &str) -> &str {
@@ -26,7 +25,8 @@ fn display_key_type(key_type: &str) -> &str {
     }
 }
 
-fn elide_middle(text: &str, max_chars: usize) -> String {
+/// Shorten `text` to `max_chars` by replacing its middle with `...`.
+pub(crate) fn elide_middle(text: &str, max_chars: usize) -> String {
     let total = text.chars().count();
     if total <= max_chars {
         return text.to_string();
@@ -65,12 +65,93 @@ fn filtered_key_indices(state: &RedisBrowserState) -> Vec<usize> {
         .collect()
 }
 
+/// TTL column text; keys without an expiry stand out from expiring ones.
+fn ttl_text(ttl_seconds: Option<i64>, ttl_label: &str) -> egui::RichText {
+    if ttl_seconds == Some(-1) {
+        egui::RichText::new("∞ no expiry").italics().weak()
+    } else {
+        egui::RichText::new(ttl_label)
+    }
+}
+
+/// Entries of the key context menu and the preview action buttons. Each one
+/// opens the confirmation bar instead of touching the server directly.
+fn key_edit_buttons(ui: &mut egui::Ui, key_name: &str, edit_request: &mut Option<(String, RedisKeyEdit)>) {
+    for (label, hint, edit) in [
+        ("⏱ Expire…", "Set a time to live (EXPIRE)", RedisKeyEdit::Expire { seconds: 3600 }),
+        ("♾ Persist…", "Remove the expiry (PERSIST)", RedisKeyEdit::Persist),
+        (
+            "✏ Rename…",
+            "Rename unless the new name exists (RENAMENX)",
+            RedisKeyEdit::Rename { new_name: key_name.to_string() },
+        ),
+        ("🗑 Delete…", "Delete the key (DEL)", RedisKeyEdit::Delete),
+    ] {
+        if ui.button(label).on_hover_text(hint).clicked() {
+            *edit_request = Some((key_name.to_string(), edit));
+            ui.close();
+        }
+    }
+}
+
+/// Confirmation bar for a pending key mutation. Returns the confirmed edit.
+fn render_key_edit_confirm(ui: &mut egui::Ui, state: &mut RedisBrowserState) -> Option<RedisBrowserAction> {
+    let (key_name, edit) = state.key_edit.as_mut()?;
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let danger = crate::window_egui::style::theme_danger(ui.ctx());
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            let shown_key = elide_middle(key_name, 48);
+            let confirm_label = match edit {
+                RedisKeyEdit::Expire { seconds } => {
+                    ui.label(format!("Expire {} after", shown_key));
+                    ui.add(egui::DragValue::new(seconds).range(1..=u64::from(u32::MAX)).speed(10));
+                    ui.label(format!("seconds ({})", crate::driver_redis::format_ttl_label(*seconds as i64)));
+                    egui::RichText::new("Set Expiry")
+                }
+                RedisKeyEdit::Persist => {
+                    ui.label(format!("Remove the expiry of {}? It will be kept until deleted.", shown_key));
+                    egui::RichText::new("Persist")
+                }
+                RedisKeyEdit::Rename { new_name } => {
+                    ui.label(format!("Rename {} to", shown_key));
+                    ui.add(egui::TextEdit::singleline(new_name).desired_width(220.0));
+                    egui::RichText::new("Rename")
+                }
+                RedisKeyEdit::Delete => {
+                    ui.colored_label(danger, format!("Delete {}? This cannot be undone.", shown_key));
+                    egui::RichText::new("Delete").color(danger)
+                }
+            };
+            let ready = match edit {
+                RedisKeyEdit::Rename { new_name } => !new_name.trim().is_empty() && new_name != key_name,
+                _ => true,
+            };
+            if ui.add_enabled(ready, egui::Button::new(confirm_label)).clicked() {
+                confirmed = true;
+            }
+            if ui.button("Cancel").clicked() {
+                cancelled = true;
+            }
+        });
+    });
+    ui.add_space(4.0);
+    if cancelled {
+        state.key_edit = None;
+    }
+    if !confirmed {
+        return None;
+    }
+    let (key_name, edit) = state.key_edit.take()?;
+    Some(RedisBrowserAction::EditKey { key_name, edit })
+}
+
 fn render_json_preview(ui: &mut egui::Ui, json_text: &str) {
     let dark = ui.visuals().dark_mode;
     let available_size = ui.available_size();
     let line_count = json_text.lines().count().max(1);
     let gutter_width = ((line_count as f32).log10().floor() as usize + 1) as f32 * 8.0 + 20.0;
-    let mut cache: HashMap<u64, egui::text::LayoutJob> = HashMap::new();
 
     let frame_fill = if dark {
         egui::Color32::from_rgb(18, 10, 10)
@@ -88,12 +169,8 @@ fn render_json_preview(ui: &mut egui::Ui, json_text: &str) {
         egui::Color32::from_rgb(150, 96, 96)
     };
 
-    let mut job = crate::syntax_ts::highlight_text_cached(
-        json_text,
-        crate::syntax_ts::LanguageKind::Redis,
-        dark,
-        &mut cache,
-    );
+    let mut job =
+        crate::syntax_ts::highlight_text(json_text, crate::syntax_ts::LanguageKind::Redis, dark);
     job.wrap.max_width = f32::INFINITY;
 
     egui::Frame::new()
@@ -245,6 +322,11 @@ pub fn render_redis_browser(
 
         ui.add_space(8.0);
 
+        if let Some(confirmed) = render_key_edit_confirm(ui, state) {
+            action = Some(confirmed);
+        }
+
+        let mut edit_request = None;
         ui.columns(2, |columns| {
             let (left_slice, right_slice) = columns.split_at_mut(1);
             let left = &mut left_slice[0];
@@ -299,6 +381,9 @@ pub fn render_redis_browser(
                                             egui::RichText::new(display_key).size(13.0),
                                         );
                                         let response = response.on_hover_text(&entry.key_name);
+                                        response.context_menu(|ui| {
+                                            key_edit_buttons(ui, &entry.key_name, &mut edit_request);
+                                        });
                                         if response.clicked() {
                                             action = Some(RedisBrowserAction::SelectKey {
                                                 key_name: entry.key_name.clone(),
@@ -309,7 +394,7 @@ pub fn render_redis_browser(
                                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                                             ui.label(entry.size_label.clone());
                                             ui.add_space(24.0);
-                                            ui.label(entry.ttl_label.clone());
+                                            ui.label(ttl_text(entry.ttl_seconds, &entry.ttl_label));
                                         });
                                     });
                                 });
@@ -350,6 +435,9 @@ pub fn render_redis_browser(
                             if ui.button("Copy JSON").clicked() {
                                 ui.ctx().copy_text(preview.json_text.clone());
                             }
+                            ui.menu_button("Actions", |ui| {
+                                key_edit_buttons(ui, &preview.key_name, &mut edit_request);
+                            });
                         });
                     });
                     ui.separator();
@@ -370,7 +458,33 @@ pub fn render_redis_browser(
                 }
             });
         });
+        if edit_request.is_some() {
+            state.key_edit = edit_request;
+        }
     });
 
     action
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elide_middle_keeps_both_ends() {
+        assert_eq!(elide_middle("short.sql", 20), "short.sql");
+        assert_eq!(elide_middle("monthly_revenue_report.sql", 15), "monthl...rt.sql");
+        assert_eq!(elide_middle("abcdef", 3), "...");
+    }
+
+    #[test]
+    fn ttl_labels_are_human_readable() {
+        use crate::driver_redis::format_ttl_label;
+        assert_eq!(format_ttl_label(-1), "No expiry");
+        assert_eq!(format_ttl_label(-2), "Expired");
+        assert_eq!(format_ttl_label(45), "in 45s");
+        assert_eq!(format_ttl_label(12 * 60 + 5), "in 12m");
+        assert_eq!(format_ttl_label(2 * 3600), "in 2h");
+        assert_eq!(format_ttl_label(2 * 3600 + 5 * 60), "in 2h 5m");
+        assert_eq!(format_ttl_label(3 * 86400 + 4 * 3600), "in 3d 4h");
+    }
+}
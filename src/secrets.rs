@@ -32,7 +32,8 @@ pub const SECRET_SENTINEL: &str = "__tabular_secret__";
 const KEYRING_SERVICE: &str = "id.tabular.database";
 
 /// Stable secret-store name for a connection credential field
-/// (`field` is one of `password`, `ssh_password`, `ssh_private_key`).
+/// (`field` is one of `password`, `ssh_password`, `ssh_private_key`,
+/// `ssh_key_passphrase`, `proxy_password`).
 pub fn connection_secret_name(connection_id: i64, field: &str) -> String {
     format!("conn:{}:{}", connection_id, field)
 }
@@ -113,7 +114,13 @@ pub fn resolve_readonly(name: &str, stored: &str) -> String {
 
 /// Remove all credential secrets belonging to a connection.
 pub fn delete_connection_secrets(connection_id: i64) {
-    for field in ["password", "ssh_password", "ssh_private_key"] {
+    for field in [
+        "password",
+        "ssh_password",
+        "ssh_private_key",
+        "ssh_key_passphrase",
+        "proxy_password",
+    ] {
         delete_secret(&connection_secret_name(connection_id, field));
     }
 }
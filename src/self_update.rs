@@ -1,6 +1,7 @@
 use log::{debug, error, warn};
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::error::Error;
 use std::fmt;
 
@@ -14,6 +15,46 @@ struct GitHubRelease {
     assets: Vec<GitHubAsset>,
     html_url: String,
     published_at: Option<String>,
+    #[serde(default)]
+    prerelease: bool,
+    #[serde(default)]
+    draft: bool,
+}
+
+/// Which releases the updater offers: stable only, or pre-releases as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "STABLE",
+            UpdateChannel::Beta => "BETA",
+        }
+    }
+    pub fn display_name(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "Stable",
+            UpdateChannel::Beta => "Beta (pre-releases)",
+        }
+    }
+    pub fn includes_prereleases(self) -> bool {
+        self == UpdateChannel::Beta
+    }
+}
+
+impl std::str::FromStr for UpdateChannel {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "BETA" => UpdateChannel::Beta,
+            _ => UpdateChannel::Stable,
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -30,8 +71,28 @@ pub struct UpdateInfo {
     pub release_notes: String,
     pub download_url: Option<String>,
     pub asset_name: Option<String>,
+    /// Published SHA-256 checksum file covering the asset, if the release has one.
+    #[serde(default)]
+    pub checksum_url: Option<String>,
     pub release_url: String,
     pub published_at: Option<String>,
+    /// Channel the check ran on.
+    #[serde(default)]
+    pub channel: UpdateChannel,
+    /// The offered release is a pre-release (only on the beta channel).
+    #[serde(default)]
+    pub prerelease: bool,
+}
+
+/// Integrity check of a downloaded update. A mismatch never gets this far: the
+/// install is aborted with an error. Releases do not publish a signing key, so
+/// the published SHA-256 checksum is the only thing checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateVerification {
+    /// The download matched the release's published SHA-256 checksum.
+    Verified,
+    /// The release has no checksum for the asset; the download is unverified.
+    NoChecksum,
 }
 
 #[derive(Debug)]
@@ -60,11 +121,30 @@ impl fmt::Display for UpdateError {
 
 impl Error for UpdateError {}
 
-pub async fn check_for_updates() -> Result<UpdateInfo, UpdateError> {
-    debug!("Checking for updates from GitHub releases...");
+fn release_version(release: &GitHubRelease) -> Option<Version> {
+    let tag = release.tag_name.strip_prefix('v').unwrap_or(&release.tag_name);
+    Version::parse(tag).ok()
+}
 
+/// Newest release the channel offers. Stable skips releases GitHub marks as
+/// pre-release and tags with a pre-release suffix (e.g. `v0.6.0-beta.1`).
+fn select_release(releases: Vec<GitHubRelease>, channel: UpdateChannel) -> Option<GitHubRelease> {
+    releases
+        .into_iter()
+        .filter(|r| !r.draft)
+        .filter_map(|r| release_version(&r).map(|v| (v, r)))
+        .filter(|(v, r)| channel.includes_prereleases() || (!r.prerelease && v.pre.is_empty()))
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, r)| r)
+}
+
+pub async fn check_for_updates(channel: UpdateChannel) -> Result<UpdateInfo, UpdateError> {
+    debug!("Checking for updates from GitHub releases ({:?} channel)...", channel);
+
+    // `/releases/latest` never returns pre-releases, so list recent releases
+    // and pick the newest one the channel allows.
     let url = format!(
-        "https://api.github.com/repos/{}/releases/latest",
+        "https://api.github.com/repos/{}/releases?per_page=30",
         GITHUB_REPO
     );
 
@@ -96,7 +176,7 @@ pub async fn check_for_updates() -> Result<UpdateInfo, UpdateError> {
             "GitHub API rate limit or access restriction ({}); falling back to web redirect check...",
             response.status()
         );
-        return check_for_updates_web_fallback().await;
+        return check_for_updates_web_fallback(channel).await;
     }
 
     if !response.status().is_success() {
@@ -106,24 +186,25 @@ pub async fn check_for_updates() -> Result<UpdateInfo, UpdateError> {
         )));
     }
 
-    let release: GitHubRelease = response
+    let releases: Vec<GitHubRelease> = response
         .json()
         .await
         .map_err(|e| UpdateError::ParseError(e.to_string()))?;
 
+    let release = select_release(releases, channel).ok_or_else(|| {
+        UpdateError::ParseError(format!("No {} release found", channel.display_name()))
+    })?;
+
     debug!("Latest release: {}", release.tag_name);
 
     // Parse versions
     let current_version = Version::parse(CURRENT_VERSION)
         .map_err(|e| UpdateError::ParseError(format!("Invalid current version: {}", e)))?;
 
-    // Remove 'v' prefix if present
-    let latest_version_str = release
-        .tag_name
-        .strip_prefix('v')
-        .unwrap_or(&release.tag_name);
-    let latest_version = Version::parse(latest_version_str)
-        .map_err(|e| UpdateError::ParseError(format!("Invalid latest version: {}", e)))?;
+    let latest_version = release_version(&release).ok_or_else(|| {
+        UpdateError::ParseError(format!("Invalid latest version: {}", release.tag_name))
+    })?;
+    let prerelease = release.prerelease || !latest_version.pre.is_empty();
 
     let update_available = latest_version > current_version;
 
@@ -133,6 +214,9 @@ pub async fn check_for_updates() -> Result<UpdateInfo, UpdateError> {
     } else {
         (None, None)
     };
+    let checksum_url = asset_name
+        .as_deref()
+        .and_then(|name| find_checksum_asset(&release.assets, name));
 
     Ok(UpdateInfo {
         current_version: CURRENT_VERSION.to_string(),
@@ -141,12 +225,19 @@ pub async fn check_for_updates() -> Result<UpdateInfo, UpdateError> {
         release_notes: release.body,
         download_url,
         asset_name,
+        checksum_url,
         release_url: release.html_url,
         published_at: release.published_at,
+        channel,
+        prerelease,
     })
 }
 
-pub async fn check_for_updates_web_fallback() -> Result<UpdateInfo, UpdateError> {
+/// Rate-limited fallback: reads the tag `/releases/latest` redirects to, which
+/// is always the newest stable release whatever the channel.
+pub async fn check_for_updates_web_fallback(
+    channel: UpdateChannel,
+) -> Result<UpdateInfo, UpdateError> {
     debug!("Checking for updates via web redirect fallback...");
     let web_release_url = format!("https://github.com/{}/releases/latest", GITHUB_REPO);
 
@@ -216,8 +307,11 @@ pub async fn check_for_updates_web_fallback() -> Result<UpdateInfo, UpdateError>
         release_notes,
         download_url: None,
         asset_name: None,
+        checksum_url: None,
         release_url,
         published_at: None,
+        channel,
+        prerelease: false,
     })
 }
 
@@ -259,6 +353,42 @@ fn find_asset_for_platform(assets: &[GitHubAsset]) -> (Option<String>, Option<St
     (None, None)
 }
 
+/// Checksum file for `asset_name`: a per-asset `<asset>.sha256` first, then a
+/// release-wide `SHA256SUMS` / `checksums.txt`.
+fn find_checksum_asset(assets: &[GitHubAsset], asset_name: &str) -> Option<String> {
+    let asset_lower = asset_name.to_lowercase();
+    let per_asset = [format!("{}.sha256", asset_lower), format!("{}.sha256sum", asset_lower)];
+    let release_wide = ["sha256sums", "sha256sums.txt", "checksums.txt", "checksums.sha256"];
+    assets
+        .iter()
+        .find(|a| per_asset.contains(&a.name.to_lowercase()))
+        .or_else(|| {
+            assets
+                .iter()
+                .find(|a| release_wide.contains(&a.name.to_lowercase().as_str()))
+        })
+        .map(|a| a.browser_download_url.clone())
+}
+
+/// Expected hex digest for `asset_name` from a checksum file: either a bare
+/// digest (per-asset file) or `sha256sum` lines of `<digest>  <file>`.
+pub fn expected_sha256(checksums: &str, asset_name: &str) -> Option<String> {
+    let is_digest = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+    checksums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let digest = parts.next().filter(|d| is_digest(d))?;
+        match parts.next() {
+            None => Some(digest.to_lowercase()),
+            Some(file) if file.trim_start_matches('*') == asset_name => Some(digest.to_lowercase()),
+            Some(_) => None,
+        }
+    })
+}
+
+pub fn sha256_hex(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
 #[derive(Debug)]
 struct PlatformInfo {
     os: &'static str,
@@ -385,6 +515,62 @@ mod tests {
         assert!(v2 > v1);
     }
 
+    fn release(tag: &str, prerelease: bool) -> GitHubRelease {
+        GitHubRelease {
+            tag_name: tag.to_string(),
+            body: String::new(),
+            assets: Vec::new(),
+            html_url: String::new(),
+            published_at: None,
+            prerelease,
+            draft: false,
+        }
+    }
+
+    #[test]
+    fn test_release_selection_by_channel() {
+        let releases = || {
+            vec![
+                release("v0.5.0", false),
+                release("v0.6.0-beta.1", true),
+                release("v0.5.1-rc.1", false),
+                release("v0.5.1", false),
+            ]
+        };
+        let stable = select_release(releases(), UpdateChannel::Stable).unwrap();
+        assert_eq!(stable.tag_name, "v0.5.1");
+        let beta = select_release(releases(), UpdateChannel::Beta).unwrap();
+        assert_eq!(beta.tag_name, "v0.6.0-beta.1");
+        assert!(select_release(vec![release("v0.6.0-beta.1", true)], UpdateChannel::Stable).is_none());
+    }
+
+    #[test]
+    fn test_checksum_lookup() {
+        let digest = sha256_hex(b"tabular");
+        assert_eq!(expected_sha256(&format!("{}\n", digest), "tabular.dmg"), Some(digest.clone()));
+        let sums = format!(
+            "{}  tabular-linux-x86_64.tar.gz\n{} *tabular.dmg\n",
+            "0".repeat(64),
+            digest.to_uppercase()
+        );
+        assert_eq!(expected_sha256(&sums, "tabular.dmg"), Some(digest));
+        assert_eq!(expected_sha256(&sums, "tabular.msi"), None);
+
+        let asset = |name: &str| GitHubAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.invalid/{}", name),
+        };
+        let assets = vec![asset("tabular.dmg"), asset("SHA256SUMS"), asset("tabular.dmg.sha256")];
+        assert_eq!(
+            find_checksum_asset(&assets, "tabular.dmg").as_deref(),
+            Some("https://example.invalid/tabular.dmg.sha256")
+        );
+        assert_eq!(
+            find_checksum_asset(&assets, "tabular.msi").as_deref(),
+            Some("https://example.invalid/SHA256SUMS")
+        );
+    }
+
     #[test]
     fn test_update_error_formatting() {
         let err_403 = UpdateError::NetworkError("GitHub API returned status: 403 Forbidden".to_string());
@@ -265,6 +265,8 @@ pub(crate) fn render_connection_dialog(
                         ui.text_edit_singleline(&mut connection_data.name);
                         ui.end_row();
 
+                        crate::connection::tag::render_tag_fields(ui, &mut connection_data);
+
                         if connection_data.connection_type == models::enums::DatabaseType::SQLite {
                             connection_data.ssh_enabled = false;
                         }
@@ -275,6 +277,49 @@ pub(crate) fn render_connection_dialog(
                                 ui.label("Database File:");
                                 ui.text_edit_singleline(&mut connection_data.database);
                                 ui.end_row();
+
+                                // Extra files ATTACHed under their own schema name
+                                ui.label("Attached Files:");
+                                ui.vertical(|ui| {
+                                    let mut remove_index = None;
+                                    for (index, attachment) in
+                                        connection_data.sqlite_attachments.iter_mut().enumerate()
+                                    {
+                                        ui.horizontal(|ui| {
+                                            ui.add(
+                                                egui::TextEdit::singleline(&mut attachment.schema)
+                                                    .hint_text("schema")
+                                                    .desired_width(80.0),
+                                            );
+                                            ui.add(
+                                                egui::TextEdit::singleline(&mut attachment.path)
+                                                    .hint_text("/path/to/other.db")
+                                                    .desired_width(220.0),
+                                            );
+                                            if ui.small_button("🗑").on_hover_text("Detach").clicked() {
+                                                remove_index = Some(index);
+                                            }
+                                        });
+                                    }
+                                    if let Some(index) = remove_index {
+                                        connection_data.sqlite_attachments.remove(index);
+                                    }
+                                    if ui.small_button("➕ Attach file").clicked() {
+                                        let n = connection_data.sqlite_attachments.len() + 1;
+                                        connection_data.sqlite_attachments.push(
+                                            models::structs::SqliteAttachment {
+                                                schema: format!("db{}", n),
+                                                path: String::new(),
+                                            },
+                                        );
+                                    }
+                                    if let Some(problem) = crate::driver_sqlite::attachment_problem(
+                                        &connection_data.sqlite_attachments,
+                                    ) {
+                                        ui.colored_label(egui::Color32::from_rgb(220, 80, 80), problem);
+                                    }
+                                });
+                                ui.end_row();
                             }
                             models::enums::DatabaseType::ApiHttp => {
                                 // API-HTTP: only Connection Name + Folder needed
@@ -292,16 +337,89 @@ pub(crate) fn render_connection_dialog(
                                 ui.text_edit_singleline(&mut connection_data.username);
                                 ui.end_row();
 
+                                let credential_file = match connection_data.connection_type {
+                                    models::enums::DatabaseType::PostgreSQL => Some("~/.pgpass"),
+                                    models::enums::DatabaseType::MySQL => Some("~/.my.cnf"),
+                                    _ => None,
+                                };
+                                let from_file = credential_file.is_some()
+                                    && connection_data.use_credential_file;
                                 ui.label("Password:");
-                                ui.add(
-                                    egui::TextEdit::singleline(&mut connection_data.password)
-                                        .password(true),
-                                );
+                                if from_file {
+                                    // Nothing to keep in Tabular's store
+                                    connection_data.password.clear();
+                                    ui.label(
+                                        egui::RichText::new(format!(
+                                            "Read from {} when connections load",
+                                            credential_file.unwrap_or_default()
+                                        ))
+                                        .weak(),
+                                    );
+                                } else {
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut connection_data.password)
+                                            .password(true),
+                                    )
+                                    .on_hover_text("${ENV_VAR} placeholders are resolved when connections load");
+                                }
                                 ui.end_row();
 
+                                if let Some(file) = credential_file {
+                                    ui.label("");
+                                    ui.checkbox(
+                                        &mut connection_data.use_credential_file,
+                                        format!("Use {}", file),
+                                    )
+                                    .on_hover_text(
+                                        "Look the password up by host, port, database and user when connecting. An empty username is taken from the file.",
+                                    );
+                                    ui.end_row();
+                                }
+
                                 ui.label("Database:");
-                                ui.text_edit_singleline(&mut connection_data.database);
+                                ui.text_edit_singleline(&mut connection_data.database)
+                                    .on_hover_text("Database new query tabs start in");
                                 ui.end_row();
+
+                                if let Err(problem) =
+                                    crate::connection::resolve_connection_env(&connection_data)
+                                {
+                                    ui.label("");
+                                    ui.colored_label(egui::Color32::from_rgb(220, 80, 80), problem);
+                                    ui.end_row();
+                                }
+
+                                if connection_data.connection_type
+                                    == models::enums::DatabaseType::PostgreSQL
+                                {
+                                    ui.label("Default schema:");
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut connection_data.default_schema)
+                                            .hint_text("public"),
+                                    )
+                                    .on_hover_text("search_path for new query tabs");
+                                    ui.end_row();
+                                }
+
+                                if connection_data.connection_type
+                                    == models::enums::DatabaseType::MsSQL
+                                {
+                                    ui.label("TLS:");
+                                    ui.horizontal(|ui| {
+                                        ui.checkbox(&mut connection_data.mssql_encrypt, "Encrypt")
+                                            .on_hover_text(
+                                                "Encrypt the whole session. When off, only the login is encrypted.",
+                                            );
+                                        ui.checkbox(
+                                            &mut connection_data.mssql_trust_server_certificate,
+                                            "Trust server certificate",
+                                        )
+                                        .on_hover_text(
+                                            "Accept self-signed certificates. Turn off to verify the server against system roots.",
+                                        );
+                                    });
+                                    ui.end_row();
+                                }
                             }
                         }
 
@@ -519,6 +637,16 @@ pub(crate) fn render_connection_dialog(
                                     ui.label("SSH Key Path:");
                                     ui.text_edit_singleline(&mut connection_data.ssh_private_key);
                                     ui.end_row();
+
+                                    ui.label("Key Passphrase:")
+                                        .on_hover_text("Leave empty for keys without a passphrase");
+                                    ui.add(
+                                        egui::TextEdit::singleline(
+                                            &mut connection_data.ssh_key_passphrase,
+                                        )
+                                        .password(true),
+                                    );
+                                    ui.end_row();
                                 }
                                 models::enums::SshAuthMethod::Password => {
                                     ui.label("SSH Password:");
@@ -542,13 +670,35 @@ pub(crate) fn render_connection_dialog(
                             ui.label("");
                             ui.label(
                                 egui::RichText::new(
-                                    "Requires local ssh binary. Password mode expects sshpass to be installed.",
+                                    "Requires local ssh binary. Password mode and key passphrases expect sshpass to be installed.",
                                 )
                                 .italics(),
                             );
                             ui.end_row();
                         }
                         } // end if != ApiHttp (SSH section)
+
+                        // Proxy section: the database is reached through a SOCKS5/HTTP proxy
+                        if ssh_supported {
+                            ui.label("Proxy:").on_hover_text(
+                                "With None, the global proxy from Preferences is used for non-local hosts",
+                            );
+                            crate::proxy_tunnel::render_proxy_fields(
+                                ui,
+                                &mut connection_data.proxy,
+                                "connection_proxy_kind_combo",
+                            );
+                            if connection_data.ssh_enabled
+                                && connection_data.proxy.kind != models::enums::ProxyKind::None
+                            {
+                                ui.label("");
+                                ui.colored_label(
+                                    egui::Color32::from_rgb(200, 150, 0),
+                                    "A proxy cannot be combined with an SSH tunnel",
+                                );
+                                ui.end_row();
+                            }
+                        }
                     });
 
                 ui.separator();
@@ -559,6 +709,15 @@ pub(crate) fn render_connection_dialog(
                         if is_edit_mode {
                             // Update existing connection
                             if let Some(id) = connection_data.id {
+                                // Attached files live on every pooled connection and in the
+                                // tree layout, so changing them needs a full reconnect.
+                                let attachments_changed = tabular
+                                    .connections
+                                    .iter()
+                                    .find(|c| c.id == Some(id))
+                                    .is_some_and(|c| {
+                                        c.sqlite_attachments != connection_data.sqlite_attachments
+                                    });
                                 if connection::update_connection_in_database(
                                     tabular,
                                     &connection_data,
@@ -578,6 +737,9 @@ pub(crate) fn render_connection_dialog(
                                         debug!("ERROR: Could not find connection {} in memory", id);
                                     }
                                 }
+                                if attachments_changed {
+                                    tabular.refresh_connection(id);
+                                }
                             } else {
                                 debug!("ERROR: Connection has no ID, cannot update");
                             }
@@ -631,6 +793,7 @@ pub(crate) fn render_connection_dialog(
                             tabular.new_connection = models::structs::ConnectionConfig::default();
                             tabular.test_connection_status = None;
                             tabular.test_connection_in_progress = false;
+                            tabular.test_connection_receiver = None;
                             tabular.show_add_connection = false;
 
                             // Kick off a background schema-cache sync so autocomplete works
@@ -666,15 +829,12 @@ pub(crate) fn render_connection_dialog(
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         // Test Connection button (tidak tersedia untuk API-HTTP)
                         if connection_data.connection_type != models::enums::DatabaseType::ApiHttp {
-                            if tabular.test_connection_in_progress {
+                            if connection::poll_connection_test(tabular) {
                                 ui.spinner();
                                 ui.label("Testing connection...");
+                                ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
                             } else if ui.button("Test Connection").clicked() {
-                                // Test connection based on database type
-                                // Note: This is synchronous, so we don't see the spinner.
-                                // TODO: Move to async task if spinner is needed.
-                                let result = connection::test_database_connection(&connection_data);
-                                tabular.test_connection_status = Some(result);
+                                connection::start_connection_test(tabular, connection_data.clone());
                             }
                         }
                     });
@@ -713,6 +873,7 @@ pub(crate) fn render_connection_dialog(
             tabular.new_connection = models::structs::ConnectionConfig::default();
             tabular.test_connection_status = None;
             tabular.test_connection_in_progress = false;
+            tabular.test_connection_receiver = None;
             tabular.show_add_connection = false;
         }
     }
@@ -748,10 +909,19 @@ pub(crate) fn load_connections(tabular: &mut window_egui::Tabular) {
              COALESCE(ssh_auth_method, 'key') AS ssh_auth_method, \
              COALESCE(ssh_private_key, '') AS ssh_private_key, \
              COALESCE(ssh_password, '') AS ssh_password, \
-             COALESCE(ssh_password, '') AS ssh_password, \
+             COALESCE(ssh_key_passphrase, '') AS ssh_key_passphrase, \
              COALESCE(ssh_accept_unknown_host_keys, 0) AS ssh_accept_unknown_host_keys, \
+             COALESCE(mssql_encrypt, 1) AS mssql_encrypt, \
+             COALESCE(mssql_trust_server_certificate, 1) AS mssql_trust_server_certificate, \
              COALESCE(custom_views, '[]') AS custom_views, \
-             replication_master_id \
+             COALESCE(sqlite_attachments, '[]') AS sqlite_attachments, \
+             replication_master_id, \
+             COALESCE(default_schema, '') AS default_schema, \
+             COALESCE(use_credential_file, 0) AS use_credential_file, \
+             COALESCE(label, '') AS label, \
+             COALESCE(color, '') AS color, \
+             COALESCE(proxy, '{}') AS proxy, \
+             last_used_database \
          FROM connections",
         )
         .fetch_all(pool_clone.as_ref())
@@ -781,10 +951,26 @@ pub(crate) fn load_connections(tabular: &mut window_egui::Tabular) {
                     let ssh_auth_method = row.try_get::<String, _>("ssh_auth_method").ok()?;
                     let ssh_private_key = row.try_get::<String, _>("ssh_private_key").ok()?;
                     let ssh_password = row.try_get::<String, _>("ssh_password").ok()?;
+                    let ssh_key_passphrase =
+                        row.try_get::<String, _>("ssh_key_passphrase").unwrap_or_default();
                     let ssh_accept_unknown_host_keys =
                         row.try_get::<i64, _>("ssh_accept_unknown_host_keys").ok()?;
+                    let mssql_encrypt = row.try_get::<i64, _>("mssql_encrypt").unwrap_or(1);
+                    let mssql_trust_server_certificate =
+                        row.try_get::<i64, _>("mssql_trust_server_certificate").unwrap_or(1);
                     let custom_views_json = row.try_get::<String, _>("custom_views").ok().unwrap_or_else(|| "[]".to_string());
+                    let sqlite_attachments_json = row.try_get::<String, _>("sqlite_attachments").ok().unwrap_or_else(|| "[]".to_string());
                     let replication_master_id = row.try_get::<Option<i64>, _>("replication_master_id").ok().flatten();
+                    let default_schema = row.try_get::<String, _>("default_schema").unwrap_or_default();
+                    let last_used_database = row.try_get::<Option<String>, _>("last_used_database").ok().flatten();
+                    let use_credential_file = row.try_get::<i64, _>("use_credential_file").unwrap_or(0);
+                    let label = row.try_get::<String, _>("label").unwrap_or_default();
+                    let color = row.try_get::<String, _>("color").unwrap_or_default();
+                    let (proxy, proxy_rewrite) = crate::proxy_tunnel::proxy_from_storage(
+                        &row.try_get::<String, _>("proxy").unwrap_or_default(),
+                        &crate::secrets::connection_secret_name(id, "proxy_password"),
+                        true,
+                    );
 
                     let (password, pw_rewrite) = crate::secrets::resolve_stored(
                         &crate::secrets::connection_secret_name(id, "password"),
@@ -798,10 +984,16 @@ pub(crate) fn load_connections(tabular: &mut window_egui::Tabular) {
                         &crate::secrets::connection_secret_name(id, "ssh_password"),
                         &ssh_password,
                     );
+                    let (ssh_key_passphrase, passphrase_rewrite) = crate::secrets::resolve_stored(
+                        &crate::secrets::connection_secret_name(id, "ssh_key_passphrase"),
+                        &ssh_key_passphrase,
+                    );
                     for (field, rewrite) in [
                         ("password", pw_rewrite),
                         ("ssh_private_key", key_rewrite),
                         ("ssh_password", sshpw_rewrite),
+                        ("ssh_key_passphrase", passphrase_rewrite),
+                        ("proxy", proxy_rewrite),
                     ] {
                         if let Some(value) = rewrite {
                             secret_rewrites.push((id, field, value));
@@ -835,12 +1027,27 @@ pub(crate) fn load_connections(tabular: &mut window_egui::Tabular) {
                         ),
                         ssh_private_key,
                         ssh_password,
+                        ssh_key_passphrase,
                         ssh_accept_unknown_host_keys: ssh_accept_unknown_host_keys != 0,
+                        mssql_encrypt: mssql_encrypt != 0,
+                        mssql_trust_server_certificate: mssql_trust_server_certificate != 0,
                         custom_views: serde_json::from_str(&custom_views_json).unwrap_or_default(),
+                        sqlite_attachments: serde_json::from_str(&sqlite_attachments_json).unwrap_or_default(),
                         replication_master_id,
+                        default_schema,
+                        last_used_database,
+                        use_credential_file: use_credential_file != 0,
+                        label,
+                        color,
+                        proxy,
+                        templates: None,
                     })
                 })
                 .collect();
+            // Placeholders and credential files resolve once, here
+            for connection in &mut tabular.connections {
+                crate::connection::resolve_loaded_connection(connection);
+            }
 
             for (id, field, value) in secret_rewrites {
                 // Field names are fixed identifiers above, never user input.
@@ -940,10 +1147,13 @@ fn externalize_connection_secrets(
     rt: &tokio::runtime::Runtime,
     pool: &std::sync::Arc<sqlx::SqlitePool>,
     connection_id: i64,
-    password: &str,
-    ssh_private_key: &str,
-    ssh_password: &str,
+    connection: &models::structs::ConnectionConfig,
 ) {
+    let password = connection.password.as_str();
+    let ssh_private_key = connection.ssh_private_key.as_str();
+    let ssh_password = connection.ssh_password.as_str();
+    let ssh_key_passphrase = connection.ssh_key_passphrase.as_str();
+    let proxy = &connection.proxy;
     let stored_password = crate::secrets::store_or_keep(
         &crate::secrets::connection_secret_name(connection_id, "password"),
         password,
@@ -956,19 +1166,31 @@ fn externalize_connection_secrets(
         &crate::secrets::connection_secret_name(connection_id, "ssh_password"),
         ssh_password,
     );
+    let stored_passphrase = crate::secrets::store_or_keep(
+        &crate::secrets::connection_secret_name(connection_id, "ssh_key_passphrase"),
+        ssh_key_passphrase,
+    );
+    let stored_proxy = crate::proxy_tunnel::proxy_json_for_storage(
+        proxy,
+        &crate::secrets::connection_secret_name(connection_id, "proxy_password"),
+    );
     if stored_password == password
         && stored_key == ssh_private_key
         && stored_ssh_password == ssh_password
+        && stored_passphrase == ssh_key_passphrase
+        && stored_proxy == serde_json::to_string(proxy).unwrap_or_else(|_| "{}".to_string())
     {
         return;
     }
     let _ = rt.block_on(async {
         sqlx::query(
-            "UPDATE connections SET password = ?, ssh_private_key = ?, ssh_password = ? WHERE id = ?",
+            "UPDATE connections SET password = ?, ssh_private_key = ?, ssh_password = ?, ssh_key_passphrase = ?, proxy = ? WHERE id = ?",
         )
         .bind(stored_password)
         .bind(stored_key)
         .bind(stored_ssh_password)
+        .bind(stored_passphrase)
+        .bind(stored_proxy)
         .bind(connection_id)
         .execute(pool.as_ref())
         .await
@@ -981,15 +1203,14 @@ pub(crate) fn save_connection_to_database(
 ) -> bool {
     if let Some(ref pool) = tabular.db_pool {
         let pool_clone = pool.clone();
-        let secret_password = connection.password.clone();
-        let secret_ssh_key = connection.ssh_private_key.clone();
-        let secret_ssh_password = connection.ssh_password.clone();
-        let connection = connection.clone();
+        // Templates are saved, never the values they resolved to
+        let connection = connection.for_storage();
+        let secrets = connection.clone();
         let rt = tokio::runtime::Runtime::new().unwrap();
 
         let result = rt.block_on(async {
           sqlx::query(
-          "INSERT INTO connections (name, host, port, username, password, database_name, connection_type, folder, ssh_enabled, ssh_host, ssh_port, ssh_username, ssh_auth_method, ssh_private_key, ssh_password, ssh_accept_unknown_host_keys, custom_views, replication_master_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+          "INSERT INTO connections (name, host, port, username, password, database_name, connection_type, folder, ssh_enabled, ssh_host, ssh_port, ssh_username, ssh_auth_method, ssh_private_key, ssh_password, ssh_key_passphrase, ssh_accept_unknown_host_keys, mssql_encrypt, mssql_trust_server_certificate, custom_views, sqlite_attachments, replication_master_id, default_schema, use_credential_file, label, color, proxy) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
           )
           .bind(connection.name)
           .bind(connection.host)
@@ -1006,9 +1227,18 @@ pub(crate) fn save_connection_to_database(
           .bind(connection.ssh_auth_method.as_db_value())
           .bind(connection.ssh_private_key)
             .bind(connection.ssh_password)
+            .bind(connection.ssh_key_passphrase)
             .bind(if connection.ssh_accept_unknown_host_keys { 1 } else { 0 })
+            .bind(if connection.mssql_encrypt { 1 } else { 0 })
+            .bind(if connection.mssql_trust_server_certificate { 1 } else { 0 })
             .bind(serde_json::to_string(&connection.custom_views).unwrap_or_else(|_| "[]".to_string()))
+            .bind(serde_json::to_string(&connection.sqlite_attachments).unwrap_or_else(|_| "[]".to_string()))
             .bind(connection.replication_master_id)
+            .bind(connection.default_schema)
+            .bind(if connection.use_credential_file { 1 } else { 0 })
+            .bind(connection.label)
+            .bind(connection.color)
+            .bind(serde_json::to_string(&connection.proxy).unwrap_or_else(|_| "{}".to_string()))
             .execute(pool_clone.as_ref())
             .await
        });
@@ -1021,9 +1251,7 @@ pub(crate) fn save_connection_to_database(
                       &rt,
                       &pool_clone,
                       res.last_insert_rowid(),
-                      &secret_password,
-                      &secret_ssh_key,
-                      &secret_ssh_password,
+                      &secrets,
                   );
                   true
               }
@@ -1038,7 +1266,7 @@ pub(crate) fn save_connection_to_database(
   // sentinel (or plaintext when no backend is available).
   fn externalize_credentials_for_update(
       connection: &models::structs::ConnectionConfig,
-  ) -> (String, String, String) {
+  ) -> (String, String, String, String, String) {
       match connection.id {
           Some(id) => (
               crate::secrets::store_or_keep(
@@ -1053,11 +1281,21 @@ pub(crate) fn save_connection_to_database(
                   &crate::secrets::connection_secret_name(id, "ssh_password"),
                   &connection.ssh_password,
               ),
+              crate::secrets::store_or_keep(
+                  &crate::secrets::connection_secret_name(id, "ssh_key_passphrase"),
+                  &connection.ssh_key_passphrase,
+              ),
+              crate::proxy_tunnel::proxy_json_for_storage(
+                  &connection.proxy,
+                  &crate::secrets::connection_secret_name(id, "proxy_password"),
+              ),
           ),
           None => (
               connection.password.clone(),
               connection.ssh_private_key.clone(),
               connection.ssh_password.clone(),
+              connection.ssh_key_passphrase.clone(),
+              serde_json::to_string(&connection.proxy).unwrap_or_else(|_| "{}".to_string()),
           ),
       }
   }
@@ -1068,9 +1306,11 @@ pub(crate) fn save_connection_to_database(
       password_stored: String,
       ssh_key_stored: String,
       ssh_password_stored: String,
+      ssh_passphrase_stored: String,
+      proxy_stored: String,
   ) -> Result<(), sqlx::Error> {
       sqlx::query(
-          "UPDATE connections SET name = ?, host = ?, port = ?, username = ?, password = ?, database_name = ?, connection_type = ?, folder = ?, ssh_enabled = ?, ssh_host = ?, ssh_port = ?, ssh_username = ?, ssh_auth_method = ?, ssh_private_key = ?, ssh_password = ?, ssh_accept_unknown_host_keys = ?, custom_views = ?, replication_master_id = ? WHERE id = ?"
+          "UPDATE connections SET name = ?, host = ?, port = ?, username = ?, password = ?, database_name = ?, connection_type = ?, folder = ?, ssh_enabled = ?, ssh_host = ?, ssh_port = ?, ssh_username = ?, ssh_auth_method = ?, ssh_private_key = ?, ssh_password = ?, ssh_key_passphrase = ?, ssh_accept_unknown_host_keys = ?, mssql_encrypt = ?, mssql_trust_server_certificate = ?, custom_views = ?, sqlite_attachments = ?, replication_master_id = ?, default_schema = ?, use_credential_file = ?, label = ?, color = ?, proxy = ? WHERE id = ?"
       )
       .bind(connection.name)
       .bind(connection.host)
@@ -1087,9 +1327,18 @@ pub(crate) fn save_connection_to_database(
       .bind(connection.ssh_auth_method.as_db_value())
       .bind(ssh_key_stored)
       .bind(ssh_password_stored)
+      .bind(ssh_passphrase_stored)
       .bind(if connection.ssh_accept_unknown_host_keys { 1 } else { 0 })
+      .bind(if connection.mssql_encrypt { 1 } else { 0 })
+      .bind(if connection.mssql_trust_server_certificate { 1 } else { 0 })
       .bind(serde_json::to_string(&connection.custom_views).unwrap_or_else(|_| "[]".to_string()))
+      .bind(serde_json::to_string(&connection.sqlite_attachments).unwrap_or_else(|_| "[]".to_string()))
       .bind(connection.replication_master_id)
+      .bind(connection.default_schema)
+      .bind(if connection.use_credential_file { 1 } else { 0 })
+      .bind(connection.label)
+      .bind(connection.color)
+      .bind(proxy_stored)
       .bind(connection.id)
       .execute(pool)
       .await
@@ -1103,13 +1352,18 @@ pub(crate) fn save_connection_to_database(
       let Some(pool_clone) = tabular.db_pool.clone() else {
           return false;
       };
-      let connection = connection.clone();
+      let connection = connection.for_storage();
       // Shared runtime: a fresh Runtime per call spawns new worker threads on
       // every save and stalls the UI thread far longer than the query itself.
       let rt = tabular.get_runtime();
 
-      let (password_stored, ssh_key_stored, ssh_password_stored) =
-          externalize_credentials_for_update(&connection);
+      let (
+          password_stored,
+          ssh_key_stored,
+          ssh_password_stored,
+          ssh_passphrase_stored,
+          proxy_stored,
+      ) = externalize_credentials_for_update(&connection);
 
       let result = rt.block_on(exec_update_connection(
           pool_clone.as_ref(),
@@ -1117,6 +1371,8 @@ pub(crate) fn save_connection_to_database(
           password_stored,
           ssh_key_stored,
           ssh_password_stored,
+          ssh_passphrase_stored,
+          proxy_stored,
       ));
 
       result.is_ok()
@@ -1135,11 +1391,16 @@ pub(crate) fn save_connection_to_database(
               .error("Cache database is not available; view not persisted");
           return;
       };
-      let connection = connection.clone();
+      let connection = connection.for_storage();
       let rt = tabular.get_runtime();
 
-      let (password_stored, ssh_key_stored, ssh_password_stored) =
-          externalize_credentials_for_update(&connection);
+      let (
+          password_stored,
+          ssh_key_stored,
+          ssh_password_stored,
+          ssh_passphrase_stored,
+          proxy_stored,
+      ) = externalize_credentials_for_update(&connection);
 
       let (tx, rx) = std::sync::mpsc::channel();
       tabular.custom_view_save_receiver = Some(rx);
@@ -1150,6 +1411,8 @@ pub(crate) fn save_connection_to_database(
               password_stored,
               ssh_key_stored,
               ssh_password_stored,
+              ssh_passphrase_stored,
+              proxy_stored,
           )
           .await
           .map_err(|e| e.to_string());
@@ -1164,10 +1427,11 @@ pub(crate) fn start_edit_connection(tabular: &mut window_egui::Tabular, connecti
         .iter()
         .find(|c| c.id == Some(connection_id))
     {
-        tabular.edit_connection = connection.clone();
+        tabular.edit_connection = connection.for_storage();
         // Reset test connection status saat buka edit dialog
         tabular.test_connection_status = None;
         tabular.test_connection_in_progress = false;
+        tabular.test_connection_receiver = None;
         tabular.show_edit_connection = true;
     } else {
         for conn in &tabular.connections {
@@ -1274,8 +1538,18 @@ pub(crate) fn initialize_database(tabular: &mut window_egui::Tabular) {
                             ssh_auth_method TEXT NOT NULL DEFAULT 'key',
                             ssh_private_key TEXT NOT NULL DEFAULT '',
                             ssh_password TEXT NOT NULL DEFAULT '',
+                            ssh_key_passphrase TEXT NOT NULL DEFAULT '',
                             ssh_accept_unknown_host_keys INTEGER NOT NULL DEFAULT 0,
-                            custom_views TEXT NOT NULL DEFAULT '[]'
+                            mssql_encrypt INTEGER NOT NULL DEFAULT 1,
+                            mssql_trust_server_certificate INTEGER NOT NULL DEFAULT 1,
+                            custom_views TEXT NOT NULL DEFAULT '[]',
+                            sqlite_attachments TEXT NOT NULL DEFAULT '[]',
+                            default_schema TEXT NOT NULL DEFAULT '',
+                            last_used_database TEXT DEFAULT NULL,
+                            use_credential_file INTEGER NOT NULL DEFAULT 0,
+                            label TEXT NOT NULL DEFAULT '',
+                            color TEXT NOT NULL DEFAULT '',
+                            proxy TEXT NOT NULL DEFAULT '{}'
                         )
                         "#
                     )
@@ -1319,6 +1593,12 @@ pub(crate) fn initialize_database(tabular: &mut window_egui::Tabular) {
                     .execute(&pool)
                     .await;
 
+                    let _ = sqlx::query(
+                        "ALTER TABLE connections ADD COLUMN sqlite_attachments TEXT NOT NULL DEFAULT '[]'"
+                    )
+                    .execute(&pool)
+                    .await;
+
                     let _ = sqlx::query(
                         "ALTER TABLE connections ADD COLUMN ssh_auth_method TEXT NOT NULL DEFAULT 'key'"
                     )
@@ -1337,18 +1617,64 @@ pub(crate) fn initialize_database(tabular: &mut window_egui::Tabular) {
                     .execute(&pool)
                     .await;
 
+                    let _ = sqlx::query(
+                        "ALTER TABLE connections ADD COLUMN ssh_key_passphrase TEXT NOT NULL DEFAULT ''"
+                    )
+                    .execute(&pool)
+                    .await;
+
                     let _ = sqlx::query(
                         "ALTER TABLE connections ADD COLUMN ssh_accept_unknown_host_keys INTEGER NOT NULL DEFAULT 0"
                     )
                     .execute(&pool)
                     .await;
 
+                    let _ = sqlx::query(
+                        "ALTER TABLE connections ADD COLUMN mssql_encrypt INTEGER NOT NULL DEFAULT 1"
+                    )
+                    .execute(&pool)
+                    .await;
+
+                    let _ = sqlx::query(
+                        "ALTER TABLE connections ADD COLUMN mssql_trust_server_certificate INTEGER NOT NULL DEFAULT 1"
+                    )
+                    .execute(&pool)
+                    .await;
+
                     let _ = sqlx::query(
                         "ALTER TABLE connections ADD COLUMN replication_master_id INTEGER DEFAULT NULL"
                     )
                     .execute(&pool)
                     .await;
 
+                    let _ = sqlx::query(
+                        "ALTER TABLE connections ADD COLUMN default_schema TEXT NOT NULL DEFAULT ''"
+                    )
+                    .execute(&pool)
+                    .await;
+
+                    let _ = sqlx::query(
+                        "ALTER TABLE connections ADD COLUMN last_used_database TEXT DEFAULT NULL"
+                    )
+                    .execute(&pool)
+                    .await;
+
+                    let _ = sqlx::query(
+                        "ALTER TABLE connections ADD COLUMN use_credential_file INTEGER NOT NULL DEFAULT 0"
+                    )
+                    .execute(&pool)
+                    .await;
+
+                    let _ = sqlx::query("ALTER TABLE connections ADD COLUMN label TEXT NOT NULL DEFAULT ''")
+                        .execute(&pool)
+                        .await;
+                    let _ = sqlx::query("ALTER TABLE connections ADD COLUMN color TEXT NOT NULL DEFAULT ''")
+                        .execute(&pool)
+                        .await;
+                    let _ = sqlx::query("ALTER TABLE connections ADD COLUMN proxy TEXT NOT NULL DEFAULT '{}'")
+                        .execute(&pool)
+                        .await;
+
                     // Create standalone folder paths table (folders that exist without connections)
                     let _ = sqlx::query(
                         r#"
@@ -1427,6 +1753,7 @@ pub(crate) fn initialize_database(tabular: &mut window_egui::Tabular) {
                             connection_id INTEGER NOT NULL,
                             connection_name TEXT NOT NULL,
                             executed_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                            database_name TEXT DEFAULT NULL,
                             FOREIGN KEY (connection_id) REFERENCES connections (id) ON DELETE CASCADE
                         )
                         "#
@@ -1434,6 +1761,13 @@ pub(crate) fn initialize_database(tabular: &mut window_egui::Tabular) {
                     .execute(&pool)
                     .await;
 
+                    // Add database_name column if it doesn't exist (for existing databases)
+                    let _ = sqlx::query(
+                        "ALTER TABLE query_history ADD COLUMN database_name TEXT DEFAULT NULL"
+                    )
+                    .execute(&pool)
+                    .await;
+
                     // Create row cache table for cached table data (first 100 rows)
                     let create_row_cache_result = sqlx::query(
                         r#"
@@ -1516,6 +1850,44 @@ pub(crate) fn initialize_database(tabular: &mut window_egui::Tabular) {
                     .execute(&pool)
                     .await;
 
+                    // Create result cache table for the opt-in per-query result cache
+                    let create_result_cache_result = sqlx::query(
+                        r#"
+                        CREATE TABLE IF NOT EXISTS query_result_cache (
+                            id INTEGER PRIMARY KEY AUTOINCREMENT,
+                            connection_id INTEGER NOT NULL,
+                            database_name TEXT NOT NULL,
+                            fingerprint TEXT NOT NULL,
+                            sql_text TEXT NOT NULL,
+                            headers_json TEXT NOT NULL,
+                            rows_json TEXT NOT NULL,
+                            byte_size INTEGER NOT NULL,
+                            cached_at INTEGER NOT NULL,
+                            expires_at INTEGER NOT NULL,
+                            FOREIGN KEY (connection_id) REFERENCES connections (id) ON DELETE CASCADE,
+                            UNIQUE(connection_id, database_name, fingerprint)
+                        )
+                        "#
+                    )
+                    .execute(&pool)
+                    .await;
+
+                    // When each table was last opened, for the table palette
+                    let create_table_access_result = sqlx::query(
+                        r#"
+                        CREATE TABLE IF NOT EXISTS table_access (
+                            connection_id INTEGER NOT NULL,
+                            database_name TEXT NOT NULL,
+                            table_name TEXT NOT NULL,
+                            last_opened_at INTEGER NOT NULL,
+                            FOREIGN KEY (connection_id) REFERENCES connections (id) ON DELETE CASCADE,
+                            PRIMARY KEY (connection_id, database_name, table_name)
+                        )
+                        "#
+                    )
+                    .execute(&pool)
+                    .await;
+
                     // Log exactly which table(s) failed instead of a vague message.
                     let mut startup_corruption_detected = false;
                     for (name, res) in [
@@ -1528,6 +1900,8 @@ pub(crate) fn initialize_database(tabular: &mut window_egui::Tabular) {
                         ("index_cache", &create_index_cache_result),
                         ("partition_cache", &create_partition_cache_result),
                         ("fk_cache", &create_fk_cache_result),
+                        ("query_result_cache", &create_result_cache_result),
+                        ("table_access", &create_table_access_result),
                     ] {
                         if let Err(e) = res {
                             warn!("Failed to create/verify table '{}': {}", name, e);
@@ -2124,7 +2498,14 @@ pub(crate) fn create_connections_folder_structure(
         Vec<&models::structs::ConnectionConfig>,
     > = std::collections::HashMap::new();
 
+    let visible = tabular.workspace_profiles.visible_connections.as_ref();
     for conn in &tabular.connections {
+        // Hidden by the active workspace profile
+        if let (Some(set), Some(id)) = (visible, conn.id)
+            && !set.contains(&id)
+        {
+            continue;
+        }
         let full_path = normalize_folder_path(conn.folder.as_deref());
         // Top-level segment — guaranteed non-empty after normalize_folder_path
         let top = full_path
@@ -2287,7 +2668,10 @@ pub(crate) fn reset_corrupted_sqlite_db(tabular: &mut window_egui::Tabular) -> b
 
     // If new pool was initialized, restore preserved data back into fresh SQLite DB
     if let Some(pool) = tabular.db_pool.clone() {
-        let conns_to_insert = preserved_connections.clone();
+        let conns_to_insert: Vec<_> = preserved_connections
+            .iter()
+            .map(|c| c.for_storage())
+            .collect();
         let hist_to_insert = preserved_history.clone();
         let rt = tokio::runtime::Runtime::new().unwrap();
         rt.block_on(async move {
@@ -2300,8 +2684,11 @@ pub(crate) fn reset_corrupted_sqlite_db(tabular: &mut window_egui::Tabular) -> b
                     INSERT OR REPLACE INTO connections (
                         id, name, host, port, username, password, database_name, connection_type,
                         folder, ssh_enabled, ssh_host, ssh_port, ssh_username, ssh_auth_method,
-                        ssh_private_key, ssh_password, ssh_accept_unknown_host_keys, custom_views, replication_master_id
-                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                        ssh_private_key, ssh_password, ssh_key_passphrase, ssh_accept_unknown_host_keys,
+                        mssql_encrypt, mssql_trust_server_certificate, custom_views, sqlite_attachments,
+                        replication_master_id, default_schema, last_used_database, use_credential_file,
+                        label, color, proxy
+                    ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                     "#
                 )
                 .bind(conn.id)
@@ -2320,9 +2707,19 @@ pub(crate) fn reset_corrupted_sqlite_db(tabular: &mut window_egui::Tabular) -> b
                 .bind(conn.ssh_auth_method.as_db_value())
                 .bind(&conn.ssh_private_key)
                 .bind(&conn.ssh_password)
+                .bind(&conn.ssh_key_passphrase)
                 .bind(if conn.ssh_accept_unknown_host_keys { 1 } else { 0 })
+                .bind(if conn.mssql_encrypt { 1 } else { 0 })
+                .bind(if conn.mssql_trust_server_certificate { 1 } else { 0 })
                 .bind(serde_json::to_string(&conn.custom_views).unwrap_or_else(|_| "[]".to_string()))
+                .bind(serde_json::to_string(&conn.sqlite_attachments).unwrap_or_else(|_| "[]".to_string()))
                 .bind(conn.replication_master_id)
+                .bind(&conn.default_schema)
+                .bind(&conn.last_used_database)
+                .bind(if conn.use_credential_file { 1 } else { 0 })
+                .bind(&conn.label)
+                .bind(&conn.color)
+                .bind(serde_json::to_string(&conn.proxy).unwrap_or_else(|_| "{}".to_string()))
                 .execute(pool.as_ref())
                 .await;
             }
@@ -2330,12 +2727,13 @@ pub(crate) fn reset_corrupted_sqlite_db(tabular: &mut window_egui::Tabular) -> b
             // Restore query history
             for hist in &hist_to_insert {
                 let _ = sqlx::query(
-                    "INSERT INTO query_history (query_text, connection_id, connection_name, executed_at) VALUES (?, ?, ?, ?)"
+                    "INSERT INTO query_history (query_text, connection_id, connection_name, executed_at, database_name) VALUES (?, ?, ?, ?, ?)"
                 )
                 .bind(&hist.query)
                 .bind(hist.connection_id)
                 .bind(&hist.connection_name)
                 .bind(&hist.executed_at)
+                .bind(&hist.database_name)
                 .execute(pool.as_ref())
                 .await;
             }
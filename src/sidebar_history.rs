@@ -43,13 +43,84 @@ fn format_date_for_display(date_str: &str) -> String {
     }
 }
 
+/// Data stored on a history tree node: the entry as JSON.
+pub(crate) fn encode_history_node(item: &models::structs::HistoryItem) -> String {
+    serde_json::to_string(item).unwrap_or_default()
+}
+
+pub(crate) fn decode_history_node(data: &str) -> Option<models::structs::HistoryItem> {
+    serde_json::from_str(data).ok()
+}
+
+/// Compact tab title for a history entry: `Hist-YYMMDD HH:MM:SS`.
+pub(crate) fn history_tab_title(executed_at: &str) -> String {
+    // executed_at is e.g. "2026-03-11 11:45:56" or "2026-03-11T11:45:56"
+    let ts = executed_at.trim();
+    let yy = ts.get(2..4).unwrap_or("");
+    let mm = ts.get(5..7).unwrap_or("");
+    let dd = ts.get(8..10).unwrap_or("");
+    let time_part = ts.get(11..19).or_else(|| ts.get(11..)).unwrap_or("");
+    if !yy.is_empty() && !time_part.is_empty() {
+        format!("Hist-{}{}{} {}", yy, mm, dd, time_part)
+    } else {
+        "Hist".to_string()
+    }
+}
+
+/// Open a history entry in a new tab bound to the connection and database it
+/// ran in, and optionally run it. When the connection no longer exists (by
+/// id, then by name) the tab opens unbound and the connection picker is shown.
+pub(crate) fn open_history_entry(
+    tabular: &mut window_egui::Tabular,
+    item: models::structs::HistoryItem,
+    run: bool,
+) {
+    let title = history_tab_title(&item.executed_at);
+    let connection_id = tabular
+        .connections
+        .iter()
+        .find(|c| c.id == Some(item.connection_id))
+        .or_else(|| {
+            tabular
+                .connections
+                .iter()
+                .find(|c| c.name == item.connection_name)
+        })
+        .and_then(|c| c.id);
+    let Some(connection_id) = connection_id else {
+        debug!(
+            "Connection '{}' from history not found; asking for one",
+            item.connection_name
+        );
+        crate::editor::create_new_tab(tabular, title, item.query.clone());
+        if run {
+            tabular.pending_query = item.query;
+            tabular.auto_execute_after_connection = true;
+        }
+        tabular.show_connection_selector = true;
+        return;
+    };
+    crate::editor::create_new_tab_with_connection_and_database(
+        tabular,
+        title,
+        item.query.clone(),
+        Some(connection_id),
+        item.database_name,
+    );
+    crate::connection::ensure_background_pool_creation(tabular, connection_id);
+    if run {
+        tabular.pending_query = item.query;
+        crate::editor::execute_query(tabular);
+    }
+}
+
 pub(crate) fn load_query_history(tabular: &mut window_egui::Tabular) {
     if let Some(pool) = &tabular.db_pool {
         let rt = tokio::runtime::Runtime::new().unwrap();
 
         let result = rt.block_on(async {
-                match sqlx::query_as::<_, (i64, String, i64, String, String)>(
-                    "SELECT id, query_text, connection_id, connection_name, executed_at FROM query_history ORDER BY executed_at DESC LIMIT 100"
+                match sqlx::query_as::<_, (i64, String, i64, String, String, Option<String>)>(
+                    "SELECT id, query_text, connection_id, connection_name, executed_at, database_name FROM query_history ORDER BY executed_at DESC LIMIT 100"
                 )
                 .fetch_all(pool.as_ref())
                 .await
@@ -63,6 +134,7 @@ pub(crate) fn load_query_history(tabular: &mut window_egui::Tabular) {
                                 connection_id: row.2,
                                 connection_name: row.3,
                                 executed_at: row.4,
+                                database_name: row.5.filter(|d| !d.is_empty()),
                             });
                         }
                         Some(history_items)
@@ -97,8 +169,10 @@ pub(crate) fn save_query_to_history(
     tabular: &mut window_egui::Tabular,
     query: &str,
     connection_id: i64,
+    database_name: Option<String>,
 ) {
     let trimmed = query.trim();
+    let database_name = database_name.filter(|d| !d.is_empty());
     if trimmed.is_empty() {
         debug!("[save_query_to_history] Skipping empty query string");
         return;
@@ -119,8 +193,11 @@ pub(crate) fn save_query_to_history(
         connection_id,
         connection_name: connection_name.clone(),
         executed_at: now_str,
+        database_name: database_name.clone(),
     };
-    if !tabular.history_items.iter().any(|h| h.query == trimmed && h.connection_id == connection_id) {
+    if !tabular.history_items.iter().any(|h| {
+        h.query == trimmed && h.connection_id == connection_id && h.database_name == database_name
+    }) {
         tabular.history_items.insert(0, new_item);
         refresh_history_tree(tabular);
     }
@@ -133,11 +210,12 @@ pub(crate) fn save_query_to_history(
         let rt = tokio::runtime::Runtime::new().unwrap();
         let insert_res = rt.block_on(async move {
             let res = sqlx::query(
-                "INSERT INTO query_history (query_text, connection_id, connection_name) VALUES (?, ?, ?)"
+                "INSERT INTO query_history (query_text, connection_id, connection_name, database_name) VALUES (?, ?, ?, ?)"
             )
             .bind(&query_text)
             .bind(connection_id)
             .bind(&conn_name)
+            .bind(&database_name)
             .execute(pool.as_ref())
             .await;
 
@@ -211,9 +289,9 @@ pub(crate) fn refresh_history_tree(tabular: &mut window_egui::Tabular) {
                 models::enums::NodeType::QueryHistItem,
             );
             hist_node.connection_id = Some(item.connection_id);
-            // Store connection info, timestamp, and original query in file_path field
-            // Format: "connection_name||executed_at||original_query"
-            hist_node.file_path = Some(format!("{}||{}||{}", item.connection_name, item.executed_at, item.query));
+            hist_node.database_name = item.database_name.clone();
+            // The whole entry travels in file_path (see decode_history_node)
+            hist_node.file_path = Some(encode_history_node(item));
             date_node.children.push(hist_node);
         }
 
@@ -264,3 +342,28 @@ pub(crate) fn filter_history_tree(tabular: &mut window_egui::Tabular) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_nodes_keep_the_connection_context() {
+        let item = models::structs::HistoryItem {
+            id: Some(7),
+            query: "SELECT 'a||b'".to_string(),
+            connection_id: 3,
+            connection_name: "prod || replica".to_string(),
+            executed_at: "2026-03-11 11:45:56".to_string(),
+            database_name: Some("sales".to_string()),
+        };
+        let decoded = decode_history_node(&encode_history_node(&item)).unwrap();
+        assert_eq!(decoded.query, item.query);
+        assert_eq!(decoded.connection_id, 3);
+        assert_eq!(decoded.connection_name, item.connection_name);
+        assert_eq!(decoded.database_name.as_deref(), Some("sales"));
+
+        assert_eq!(history_tab_title(&item.executed_at), "Hist-260311 11:45:56");
+        assert_eq!(history_tab_title(""), "Hist");
+    }
+}
@@ -36,6 +36,151 @@ fn parse_query_metadata(content: &str) -> QueryMetadata {
     meta
 }
 
+/// Keys of the saved-query metadata lines (`-- @key: value`).
+fn meta_key(line: &str) -> Option<(String, &str)> {
+    let (key, value) = line
+        .trim()
+        .strip_prefix("--")?
+        .trim_start()
+        .strip_prefix('@')?
+        .split_once(':')?;
+    let key = key.trim().to_lowercase();
+    matches!(key.as_str(), "name" | "desc" | "description" | "tags").then(|| (key, value.trim()))
+}
+
+/// Tags typed as `a, b` or `#a #b`.
+pub(crate) fn split_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(|c: char| c == ',' || c.is_whitespace()) {
+        let tag = tag.trim().trim_start_matches('#');
+        if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// Lines of the leading comment block: everything before the first line
+/// that is neither blank nor a `--` comment.
+fn leading_comment_lines(content: &str) -> usize {
+    content
+        .lines()
+        .take_while(|l| l.trim().is_empty() || l.trim_start().starts_with("--"))
+        .count()
+}
+
+/// Read `-- @name:`, `-- @desc:` and `-- @tags:` from the leading comment
+/// block. Other comments and malformed lines are ignored, so any file loads.
+pub(crate) fn parse_saved_query_meta(content: &str) -> models::structs::SavedQueryMeta {
+    let mut meta = models::structs::SavedQueryMeta::default();
+    for line in content.lines().take(leading_comment_lines(content)) {
+        let Some((key, value)) = meta_key(line) else {
+            continue;
+        };
+        match key.as_str() {
+            "name" => meta.name = value.to_string(),
+            "tags" => {
+                for tag in split_tags(value) {
+                    if !meta.tags.iter().any(|t| t.eq_ignore_ascii_case(&tag)) {
+                        meta.tags.push(tag);
+                    }
+                }
+            }
+            _ => {
+                // Repeated description lines form one multi-line description
+                if !meta.description.is_empty() {
+                    meta.description.push('\n');
+                }
+                meta.description.push_str(value);
+            }
+        }
+    }
+    meta
+}
+
+/// `content` with its metadata lines replaced by `meta` (removed when `meta`
+/// is empty). Other leading comments stay where they are.
+pub(crate) fn with_saved_query_meta(
+    content: &str,
+    meta: &models::structs::SavedQueryMeta,
+) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    if !meta.name.trim().is_empty() {
+        lines.push(format!("-- @name: {}", meta.name.trim()));
+    }
+    for desc in meta.description.lines().filter(|l| !l.trim().is_empty()) {
+        lines.push(format!("-- @desc: {}", desc.trim()));
+    }
+    if !meta.tags.is_empty() {
+        lines.push(format!("-- @tags: {}", meta.tags.join(", ")));
+    }
+    let leading = leading_comment_lines(content);
+    lines.extend(
+        content
+            .lines()
+            .enumerate()
+            .filter(|(i, l)| *i >= leading || meta_key(l).is_none())
+            .map(|(_, l)| l.to_string()),
+    );
+    let mut out = lines.join("\n");
+    if content.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+fn collect_saved_query_meta(
+    nodes: &[models::structs::TreeNode],
+    out: &mut std::collections::HashMap<String, models::structs::SavedQueryMeta>,
+) {
+    for node in nodes {
+        if node.node_type == models::enums::NodeType::Query
+            && let Some(path) = &node.file_path
+            && let Ok(content) = std::fs::read_to_string(path)
+        {
+            let meta = parse_saved_query_meta(&content);
+            if !meta.is_empty() {
+                out.insert(path.clone(), meta);
+            }
+        }
+        collect_saved_query_meta(&node.children, out);
+    }
+}
+
+/// The queries tree narrowed to files whose tags match every term of the tag
+/// filter. Folders are kept, expanded, when something inside them matches.
+pub(crate) fn filter_queries_by_tag(
+    nodes: &[models::structs::TreeNode],
+    metas: &std::collections::HashMap<String, models::structs::SavedQueryMeta>,
+    terms: &[String],
+) -> Vec<models::structs::TreeNode> {
+    let mut out = Vec::new();
+    for node in nodes {
+        match node.node_type {
+            models::enums::NodeType::QueryFolder => {
+                let children = filter_queries_by_tag(&node.children, metas, terms);
+                if !children.is_empty() {
+                    let mut folder = node.clone();
+                    folder.children = children;
+                    folder.is_expanded = true;
+                    out.push(folder);
+                }
+            }
+            _ => {
+                if node
+                    .file_path
+                    .as_ref()
+                    .and_then(|p| metas.get(p))
+                    .is_some_and(|m| m.matches_tags(terms))
+                {
+                    out.push(node.clone());
+                }
+            }
+        }
+    }
+    out
+}
+
 pub(crate) fn load_queries_from_directory(tabular: &mut window_egui::Tabular) {
     tabular.queries_tree.clear();
 
@@ -54,6 +199,10 @@ pub(crate) fn load_queries_from_directory(tabular: &mut window_egui::Tabular) {
             _ => a.name.cmp(&b.name), // Alphabetical within same type
         }
     });
+
+    let mut metas = std::collections::HashMap::new();
+    collect_saved_query_meta(&tabular.queries_tree, &mut metas);
+    tabular.saved_query_meta = metas;
 }
 
 pub(crate) fn create_query_folder(
@@ -131,6 +280,7 @@ pub(crate) fn move_query_to_folder(
     // Move the file
     std::fs::rename(source_path, &target_file_path)
         .map_err(|e| format!("Failed to move file: {}", e))?;
+    crate::query_presets::move_presets(tabular, query_file_path, &target_file_path);
 
     // Close any open tabs for this file and update with new path
     editor::close_tabs_for_file(tabular, query_file_path);
@@ -154,6 +304,7 @@ pub(crate) fn move_query_to_root(
     // Move the file to root
     std::fs::rename(source_path, &target_file_path)
         .map_err(|e| format!("Failed to move file: {}", e))?;
+    crate::query_presets::move_presets(tabular, query_file_path, &target_file_path);
 
     // Close any open tabs for this file and update with new path
     editor::close_tabs_for_file(tabular, query_file_path);
@@ -336,7 +487,10 @@ pub(crate) fn open_query_file(
         };
     let effective_connection_id = resolved_connection_id.or(auto_single_connection);
 
+    let tab_id = tabular.next_tab_id;
+    tabular.next_tab_id += 1;
     let new_tab = models::structs::QueryTab {
+        id: tab_id,
         title: filename,
         content: content.clone(),
         file_path: Some(file_path.to_string()),
@@ -355,7 +509,7 @@ pub(crate) fn open_query_file(
         active_result_index: 0,
         is_table_browse_mode: false,
         current_page: 0,
-        page_size: 0,
+        page_size: tabular.default_page_size,
         total_rows: 0,
         base_query: String::new(), // Empty base query for file queries
         dba_special_mode: None,
@@ -370,6 +524,17 @@ pub(crate) fn open_query_file(
         tx_mode: false,
         tx_active: false,
         session: None,
+        split_ratio: None,
+        record_view: false,
+        transposed: false,
+        bookmarks: Vec::new(),
+        hidden_columns: Vec::new(),
+        hidden_columns_headers: Vec::new(),
+        index_suggestions: Vec::new(),
+        pinned_results: Vec::new(),
+        active_pinned: None,
+        live_result: None,
+        param_values: Vec::new(),
     };
 
     tabular.query_tabs.push(new_tab);
@@ -380,6 +545,8 @@ pub(crate) fn open_query_file(
     tabular.last_highlight_hash = None;
     tabular.sql_semantic_snapshot = None;
     tabular.current_object_ddl = None;
+    tabular.current_page = 0;
+    tabular.page_size = tabular.default_page_size;
 
     // If the file specified a connection, set it for active tab and eagerly create the pool
     if let Some(conn_id) = effective_connection_id {
@@ -432,6 +599,7 @@ pub(crate) fn handle_query_remove_request_by_hash(
         // Remove the file from filesystem
         match std::fs::remove_file(&file_path) {
             Ok(()) => {
+                crate::query_presets::remove_presets(tabular, &file_path);
                 // Set needs_refresh flag for next update cycle
                 tabular.needs_refresh = true;
 
@@ -483,3 +651,36 @@ pub(crate) fn find_query_file_by_hash(hash: i64) -> Option<String> {
 
     search_in_dir(&query_dir, hash)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saved_query_meta_round_trips_through_leading_comments() {
+        let content = "-- tabular: connection_id=3\n-- @name: Monthly revenue\n-- @desc: Paid invoices\n-- @desc: grouped by month\n-- @tags: billing, #reports\nSELECT 1;\n-- @tags: ignored\n";
+        let meta = parse_saved_query_meta(content);
+        assert_eq!(meta.name, "Monthly revenue");
+        assert_eq!(meta.description, "Paid invoices\ngrouped by month");
+        assert_eq!(meta.tags, vec!["billing", "reports"]);
+        assert!(meta.matches_tags(&["BILL".to_string()]));
+        assert!(!meta.matches_tags(&["billing".to_string(), "ops".to_string()]));
+
+        // Malformed or unknown lines are ignored
+        assert!(parse_saved_query_meta("-- @tags\n-- @owner: me\nSELECT 1").is_empty());
+
+        let renamed = models::structs::SavedQueryMeta {
+            name: "Revenue".into(),
+            tags: vec!["billing".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            with_saved_query_meta(content, &renamed),
+            "-- @name: Revenue\n-- @tags: billing\n-- tabular: connection_id=3\nSELECT 1;\n-- @tags: ignored\n"
+        );
+        assert_eq!(
+            with_saved_query_meta("-- @name: x\nSELECT 1", &Default::default()),
+            "SELECT 1"
+        );
+    }
+}
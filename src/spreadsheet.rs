@@ -66,7 +66,8 @@ pub trait SpreadsheetOperations {
         target_table_for_update: Option<&str>,
     ) -> Option<String>;
     
-    fn spreadsheet_generate_sql(&self) -> Option<String>;
+    /// One statement per pending operation, in order.
+    fn spreadsheet_generate_statements(&self) -> Vec<String>;
 
     fn spreadsheet_row_where_all_columns(
         &self,
@@ -84,6 +85,7 @@ pub trait SpreadsheetOperations {
             let state = self.get_spreadsheet_state_mut();
             state.editing_cell = Some((row, col));
             state.cell_edit_text = val;
+            state.edit_focus_requested = false;
         }
     }
 
@@ -499,7 +501,7 @@ pub trait SpreadsheetOperations {
 
     // This method needs to be implemented by the struct that implements this trait
     // It should execute the SQL and handle the response appropriately
-    fn execute_spreadsheet_sql(&mut self, sql: String);
+    fn execute_spreadsheet_sql(&mut self, statements: Vec<String>);
 }
 
 // Implement the SpreadsheetOperations trait for Tabular
@@ -703,47 +705,53 @@ impl SpreadsheetOperations for Tabular {
         }
     }
 
-    fn execute_spreadsheet_sql(&mut self, sql: String) {
+    fn execute_spreadsheet_sql(&mut self, statements: Vec<String>) {
         if let Some(conn_id) = self.current_connection_id {
-            if let Some((headers, data)) =
-                connection::execute_query_with_connection(self, conn_id, sql)
-            {
-                // Detect error tables returned by executor (headers == ["Error"]) and treat as failure
-                let is_error_table = headers.len() == 1 && headers[0].eq_ignore_ascii_case("error");
-                if is_error_table {
-                    let msg = data
-                        .first()
-                        .and_then(|r| r.first())
-                        .cloned()
-                        .unwrap_or_else(|| "Unknown query error".to_string());
-                    debug!("❌ SQL execution returned error table: {}", msg);
-                    self.error_message = msg;
-                    self.show_error_message = true;
-                    // Do NOT clear pending operations on failure
-                } else {
-                    debug!("🔥 SQL executed successfully, clearing pending operations");
-                    self.spreadsheet_state.pending_operations.clear();
-                    self.spreadsheet_state.is_dirty = false;
-
-                    // Clear newly created rows highlight after successful save
-                    self.newly_created_rows.clear();
-
-                    // Refresh grid after save so inserted rows become visible
-                    if self.is_table_browse_mode {
-                        if self.use_server_pagination && !self.current_base_query.is_empty() {
-                            // Re-run current page of the base query
-                            self.execute_paginated_query();
-                        } else {
-                            // Client-side mode: simply re-sync current page slice
-                            self.update_current_page_data();
-                        }
+            if self.spreadsheet_save_receiver.is_some() {
+                debug!("Previous save still running, ignoring this one");
+                return;
+            }
+            let ops = self.spreadsheet_state.pending_operations.clone();
+            let transactional = self
+                .connections
+                .iter()
+                .find(|c| c.id == Some(conn_id))
+                .is_some_and(|c| connection::session::supports_transactions(&c.connection_type));
+            // Several statements (e.g. a pasted block) are saved all-or-nothing,
+            // on the runtime; poll_spreadsheet_save finishes the save
+            let outcome = if statements.len() > 1 && transactional {
+                let database = self
+                    .query_tabs
+                    .get(self.active_tab_index)
+                    .and_then(|t| t.database_name.clone())
+                    .filter(|s| !s.is_empty());
+                match connection::session::spawn_in_transaction(self, conn_id, database, statements) {
+                    Ok(rx) => {
+                        self.spreadsheet_save_receiver = Some((ops, rx));
+                        return;
                     }
+                    Err(e) => Err(e),
                 }
             } else {
-                debug!("🔥 SQL execution failed");
-                self.error_message = "Failed to save table changes".to_string();
-                self.show_error_message = true;
-            }
+                match connection::execute_query_with_connection(self, conn_id, statements.join(";\n")) {
+                    // Detect error tables returned by executor (headers == ["Error"]) and treat as failure
+                    Some((headers, data))
+                        if headers.len() == 1 && headers[0].eq_ignore_ascii_case("error") =>
+                    {
+                        Err(data
+                            .first()
+                            .and_then(|r| r.first())
+                            .cloned()
+                            .unwrap_or_else(|| "Unknown query error".to_string()))
+                    }
+                    Some(_) => Ok(()),
+                    None => {
+                        debug!("🔥 SQL execution failed");
+                        Err("Failed to save table changes".to_string())
+                    }
+                }
+            };
+            finish_spreadsheet_save(self, &ops, outcome);
         }
     }
 
@@ -761,13 +769,14 @@ impl SpreadsheetOperations for Tabular {
             let state = self.get_spreadsheet_state_mut();
             state.editing_cell = Some((row, col));
             state.cell_edit_text = val;
+            state.edit_focus_requested = false;
         }
     }
 
     fn spreadsheet_finish_cell_edit(&mut self, save: bool) {
         let editing_cell = self.get_spreadsheet_state().editing_cell;
         if let Some((row, col)) = editing_cell {
-            let new_val = self.get_spreadsheet_state().cell_edit_text.clone();
+            let mut new_val = self.get_spreadsheet_state().cell_edit_text.clone();
             self.get_spreadsheet_state_mut().cell_edit_text.clear();
             self.get_spreadsheet_state_mut().editing_cell = None;
 
@@ -786,6 +795,24 @@ impl SpreadsheetOperations for Tabular {
                             .cloned()
                     });
 
+                // Values edited in the display timezone are written back in the stored zone
+                if let Some(old) = old_val.as_ref()
+                    && self.timezone_for_edit_export
+                    && crate::data_table::temporal_columns(self)
+                        .get(col)
+                        .copied()
+                        .unwrap_or(false)
+                {
+                    let tz = self.display_timezone;
+                    if crate::data_table::convert_for_display(old, tz).as_deref() == Some(new_val.as_str()) {
+                        new_val = old.clone();
+                    } else if let Some(stored) =
+                        crate::data_table::convert_from_display(&new_val, old, tz)
+                    {
+                        new_val = stored;
+                    }
+                }
+
                 let maybe_old = old_val.clone();
                 match maybe_old {
                     Some(ref old) if *old != new_val => {
@@ -1129,17 +1156,22 @@ impl SpreadsheetOperations for Tabular {
         }
     }
 
-    fn spreadsheet_generate_sql(&self) -> Option<String> {
-        debug!("🔥 spreadsheet_generate_sql called");
+    fn spreadsheet_generate_statements(&self) -> Vec<String> {
+        debug!("🔥 spreadsheet_generate_statements called");
 
-        let conn_id = self.get_current_connection_id()?;
+        let Some(conn_id) = self.get_current_connection_id() else {
+            return Vec::new();
+        };
         debug!("🔥 Found connection ID: {}", conn_id);
 
-        let conn = self
+        let Some(conn) = self
             .get_connections()
             .iter()
             .find(|c| c.id == Some(conn_id))
-            .cloned()?;
+            .cloned()
+        else {
+            return Vec::new();
+        };
         debug!("🔥 Found connection config");
 
         let table = self.spreadsheet_extract_table_name();
@@ -1164,7 +1196,7 @@ impl SpreadsheetOperations for Tabular {
                 log::debug!("🔥 Col {}: name='{}', table='{:?}', orig='{:?}'", i, m.name, m.table_name, m.original_name);
             }
         } else {
-            log::warn!("🔥 No metadata found in spreadsheet_generate_sql override");
+            log::warn!("🔥 No metadata found in spreadsheet_generate_statements override");
         }
 
         // Determine derived primary keys from metadata if available
@@ -1346,11 +1378,7 @@ impl SpreadsheetOperations for Tabular {
                 }
             }
         }
-        if stmts.is_empty() {
-            None
-        } else {
-            Some(stmts.join(";\n"))
-        }
+        stmts
     }
 
     fn spreadsheet_save_changes(&mut self) {
@@ -1405,19 +1433,17 @@ impl SpreadsheetOperations for Tabular {
             }
         }
 
-        if let Some(sql) = self.spreadsheet_generate_sql() {
-            debug!("Generated SQL: {}", sql);
+        let statements = self.spreadsheet_generate_statements();
+        if !statements.is_empty() {
+            debug!("Generated SQL: {}", statements.join(";\n"));
             if let Some(conn_id) = self.get_current_connection_id() {
                 debug!("Executing SQL with connection {}", conn_id);
 
-                // Execute without transaction wrapper to avoid MySQL prepared statement issues
-                debug!("Executing SQL: {}", sql);
-
                 // Note: This is a bit tricky because we need to call connection::execute_query_with_connection
                 // but this trait doesn't know about the full Tabular struct. We'll need to implement this
                 // in the actual implementation of the trait.
                 // in the actual implementation of the trait.
-                self.execute_spreadsheet_sql(sql);
+                self.execute_spreadsheet_sql(statements);
                 // SUCCESS: User requested to stop editing cell on success
                 self.spreadsheet_finish_cell_edit(false);
             } else {
@@ -1443,3 +1469,64 @@ impl SpreadsheetOperations for Tabular {
         self.spreadsheet_build_where_clause(conn, row, headers, pk_columns, None, None)
     }
 }
+
+/// Apply the outcome of a grid save: keep the edits on success, otherwise
+/// show the error and put failed updates back.
+fn finish_spreadsheet_save(
+    tabular: &mut Tabular,
+    ops: &[models::structs::CellEditOperation],
+    outcome: Result<(), String>,
+) {
+    match outcome {
+        Err(msg) => {
+            debug!("❌ SQL execution failed: {}", msg);
+            tabular.error_message = msg;
+            tabular.show_error_message = true;
+            // Failed updates go back to their old value; inserts and
+            // deletes stay pending
+            crate::data_table::revert_failed_updates(tabular, ops);
+            crate::data_table::flash_saved_edits(tabular, ops, false);
+        }
+        Ok(()) => {
+            debug!("🔥 SQL executed successfully, clearing pending operations");
+            // Edits made while the save ran stay pending
+            let state = &mut tabular.spreadsheet_state;
+            let saved = ops.len().min(state.pending_operations.len());
+            state.pending_operations.drain(..saved);
+            state.is_dirty = !state.pending_operations.is_empty();
+
+            // Clear newly created rows highlight after successful save
+            tabular.newly_created_rows.clear();
+            crate::data_table::flash_saved_edits(tabular, ops, true);
+
+            // Refresh grid after save so inserted rows become visible
+            if tabular.is_table_browse_mode {
+                if tabular.use_server_pagination && !tabular.current_base_query.is_empty() {
+                    // Re-run current page of the base query
+                    tabular.execute_paginated_query();
+                } else {
+                    // Client-side mode: simply re-sync current page slice
+                    tabular.update_current_page_data();
+                }
+            }
+        }
+    }
+}
+
+/// Finish a grid save that runs in a transaction on the runtime.
+pub(crate) fn poll_spreadsheet_save(tabular: &mut Tabular) {
+    let Some((ops, rx)) = tabular.spreadsheet_save_receiver.take() else {
+        return;
+    };
+    match rx.try_recv() {
+        Ok(outcome) => finish_spreadsheet_save(tabular, &ops, outcome),
+        Err(std::sync::mpsc::TryRecvError::Empty) => {
+            tabular.spreadsheet_save_receiver = Some((ops, rx));
+        }
+        Err(std::sync::mpsc::TryRecvError::Disconnected) => finish_spreadsheet_save(
+            tabular,
+            &ops,
+            Err("The save was interrupted".to_string()),
+        ),
+    }
+}
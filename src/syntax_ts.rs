@@ -44,7 +44,7 @@
 
 #![allow(dead_code)]
 
-use eframe::egui::text::LayoutJob; // For public highlight API (ported from legacy syntax.rs)
+use eframe::egui::text::{LayoutJob, LayoutSection}; // For public highlight API (ported from legacy syntax.rs)
 use eframe::egui::{Color32, TextFormat};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
@@ -1472,34 +1472,137 @@ fn is_sql_keyword(token: &str) -> bool {
 
 // ---------------- Legacy heuristic highlighter (ported from syntax.rs) ----------------
 
-/// Cached highlighting with hash-based lookup.
-/// Keeps only the most recent 2 entries to bound memory while still serving
-/// cache hits during idle/re-render without re-computing the same text twice.
-pub fn highlight_text_cached(
-    text: &str,
-    lang: LanguageKind,
-    dark: bool,
-    cache: &mut std::collections::HashMap<u64, LayoutJob>,
-) -> LayoutJob {
-    let mut hasher = DefaultHasher::new();
-    text.hash(&mut hasher);
-    lang.hash(&mut hasher);
-    dark.hash(&mut hasher);
-    let hash = hasher.finish();
-    if let Some(cached_job) = cache.get(&hash) {
-        return cached_job.clone();
+/// Everything besides the text that decides how a buffer is colored.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HighlightContext {
+    pub lang: LanguageKind,
+    pub theme: crate::models::structs::EditorColorTheme,
+    pub dark: bool,
+    pub db_type: Option<crate::models::enums::DatabaseType>,
+}
+
+struct HighlightSlot {
+    context: HighlightContext,
+    text_hash: u64,
+    job: LayoutJob,
+    lines: LineFragments,
+}
+
+/// Lexer state carried from the end of one line into the next, so constructs
+/// spanning lines (block comments, quoted strings) color the following lines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineState {
+    #[default]
+    Normal,
+    BlockComment,
+    String,
+}
+
+/// A line is re-tokenized only when its text, its start state or its
+/// `--AI` block membership changes; a changed end state therefore
+/// invalidates the lines below it on the next pass automatically.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct LineKey {
+    text_hash: u64,
+    state_in: LineState,
+    ai_block: bool,
+}
+
+#[derive(Clone)]
+struct LineFragment {
+    // Byte ranges are relative to the start of the line
+    sections: Vec<LayoutSection>,
+    state_out: LineState,
+}
+
+type LineFragments = std::collections::HashMap<LineKey, LineFragment>;
+
+/// Small LRU of highlighted buffers with one slot per [`HighlightContext`].
+/// Re-highlighting a context with different text replaces its slot, so edits
+/// never pile up dead layout jobs; switching theme or connection gets its own
+/// slot instead of reusing colors computed for another context.
+pub struct HighlightCache {
+    slots: Vec<HighlightSlot>, // least recently used first
+    capacity: usize,
+    hits: u64,
+    misses: u64,
+    last_pass_lines: (usize, usize),
+}
+
+impl Default for HighlightCache {
+    fn default() -> Self {
+        Self::with_capacity(8)
+    }
+}
+
+impl HighlightCache {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vec::with_capacity(capacity),
+            capacity: capacity.max(1),
+            hits: 0,
+            misses: 0,
+            last_pass_lines: (0, 0),
+        }
     }
-    // Evict stale entries early (keep at most 2) to avoid unbounded memory growth during typing
-    if cache.len() >= 2 {
-        cache.clear();
+
+    pub fn highlight(&mut self, text: &str, context: &HighlightContext) -> LayoutJob {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let text_hash = hasher.finish();
+        let mut previous_lines = LineFragments::new();
+        if let Some(pos) = self.slots.iter().position(|s| &s.context == context) {
+            let slot = self.slots.remove(pos);
+            if slot.text_hash == text_hash {
+                self.hits += 1;
+                let job = slot.job.clone();
+                self.slots.push(slot);
+                return job;
+            }
+            // Same context, edited text: unchanged lines are reused below
+            previous_lines = slot.lines;
+        }
+        self.misses += 1;
+        if self.slots.len() >= self.capacity {
+            self.slots.remove(0);
+        }
+        sync_semantics(text, context.lang);
+        let (job, lines, counts) =
+            highlight_lines(text, context.lang, context.dark, &previous_lines);
+        self.last_pass_lines = counts;
+        self.slots.push(HighlightSlot {
+            context: context.clone(),
+            text_hash,
+            job: job.clone(),
+            lines,
+        });
+        job
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// `(hits, misses)` since creation.
+    pub fn stats(&self) -> (u64, u64) {
+        (self.hits, self.misses)
+    }
+
+    /// `(reused, re-tokenized)` line counts of the most recent miss.
+    pub fn last_pass_lines(&self) -> (usize, usize) {
+        self.last_pass_lines
     }
-    let job = highlight_text(text, lang, dark);
-    cache.insert(hash, job.clone());
-    job
 }
 
-/// Whole text highlighter (tree-sitter path is kept for side effects only).
-pub fn highlight_text(text: &str, lang: LanguageKind, dark: bool) -> LayoutJob {
+fn sync_semantics(text: &str, lang: LanguageKind) {
     if matches!(
         lang,
         LanguageKind::Sql | LanguageKind::Redis | LanguageKind::Mongo
@@ -1510,74 +1613,194 @@ pub fn highlight_text(text: &str, lang: LanguageKind, dark: bool) -> LayoutJob {
             let _ = ensure_semantics(lang, text);
         }
     }
-    let mut job = LayoutJob::default();
+    #[cfg(not(feature = "tree_sitter_sequel"))]
+    let _ = text;
+}
 
-    // Pre-scan for --AI ... -- blocks so we can highlight them with AI prompt color.
-    // Build a set of line indices that fall inside an AI block (inclusive of --AI and -- lines).
+/// Whole text highlighter (tree-sitter path is kept for side effects only).
+pub fn highlight_text(text: &str, lang: LanguageKind, dark: bool) -> LayoutJob {
+    sync_semantics(text, lang);
+    highlight_lines(text, lang, dark, &LineFragments::new()).0
+}
+
+/// Highlight `text` line by line, reusing fragments from `previous` whose key
+/// still matches. Returns the job, the fragments for this text and
+/// `(reused, re-tokenized)` line counts.
+fn highlight_lines(
+    text: &str,
+    lang: LanguageKind,
+    dark: bool,
+    previous: &LineFragments,
+) -> (LayoutJob, LineFragments, (usize, usize)) {
+    let mut job = LayoutJob {
+        text: text.to_string(),
+        ..Default::default()
+    };
+    let mut fragments = LineFragments::new();
+    let mut counts = (0, 0);
+    let mut state = LineState::Normal;
+    // --AI ... -- blocks get the AI prompt color (inclusive of the --AI and -- lines)
     let mut in_ai_block = false;
-    let mut ai_block_lines = std::collections::HashSet::<usize>::new();
-    for (i, line) in text.lines().enumerate() {
+    let mut line_start = 0usize;
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            job.sections.push(LayoutSection {
+                leading_space: 0.0,
+                byte_range: (line_start - 1).into()..line_start.into(),
+                format: TextFormat::default(),
+            });
+        }
         let trimmed = line.trim();
         if trimmed == "--AI" || trimmed.starts_with("--AI ") || trimmed.starts_with("--AI\t") {
             in_ai_block = true;
         }
-        if in_ai_block {
-            ai_block_lines.insert(i);
-        }
-        if in_ai_block && !ai_block_lines.is_empty() && trimmed == "--" {
+        let ai_block = in_ai_block;
+        if in_ai_block && trimmed == "--" {
             in_ai_block = false;
         }
+
+        let mut hasher = DefaultHasher::new();
+        line.hash(&mut hasher);
+        let key = LineKey {
+            text_hash: hasher.finish(),
+            state_in: state,
+            ai_block,
+        };
+        let fragment = match fragments.get(&key).or_else(|| previous.get(&key)) {
+            Some(f) => {
+                counts.0 += 1;
+                f.clone()
+            }
+            None => {
+                counts.1 += 1;
+                highlight_line_fragment(line, lang, dark, state, ai_block)
+            }
+        };
+        job.sections.extend(fragment.sections.iter().map(|sec| {
+            let mut sec = sec.clone();
+            sec.byte_range = sec.byte_range.start + line_start..sec.byte_range.end + line_start;
+            sec
+        }));
+        state = fragment.state_out;
+        fragments.insert(key, fragment);
+        line_start += line.len() + 1;
     }
+    (job, fragments, counts)
+}
 
-    for (i, line) in text.lines().enumerate() {
-        if i > 0 {
-            job.append("\n", 0.0, TextFormat::default());
-        }
-        if ai_block_lines.contains(&i) {
-            // Highlight the entire line with the distinct AI-block color
-            job.append(
-                line,
-                0.0,
-                TextFormat {
-                    color: ai_block_color(dark),
-                    italics: true,
-                    ..Default::default()
-                },
-            );
-        } else if line.trim_start().starts_with("-- ✨ AI:") {
-            // Loading placeholder line — styled differently so user knows AI is working
-            job.append(
-                line,
-                0.0,
-                TextFormat {
-                    color: if dark { Color32::from_rgb(140, 90, 220) } else { Color32::from_rgb(150, 60, 210) },
-                    italics: true,
-                    ..Default::default()
-                },
-            );
-        } else {
-            highlight_single_line(line, lang, dark, &mut job);
-        }
+fn highlight_line_fragment(
+    line: &str,
+    lang: LanguageKind,
+    dark: bool,
+    state_in: LineState,
+    ai_block: bool,
+) -> LineFragment {
+    let mut job = LayoutJob::default();
+    let state_out = if ai_block {
+        // Highlight the entire line with the distinct AI-block color
+        job.append(
+            line,
+            0.0,
+            TextFormat {
+                color: ai_block_color(dark),
+                italics: true,
+                ..Default::default()
+            },
+        );
+        state_in
+    } else if line.trim_start().starts_with("-- ✨ AI:") {
+        // Loading placeholder line — styled differently so user knows AI is working
+        job.append(
+            line,
+            0.0,
+            TextFormat {
+                color: if dark { Color32::from_rgb(140, 90, 220) } else { Color32::from_rgb(150, 60, 210) },
+                italics: true,
+                ..Default::default()
+            },
+        );
+        state_in
+    } else {
+        highlight_single_line(line, lang, dark, state_in, &mut job)
+    };
+    LineFragment {
+        sections: job.sections,
+        state_out,
     }
-    job
 }
 
 /// Single-line highlighter (excludes trailing newline)
 pub fn highlight_line(line: &str, lang: LanguageKind, dark: bool) -> LayoutJob {
     let mut job = LayoutJob::default();
-    highlight_single_line(line, lang, dark, &mut job);
+    highlight_single_line(line, lang, dark, LineState::Normal, &mut job);
     job
 }
 
-fn highlight_single_line(line: &str, lang: LanguageKind, dark: bool, job: &mut LayoutJob) {
+/// Highlight one line starting in `state`; returns the state at the line end.
+fn highlight_single_line(
+    line: &str,
+    lang: LanguageKind,
+    dark: bool,
+    state: LineState,
+    job: &mut LayoutJob,
+) -> LineState {
     if matches!(lang, LanguageKind::Redis) {
         highlight_json_like_line(line, dark, job);
-        return;
+        return LineState::Normal;
     }
 
-    let mut chars = line.char_indices().peekable();
+    // Finish a construct left open by the previous line
+    let mut resume_at = 0;
+    let (close, color) = match state {
+        LineState::Normal => ("", normal_color(dark)),
+        LineState::BlockComment => ("*/", comment_color(dark)),
+        LineState::String => ("'", string_color(dark)),
+    };
+    if !close.is_empty() {
+        let Some(pos) = line.find(close) else {
+            job.append(
+                line,
+                0.0,
+                TextFormat {
+                    color,
+                    ..Default::default()
+                },
+            );
+            return state;
+        };
+        resume_at = pos + close.len();
+        job.append(
+            &line[..resume_at],
+            0.0,
+            TextFormat {
+                color,
+                ..Default::default()
+            },
+        );
+    }
+
+    let sql = matches!(lang, LanguageKind::Sql);
+    let mut chars = line.char_indices().skip_while(|&(i, _)| i < resume_at).peekable();
     while let Some((start_idx, ch)) = chars.next() {
-        if ch == '\'' {
+        if sql && ch == '/' && matches!(chars.peek(), Some(&(_, '*'))) {
+            // Block comment, possibly running past the end of the line
+            let (end_idx, state_out) = match line[start_idx + 2..].find("*/") {
+                Some(pos) => (start_idx + 2 + pos + 2, None),
+                None => (line.len(), Some(LineState::BlockComment)),
+            };
+            job.append(
+                &line[start_idx..end_idx],
+                0.0,
+                TextFormat {
+                    color: comment_color(dark),
+                    ..Default::default()
+                },
+            );
+            if let Some(state_out) = state_out {
+                return state_out;
+            }
+            while chars.next_if(|&(i, _)| i < end_idx).is_some() {}
+        } else if ch == '\'' {
             let mut end_idx = start_idx + 1;
             let mut found_end = false;
             for (idx, c) in chars.by_ref() {
@@ -1598,6 +1821,9 @@ fn highlight_single_line(line: &str, lang: LanguageKind, dark: bool, job: &mut L
                     ..Default::default()
                 },
             );
+            if !found_end && sql {
+                return LineState::String;
+            }
         } else if ch.is_ascii_alphabetic() || ch == '_' {
             let mut end_idx = start_idx;
             let mut word_chars = vec![ch];
@@ -1655,6 +1881,7 @@ fn highlight_single_line(line: &str, lang: LanguageKind, dark: bool, job: &mut L
             );
         }
     }
+    LineState::Normal
 }
 
 fn highlight_json_like_line(line: &str, dark: bool, job: &mut LayoutJob) {
@@ -1874,3 +2101,85 @@ fn ai_block_color(dark: bool) -> Color32 {
 
 // Static keyword tables removed: now using tree-sitter classification and
 // lightweight heuristics (uppercase words) for the legacy fallback.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{enums::DatabaseType, structs::EditorColorTheme};
+
+    fn context(theme: EditorColorTheme, db_type: DatabaseType) -> HighlightContext {
+        HighlightContext {
+            lang: LanguageKind::Sql,
+            theme,
+            dark: true,
+            db_type: Some(db_type),
+        }
+    }
+
+    #[test]
+    fn highlight_cache_hits_and_keys_on_context() {
+        let mut cache = HighlightCache::with_capacity(2);
+        let pg = context(EditorColorTheme::GithubDark, DatabaseType::PostgreSQL);
+        let text = "SELECT id, name FROM users WHERE id = 1;\n".repeat(200);
+        let first = cache.highlight(&text, &pg);
+        for _ in 0..100 {
+            assert_eq!(cache.highlight(&text, &pg).sections.len(), first.sections.len());
+        }
+        assert_eq!(cache.stats(), (100, 1));
+
+        // Editing replaces the context's slot instead of adding another one
+        cache.highlight("SELECT 1", &pg);
+        assert_eq!((cache.len(), cache.stats().1), (1, 2));
+
+        // Theme and dialect are part of the key; the LRU stays bounded
+        let gruvbox = context(EditorColorTheme::Gruvbox, DatabaseType::PostgreSQL);
+        let mysql = context(EditorColorTheme::GithubDark, DatabaseType::MySQL);
+        cache.highlight("SELECT 1", &gruvbox);
+        cache.highlight("SELECT 1", &mysql);
+        assert_eq!((cache.len(), cache.stats()), (2, (100, 4)));
+        cache.highlight("SELECT 1", &pg);
+        assert_eq!(cache.stats(), (100, 5)); // evicted as least recently used
+    }
+
+    fn color_at(job: &LayoutJob, byte: usize) -> Color32 {
+        job.sections
+            .iter()
+            .find(|s| s.byte_range.contains(&byte.into()))
+            .map(|s| s.format.color)
+            .unwrap()
+    }
+
+    #[test]
+    fn multi_line_constructs_carry_state_and_edits_retokenize_few_lines() {
+        let text = "SELECT 1 /* note\nstill comment */ FROM t\nWHERE a = 'x\ny'";
+        let job = highlight_text(text, LanguageKind::Sql, true);
+        assert_eq!(job.text, text);
+        assert_eq!(color_at(&job, text.find("still").unwrap()), comment_color(true));
+        assert_eq!(color_at(&job, text.find("FROM").unwrap()), word_color("FROM", LanguageKind::Sql, true));
+        assert_eq!(color_at(&job, text.rfind('y').unwrap()), string_color(true));
+
+        let ctx = HighlightContext {
+            lang: LanguageKind::Sql,
+            theme: EditorColorTheme::GithubDark,
+            dark: true,
+            db_type: None,
+        };
+        let mut cache = HighlightCache::default();
+        let mut query: String = (0..500)
+            .map(|i| format!("SELECT col_{i}, 'v{i}' FROM t_{i} WHERE id = {i};\n"))
+            .collect();
+        cache.highlight(&query, &ctx);
+        assert_eq!(cache.last_pass_lines(), (0, 501));
+
+        // Typing on one line re-tokenizes just that line
+        query.insert(query.find("col_250").unwrap(), 'x');
+        cache.highlight(&query, &ctx);
+        assert_eq!(cache.last_pass_lines(), (500, 1));
+
+        // Opening a block comment invalidates every line below it
+        query.insert_str(query.find("SELECT col_498").unwrap(), "/* ");
+        let job = cache.highlight(&query, &ctx);
+        assert_eq!(cache.last_pass_lines(), (498, 3));
+        assert_eq!(color_at(&job, query.find("col_499").unwrap()), comment_color(true));
+    }
+}
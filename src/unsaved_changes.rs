@@ -0,0 +1,255 @@
+//! Unsaved-changes prompts.
+//!
+//! Closing a query tab with edits asks "Save / Don't save / Cancel"; quitting
+//! with edited tabs lists them and asks once for all. "Save" goes through
+//! `editor::save_current_tab`, so tabs without a file open the regular Save
+//! Query dialog and the close (or quit) resumes once that dialog has written
+//! the file. Cancelling the dialog cancels the close.
+
+use eframe::egui;
+
+use crate::{editor, window_egui};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloseTarget {
+    Tab(usize),
+    App,
+}
+
+#[derive(Default)]
+pub struct UnsavedChangesState {
+    /// Prompt on screen and what it is about to close.
+    pub prompt: Option<CloseTarget>,
+    /// The Save Query dialog is open for this tab on behalf of `target`.
+    awaiting_save: Option<(usize, CloseTarget)>,
+    /// Quitting was confirmed; let the next close request through.
+    exit_confirmed: bool,
+    /// Move keyboard focus to the default button on the prompt's first frame.
+    focus_pending: bool,
+}
+
+/// Whether tab `index` holds edits that closing would lose. Untitled tabs
+/// that are empty again do not count.
+pub(crate) fn is_tab_unsaved(tabular: &window_egui::Tabular, index: usize) -> bool {
+    let Some(tab) = tabular.query_tabs.get(index) else {
+        return false;
+    };
+    if tab.http_client_state.is_some() || tab.redis_browser_state.is_some() {
+        return false;
+    }
+    let content = if index == tabular.active_tab_index {
+        &tabular.editor.text
+    } else {
+        &tab.content
+    };
+    let edited = tab.is_modified
+        || (index == tabular.active_tab_index
+            && tab.file_path.is_some()
+            && tab.content != tabular.editor.text);
+    edited && (tab.file_path.is_some() || !content.trim().is_empty())
+}
+
+pub(crate) fn unsaved_tab_indices(tabular: &window_egui::Tabular) -> Vec<usize> {
+    (0..tabular.query_tabs.len())
+        .filter(|&i| is_tab_unsaved(tabular, i))
+        .collect()
+}
+
+fn open_prompt(tabular: &mut window_egui::Tabular, target: CloseTarget) {
+    tabular.unsaved_changes.prompt = Some(target);
+    tabular.unsaved_changes.focus_pending = true;
+}
+
+/// Close tab `index`, asking first when it has unsaved edits.
+pub(crate) fn request_close_tab(tabular: &mut window_egui::Tabular, index: usize) {
+    if is_tab_unsaved(tabular, index) {
+        open_prompt(tabular, CloseTarget::Tab(index));
+    } else {
+        editor::close_tab(tabular, index);
+    }
+}
+
+/// Hold the window open when it is asked to close with unsaved tabs. Call
+/// once per frame before any panel is drawn.
+pub(crate) fn intercept_app_close(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    if !ctx.input(|i| i.viewport().close_requested()) || tabular.unsaved_changes.exit_confirmed {
+        return;
+    }
+    if !unsaved_tab_indices(tabular).is_empty() {
+        ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+        open_prompt(tabular, CloseTarget::App);
+    }
+}
+
+fn quit(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    tabular.unsaved_changes.exit_confirmed = true;
+    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+}
+
+/// Save tab `index`. Returns false when the Save Query dialog had to be opened
+/// (the tab has no file yet) or writing failed.
+fn save_tab(tabular: &mut window_egui::Tabular, index: usize) -> bool {
+    if index != tabular.active_tab_index {
+        editor::switch_to_tab(tabular, index);
+    }
+    if let Err(e) = editor::save_current_tab(tabular) {
+        tabular.toasts.error(format!("Failed to save: {e}"));
+        return false;
+    }
+    !tabular.show_save_dialog && !is_tab_unsaved(tabular, index)
+}
+
+/// Save every unsaved tab, then close `target`. Stops at the first tab that
+/// needs the Save Query dialog and resumes from `resume_after_save`.
+fn save_and_close(tabular: &mut window_egui::Tabular, ctx: &egui::Context, target: CloseTarget) {
+    let pending = match target {
+        CloseTarget::Tab(index) => vec![index],
+        CloseTarget::App => unsaved_tab_indices(tabular),
+    };
+    for index in pending {
+        if !save_tab(tabular, index) {
+            if tabular.show_save_dialog {
+                tabular.unsaved_changes.awaiting_save = Some((index, target));
+            }
+            return;
+        }
+    }
+    match target {
+        CloseTarget::Tab(index) => editor::close_tab(tabular, index),
+        CloseTarget::App => quit(tabular, ctx),
+    }
+}
+
+/// Continue a "Save" once the Save Query dialog it opened is closed.
+fn resume_after_save(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let Some((index, target)) = tabular.unsaved_changes.awaiting_save else {
+        return;
+    };
+    if tabular.show_save_dialog {
+        return;
+    }
+    tabular.unsaved_changes.awaiting_save = None;
+    if is_tab_unsaved(tabular, index) {
+        // Save dialog cancelled: keep everything open
+        if target == CloseTarget::App {
+            open_prompt(tabular, target);
+        }
+        return;
+    }
+    save_and_close(tabular, ctx, target);
+}
+
+pub(crate) fn render_unsaved_changes_dialog(
+    tabular: &mut window_egui::Tabular,
+    ctx: &egui::Context,
+) {
+    resume_after_save(tabular, ctx);
+    let Some(target) = tabular.unsaved_changes.prompt else {
+        return;
+    };
+    let titles: Vec<String> = match target {
+        CloseTarget::Tab(index) => tabular
+            .query_tabs
+            .get(index)
+            .map(|t| vec![t.title.clone()])
+            .unwrap_or_default(),
+        CloseTarget::App => unsaved_tab_indices(tabular)
+            .into_iter()
+            .map(|i| tabular.query_tabs[i].title.clone())
+            .collect(),
+    };
+    if titles.is_empty() {
+        tabular.unsaved_changes.prompt = None;
+        return;
+    }
+
+    #[derive(PartialEq)]
+    enum Choice {
+        Save,
+        Discard,
+        Cancel,
+    }
+    let mut choice = None;
+    let focus_pending = std::mem::take(&mut tabular.unsaved_changes.focus_pending);
+    egui::Window::new("Unsaved Changes")
+        .collapsible(false)
+        .resizable(false)
+        .default_width(380.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            match target {
+                CloseTarget::Tab(_) => {
+                    ui.label(format!("Save changes to '{}' before closing?", titles[0]));
+                }
+                CloseTarget::App => {
+                    ui.label("These tabs have unsaved changes:");
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for title in &titles {
+                                ui.label(format!("  • {}", title));
+                            }
+                        });
+                }
+            }
+            ui.label(
+                egui::RichText::new("Tab / ←→ to move, Enter to choose, Esc to cancel")
+                    .small()
+                    .weak(),
+            );
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                let save_label = if target == CloseTarget::App {
+                    "Save All"
+                } else {
+                    "Save"
+                };
+                let buttons = [
+                    ui.button(save_label),
+                    ui.button("Don't Save"),
+                    ui.button("Cancel"),
+                ];
+                if focus_pending {
+                    buttons[0].request_focus();
+                }
+                // Arrow keys move between the buttons as well as Tab
+                if let Some(focused) = buttons.iter().position(|b| b.has_focus()) {
+                    let step = ui.input(|i| {
+                        if i.key_pressed(egui::Key::ArrowRight) {
+                            Some(1)
+                        } else if i.key_pressed(egui::Key::ArrowLeft) {
+                            Some(buttons.len() - 1)
+                        } else {
+                            None
+                        }
+                    });
+                    if let Some(step) = step {
+                        buttons[(focused + step) % buttons.len()].request_focus();
+                    }
+                }
+                for (button, c) in
+                    buttons
+                        .iter()
+                        .zip([Choice::Save, Choice::Discard, Choice::Cancel])
+                {
+                    if button.clicked() {
+                        choice = Some(c);
+                    }
+                }
+            });
+        });
+    if choice.is_none() && ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+        choice = Some(Choice::Cancel);
+    }
+
+    let Some(choice) = choice else {
+        return;
+    };
+    tabular.unsaved_changes.prompt = None;
+    match (choice, target) {
+        (Choice::Cancel, _) => {}
+        (Choice::Discard, CloseTarget::Tab(index)) => editor::close_tab(tabular, index),
+        (Choice::Discard, CloseTarget::App) => quit(tabular, ctx),
+        (Choice::Save, target) => save_and_close(tabular, ctx, target),
+    }
+}
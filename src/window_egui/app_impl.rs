@@ -4,6 +4,7 @@ use chrono::{DateTime, Duration, Utc};
 use super::{Tabular, PrefTab, style};
 
 const TAB_BUTTON_HEIGHT: f32 = 36.0;
+const SIDEBAR_TOGGLE_HINT: &str = if cfg!(target_os = "macos") { "⌘\\" } else { "Ctrl+\\" };
 use crate::{models, connection, editor, data_table, sidebar_database, sidebar_history,
             sidebar_query, spreadsheet::SpreadsheetOperations, dialog,
             cache_data};
@@ -88,6 +89,7 @@ impl Tabular {
                 self.pool_wait_in_progress = false;
                 self.pool_wait_connection_id = None;
                 self.pool_wait_query.clear();
+                self.pool_wait_params.clear();
                 self.pool_wait_started_at = None;
             }
         }
@@ -124,10 +126,16 @@ impl Tabular {
                             draw_tab(ui, &mut self.settings_active_pref_tab, PrefTab::ApplicationTheme, "Application Theme");
                             draw_tab(ui, &mut self.settings_active_pref_tab, PrefTab::EditorTheme, "Editor Theme");
                             draw_tab(ui, &mut self.settings_active_pref_tab, PrefTab::Performance, "Performance Settings");
+                            draw_tab(ui, &mut self.settings_active_pref_tab, PrefTab::DataGrid, "Data Grid");
                             draw_tab(ui, &mut self.settings_active_pref_tab, PrefTab::DataDirectory, "Data Directory");
                             draw_tab(ui, &mut self.settings_active_pref_tab, PrefTab::Update, "Update");
+                            draw_tab(ui, &mut self.settings_active_pref_tab, PrefTab::Network, "🌐 Network");
+                            draw_tab(ui, &mut self.settings_active_pref_tab, PrefTab::Shortcuts, "⌨ Shortcuts");
                             draw_tab(ui, &mut self.settings_active_pref_tab, PrefTab::AiAssistant, "✨ AI Assistant");
                         });
+                        if self.offline_mode {
+                            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "✈ Offline mode is on: update checks and background schema syncs are disabled");
+                        }
                         ui.separator();
                         ui.add_space(4.0);
 
@@ -259,7 +267,21 @@ impl Tabular {
                                     ui.separator();
                                     ui.checkbox(&mut self.advanced_editor.show_line_numbers, "Line numbers").changed();
                                     if ui.checkbox(&mut self.advanced_editor.word_wrap, "Word wrap").changed() { self.prefs_dirty = true; self.try_save_prefs(); }
+                                    if ui.checkbox(&mut self.advanced_editor.vim_mode, "Vim mode").on_hover_text("Modal editing: Esc for Normal mode, i/a/o to insert, v to select, :w / :run / :q").changed() {
+                                        self.vim = crate::editor_vim::VimState::default();
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Default editor height:");
+                                    let mut pct = (self.table_split_ratio * 100.0).round();
+                                    let range = (super::render_dialogs::SPLIT_RATIO_MIN * 100.0)..=(super::render_dialogs::SPLIT_RATIO_MAX * 100.0);
+                                    if ui.add(egui::Slider::new(&mut pct, range).suffix("%")).changed() {
+                                        self.table_split_ratio = pct / 100.0;
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
                                 });
+                                ui.label(egui::RichText::new("Share of the editor/result split for tabs that haven't been resized. Double-click the splitter to reset a tab.").size(11.0).color(egui::Color32::from_gray(120)));
                             }
                             PrefTab::Performance => {
                                 ui.heading("Performance Settings");
@@ -278,6 +300,24 @@ impl Tabular {
                                 });
                                 ui.label(egui::RichText::new("Server pagination queries data in smaller chunks (e.g., 100 rows at a time) from the database.\nThis is much faster for large tables but may not work with all custom queries.").size(11.0).color(egui::Color32::from_gray(120)));
                                 ui.add_space(8.0);
+                                ui.horizontal(|ui| {
+                                    ui.label("Default page size");
+                                    if ui.add(egui::DragValue::new(&mut self.default_page_size).range(10..=100_000).speed(10)).changed() {
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
+                                    ui.label("rows");
+                                });
+                                ui.label(egui::RichText::new("Rows per result page when a new tab opens. The page size picker under the grid still changes it for the current tab.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                ui.horizontal(|ui| {
+                                    ui.label("Pause streamed results after");
+                                    if ui.add(egui::DragValue::new(&mut self.stream_row_ceiling).range(1_000..=10_000_000).speed(1_000)).changed() {
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
+                                    ui.label("rows");
+                                });
+                                ui.label(egui::RichText::new("SELECTs run without server pagination show their rows while they arrive. Fetching pauses at this many rows so a huge result cannot fill memory; the running-query panel then offers to fetch more or stop there.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
                                 ui.horizontal(|ui| {
                                     if ui.checkbox(&mut self.enable_debug_logging, "Enable Debug Logging").changed() {
                                         self.prefs_dirty = true; self.try_save_prefs();
@@ -302,6 +342,146 @@ impl Tabular {
                                     }
                                 });
                                 ui.label(egui::RichText::new("Default interval used when Redis browser auto-refresh is enabled.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                ui.horizontal(|ui| {
+                                    ui.label("Multi-statement results:");
+                                    let mut changed = ui.radio_value(&mut self.batch_results_in_tabs, false, "Sub-tabs in one tab").changed();
+                                    changed |= ui.radio_value(&mut self.batch_results_in_tabs, true, "A new tab per SELECT").changed();
+                                    if changed {
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
+                                });
+                                ui.label(egui::RichText::new("Running several statements at once shows each result set either as a sub-tab of the current tab or in its own editor tab, titled from its statement.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                ui.horizontal(|ui| {
+                                    ui.label("Confirm DELETE / UPDATE affecting more than");
+                                    if ui.add(egui::DragValue::new(&mut self.destructive_confirm_over_rows).range(0..=1_000_000_000)).changed() {
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
+                                    ui.label("rows");
+                                });
+                                ui.label(egui::RichText::new("With 0, every DELETE or UPDATE without WHERE asks for confirmation. Above 0, each DELETE or UPDATE is first counted with SELECT COUNT(*) and only asks when it would touch more rows. DROP and TRUNCATE always ask.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                ui.horizontal(|ui| {
+                                    if ui.checkbox(&mut self.safe_mode, "Safe mode: undo data changes after").changed() {
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
+                                    if ui.add_enabled(self.safe_mode, egui::DragValue::new(&mut self.safe_mode_window_secs).range(1..=300)).changed() {
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
+                                    ui.label("seconds");
+                                });
+                                ui.label(egui::RichText::new("INSERT, UPDATE and DELETE run in a transaction with a savepoint on their own connection and are rolled back unless you click \"Keep changes\" before the countdown ends. Tabs in manual-commit mode are not affected.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                if ui.checkbox(&mut self.run_in_transaction, "Run scripts in a transaction").changed() {
+                                    self.prefs_dirty = true; self.try_save_prefs();
+                                }
+                                ui.label(egui::RichText::new("A script of several statements runs between BEGIN and COMMIT on one connection and is rolled back as a whole if any statement fails. Statements that commit implicitly, such as DDL on MySQL, cannot be undone. Redis and MongoDB ignore this.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                ui.horizontal(|ui| {
+                                    if ui.checkbox(&mut self.index_hints, "Suggest indexes for queries slower than").changed() {
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
+                                    if ui.add_enabled(self.index_hints, egui::DragValue::new(&mut self.index_hint_min_ms).range(0..=600_000)).changed() {
+                                        self.prefs_dirty = true; self.try_save_prefs();
+                                    }
+                                    ui.label("ms");
+                                });
+                                ui.label(egui::RichText::new("Columns the query filters or joins on that start no cached index of their table are listed above the results. Clicking one opens the Create Index dialog; nothing is created automatically.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                if ui.checkbox(&mut self.result_cache_enabled, "Cache query results").changed() {
+                                    self.prefs_dirty = true; self.try_save_prefs();
+                                }
+                                ui.label(egui::RichText::new("Re-running the same read-only query within the TTL returns the stored result instantly. Statements that modify data are never cached.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_enabled_ui(self.result_cache_enabled, |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Time to live (seconds):");
+                                        if ui.add(egui::DragValue::new(&mut self.result_cache_ttl_secs).range(1..=86_400)).changed() {
+                                            self.prefs_dirty = true; self.try_save_prefs();
+                                        }
+                                        ui.label("Max size (MB):");
+                                        if ui.add(egui::DragValue::new(&mut self.result_cache_max_mb).range(1..=4096)).changed() {
+                                            self.prefs_dirty = true; self.try_save_prefs();
+                                        }
+                                    });
+                                });
+                                ui.horizontal(|ui| {
+                                    let (entries, bytes) = crate::cache_data::query_result_cache_usage(self);
+                                    ui.label(egui::RichText::new(format!("{} cached result(s), {:.1} MB", entries, bytes as f64 / (1024.0 * 1024.0))).size(11.0).color(egui::Color32::from_gray(120)));
+                                    if ui.add_enabled(entries > 0, egui::Button::new("Clear result cache")).clicked() {
+                                        crate::cache_data::invalidate_query_result_cache(self, None, None);
+                                    }
+                                });
+                            }
+                            PrefTab::DataGrid => {
+                                ui.heading("Data Grid");
+                                ui.label("Display timezone for TIMESTAMP / DATETIME columns:");
+                                ui.horizontal(|ui| {
+                                    let presets = [
+                                        crate::config::DisplayTimezone::AsStored,
+                                        crate::config::DisplayTimezone::Utc,
+                                        crate::config::DisplayTimezone::Local,
+                                    ];
+                                    for tz in presets {
+                                        if ui.radio(self.display_timezone == tz, tz.display_name()).clicked() {
+                                            self.display_timezone = tz;
+                                            self.prefs_dirty = true; self.try_save_prefs();
+                                        }
+                                    }
+                                });
+                                ui.horizontal(|ui| {
+                                    if self.display_timezone_input.is_empty()
+                                        && let crate::config::DisplayTimezone::Zone(tz) = self.display_timezone
+                                    {
+                                        self.display_timezone_input = tz.name().to_string();
+                                    }
+                                    ui.label("IANA zone:");
+                                    let resp = ui.add(
+                                        egui::TextEdit::singleline(&mut self.display_timezone_input)
+                                            .desired_width(200.0)
+                                            .hint_text("e.g. Asia/Jakarta"),
+                                    );
+                                    if resp.lost_focus() || ui.button("Apply").clicked() {
+                                        match self.display_timezone_input.trim().parse::<chrono_tz::Tz>() {
+                                            Ok(tz) => {
+                                                self.display_timezone = crate::config::DisplayTimezone::Zone(tz);
+                                                self.prefs_dirty = true; self.try_save_prefs();
+                                            }
+                                            Err(_) if !self.display_timezone_input.trim().is_empty() => {
+                                                self.prefs_save_feedback = Some(format!("Unknown timezone '{}'", self.display_timezone_input.trim()));
+                                                self.prefs_last_saved_at = Some(std::time::Instant::now());
+                                            }
+                                            Err(_) => {}
+                                        }
+                                    }
+                                });
+                                ui.label(egui::RichText::new("Values without an explicit offset are assumed to be stored in UTC.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                if ui.checkbox(&mut self.timezone_for_edit_export, "Use display timezone when editing and exporting").changed() {
+                                    self.prefs_dirty = true; self.try_save_prefs();
+                                }
+                                ui.label(egui::RichText::new("When off, cell editors and exports use the raw stored values.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(12.0);
+                                ui.label(egui::RichText::new("Large values").strong());
+                                if crate::data_table::render_cell_preview_settings(self, ui) {
+                                    self.prefs_dirty = true; self.try_save_prefs();
+                                }
+                                ui.add_space(12.0);
+                                ui.label(egui::RichText::new("Numbers").strong());
+                                if crate::data_table::render_number_settings(self, ui) {
+                                    self.prefs_dirty = true; self.try_save_prefs();
+                                }
+                                ui.label(egui::RichText::new("Numeric columns are right-aligned. Values are only formatted for display; override single columns from the header menu.").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(12.0);
+                                ui.label(egui::RichText::new("Column formatters").strong());
+                                ui.label(egui::RichText::new("Show matching columns through a display transform. Patterns match column names (* is a wildcard) or, with a type: prefix, column types. The first matching rule wins.").size(11.0).color(egui::Color32::from_gray(120)));
+                                if crate::data_table::render_formatter_settings(self, ui) {
+                                    self.prefs_dirty = true; self.try_save_prefs();
+                                }
+                                if ui.checkbox(&mut self.export_formatted_values, "Export formatted values").changed() {
+                                    self.prefs_dirty = true; self.try_save_prefs();
+                                }
+                                ui.label(egui::RichText::new("When off, exports contain the raw stored values of formatted columns.").size(11.0).color(egui::Color32::from_gray(120)));
                             }
                             PrefTab::DataDirectory => {
                                 ui.heading("Data Directory");
@@ -331,12 +511,52 @@ impl Tabular {
                             }
                             PrefTab::Update => {
                                 ui.heading("Updates");
+                                ui.horizontal(|ui| { if ui.checkbox(&mut self.offline_mode, egui::RichText::new("✈ Offline mode").strong()).changed() { self.prefs_dirty = true; self.try_save_prefs(); } });
+                                ui.label(egui::RichText::new("For airgapped environments: never checks for or downloads updates, and does not sync schemas in the background when a connection is opened").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                let online = !self.offline_mode;
+                                ui.add_enabled_ui(online, |ui| {
                                 ui.horizontal(|ui| { if ui.checkbox(&mut self.auto_check_updates, "Automatically check for updates on startup").changed() { self.prefs_dirty = true; self.try_save_prefs(); } });
                                 ui.label(egui::RichText::new("When enabled, Tabular will check for new versions from GitHub releases").size(11.0).color(egui::Color32::from_gray(120)));
+                                ui.add_space(8.0);
+                                ui.horizontal(|ui| {
+                                    ui.label("Update channel:");
+                                    let before = self.update_channel;
+                                    for channel in [crate::self_update::UpdateChannel::Stable, crate::self_update::UpdateChannel::Beta] {
+                                        ui.radio_value(&mut self.update_channel, channel, channel.display_name());
+                                    }
+                                    if self.update_channel != before {
+                                        // A result from the other channel is stale now
+                                        self.update_info = None;
+                                        self.prefs_dirty = true;
+                                        self.try_save_prefs();
+                                    }
+                                });
+                                ui.label(egui::RichText::new("Beta also offers pre-release builds for early testing").size(11.0).color(egui::Color32::from_gray(120)));
+                                });
+                            }
+                            PrefTab::Network => {
+                                ui.heading("🌐 Network");
+                                let changed = egui::Grid::new("global_proxy_grid")
+                                    .num_columns(2)
+                                    .spacing([10.0, 6.0])
+                                    .show(ui, |ui| {
+                                        ui.label("Global proxy:");
+                                        crate::proxy_tunnel::render_proxy_fields(ui, &mut self.global_proxy, "global_proxy_kind_combo")
+                                    })
+                                    .inner;
+                                if changed {
+                                    crate::proxy_tunnel::set_global_proxy(&self.global_proxy);
+                                    self.prefs_dirty = true; self.try_save_prefs();
+                                }
+                                ui.label(egui::RichText::new("Used by connections that set no proxy of their own. Connections to localhost, SQLite files and HTTP APIs never go through it, and connections with an SSH tunnel ignore it.").size(11.0).color(egui::Color32::from_gray(120)));
+                            }
+                            PrefTab::Shortcuts => {
+                                self.render_keybindings_settings(ui);
                             }
                             PrefTab::AiAssistant => {
                                 ui.heading("✨ AI Assistant");
-                                ui.label(egui::RichText::new("Press Cmd+Shift+A in the editor to toggle the AI panel.").size(11.0).color(egui::Color32::from_gray(130)));
+                                ui.label(egui::RichText::new(format!("Press {} in the editor to toggle the AI panel.", self.keybindings.hint(super::keybindings::Action::ToggleAiPanel))).size(11.0).color(egui::Color32::from_gray(130)));
                                 ui.add_space(8.0);
 
                                 // Provider selection
@@ -561,6 +781,7 @@ impl Tabular {
                         } => {
                             // Remove from refreshing set
                             self.refreshing_connections.remove(&connection_id);
+                            self.refresh_progress.remove(&connection_id);
 
                             if success {
                                 debug!(
@@ -628,6 +849,24 @@ impl Tabular {
                                 self.pending_expansion_restore.remove(&connection_id);
                             }
                         }
+                        models::enums::BackgroundResult::RefreshProgress {
+                            connection_id,
+                            progress,
+                        } => {
+                            let previous = self
+                                .refresh_progress
+                                .insert(connection_id, progress)
+                                .unwrap_or_default();
+                            // A user-triggered refresh cleared this connection's nodes; fill
+                            // them in as databases arrive instead of waiting for the end.
+                            // Background auto-syncs keep their intact tree untouched.
+                            if progress.databases_done != previous.databases_done
+                                && self.pending_expansion_restore.contains_key(&connection_id)
+                            {
+                                self.apply_refresh_progress_to_tree(connection_id);
+                            }
+                            ctx.request_repaint();
+                        }
                         models::enums::BackgroundResult::PrefetchProgress {
                             connection_id,
                             completed,
@@ -829,6 +1068,7 @@ impl Tabular {
                             database_name,
                             search_text,
                             keys,
+                            ttls,
                         } => {
                             let mut merged_keys_for_cache: Option<Vec<(String, String)>> = None;
 
@@ -841,16 +1081,12 @@ impl Tabular {
 
                                     for (key_name, key_type) in &keys {
                                         if !state.keys.iter().any(|entry| entry.key_name == *key_name) {
-                                            state.keys.push(models::structs::RedisBrowserKeyEntry {
-                                                key_name: key_name.clone(),
-                                                key_type: key_type.clone(),
-                                                ttl_label: if database_name == crate::driver_redis::REDIS_CLUSTER_KEYSPACE {
-                                                    "Cluster".to_string()
-                                                } else {
-                                                    database_name.clone()
-                                                },
-                                                size_label: "-".to_string(),
-                                            });
+                                            state.keys.push(crate::driver_redis::browser_key_entry(
+                                                key_name.clone(),
+                                                key_type.clone(),
+                                                ttls.get(key_name).copied(),
+                                                &database_name,
+                                            ));
                                         }
                                     }
 
@@ -920,6 +1156,10 @@ impl Tabular {
                 }
 
 
+            while let Ok(event) = self.query_stream_receiver.try_recv() {
+                self.handle_stream_event(event);
+                ctx.request_repaint();
+            }
             while let Ok(message) = self.query_result_receiver.try_recv() {
                 self.handle_query_result_message(message);
                 ctx.request_repaint();
@@ -930,10 +1170,13 @@ impl Tabular {
     /// Extracted verbatim from `update()`.
     fn render_left_sidebar(&mut self, root_ui: &mut egui::Ui) {
             let ctx = &root_ui.ctx().clone();
-            if self.sidebar_visible {
-                egui::Panel::left("sidebar")
+            if self.sidebar_collapsed {
+                self.render_collapsed_sidebar_strip(root_ui);
+                return;
+            }
+            let sidebar_panel = egui::Panel::left("sidebar")
                 .resizable(true)
-                .default_size(250.0)
+                .default_size(self.sidebar_width)
                 .min_size(150.0)
                 .max_size(500.0)
                 // Reduce default inner padding so tree rows (connection/database/table) start closer to the left edge
@@ -1101,10 +1344,31 @@ impl Tabular {
                                         }
                                     });
 
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut self.query_tag_filter)
+                                            .hint_text("🏷 Filter by tag")
+                                            .desired_width(f32::INFINITY),
+                                    );
+                                    let tag_terms = sidebar_query::split_tags(&self.query_tag_filter);
+
                                     // Render the queries tree and process any clicked items into new tabs
-                                    let mut queries_tree = std::mem::take(&mut self.queries_tree);
-                                    let query_files_to_open = self.render_tree(ui, &mut queries_tree, false);
-                                    self.queries_tree = queries_tree;
+                                    let query_files_to_open = if tag_terms.is_empty() {
+                                        let mut queries_tree = std::mem::take(&mut self.queries_tree);
+                                        let opened = self.render_tree(ui, &mut queries_tree, false);
+                                        self.queries_tree = queries_tree;
+                                        opened
+                                    } else {
+                                        // Filtered view is a throwaway copy; expansion changes are not kept
+                                        let mut filtered = sidebar_query::filter_queries_by_tag(
+                                            &self.queries_tree,
+                                            &self.saved_query_meta,
+                                            &tag_terms,
+                                        );
+                                        if filtered.is_empty() {
+                                            ui.label(egui::RichText::new("No queries with these tags").weak());
+                                        }
+                                        self.render_tree(ui, &mut filtered, false)
+                                    };
 
                                     for (filename, content, file_path, _) in query_files_to_open {
                                         if file_path.is_empty() {
@@ -1225,38 +1489,27 @@ impl Tabular {
                                     }
 
                                     for (filename, content, file_data, _) in query_files_to_open {
-                                        // file_data for history contains "connection_name||query"
-                                        if let Some((connection_name, _query)) = file_data.split_once("||") {
-                                            // Try to find matching connection by name to preselect in the new tab
-                                            let conn_id = self
-                                                .connections
-                                                .iter()
-                                                .find(|c| c.name == connection_name)
-                                                .and_then(|c| c.id);
-                                            if let Some(cid) = conn_id {
-                                                log::debug!(
-                                                    "✅ Processing history click: New tab '{}' with connection '{}' (id={})",
-                                                    filename, connection_name, cid
-                                                );
-                                                crate::editor::create_new_tab_with_connection(
-                                                    self,
-                                                    filename,
-                                                    content,
-                                                    Some(cid),
-                                                );
-                                                continue;
-                                            } else if !connection_name.is_empty() {
-                                                log::debug!(
-                                                    "⚠️ Connection '{}' from history not found. Opening tab without binding.",
-                                                    connection_name
-                                                );
-                                            }
+                                        // file_data for history is the encoded entry (connection, database, query)
+                                        if let Some(item) = crate::sidebar_history::decode_history_node(&file_data) {
+                                            log::debug!(
+                                                "✅ Processing history click: New tab '{}' for connection '{}' (id={})",
+                                                filename, item.connection_name, item.connection_id
+                                            );
+                                            crate::sidebar_history::open_history_entry(self, item, false);
+                                        } else {
+                                            crate::editor::create_new_tab(self, filename, content);
                                         }
-                                        log::debug!(
-                                            "✅ Processing history click: Creating new tab for '{}' (no connection binding)",
-                                            filename
-                                        );
-                                        crate::editor::create_new_tab(self, filename, content);
+                                    }
+
+                                    // "Execute Query" from a history item's context menu
+                                    let run_request = ui.ctx().data_mut(|d| {
+                                        d.remove_temp::<String>(egui::Id::new("history_run_request"))
+                                    });
+                                    if let Some(item) = run_request
+                                        .as_deref()
+                                        .and_then(crate::sidebar_history::decode_history_node)
+                                    {
+                                        crate::sidebar_history::open_history_entry(self, item, true);
                                     }
                                 }
                                 _ => {}
@@ -1281,6 +1534,7 @@ impl Tabular {
                                         // Reset test connection status saat buka add dialog
                                         self.test_connection_status = None;
                                         self.test_connection_in_progress = false;
+                                        self.test_connection_receiver = None;
                                         self.show_add_connection = true;
                                     }
                                     _ => {
@@ -1291,7 +1545,57 @@ impl Tabular {
                         });
                     });
                 });
-            }
+            self.remember_sidebar_width(ctx, sidebar_panel.response.rect.width());
+    }
+
+    /// Thin strip shown in place of the collapsed sidebar with an expand button.
+    fn render_collapsed_sidebar_strip(&mut self, root_ui: &mut egui::Ui) {
+        let dark = root_ui.visuals().dark_mode;
+        egui::Panel::left("sidebar_collapsed_strip")
+            .resizable(false)
+            .exact_size(22.0)
+            .frame(
+                egui::Frame::default()
+                    .fill(if dark {
+                        egui::Color32::from_rgb(20, 20, 20)
+                    } else {
+                        egui::Color32::from_rgb(245, 245, 245)
+                    })
+                    .inner_margin(egui::Margin::same(0)),
+            )
+            .show(root_ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(8.0);
+                    if ui
+                        .add(egui::Button::new("▶").frame(false))
+                        .on_hover_text(format!("Show Sidebar ({})", SIDEBAR_TOGGLE_HINT))
+                        .clicked()
+                    {
+                        self.toggle_sidebar();
+                    }
+                });
+            });
+    }
+
+    /// Collapse or expand the left sidebar and persist the choice.
+    /// Tree expansion state lives on `Tabular`, so it survives the round trip.
+    pub(crate) fn toggle_sidebar(&mut self) {
+        self.sidebar_collapsed = !self.sidebar_collapsed;
+        self.prefs_dirty = true;
+        self.try_save_prefs();
+    }
+
+    /// Track the user-dragged sidebar width; persisted once the drag ends.
+    fn remember_sidebar_width(&mut self, ctx: &egui::Context, width: f32) {
+        self.sidebar_width = width;
+        let unsaved = self
+            .last_saved_prefs
+            .as_ref()
+            .is_none_or(|p| (p.sidebar_width - width).abs() >= 1.0);
+        if unsaved && !ctx.input(|i| i.pointer.any_down()) {
+            self.prefs_dirty = true;
+            self.try_save_prefs();
+        }
     }
 
     /// Render the AI Assistant right side panel.
@@ -1399,7 +1703,7 @@ impl Tabular {
                             ui.spacing_mut().item_spacing.x = 2.0;
 
                             // Sidebar Toggle
-                            let toggle_icon = if self.sidebar_visible { "◀" } else { "▶" };
+                            let toggle_icon = if self.sidebar_collapsed { "▶" } else { "◀" };
                             if ui
                                 .add_sized(
                                     [34.0, 34.0],
@@ -1408,14 +1712,14 @@ impl Tabular {
                                         .stroke(egui::Stroke::NONE)
                                         .corner_radius(0.3),
                                 )
-                                .on_hover_text(if self.sidebar_visible {
-                                    "Hide Sidebar"
-                                } else {
-                                    "Show Sidebar"
-                                })
+                                .on_hover_text(format!(
+                                    "{} Sidebar ({})",
+                                    if self.sidebar_collapsed { "Show" } else { "Hide" },
+                                    SIDEBAR_TOGGLE_HINT
+                                ))
                                 .clicked()
                             {
-                                self.sidebar_visible = !self.sidebar_visible;
+                                self.toggle_sidebar();
                             }
 
                             // Scrollable container for tab buttons + '+' button
@@ -1428,6 +1732,7 @@ impl Tabular {
                                         ui.add_space(8.0);
                                         let mut to_close = None;
                                         let mut to_switch = None;
+                                        let mut to_compare = None;
 
                                         if self.last_active_tab_index != Some(self.active_tab_index) {
                                             self.scroll_to_active_tab = true;
@@ -1474,21 +1779,69 @@ impl Tabular {
                                             } else {
                                                 ui.visuals().text_color()
                                             };
-                                            let mut title = tab.title.clone();
-                                            if let Some(cid) = tab.connection_id
-                                                && let Some(n) = self.get_connection_name(cid)
-                                            {
-                                                title = format!("{} [{}]", title, n);
+                                            let name = tab.display_name();
+                                            let mut markers = String::new();
+                                            if crate::editor::tab_has_running_query(self, i) {
+                                                markers.push_str("⏳ ");
                                             }
+                                            if crate::unsaved_changes::is_tab_unsaved(self, i) {
+                                                markers.push_str("● ");
+                                            }
+                                            let connection_name =
+                                                tab.connection_id.and_then(|cid| self.get_connection_name(cid));
+                                            let tag = tab
+                                                .connection_id
+                                                .and_then(|cid| crate::connection::tag::tag_for_connection(self, cid));
+                                            let label = tag
+                                                .as_ref()
+                                                .filter(|t| !t.label.is_empty())
+                                                .map(|t| format!("{} · ", t.label))
+                                                .unwrap_or_default();
+                                            let hint = match (&connection_name, &tab.database_name) {
+                                                (Some(c), Some(db)) if !db.is_empty() => format!(" [{}{}/{}]", label, c, db),
+                                                (Some(c), _) => format!(" [{}{}]", label, c),
+                                                _ => String::new(),
+                                            };
+                                            let tag_color = tag.and_then(|t| t.color);
                                             let close_size = 16.0;
-                                            let tab_width = (title.len() as f32 * 8.0 + 64.0)
+                                            let full_len = (markers.chars().count()
+                                                + name.chars().count()
+                                                + hint.chars().count()) as f32;
+                                            let tab_width = (full_len * 8.0 + 64.0)
                                                 .clamp(min_single_tab_w, tab_width_cap);
+                                            // Shorten the name in the middle so the extension and
+                                            // the connection hint stay visible.
+                                            let fit_chars = ((tab_width - close_size - 18.0) / 7.0) as usize;
+                                            let name_budget = fit_chars
+                                                .saturating_sub(markers.chars().count() + hint.chars().count())
+                                                .max(8);
+                                            let title = format!(
+                                                "{}{}{}",
+                                                markers,
+                                                crate::redis_browser::elide_middle(&name, name_budget),
+                                                hint
+                                            );
                                             let menu_tab_height = 34.0;
                                             let (tab_rect, tab_resp) = ui.allocate_exact_size(
                                                 egui::vec2(tab_width, menu_tab_height),
                                                 egui::Sense::click(),
                                             );
 
+                                            let mut tooltip = tab.title.clone();
+                                            if let Some(path) = &tab.file_path
+                                                && tab.http_client_state.is_none()
+                                                && tab.redis_browser_state.is_none()
+                                            {
+                                                tooltip.push_str(&format!("\n{}", path));
+                                            }
+                                            if let Some(c) = &connection_name {
+                                                tooltip.push_str(&format!("\nConnection: {}", c));
+                                            }
+                                            if let Some(db) = tab.database_name.as_deref().filter(|d| !d.is_empty()) {
+                                                tooltip.push_str(&format!("\nDatabase: {}", db));
+                                            }
+                                            let tab_resp = tab_resp.on_hover_text(tooltip);
+
                                             if active && self.scroll_to_active_tab {
                                                 tab_resp.scroll_to_me(Some(egui::Align::Center));
                                             }
@@ -1501,15 +1854,16 @@ impl Tabular {
                                                 egui::Stroke::new(1.0, border_color),
                                                 egui::StrokeKind::Outside,
                                             );
-                                            if active {
+                                            // The connection's tag color marks every tab on it
+                                            if active || tag_color.is_some() {
                                                 let accent_rect = egui::Rect::from_min_size(
                                                     egui::pos2(tab_rect.left() - 1.0, tab_rect.top()),
-                                                    egui::vec2(tab_rect.width() + 2.0, 3.0),
+                                                    egui::vec2(tab_rect.width() + 2.0, if active { 3.0 } else { 2.0 }),
                                                 );
                                                 ui.painter().rect_filled(
                                                     accent_rect,
                                                     0.0,
-                                                    super::style::theme_accent(ui.ctx()),
+                                                    tag_color.unwrap_or_else(|| super::style::theme_accent(ui.ctx())),
                                                 );
                                             }
                                             let close_rect = egui::Rect::from_min_size(
@@ -1561,6 +1915,20 @@ impl Tabular {
                                                 }
                                             }
 
+                                            if tab_count > 1 {
+                                                tab_resp.context_menu(|ui| {
+                                                    let label = if active {
+                                                        "Compare with…"
+                                                    } else {
+                                                        "Compare with active tab"
+                                                    };
+                                                    if ui.button(label).clicked() {
+                                                        to_compare = Some(i);
+                                                        ui.close();
+                                                    }
+                                                });
+                                            }
+
                                             if tab_resp.clicked()
                                                 && !close_rect.contains(
                                                     tab_resp.interact_pointer_pos().unwrap_or(egui::Pos2::ZERO),
@@ -1594,11 +1962,14 @@ impl Tabular {
                                             editor::create_new_tab(self, "Untitled Query".to_string(), String::new());
                                         }
                                         if let Some(i) = to_close {
-                                            editor::close_tab(self, i);
+                                            crate::unsaved_changes::request_close_tab(self, i);
                                         }
                                         if let Some(i) = to_switch {
                                             editor::switch_to_tab(self, i);
                                         }
+                                        if let Some(i) = to_compare {
+                                            editor::open_tab_compare(self, Some(i));
+                                        }
                                     });
                                 });
                         },
@@ -1688,6 +2059,19 @@ impl Tabular {
                                                 self.show_settings_window = true;
                                                 self.show_settings_menu = false;
                                             }
+                                            if ui
+                                                .add(
+                                                    egui::Button::new(format!(
+                                                        "Workspace Profiles ({})",
+                                                        self.workspace_profiles.active
+                                                    ))
+                                                    .fill(egui::Color32::TRANSPARENT),
+                                                )
+                                                .clicked()
+                                            {
+                                                crate::workspace_profile::open_window(self);
+                                                self.show_settings_menu = false;
+                                            }
                                             ui.separator();
                                             if ui
                                                 .add(
@@ -1764,11 +2148,27 @@ impl Tabular {
                                 self.show_ai_panel = !self.show_ai_panel;
                             }
 
-                            let conn_list: Vec<(i64, String)> = self
+                            // 2.5 Parameter presets of the saved query
+                            if crate::query_presets::render_preset_menu(self, ui) {
+                                add_divider(ui);
+                            }
+
+                            let mut conn_list: Vec<(i64, String, String)> = self
                                 .connections
                                 .iter()
-                                .filter_map(|c| c.id.map(|id| (id, c.name.clone())))
+                                .filter_map(|c| {
+                                    let folder = c
+                                        .folder
+                                        .as_deref()
+                                        .map(str::trim)
+                                        .map(|f| f.trim_matches('/'))
+                                        .filter(|f| !f.is_empty())
+                                        .unwrap_or("Default")
+                                        .to_string();
+                                    c.id.map(|id| (id, c.name.clone(), folder))
+                                })
                                 .collect();
+                            conn_list.sort_by(|a, b| a.2.cmp(&b.2).then_with(|| a.1.to_lowercase().cmp(&b.1.to_lowercase())));
                             let (tab_conn_id, tab_db_name) = self
                                 .query_tabs
                                 .get(self.active_tab_index)
@@ -1798,15 +2198,17 @@ impl Tabular {
                                     .show_ui(ui, |ui| {
                                         for db in &dbs {
                                             if ui.selectable_label(active_db == *db, db).clicked() {
+                                                let picked = (db != "(default)").then(|| db.clone());
                                                 if let Some(tab) =
                                                     self.query_tabs.get_mut(self.active_tab_index)
                                                 {
-                                                    tab.database_name = if db == "(default)" {
-                                                        None
-                                                    } else {
-                                                        Some(db.clone())
-                                                    };
+                                                    tab.database_name = picked.clone();
                                                 }
+                                                crate::connection::remember_last_used_database(
+                                                    self,
+                                                    cid,
+                                                    picked.as_deref(),
+                                                );
                                                 self.current_table_headers.clear();
                                                 self.current_table_data.clear();
                                             }
@@ -1816,14 +2218,10 @@ impl Tabular {
                                 add_divider(ui);
 
                                 // 3.5 Active Schema / Search Path selector
-                                let (tab_schema, active_conn_type) = self
+                                let tab_schema = self
                                     .query_tabs
                                     .get(self.active_tab_index)
-                                    .map(|t| {
-                                        let conn_type = self.connections.iter().find(|c| c.id == t.connection_id).map(|c| c.connection_type.clone());
-                                        (t.schema_name.clone(), conn_type)
-                                    })
-                                    .unwrap_or((None, None));
+                                    .and_then(|t| t.schema_name.clone());
 
                                 let mut schemas = self.get_schemas_cached(cid, tab_db_name.as_deref());
                                 if schemas.is_empty() {
@@ -1840,10 +2238,7 @@ impl Tabular {
                                                 if let Some(tab) = self.query_tabs.get_mut(self.active_tab_index) {
                                                     tab.schema_name = Some(s.clone());
                                                 }
-                                                if matches!(active_conn_type, Some(models::enums::DatabaseType::PostgreSQL)) {
-                                                    let set_path_query = format!("SET search_path TO {}, public;", s);
-                                                    let _ = crate::connection::execute_query_with_connection(self, cid, set_path_query);
-                                                }
+                                                // Each query job applies the tab's schema on its own connection
                                                 self.toasts.info(format!("Switched active schema to '{}'", s));
                                             }
                                         }
@@ -1852,25 +2247,36 @@ impl Tabular {
 
                             add_divider(ui);
 
-                            // 4. Connection selector (Leftmost in group)
-                            egui::ComboBox::from_id_salt("query_conn_select")
-                                .width(130.0)
-                                .selected_text(current_conn_name)
-                                .show_ui(ui, |ui| {
-                                    for (cid, name) in &conn_list {
-                                        let selected = tab_conn_id == Some(*cid);
-                                        if ui.selectable_label(selected, name).clicked() {
-                                            if let Some(tab) =
-                                                self.query_tabs.get_mut(self.active_tab_index)
-                                            {
-                                                tab.connection_id = Some(*cid);
-                                                tab.database_name = None; // reset db for new connection
+                            // 4. Connection selector (Leftmost in group), grouped like the sidebar.
+                            // An open transaction pins the tab to its connection.
+                            let tx_active = self
+                                .query_tabs
+                                .get(self.active_tab_index)
+                                .is_some_and(|t| t.tx_active);
+                            let mut switch_to = None;
+                            ui.add_enabled_ui(!tx_active, |ui| {
+                                egui::ComboBox::from_id_salt("query_conn_select")
+                                    .width(130.0)
+                                    .selected_text(current_conn_name)
+                                    .show_ui(ui, |ui| {
+                                        let mut last_folder: Option<&str> = None;
+                                        for (cid, name, folder) in &conn_list {
+                                            if last_folder != Some(folder.as_str()) {
+                                                ui.label(egui::RichText::new(folder).small().weak());
+                                                last_folder = Some(folder);
+                                            }
+                                            let selected = tab_conn_id == Some(*cid);
+                                            if ui.selectable_label(selected, format!("  {}", name)).clicked() {
+                                                switch_to = Some(*cid);
                                             }
-                                            self.current_table_headers.clear();
-                                            self.current_table_data.clear();
                                         }
-                                    }
-                                });
+                                    });
+                            })
+                            .response
+                            .on_disabled_hover_text("Commit or roll back the open transaction to switch connections");
+                            if let Some(cid) = switch_to {
+                                self.switch_active_tab_connection(cid);
+                            }
                         },
                     );
 
@@ -2120,6 +2526,65 @@ impl Tabular {
                                  self.save_diagram(cid, &db, &state);
                              }
 
+                        // Key edits run right away and then reload the key list like Refresh
+                        if let Some(conn_id) = redis_connection_id
+                            && let Some(crate::redis_browser::RedisBrowserAction::EditKey { key_name, edit }) =
+                                redis_action.take_if(|action| {
+                                    matches!(action, crate::redis_browser::RedisBrowserAction::EditKey { .. })
+                                })
+                        {
+                            let (database_name, key_type) = self
+                                .query_tabs
+                                .get(self.active_tab_index)
+                                .and_then(|tab| tab.redis_browser_state.as_ref())
+                                .map(|state| {
+                                    let key_type = state
+                                        .keys
+                                        .iter()
+                                        .find(|entry| entry.key_name == key_name)
+                                        .map(|entry| entry.key_type.clone())
+                                        .unwrap_or_default();
+                                    (state.keyspace_label.clone(), key_type)
+                                })
+                                .unwrap_or_else(|| (crate::driver_redis::REDIS_CLUSTER_KEYSPACE.to_string(), String::new()));
+                            let result =
+                                crate::driver_redis::apply_redis_key_edit(self, conn_id, &database_name, &key_name, &edit);
+                            let key_remains = matches!(
+                                edit,
+                                models::structs::RedisKeyEdit::Expire { .. } | models::structs::RedisKeyEdit::Persist
+                            );
+                            let preview = key_remains.then(|| {
+                                crate::driver_redis::fetch_redis_browser_preview(
+                                    self,
+                                    conn_id,
+                                    &database_name,
+                                    &key_name,
+                                    &key_type,
+                                )
+                            });
+                            if let Ok(message) = &result {
+                                self.toasts.success(message.clone());
+                            }
+                            if let Some(tab) = self.query_tabs.get_mut(self.active_tab_index)
+                                && let Some(state) = &mut tab.redis_browser_state
+                            {
+                                state.last_error = result.err();
+                                if state.selected_key.as_deref() == Some(key_name.as_str()) {
+                                    match preview {
+                                        Some(Ok(preview)) => state.preview = Some(preview),
+                                        Some(Err(error)) => state.last_error = Some(error),
+                                        None if state.last_error.is_none() => {
+                                            state.selected_key = None;
+                                            state.selected_key_type = None;
+                                            state.preview = None;
+                                        }
+                                        None => {}
+                                    }
+                                }
+                            }
+                            redis_action = Some(crate::redis_browser::RedisBrowserAction::Refresh);
+                        }
+
                         if let Some(conn_id) = redis_connection_id
                             && let Some(action) = redis_action
                         {
@@ -2220,6 +2685,7 @@ impl Tabular {
                                         }
                                     }
                                 }
+                                crate::redis_browser::RedisBrowserAction::EditKey { .. } => {}
                             }
                         }
                     
@@ -2690,7 +3156,7 @@ impl Tabular {
 
     /// Persist preferences immediately when `prefs_dirty` is set.
     /// Extracted from the former `try_save_prefs` closure in `update()`.
-    fn try_save_prefs(&mut self) {
+    pub(crate) fn try_save_prefs(&mut self) {
         if self.prefs_dirty {
             if let (Some(store), Some(rt)) = (self.config_store.as_ref(), self.runtime.as_ref()) {
                 let prefs = crate::config::AppPreferences {
@@ -2713,6 +3179,8 @@ impl Tabular {
                         None
                     },
                     auto_check_updates: self.auto_check_updates,
+                    update_channel: self.update_channel,
+                    offline_mode: self.offline_mode,
                     use_server_pagination: self.use_server_pagination,
                     last_update_check_iso: self
                         .last_saved_prefs
@@ -2724,6 +3192,39 @@ impl Tabular {
                     ai_provider: self.ai_provider,
                     ai_base_url: self.ai_base_url.clone(),
                     redis_browser_auto_refresh_seconds: self.redis_browser_auto_refresh_default_seconds.max(1),
+                    display_timezone: self.display_timezone.as_str().to_string(),
+                    timezone_for_edit_export: self.timezone_for_edit_export,
+                    sidebar_collapsed: self.sidebar_collapsed,
+                    sidebar_width: self.sidebar_width,
+                    editor_split_ratio: self.table_split_ratio,
+                    result_cache_enabled: self.result_cache_enabled,
+                    result_cache_ttl_secs: self.result_cache_ttl_secs,
+                    result_cache_max_mb: self.result_cache_max_mb,
+                    keybindings: self.keybindings.to_config(),
+                    vim_mode: self.advanced_editor.vim_mode,
+                    batch_results_in_tabs: self.batch_results_in_tabs,
+                    destructive_confirm_over_rows: self.destructive_confirm_over_rows,
+                    safe_mode: self.safe_mode,
+                    safe_mode_window_secs: self.safe_mode_window_secs,
+                    run_in_transaction: self.run_in_transaction,
+                    index_hints: self.index_hints,
+                    index_hint_min_ms: self.index_hint_min_ms,
+                    column_formatters: serde_json::to_string(&self.column_format_rules)
+                        .unwrap_or_default(),
+                    export_formatted_values: self.export_formatted_values,
+                    number_format: self.number_format,
+                    number_style: self.number_style.id().to_string(),
+                    number_decimals: self
+                        .number_decimals
+                        .map(|d| d.to_string())
+                        .unwrap_or_default(),
+                    cell_preview_chars: self.cell_preview_chars,
+                    stream_row_ceiling: self.stream_row_ceiling,
+                    default_page_size: self.default_page_size,
+                    export_skip_hidden_columns: self.export_skip_hidden_columns,
+                    table_layouts: serde_json::to_string(&self.saved_column_layouts)
+                        .unwrap_or_default(),
+                    global_proxy: serde_json::to_string(&self.global_proxy).unwrap_or_default(),
                 };
                 rt.block_on(store.save(&prefs));
                 log::debug!(
@@ -2746,6 +3247,8 @@ impl App for Tabular {
         let ctx = &root_ui.ctx().clone();
         // Ensure theme/style is applied for current `app_theme` each frame (idempotent)
         crate::window_egui::style::apply_theme(ctx, self.app_theme);
+        // Ask before the window closes over unsaved tabs
+        crate::unsaved_changes::intercept_app_close(self, ctx);
         
         // If Cmd+A was pressed, set a short-lived flag or state?
         // Actually, we need to know if "Select All" happened recently.
@@ -2793,6 +3296,15 @@ impl App for Tabular {
             self.pending_connection_pools.clear();
         }
 
+        // A shortcut being rebound in Preferences swallows this frame's key press
+        self.capture_keybinding(ctx);
+
+        // Open .sql / SQLite files dropped onto the window
+        crate::file_drop::handle_dropped_files(self, ctx);
+
+        // Mirror modified tab buffers to recovery files (debounced)
+        crate::recovery::tick(self);
+
         // Handle forced refresh flag
         if self.needs_refresh {
             self.needs_refresh = false;
@@ -2939,6 +3451,7 @@ impl App for Tabular {
                     };
                     self.advanced_editor.font_size = prefs.font_size;
                     self.advanced_editor.word_wrap = prefs.word_wrap;
+                    self.advanced_editor.vim_mode = prefs.vim_mode;
                     // Load custom data directory if set
                     if let Some(custom_dir) = &prefs.data_directory {
                         self.data_directory = custom_dir.clone();
@@ -2957,6 +3470,8 @@ impl App for Tabular {
 
                     // Load auto-update preference
                     self.auto_check_updates = prefs.auto_check_updates;
+                    self.update_channel = prefs.update_channel;
+                    self.offline_mode = prefs.offline_mode;
 
                     // Load server pagination preference
                     self.use_server_pagination = prefs.use_server_pagination;
@@ -2966,8 +3481,8 @@ impl App for Tabular {
                     self.prefs_loaded = true;
                     log::debug!("Preferences loaded successfully on startup");
 
-                    // Check for updates on startup if enabled, but only once per day
-                    if prefs.auto_check_updates {
+                    // Check for updates on startup if enabled (never in offline mode), but only once per day
+                    if prefs.auto_check_updates && !prefs.offline_mode {
                         let mut should_check = true;
                         if let Some(store_ref) = self.config_store.as_ref()
                             && let Some(last_iso) = rt.block_on(store_ref.get_last_update_check())
@@ -2989,7 +3504,9 @@ impl App for Tabular {
                         {
                             // Persist timestamp immediately to prevent repeated checks this session
                             rt.block_on(store_ref.set_last_update_check_now());
-                            let _ = sender.send(models::enums::BackgroundTask::CheckForUpdates);
+                            let _ = sender.send(models::enums::BackgroundTask::CheckForUpdates {
+                                channel: self.update_channel,
+                            });
                         }
                     }
                 }
@@ -3023,12 +3540,13 @@ impl App for Tabular {
             if ready {
                 if let Some(conn_id) = self.pool_wait_connection_id {
                     let queued = self.pool_wait_query.clone();
+                    let params = std::mem::take(&mut self.pool_wait_params);
                     
                     // Execute asynchronously to avoid freezing if connection is still slow
                     let job_id = self.next_query_job_id;
                     self.next_query_job_id += 1;
                     
-                    match crate::connection::prepare_query_job(self, conn_id, queued.clone(), job_id) {
+                    match crate::connection::prepare_bound_query_job(self, conn_id, queued.clone(), job_id, &params) {
                         Ok(job) => {
                             match crate::connection::spawn_query_job(self, job.clone(), self.query_result_sender.clone()) {
                                 Ok(handle) => {
@@ -3040,6 +3558,7 @@ impl App for Tabular {
                                         completed: false,
                                     });
                                     self.active_query_handles.insert(job_id, handle);
+                                    crate::editor::remember_result_target(self, job_id);
                                     log::debug!("🚀 Asynchronously queued pool-wait query (Job {})", job_id);
                                 }
                                 Err(e) => {
@@ -3061,6 +3580,7 @@ impl App for Tabular {
                 self.pool_wait_in_progress = false;
                 self.pool_wait_connection_id = None;
                 self.pool_wait_query.clear();
+                self.pool_wait_params.clear();
                 self.pool_wait_started_at = None;
             } else {
                 // Keep UI updated while waiting
@@ -3134,6 +3654,29 @@ impl App for Tabular {
             }
         });
 
+        // Paste into the data grid: a block copied from a spreadsheet is laid
+        // over the cells from the selected one, after a confirmation preview.
+        // Skipped while any text field has focus (editor, dialogs, cell edit).
+        if self.table_recently_clicked
+            && self.table_bottom_view == models::structs::TableBottomView::Data
+            && self.selected_cell.is_some()
+            && self.spreadsheet_state.editing_cell.is_none()
+            && self.bulk_paste.is_none()
+            && !ctx.egui_wants_keyboard_input()
+        {
+            let pasted = ctx.input(|i| {
+                i.events.iter().find_map(|e| match e {
+                    egui::Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                })
+            });
+            if let Some(text) = pasted
+                && data_table::open_bulk_paste(self, &text)
+            {
+                debug!("📋 Paste into grid at {:?} ({} chars)", self.selected_cell, text.len());
+            }
+        }
+
         // Detect Save shortcut using consume_key so it works reliably on macOS/Windows/Linux
         let mut save_shortcut = false;
         
@@ -3144,134 +3687,69 @@ impl App for Tabular {
             false
         };
 
-        if !is_diagram_active {
-            ctx.input_mut(|i| {
-                if i.consume_key(egui::Modifiers::COMMAND, egui::Key::S)
-                    || i.consume_key(egui::Modifiers::CTRL, egui::Key::S)
-                {
-                    save_shortcut = true;
-                    println!("🔥 Save shortcut detected!");
-                }
-            });
+        if !is_diagram_active
+            && self.keybinding_capture.is_none()
+            && self.keybindings.consume(ctx, super::keybindings::Action::SaveTab)
+        {
+            save_shortcut = true;
+            println!("🔥 Save shortcut detected!");
         }
 
-        // Handle keyboard shortcuts
-        ctx.input(|i| {
-            // CMD+W or CTRL+W to close current tab
-            if (i.modifiers.mac_cmd || i.modifiers.ctrl)
-                && i.key_pressed(egui::Key::W)
-                && !self.query_tabs.is_empty()
-            {
-                editor::close_tab(self, self.active_tab_index);
-            }
-
-            // CMD+Q or CTRL+Q to quit application
-            if (i.modifiers.mac_cmd || i.modifiers.ctrl) && i.key_pressed(egui::Key::Q) {
-                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
-            }
-
-            // CMD+SHIFT+P or CMD/CTRL+K to open command palette
-            if (i.modifiers.mac_cmd && i.modifiers.shift && i.key_pressed(egui::Key::P))
-                || ((i.modifiers.mac_cmd || i.modifiers.ctrl)
-                    && i.key_pressed(egui::Key::K)
-                    && !self.show_command_palette)
-            {
-                editor::open_command_palette(self);
-            }
-
-            // F12 — Go to definition (navigate sidebar to table under cursor)
-            if i.key_pressed(egui::Key::F12) && !self.show_command_palette {
-                editor::go_to_definition(self);
-            }
-
-            // F2 — Rename symbol under cursor
-            if i.key_pressed(egui::Key::F2) && !self.show_command_palette {
-                editor::begin_rename_symbol(self);
-            }
+        // App-level shortcuts (new/close tab, palette, sidebar, refresh, ...)
+        self.handle_keybindings(ctx);
 
-            // CMD/CTRL+R to refresh current view
-            if (i.modifiers.mac_cmd || i.modifiers.ctrl) && i.key_pressed(egui::Key::R) {
-                match self.table_bottom_view {
-                    models::structs::TableBottomView::Structure => {
-                        self.request_structure_refresh = true;
-                        data_table::load_structure_info_for_current_table(self);
-                    }
-                    _ => {
-                        data_table::refresh_current_table_data(self);
-                    }
-                }
-            }
-
-            // Handle table cell navigation with arrow keys
-            // Only allow table navigation when table was recently clicked
+        ctx.input(|i| {
+            // Keyboard navigation of the data grid: arrows, Home/End (row start/end),
+            // PageUp/PageDown (one screenful), Ctrl/Cmd+Home/End (first/last row).
+            // Only active once the table was clicked and no cell is being edited
+            // (the edit overlay handles its own arrow keys).
             if !self.show_command_palette
+                && !self.table_palette.open
                 && !self.show_theme_selector
                 && self.selected_cell.is_some()
                 && self.table_recently_clicked
+                && self.spreadsheet_state.editing_cell.is_none()
             {
-                let mut cell_changed = false;
-                let mut consumed_arrow = false; // track if we handled an arrow key so we can suppress editor reaction
-                if let Some((row, col)) = self.selected_cell {
-                    let max_rows = self.current_table_data.len();
-                    let shift = i.modifiers.shift;
-                    if shift && self.table_sel_anchor.is_none() {
-                        self.table_sel_anchor = Some((row, col));
-                    }
-
-                    if i.key_pressed(egui::Key::ArrowRight) {
-                        // Check the current row's column count for bounds
-                        if let Some(current_row) = self.current_table_data.get(row)
-                            && col + 1 < current_row.len()
-                        {
-                            self.selected_cell = Some((row, col + 1));
-                            cell_changed = true;
-                            self.scroll_to_selected_cell = true;
-                            log::debug!("➡️ Arrow Right: Moving to ({}, {})", row, col + 1);
-                            consumed_arrow = true;
-                        }
-                    } else if i.key_pressed(egui::Key::ArrowLeft) && col > 0 {
-                        self.selected_cell = Some((row, col - 1));
-                        cell_changed = true;
-                        self.scroll_to_selected_cell = true;
-                        log::debug!("⬅️ Arrow Left: Moving to ({}, {})", row, col - 1);
-                        consumed_arrow = true;
-                    } else if i.key_pressed(egui::Key::ArrowDown) && row + 1 < max_rows {
-                        // Check if the target row has enough columns
-                        if let Some(target_row) = self.current_table_data.get(row + 1) {
-                            let target_col = col.min(target_row.len().saturating_sub(1));
-                            self.selected_cell = Some((row + 1, target_col));
-                            cell_changed = true;
-                            self.scroll_to_selected_cell = true;
-                            log::debug!("⬇️ Arrow Down: Moving to ({}, {})", row + 1, target_col);
-                            consumed_arrow = true;
-                        }
-                    } else if i.key_pressed(egui::Key::ArrowUp) && row > 0 {
-                        // Check if the target row has enough columns
-                        if let Some(target_row) = self.current_table_data.get(row - 1) {
-                            let target_col = col.min(target_row.len().saturating_sub(1));
-                            self.selected_cell = Some((row - 1, target_col));
-                            cell_changed = true;
-                            self.scroll_to_selected_cell = true;
-                            log::debug!("⬆️ Arrow Up: Moving to ({}, {})", row - 1, target_col);
-                            consumed_arrow = true;
-                        }
-                    }
-
-                    // Update selected_row when cell changes
-                    if cell_changed && let Some((new_row, _)) = self.selected_cell {
-                        self.selected_row = Some(new_row);
-                        if !shift {
-                            self.table_sel_anchor = None;
-                        }
+                let command = i.modifiers.command || i.modifiers.ctrl;
+                let grid_move = if i.key_pressed(egui::Key::ArrowRight) {
+                    Some(data_table::GridMove::Right)
+                } else if i.key_pressed(egui::Key::ArrowLeft) {
+                    Some(data_table::GridMove::Left)
+                } else if i.key_pressed(egui::Key::ArrowDown) {
+                    Some(data_table::GridMove::Down)
+                } else if i.key_pressed(egui::Key::ArrowUp) {
+                    Some(data_table::GridMove::Up)
+                } else if i.key_pressed(egui::Key::PageDown) {
+                    Some(data_table::GridMove::PageDown)
+                } else if i.key_pressed(egui::Key::PageUp) {
+                    Some(data_table::GridMove::PageUp)
+                } else if i.key_pressed(egui::Key::Home) {
+                    Some(if command {
+                        data_table::GridMove::FirstRow
+                    } else {
+                        data_table::GridMove::RowStart
+                    })
+                } else if i.key_pressed(egui::Key::End) {
+                    Some(if command {
+                        data_table::GridMove::LastRow
+                    } else {
+                        data_table::GridMove::RowEnd
+                    })
+                } else {
+                    None
+                };
+                if let Some(grid_move) = grid_move {
+                    if data_table::move_grid_selection(self, grid_move, i.modifiers.shift) {
+                        log::debug!("⌨️ Grid {:?}: now at {:?}", grid_move, self.selected_cell);
                     }
-                }
-                if consumed_arrow {
+                    // Swallow the key even at the grid edge so the editor doesn't react
                     self.suppress_editor_arrow_once = true;
                 }
             }
 
             // Handle Structure (Columns/Indexes) cell navigation with arrow keys
             if !self.show_command_palette
+                && !self.table_palette.open
                 && !self.show_theme_selector
                 && self.table_bottom_view == models::structs::TableBottomView::Structure
                 && self.structure_selected_cell.is_some()
@@ -3379,6 +3857,16 @@ impl App for Tabular {
                 }
             }
 
+            if self.table_palette.open {
+                if i.key_pressed(egui::Key::ArrowDown) {
+                    super::table_palette::navigate_table_palette(self, 1);
+                } else if i.key_pressed(egui::Key::ArrowUp) {
+                    super::table_palette::navigate_table_palette(self, -1);
+                } else if i.key_pressed(egui::Key::Enter) {
+                    super::table_palette::open_selected_table(self);
+                }
+            }
+
             // Handle theme selector navigation
             if self.show_theme_selector {
                 // Arrow key navigation
@@ -3407,9 +3895,15 @@ impl App for Tabular {
                     self.show_command_palette = false;
                     self.command_palette_input.clear();
                     self.command_palette_selected_index = 0;
-                } else if self.spreadsheet_state.editing_cell.is_some() {
+                } else if self.table_palette.open {
+                    super::table_palette::close_table_palette(self);
+                } else if let Some((erow, ecol)) = self.spreadsheet_state.editing_cell {
                     // If currently editing a cell, cancel the in-progress edit only
+                    // and leave the cell selected with the grid keeping keyboard focus
                     self.spreadsheet_finish_cell_edit(false);
+                    self.selected_cell = Some((erow, ecol));
+                    self.selected_row = Some(erow);
+                    self.table_recently_clicked = true;
                 } else if !self.spreadsheet_state.pending_operations.is_empty()
                     || self.spreadsheet_state.is_dirty
                 {
@@ -3432,7 +3926,8 @@ impl App for Tabular {
                         }
                     }
                 } else {
-                    // Clear selections in table
+                    // Clear selections in table and hand keyboard focus back to the editor
+                    let grid_had_focus = self.table_recently_clicked;
                     self.selected_rows.clear();
                     self.selected_columns.clear();
                     self.selected_row = None;
@@ -3441,6 +3936,10 @@ impl App for Tabular {
                     self.table_dragging = false;
                     self.last_clicked_row = None;
                     self.last_clicked_column = None;
+                    if grid_had_focus {
+                        self.table_recently_clicked = false;
+                        self.editor_focus_request = true;
+                    }
                 }
             }
         });
@@ -3476,10 +3975,11 @@ impl App for Tabular {
                     op_count
                 );
                 self.spreadsheet_save_changes();
-                if !self.spreadsheet_state.is_dirty {
-                    self.toasts.success(format!("Berhasil menyimpan {} perubahan tabel", op_count));
-                } else if self.show_error_message {
+                // Failed updates are reverted, so a clean state alone does not mean success
+                if self.show_error_message {
                     self.toasts.error(format!("Gagal menyimpan tabel: {}", self.error_message));
+                } else if !self.spreadsheet_state.is_dirty {
+                    self.toasts.success(format!("Berhasil menyimpan {} perubahan tabel", op_count));
                 }
             } else if !self.query_tabs.is_empty() {
                 println!("🔥 No spreadsheet operations, saving query tab instead");
@@ -3499,6 +3999,10 @@ impl App for Tabular {
             editor::render_command_palette(self, ctx);
         }
 
+        if self.table_palette.open {
+            super::table_palette::render_table_palette(self, ctx);
+        }
+
         // Render theme selector if open
         if self.show_theme_selector {
             editor::render_theme_selector(self, ctx);
@@ -3509,6 +4013,14 @@ impl App for Tabular {
             editor::render_rename_symbol_dialog(self, ctx);
         }
 
+        if self.in_list_dialog.open {
+            crate::editor_in_list::render_in_list_dialog(self, ctx);
+        }
+
+        if !self.definition_picker.is_empty() {
+            editor::render_definition_picker(self, ctx);
+        }
+
         // Flush pending clipboard text (set during sidebar processing, not render)
         if let Some(text) = self.pending_clipboard_text.take() {
             ctx.copy_text(text);
@@ -3519,6 +4031,16 @@ impl App for Tabular {
             crate::window_egui::render_dialogs::render_schema_diff_dialog(self, ctx);
         }
 
+        // Compare-tabs diff view
+        if self.tab_compare_state.is_some() {
+            crate::window_egui::render_dialogs::render_tab_compare_dialog(self, ctx);
+        }
+
+        // PostgreSQL LISTEN/NOTIFY panel
+        if self.show_pg_listen_panel {
+            crate::window_egui::render_dialogs::render_pg_listen_panel(self, ctx);
+        }
+
         // Show cache miss dialog (topmost)
         self.render_cache_miss_dialog(ctx);
 
@@ -3541,11 +4063,15 @@ impl App for Tabular {
 
         // Poll for update install completion (async thread sends on channel)
         if let Some(rx) = &self.update_install_receiver
-            && let Ok(success) = rx.try_recv()
+            && let Ok(result) = rx.try_recv()
         {
             self.update_download_in_progress = false;
             self.update_download_started = false; // Reset this flag to prevent loop
-            self.update_installed = success;
+            self.update_installed = result.is_ok();
+            match result {
+                Ok(verification) => self.update_verification = Some(verification),
+                Err(e) => self.update_install_error = Some(e),
+            }
             self.show_update_notification = true; // show completion toast
             self.update_install_receiver = None; // cleanup
             ctx.request_repaint();
@@ -3558,6 +4084,8 @@ impl App for Tabular {
             let downloading = self.update_download_in_progress;
             let installed = self.update_installed;
             let download_started = self.update_download_started;
+            let verification = self.update_verification;
+            let install_error = self.update_install_error.clone();
             let mut keep_open = true;
             egui::Window::new("Update")
                 .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -16.0))
@@ -3578,6 +4106,22 @@ impl App for Tabular {
                                     egui::RichText::new("✅ Update downloaded successfully!")
                                         .strong(),
                                 );
+                                if verification
+                                    == Some(crate::self_update::UpdateVerification::Verified)
+                                {
+                                    ui.label(
+                                        egui::RichText::new("🔒 SHA-256 checksum verified")
+                                            .size(12.0),
+                                    );
+                                } else {
+                                    ui.label(
+                                        egui::RichText::new(
+                                            "⚠ No published checksum; download not verified",
+                                        )
+                                        .size(12.0)
+                                        .color(egui::Color32::from_rgb(255, 165, 0)),
+                                    );
+                                }
 
                                 #[cfg(target_os = "macos")]
                                 ui.label(
@@ -3630,6 +4174,9 @@ impl App for Tabular {
                                 });
                             });
                         } else if info.update_available {
+                            if let Some(error) = &install_error {
+                                ui.colored_label(egui::Color32::from_rgb(255, 100, 100), error);
+                            }
                             ui.horizontal(|ui| {
                                 ui.label(format!("Update {} available", info.latest_version));
                                 if ui.button("Details").clicked() {
@@ -3759,6 +4306,11 @@ impl App for Tabular {
             }
         }
 
+        crate::spreadsheet::poll_spreadsheet_save(self);
+        if self.spreadsheet_save_receiver.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        }
+
         self.render_replication_dialog(ctx);
         dialog::render_save_dialog(self, ctx);
         connection::render_connection_selector(self, ctx);
@@ -3768,8 +4320,24 @@ impl App for Tabular {
         dialog::render_index_dialog(self, ctx);
         dialog::render_create_table_dialog(self, ctx);
         dialog::render_csv_import_dialog(self, ctx);
+        dialog::render_routine_call_dialog(self, ctx);
+        crate::data_table::render_sqlite_export_dialog(self, ctx);
+        crate::data_table::render_cell_detail(self, ctx);
+        crate::data_table::render_structured_editor(self, ctx);
+        crate::data_table::render_column_stats(self, ctx);
+        crate::data_table::render_result_search(self, ctx);
+        crate::data_table::render_bulk_paste(self, ctx);
+        crate::data_table::render_manage_columns(self, ctx);
+        crate::file_drop::render_sqlite_drop_prompt(self, ctx);
+        connection::render_fleet_run_dialog(self, ctx);
+        connection::safe_mode::render_safe_change_banner(self, ctx);
         dialog::render_parameter_dialog(self, ctx);
+        dialog::render_parameters_panel(self, ctx);
         dialog::render_unsafe_dml_dialog(self, ctx);
+        dialog::render_session_kill_dialog(self, ctx);
+        crate::recovery::render_recovery_dialog(self, ctx);
+        crate::workspace_profile::render_profiles_window(self, ctx);
+        crate::unsaved_changes::render_unsaved_changes_dialog(self, ctx);
         sidebar_query::render_create_folder_dialog(self, ctx);
         sidebar_query::render_move_to_folder_dialog(self, ctx);
         // Update dialog
@@ -3795,6 +4363,12 @@ impl App for Tabular {
         // stack above all panels and dialogs.
         self.toasts.show(ctx);
     } // end update
+
+    fn on_exit(&mut self) {
+        crate::workspace_profile::save_on_exit(self);
+        // Normal shutdown: this session's recovery files are no longer needed
+        self.recovery_writer.finish(&crate::recovery::recovery_dir());
+    }
 } // end impl App for Tabular
 
 
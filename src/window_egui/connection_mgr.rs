@@ -251,8 +251,12 @@ impl super::Tabular {
     }
     /// Trigger a background schema-cache sync the first time a connection is added or
     /// opened, so autocomplete suggestions become available without a manual refresh.
-    /// Runs at most once per connection per session and skips API-HTTP (no schema).
+    /// Runs at most once per connection per session and skips API-HTTP (no schema)
+    /// and offline mode.
     pub fn maybe_auto_sync_connection(&mut self, connection_id: i64) {
+        if self.offline_mode {
+            return;
+        }
         let is_http = self
             .connections
             .iter()
@@ -331,7 +335,7 @@ impl super::Tabular {
             );
         } else {
             debug!(
-                "⚠️ Could not locate connection node {} in primary tree; trying filtered tree / rebuild",
+                "⚠️ Could not locate connection node {} in primary tree; trying filtered tree",
                 connection_id
             );
             // Try filtered tree (search results)
@@ -351,33 +355,13 @@ impl super::Tabular {
                     conn_node.name, was_expanded
                 );
             } else {
-                // As a last resort rebuild the whole tree then search again
-                crate::sidebar_database::refresh_connections_tree(self);
-                if let Some(conn_node2) =
-                    Self::find_connection_node_recursive(&mut self.items_tree, connection_id)
-                {
-                    let mut expansion_state = std::collections::HashMap::new();
-                    Self::save_expansion_state(conn_node2, &mut expansion_state);
-                    self.pending_expansion_restore.insert(connection_id, expansion_state);
-
-                    let was_expanded = conn_node2.is_expanded;
-                    conn_node2.children.clear();
-                    conn_node2.is_loaded = false;
-                    conn_node2.is_expanded = was_expanded;
-                    debug!(
-                        "🔄 Reset connection node after rebuild: {} (expanded: {})",
-                        conn_node2.name, was_expanded
-                    );
-                } else {
-                    debug!(
-                        "❌ Still could not locate connection node {} after rebuild. Existing connection IDs: {:?}",
-                        connection_id,
-                        self.connections
-                            .iter()
-                            .filter_map(|c| c.id)
-                            .collect::<Vec<_>>()
-                    );
-                }
+                // Not in the tree (e.g. hidden by a filter). Refresh the cache anyway; the node
+                // loads from it when shown. Rebuilding the whole tree here would collapse
+                // every other connection.
+                debug!(
+                    "⚠️ Connection node {} not in tree; refreshing cache only",
+                    connection_id
+                );
             }
         }
 
@@ -403,6 +387,23 @@ impl super::Tabular {
         }
     }
 
+    /// Repopulate a refreshing connection's subtree from the partially filled cache so
+    /// databases appear as they arrive. Only this connection's node is touched.
+    pub(crate) fn apply_refresh_progress_to_tree(&mut self, connection_id: i64) {
+        let mut tree = std::mem::take(&mut self.items_tree);
+        if let Some(conn_node) = Self::find_connection_node_recursive(&mut tree, connection_id)
+            && conn_node.is_expanded
+        {
+            // Keep anything the user expanded mid-refresh along with the saved state
+            let state = self.pending_expansion_restore.entry(connection_id).or_default();
+            Self::save_expansion_state(conn_node, state);
+            conn_node.children.clear();
+            conn_node.is_loaded = false;
+            self.load_expanded_nodes_recursive(connection_id, conn_node);
+        }
+        self.items_tree = tree;
+    }
+
     pub fn restore_expansion_state(
         node: &mut models::structs::TreeNode,
         state_map: &std::collections::HashMap<String, bool>,
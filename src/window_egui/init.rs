@@ -50,13 +50,47 @@ impl super::Tabular {
         };
         self.advanced_editor.font_size = prefs.font_size;
         self.advanced_editor.word_wrap = prefs.word_wrap;
+        self.advanced_editor.vim_mode = prefs.vim_mode;
         if let Some(dir) = prefs.data_directory.clone() {
             self.data_directory = dir;
         }
         self.auto_check_updates = prefs.auto_check_updates;
+        self.update_channel = prefs.update_channel;
+        self.offline_mode = prefs.offline_mode;
         self.use_server_pagination = prefs.use_server_pagination;
         self.enable_debug_logging = prefs.enable_debug_logging;
         self.redis_browser_auto_refresh_default_seconds = prefs.redis_browser_auto_refresh_seconds.max(1);
+        self.display_timezone = prefs.display_timezone.parse().unwrap_or_default();
+        self.timezone_for_edit_export = prefs.timezone_for_edit_export;
+        self.sidebar_collapsed = prefs.sidebar_collapsed;
+        self.sidebar_width = prefs.sidebar_width.clamp(150.0, 500.0);
+        self.table_split_ratio = prefs
+            .editor_split_ratio
+            .clamp(super::render_dialogs::SPLIT_RATIO_MIN, super::render_dialogs::SPLIT_RATIO_MAX);
+        self.result_cache_enabled = prefs.result_cache_enabled;
+        self.result_cache_ttl_secs = prefs.result_cache_ttl_secs.max(1);
+        self.result_cache_max_mb = prefs.result_cache_max_mb.max(1);
+        self.batch_results_in_tabs = prefs.batch_results_in_tabs;
+        self.destructive_confirm_over_rows = prefs.destructive_confirm_over_rows;
+        self.safe_mode = prefs.safe_mode;
+        self.safe_mode_window_secs = prefs.safe_mode_window_secs.max(1);
+        self.run_in_transaction = prefs.run_in_transaction;
+        self.index_hints = prefs.index_hints;
+        self.index_hint_min_ms = prefs.index_hint_min_ms;
+        self.column_format_rules = serde_json::from_str(&prefs.column_formatters).unwrap_or_default();
+        self.export_formatted_values = prefs.export_formatted_values;
+        self.number_format = prefs.number_format;
+        self.number_style = crate::data_table::NumberStyle::from_id(&prefs.number_style);
+        self.number_decimals = prefs.number_decimals.parse().ok();
+        self.cell_preview_chars = prefs.cell_preview_chars;
+        self.stream_row_ceiling = prefs.stream_row_ceiling;
+        self.default_page_size = prefs.default_page_size.max(1);
+        self.page_size = self.default_page_size;
+        self.export_skip_hidden_columns = prefs.export_skip_hidden_columns;
+        self.saved_column_layouts = serde_json::from_str(&prefs.table_layouts).unwrap_or_default();
+        self.global_proxy = serde_json::from_str(&prefs.global_proxy).unwrap_or_default();
+        crate::proxy_tunnel::set_global_proxy(&self.global_proxy);
+        self.keybindings = super::keybindings::Keybindings::from_config(&prefs.keybindings);
         // Mirror AI settings
         self.ai_api_key = prefs.ai_api_key.clone();
         self.ai_model = prefs.ai_model.clone();
@@ -161,6 +195,9 @@ impl super::Tabular {
         let (result_sender, result_receiver) = mpsc::channel::<models::enums::BackgroundResult>();
         let (query_result_sender, query_result_receiver) =
             mpsc::channel::<connection::QueryResultMessage>();
+        let (query_stream_sender, query_stream_receiver) =
+            mpsc::channel::<connection::StreamEvent>();
+        let (autocomplete_cols_sender, autocomplete_cols_receiver) = mpsc::channel();
 
         // Create shared runtime for all database operations
         let runtime = match tokio::runtime::Runtime::new() {
@@ -186,6 +223,8 @@ impl super::Tabular {
             selected_menu: "Database".to_string(),
             items_tree: Vec::new(),
             queries_tree: Vec::new(),
+            saved_query_meta: HashMap::new(),
+            query_tag_filter: String::new(),
             history_tree: Vec::new(),
             history_items: Vec::new(),
             connections: Vec::new(),
@@ -196,8 +235,10 @@ impl super::Tabular {
             connection_pools: HashMap::new(), // Start with empty cache
             pending_connection_pools: std::collections::HashSet::new(), // Track pools being created
             shared_connection_pools: Arc::new(std::sync::Mutex::new(HashMap::new())), // Shared pools for background tasks
+            table_stats: Arc::new(std::sync::Mutex::new(HashMap::new())),
             pending_pool_log_last: HashMap::new(),
             prefetch_progress: HashMap::new(),
+            refresh_progress: HashMap::new(),
             prefetch_in_progress: std::collections::HashSet::new(),
             show_edit_connection: false,
             edit_connection: models::structs::ConnectionConfig::default(),
@@ -209,7 +250,8 @@ impl super::Tabular {
             current_object_ddl: None,
             current_connection_id: None,
             current_page: 0,
-            page_size: 500, // Default 500 rows per page
+            page_size: crate::config::default_page_size(),
+            page_jump_input: String::new(),
             total_rows: 0,
             all_table_data: Vec::new(),
             // Server-side pagination
@@ -217,14 +259,25 @@ impl super::Tabular {
             actual_total_rows: None,
             current_base_query: String::new(),
             table_split_ratio: 0.6, // Default 60% for editor, 40% for table
+            record_view_index: 0,
+            result_cache_enabled: false,
+            result_cache_ttl_secs: crate::config::default_result_cache_ttl_secs(),
+            result_cache_max_mb: crate::config::default_result_cache_max_mb(),
+            batch_results_in_tabs: false,
+            result_targets: HashMap::new(),
+            result_cache_jobs: HashMap::new(),
             sort_column: None,
             sort_ascending: true,
             test_connection_status: None,
             test_connection_in_progress: false,
+            test_connection_receiver: None,
             background_sender: Some(background_sender),
             background_receiver: Some(result_receiver),
             query_result_sender,
             query_result_receiver,
+            query_stream_sender,
+            query_stream_receiver,
+            query_streams: std::collections::HashMap::new(),
             active_query_jobs: std::collections::HashMap::new(),
             active_query_handles: std::collections::HashMap::new(),
             cancelled_query_jobs: std::collections::HashMap::new(),
@@ -237,6 +290,7 @@ impl super::Tabular {
             fetching_databases: std::collections::HashSet::new(),
             pending_expansion_restore: std::collections::HashMap::new(),
             pending_auto_load: std::collections::HashSet::new(),
+            pending_tree_reveal: None,
             auto_synced_connections: std::collections::HashSet::new(),
             query_tabs: Vec::new(),
             active_tab_index: 0,
@@ -246,6 +300,8 @@ impl super::Tabular {
             show_save_dialog: false,
             save_filename: String::new(),
             save_directory: String::new(),
+            save_query_meta: models::structs::SavedQueryMeta::default(),
+            save_query_tags: String::new(),
             save_directory_picker_result: None,
             show_connection_selector: false,
             pending_query: String::new(),
@@ -255,9 +311,36 @@ impl super::Tabular {
             show_parameter_dialog: false,
             parameter_dialog_query: String::new(),
             parameter_inputs: Vec::new(),
+            parameter_dialog_file: None,
+            parameter_preset_name: String::new(),
+            parameter_presets: HashMap::new(),
+            pending_parameter_preset: None,
+            show_parameters_panel: false,
             show_unsafe_dml_dialog: false,
             unsafe_dml_query: String::new(),
             unsafe_dml_type: String::new(),
+            unsafe_dml_filtered: false,
+            unsafe_dml_count_sql: None,
+            unsafe_dml_count: None,
+            fleet_run: None,
+            destructive_confirm_over_rows: 0,
+            safe_mode: false,
+            run_in_transaction: false,
+            safe_mode_window_secs: crate::config::default_safe_mode_window_secs(),
+            safe_change: None,
+            index_hints: false,
+            index_hint_min_ms: crate::config::default_index_hint_min_ms(),
+            dismissed_index_hints: std::collections::HashSet::new(),
+            column_format_rules: Vec::new(),
+            export_formatted_values: false,
+            number_format: false,
+            number_style: crate::data_table::NumberStyle::Locale,
+            number_decimals: None,
+            cell_preview_chars: crate::config::default_cell_preview_chars(),
+            stream_row_ceiling: crate::config::default_stream_row_ceiling(),
+            default_page_size: crate::config::default_page_size(),
+            export_skip_hidden_columns: false,
+            show_manage_columns: false,
             error_message: String::new(),
             show_error_message: false,
             advanced_editor: models::structs::AdvancedEditor::default(),
@@ -283,6 +366,9 @@ impl super::Tabular {
             database_search_text: String::new(),
             filtered_items_tree: Vec::new(),
             cache_miss_request: None,
+            column_allowed_values: std::collections::HashMap::new(),
+            filter_chips: Vec::new(),
+            column_distinct_values: std::collections::HashMap::new(),
             show_search_results: false,
             history_search_text: String::new(),
             filtered_history_tree: Vec::new(),
@@ -309,8 +395,16 @@ impl super::Tabular {
             table_sel_anchor: None,
             table_dragging: false,
             scroll_to_selected_cell: false,
+            data_visible_rows: 20,
+            editor_focus_request: false,
+            editor_scroll_to_cursor: false,
+            bookmark_snapshot: None,
+            display_timezone: crate::config::DisplayTimezone::AsStored,
+            timezone_for_edit_export: false,
             // Column width management
             column_widths: Vec::new(),
+            saved_column_layouts: std::collections::HashMap::new(),
+            global_proxy: crate::models::structs::ProxyConfig::default(),
             min_column_width: 50.0,
             // Gear menu and about dialog
             show_about_dialog: false,
@@ -331,14 +425,21 @@ impl super::Tabular {
             last_autocomplete_trigger_len: 0,
             pending_cursor_set: None,
             editor_focus_boost_frames: 0,
+            editor_has_focus: false,
             autocomplete_expected_cursor: None,
             autocomplete_protection_frames: 0,
             autocomplete_navigated: false,
+            autocomplete_hover_index: None,
+            autocomplete_scroll_to_selected: false,
             autocomplete_last_update: None,
-            autocomplete_debounce_ms: 180,
+            autocomplete_debounce_ms: 100,
+            autocomplete_pending: false,
             fk_cache_warmed: std::collections::HashSet::new(),
             autocomplete_cols_warmed: std::collections::HashSet::new(),
             autocomplete_cols_mem: std::collections::HashMap::new(),
+            autocomplete_cols_inflight: std::collections::HashSet::new(),
+            autocomplete_cols_sender,
+            autocomplete_cols_receiver,
             selection_force_clear: false,
             // Index dialog defaults
             show_index_dialog: false,
@@ -392,6 +493,7 @@ impl super::Tabular {
             directory_picker_result: None,
             sqlite_path_picker_result: None,
             temp_sqlite_path: None,
+            pending_sqlite_drops: Vec::new(),
             // Self-update settings
             update_info: None,
             show_update_dialog: false,
@@ -400,25 +502,33 @@ impl super::Tabular {
             last_update_check: None,
             update_download_in_progress: false,
             auto_check_updates: true,
+            update_channel: crate::self_update::UpdateChannel::Stable,
+            offline_mode: false,
             manual_update_check: false,
             show_update_notification: false,
             update_download_started: false,
             update_installed: false,
             update_install_receiver: None,
+            update_verification: None,
+            update_install_error: None,
             enable_debug_logging: false, // Default to false
             auto_updater: crate::auto_updater::AutoUpdater::new().ok(),
             settings_active_pref_tab: PrefTab::ApplicationTheme,
+            display_timezone_input: String::new(),
             show_settings_menu: false,
             // Pool-wait defaults
             pool_wait_in_progress: false,
             pool_wait_connection_id: None,
             pool_wait_query: String::new(),
+            pool_wait_params: Vec::new(),
             pool_wait_started_at: None,
+            reconnect_prompt: None,
+            reconnect_auto_retry_used: false,
             // Spreadsheet editing state
             spreadsheet_state: crate::models::structs::SpreadsheetState::default(),
             extra_cursors: Vec::new(),
             last_editor_text: String::new(),
-            highlight_cache: std::collections::HashMap::new(),
+            highlight_cache: crate::syntax_ts::HighlightCache::default(),
             last_highlight_hash: None,
             suppress_editor_arrow_once: false,
             sql_semantic_snapshot: None,
@@ -438,6 +548,7 @@ impl super::Tabular {
             last_plan_hash: None,
             last_plan_cache_key: None,
             last_ctes: None,
+            query_plan_panel: Default::default(),
             lint_messages: Vec::new(),
             show_lint_panel: false,
             lint_panel_shown_at: None,
@@ -465,8 +576,10 @@ impl super::Tabular {
             new_view_connection_id: None,
             edit_view_original_name: None,
             custom_view_save_receiver: None,
+            spreadsheet_save_receiver: None,
             global_backspace_pressed: false,
-            sidebar_visible: true,
+            sidebar_collapsed: false,
+            sidebar_width: crate::config::default_sidebar_width(),
             show_add_replication_dialog: false,
             replication_dialog: None,
             replication_setup_receiver: None,
@@ -493,16 +606,38 @@ impl super::Tabular {
             toasts: crate::window_egui::notifications::ToastManager::default(),
             show_csv_import_dialog: false,
             csv_import_state: None,
+            routine_call_state: None,
+            sqlite_export_state: None,
+            cell_detail: None,
+            structured_edit: None,
+            column_stats: None,
+            result_search: None,
+            bulk_paste: None,
+            edit_flashes: Vec::new(),
             rename_symbol_active: false,
             rename_symbol_old: String::new(),
             rename_symbol_new: String::new(),
+            in_list_dialog: Default::default(),
+            table_palette: Default::default(),
+            definition_picker: Vec::new(),
             data_scroll_x: 0.0,
-            data_scroll_y: 0.0,
             cached_connection_types: std::collections::HashMap::new(),
             pending_clipboard_text: None,
+            show_pg_listen_panel: false,
+            pg_listen: models::structs::PgListenState::default(),
+            pending_session_kill: None,
+            recovery_writer: crate::recovery::RecoveryWriter::default(),
+            workspace_profiles: Default::default(),
+            unsaved_changes: Default::default(),
+            recovery_files: Vec::new(),
+            show_recovery_dialog: false,
+            keybindings: super::keybindings::Keybindings::default(),
+            vim: crate::editor_vim::VimState::default(),
+            keybinding_capture: None,
             show_schema_diff_dialog: false,
             schema_diff_state: None,
             schema_diff_receiver: None,
+            tab_compare_state: None,
         };
 
         // Clear any old cached pools
@@ -518,6 +653,9 @@ impl super::Tabular {
         // Create initial query tab
         editor::create_new_tab(&mut app, "Untitled Query".to_string(), String::new());
 
+        // Offer to restore buffers left behind by a crashed session
+        crate::recovery::check_on_startup(&mut app);
+
         // Start background thread AFTER database is initialized
         app.start_background_worker(background_receiver, result_sender);
 
@@ -677,20 +815,27 @@ impl super::Tabular {
                                 {
                                     Ok((available_keyspaces, keyspace_label, key_pairs, is_cluster)) => {
                                         let key_count = key_pairs.len();
+                                        // TTLs are cheap on a single keyspace; a cluster loads them per key on selection
+                                        let ttls = if is_cluster {
+                                            std::collections::HashMap::new()
+                                        } else {
+                                            let names: Vec<String> =
+                                                key_pairs.iter().map(|(key_name, _)| key_name.clone()).collect();
+                                            driver_redis::fetch_key_ttls(&connection, &keyspace_label, &names).await
+                                        };
+                                        let keyspace = if is_cluster {
+                                            driver_redis::REDIS_CLUSTER_KEYSPACE
+                                        } else {
+                                            keyspace_label.as_str()
+                                        };
                                         Some(models::structs::RedisBrowserState {
                                             available_keyspaces,
                                             keyspace_label: keyspace_label.clone(),
                                             keys: key_pairs
                                                 .into_iter()
-                                                .map(|(key_name, key_type)| models::structs::RedisBrowserKeyEntry {
-                                                    key_name,
-                                                    key_type,
-                                                    ttl_label: if is_cluster {
-                                                        "Cluster".to_string()
-                                                    } else {
-                                                        keyspace_label.clone()
-                                                    },
-                                                    size_label: "-".to_string(),
+                                                .map(|(key_name, key_type)| {
+                                                    let ttl = ttls.get(&key_name).copied();
+                                                    driver_redis::browser_key_entry(key_name, key_type, ttl, keyspace)
                                                 })
                                                 .collect(),
                                             status_text: if is_cluster {
@@ -723,7 +868,7 @@ impl super::Tabular {
                         search_text,
                     } => {
                         if let Ok(rt) = tokio::runtime::Runtime::new() {
-                            let keys = rt.block_on(async {
+                            let found = rt.block_on(async {
                                 let redis_manager = {
                                     let pools = shared_pools.lock().ok()?;
                                     if let Some(models::enums::DatabasePool::Redis(mgr)) = pools.get(&connection_id) {
@@ -740,23 +885,26 @@ impl super::Tabular {
                                 )
                                 .await?;
 
-                                Some(
-                                    driver_redis::search_redis_browser_keys_from_connection(
-                                        &connection,
-                                        &redis_manager,
-                                        &database_name,
-                                        &search_text,
-                                        200,
-                                    )
-                                    .await,
+                                let keys = driver_redis::search_redis_browser_keys_from_connection(
+                                    &connection,
+                                    &redis_manager,
+                                    &database_name,
+                                    &search_text,
+                                    200,
                                 )
+                                .await;
+                                let names: Vec<String> = keys.iter().map(|(key_name, _)| key_name.clone()).collect();
+                                let ttls = driver_redis::fetch_key_ttls(&connection, &database_name, &names).await;
+                                Some((keys, ttls))
                             });
+                            let (keys, ttls) = found.unwrap_or_default();
 
                             let _ = result_sender.send(models::enums::BackgroundResult::RedisBrowserSearchFetched {
                                 connection_id,
                                 database_name,
                                 search_text,
-                                keys: keys.unwrap_or_default(),
+                                keys,
+                                ttls,
                             });
                         }
                     }
@@ -766,15 +914,45 @@ impl super::Tabular {
                             connection_id,
                             cache_pool.is_some()
                         );
-                        // Perform actual refresh and cache preload on a lightweight runtime
+                        // Perform actual refresh and cache preload on a lightweight runtime,
+                        // sampling the cache meanwhile so the sidebar can show real progress.
                         let success = if let Some(cache_pool_arc) = &cache_pool {
                             match tokio::runtime::Runtime::new() {
-                                Ok(rt) => rt.block_on(
-                                    crate::connection::refresh_connection_background_async(
+                                Ok(rt) => rt.block_on(async {
+                                    let pool = Some(cache_pool_arc.clone());
+                                    let refresh = crate::connection::refresh_connection_background_async(
                                         connection_id,
-                                        &Some(cache_pool_arc.clone()),
-                                    ),
-                                ),
+                                        &pool,
+                                    );
+                                    tokio::pin!(refresh);
+                                    let period = std::time::Duration::from_millis(400);
+                                    let mut ticker = tokio::time::interval_at(
+                                        tokio::time::Instant::now() + period,
+                                        period,
+                                    );
+                                    let mut last_sent = None;
+                                    loop {
+                                        tokio::select! {
+                                            ok = &mut refresh => break ok,
+                                            _ = ticker.tick() => {
+                                                let progress = crate::connection::refresh_progress_snapshot(
+                                                    cache_pool_arc.as_ref(),
+                                                    connection_id,
+                                                )
+                                                .await;
+                                                if last_sent != Some(progress) {
+                                                    last_sent = Some(progress);
+                                                    let _ = result_sender.send(
+                                                        models::enums::BackgroundResult::RefreshProgress {
+                                                            connection_id,
+                                                            progress,
+                                                        },
+                                                    );
+                                                }
+                                            }
+                                        }
+                                    }
+                                }),
                                 Err(_) => false,
                             }
                         } else {
@@ -790,10 +968,10 @@ impl super::Tabular {
                                 success,
                             });
                     }
-                    models::enums::BackgroundTask::CheckForUpdates => {
+                    models::enums::BackgroundTask::CheckForUpdates { channel } => {
                         // Perform update check on a lightweight runtime (if required by async API)
                         let result = if let Ok(rt) = tokio::runtime::Runtime::new() {
-                            rt.block_on(crate::self_update::check_for_updates())
+                            rt.block_on(crate::self_update::check_for_updates(channel))
                                 .map_err(|e| e.to_string())
                         } else {
                             Err("Failed to create runtime for update check".to_string())
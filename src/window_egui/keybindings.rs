@@ -0,0 +1,569 @@
+//! Keyboard shortcuts: an `Action` → `KeyChord` map persisted in preferences
+//! (only the user's overrides are stored) plus the dispatcher for app-level
+//! actions. Widget-local actions (run, format, comment, ...) are consulted
+//! by the widget that owns them through `Keybindings::pressed`/`consume`.
+
+use std::collections::HashMap;
+
+use eframe::egui;
+use log::debug;
+
+use crate::editor;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    RunQuery,
+    FormatSql,
+    ExplainQuery,
+    ToggleComment,
+    NewTab,
+    CloseTab,
+    SaveTab,
+    CommandPalette,
+    GoToTable,
+    ToggleSidebar,
+    Refresh,
+    OpenSettings,
+    GoToDefinition,
+    JumpToDeclaration,
+    RenameSymbol,
+    ToggleAiPanel,
+    ToggleTransaction,
+    ToggleBookmark,
+    NextBookmark,
+    PreviousBookmark,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 21] = [
+        Action::RunQuery,
+        Action::FormatSql,
+        Action::ExplainQuery,
+        Action::ToggleComment,
+        Action::NewTab,
+        Action::CloseTab,
+        Action::SaveTab,
+        Action::CommandPalette,
+        Action::GoToTable,
+        Action::ToggleSidebar,
+        Action::Refresh,
+        Action::OpenSettings,
+        Action::GoToDefinition,
+        Action::JumpToDeclaration,
+        Action::RenameSymbol,
+        Action::ToggleAiPanel,
+        Action::ToggleTransaction,
+        Action::ToggleBookmark,
+        Action::NextBookmark,
+        Action::PreviousBookmark,
+        Action::Quit,
+    ];
+
+    /// Actions fired by [`super::Tabular::handle_keybindings`]; the others are
+    /// handled where their context lives (editor, run button, save handling).
+    const GLOBAL: [Action; 11] = [
+        Action::ExplainQuery,
+        Action::NewTab,
+        Action::CloseTab,
+        Action::CommandPalette,
+        Action::GoToTable,
+        Action::ToggleSidebar,
+        Action::Refresh,
+        Action::OpenSettings,
+        Action::GoToDefinition,
+        Action::RenameSymbol,
+        Action::ToggleTransaction,
+    ];
+
+    /// Stable key used in the persisted preference.
+    pub fn id(self) -> &'static str {
+        match self {
+            Action::RunQuery => "run_query",
+            Action::FormatSql => "format_sql",
+            Action::ExplainQuery => "explain_query",
+            Action::ToggleComment => "toggle_comment",
+            Action::NewTab => "new_tab",
+            Action::CloseTab => "close_tab",
+            Action::SaveTab => "save_tab",
+            Action::CommandPalette => "command_palette",
+            Action::GoToTable => "go_to_table",
+            Action::ToggleSidebar => "toggle_sidebar",
+            Action::Refresh => "refresh",
+            Action::OpenSettings => "open_settings",
+            Action::GoToDefinition => "go_to_definition",
+            Action::JumpToDeclaration => "jump_to_declaration",
+            Action::RenameSymbol => "rename_symbol",
+            Action::ToggleAiPanel => "toggle_ai_panel",
+            Action::ToggleTransaction => "toggle_transaction",
+            Action::ToggleBookmark => "toggle_bookmark",
+            Action::NextBookmark => "next_bookmark",
+            Action::PreviousBookmark => "previous_bookmark",
+            Action::Quit => "quit",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::RunQuery => "Run query",
+            Action::FormatSql => "Format SQL",
+            Action::ExplainQuery => "Explain query",
+            Action::ToggleComment => "Toggle line comment",
+            Action::NewTab => "New tab",
+            Action::CloseTab => "Close tab",
+            Action::SaveTab => "Save tab",
+            Action::CommandPalette => "Command palette",
+            Action::GoToTable => "Go to table",
+            Action::ToggleSidebar => "Toggle sidebar",
+            Action::Refresh => "Refresh view",
+            Action::OpenSettings => "Preferences",
+            Action::GoToDefinition => "Go to definition (sidebar)",
+            Action::JumpToDeclaration => "Jump to declaration (editor)",
+            Action::RenameSymbol => "Rename symbol",
+            Action::ToggleAiPanel => "Toggle AI panel",
+            Action::ToggleTransaction => "Toggle transaction mode",
+            Action::ToggleBookmark => "Toggle bookmark",
+            Action::NextBookmark => "Next bookmark",
+            Action::PreviousBookmark => "Previous bookmark",
+            Action::Quit => "Quit",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.id() == id)
+    }
+
+    fn default_chord(self) -> KeyChord {
+        use egui::Key;
+        match self {
+            Action::RunQuery => KeyChord::command(Key::Enter),
+            Action::FormatSql => KeyChord::command_shift(Key::F),
+            Action::ExplainQuery => KeyChord::command_shift(Key::E),
+            Action::ToggleComment => KeyChord::command(Key::Slash),
+            Action::NewTab => KeyChord::command(Key::T),
+            Action::CloseTab => KeyChord::command(Key::W),
+            Action::SaveTab => KeyChord::command(Key::S),
+            Action::CommandPalette => KeyChord::command(Key::K),
+            Action::GoToTable => KeyChord::command(Key::P),
+            Action::ToggleSidebar => KeyChord::command(Key::Backslash),
+            Action::Refresh => KeyChord::command(Key::R),
+            Action::OpenSettings => KeyChord::command(Key::Comma),
+            Action::GoToDefinition => KeyChord::plain(Key::F12),
+            Action::JumpToDeclaration => KeyChord::command(Key::B),
+            Action::RenameSymbol => KeyChord::plain(Key::F2),
+            Action::ToggleAiPanel => KeyChord::command_shift(Key::A),
+            Action::ToggleTransaction => KeyChord::command_shift(Key::T),
+            Action::ToggleBookmark => KeyChord::command(Key::F2),
+            Action::NextBookmark => KeyChord::shift(Key::F2),
+            Action::PreviousBookmark => KeyChord::command_shift(Key::F2),
+            Action::Quit => KeyChord::command(Key::Q),
+        }
+    }
+}
+
+/// A key plus modifiers. `command` is Cmd on macOS and Ctrl elsewhere; on
+/// macOS Ctrl is accepted as well, matching the historical shortcuts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyChord {
+    pub command: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub key: egui::Key,
+}
+
+impl KeyChord {
+    const fn plain(key: egui::Key) -> Self {
+        Self { command: false, alt: false, shift: false, key }
+    }
+
+    const fn shift(key: egui::Key) -> Self {
+        Self { command: false, alt: false, shift: true, key }
+    }
+
+    const fn command(key: egui::Key) -> Self {
+        Self { command: true, alt: false, shift: false, key }
+    }
+
+    const fn command_shift(key: egui::Key) -> Self {
+        Self { command: true, alt: false, shift: true, key }
+    }
+
+    fn modifiers_match(&self, m: &egui::Modifiers) -> bool {
+        (m.mac_cmd || m.command || m.ctrl) == self.command
+            && m.shift == self.shift
+            && m.alt == self.alt
+    }
+
+    pub fn pressed(&self, i: &egui::InputState) -> bool {
+        i.key_pressed(self.key) && self.modifiers_match(&i.modifiers)
+    }
+
+    /// Remove the matching key-press events so widgets (e.g. the editor's
+    /// TextEdit) don't also react. Returns whether the chord was pressed.
+    pub fn consume(&self, i: &mut egui::InputState) -> bool {
+        let before = i.events.len();
+        i.events.retain(|e| {
+            !matches!(
+                e,
+                egui::Event::Key { key, pressed: true, modifiers, .. }
+                    if *key == self.key && self.modifiers_match(modifiers)
+            )
+        });
+        i.events.len() != before
+    }
+
+    /// First key press of the frame as a chord (used when rebinding).
+    fn from_event(e: &egui::Event) -> Option<Self> {
+        match e {
+            egui::Event::Key { key, pressed: true, modifiers, .. } => Some(Self {
+                command: modifiers.mac_cmd || modifiers.command || modifiers.ctrl,
+                alt: modifiers.alt,
+                shift: modifiers.shift,
+                key: *key,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Human readable, platform aware: "⌘ Shift+F" on macOS, "Ctrl+Shift+F" elsewhere.
+    pub fn label(&self) -> String {
+        let mac = cfg!(target_os = "macos");
+        let mut out = String::new();
+        if self.command {
+            out.push_str(if mac { "⌘ " } else { "Ctrl+" });
+        }
+        if self.alt {
+            out.push_str(if mac { "⌥ " } else { "Alt+" });
+        }
+        if self.shift {
+            out.push_str("Shift+");
+        }
+        out.push_str(self.key.symbol_or_name());
+        out
+    }
+
+    /// Platform neutral form stored in preferences, e.g. "Cmd+Shift+F".
+    fn to_config(self) -> String {
+        let mut parts = Vec::new();
+        if self.command {
+            parts.push("Cmd");
+        }
+        if self.alt {
+            parts.push("Alt");
+        }
+        if self.shift {
+            parts.push("Shift");
+        }
+        parts.push(self.key.name());
+        parts.join("+")
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let mut chord = Self::plain(egui::Key::Escape);
+        let mut key = None;
+        for part in s.split('+').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "cmd" | "ctrl" | "command" => chord.command = true,
+                "alt" | "option" => chord.alt = true,
+                "shift" => chord.shift = true,
+                _ => key = Some(egui::Key::from_name(part)?),
+            }
+        }
+        chord.key = key?;
+        Some(chord)
+    }
+}
+
+/// Historical macOS command palette shortcut, kept next to the configurable
+/// binding unless the user assigned it to another action.
+const MAC_COMMAND_PALETTE: KeyChord = KeyChord::command_shift(egui::Key::P);
+
+/// Effective bindings: defaults overlaid with user overrides.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Keybindings {
+    overrides: HashMap<Action, KeyChord>,
+}
+
+impl Keybindings {
+    pub fn chord(&self, action: Action) -> KeyChord {
+        self.overrides
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_chord())
+    }
+
+    pub fn is_default(&self, action: Action) -> bool {
+        self.chord(action) == action.default_chord()
+    }
+
+    pub fn set(&mut self, action: Action, chord: KeyChord) {
+        if chord == action.default_chord() {
+            self.overrides.remove(&action);
+        } else {
+            self.overrides.insert(action, chord);
+        }
+    }
+
+    pub fn reset(&mut self, action: Action) {
+        self.overrides.remove(&action);
+    }
+
+    pub fn reset_all(&mut self) {
+        self.overrides.clear();
+    }
+
+    pub fn pressed(&self, i: &egui::InputState, action: Action) -> bool {
+        self.chord(action).pressed(i)
+    }
+
+    pub fn consume(&self, ctx: &egui::Context, action: Action) -> bool {
+        let chord = self.chord(action);
+        ctx.input_mut(|i| chord.consume(i))
+    }
+
+    /// Shortcut hint for menus and the command palette.
+    pub fn hint(&self, action: Action) -> String {
+        self.chord(action).label()
+    }
+
+    /// Other actions bound to the same chord as `action`.
+    pub fn conflicts(&self, action: Action) -> Vec<Action> {
+        let chord = self.chord(action);
+        Action::ALL
+            .into_iter()
+            .filter(|a| *a != action && self.chord(*a) == chord)
+            .collect()
+    }
+
+    /// JSON object of overrides (`{"run_query":"Cmd+R"}`); empty when all defaults.
+    pub fn to_config(&self) -> String {
+        if self.overrides.is_empty() {
+            return String::new();
+        }
+        let map: std::collections::BTreeMap<&str, String> = self
+            .overrides
+            .iter()
+            .map(|(a, c)| (a.id(), c.to_config()))
+            .collect();
+        serde_json::to_string(&map).unwrap_or_default()
+    }
+
+    /// Parse the stored overrides, ignoring unknown actions or keys.
+    pub fn from_config(raw: &str) -> Self {
+        let mut bindings = Self::default();
+        if let Ok(map) = serde_json::from_str::<HashMap<String, String>>(raw) {
+            for (id, chord) in map {
+                if let (Some(action), Some(chord)) = (Action::from_id(&id), KeyChord::parse(&chord)) {
+                    bindings.set(action, chord);
+                }
+            }
+        }
+        bindings
+    }
+}
+
+impl super::Tabular {
+    /// While a binding is being recorded in Preferences, take the first key
+    /// press of the frame before any widget sees it. Escape cancels.
+    pub(crate) fn capture_keybinding(&mut self, ctx: &egui::Context) {
+        let Some(action) = self.keybinding_capture else {
+            return;
+        };
+        let captured = ctx.input_mut(|i| {
+            let chord = i.events.iter().find_map(KeyChord::from_event);
+            if chord.is_some() {
+                i.events.retain(|e| !matches!(e, egui::Event::Key { .. } | egui::Event::Text(_)));
+            }
+            chord
+        });
+        let Some(chord) = captured else { return };
+        self.keybinding_capture = None;
+        if chord == KeyChord::plain(egui::Key::Escape) {
+            return;
+        }
+        self.keybindings.set(action, chord);
+        self.prefs_dirty = true;
+        self.try_save_prefs();
+    }
+
+    /// Central dispatcher for app-level shortcuts.
+    pub(crate) fn handle_keybindings(&mut self, ctx: &egui::Context) {
+        if self.keybinding_capture.is_some() {
+            return;
+        }
+        // Set again by the editor when it renders with focus this frame
+        let editor_focused = std::mem::take(&mut self.editor_has_focus);
+        for action in Action::GLOBAL {
+            let blocked_by_palette = matches!(
+                action,
+                Action::GoToDefinition | Action::RenameSymbol | Action::CommandPalette
+            ) && self.show_command_palette;
+            // The focused editor jumps to the declaration under the cursor instead
+            let left_to_editor = action == Action::GoToDefinition && editor_focused;
+            if blocked_by_palette || left_to_editor || !self.keybindings.consume(ctx, action) {
+                continue;
+            }
+            debug!("⌨️ Shortcut: {:?}", action);
+            self.dispatch_action(ctx, action);
+        }
+        if cfg!(target_os = "macos")
+            && !self.show_command_palette
+            && Action::ALL
+                .into_iter()
+                .all(|a| self.keybindings.chord(a) != MAC_COMMAND_PALETTE)
+            && ctx.input_mut(|i| MAC_COMMAND_PALETTE.consume(i))
+        {
+            editor::open_command_palette(self);
+        }
+        if self.keybindings.consume(ctx, Action::Quit) {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+    }
+
+    fn dispatch_action(&mut self, _ctx: &egui::Context, action: Action) {
+        match action {
+            Action::ExplainQuery => {
+                let text = if self.selected_text.trim().is_empty() {
+                    self.editor.text.clone()
+                } else {
+                    self.selected_text.clone()
+                };
+                editor::explain_current_query(self, text);
+            }
+            Action::NewTab => {
+                editor::create_new_tab(self, "Untitled Query".to_string(), String::new());
+            }
+            Action::CloseTab if !self.query_tabs.is_empty() => {
+                crate::unsaved_changes::request_close_tab(self, self.active_tab_index);
+            }
+            Action::CommandPalette => editor::open_command_palette(self),
+            Action::GoToTable => super::table_palette::open_table_palette(self),
+            Action::ToggleSidebar => self.toggle_sidebar(),
+            Action::Refresh => match self.table_bottom_view {
+                crate::models::structs::TableBottomView::Structure => {
+                    self.request_structure_refresh = true;
+                    crate::data_table::load_structure_info_for_current_table(self);
+                }
+                _ => crate::data_table::refresh_current_table_data(self),
+            },
+            Action::OpenSettings => self.show_settings_window = true,
+            Action::GoToDefinition => editor::go_to_definition(self),
+            Action::RenameSymbol => editor::begin_rename_symbol(self),
+            Action::ToggleTransaction => {
+                if let Some(tab) = self.query_tabs.get_mut(self.active_tab_index) {
+                    tab.tx_mode = !tab.tx_mode;
+                    if !tab.tx_mode {
+                        if let Some(s) = tab.session.take() {
+                            s.close();
+                        }
+                        tab.tx_active = false;
+                    }
+                }
+            }
+            // Handled by the owning widget
+            _ => {}
+        }
+    }
+
+    /// Preferences → Shortcuts tab.
+    pub(crate) fn render_keybindings_settings(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Keyboard Shortcuts");
+        ui.label(
+            egui::RichText::new(if cfg!(target_os = "macos") {
+                "Click a shortcut and press the new key combination (Esc cancels). ⌘ also accepts Ctrl."
+            } else {
+                "Click a shortcut and press the new key combination (Esc cancels)."
+            })
+            .size(11.0)
+            .color(egui::Color32::from_gray(120)),
+        );
+        ui.add_space(6.0);
+
+        let mut changed = false;
+        egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+            egui::Grid::new("keybindings_grid")
+                .num_columns(3)
+                .striped(true)
+                .spacing([12.0, 4.0])
+                .show(ui, |ui| {
+                    for action in Action::ALL {
+                        ui.label(action.label());
+                        let capturing = self.keybinding_capture == Some(action);
+                        let text = if capturing {
+                            "Press keys…".to_string()
+                        } else {
+                            self.keybindings.hint(action)
+                        };
+                        if ui
+                            .add(egui::Button::new(egui::RichText::new(text).monospace()).selected(capturing))
+                            .clicked()
+                        {
+                            self.keybinding_capture = if capturing { None } else { Some(action) };
+                        }
+                        ui.horizontal(|ui| {
+                            if !self.keybindings.is_default(action)
+                                && ui.small_button("↺").on_hover_text("Reset to default").clicked()
+                            {
+                                self.keybindings.reset(action);
+                                changed = true;
+                            }
+                            let conflicts = self.keybindings.conflicts(action);
+                            if !conflicts.is_empty() {
+                                let names: Vec<&str> = conflicts.iter().map(|a| a.label()).collect();
+                                ui.colored_label(
+                                    super::style::theme_danger(ui.ctx()),
+                                    format!("⚠ Conflicts with {}", names.join(", ")),
+                                );
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+        ui.add_space(8.0);
+        if ui.button("Reset to Defaults").clicked() {
+            self.keybindings.reset_all();
+            self.keybinding_capture = None;
+            changed = true;
+        }
+        if changed {
+            self.prefs_dirty = true;
+            self.try_save_prefs();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_round_trip_through_config() {
+        let mut bindings = Keybindings::default();
+        assert_eq!(bindings.to_config(), "");
+        bindings.set(Action::RunQuery, KeyChord::plain(egui::Key::F5));
+        bindings.set(Action::FormatSql, KeyChord::command_shift(egui::Key::F));
+        let raw = bindings.to_config();
+        assert_eq!(raw, r#"{"run_query":"F5"}"#);
+        let loaded = Keybindings::from_config(&raw);
+        assert_eq!(loaded, bindings);
+        assert_eq!(loaded.chord(Action::RunQuery).key, egui::Key::F5);
+        // Unknown actions and malformed chords are ignored
+        let loaded = Keybindings::from_config(r#"{"nope":"Cmd+K","new_tab":"Cmd+Bogus"}"#);
+        assert_eq!(loaded, Keybindings::default());
+    }
+
+    #[test]
+    fn defaults_are_conflict_free_and_conflicts_are_reported() {
+        let mut bindings = Keybindings::default();
+        for action in Action::ALL {
+            assert!(bindings.conflicts(action).is_empty(), "{:?}", action);
+        }
+        bindings.set(Action::NewTab, KeyChord::command(egui::Key::R));
+        assert_eq!(bindings.conflicts(Action::Refresh), vec![Action::NewTab]);
+        assert_eq!(
+            KeyChord::parse("Cmd+Shift+Slash"),
+            Some(KeyChord { command: true, alt: false, shift: true, key: egui::Key::Slash })
+        );
+    }
+}
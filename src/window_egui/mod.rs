@@ -15,13 +15,16 @@ pub mod app_impl;
 pub mod connection_mgr;
 pub mod diagram;
 pub mod init;
+pub mod keybindings;
 pub mod notifications;
 pub mod pagination;
 pub mod query_jobs;
+pub mod query_plan;
 pub mod render_dialogs;
 pub mod search;
 pub mod settings;
 pub mod sidebar_tree;
+pub mod table_palette;
 pub mod table_wizard;
 pub mod tree_loader;
 pub mod update;
@@ -34,6 +37,9 @@ pub struct Tabular {
     pub selected_menu: String,
     pub items_tree: Vec<models::structs::TreeNode>,
     pub queries_tree: Vec<models::structs::TreeNode>,
+    // Saved query file path -> metadata from its leading comment block
+    pub saved_query_meta: HashMap<String, models::structs::SavedQueryMeta>,
+    pub query_tag_filter: String,
     pub history_tree: Vec<models::structs::TreeNode>,
     pub history_items: Vec<models::structs::HistoryItem>, // Actual history data
     pub connections: Vec<models::structs::ConnectionConfig>,
@@ -48,10 +54,13 @@ pub struct Tabular {
     pub pending_connection_pools: std::collections::HashSet<i64>,
     // Shared connection pools for background tasks
     pub shared_connection_pools: Arc<std::sync::Mutex<HashMap<i64, models::enums::DatabasePool>>>,
+    // Row-count / size estimates behind the table tooltips in the tree
+    pub table_stats: models::structs::TableStatsCache,
     // Rate-limit log spam for pending pool creation messages
     pub pending_pool_log_last: HashMap<i64, std::time::Instant>,
     // Prefetch progress tracking
     pub prefetch_progress: HashMap<i64, (usize, usize)>, // connection_id -> (completed, total)
+    pub refresh_progress: HashMap<i64, models::structs::SchemaRefreshProgress>,
     pub prefetch_in_progress: std::collections::HashSet<i64>, // connections currently prefetching
     // Context menu and edit connection fields
     pub show_edit_connection: bool,
@@ -68,25 +77,43 @@ pub struct Tabular {
     // Pagination
     pub current_page: usize,
     pub page_size: usize,
+    // Footer "Go to page" field while the user is typing a page number
+    pub page_jump_input: String,
     pub total_rows: usize,
     pub all_table_data: Vec<Vec<String>>, // Store all data for pagination
     // Server-side pagination
     pub use_server_pagination: bool,
     pub actual_total_rows: Option<usize>, // Real total from COUNT query
     pub current_base_query: String,       // Original query without LIMIT/OFFSET
-    // Splitter position for resizable table view (0.0 to 1.0)
+    // Default editor share of the editor/result split (0.0 to 1.0); tabs may override
     pub table_split_ratio: f32,
+    // Row shown by the record view (index into the current page)
+    pub record_view_index: usize,
+    // Opt-in query result cache (Preferences → Performance)
+    pub result_cache_enabled: bool,
+    pub result_cache_ttl_secs: u64,
+    pub result_cache_max_mb: u64,
+    pub batch_results_in_tabs: bool,
+    // Tab each running query job reports to (see editor::ResultTarget)
+    pub result_targets: HashMap<u64, crate::editor::ResultTarget>,
+    // job_id -> (connection_id, database, sql) for results to store once they arrive
+    pub result_cache_jobs: HashMap<u64, (i64, String, String)>,
     // Table sorting state
     pub sort_column: Option<usize>,
     pub sort_ascending: bool,
     // Test connection status
     pub test_connection_status: Option<(bool, String)>, // (success, message)
     pub test_connection_in_progress: bool,
+    pub test_connection_receiver: Option<std::sync::mpsc::Receiver<(bool, String)>>,
     // Background processing channels
     pub background_sender: Option<Sender<models::enums::BackgroundTask>>,
     pub background_receiver: Option<Receiver<models::enums::BackgroundResult>>,
     pub query_result_sender: Sender<connection::QueryResultMessage>,
     pub query_result_receiver: Receiver<connection::QueryResultMessage>,
+    /// Row batches of streamed SELECTs, and their progress by job id.
+    pub query_stream_sender: Sender<connection::StreamEvent>,
+    pub query_stream_receiver: Receiver<connection::StreamEvent>,
+    pub query_streams: std::collections::HashMap<u64, connection::StreamProgress>,
     pub active_query_jobs: std::collections::HashMap<u64, connection::QueryJobStatus>,
     pub active_query_handles: std::collections::HashMap<u64, tokio::task::JoinHandle<()>>,
     pub cancelled_query_jobs: std::collections::HashMap<u64, std::time::Instant>,
@@ -106,18 +133,23 @@ pub struct Tabular {
         std::collections::HashMap<i64, std::collections::HashMap<String, bool>>,
     // Connections that need their expanded nodes loaded after state restore
     pub pending_auto_load: std::collections::HashSet<i64>,
+    // Tree node to scroll to once a reveal has expanded the way to it
+    pub pending_tree_reveal: Option<models::structs::DefinitionTarget>,
     // Connections already auto-synced (schema cache) this session, so we don't re-sync on every open
     pub auto_synced_connections: std::collections::HashSet<i64>,
     // Query tab system
     pub query_tabs: Vec<models::structs::QueryTab>,
     pub active_tab_index: usize,
-    pub next_tab_id: usize,
+    pub next_tab_id: u64,
     pub scroll_to_active_tab: bool,
     pub last_active_tab_index: Option<usize>,
     // Save dialog
     pub show_save_dialog: bool,
     pub save_filename: String,
     pub save_directory: String,
+    // Metadata fields of the save dialog; tags as typed, comma separated
+    pub save_query_meta: models::structs::SavedQueryMeta,
+    pub save_query_tags: String,
     pub save_directory_picker_result: Option<std::sync::mpsc::Receiver<String>>,
     // Connection selection dialog
     pub show_connection_selector: bool,
@@ -129,10 +161,56 @@ pub struct Tabular {
     pub show_parameter_dialog: bool,
     pub parameter_dialog_query: String,
     pub parameter_inputs: Vec<(String, String)>,
+    // Saved query the dialog's query came from, for its parameter presets;
+    // presets per query file, loaded on first use (see query_presets.rs)
+    pub parameter_dialog_file: Option<String>,
+    pub parameter_preset_name: String,
+    pub parameter_presets: HashMap<String, Vec<models::structs::ParameterPreset>>,
+    // Preset picked in the top bar for the next run
+    pub pending_parameter_preset: Option<models::structs::ParameterPreset>,
+    // Non-modal panel listing the placeholders of the editor text
+    pub show_parameters_panel: bool,
     // Unsafe DML Warning Dialog state
     pub show_unsafe_dml_dialog: bool,
     pub unsafe_dml_query: String,
     pub unsafe_dml_type: String,
+    // Statement confirmed without a WHERE clause vs. a filtered DELETE/UPDATE
+    // only stopped because its dry-run count is over the threshold
+    pub unsafe_dml_filtered: bool,
+    pub unsafe_dml_count_sql: Option<String>,
+    pub unsafe_dml_count: Option<crate::editor::DryRunCount>,
+    // "Run on multiple connections" picker and its in-flight run
+    pub fleet_run: Option<crate::connection::fleet::FleetRunState>,
+    pub destructive_confirm_over_rows: u64,
+    // Safe mode: data-modifying statements wait for "Keep changes" before
+    // they commit; `safe_change` is the change currently waiting
+    pub safe_mode: bool,
+    pub safe_mode_window_secs: u64,
+    pub safe_change: Option<crate::connection::safe_mode::SafeChange>,
+    // Multi-statement scripts run in one transaction on SQL engines
+    pub run_in_transaction: bool,
+    // Index hints after slow queries; dismissed (connection, table, column)
+    // hints stay away for the session
+    pub index_hints: bool,
+    pub index_hint_min_ms: u64,
+    pub dismissed_index_hints: std::collections::HashSet<(i64, String, String)>,
+    // Per-column display formatters (Preferences → Data Grid)
+    pub column_format_rules: Vec<crate::data_table::ColumnFormatRule>,
+    pub export_formatted_values: bool,
+    // Thousands separators for numeric columns; `None` decimals keep the
+    // stored precision
+    pub number_format: bool,
+    pub number_style: crate::data_table::NumberStyle,
+    pub number_decimals: Option<u32>,
+    // Longer cells are shown as a preview (see data_table::cell_preview)
+    pub cell_preview_chars: usize,
+    // Streamed SELECTs pause after this many rows until the user fetches more
+    pub stream_row_ceiling: usize,
+    // Rows per page a new tab starts with (Preferences > Performance)
+    pub default_page_size: usize,
+    // Hidden grid columns: leave them out of exports; "Manage Columns" window
+    pub export_skip_hidden_columns: bool,
+    pub show_manage_columns: bool,
     // Error message display
     pub error_message: String,
     pub show_error_message: bool,
@@ -166,6 +244,18 @@ pub struct Tabular {
     pub filtered_items_tree: Vec<models::structs::TreeNode>,
     // Cache miss confirmation state
     pub cache_miss_request: Option<(i64, String, String)>, // (connection_id, database_name, table_name)
+    // Allowed values per (connection_id, database, table, column); None = free text
+    pub column_allowed_values: HashMap<
+        (i64, String, String, String),
+        Option<models::structs::AllowedValues>,
+    >,
+    // Quick filter chips AND-ed with sql_filter_text in table browse mode
+    pub filter_chips: Vec<crate::data_table::FilterChip>,
+    // Capped distinct values per (connection_id, database, table, column)
+    pub column_distinct_values: HashMap<
+        (i64, String, String, String),
+        Option<crate::data_table::DistinctValues>,
+    >,
     pub show_search_results: bool,
     // History search functionality
     pub history_search_text: String,
@@ -201,8 +291,24 @@ pub struct Tabular {
     pub table_dragging: bool,
     // Scroll to selected cell flag
     pub scroll_to_selected_cell: bool,
+    // Rows that fit in the data grid viewport (PageUp/PageDown step)
+    pub data_visible_rows: usize,
+    // Move keyboard focus back to the SQL editor on the next frame
+    pub editor_focus_request: bool,
+    // Scroll the editor to the caret on its next render (bookmark jumps)
+    pub editor_scroll_to_cursor: bool,
+    // Active tab index and the text its bookmarks were last lined up with
+    pub bookmark_snapshot: Option<(usize, String)>,
+    // Zone temporal result columns are displayed in (persisted preference)
+    pub display_timezone: crate::config::DisplayTimezone,
+    // Also apply display_timezone when editing cells and exporting results
+    pub timezone_for_edit_export: bool,
     // Column width management for resizable columns
     pub column_widths: Vec<f32>, // Store individual column widths
+    // Widths the user set per browsed table, keyed "connection/database/table"
+    pub saved_column_layouts: std::collections::HashMap<String, crate::data_table::SavedColumnLayout>,
+    // Proxy for connections that do not set their own
+    pub global_proxy: crate::models::structs::ProxyConfig,
     pub min_column_width: f32,
     // One-frame suppression flag to prevent editor autocomplete reacting to arrow keys consumed by table navigation
     /// One-frame flag set by table arrow navigation to suppress editor autocomplete
@@ -224,6 +330,8 @@ pub struct Tabular {
     pub directory_picker_result: Option<std::sync::mpsc::Receiver<String>>,
     pub sqlite_path_picker_result: Option<std::sync::mpsc::Receiver<String>>,
     pub temp_sqlite_path: Option<String>,
+    // Dropped SQLite files waiting for the save/open-once prompt
+    pub pending_sqlite_drops: Vec<std::path::PathBuf>,
     // Logo texture
     pub logo_texture: Option<egui::TextureHandle>,
     // Pre-loaded PNG icons for each DB type (key = DatabaseType::icon_key())
@@ -243,14 +351,21 @@ pub struct Tabular {
     pub pending_cursor_set: Option<usize>,
     // Keep editor focused for a few frames after actions like autocomplete accept
     pub editor_focus_boost_frames: u8,
+    // Whether the SQL editor had keyboard focus on the last frame
+    pub editor_has_focus: bool,
     // Enforce caret after autocomplete for a few frames
     pub autocomplete_expected_cursor: Option<usize>,
     pub autocomplete_protection_frames: u8,
     // Tracks whether user has navigated autocomplete popup (ArrowUp/Down or similar)
     pub autocomplete_navigated: bool,
-    // Autocomplete throttle
+    // Suggestion under the mouse this frame (previewed in the documentation pane)
+    pub autocomplete_hover_index: Option<usize>,
+    // Keyboard navigation moved the selection; scroll it into view on next render
+    pub autocomplete_scroll_to_selected: bool,
+    // Autocomplete debounce: time of the last edit and whether a rebuild is due
     pub autocomplete_last_update: Option<std::time::Instant>,
     pub autocomplete_debounce_ms: u64,
+    pub autocomplete_pending: bool,
     // Connections whose foreign-key cache has been warmed this session (lazy,
     // one-shot) so SQL-editor JOIN-ON autocomplete works without an open ERD.
     pub fk_cache_warmed: std::collections::HashSet<i64>,
@@ -262,15 +377,19 @@ pub struct Tabular {
     // never "disappear" due to a later SQLite cache-read miss or db-scope
     // mismatch, and we avoid repeated blocking lookups on the UI thread.
     pub autocomplete_cols_mem: std::collections::HashMap<(i64, String), Vec<String>>,
+    // Live column fetches running in the background, so a table is fetched once
+    // even while the user keeps typing its name
+    pub autocomplete_cols_inflight: std::collections::HashSet<(i64, String)>,
+    pub autocomplete_cols_sender: Sender<crate::editor_autocomplete::FetchedColumns>,
+    pub autocomplete_cols_receiver: Receiver<crate::editor_autocomplete::FetchedColumns>,
     // Ensure selection is cleared on the next frame after a destructive action (e.g., Delete)
     pub selection_force_clear: bool,
     // Multi-cursor support: additional caret positions (primary caret tracked separately)
     pub extra_cursors: Vec<usize>,
     pub last_editor_text: String, // For detecting text changes in multi-cursor mode (deprecated; will derive from editor.text)
-    // Syntax highlighting cache (text_hash -> LayoutJob)
-    pub highlight_cache: std::collections::HashMap<u64, eframe::egui::text::LayoutJob>,
+    // Syntax highlighting cache (per theme/dialect LRU of LayoutJobs)
+    pub highlight_cache: crate::syntax_ts::HighlightCache,
     pub last_highlight_hash: Option<u64>,
-    // New per-line highlight cache was used by the removed custom editor; no longer needed
     // Index dialog
     pub show_index_dialog: bool,
     pub index_dialog: Option<models::structs::IndexDialogState>,
@@ -335,24 +454,39 @@ pub struct Tabular {
     pub last_update_check: Option<std::time::Instant>,
     pub update_download_in_progress: bool,
     pub auto_check_updates: bool,
+    pub update_channel: crate::self_update::UpdateChannel,
+    // Offline mode: no update checks/downloads and no eager background syncs
+    pub offline_mode: bool,
     pub manual_update_check: bool, // Track if update check was manually triggered
     // Lightweight notification (toast) instead of full dialog for auto updates
     pub show_update_notification: bool,
     pub update_download_started: bool,
     pub update_installed: bool,
-    pub update_install_receiver: Option<std::sync::mpsc::Receiver<bool>>, // receive success flag
+    pub update_install_receiver:
+        Option<std::sync::mpsc::Receiver<Result<crate::self_update::UpdateVerification, String>>>, // receive checksum status or failure
+    // Checksum status of the downloaded update, or why the install was aborted
+    pub update_verification: Option<crate::self_update::UpdateVerification>,
+    pub update_install_error: Option<String>,
     pub enable_debug_logging: bool, // New field for debug logging
     // Auto updater instance
     pub auto_updater: Option<crate::auto_updater::AutoUpdater>,
     // Preferences window active tab
     pub settings_active_pref_tab: PrefTab,
+    // Text buffer for the IANA zone field in Preferences → Data Grid
+    pub display_timezone_input: String,
     // Lightweight settings context menu (gear popup)
     pub show_settings_menu: bool,
     // Query execution wait when pool is being created
     pub pool_wait_in_progress: bool,
     pub pool_wait_connection_id: Option<i64>,
     pub pool_wait_query: String,
+    // Parameter values bound to pool_wait_query when it runs
+    pub pool_wait_params: Vec<(String, String)>,
     pub pool_wait_started_at: Option<std::time::Instant>,
+    // Lost-connection recovery: (connection_id, query) offered as "Reconnect and retry"
+    pub reconnect_prompt: Option<(i64, String)>,
+    // True while an automatic reconnect+retry is in flight (retry at most once)
+    pub reconnect_auto_retry_used: bool,
     // Spreadsheet editing state
     pub spreadsheet_state: crate::models::structs::SpreadsheetState,
     // Lapce buffer integration for editor (replaces egui_code_editor)
@@ -375,6 +509,7 @@ pub struct Tabular {
     pub last_plan_hash: Option<u64>,
     pub last_plan_cache_key: Option<String>,
     pub last_ctes: Option<Vec<String>>, // names of remaining CTEs after rewrites
+    pub query_plan_panel: query_plan::QueryPlanPanel,
     pub sql_semantic_snapshot: Option<Arc<crate::syntax_ts::SqlSemanticSnapshot>>,
     pub lint_messages: Vec<query_tools::LintMessage>,
     pub show_lint_panel: bool,
@@ -407,9 +542,13 @@ pub struct Tabular {
     pub edit_view_original_name: Option<String>,
     // Result of the background custom-view save (Ok = persisted, Err = message)
     pub custom_view_save_receiver: Option<std::sync::mpsc::Receiver<Result<(), String>>>,
+    // Grid save running in a transaction (polled in app_impl)
+    pub spreadsheet_save_receiver: Option<crate::models::structs::SpreadsheetSave>,
     
     pub global_backspace_pressed: bool,
-    pub sidebar_visible: bool,
+    // Left sidebar hidden (thin strip shown instead) and its remembered width
+    pub sidebar_collapsed: bool,
+    pub sidebar_width: f32,
     
     // Replication dialog state
     pub show_add_replication_dialog: bool,
@@ -452,21 +591,56 @@ pub struct Tabular {
     // CSV Import wizard
     pub show_csv_import_dialog: bool,
     pub csv_import_state: Option<models::structs::CsvImportState>,
+    // Call dialog for a stored procedure / function (open while Some)
+    pub routine_call_state: Option<models::structs::RoutineCallState>,
+    pub sqlite_export_state: Option<crate::data_table::SqliteExportState>,
+    pub cell_detail: Option<crate::data_table::CellDetailState>,
+    // Element editor for a PostgreSQL array / composite cell
+    pub structured_edit: Option<crate::data_table::StructuredEditState>,
+    // Column statistics window (open while Some)
+    pub column_stats: Option<crate::data_table::ColumnStatsState>,
+    // Find in all results window (open while Some)
+    pub result_search: Option<crate::data_table::ResultSearchState>,
+    // Block pasted into the grid, waiting for confirmation (dialog open while Some)
+    pub bulk_paste: Option<crate::data_table::BulkPaste>,
+    // Cells of the last grid save, flashing green/red
+    pub edit_flashes: Vec<crate::data_table::EditFlash>,
     // Rename symbol dialog (F2 in editor)
     pub rename_symbol_active: bool,
     pub rename_symbol_old: String,
     pub rename_symbol_new: String,
+    // "Paste as IN List" dialog
+    pub in_list_dialog: crate::editor_in_list::InListDialog,
+    // "Go to table" palette
+    pub table_palette: table_palette::TablePalette,
+    // Go-to-definition picker for names found in several databases (open while non-empty)
+    pub definition_picker: Vec<models::structs::DefinitionTarget>,
     // Scroll offsets synced between sticky header and data grid
     pub data_scroll_x: f32,
-    pub data_scroll_y: f32,
     // Cached connection-type lookup rebuilt only when connections list changes
     pub cached_connection_types: std::collections::HashMap<i64, models::enums::DatabaseType>,
     // Pending clipboard text (written to egui clipboard on next frame)
     pub pending_clipboard_text: Option<String>,
+    // PostgreSQL LISTEN/NOTIFY panel
+    pub show_pg_listen_panel: bool,
+    pub pg_listen: models::structs::PgListenState,
+    pub pending_session_kill: Option<models::structs::SessionKillRequest>,
+    // Crash recovery of unsaved tab buffers (see recovery.rs)
+    pub recovery_writer: crate::recovery::RecoveryWriter,
+    pub workspace_profiles: crate::workspace_profile::ProfilesState,
+    pub unsaved_changes: crate::unsaved_changes::UnsavedChangesState,
+    pub recovery_files: Vec<crate::recovery::RecoveryFile>,
+    pub show_recovery_dialog: bool,
+    // Configurable shortcuts; `keybinding_capture` is the action being rebound
+    pub keybindings: keybindings::Keybindings,
+    pub keybinding_capture: Option<keybindings::Action>,
+    pub vim: crate::editor_vim::VimState,
     // Schema Diff dialog
     pub show_schema_diff_dialog: bool,
     pub schema_diff_state: Option<models::structs::SchemaDiffState>,
     pub schema_diff_receiver: Option<std::sync::mpsc::Receiver<models::structs::SchemaDiffResult>>,
+    // Compare-tabs diff view (Some while open)
+    pub tab_compare_state: Option<models::structs::TabCompareState>,
 }
 
 // Preference tabs enumeration
@@ -475,8 +649,11 @@ pub enum PrefTab {
     ApplicationTheme,
     EditorTheme,
     Performance,
+    DataGrid,
     DataDirectory,
     Update,
+    Network,
+    Shortcuts,
     AiAssistant,
 }
 
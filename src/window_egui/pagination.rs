@@ -61,6 +61,7 @@ impl super::Tabular {
                     };
                     self.active_query_jobs.insert(job_id, status);
                     self.pending_paginated_jobs.insert(job_id);
+                    crate::editor::remember_result_target(self, job_id);
 
                     match connection::spawn_query_job(self, job, self.query_result_sender.clone()) {
                         Ok(handle) => {
@@ -265,41 +266,8 @@ impl super::Tabular {
                 format!("{} LIMIT {} OFFSET {}", base_query, limit, offset)
             }
             models::enums::DatabaseType::MsSQL => {
-                // MsSQL requires ORDER BY for OFFSET/FETCH. Inject ORDER BY 1 if missing.
-                // Handle optional leading USE statement separated by semicolon.
-                let mut base = base_query.clone();
-                debug!("🔍 MsSQL base query before processing: {}", base);
-
-                let mut prefix = String::new();
-                // Separate USE ...; prefix if present so pagination applies only to SELECT part
-                if let Some(use_end) = base.find(";\nSELECT") {
-                    // include the semicolon in prefix
-                    prefix = base[..=use_end].to_string();
-                    base = base[use_end + 2..].to_string(); // skip "\n" keeping SELECT...
-                }
-
-                // Trim and remove trailing semicolons/spaces
-                let mut select_part = base.trim().trim_end_matches(';').to_string();
-                debug!("🔍 MsSQL select part before TOP removal: {}", select_part);
-
-                // Enhanced TOP removal using case-insensitive regex-like approach
-                select_part = driver_mssql::sanitize_mssql_select_for_pagination(&select_part);
-                debug!("🔍 MsSQL select part after TOP removal: {}", select_part);
-
-                // Detect ORDER BY (case-insensitive)
-                let has_order = select_part.to_lowercase().contains("order by");
-                if !has_order {
-                    select_part.push_str(" ORDER BY 1");
-                }
-                let effective_limit = if limit == 0 { 100 } else { limit }; // safety
-                let mut final_query = format!(
-                    "{}{} OFFSET {} ROWS FETCH NEXT {} ROWS ONLY",
-                    prefix, select_part, offset, effective_limit
-                );
-                // check if contain TOP 1000 than replace it
-                final_query = final_query.replace("TOP 10000", "");
-                debug!(" *** final_query *** : {}", final_query);
-
+                let final_query =
+                    driver_mssql::build_mssql_paginated_query(base_query, offset, limit);
                 debug!("🧪 MsSQL final paginated query: {}", final_query);
                 final_query
             }
@@ -329,6 +297,7 @@ impl super::Tabular {
                 self.update_current_page_data();
             }
             data_table::clear_table_selection(self);
+            data_table::store_tab_pagination(self);
         }
     }
     pub fn execute_count_query(&mut self) -> Option<usize> {
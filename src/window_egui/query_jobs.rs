@@ -1,9 +1,40 @@
-use crate::{connection, editor, models, sidebar_history};
+use crate::{connection, data_table, editor, models, sidebar_history};
+use log::debug;
+
+/// Message panel text for a finished statement.
+fn status_message(message: &connection::QueryResultMessage) -> String {
+    if !message.success {
+        return format!(
+            "Error: {}",
+            message.error.as_deref().unwrap_or("Unknown error")
+        );
+    }
+    let duration_ms = message.duration.as_millis();
+    let row_count = message.affected_rows.unwrap_or(message.rows.len());
+    if message.produced_result_set {
+        format!(
+            "Query executed successfully in {}.{:03}s • {} row(s)",
+            duration_ms / 1000,
+            duration_ms % 1000,
+            row_count
+        )
+    } else {
+        format!(
+            "Statement executed successfully in {}.{:03}s • {} row(s) affected",
+            duration_ms / 1000,
+            duration_ms % 1000,
+            row_count
+        )
+    }
+}
 
 impl super::Tabular {
-    pub fn handle_query_result_message(&mut self, message: connection::QueryResultMessage) {
+    pub fn handle_query_result_message(&mut self, mut message: connection::QueryResultMessage) {
         self.prune_cancelled_jobs();
         self.active_query_handles.remove(&message.job_id);
+        let stream = self.query_streams.remove(&message.job_id);
+        let cache_target = self.result_cache_jobs.remove(&message.job_id);
+        let result_target = self.result_targets.remove(&message.job_id);
 
         // Drop this job from its sequential-batch group (if any); the group
         // entry disappears once every member has reported a result.
@@ -42,23 +73,61 @@ impl super::Tabular {
             self.last_compiled_headers = ast_headers;
         }
 
+        // Result for a tab that is no longer (or never was) in front: store it there
+        if let Some(target) = result_target
+            && let Some(tab_index) = editor::result_target_index(self, &target)
+            && tab_index != self.active_tab_index
+        {
+            if was_paginated {
+                if message.success {
+                    editor::store_page_in_tab(self, tab_index, &message);
+                }
+            } else {
+                editor::store_result_in_tab(self, tab_index, &message, status_message(&message));
+                if message.success {
+                    let database_name =
+                        self.query_tabs.get(tab_index).and_then(|t| t.database_name.clone());
+                    sidebar_history::save_query_to_history(
+                        self,
+                        &message.query,
+                        message.connection_id,
+                        database_name,
+                    );
+                }
+            }
+            self.query_execution_in_progress = !self.active_query_jobs.is_empty();
+            self.extend_query_icon_hold();
+            return;
+        }
+
         // Update query message panel
+        self.query_message = status_message(&message);
+        if message.success
+            && let Some(stream) = &stream
+            && stream.control.stopped()
+        {
+            self.query_message
+                .push_str(" • stopped early, the remaining rows were not fetched");
+        }
+        // A stopped stream holds only part of the result: never cache it
+        let stopped_early = stream.as_ref().is_some_and(|s| s.control.stopped());
+        // A streamed result keeps the page the user scrolled to meanwhile
+        let streamed_page = stream.filter(|s| s.received > 0).map(|_| self.current_page);
         if message.success {
-            let duration_ms = message.duration.as_millis();
-            let row_count = message.affected_rows.unwrap_or(message.rows.len());
-            self.query_message = format!(
-                "Query executed successfully in {}.{:03}s • {} row(s) affected",
-                duration_ms / 1000,
-                duration_ms % 1000,
-                row_count
-            );
             self.query_message_is_error = false;
+            self.reconnect_auto_retry_used = false;
+            self.reconnect_prompt = None;
             // Auto-switch to Data tab to show results
             self.table_bottom_view = models::structs::TableBottomView::Data;
         } else {
             let error_msg = message.error.clone().unwrap_or_else(|| "Unknown error".to_string());
-            self.query_message = format!("Error: {}", error_msg);
             self.query_message_is_error = true;
+            if connection::classify_query_error(&error_msg)
+                == connection::QueryErrorCategory::ConnectionLost
+                && self.handle_connection_lost(message.connection_id, &message.query)
+            {
+                return;
+            }
             // Keep Data view active in bottom panel
             self.table_bottom_view = models::structs::TableBottomView::Data;
         }
@@ -71,15 +140,37 @@ impl super::Tabular {
             active_tab.query_message_is_error = self.query_message_is_error;
         }
 
+        if let Some((connection_id, database_name, sql)) = cache_target
+            && message.success
+            && !was_paginated
+            && !stopped_early
+        {
+            crate::cache_data::save_query_result_to_cache(
+                self,
+                connection_id,
+                &database_name,
+                &sql,
+                &message.headers,
+                &message.rows,
+            );
+        }
+
         if was_paginated && message.success {
             self.apply_paginated_query_result(&message);
             return;
         }
 
+        // The rows streamed into the grid are superseded by the message's own;
+        // free them before the result is copied into place
+        if streamed_page.is_some() {
+            self.all_table_data = Vec::new();
+            self.current_table_data = Vec::new();
+        }
+
         // Store result in multi-tab result list
         let mut result_obj = models::structs::QueryResult {
             headers: message.headers.clone(),
-            rows: message.rows.clone(),
+            rows: message.rows.iter().take(500).cloned().collect(),
             all_rows: message.rows.clone(),
             table_name: if message.success {
                 format!("Result {}", self.next_query_job_id) // Placeholder, updated below
@@ -94,8 +185,10 @@ impl super::Tabular {
             execution_time_ms: message.duration.as_millis(),
             column_metadata: message.column_metadata.clone(),
             explain_plan_json: None,
+            cached: None,
         };
 
+        let mut shown_in_grid = false;
         if let Some(active_tab) = self.query_tabs.get_mut(self.active_tab_index) {
             // Determine index
             let new_index = active_tab.results.len();
@@ -103,7 +196,7 @@ impl super::Tabular {
             
             // If it's an error and we have results, maybe keep the error in a separate Result tab?
             // For now, simple append.
-            active_tab.results.push(result_obj.clone());
+            active_tab.results.push(result_obj);
 
             // Logic to auto-switch logic:
             // If this is the FIRST result, or if we are actively viewing the "latest" result (potentially),
@@ -112,16 +205,41 @@ impl super::Tabular {
             // Or if the user hasn't manually switched to another result yet.
             if new_index == 0 {
                 active_tab.active_result_index = 0;
-                editor::process_query_result(self, &message.query, message.connection_id, Some((message.headers.clone(), message.rows.clone())), message.column_metadata.clone());
+                editor::process_query_result(self, &message.query, message.connection_id, Some((message.headers.clone(), std::mem::take(&mut message.rows))), message.column_metadata.clone());
+                shown_in_grid = true;
             } else {
                 // Save query to history for multi-statement execution results (new_index > 0)
                 if message.success {
-                    sidebar_history::save_query_to_history(self, &message.query, message.connection_id);
+                    let database_name = self
+                        .query_tabs
+                        .get(self.active_tab_index)
+                        .and_then(|t| t.database_name.clone());
+                    sidebar_history::save_query_to_history(
+                        self,
+                        &message.query,
+                        message.connection_id,
+                        database_name,
+                    );
                 }
             }
         } else {
              // Fallback for no active tab? Should not happen.
-             editor::process_query_result(self, &message.query, message.connection_id, Some((message.headers.clone(), message.rows.clone())), message.column_metadata.clone());
+             editor::process_query_result(self, &message.query, message.connection_id, Some((message.headers.clone(), std::mem::take(&mut message.rows))), message.column_metadata.clone());
+             shown_in_grid = true;
+        }
+
+        // A statement without a result set gets an explicit outcome instead of an empty grid
+        if shown_in_grid && message.success && !message.produced_result_set {
+            self.current_table_name = data_table::statement_outcome_caption(
+                message.affected_rows.unwrap_or(0),
+            );
+        }
+        if shown_in_grid {
+            data_table::suggest_indexes(self, &message);
+        }
+        if shown_in_grid && let Some(page) = streamed_page {
+            self.current_page = page.min(data_table::get_total_pages(self).saturating_sub(1));
+            data_table::update_current_page_data(self);
         }
 
         if let Some(active_tab) = self.query_tabs.get_mut(self.active_tab_index) {
@@ -136,17 +254,113 @@ impl super::Tabular {
             active_tab.result_table_name = self.current_table_name.clone();
         }
 
-        self.query_execution_in_progress = false;
+        // Other tabs may still have queries in flight
+        self.query_execution_in_progress = !self.active_query_jobs.is_empty();
         self.extend_query_icon_hold();
     }
+    /// React to a query that failed because the connection dropped: the stale
+    /// pool is discarded and a read-only statement is retried once
+    /// automatically on a fresh pool. Writes (which may already have been
+    /// applied) and a second failure leave a "Reconnect and retry" prompt in
+    /// the message panel instead. Returns true when a retry was queued.
+    fn handle_connection_lost(&mut self, connection_id: i64, query: &str) -> bool {
+        debug!("🔌 Connection {} lost while running query", connection_id);
+        connection::cleanup_connection_pool(self, connection_id);
+
+        if !self.reconnect_auto_retry_used && crate::cache_data::is_result_cacheable(query) {
+            self.reconnect_auto_retry_used = true;
+            self.reconnect_and_retry(connection_id, query.to_string());
+            return true;
+        }
+
+        self.reconnect_auto_retry_used = false;
+        self.reconnect_prompt = Some((connection_id, query.to_string()));
+        self.query_message.push_str("\n\nThe connection to the server was lost.");
+        false
+    }
+
+    /// Rebuild the pool for `connection_id` (re-opening its SSH tunnel when
+    /// configured) and re-run `query` once the pool is ready.
+    pub fn reconnect_and_retry(&mut self, connection_id: i64, query: String) {
+        self.reconnect_prompt = None;
+        connection::cleanup_connection_pool(self, connection_id);
+        connection::ensure_background_pool_creation(self, connection_id);
+
+        self.query_execution_in_progress = true;
+        self.pool_wait_in_progress = true;
+        self.pool_wait_connection_id = Some(connection_id);
+        self.pool_wait_query = query;
+        self.pool_wait_params.clear();
+        self.pool_wait_started_at = Some(std::time::Instant::now());
+        self.current_table_name = "Reconnecting… retrying query".to_string();
+        self.toasts
+            .info("Connection lost — reconnecting and retrying the query");
+    }
+
+    /// Rebind the active tab to another connection from the toolbar switcher.
+    /// The editor text stays; results, messages and plan of the previous
+    /// connection are cleared, and the new connection's pool is opened.
+    pub fn switch_active_tab_connection(&mut self, connection_id: i64) {
+        let Some(tab) = self.query_tabs.get_mut(self.active_tab_index) else {
+            return;
+        };
+        if tab.connection_id == Some(connection_id) {
+            return;
+        }
+        // Database and schema start over from the new connection's defaults
+        tab.schema_name = None;
+        tab.result_headers.clear();
+        tab.result_rows.clear();
+        tab.result_all_rows.clear();
+        tab.result_table_name.clear();
+        tab.result_column_metadata = None;
+        tab.results.clear();
+        tab.active_result_index = 0;
+        tab.base_query.clear();
+        tab.total_rows = 0;
+        tab.current_page = 0;
+        tab.explain_plan_json = None;
+        tab.query_message.clear();
+        tab.query_message_is_error = false;
+        tab.index_suggestions.clear();
+        self.set_active_tab_connection_with_database(Some(connection_id), None);
+
+        self.current_table_headers.clear();
+        self.current_table_data.clear();
+        self.all_table_data.clear();
+        self.current_table_name.clear();
+        self.current_column_metadata = None;
+        self.total_rows = 0;
+        self.current_page = 0;
+        self.selected_cell = None;
+        self.selected_rows.clear();
+        self.selected_columns.clear();
+        self.query_message.clear();
+        self.query_message_is_error = false;
+    }
+
     pub fn set_active_tab_connection_with_database(
         &mut self,
         connection_id: Option<i64>,
         database_name: Option<String>,
     ) {
+        let connection = connection_id
+            .and_then(|cid| self.connections.iter().find(|c| c.id == Some(cid)));
+        let schema_name = connection.and_then(connection::default_tab_schema);
+        let database_name = match (database_name, connection_id) {
+            (Some(db), Some(cid)) => {
+                connection::remember_last_used_database(self, cid, Some(&db));
+                Some(db)
+            }
+            (None, _) => connection.and_then(connection::default_tab_database),
+            (db, None) => db,
+        };
         if let Some(tab) = self.query_tabs.get_mut(self.active_tab_index) {
             tab.connection_id = connection_id;
             tab.database_name = database_name;
+            if tab.schema_name.is_none() {
+                tab.schema_name = schema_name;
+            }
         }
 
         // Eagerly open the connection pool when a connection is assigned to the active tab.
@@ -160,13 +374,9 @@ impl super::Tabular {
             let already_has_pool = self.connection_pools.contains_key(&cid);
             let already_pending = self.pending_connection_pools.contains(&cid);
             if !already_has_pool && !already_pending {
-                // Use (or create) the shared runtime to synchronously kick off pool creation.
-                // We block only for the quick-attempt path inside get_or_create_connection_pool;
-                // if it becomes a background creation it will return fast.
-                let rt = self.get_runtime();
-                rt.block_on(async {
-                    let _ = crate::connection::get_or_create_connection_pool(self, cid).await;
-                });
+                // Create the pool on the runtime; queries issued before it is
+                // ready wait for it via the pool-wait poller instead of blocking here.
+                connection::ensure_background_pool_creation(self, cid);
             }
         }
     }
@@ -231,7 +441,9 @@ impl super::Tabular {
         }
 
         let had_status = self.active_query_jobs.remove(&job_id).is_some();
+        self.query_streams.remove(&job_id);
         let was_paginated = self.pending_paginated_jobs.remove(&job_id);
+        self.result_targets.remove(&job_id);
 
         if had_status || was_paginated || cancelled {
             self.cancelled_query_jobs
@@ -269,11 +481,120 @@ impl super::Tabular {
         }
         self.active_query_jobs.clear();
         self.active_query_handles.clear();
+        self.query_streams.clear();
         self.query_job_batches.clear();
+        self.result_targets.clear();
         self.query_execution_in_progress = false;
         self.current_table_name = "All queries cancelled".to_string();
         self.extend_query_icon_hold();
     }
+    /// Stream the rows of `job` into the grid as they arrive, for drivers
+    /// that can and statements that return rows.
+    pub(crate) fn attach_query_stream(&mut self, job: &mut connection::types::QueryJob) {
+        let options = &job.options;
+        let streamable = matches!(
+            options.connection.connection_type,
+            models::enums::DatabaseType::PostgreSQL
+                | models::enums::DatabaseType::MySQL
+                | models::enums::DatabaseType::SQLite
+        ) && !options.use_server_pagination
+            && options.dba_special_mode.is_none()
+            && connection::statement_returns_rows(&options.query);
+        if !streamable {
+            return;
+        }
+        let control = std::sync::Arc::new(connection::StreamControl::new(self.stream_row_ceiling));
+        job.options.stream = Some(connection::QueryStream {
+            job_id: job.job_id,
+            sender: self.query_stream_sender.clone(),
+            control: control.clone(),
+        });
+        self.query_streams.insert(
+            job.job_id,
+            connection::StreamProgress {
+                control,
+                received: 0,
+                paused: false,
+            },
+        );
+    }
+
+    /// True when the rows of streamed job `job_id` belong in the grid on screen.
+    fn stream_shows_in_grid(&self, job_id: u64) -> bool {
+        self.result_targets
+            .get(&job_id)
+            .and_then(|target| editor::result_target_index(self, target))
+            == Some(self.active_tab_index)
+    }
+
+    pub fn handle_stream_event(&mut self, event: connection::StreamEvent) {
+        match event {
+            connection::StreamEvent::Rows {
+                job_id,
+                headers,
+                rows,
+            } => {
+                // Cancelled jobs may still have batches in flight
+                let Some(progress) = self.query_streams.get_mut(&job_id) else {
+                    return;
+                };
+                let first_batch = progress.received == 0;
+                progress.received += rows.len();
+                progress.paused = false;
+                let received = progress.received;
+                if !self.stream_shows_in_grid(job_id) {
+                    return;
+                }
+                if first_batch {
+                    if let Some(tab) = self.query_tabs.get_mut(self.active_tab_index) {
+                        tab.active_pinned = None;
+                        tab.live_result = None;
+                    }
+                    data_table::clear_table_selection(self);
+                    self.current_table_headers = headers;
+                    self.current_column_metadata = None;
+                    self.table_bottom_view = models::structs::TableBottomView::Data;
+                    data_table::update_pagination_data(self, rows);
+                } else {
+                    let page_was_full = self.current_table_data.len() >= self.page_size;
+                    self.all_table_data.extend(rows);
+                    self.total_rows = self.all_table_data.len();
+                    if !page_was_full {
+                        data_table::update_current_page_data(self);
+                    }
+                }
+                self.current_table_name = format!("Streaming… {} row(s) received", received);
+            }
+            connection::StreamEvent::Paused { job_id, received } => {
+                if let Some(progress) = self.query_streams.get_mut(&job_id) {
+                    progress.paused = true;
+                    if self.stream_shows_in_grid(job_id) {
+                        self.current_table_name = format!(
+                            "Paused at {} row(s) — fetch more or stop here",
+                            received
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Let paused streamed job `job_id` fetch another ceiling's worth of rows.
+    pub fn fetch_more_stream_rows(&mut self, job_id: u64) {
+        if let Some(progress) = self.query_streams.get_mut(&job_id) {
+            progress.paused = false;
+            progress.control.fetch_more(self.stream_row_ceiling);
+        }
+    }
+
+    /// Finish streamed job `job_id` with the rows received so far.
+    pub fn stop_stream(&mut self, job_id: u64) {
+        if let Some(progress) = self.query_streams.get_mut(&job_id) {
+            progress.paused = false;
+            progress.control.stop();
+        }
+    }
+
     pub fn prune_cancelled_jobs(&mut self) {
         let now = std::time::Instant::now();
         let ttl = std::time::Duration::from_secs(30);
@@ -0,0 +1,117 @@
+use eframe::egui;
+
+use crate::models;
+
+/// State of the "Show query plan" panel in the result area.
+#[derive(Default)]
+pub struct QueryPlanPanel {
+    pub open: bool,
+    /// (statement, database type, auto-limit) the current report was built for.
+    #[cfg(feature = "query_ast")]
+    key: Option<(String, models::enums::DatabaseType, bool)>,
+    #[cfg(feature = "query_ast")]
+    report: Option<Result<crate::query_ast::PlanInspection, String>>,
+}
+
+impl super::Tabular {
+    /// Statement the plan panel describes: the selection when there is one,
+    /// otherwise the statement under the cursor (same priority as execution).
+    #[cfg(feature = "query_ast")]
+    fn query_plan_source(&mut self) -> String {
+        if !self.selected_text.trim().is_empty() {
+            self.selected_text.trim().to_string()
+        } else {
+            crate::editor::extract_query_from_cursor(self).trim().to_string()
+        }
+    }
+
+    /// Render the query plan panel above the result grid when it is toggled on.
+    /// The report is rebuilt only when the statement or connection type changes.
+    #[cfg(feature = "query_ast")]
+    pub(crate) fn render_query_plan_panel(&mut self, ui: &mut egui::Ui) {
+        if !self.query_plan_panel.open {
+            return;
+        }
+        let sql = self.query_plan_source();
+        let db_type = self
+            .query_tabs
+            .get(self.active_tab_index)
+            .and_then(|t| t.connection_id)
+            .and_then(|id| self.connections.iter().find(|c| c.id == Some(id)))
+            .map(|c| c.connection_type.clone());
+        let inject_auto_limit = self.use_server_pagination;
+
+        let panel = &mut self.query_plan_panel;
+        match db_type {
+            Some(db_type) if !sql.is_empty() => {
+                let key = (sql, db_type, inject_auto_limit);
+                if panel.key.as_ref() != Some(&key) {
+                    panel.report = Some(
+                        crate::query_ast::inspect_plan(&key.0, &key.1, inject_auto_limit)
+                            .map_err(|e| e.to_string()),
+                    );
+                    panel.key = Some(key);
+                }
+            }
+            _ => {
+                panel.key = None;
+                panel.report = None;
+            }
+        }
+
+        let mut close = false;
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.set_width(ui.available_width());
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("🧭 Query plan").strong());
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if super::style::render_close_icon_button(ui).clicked() {
+                        close = true;
+                    }
+                });
+            });
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .id_salt("query_plan_panel_scroll")
+                .max_height(240.0)
+                .show(ui, |ui| match &self.query_plan_panel.report {
+                    None => {
+                        ui.label("Place the cursor in a SELECT on a connected tab to see its plan.");
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(
+                            super::style::theme_danger(ui.ctx()),
+                            format!("Cannot plan this statement: {}", e),
+                        );
+                    }
+                    Some(Ok(report)) => {
+                        let (nodes, depth, subs, subs_corr, windows) = report.metrics;
+                        ui.label(format!(
+                            "Nodes: {}   Depth: {}   Subqueries: {} ({} correlated)   Windows: {}",
+                            nodes, depth, subs, subs_corr, windows
+                        ));
+                        ui.label(format!("Structural hash: {:016x}", report.structural_hash));
+                        ui.label(if report.rules.is_empty() {
+                            "Rewrite rules: none".to_string()
+                        } else {
+                            format!("Rewrite rules: {}", report.rules.join(" → "))
+                        });
+                        egui::CollapsingHeader::new("Logical plan")
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                // Skip the "-- debug plan for ..." header line
+                                let body = report.plan.split_once('\n').map_or("", |(_, b)| b);
+                                ui.code(body.trim_end());
+                            });
+                        egui::CollapsingHeader::new("Emitted SQL").show(ui, |ui| {
+                            ui.code(&report.emitted_sql);
+                        });
+                    }
+                });
+        });
+        if close {
+            self.query_plan_panel.open = false;
+        }
+        ui.add_space(4.0);
+    }
+}
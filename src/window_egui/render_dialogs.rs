@@ -1,8 +1,10 @@
 use eframe::egui;
 use log::debug;
-use crate::{models, connection, query_tools, editor, data_table};
-
+use crate::{models, connection, query_tools, editor, editor_bookmarks, data_table};
 
+/// Bounds for the editor share of the editor/result split so neither pane disappears.
+pub(crate) const SPLIT_RATIO_MIN: f32 = 0.15;
+pub(crate) const SPLIT_RATIO_MAX: f32 = 0.85;
 
 impl super::Tabular {
     pub fn render_bottom_right_dock(&mut self, ctx: &egui::Context, rendered_http: bool, rendered_redis_browser: bool) {
@@ -134,6 +136,19 @@ impl super::Tabular {
                                             }
                                         });
                                     });
+
+                                if self.query_message_is_error
+                                    && let Some((conn_id, query)) = self.reconnect_prompt.clone()
+                                {
+                                    ui.add_space(6.0);
+                                    if ui.button("🔄 Reconnect and retry").clicked() {
+                                        // Manual retry: a further disconnect prompts again
+                                        // instead of auto-retrying.
+                                        self.reconnect_auto_retry_used = true;
+                                        self.reconnect_and_retry(conn_id, query);
+                                        close_msg_toast = true;
+                                    }
+                                }
                             });
                         });
                 });
@@ -704,6 +719,9 @@ impl super::Tabular {
         ui: &mut egui::Ui,
         context_id: &str, // "view_query" or "regular_query"
     ) {
+        // Tagged connection: banner on top, its color down the left edge
+        let tag_color = crate::connection::tag::render_connection_banner(self, ui);
+        let edge_top = ui.cursor().top();
         let avail = ui.available_height();
         let executed = self
             .query_tabs
@@ -714,12 +732,15 @@ impl super::Tabular {
         let has_message = !self.current_table_name.is_empty();
         let show_bottom = has_headers || has_message || executed;
 
-        if show_bottom {
-            self.table_split_ratio = self.table_split_ratio.clamp(0.05, 0.995);
-        }
+        let split_ratio = self
+            .query_tabs
+            .get(self.active_tab_index)
+            .and_then(|t| t.split_ratio)
+            .unwrap_or(self.table_split_ratio)
+            .clamp(SPLIT_RATIO_MIN, SPLIT_RATIO_MAX);
 
         let editor_h = if show_bottom {
-            let mut h = avail * self.table_split_ratio;
+            let mut h = avail * split_ratio;
             if has_headers {
                 h = h.clamp(100.0, (avail - 50.0).max(100.0));
             } else {
@@ -772,10 +793,11 @@ impl super::Tabular {
                         .size(8.0)
                 };
                 let play_tooltip = if is_loading {
-                    "Executing query…"
+                    "Executing query…".to_string()
                 } else {
-                    "CMD+Enter to execute"
+                    format!("{} to execute", self.keybindings.hint(super::keybindings::Action::RunQuery))
                 };
+                let format_hint = self.keybindings.hint(super::keybindings::Action::FormatSql);
 
                 let (tx_mode, tx_active) = self
                     .query_tabs
@@ -870,13 +892,15 @@ impl super::Tabular {
                                         egui::Color32::from_rgb(190, 190, 190)
                                     };
 
+                                    editor_bookmarks::render_bookmark_menu(self, ui);
+
                                     let format_button = egui::Button::new(egui::RichText::new("</>").size(8.0))
                                         .fill(base_fill)
                                         .stroke(egui::Stroke::new(1.0, base_border))
                                         .corner_radius(egui::CornerRadius::same(button_corner));
                                     if ui
                                         .add_sized(button_size, format_button)
-                                        .on_hover_text("Format SQL (Cmd+Shift+F)")
+                                        .on_hover_text(format!("Format SQL ({})", format_hint))
                                         .clicked()
                                     {
                                         format_clicked = true;
@@ -1025,8 +1049,10 @@ impl super::Tabular {
                     ui.ctx().request_repaint();
                 }
 
-                // Keyboard shortcut
-                if ui.input(|i| (i.modifiers.ctrl || i.modifiers.mac_cmd) && i.key_pressed(egui::Key::Enter)) {
+                // Keyboard shortcut (Run query, default Cmd/Ctrl+Enter)
+                if self.keybinding_capture.is_none()
+                    && ui.input(|i| self.keybindings.pressed(i, super::keybindings::Action::RunQuery))
+                {
                     let has_q = if !self.selected_text.trim().is_empty() {
                         true
                     } else {
@@ -1063,73 +1089,38 @@ impl super::Tabular {
             let desired_h = 6.0;
             let available_w = ui.available_width();
             let (rect, resp) = ui.allocate_at_least(egui::vec2(available_w, desired_h), egui::Sense::click_and_drag());
+            let resp = resp
+                .on_hover_cursor(egui::CursorIcon::ResizeVertical)
+                .on_hover_text("Drag to resize • double-click to reset");
             let stroke = egui::Stroke::new(1.0, ui.visuals().widgets.noninteractive.fg_stroke.color);
             ui.painter().hline(rect.x_range(), rect.center().y, stroke);
-            if resp.dragged() {
+            // The ratio is remembered on the active tab; double-click falls back to the default
+            if resp.double_clicked() {
+                if let Some(tab) = self.query_tabs.get_mut(self.active_tab_index) {
+                    tab.split_ratio = None;
+                }
+            } else if resp.dragged() {
                 let drag_delta = resp.drag_delta().y;
                 if avail > 0.0 {
-                    self.table_split_ratio = (self.table_split_ratio + (drag_delta / avail)).clamp(0.05, 0.995);
+                    let ratio = (split_ratio + (drag_delta / avail)).clamp(SPLIT_RATIO_MIN, SPLIT_RATIO_MAX);
+                    match self.query_tabs.get_mut(self.active_tab_index) {
+                        Some(tab) => tab.split_ratio = Some(ratio),
+                        None => self.table_split_ratio = ratio,
+                    }
                 }
                 ui.memory_mut(|m| m.request_focus(handle_id));
             }
             ui.add_space(2.0);
             
-            // RESULT TAB BAR
-            // Only show if we have more than one result in the active tab
-            let mut result_tabs_info: Option<(usize, usize)> = None; // (count, active_index)
-            if let Some(tab) = self.query_tabs.get(self.active_tab_index).filter(|t| t.results.len() > 1) {
-                    result_tabs_info = Some((tab.results.len(), tab.active_result_index));
-            }
-
-            if let Some((count, active_idx)) = result_tabs_info {
-                ui.horizontal(|ui| {
-                    ui.spacing_mut().item_spacing.x = 2.0;
-                    for i in 0..count {
-                        let label = format!("Result {}", i + 1);
-                        let is_active = i == active_idx;
-                        let btn = if is_active {
-                             egui::Button::new(egui::RichText::new(label).strong().color(egui::Color32::WHITE))
-                                .fill(super::style::theme_accent(ui.ctx()))
-                        } else {
-                             egui::Button::new(label)
-                        };
-                        
-                        if ui.add(btn).clicked() {
-                            // Switch result tab!
-                             if let Some(tab) = self.query_tabs.get_mut(self.active_tab_index) {
-                                tab.active_result_index = i;
-                                if let Some(res) = tab.results.get(i) {
-                                    // Sync to viewport fields
-                                    self.current_table_headers = res.headers.clone();
-                                    self.current_table_data = res.rows.clone();
-                                    self.all_table_data = res.all_rows.clone();
-                                    self.current_table_name = res.table_name.clone();
-                                    self.total_rows = res.total_rows;
-                                    self.current_page = res.current_page;
-                                    self.page_size = res.page_size;
-                                    self.query_message = res.query_message.clone();
-                                    self.query_message_is_error = res.query_message_is_error;
-                                    self.show_message_panel = true; // Always show message panel context
-                                     // Also update Viewport fields in Tab
-                                    tab.result_headers = res.headers.clone();
-                                    tab.result_rows = res.rows.clone();
-                                    tab.result_all_rows = res.all_rows.clone();
-                                    tab.result_table_name = res.table_name.clone();
-                                    tab.query_message = res.query_message.clone();
-                                    tab.query_message_is_error = res.query_message_is_error;
-                                    tab.total_rows = res.total_rows;
-                                    tab.current_page = res.current_page;
-                                }
-                             }
-                        }
-                    }
-                });
-                ui.separator();
-            }
+            // RESULT TAB BAR: results of a multi-statement run, then pinned results
+            data_table::render_result_tab_bar(self, ui);
 
             // Render bottom panel data grid
             data_table::render_table_data(self, ui);
         }
+        if let Some(color) = tag_color {
+            crate::connection::tag::paint_connection_edge(ui, edge_top, color);
+        }
     }
     pub fn render_active_query_jobs_overlay(&mut self, ctx: &egui::Context) {
         self.prune_cancelled_jobs();
@@ -1225,14 +1216,26 @@ impl super::Tabular {
                                     preview.push('…');
                                 }
 
-                                let chip_text = format!(
-                                    "{} • {} • {}",
-                                    connection_label,
-                                    elapsed_label,
-                                    preview.trim()
-                                );
-
                                 let job_id = status.job_id;
+                                let stream = self
+                                    .query_streams
+                                    .get(&job_id)
+                                    .map(|p| (p.received, p.paused));
+                                let chip_text = match stream {
+                                    Some((received, _)) if received > 0 => format!(
+                                        "{} • {} • {} row(s) • {}",
+                                        connection_label,
+                                        elapsed_label,
+                                        received,
+                                        preview.trim()
+                                    ),
+                                    _ => format!(
+                                        "{} • {} • {}",
+                                        connection_label,
+                                        elapsed_label,
+                                        preview.trim()
+                                    ),
+                                };
                                 ui.horizontal_wrapped(|ui| {
                                     ui.spacing_mut().item_spacing = egui::vec2(6.0, 0.0);
 
@@ -1258,6 +1261,31 @@ impl super::Tabular {
                                     {
                                         ctx.request_repaint();
                                     }
+
+                                    if let Some((_, true)) = stream {
+                                        if ui
+                                            .button(
+                                                egui::RichText::new(format!(
+                                                    "Fetch {} more",
+                                                    self.stream_row_ceiling
+                                                ))
+                                                .size(11.0),
+                                            )
+                                            .on_hover_text(
+                                                "The row ceiling was reached; fetch the next batch",
+                                            )
+                                            .clicked()
+                                        {
+                                            self.fetch_more_stream_rows(job_id);
+                                        }
+                                        if ui
+                                            .button(egui::RichText::new("Stop here").size(11.0))
+                                            .on_hover_text("Keep the rows received so far")
+                                            .clicked()
+                                        {
+                                            self.stop_stream(job_id);
+                                        }
+                                    }
                                 });
                             }
                         });
@@ -1437,3 +1465,317 @@ pub fn render_schema_diff_dialog(tabular: &mut super::Tabular, ctx: &egui::Conte
         tabular.show_schema_diff_dialog = false;
     }
 }
+
+/// Read-only side-by-side diff of two query tabs ("Compare tab with...").
+pub fn render_tab_compare_dialog(tabular: &mut super::Tabular, ctx: &egui::Context) {
+    let tab_titles: Vec<String> = tabular
+        .query_tabs
+        .iter()
+        .enumerate()
+        .map(|(i, t)| format!("{}. {}", i + 1, t.title))
+        .collect();
+    let texts: Vec<String> = (0..tab_titles.len())
+        .map(|i| crate::editor::tab_text(tabular, i))
+        .collect();
+
+    let mut open = true;
+    let mut copy_text: Option<String> = None;
+    let Some(state) = &mut tabular.tab_compare_state else {
+        return;
+    };
+    if tab_titles.len() < 2 {
+        tabular.tab_compare_state = None;
+        return;
+    }
+    // Tabs may have been closed while the view was open.
+    state.left_tab = state.left_tab.min(tab_titles.len() - 1);
+    state.right_tab = state.right_tab.min(tab_titles.len() - 1);
+    state.refresh(&texts[state.left_tab], &texts[state.right_tab]);
+
+    let dark = ctx.global_style().visuals.dark_mode;
+    let (removed_bg, added_bg) = if dark {
+        (
+            egui::Color32::from_rgb(75, 30, 34),
+            egui::Color32::from_rgb(28, 64, 38),
+        )
+    } else {
+        (
+            egui::Color32::from_rgb(255, 220, 220),
+            egui::Color32::from_rgb(215, 245, 215),
+        )
+    };
+
+    egui::Window::new("Compare Tabs")
+        .open(&mut open)
+        .default_size(egui::vec2(900.0, 560.0))
+        .resizable(true)
+        .collapsible(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+        .show(ctx, |ui| {
+            // ── Tab pickers ───────────────────────────────────────────
+            ui.horizontal(|ui| {
+                ui.label("Left:");
+                egui::ComboBox::from_id_salt("tab_compare_left")
+                    .selected_text(&tab_titles[state.left_tab])
+                    .width(220.0)
+                    .show_ui(ui, |ui| {
+                        for (i, title) in tab_titles.iter().enumerate() {
+                            ui.selectable_value(&mut state.left_tab, i, title);
+                        }
+                    });
+                if ui.button("⇄").on_hover_text("Swap sides").clicked() {
+                    std::mem::swap(&mut state.left_tab, &mut state.right_tab);
+                }
+                ui.label("Right:");
+                egui::ComboBox::from_id_salt("tab_compare_right")
+                    .selected_text(&tab_titles[state.right_tab])
+                    .width(220.0)
+                    .show_ui(ui, |ui| {
+                        for (i, title) in tab_titles.iter().enumerate() {
+                            ui.selectable_value(&mut state.right_tab, i, title);
+                        }
+                    });
+            });
+
+            ui.add_space(4.0);
+
+            // ── Summary + copy actions ────────────────────────────────
+            ui.horizontal(|ui| {
+                if state.added == 0 && state.removed == 0 {
+                    ui.label("No differences");
+                } else {
+                    ui.colored_label(egui::Color32::from_rgb(80, 180, 80), format!("+{}", state.added));
+                    ui.colored_label(egui::Color32::from_rgb(220, 70, 70), format!("-{}", state.removed));
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("📋 Copy Right").clicked() {
+                        copy_text = Some(texts[state.right_tab].clone());
+                    }
+                    if ui.button("📋 Copy Left").clicked() {
+                        copy_text = Some(texts[state.left_tab].clone());
+                    }
+                    if ui
+                        .button("📋 Copy Merged")
+                        .on_hover_text("Unified diff with -/+ line markers")
+                        .clicked()
+                    {
+                        copy_text = Some(state.unified.clone());
+                    }
+                });
+            });
+
+            ui.separator();
+
+            // ── Side-by-side rows ─────────────────────────────────────
+            let font = egui::FontId::monospace(12.0);
+            let row_height = ui.fonts_mut(|f| f.row_height(&font)) + 4.0;
+            let text_color = ui.visuals().text_color();
+            let gutter_color = ui.visuals().weak_text_color();
+            egui::ScrollArea::both()
+                .auto_shrink([false, false])
+                .show_rows(ui, row_height, state.rows.len(), |ui, range| {
+                    let half = (ui.available_width() / 2.0).max(200.0);
+                    for row in &state.rows[range] {
+                        let (rect, _) = ui.allocate_exact_size(
+                            egui::vec2(half * 2.0, row_height),
+                            egui::Sense::hover(),
+                        );
+                        let sides = [
+                            (&row.left, removed_bg, rect.left()),
+                            (&row.right, added_bg, rect.left() + half),
+                        ];
+                        for (side, changed_bg, x) in sides {
+                            let cell = egui::Rect::from_min_size(
+                                egui::pos2(x, rect.top()),
+                                egui::vec2(half - 2.0, row_height),
+                            );
+                            if row.changed && side.is_some() {
+                                ui.painter().rect_filled(cell, 0.0, changed_bg);
+                            }
+                            let Some((line_no, text)) = side else {
+                                continue;
+                            };
+                            let painter = ui.painter().with_clip_rect(cell);
+                            painter.text(
+                                egui::pos2(cell.left() + 36.0, cell.center().y),
+                                egui::Align2::RIGHT_CENTER,
+                                (line_no + 1).to_string(),
+                                font.clone(),
+                                gutter_color,
+                            );
+                            painter.text(
+                                egui::pos2(cell.left() + 44.0, cell.center().y),
+                                egui::Align2::LEFT_CENTER,
+                                text,
+                                font.clone(),
+                                text_color,
+                            );
+                        }
+                    }
+                });
+        });
+
+    if let Some(text) = copy_text {
+        ctx.copy_text(text);
+        tabular.toasts.success("Copied to clipboard");
+    }
+    if !open {
+        tabular.tab_compare_state = None;
+    }
+}
+
+/// PostgreSQL LISTEN/NOTIFY panel: subscribe to channels on a dedicated
+/// connection and stream incoming payloads. Closing the panel stops listening.
+pub fn render_pg_listen_panel(tabular: &mut super::Tabular, ctx: &egui::Context) {
+    // Drain notifications first so the list is current for this frame.
+    let state = &mut tabular.pg_listen;
+    if let Some(receiver) = &state.receiver {
+        while let Ok(message) = receiver.try_recv() {
+            match message {
+                models::enums::PgListenMessage::Notification(event) => state.events.push(event),
+                models::enums::PgListenMessage::Status(status) => state.status = status,
+            }
+        }
+        let max = models::structs::PgListenState::MAX_EVENTS;
+        if state.events.len() > max {
+            let excess = state.events.len() - max;
+            state.events.drain(..excess);
+        }
+        ctx.request_repaint_after(std::time::Duration::from_millis(250));
+    }
+
+    let pg_connections: Vec<(i64, String)> = tabular
+        .connections
+        .iter()
+        .filter(|c| c.connection_type == models::enums::DatabaseType::PostgreSQL)
+        .filter_map(|c| c.id.map(|id| (id, c.name.clone())))
+        .collect();
+
+    let mut open = true;
+    let mut start = false;
+    let mut stop = false;
+    egui::Window::new("PostgreSQL Notifications")
+        .open(&mut open)
+        .default_size(egui::vec2(720.0, 420.0))
+        .resizable(true)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            let state = &mut tabular.pg_listen;
+            let listening = state.is_listening();
+            ui.horizontal(|ui| {
+                ui.label("Connection:");
+                ui.add_enabled_ui(!listening, |ui| {
+                    egui::ComboBox::from_id_salt("pg_listen_connection")
+                        .selected_text(
+                            pg_connections
+                                .iter()
+                                .find(|(id, _)| Some(*id) == state.connection_id)
+                                .map(|(_, name)| name.as_str())
+                                .unwrap_or("Select…"),
+                        )
+                        .width(180.0)
+                        .show_ui(ui, |ui| {
+                            for (id, name) in &pg_connections {
+                                ui.selectable_value(&mut state.connection_id, Some(*id), name);
+                            }
+                        });
+                    ui.label("Channels:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.channels_input)
+                            .hint_text("orders, audit")
+                            .desired_width(200.0),
+                    );
+                });
+                if listening {
+                    if ui.button("⏹ Stop").clicked() {
+                        stop = true;
+                    }
+                } else {
+                    let can_start = state.connection_id.is_some()
+                        && !crate::driver_postgres::parse_listen_channels(&state.channels_input)
+                            .is_empty();
+                    if ui.add_enabled(can_start, egui::Button::new("▶ Listen")).clicked() {
+                        start = true;
+                    }
+                }
+                if ui.button("Clear").clicked() {
+                    state.events.clear();
+                }
+            });
+            if !state.status.is_empty() {
+                ui.label(
+                    egui::RichText::new(&state.status)
+                        .size(11.0)
+                        .color(egui::Color32::from_gray(120)),
+                );
+            }
+            ui.separator();
+
+            if state.events.is_empty() {
+                ui.label(
+                    egui::RichText::new("No notifications yet")
+                        .italics()
+                        .color(egui::Color32::from_gray(120)),
+                );
+                return;
+            }
+            egui::ScrollArea::both()
+                .auto_shrink([false, false])
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    egui::Grid::new("pg_listen_events")
+                        .striped(true)
+                        .num_columns(3)
+                        .show(ui, |ui| {
+                            ui.strong("Time");
+                            ui.strong("Channel");
+                            ui.strong("Payload");
+                            ui.end_row();
+                            for event in &state.events {
+                                ui.monospace(event.received_at.format("%H:%M:%S%.3f").to_string());
+                                ui.label(&event.channel);
+                                ui.add(egui::Label::new(egui::RichText::new(&event.payload).monospace()).wrap());
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+
+    if start {
+        start_pg_listen(tabular);
+    }
+    if stop || !open {
+        tabular.pg_listen.stop();
+        if stop {
+            tabular.pg_listen.status = "Stopped".to_string();
+        }
+    }
+    if !open {
+        tabular.show_pg_listen_panel = false;
+    }
+}
+
+fn start_pg_listen(tabular: &mut super::Tabular) {
+    let channels = crate::driver_postgres::parse_listen_channels(&tabular.pg_listen.channels_input);
+    let Some(connection) = tabular
+        .pg_listen
+        .connection_id
+        .and_then(|id| tabular.connections.iter().find(|c| c.id == Some(id)))
+        .cloned()
+    else {
+        return;
+    };
+    let options = match crate::driver_postgres::listen_connect_options(&connection) {
+        Ok(options) => options,
+        Err(e) => {
+            tabular.pg_listen.status = format!("Cannot connect: {}", e);
+            return;
+        }
+    };
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let stop_flag = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+    crate::driver_postgres::spawn_notify_listener(options, channels, sender, stop_flag.clone());
+    tabular.pg_listen.receiver = Some(receiver);
+    tabular.pg_listen.stop_flag = Some(stop_flag);
+    tabular.pg_listen.status = format!("Connecting to {}…", connection.name);
+}
@@ -38,9 +38,8 @@ impl super::Tabular {
         let mut matches = false;
         let mut filtered_children = Vec::new();
 
-        // Check if current node matches using case-sensitive LIKE search
-        // LIKE search: if search text is contained anywhere in the node name
-        if node.name.contains(search_text) {
+        // Case-insensitive LIKE search: search text contained anywhere in the node name
+        if node.name.to_lowercase().contains(&search_text.to_lowercase()) {
             matches = true;
         }
 
@@ -100,15 +99,15 @@ impl super::Tabular {
                 models::enums::DatabaseType::MySQL
                 | models::enums::DatabaseType::PostgreSQL
                 | models::enums::DatabaseType::SQLite => {
-                    self.search_sql_tables(connection_id, search_text, &conn_type);
+                    self.search_sql_tables(connection_id, search_text);
                 }
                 models::enums::DatabaseType::MsSQL => {
                     // Basic table search (reuse SQL logic)
-                    self.search_sql_tables(connection_id, search_text, &conn_type);
+                    self.search_sql_tables(connection_id, search_text);
                 }
                 models::enums::DatabaseType::MongoDB => {
                     // Reuse SQL table cache search; collections are stored in table_cache with table_type='collection'
-                    self.search_sql_tables(connection_id, search_text, &conn_type);
+                    self.search_sql_tables(connection_id, search_text);
                 }
                 models::enums::DatabaseType::ApiHttp => {}
             }
@@ -195,81 +194,54 @@ impl super::Tabular {
             self.filtered_items_tree.push(search_result_node);
         }
     }
-    pub fn search_sql_tables(
-        &mut self,
-        connection_id: i64,
-        search_text: &str,
-        db_type: &models::enums::DatabaseType,
-    ) {
-        // Search through cached table data and column data
+    pub fn search_sql_tables(&mut self, connection_id: i64, search_text: &str) {
+        // Search the cached catalog (table_cache/column_cache) across every database of
+        // the connection, so objects in collapsed or never-expanded nodes are found too.
         if let Some(ref pool) = self.db_pool {
             let pool_clone = pool.clone();
-            let search_pattern = format!("*{}*", search_text); // Using GLOB pattern for case-sensitive search
+            // The cache is always SQLite, whose LIKE is case-insensitive for ASCII.
+            let search_pattern = catalog_like_pattern(search_text);
             let rt = tokio::runtime::Runtime::new().unwrap();
 
-            // Search tables
+            // Search tables and views
             let table_search_results = rt.block_on(async {
-                let query = match db_type {
-                    models::enums::DatabaseType::SQLite => {
-                        "SELECT table_name, database_name, table_type FROM table_cache WHERE connection_id = ? AND table_name GLOB ? ORDER BY table_name"
-                    }
-                    _ => {
-                        "SELECT table_name, database_name, table_type FROM table_cache WHERE connection_id = ? AND table_name LIKE ? COLLATE BINARY ORDER BY database_name, table_name"
-                    }
-                };
-
-                let search_param = match db_type {
-                    models::enums::DatabaseType::SQLite => &search_pattern,
-                    _ => &format!("%{}%", search_text), // For non-SQLite, use LIKE with COLLATE BINARY for case sensitivity
-                };
-
-                sqlx::query_as::<_, (String, String, String)>(query)
-                    .bind(connection_id)
-                    .bind(search_param)
-                    .fetch_all(pool_clone.as_ref())
-                    .await
-                    .unwrap_or_default()
+                sqlx::query_as::<_, (String, String, String)>(
+                    "SELECT table_name, database_name, table_type FROM table_cache WHERE connection_id = ? AND table_name LIKE ? ESCAPE '\\' ORDER BY database_name, table_name",
+                )
+                .bind(connection_id)
+                .bind(&search_pattern)
+                .fetch_all(pool_clone.as_ref())
+                .await
+                .unwrap_or_default()
             });
 
             // Search columns
             let column_search_results = rt.block_on(async {
-                let query = match db_type {
-                    models::enums::DatabaseType::SQLite => {
-                        "SELECT DISTINCT table_name, database_name, column_name, data_type FROM column_cache WHERE connection_id = ? AND column_name GLOB ? ORDER BY table_name"
-                    }
-                    _ => {
-                        "SELECT DISTINCT table_name, database_name, column_name, data_type FROM column_cache WHERE connection_id = ? AND column_name LIKE ? COLLATE BINARY ORDER BY database_name, table_name"
-                    }
-                };
-
-                let search_param = match db_type {
-                    models::enums::DatabaseType::SQLite => &search_pattern,
-                    _ => &format!("%{}%", search_text), // For non-SQLite, use LIKE with COLLATE BINARY for case sensitivity
-                };
-
-                sqlx::query_as::<_, (String, String, String, String)>(query)
-                    .bind(connection_id)
-                    .bind(search_param)
-                    .fetch_all(pool_clone.as_ref())
-                    .await
-                    .unwrap_or_default()
+                sqlx::query_as::<_, (String, String, String, String)>(
+                    "SELECT DISTINCT table_name, database_name, column_name, data_type FROM column_cache WHERE connection_id = ? AND column_name LIKE ? ESCAPE '\\' ORDER BY database_name, table_name, column_name",
+                )
+                .bind(connection_id)
+                .bind(&search_pattern)
+                .fetch_all(pool_clone.as_ref())
+                .await
+                .unwrap_or_default()
             });
 
-            // Group table results by database
-            let mut table_results_by_db: std::collections::HashMap<String, Vec<String>> =
-                std::collections::HashMap::new();
-            for (table_name, database_name, _table_type) in table_search_results {
+            // Group table results by database, keeping the object type
+            let mut table_results_by_db: std::collections::BTreeMap<String, Vec<(String, String)>> =
+                std::collections::BTreeMap::new();
+            for (table_name, database_name, table_type) in table_search_results {
                 table_results_by_db
                     .entry(database_name)
                     .or_default()
-                    .push(table_name);
+                    .push((table_name, table_type));
             }
 
             // Group column results by database and table
-            let mut column_results_by_db: std::collections::HashMap<
+            let mut column_results_by_db: std::collections::BTreeMap<
                 String,
-                std::collections::HashMap<String, Vec<(String, String)>>,
-            > = std::collections::HashMap::new();
+                std::collections::BTreeMap<String, Vec<(String, String)>>,
+            > = std::collections::BTreeMap::new();
             for (table_name, database_name, column_name, data_type) in column_search_results {
                 column_results_by_db
                     .entry(database_name)
@@ -288,7 +260,7 @@ impl super::Tabular {
                     .map(|c| c.name.clone())
                     .unwrap_or_else(|| "Unknown Connection".to_string());
 
-                let total_tables: usize = table_results_by_db.values().map(|v| v.len()).sum();
+                let total_objects: usize = table_results_by_db.values().map(|v| v.len()).sum();
                 let total_columns: usize = column_results_by_db
                     .values()
                     .flat_map(|db| db.values())
@@ -297,23 +269,23 @@ impl super::Tabular {
 
                 let mut search_result_node = models::structs::TreeNode::new(
                     format!(
-                        "🔍 Search Results in {} ({} tables, {} columns)",
-                        connection_name, total_tables, total_columns
+                        "🔍 Search Results in {} ({} objects, {} columns)",
+                        connection_name, total_objects, total_columns
                     ),
                     models::enums::NodeType::CustomFolder,
                 );
                 search_result_node.connection_id = Some(connection_id);
                 search_result_node.is_expanded = true;
 
-                // Combine all databases from both searches
-                let mut all_databases: std::collections::HashSet<String> =
-                    std::collections::HashSet::new();
+                // Combine all databases from both searches, in name order
+                let mut all_databases: std::collections::BTreeSet<String> =
+                    std::collections::BTreeSet::new();
                 all_databases.extend(table_results_by_db.keys().cloned());
                 all_databases.extend(column_results_by_db.keys().cloned());
 
                 // Add databases and their tables/columns
                 for database_name in all_databases {
-                    let tables = table_results_by_db
+                    let objects = table_results_by_db
                         .get(&database_name)
                         .cloned()
                         .unwrap_or_default();
@@ -324,9 +296,9 @@ impl super::Tabular {
 
                     let mut db_node = models::structs::TreeNode::new(
                         format!(
-                            "📁 {} ({} tables, {} column matches)",
+                            "📁 {} ({} objects, {} column matches)",
                             database_name,
-                            tables.len(),
+                            objects.len(),
                             column_tables.values().map(|cols| cols.len()).sum::<usize>()
                         ),
                         models::enums::NodeType::Database,
@@ -335,11 +307,12 @@ impl super::Tabular {
                     db_node.database_name = Some(database_name.clone());
                     db_node.is_expanded = true;
 
-                    // Add tables found by table name search
-                    for table_name in tables {
+                    // Add objects found by name search, labelled with their type
+                    for (table_name, table_type) in objects {
+                        let (icon, node_type) = catalog_object_kind(&table_type);
                         let mut table_node = models::structs::TreeNode::new(
-                            format!("📋 {} (table name match)", table_name),
-                            models::enums::NodeType::Table,
+                            format!("{} {} · {}", icon, table_name, table_type.to_lowercase()),
+                            node_type,
                         );
                         table_node.connection_id = Some(connection_id);
                         table_node.database_name = Some(database_name.clone());
@@ -358,17 +331,17 @@ impl super::Tabular {
                         table_node.database_name = Some(database_name.clone());
                         // Store the actual table name without icon for query generation
                         table_node.table_name = Some(table_name.clone());
+                        table_node.is_expanded = true;
 
                         // Add matching columns as children
                         for (column_name, data_type) in columns {
                             let mut column_node = models::structs::TreeNode::new(
-                                format!("🔧 {} ({})", column_name, data_type),
+                                format!("🔧 {}.{} ({})", table_name, column_name, data_type),
                                 models::enums::NodeType::Column,
                             );
                             column_node.connection_id = Some(connection_id);
                             column_node.database_name = Some(database_name.clone());
                             // For columns, we can store the table name in table_name field
-                            // The actual column name is already in the display name without icon
                             column_node.table_name = Some(table_name.clone());
                             table_node.children.push(column_node);
                         }
@@ -383,6 +356,70 @@ impl super::Tabular {
             }
         }
     }
+    /// Expand the main tree down to a table/view picked from the search results:
+    /// parent folders, connection, database and object folder. Each level is loaded
+    /// from the schema cache before descending, so never-expanded paths work too.
+    /// With a `column`, the table's Columns folder is opened as well. The tree
+    /// scrolls to the revealed row on its next render.
+    pub(crate) fn reveal_object_in_tree(
+        &mut self,
+        connection_id: i64,
+        database_name: Option<&str>,
+        object_name: &str,
+        node_type: &models::enums::NodeType,
+        column: Option<&str>,
+    ) {
+        let mut tree = std::mem::take(&mut self.items_tree);
+        expand_ancestors_of_connection(&mut tree, connection_id);
+        if let Some(mut node) = Self::find_connection_node_recursive(&mut tree, connection_id) {
+            // Connection → [Databases] → Database → Tables/Views → object
+            for _ in 0..5 {
+                node.is_expanded = true;
+                self.load_expanded_nodes_recursive(connection_id, node);
+                match reveal_step(&node.children, database_name, object_name, node_type) {
+                    Some(index) => node = &mut node.children[index],
+                    None => break,
+                }
+                if matches!(
+                    node.node_type,
+                    models::enums::NodeType::Table | models::enums::NodeType::View
+                ) {
+                    if column.is_some() {
+                        if node.children.is_empty() {
+                            let db = node.database_name.clone().unwrap_or_default();
+                            node.children = self.table_subfolders(connection_id, object_name, &db);
+                            node.is_loaded = true;
+                        }
+                        node.is_expanded = true;
+                        for child in &mut node.children {
+                            if child.node_type == models::enums::NodeType::ColumnsFolder {
+                                child.is_expanded = true;
+                            }
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+        self.items_tree = tree;
+        self.pending_tree_reveal = database_name.map(|db| models::structs::DefinitionTarget {
+            connection_id,
+            database_name: db.to_string(),
+            table: object_name.to_string(),
+            node_type: node_type.clone(),
+            column: column.map(str::to_string),
+        });
+    }
+    /// Expand a connection node (and the folders above it) and load its children.
+    pub(crate) fn reveal_connection_in_tree(&mut self, connection_id: i64) {
+        let mut tree = std::mem::take(&mut self.items_tree);
+        expand_ancestors_of_connection(&mut tree, connection_id);
+        if let Some(node) = Self::find_connection_node_recursive(&mut tree, connection_id) {
+            node.is_expanded = true;
+            self.load_expanded_nodes_recursive(connection_id, node);
+        }
+        self.items_tree = tree;
+    }
     pub fn find_redis_key_info(
         node: &models::structs::TreeNode,
         key_name: &str,
@@ -686,3 +723,151 @@ impl super::Tabular {
         job
     }
 }
+
+/// LIKE pattern for a substring match against the catalog cache, with `%`, `_`
+/// and `\\` in the user's text escaped so they match literally.
+fn catalog_like_pattern(search_text: &str) -> String {
+    let mut pattern = String::with_capacity(search_text.len() + 2);
+    pattern.push('%');
+    for ch in search_text.chars() {
+        if matches!(ch, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(ch);
+    }
+    pattern.push('%');
+    pattern
+}
+
+/// Icon and node type for a `table_cache.table_type` value.
+fn catalog_object_kind(table_type: &str) -> (&'static str, models::enums::NodeType) {
+    if table_type.to_lowercase().contains("view") {
+        ("👁", models::enums::NodeType::View)
+    } else {
+        ("📋", models::enums::NodeType::Table)
+    }
+}
+
+const TREE_REVEAL_ID: &str = "tree_reveal_target";
+
+/// Hand the pending reveal to this frame's tree render (see [`scroll_to_revealed`]).
+pub(super) fn publish_tree_reveal(ui: &egui::Ui, target: models::structs::DefinitionTarget) {
+    ui.data_mut(|d| d.insert_temp(egui::Id::new(TREE_REVEAL_ID), target));
+}
+
+/// Scroll the tree to `node`'s row when it is the row being revealed.
+pub(super) fn scroll_to_revealed(
+    ui: &egui::Ui,
+    node: &models::structs::TreeNode,
+    response: &egui::Response,
+) {
+    let id = egui::Id::new(TREE_REVEAL_ID);
+    let hit = ui
+        .data(|d| d.get_temp::<models::structs::DefinitionTarget>(id))
+        .is_some_and(|target| target.matches_node(node));
+    if hit {
+        ui.data_mut(|d| d.remove::<models::structs::DefinitionTarget>(id));
+        response.scroll_to_me(Some(egui::Align::Center));
+    }
+}
+
+/// Mark every folder on the way to the connection node as expanded.
+fn expand_ancestors_of_connection(nodes: &mut [models::structs::TreeNode], connection_id: i64) -> bool {
+    for node in nodes.iter_mut() {
+        if node.node_type == models::enums::NodeType::Connection
+            && node.connection_id == Some(connection_id)
+        {
+            return true;
+        }
+        if expand_ancestors_of_connection(&mut node.children, connection_id) {
+            node.is_expanded = true;
+            return true;
+        }
+    }
+    false
+}
+
+/// Pick the child to descend into when revealing an object: the object itself,
+/// else its database, else the folder that would contain it.
+fn reveal_step(
+    children: &[models::structs::TreeNode],
+    database_name: Option<&str>,
+    object_name: &str,
+    node_type: &models::enums::NodeType,
+) -> Option<usize> {
+    use models::enums::NodeType;
+    let folder = if *node_type == NodeType::View {
+        NodeType::ViewsFolder
+    } else {
+        NodeType::TablesFolder
+    };
+    let in_database = |node: &models::structs::TreeNode| match (database_name, node.database_name.as_deref()) {
+        (Some(wanted), Some(actual)) => wanted == actual,
+        _ => true,
+    };
+    children
+        .iter()
+        .position(|c| {
+            matches!(c.node_type, NodeType::Table | NodeType::View)
+                && (c.table_name.as_deref() == Some(object_name) || c.name == object_name)
+        })
+        .or_else(|| {
+            children.iter().position(|c| {
+                c.node_type == NodeType::Database
+                    && database_name.is_some_and(|db| {
+                        c.database_name.as_deref() == Some(db) || c.name == db
+                    })
+            })
+        })
+        .or_else(|| children.iter().position(|c| c.node_type == NodeType::DatabasesFolder))
+        .or_else(|| children.iter().position(|c| c.node_type == folder && in_database(c)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use models::enums::NodeType;
+    use models::structs::TreeNode;
+
+    #[test]
+    fn like_pattern_escapes_wildcards() {
+        assert_eq!(catalog_like_pattern("user"), "%user%");
+        assert_eq!(catalog_like_pattern("order_id"), "%order\\_id%");
+        assert_eq!(catalog_like_pattern("50%"), "%50\\%%");
+    }
+
+    #[test]
+    fn reveal_step_walks_database_then_folder_then_object() {
+        let mut shop = TreeNode::new("shop".to_string(), NodeType::Database);
+        shop.database_name = Some("shop".to_string());
+        let mut other = TreeNode::new("crm".to_string(), NodeType::Database);
+        other.database_name = Some("crm".to_string());
+        let top = vec![
+            TreeNode::new("Databases".to_string(), NodeType::DatabasesFolder),
+        ];
+        assert_eq!(reveal_step(&top, Some("shop"), "orders", &NodeType::Table), Some(0));
+
+        let dbs = vec![other, shop];
+        assert_eq!(reveal_step(&dbs, Some("shop"), "orders", &NodeType::Table), Some(1));
+
+        let folders = vec![
+            TreeNode::new("Tables".to_string(), NodeType::TablesFolder),
+            TreeNode::new("Views".to_string(), NodeType::ViewsFolder),
+        ];
+        assert_eq!(reveal_step(&folders, Some("shop"), "v_sales", &NodeType::View), Some(1));
+
+        let tables = vec![
+            TreeNode::new("customers".to_string(), NodeType::Table),
+            TreeNode::new("orders".to_string(), NodeType::Table),
+        ];
+        assert_eq!(reveal_step(&tables, Some("shop"), "orders", &NodeType::Table), Some(1));
+        assert_eq!(reveal_step(&tables, Some("shop"), "missing", &NodeType::Table), None);
+    }
+
+    #[test]
+    fn views_are_typed_as_views() {
+        assert_eq!(catalog_object_kind("VIEW").1, NodeType::View);
+        assert_eq!(catalog_object_kind("table").1, NodeType::Table);
+        assert_eq!(catalog_object_kind("collection").1, NodeType::Table);
+    }
+}
@@ -19,8 +19,14 @@ pub(crate) struct RenderTreeNodeParams<'a> {
     connection_types: &'a std::collections::HashMap<i64, models::enums::DatabaseType>,
     // Prefetch progress tracking
     prefetch_progress: &'a HashMap<i64, (usize, usize)>,
+    // Schema refresh progress sampled from the cache
+    refresh_progress: &'a HashMap<i64, models::structs::SchemaRefreshProgress>,
     // Pre-loaded PNG textures for DB type icons (key = DatabaseType::icon_key())
     db_icon_textures: &'a HashMap<String, egui::TextureHandle>,
+    // Table tooltip estimates; hovering marks missing ones for fetching
+    table_stats: &'a models::structs::TableStatsCache,
+    // Saved query metadata (friendly name, description, tags) by file path
+    saved_query_meta: &'a HashMap<String, models::structs::SavedQueryMeta>,
 }
 
 
@@ -58,6 +64,13 @@ impl super::Tabular {
             }
         }
 
+        // Fetch size estimates for tables hovered during the previous frame
+        connection::spawn_requested_table_stats(self);
+
+        if !is_search_mode && let Some(target) = self.pending_tree_reveal.take() {
+            super::search::publish_tree_reveal(ui, target);
+        }
+
         // Rebuild connection_type cache only when connections list length changes
         if self.cached_connection_types.len() != self.connections.iter().filter(|c| c.id.is_some()).count() {
             self.cached_connection_types.clear();
@@ -132,7 +145,10 @@ impl super::Tabular {
                     is_search_mode,
                     connection_types,
                     prefetch_progress: &self.prefetch_progress,
+                    refresh_progress: &self.refresh_progress,
                     db_icon_textures: &self.db_icon_textures,
+                    table_stats: &self.table_stats,
+                    saved_query_meta: &self.saved_query_meta,
                 },
             );
             if let Some(expansion_req) = expansion_request {
@@ -239,24 +255,9 @@ impl super::Tabular {
                 } else {
                     vec![]
                 };
-                // Auto-map by position (will be refined in the dialog)
-                let column_mappings = vec![];
-                self.csv_import_state = Some(crate::models::structs::CsvImportState {
-                    connection_id: conn_id,
-                    database_name: db_name,
-                    table_name,
-                    db_type,
-                    file_path: None,
-                    delimiter: ',',
-                    has_header_row: true,
-                    null_value: String::new(),
-                    preview_headers: vec![],
-                    preview_rows: vec![],
-                    table_columns: table_cols,
-                    column_mappings,
-                    status: crate::models::structs::CsvImportStatus::Idle,
-                    progress_message: String::new(),
-                });
+                self.csv_import_state = Some(crate::models::structs::CsvImportState::new(
+                    conn_id, db_name, table_name, db_type, table_cols,
+                ));
                 self.show_csv_import_dialog = true;
             }
         }
@@ -289,6 +290,18 @@ impl super::Tabular {
                 Some(models::enums::DBASpecialMode::ReplicationStatus)
             } else if trimmed_query.eq_ignore_ascii_case("SHOW MASTER STATUS;") {
                 Some(models::enums::DBASpecialMode::MasterStatus)
+            } else if self
+                .connections
+                .iter()
+                .find(|c| c.id == Some(conn_id))
+                .is_some_and(|c| {
+                    crate::connection::kill_session::is_session_view_query(
+                        &c.connection_type,
+                        trimmed_query,
+                    )
+                })
+            {
+                Some(models::enums::DBASpecialMode::SessionList)
             } else {
                 None
             };
@@ -345,6 +358,7 @@ impl super::Tabular {
                 self.pool_wait_in_progress = true;
                 self.pool_wait_connection_id = Some(conn_id);
                 self.pool_wait_query = query.clone();
+                self.pool_wait_params.clear();
                 self.pool_wait_started_at = Some(std::time::Instant::now());
                 self.current_table_name = "Connecting… waiting for pool".to_string();
             }
@@ -410,7 +424,7 @@ impl super::Tabular {
                                 }
                             }
                             models::enums::DatabasePool::SQLite(p) => {
-                                if let Ok(cols) = crate::driver_sqlite::fetch_sqlite_columns(&p).await {
+                                if let Ok(cols) = crate::driver_sqlite::fetch_sqlite_columns(&p, &db_name).await {
                                     columns_map = cols;
                                 }
                             }
@@ -435,7 +449,7 @@ impl super::Tabular {
                       }
                 },
                 Some(models::enums::DatabaseType::SQLite) => {
-                      if let Some(t) = crate::driver_sqlite::fetch_tables_from_sqlite_connection(self, conn_id, "table") {
+                      if let Some(t) = crate::driver_sqlite::fetch_tables_from_sqlite_connection(self, conn_id, &db_name, "table") {
                           all_tables = t;
                       }
                 },
@@ -991,101 +1005,441 @@ impl super::Tabular {
         }
 
         // Handle table click requests - create new tab for each table
-        for (connection_id, table_name, node_type, predefined_db_name) in table_click_requests {
-            // Find the connection to determine the database type and database name
-            let connection = self
+        self.open_table_click_requests(nodes, table_click_requests, is_search_mode);
+
+        // Handle index click requests - open Edit Index dialog
+        for (connection_id, index_name, database_name, table_name) in index_click_requests {
+            if let Some(conn) = self
                 .connections
                 .iter()
-                .find(|conn| conn.id == Some(connection_id))
-                .cloned();
-
-            if let Some(conn) = connection {
-                let is_view = node_type == models::enums::NodeType::View;
-                // Find the database name from the tree structure
-                let mut database_name: Option<String> = predefined_db_name;
-
-                // Optimization: Only search if not provided (should be provided for most table clicks)
-                if database_name.is_none() {
-                    for node in nodes.iter() {
-                        if let Some(db_name) =
-                            Tabular::find_database_name_for_table(node, connection_id, &table_name)
-                        {
-                            database_name = Some(db_name);
-                            break;
-                        }
-                    }
+                .find(|c| c.id == Some(connection_id))
+                .cloned()
+            {
+                // Prefill dialog state for Edit
+                if let Some(tn) = table_name.clone() {
+                    self.index_dialog = Some(models::structs::IndexDialogState {
+                        mode: models::structs::IndexDialogMode::Edit,
+                        connection_id,
+                        database_name: database_name.clone(),
+                        table_name: tn,
+                        existing_index_name: Some(index_name.clone()),
+                        index_name: index_name.clone(),
+                        columns: String::new(),
+                        unique: false,
+                        method: None,
+                        db_type: conn.connection_type.clone(),
+                    });
+                    self.show_index_dialog = true;
                 }
+            }
+        }
 
-                // If no database found in tree, use connection default
-                if database_name.is_none() {
-                    database_name = Some(conn.database.clone());
-                }
+        // Handle create index requests - open Create Index dialog
+        for (connection_id, database_name, table_name) in create_index_requests {
+            if let Some(conn) = self
+                .connections
+                .iter()
+                .find(|c| c.id == Some(connection_id))
+                .cloned()
+                && let Some(tn) = table_name.clone()
+            {
+                self.index_dialog = Some(models::structs::IndexDialogState {
+                    mode: models::structs::IndexDialogMode::Create,
+                    connection_id,
+                    database_name: database_name.clone(),
+                    table_name: tn.clone(),
+                    existing_index_name: None,
+                    index_name: format!("idx_{}_col", tn),
+                    columns: "columns comma-separated".to_string(),
+                    unique: false,
+                    method: None,
+                    db_type: conn.connection_type.clone(),
+                });
+                self.show_index_dialog = true;
+            }
+        }
 
-                match conn.connection_type {
-                    models::enums::DatabaseType::Redis => {
-                        // Redis objects never carry ALTER view DDL
-                        self.current_object_ddl = None;
-                        // Check if this is a Redis key (has specific Redis data types in the tree structure)
-                        // For Redis keys, we need to find which database they belong to
-                        let mut is_redis_key = false;
-                        let mut key_type: Option<String> = None;
+        let results = query_files_to_open.clone();
 
-                        for node in nodes.iter() {
-                            if let Some((_, k_type)) =
-                                Tabular::find_redis_key_info(node, &table_name)
-                            {
-                                key_type = Some(k_type.clone());
-                                is_redis_key = true;
-                                break;
-                            }
-                        }
+        // Handle context menu requests (deduplicate to avoid multiple calls)
+        let mut processed_removals = std::collections::HashSet::new();
+        let mut processed_refreshes = std::collections::HashSet::new();
+        let mut needs_full_refresh = false;
 
-                        if is_redis_key {
-                            if let Some(k_type) = key_type {
-                                let keyspace = database_name.clone().unwrap_or_default();
-                                let tab_title = format!("Redis Key: {} ({})", table_name, k_type);
+        for context_id in context_menu_requests {
+            debug!("🔍 Processing context_id: {}", context_id);
 
-                                if let Some(existing_index) = editor::find_tab_for_target(
-                                    self,
-                                    &tab_title,
-                                    connection_id,
-                                    database_name.as_deref(),
-                                ) {
-                                    if existing_index != self.active_tab_index {
-                                        editor::switch_to_tab(self, existing_index);
-                                    }
-                                } else {
-                                    let preview_result = crate::driver_redis::fetch_redis_key_pretty_json(
-                                        self,
-                                        connection_id,
-                                        &keyspace,
-                                        &table_name,
-                                        &k_type,
-                                    );
+            if context_id >= 62000 {
+                // Restart Replication
+                let conn_id = context_id - 62000;
+                let replica_pool_opt = self.connection_pools.get(&conn_id).cloned();
+                let mut master_id_opt = None;
+                if let Some(conn) = self.connections.iter().find(|c| c.id == Some(conn_id)) {
+                    master_id_opt = conn.replication_master_id;
+                }
+                
+                if let (Some(models::enums::DatabasePool::MySQL(replica_pool)), Some(master_id)) = (replica_pool_opt, master_id_opt) {
+                     if let Some(models::enums::DatabasePool::MySQL(master_pool)) = self.connection_pools.get(&master_id).cloned() {
+                         let rt = self.get_runtime();
+                         let (tx, rx) = std::sync::mpsc::channel();
+                         self.replication_setup_receiver = Some(rx);
+                         
+                         rt.spawn(async move {
+                             let res = crate::driver_mysql::restart_replication(&master_pool, &replica_pool).await;
+                             let _ = tx.send(res);
+                         });
+                         
+                         self.query_message = "Restarting replication...".to_string();
+                         self.show_message_panel = true;
+                         self.query_message_is_error = false;
+                     } else {
+                         self.query_message = "Master connection is not active. Please connect to Master first.".to_string();
+                         self.show_message_panel = true;
+                         self.query_message_is_error = true;
+                     }
+                } else {
+                     self.query_message = "Could not identify Master connection or pools not active.".to_string();
+                     self.show_message_panel = true;
+                     self.query_message_is_error = true;
+                }
+            } else if context_id >= 61000 {
+                // Stop Replication
+                let conn_id = context_id - 61000;
+                if let Some(models::enums::DatabasePool::MySQL(pool)) = self.connection_pools.get(&conn_id).cloned() {
+                     let rt = self.get_runtime();
+                     let (tx, rx) = std::sync::mpsc::channel();
+                     self.replication_setup_receiver = Some(rx);
+                     rt.spawn(async move {
+                         let res = crate::driver_mysql::stop_replication(&pool).await;
+                         let _ = tx.send(res);
+                     });
+                     self.query_message = "Stopping replication...".to_string();
+                     self.show_message_panel = true;
+                     self.query_message_is_error = false;
+                }
+            } else if context_id >= 60000 {
+                // Start Replication
+                let conn_id = context_id - 60000;
+                if let Some(models::enums::DatabasePool::MySQL(pool)) = self.connection_pools.get(&conn_id).cloned() {
+                     let rt = self.get_runtime();
+                     let (tx, rx) = std::sync::mpsc::channel();
+                     self.replication_setup_receiver = Some(rx);
+                     rt.spawn(async move {
+                         let res = crate::driver_mysql::start_replication(&pool).await;
+                         let _ = tx.send(res);
+                     });
+                     self.query_message = "Starting replication...".to_string();
+                     self.show_message_panel = true;
+                     self.query_message_is_error = false;
+                }
+            } else if context_id >= 50000 {
+                // ID >= 50000 means create folder in folder operation
+                let hash = context_id - 50000;
+                debug!("📁 Create folder operation with hash: {}", hash);
+                self.handle_create_folder_in_folder_request(hash);
+                // Force immediate UI repaint after create folder request
+                ui.ctx().request_repaint();
+            } else if context_id >= 40000 {
+                // ID >= 40000 means move query to folder operation
+                let hash = context_id - 40000;
+                debug!("📦 Move query operation with hash: {}", hash);
+                sidebar_query::handle_query_move_request(self, hash);
+            } else if context_id >= 20000 {
+                // ID >= 20000 means query edit operation
+                let hash = context_id - 20000;
+                debug!("✏️ Query edit operation with hash: {}", hash);
+                sidebar_query::handle_query_edit_request(self, hash);
+            } else if context_id <= -50000 {
+                // ID <= -50000 means remove folder operation
+                let hash = (-context_id) - 50000;
+                debug!("🗑️ Remove folder operation with hash: {}", hash);
+                self.handle_remove_folder_request(hash);
+                // Force immediate UI repaint after folder removal
+                ui.ctx().request_repaint();
+            } else if context_id <= -20000 {
+                // ID <= -20000 means query removal operation
+                let hash = (-context_id) - 20000;
+                debug!("🗑️ Remove query operation with hash: {}", hash);
+                if sidebar_query::handle_query_remove_request_by_hash(self, hash) {
+                    // Force refresh of queries tree if removal was successful
+                    sidebar_query::load_queries_from_directory(self);
 
-                                    let tab_content = match preview_result {
-                                        Ok(pretty_json) => pretty_json,
-                                        Err(error) => serde_json::to_string_pretty(&serde_json::json!({
-                                            "key": table_name,
-                                            "type": k_type,
-                                            "database": keyspace,
-                                            "error": error,
-                                        }))
-                                        .unwrap_or_else(|_| "{\n  \"error\": \"Failed to build Redis preview\"\n}".to_string()),
-                                    };
+                    // Force immediate UI repaint - this is crucial!
+                    ui.ctx().request_repaint();
 
-                                    editor::create_new_tab_with_connection_and_database(
-                                        self,
-                                        tab_title,
-                                        tab_content,
-                                        Some(connection_id),
-                                        database_name.clone(),
-                                    );
+                    // Set needs_refresh flag to ensure UI updates
+                    self.needs_refresh = true;
+                }
+            } else if context_id > 10000 {
+                // ID > 10000 means copy connection (connection_id = context_id - 10000)
+                let connection_id = context_id - 10000;
+                debug!(
+                    "📋 Copy connection operation for connection: {}",
+                    connection_id
+                );
+                sidebar_database::copy_connection(self, connection_id);
 
-                                    if let Some(active_tab) = self.query_tabs.get_mut(self.active_tab_index) {
-                                        active_tab.file_path = Some(crate::driver_redis::fetch_redis_key_preview_filename(&table_name));
-                                        active_tab.query_message = format!("Loaded Redis key '{}' as JSON preview", table_name);
-                                        active_tab.query_message_is_error = false;
+                // Force immediate tree refresh and UI update
+                self.items_tree.clear();
+                sidebar_database::refresh_connections_tree(self);
+                needs_full_refresh = true;
+                ui.ctx().request_repaint();
+
+                // Break early to prevent further processing
+                break;
+            } else if (3000..4000).contains(&context_id) {
+                // ID 3000-3999 means disconnect (connection_id = context_id - 3000)
+                let connection_id = context_id - 3000;
+                debug!("🔌 Disconnect operation for connection: {}", connection_id);
+                self.disconnect_connection(connection_id);
+                // Mark for repaint so status updates immediately
+                ui.ctx().request_repaint();
+            } else if (1000..10000).contains(&context_id) {
+                // ID 1000-9999 means refresh connection (connection_id = context_id - 1000)
+                let connection_id = context_id - 1000;
+                debug!(
+                    "🔄 Refresh connection operation for connection: {}",
+                    connection_id
+                );
+                if !processed_refreshes.contains(&connection_id) {
+                    processed_refreshes.insert(connection_id);
+                    // Only refresh that single connection node without rebuilding the whole tree
+                    self.refresh_connection(connection_id);
+                    // Mark for repaint so spinner state shows immediately
+                    ui.ctx().request_repaint();
+                    // Do NOT trigger full tree rebuild here; preserving folder expansion avoids the
+                    // perception that the connection disappeared after refresh.
+                }
+            } else if context_id > 0 {
+                // Positive ID means edit connection
+                sidebar_database::start_edit_connection(self, context_id);
+            } else {
+                // Negative ID means remove connection
+                let connection_id = -context_id;
+                if !processed_removals.contains(&connection_id) {
+                    processed_removals.insert(connection_id);
+                    connection::remove_connection(self, connection_id);
+
+                    // No need for full tree refresh - remove_connection already does incremental update
+                    needs_full_refresh = true;
+                    ui.ctx().request_repaint();
+
+                    // Break early to prevent further processing
+                    break;
+                }
+            }
+        }
+
+        // Force complete UI refresh after any removal
+        if needs_full_refresh {
+            // Completely clear and rebuild the tree
+            self.items_tree.clear();
+            sidebar_database::refresh_connections_tree(self);
+            self.needs_refresh = true; // Set flag for next update cycle
+            ui.ctx().request_repaint();
+
+            // Return early to prevent any further processing of the old tree
+            return Vec::new();
+        }
+
+        // Clean up processed folder removal mappings (optional - only if we want to prevent memory buildup)
+        // We could also keep them for potential retry scenarios
+
+        // Handle DnD drop: move connection to target folder
+        let dnd_drop: Option<(i64, String)> = ui
+            .ctx()
+            .data(|d| d.get_temp(egui::Id::new("conn_dnd_drop")));
+        if let Some((drag_conn_id, target_folder)) = dnd_drop {
+            log::warn!("[DnD] EXECUTING DROP conn_id={} -> folder='{}'", drag_conn_id, target_folder);
+            ui.ctx().data_mut(|d| {
+                d.remove_temp::<(i64, String)>(egui::Id::new("conn_dnd_drop"));
+                d.remove_temp::<i64>(egui::Id::new("conn_dnd_source"));
+            });
+            if let Some(conn) = self
+                .connections
+                .iter_mut()
+                .find(|c| c.id == Some(drag_conn_id))
+            {
+                conn.folder = Some(target_folder);
+                let conn_clone = conn.clone();
+                sidebar_database::update_connection_in_database(self, &conn_clone);
+                sidebar_database::refresh_connections_tree(self);
+                self.needs_refresh = true;
+                ui.ctx().request_repaint();
+            }
+        }
+
+        // Handle "Create Subfolder" context menu request
+        let subfolder_req: Option<String> = ui
+            .ctx()
+            .data(|d| d.get_temp(egui::Id::new("conn_subfolder_req")));
+        if let Some(parent_path) = subfolder_req {
+            ui.ctx().data_mut(|d| {
+                d.remove_temp::<String>(egui::Id::new("conn_subfolder_req"));
+            });
+            self.subfolder_parent_path = parent_path;
+            self.new_subfolder_name.clear();
+            self.show_create_subfolder_dialog = true;
+        }
+
+        // Handle "Add Connection Here" context menu request
+        let add_to_folder: Option<String> = ui
+            .ctx()
+            .data(|d| d.get_temp(egui::Id::new("conn_add_to_folder")));
+        if let Some(folder_path) = add_to_folder {
+            ui.ctx().data_mut(|d| {
+                d.remove_temp::<String>(egui::Id::new("conn_add_to_folder"));
+            });
+            self.new_connection.folder = Some(folder_path);
+            self.show_add_connection = true;
+        }
+
+        // Handle "Delete Folder" context menu request
+        let delete_folder: Option<String> = ui
+            .ctx()
+            .data(|d| d.get_temp(egui::Id::new("conn_delete_folder")));
+        if let Some(folder_path) = delete_folder {
+            ui.ctx().data_mut(|d| {
+                d.remove_temp::<String>(egui::Id::new("conn_delete_folder"));
+            });
+            sidebar_database::delete_connection_folder(self, &folder_path);
+        }
+
+        // Handle "Call…" on a procedure / function node
+        let routine_call: Option<(i64, Option<String>, String, bool)> = ui
+            .ctx()
+            .data(|d| d.get_temp(egui::Id::new("routine_call_req")));
+        if let Some((conn_id, db_name, routine_name, is_function)) = routine_call {
+            ui.ctx().data_mut(|d| {
+                d.remove_temp::<(i64, Option<String>, String, bool)>(egui::Id::new(
+                    "routine_call_req",
+                ));
+            });
+            let kind = if is_function {
+                models::structs::RoutineKind::Function
+            } else {
+                models::structs::RoutineKind::Procedure
+            };
+            connection::open_routine_call(self, conn_id, db_name, routine_name, kind);
+        }
+
+        // Return query files that were clicked
+        results
+    }
+
+    /// Open the tables/views clicked in a tree (or picked elsewhere, e.g. the
+    /// table palette) in a tab, reusing an existing tab for the same target.
+    pub(crate) fn open_table_click_requests(
+        &mut self,
+        nodes: &[models::structs::TreeNode],
+        table_click_requests: Vec<(i64, String, models::enums::NodeType, Option<String>)>,
+        is_search_mode: bool,
+    ) {
+        for (connection_id, table_name, node_type, predefined_db_name) in table_click_requests {
+            // Find the connection to determine the database type and database name
+            let connection = self
+                .connections
+                .iter()
+                .find(|conn| conn.id == Some(connection_id))
+                .cloned();
+
+            if let Some(conn) = connection {
+                let is_view = node_type == models::enums::NodeType::View;
+                // Find the database name from the tree structure
+                let mut database_name: Option<String> = predefined_db_name;
+
+                // Optimization: Only search if not provided (should be provided for most table clicks)
+                if database_name.is_none() {
+                    for node in nodes.iter() {
+                        if let Some(db_name) =
+                            Tabular::find_database_name_for_table(node, connection_id, &table_name)
+                        {
+                            database_name = Some(db_name);
+                            break;
+                        }
+                    }
+                }
+
+                // If no database found in tree, use connection default
+                if database_name.is_none() {
+                    database_name = Some(conn.database.clone());
+                }
+
+                // Clicking a search result also reveals the object in the main tree
+                if is_search_mode && matches!(node_type, models::enums::NodeType::Table | models::enums::NodeType::View) {
+                    self.reveal_object_in_tree(connection_id, database_name.as_deref(), &table_name, &node_type, None);
+                }
+                if matches!(node_type, models::enums::NodeType::Table | models::enums::NodeType::View)
+                    && let Some(db_name) = &database_name
+                {
+                    crate::cache_data::record_table_access(self, connection_id, db_name, &table_name);
+                }
+
+                match conn.connection_type {
+                    models::enums::DatabaseType::Redis => {
+                        // Redis objects never carry ALTER view DDL
+                        self.current_object_ddl = None;
+                        // Check if this is a Redis key (has specific Redis data types in the tree structure)
+                        // For Redis keys, we need to find which database they belong to
+                        let mut is_redis_key = false;
+                        let mut key_type: Option<String> = None;
+
+                        for node in nodes.iter() {
+                            if let Some((_, k_type)) =
+                                Tabular::find_redis_key_info(node, &table_name)
+                            {
+                                key_type = Some(k_type.clone());
+                                is_redis_key = true;
+                                break;
+                            }
+                        }
+
+                        if is_redis_key {
+                            if let Some(k_type) = key_type {
+                                let keyspace = database_name.clone().unwrap_or_default();
+                                let tab_title = format!("Redis Key: {} ({})", table_name, k_type);
+
+                                if let Some(existing_index) = editor::find_tab_for_target(
+                                    self,
+                                    &tab_title,
+                                    connection_id,
+                                    database_name.as_deref(),
+                                ) {
+                                    if existing_index != self.active_tab_index {
+                                        editor::switch_to_tab(self, existing_index);
+                                    }
+                                } else {
+                                    let preview_result = crate::driver_redis::fetch_redis_key_pretty_json(
+                                        self,
+                                        connection_id,
+                                        &keyspace,
+                                        &table_name,
+                                        &k_type,
+                                    );
+
+                                    let tab_content = match preview_result {
+                                        Ok(pretty_json) => pretty_json,
+                                        Err(error) => serde_json::to_string_pretty(&serde_json::json!({
+                                            "key": table_name,
+                                            "type": k_type,
+                                            "database": keyspace,
+                                            "error": error,
+                                        }))
+                                        .unwrap_or_else(|_| "{\n  \"error\": \"Failed to build Redis preview\"\n}".to_string()),
+                                    };
+
+                                    editor::create_new_tab_with_connection_and_database(
+                                        self,
+                                        tab_title,
+                                        tab_content,
+                                        Some(connection_id),
+                                        database_name.clone(),
+                                    );
+
+                                    if let Some(active_tab) = self.query_tabs.get_mut(self.active_tab_index) {
+                                        active_tab.file_path = Some(crate::driver_redis::fetch_redis_key_preview_filename(&table_name));
+                                        active_tab.query_message = format!("Loaded Redis key '{}' as JSON preview", table_name);
+                                        active_tab.query_message_is_error = false;
                                     }
 
                                     self.current_connection_id = Some(connection_id);
@@ -1260,8 +1614,16 @@ impl super::Tabular {
                                         table_name.clone(),
                                     )
                                 }
-                                models::enums::DatabaseType::SQLite
-                                | models::enums::DatabaseType::Redis => {
+                                models::enums::DatabaseType::SQLite => {
+                                    format!(
+                                        "SELECT * FROM {} LIMIT 100;",
+                                        crate::driver_sqlite::qualified_table_name(
+                                            Some(db_name),
+                                            &table_name
+                                        )
+                                    )
+                                }
+                                models::enums::DatabaseType::Redis => {
                                     format!("SELECT * FROM `{}` LIMIT 100;", table_name)
                                 }
                                 models::enums::DatabaseType::MongoDB
@@ -1398,8 +1760,16 @@ impl super::Tabular {
                                         // Remove the LIMIT part from MsSQL query
                                         mssql_query.replace("SELECT TOP 100", "SELECT")
                                     }
-                                    models::enums::DatabaseType::SQLite
-                                    | models::enums::DatabaseType::Redis => {
+                                    models::enums::DatabaseType::SQLite => {
+                                        format!(
+                                            "SELECT * FROM {}",
+                                            crate::driver_sqlite::qualified_table_name(
+                                                Some(db_name),
+                                                &table_name
+                                            )
+                                        )
+                                    }
+                                    models::enums::DatabaseType::Redis => {
                                         format!("SELECT * FROM `{}`", table_name)
                                     }
                                     models::enums::DatabaseType::MongoDB
@@ -1435,6 +1805,7 @@ impl super::Tabular {
                                 // Keep browse mode enabled for filters to apply on cached data
                                 self.is_table_browse_mode = true;
                                 self.sql_filter_text.clear();
+                                self.filter_chips.clear();
                                 // New table opened; structure target should refresh on demand
                                 self.last_structure_target = None;
                             } else {
@@ -1465,6 +1836,7 @@ impl super::Tabular {
                                     self.pool_wait_in_progress = true;
                                     self.pool_wait_connection_id = Some(connection_id);
                                     self.pool_wait_query = first_query;
+                                    self.pool_wait_params.clear();
                                     self.pool_wait_started_at = Some(std::time::Instant::now());
                                     self.current_table_name =
                                         "Connecting… waiting for pool".to_string();
@@ -1500,435 +1872,136 @@ impl super::Tabular {
                                     };
                                 debug!("🔄 Client-side query after sanitization: {}", safe_query);
 
-                                // If pool not ready, queue and show loading; otherwise execute now.
-                                // Only check the cache here — never block the UI thread waiting for
-                                // a pool to be created; the pool-wait poller in app_impl.rs handles
-                                // running the query as soon as the pool becomes available.
-                                let pool_ready = self.connection_pools.contains_key(&connection_id)
-                                    || self
-                                        .shared_connection_pools
-                                        .lock()
-                                        .map(|p| p.contains_key(&connection_id))
-                                        .unwrap_or(false);
-
-                                if !pool_ready {
-                                    crate::connection::ensure_background_pool_creation(
-                                        self,
-                                        connection_id,
-                                    );
-                                    self.pool_wait_in_progress = true;
-                                    self.pool_wait_connection_id = Some(connection_id);
-                                    self.pool_wait_query = safe_query;
-                                    self.pool_wait_started_at = Some(std::time::Instant::now());
-                                    self.current_table_name =
-                                        "Connecting… waiting for pool".to_string();
-                                } else if let Some((headers, data)) =
-                                    connection::execute_query_with_connection(
-                                        self,
-                                        connection_id,
-                                        safe_query,
-                                    )
-                                {
-                                    self.current_table_headers = headers;
-                                    self.current_table_data = data.clone();
-                                    self.all_table_data = data;
-                                    // current_table_name sudah diset lebih awal
-                                    self.is_table_browse_mode = true; // Enable filter for table browse
-                                    self.sql_filter_text.clear(); // Clear any previous filter
-                                    self.total_rows = self.all_table_data.len();
-                                    self.current_page = 0;
-                                    if let Some(active_tab) =
-                                        self.query_tabs.get_mut(self.active_tab_index)
-                                    {
-                                        active_tab.result_headers =
-                                            self.current_table_headers.clone();
-                                        active_tab.result_rows = self.current_table_data.clone();
-                                        active_tab.result_all_rows = self.all_table_data.clone();
-                                        active_tab.result_table_name =
-                                            self.current_table_name.clone();
-                                        active_tab.is_table_browse_mode = self.is_table_browse_mode;
-                                        active_tab.current_page = self.current_page;
-                                        active_tab.page_size = self.page_size;
-                                        active_tab.total_rows = self.total_rows;
-                                    }
-                                    // Save latest first page into row cache (best-effort)
-                                    if let Some(dbn) = &database_name {
-                                        let snapshot: Vec<Vec<String>> =
-                                            self.all_table_data.iter().take(100).cloned().collect();
-                                        let headers_clone = self.current_table_headers.clone();
-                                        crate::cache_data::save_table_rows_to_cache(
-                                            self,
-                                            connection_id,
-                                            dbn,
-                                            &table_name,
-                                            &headers_clone,
-                                            &snapshot,
-                                        );
-                                        debug!(
-                                            "💾 Cached first 100 rows after live fetch for {}/{}",
-                                            dbn, table_name
-                                        );
-                                    }
-                                }
-                            } else {
-                                debug!(
-                                    "🛑 Skipping client-side live load on table click because cache exists"
-                                );
-                                self.last_structure_target = None;
-                            }
-                        }
-                        }
-                    }
-                };
-
-                if is_view {
-                    let ddl = connection::fetch_view_definition(
-                        &conn,
-                        database_name.as_deref(),
-                        &table_name,
-                    )
-                    .unwrap_or_else(|| {
-                        format!("-- Unable to fetch view definition for {}", table_name)
-                    });
-                    self.current_object_ddl = Some(ddl.clone());
-                    if let Some(active_tab) = self.query_tabs.get_mut(self.active_tab_index) {
-                        active_tab.object_ddl = Some(ddl);
-                    }
-                    self.table_bottom_view = models::structs::TableBottomView::Query;
-                }
-            }
-
-            // FIX: Jika user sedang berada pada view Structure dan berpindah klik ke table lain,
-            // sebelumnya struktur tidak di-refresh sehingga masih menampilkan struktur table lama.
-            // Di sini kita paksa reload struktur untuk table baru.
-            if self.table_bottom_view == models::structs::TableBottomView::Structure {
-                // Load only if target changed
-                if let Some(conn_id) = self.current_connection_id {
-                    let db = self
-                        .query_tabs
-                        .get(self.active_tab_index)
-                        .and_then(|t| t.database_name.clone())
-                        .unwrap_or_default();
-                    let table = data_table::infer_current_table_name(self);
-                    let current_target = (conn_id, db.clone(), table.clone());
-                    if self
-                        .last_structure_target
-                        .as_ref()
-                        .map(|t| t != &current_target)
-                        .unwrap_or(true)
-                    {
-                        data_table::load_structure_info_for_current_table(self);
-                    }
-                } else {
-                    data_table::load_structure_info_for_current_table(self);
-                }
-            } else {
-                // Pastikan struktur lama dibersihkan agar ketika user pindah ke Structure langsung memicu load.
-                self.structure_columns.clear();
-                self.structure_indexes.clear();
-            }
-        }
-
-        // Handle index click requests - open Edit Index dialog
-        for (connection_id, index_name, database_name, table_name) in index_click_requests {
-            if let Some(conn) = self
-                .connections
-                .iter()
-                .find(|c| c.id == Some(connection_id))
-                .cloned()
-            {
-                // Prefill dialog state for Edit
-                if let Some(tn) = table_name.clone() {
-                    self.index_dialog = Some(models::structs::IndexDialogState {
-                        mode: models::structs::IndexDialogMode::Edit,
-                        connection_id,
-                        database_name: database_name.clone(),
-                        table_name: tn,
-                        existing_index_name: Some(index_name.clone()),
-                        index_name: index_name.clone(),
-                        columns: String::new(),
-                        unique: false,
-                        method: None,
-                        db_type: conn.connection_type.clone(),
-                    });
-                    self.show_index_dialog = true;
-                }
-            }
-        }
-
-        // Handle create index requests - open Create Index dialog
-        for (connection_id, database_name, table_name) in create_index_requests {
-            if let Some(conn) = self
-                .connections
-                .iter()
-                .find(|c| c.id == Some(connection_id))
-                .cloned()
-                && let Some(tn) = table_name.clone()
-            {
-                self.index_dialog = Some(models::structs::IndexDialogState {
-                    mode: models::structs::IndexDialogMode::Create,
-                    connection_id,
-                    database_name: database_name.clone(),
-                    table_name: tn.clone(),
-                    existing_index_name: None,
-                    index_name: format!("idx_{}_col", tn),
-                    columns: "columns comma-separated".to_string(),
-                    unique: false,
-                    method: None,
-                    db_type: conn.connection_type.clone(),
-                });
-                self.show_index_dialog = true;
-            }
-        }
-
-        let results = query_files_to_open.clone();
-
-        // Handle context menu requests (deduplicate to avoid multiple calls)
-        let mut processed_removals = std::collections::HashSet::new();
-        let mut processed_refreshes = std::collections::HashSet::new();
-        let mut needs_full_refresh = false;
-
-        for context_id in context_menu_requests {
-            debug!("🔍 Processing context_id: {}", context_id);
-
-            if context_id >= 62000 {
-                // Restart Replication
-                let conn_id = context_id - 62000;
-                let replica_pool_opt = self.connection_pools.get(&conn_id).cloned();
-                let mut master_id_opt = None;
-                if let Some(conn) = self.connections.iter().find(|c| c.id == Some(conn_id)) {
-                    master_id_opt = conn.replication_master_id;
-                }
-                
-                if let (Some(models::enums::DatabasePool::MySQL(replica_pool)), Some(master_id)) = (replica_pool_opt, master_id_opt) {
-                     if let Some(models::enums::DatabasePool::MySQL(master_pool)) = self.connection_pools.get(&master_id).cloned() {
-                         let rt = self.get_runtime();
-                         let (tx, rx) = std::sync::mpsc::channel();
-                         self.replication_setup_receiver = Some(rx);
-                         
-                         rt.spawn(async move {
-                             let res = crate::driver_mysql::restart_replication(&master_pool, &replica_pool).await;
-                             let _ = tx.send(res);
-                         });
-                         
-                         self.query_message = "Restarting replication...".to_string();
-                         self.show_message_panel = true;
-                         self.query_message_is_error = false;
-                     } else {
-                         self.query_message = "Master connection is not active. Please connect to Master first.".to_string();
-                         self.show_message_panel = true;
-                         self.query_message_is_error = true;
-                     }
-                } else {
-                     self.query_message = "Could not identify Master connection or pools not active.".to_string();
-                     self.show_message_panel = true;
-                     self.query_message_is_error = true;
-                }
-            } else if context_id >= 61000 {
-                // Stop Replication
-                let conn_id = context_id - 61000;
-                if let Some(models::enums::DatabasePool::MySQL(pool)) = self.connection_pools.get(&conn_id).cloned() {
-                     let rt = self.get_runtime();
-                     let (tx, rx) = std::sync::mpsc::channel();
-                     self.replication_setup_receiver = Some(rx);
-                     rt.spawn(async move {
-                         let res = crate::driver_mysql::stop_replication(&pool).await;
-                         let _ = tx.send(res);
-                     });
-                     self.query_message = "Stopping replication...".to_string();
-                     self.show_message_panel = true;
-                     self.query_message_is_error = false;
-                }
-            } else if context_id >= 60000 {
-                // Start Replication
-                let conn_id = context_id - 60000;
-                if let Some(models::enums::DatabasePool::MySQL(pool)) = self.connection_pools.get(&conn_id).cloned() {
-                     let rt = self.get_runtime();
-                     let (tx, rx) = std::sync::mpsc::channel();
-                     self.replication_setup_receiver = Some(rx);
-                     rt.spawn(async move {
-                         let res = crate::driver_mysql::start_replication(&pool).await;
-                         let _ = tx.send(res);
-                     });
-                     self.query_message = "Starting replication...".to_string();
-                     self.show_message_panel = true;
-                     self.query_message_is_error = false;
-                }
-            } else if context_id >= 50000 {
-                // ID >= 50000 means create folder in folder operation
-                let hash = context_id - 50000;
-                debug!("📁 Create folder operation with hash: {}", hash);
-                self.handle_create_folder_in_folder_request(hash);
-                // Force immediate UI repaint after create folder request
-                ui.ctx().request_repaint();
-            } else if context_id >= 40000 {
-                // ID >= 40000 means move query to folder operation
-                let hash = context_id - 40000;
-                debug!("📦 Move query operation with hash: {}", hash);
-                sidebar_query::handle_query_move_request(self, hash);
-            } else if context_id >= 20000 {
-                // ID >= 20000 means query edit operation
-                let hash = context_id - 20000;
-                debug!("✏️ Query edit operation with hash: {}", hash);
-                sidebar_query::handle_query_edit_request(self, hash);
-            } else if context_id <= -50000 {
-                // ID <= -50000 means remove folder operation
-                let hash = (-context_id) - 50000;
-                debug!("🗑️ Remove folder operation with hash: {}", hash);
-                self.handle_remove_folder_request(hash);
-                // Force immediate UI repaint after folder removal
-                ui.ctx().request_repaint();
-            } else if context_id <= -20000 {
-                // ID <= -20000 means query removal operation
-                let hash = (-context_id) - 20000;
-                debug!("🗑️ Remove query operation with hash: {}", hash);
-                if sidebar_query::handle_query_remove_request_by_hash(self, hash) {
-                    // Force refresh of queries tree if removal was successful
-                    sidebar_query::load_queries_from_directory(self);
-
-                    // Force immediate UI repaint - this is crucial!
-                    ui.ctx().request_repaint();
-
-                    // Set needs_refresh flag to ensure UI updates
-                    self.needs_refresh = true;
-                }
-            } else if context_id > 10000 {
-                // ID > 10000 means copy connection (connection_id = context_id - 10000)
-                let connection_id = context_id - 10000;
-                debug!(
-                    "📋 Copy connection operation for connection: {}",
-                    connection_id
-                );
-                sidebar_database::copy_connection(self, connection_id);
-
-                // Force immediate tree refresh and UI update
-                self.items_tree.clear();
-                sidebar_database::refresh_connections_tree(self);
-                needs_full_refresh = true;
-                ui.ctx().request_repaint();
-
-                // Break early to prevent further processing
-                break;
-            } else if (3000..4000).contains(&context_id) {
-                // ID 3000-3999 means disconnect (connection_id = context_id - 3000)
-                let connection_id = context_id - 3000;
-                debug!("🔌 Disconnect operation for connection: {}", connection_id);
-                self.disconnect_connection(connection_id);
-                // Mark for repaint so status updates immediately
-                ui.ctx().request_repaint();
-            } else if (1000..10000).contains(&context_id) {
-                // ID 1000-9999 means refresh connection (connection_id = context_id - 1000)
-                let connection_id = context_id - 1000;
-                debug!(
-                    "🔄 Refresh connection operation for connection: {}",
-                    connection_id
-                );
-                if !processed_refreshes.contains(&connection_id) {
-                    processed_refreshes.insert(connection_id);
-                    // Only refresh that single connection node without rebuilding the whole tree
-                    self.refresh_connection(connection_id);
-                    // Mark for repaint so spinner state shows immediately
-                    ui.ctx().request_repaint();
-                    // Do NOT trigger full tree rebuild here; preserving folder expansion avoids the
-                    // perception that the connection disappeared after refresh.
-                }
-            } else if context_id > 0 {
-                // Positive ID means edit connection
-                sidebar_database::start_edit_connection(self, context_id);
-            } else {
-                // Negative ID means remove connection
-                let connection_id = -context_id;
-                if !processed_removals.contains(&connection_id) {
-                    processed_removals.insert(connection_id);
-                    connection::remove_connection(self, connection_id);
-
-                    // No need for full tree refresh - remove_connection already does incremental update
-                    needs_full_refresh = true;
-                    ui.ctx().request_repaint();
+                                // If pool not ready, queue and show loading; otherwise execute now.
+                                // Only check the cache here — never block the UI thread waiting for
+                                // a pool to be created; the pool-wait poller in app_impl.rs handles
+                                // running the query as soon as the pool becomes available.
+                                let pool_ready = self.connection_pools.contains_key(&connection_id)
+                                    || self
+                                        .shared_connection_pools
+                                        .lock()
+                                        .map(|p| p.contains_key(&connection_id))
+                                        .unwrap_or(false);
 
-                    // Break early to prevent further processing
-                    break;
+                                if !pool_ready {
+                                    crate::connection::ensure_background_pool_creation(
+                                        self,
+                                        connection_id,
+                                    );
+                                    self.pool_wait_in_progress = true;
+                                    self.pool_wait_connection_id = Some(connection_id);
+                                    self.pool_wait_query = safe_query;
+                                    self.pool_wait_params.clear();
+                                    self.pool_wait_started_at = Some(std::time::Instant::now());
+                                    self.current_table_name =
+                                        "Connecting… waiting for pool".to_string();
+                                } else if let Some((headers, data)) =
+                                    connection::execute_query_with_connection(
+                                        self,
+                                        connection_id,
+                                        safe_query,
+                                    )
+                                {
+                                    self.current_table_headers = headers;
+                                    self.current_table_data = data.clone();
+                                    self.all_table_data = data;
+                                    // current_table_name sudah diset lebih awal
+                                    self.is_table_browse_mode = true; // Enable filter for table browse
+                                    self.sql_filter_text.clear(); // Clear any previous filter
+                                    self.filter_chips.clear();
+                                    self.total_rows = self.all_table_data.len();
+                                    self.current_page = 0;
+                                    if let Some(active_tab) =
+                                        self.query_tabs.get_mut(self.active_tab_index)
+                                    {
+                                        active_tab.result_headers =
+                                            self.current_table_headers.clone();
+                                        active_tab.result_rows = self.current_table_data.clone();
+                                        active_tab.result_all_rows = self.all_table_data.clone();
+                                        active_tab.result_table_name =
+                                            self.current_table_name.clone();
+                                        active_tab.is_table_browse_mode = self.is_table_browse_mode;
+                                        active_tab.current_page = self.current_page;
+                                        active_tab.page_size = self.page_size;
+                                        active_tab.total_rows = self.total_rows;
+                                    }
+                                    // Save latest first page into row cache (best-effort)
+                                    if let Some(dbn) = &database_name {
+                                        let snapshot: Vec<Vec<String>> =
+                                            self.all_table_data.iter().take(100).cloned().collect();
+                                        let headers_clone = self.current_table_headers.clone();
+                                        crate::cache_data::save_table_rows_to_cache(
+                                            self,
+                                            connection_id,
+                                            dbn,
+                                            &table_name,
+                                            &headers_clone,
+                                            &snapshot,
+                                        );
+                                        debug!(
+                                            "💾 Cached first 100 rows after live fetch for {}/{}",
+                                            dbn, table_name
+                                        );
+                                    }
+                                }
+                            } else {
+                                debug!(
+                                    "🛑 Skipping client-side live load on table click because cache exists"
+                                );
+                                self.last_structure_target = None;
+                            }
+                        }
+                        }
+                    }
+                };
+
+                if is_view {
+                    let ddl = connection::fetch_view_definition(
+                        &conn,
+                        database_name.as_deref(),
+                        &table_name,
+                    )
+                    .unwrap_or_else(|| {
+                        format!("-- Unable to fetch view definition for {}", table_name)
+                    });
+                    self.current_object_ddl = Some(ddl.clone());
+                    if let Some(active_tab) = self.query_tabs.get_mut(self.active_tab_index) {
+                        active_tab.object_ddl = Some(ddl);
+                    }
+                    self.table_bottom_view = models::structs::TableBottomView::Query;
                 }
             }
-        }
-
-        // Force complete UI refresh after any removal
-        if needs_full_refresh {
-            // Completely clear and rebuild the tree
-            self.items_tree.clear();
-            sidebar_database::refresh_connections_tree(self);
-            self.needs_refresh = true; // Set flag for next update cycle
-            ui.ctx().request_repaint();
-
-            // Return early to prevent any further processing of the old tree
-            return Vec::new();
-        }
 
-        // Clean up processed folder removal mappings (optional - only if we want to prevent memory buildup)
-        // We could also keep them for potential retry scenarios
-
-        // Handle DnD drop: move connection to target folder
-        let dnd_drop: Option<(i64, String)> = ui
-            .ctx()
-            .data(|d| d.get_temp(egui::Id::new("conn_dnd_drop")));
-        if let Some((drag_conn_id, target_folder)) = dnd_drop {
-            log::warn!("[DnD] EXECUTING DROP conn_id={} -> folder='{}'", drag_conn_id, target_folder);
-            ui.ctx().data_mut(|d| {
-                d.remove_temp::<(i64, String)>(egui::Id::new("conn_dnd_drop"));
-                d.remove_temp::<i64>(egui::Id::new("conn_dnd_source"));
-            });
-            if let Some(conn) = self
-                .connections
-                .iter_mut()
-                .find(|c| c.id == Some(drag_conn_id))
-            {
-                conn.folder = Some(target_folder);
-                let conn_clone = conn.clone();
-                sidebar_database::update_connection_in_database(self, &conn_clone);
-                sidebar_database::refresh_connections_tree(self);
-                self.needs_refresh = true;
-                ui.ctx().request_repaint();
+            // FIX: Jika user sedang berada pada view Structure dan berpindah klik ke table lain,
+            // sebelumnya struktur tidak di-refresh sehingga masih menampilkan struktur table lama.
+            // Di sini kita paksa reload struktur untuk table baru.
+            if self.table_bottom_view == models::structs::TableBottomView::Structure {
+                // Load only if target changed
+                if let Some(conn_id) = self.current_connection_id {
+                    let db = self
+                        .query_tabs
+                        .get(self.active_tab_index)
+                        .and_then(|t| t.database_name.clone())
+                        .unwrap_or_default();
+                    let table = data_table::infer_current_table_name(self);
+                    let current_target = (conn_id, db.clone(), table.clone());
+                    if self
+                        .last_structure_target
+                        .as_ref()
+                        .map(|t| t != &current_target)
+                        .unwrap_or(true)
+                    {
+                        data_table::load_structure_info_for_current_table(self);
+                    }
+                } else {
+                    data_table::load_structure_info_for_current_table(self);
+                }
+            } else {
+                // Pastikan struktur lama dibersihkan agar ketika user pindah ke Structure langsung memicu load.
+                self.structure_columns.clear();
+                self.structure_indexes.clear();
             }
         }
-
-        // Handle "Create Subfolder" context menu request
-        let subfolder_req: Option<String> = ui
-            .ctx()
-            .data(|d| d.get_temp(egui::Id::new("conn_subfolder_req")));
-        if let Some(parent_path) = subfolder_req {
-            ui.ctx().data_mut(|d| {
-                d.remove_temp::<String>(egui::Id::new("conn_subfolder_req"));
-            });
-            self.subfolder_parent_path = parent_path;
-            self.new_subfolder_name.clear();
-            self.show_create_subfolder_dialog = true;
-        }
-
-        // Handle "Add Connection Here" context menu request
-        let add_to_folder: Option<String> = ui
-            .ctx()
-            .data(|d| d.get_temp(egui::Id::new("conn_add_to_folder")));
-        if let Some(folder_path) = add_to_folder {
-            ui.ctx().data_mut(|d| {
-                d.remove_temp::<String>(egui::Id::new("conn_add_to_folder"));
-            });
-            self.new_connection.folder = Some(folder_path);
-            self.show_add_connection = true;
-        }
-
-        // Handle "Delete Folder" context menu request
-        let delete_folder: Option<String> = ui
-            .ctx()
-            .data(|d| d.get_temp(egui::Id::new("conn_delete_folder")));
-        if let Some(folder_path) = delete_folder {
-            ui.ctx().data_mut(|d| {
-                d.remove_temp::<String>(egui::Id::new("conn_delete_folder"));
-            });
-            sidebar_database::delete_connection_folder(self, &folder_path);
-        }
-
-        // Return query files that were clicked
-        results
     }
     pub(crate) fn render_tree_node_with_table_expansion(
         ui: &mut egui::Ui,
@@ -2170,7 +2243,13 @@ impl super::Tabular {
                     if let Some(conn_id) = node.connection_id {
                         // Show refreshing spinner
                         if params.refreshing_connections.contains(&conn_id) {
-                            name_text.push_str(" ⏳ Syncing…");
+                            match params.refresh_progress.get(&conn_id) {
+                                Some(progress) => {
+                                    name_text.push_str(" ⏳ ");
+                                    name_text.push_str(&progress.label());
+                                }
+                                None => name_text.push_str(" ⏳ Syncing…"),
+                            }
                         }
                         // Show prefetch progress
                         if let Some((completed, total)) = params.prefetch_progress.get(&conn_id) {
@@ -2202,6 +2281,7 @@ impl super::Tabular {
                             .sense(egui::Sense::click()),
                     )
                 };
+                super::search::scroll_to_revealed(ui, node, &response);
 
                 // Tooltip for connection status
                 if node.node_type == models::enums::NodeType::Connection && !status_text.is_empty() {
@@ -2222,6 +2302,20 @@ impl super::Tabular {
                     response = response.on_hover_text(tip);
                 }
 
+                // Tooltip with row-count / size estimates, fetched on first hover
+                if node.node_type == models::enums::NodeType::Table
+                    && let Some(conn_id) = node.connection_id
+                {
+                    let key = (
+                        conn_id,
+                        node.database_name.clone().unwrap_or_default(),
+                        node.table_name.clone().unwrap_or_else(|| node.name.clone()),
+                    );
+                    response = response.on_hover_ui(|ui| {
+                        connection::render_table_stats_tooltip(ui, params.table_stats, key);
+                    });
+                }
+
                 // Drag source: Connection nodes can be dragged to a folder.
                 if node.node_type == models::enums::NodeType::Connection
                     && let Some(conn_id) = node.connection_id {
@@ -3001,7 +3095,10 @@ impl super::Tabular {
                                 is_search_mode: params.is_search_mode,
                                 connection_types: params.connection_types,
                                 prefetch_progress: params.prefetch_progress,
+                                refresh_progress: params.refresh_progress,
                                 db_icon_textures: params.db_icon_textures,
+                                table_stats: params.table_stats,
+                                saved_query_meta: params.saved_query_meta,
                             },
                         );
                         if let Some(child_expansion) = child_expansion_request {
@@ -3123,7 +3220,10 @@ impl super::Tabular {
                                     is_search_mode: params.is_search_mode,
                                     connection_types: params.connection_types,
                                     prefetch_progress: params.prefetch_progress,
+                                    refresh_progress: params.refresh_progress,
                                     db_icon_textures: params.db_icon_textures,
+                                    table_stats: params.table_stats,
+                                    saved_query_meta: params.saved_query_meta,
                                 },
                             );
 
@@ -3255,22 +3355,34 @@ impl super::Tabular {
                 );
 
                 // Add tooltip with the full query if available
-                if let Some(data) = &node.file_path {
-                    if let Some((connection_name, original_query)) = data.split_once("||") {
-                        button_response.on_hover_text_at_pointer(format!(
-                            "Connection: {}\nFull query:\n{}",
-                            connection_name, original_query
-                        ))
-                    } else {
-                        button_response.on_hover_text_at_pointer(format!("Full query:\n{}", data))
-                    }
+                if let Some(item) = node
+                    .file_path
+                    .as_deref()
+                    .and_then(crate::sidebar_history::decode_history_node)
+                {
+                    let database = item
+                        .database_name
+                        .as_deref()
+                        .map(|db| format!(" / {}", db))
+                        .unwrap_or_default();
+                    button_response.on_hover_text_at_pointer(format!(
+                        "Connection: {}{}\nFull query:\n{}",
+                        item.connection_name, database, item.query
+                    ))
                 } else {
                     button_response
                 }
             } else {
+                let saved_meta = if node.node_type == models::enums::NodeType::Query {
+                    node.file_path
+                        .as_ref()
+                        .and_then(|p| params.saved_query_meta.get(p))
+                } else {
+                    None
+                };
                 // For all other node types, use horizontal layout with icons.
                 // Add a spacer equal to the triangle width so leaf rows align with expandable rows (left-aligned look).
-                ui.horizontal(|ui| {
+                let response = ui.horizontal(|ui| {
                     // Reserve space equal to triangle toggle width (16px) for alignment
                     let _sp = ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
 
@@ -3318,7 +3430,10 @@ impl super::Tabular {
                         _ => "🧾",
                     };
 
-                    let label_text = format!("{} {}", icon, node.name);
+                    let label_text = match saved_meta.filter(|m| !m.name.is_empty()) {
+                        Some(meta) => format!("{} {}", icon, meta.name),
+                        None => format!("{} {}", icon, node.name),
+                    };
                     // Use left-aligned label without forcing a full-row size to avoid centered look.
                     ui.add(
                         egui::Label::new(label_text)
@@ -3326,8 +3441,22 @@ impl super::Tabular {
                             .sense(egui::Sense::click()),
                     )
                 })
-                .inner
+                .inner;
+                match saved_meta {
+                    Some(meta) => {
+                        let mut tip = node.name.clone();
+                        if !meta.description.is_empty() {
+                            tip.push_str(&format!("\n\n{}", meta.description));
+                        }
+                        if !meta.tags.is_empty() {
+                            tip.push_str(&format!("\n\nTags: {}", meta.tags.join(", ")));
+                        }
+                        response.on_hover_text(tip)
+                    }
+                    None => response,
+                }
             };
+            super::search::scroll_to_revealed(ui, node, &response);
 
             // DBA quick views + custom views act as actions, so require an explicit
             // double-click (like Table/View) instead of firing on the first click.
@@ -3421,45 +3550,17 @@ impl super::Tabular {
                         debug!("🖱️ QueryHistItem clicked: {}", node.name);
                         // For history items, create a new tab with the original query
                         if let Some(data) = &node.file_path {
-                            // Parse connection name, timestamp, and query from the stored data
-                            // Format: "connection_name||executed_at||original_query"
-                            if let Some((_connection_name, rest)) = data.split_once("||") {
-                                let (executed_at, original_query) = rest
-                                    .split_once("||")
-                                    .unwrap_or(("", rest));
-                                // Build compact tab title: Hist-YYMMDD HH:MM:SS
-                                let tab_title = {
-                                    // executed_at is e.g. "2026-03-11 11:45:56" or "2026-03-11T11:45:56"
-                                    let ts = executed_at.trim();
-                                    let yy = ts.get(2..4).unwrap_or("");
-                                    let mm = ts.get(5..7).unwrap_or("");
-                                    let dd = ts.get(8..10).unwrap_or("");
-                                    let time_part = ts.get(11..19)
-                                        .or_else(|| ts.get(11..))
-                                        .unwrap_or("");
-                                    if !yy.is_empty() && !time_part.is_empty() {
-                                        format!("Hist-{}{}{} {}", yy, mm, dd, time_part)
-                                    } else {
-                                        "Hist".to_string()
-                                    }
-                                };
+                            if let Some(item) = crate::sidebar_history::decode_history_node(data) {
+                                let tab_title =
+                                    crate::sidebar_history::history_tab_title(&item.executed_at);
                                 // Collect to be handled by parent (render_tree) -> will create a NEW TAB
                                 debug!(
                                     "📝 Setting query_file_to_open (history): title='{}', query_len={}",
                                     tab_title,
-                                    original_query.len()
+                                    item.query.len()
                                 );
-                                // Pass the original data in the 3rd field so caller can bind connection
-                                query_file_to_open =
-                                    Some((tab_title, original_query.to_string(), data.clone()));
-                            } else {
-                                debug!("📝 Using fallback format for old history item");
-                                // Fallback for old format without connection name
-                                query_file_to_open = Some((
-                                    "Hist".to_string(),
-                                    data.clone(),
-                                    String::new(),
-                                ));
+                                // Pass the encoded entry in the 3rd field so caller can bind connection + database
+                                query_file_to_open = Some((tab_title, item.query, data.clone()));
                             }
                         } else {
                             debug!("❌ No file_path data for history item");
@@ -3477,6 +3578,26 @@ impl super::Tabular {
             }
 
 
+            // Add context menu for procedure / function nodes
+            if matches!(
+                node.node_type,
+                models::enums::NodeType::StoredProcedure | models::enums::NodeType::UserFunction
+            ) && let Some(conn_id) = node.connection_id
+            {
+                response.context_menu(|ui| {
+                    if ui.button("▶ Call…").clicked() {
+                        let is_function = node.node_type == models::enums::NodeType::UserFunction;
+                        ui.ctx().data_mut(|d| {
+                            d.insert_temp(
+                                egui::Id::new("routine_call_req"),
+                                (conn_id, node.database_name.clone(), node.name.clone(), is_function),
+                            );
+                        });
+                        ui.close();
+                    }
+                });
+            }
+
             // Add context menu for query nodes
             if node.node_type == models::enums::NodeType::Query {
                 response.context_menu(|ui| {
@@ -3514,40 +3635,34 @@ impl super::Tabular {
             // Add context menu for history items
             if node.node_type == models::enums::NodeType::QueryHistItem {
                 response.context_menu(|ui| {
+                    let item = node
+                        .file_path
+                        .as_deref()
+                        .and_then(crate::sidebar_history::decode_history_node);
                     if ui.button("📋 Copy Query").clicked() {
-                        if let Some(data) = &node.file_path {
-                            if let Some((_connection_name, original_query)) = data.split_once("||")
-                            {
-                                ui.ctx().copy_text(original_query.to_string());
-                            } else {
-                                ui.ctx().copy_text(data.clone());
-                            }
+                        if let Some(item) = &item {
+                            ui.ctx().copy_text(item.query.clone());
                         }
                         ui.close();
                     }
 
-                    if ui.button("▶️ Execute Query").clicked() {
+                    if ui
+                        .button("▶️ Execute Query")
+                        .on_hover_text("Run in a new tab on the connection and database it ran in")
+                        .clicked()
+                    {
                         if let Some(data) = &node.file_path {
-                            if let Some((_connection_name, original_query)) = data.split_once("||")
-                            {
-                                editor.set_text(original_query.to_string());
-                            } else {
-                                editor.set_text(data.clone());
-                            }
-                            editor.mark_text_modified();
-                            // This will trigger the execution flow when the context menu closes
+                            // Picked up by the sidebar after the tree is drawn
+                            ui.ctx().data_mut(|d| {
+                                d.insert_temp(egui::Id::new("history_run_request"), data.clone())
+                            });
                         }
                         ui.close();
                     }
 
                     if ui.button("🔁 Auto Refresh Execute").clicked() {
-                        if let Some(data) = &node.file_path {
-                            if let Some((_connection_name, original_query)) = data.split_once("||")
-                            {
-                                editor.set_text(original_query.to_string());
-                            } else {
-                                editor.set_text(data.clone());
-                            }
+                        if let Some(item) = &item {
+                            editor.set_text(item.query.clone());
                             editor.mark_text_modified();
                             // Store query + connection for auto refresh; central UI will read these fields
                             if let Some(conn_id) = node.connection_id {
@@ -0,0 +1,302 @@
+//! "Go to table" palette: fuzzy-find a cached table or view of the active
+//! connection (or of every connection) and open it as if it was clicked in the
+//! sidebar. Candidates come from the table cache; tables opened before rank
+//! first, most recent on top.
+
+use eframe::egui;
+
+use crate::editor_autocomplete_new::fuzzy_match;
+use crate::{cache_data, models, window_egui};
+
+/// Rows listed at once; typing narrows the rest down.
+const MAX_SHOWN: usize = 200;
+
+/// Bonus for tables opened before. Lifts them over other scattered matches
+/// while a name that starts with the query still wins.
+const RECENT_BONUS: i32 = 200;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableCandidate {
+    pub connection_id: i64,
+    pub connection_name: String,
+    pub database: String,
+    pub table: String,
+    pub is_view: bool,
+    /// Unix seconds of the last time the table was opened.
+    pub last_opened: Option<i64>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TablePalette {
+    pub open: bool,
+    pub input: String,
+    pub selected_index: usize,
+    /// Search every connection instead of the active one.
+    pub all_connections: bool,
+    pub candidates: Vec<TableCandidate>,
+}
+
+/// Indices of `candidates` matching `query`, best first. An empty query lists
+/// the recently opened tables first. `db.table` queries match the database too.
+fn rank_candidates(candidates: &[TableCandidate], query: &str) -> Vec<usize> {
+    let query = query.trim();
+    let mut ranked: Vec<(i32, usize)> = candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| {
+            let score = if query.contains('.') {
+                fuzzy_match(query, &format!("{}.{}", c.database, c.table))
+            } else {
+                fuzzy_match(query, &c.table)
+            }?;
+            let bonus = if c.last_opened.is_some() {
+                RECENT_BONUS
+            } else {
+                0
+            };
+            Some((score + bonus, i))
+        })
+        .collect();
+    ranked.sort_by(|(score_a, a), (score_b, b)| {
+        let (a, b) = (&candidates[*a], &candidates[*b]);
+        score_b
+            .cmp(score_a)
+            .then_with(|| b.last_opened.cmp(&a.last_opened))
+            .then_with(|| a.table.len().cmp(&b.table.len()))
+            .then_with(|| a.table.cmp(&b.table))
+            .then_with(|| a.database.cmp(&b.database))
+    });
+    ranked.into_iter().map(|(_, i)| i).collect()
+}
+
+/// Connection of the active tab, else the one last used.
+fn active_connection_id(tabular: &window_egui::Tabular) -> Option<i64> {
+    tabular
+        .query_tabs
+        .get(tabular.active_tab_index)
+        .and_then(|t| t.connection_id)
+        .or(tabular.current_connection_id)
+}
+
+fn load_candidates(tabular: &mut window_egui::Tabular) {
+    let scope = if tabular.table_palette.all_connections {
+        None
+    } else {
+        active_connection_id(tabular)
+    };
+    tabular.table_palette.candidates = cache_data::get_table_palette_candidates(tabular, scope)
+        .into_iter()
+        .map(
+            |(connection_id, database, table, table_type, last_opened)| TableCandidate {
+                connection_name: tabular
+                    .get_connection_name(connection_id)
+                    .unwrap_or_else(|| format!("Connection {}", connection_id)),
+                connection_id,
+                database,
+                table,
+                is_view: table_type == "view",
+                last_opened,
+            },
+        )
+        .collect();
+}
+
+pub(crate) fn open_table_palette(tabular: &mut window_egui::Tabular) {
+    tabular.show_command_palette = false;
+    tabular.table_palette.open = true;
+    tabular.table_palette.input.clear();
+    tabular.table_palette.selected_index = 0;
+    if active_connection_id(tabular).is_none() {
+        tabular.table_palette.all_connections = true;
+    }
+    load_candidates(tabular);
+}
+
+pub(crate) fn close_table_palette(tabular: &mut window_egui::Tabular) {
+    tabular.table_palette.open = false;
+    tabular.table_palette.input.clear();
+    tabular.table_palette.selected_index = 0;
+    tabular.table_palette.candidates.clear();
+}
+
+pub(crate) fn navigate_table_palette(tabular: &mut window_egui::Tabular, direction: i32) {
+    let palette = &mut tabular.table_palette;
+    let count = rank_candidates(&palette.candidates, &palette.input)
+        .len()
+        .min(MAX_SHOWN);
+    if count == 0 {
+        return;
+    }
+    palette.selected_index = if direction > 0 {
+        (palette.selected_index + 1) % count
+    } else {
+        (palette.selected_index + count - 1) % count
+    };
+}
+
+/// Open the selected candidate the way a sidebar click would.
+pub(crate) fn open_selected_table(tabular: &mut window_egui::Tabular) {
+    let palette = &tabular.table_palette;
+    let Some(candidate) = rank_candidates(&palette.candidates, &palette.input)
+        .get(palette.selected_index)
+        .and_then(|&i| palette.candidates.get(i))
+        .cloned()
+    else {
+        return;
+    };
+    close_table_palette(tabular);
+    let node_type = if candidate.is_view {
+        models::enums::NodeType::View
+    } else {
+        models::enums::NodeType::Table
+    };
+    tabular.open_table_click_requests(
+        &[],
+        vec![(
+            candidate.connection_id,
+            candidate.table,
+            node_type,
+            Some(candidate.database),
+        )],
+        false,
+    );
+}
+
+pub(crate) fn render_table_palette(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    let mut open_index: Option<usize> = None;
+    egui::Area::new(egui::Id::new("table_palette"))
+        .fixed_pos(egui::pos2(
+            ctx.content_rect().center().x - 300.0,
+            ctx.content_rect().center().y - 200.0,
+        ))
+        .show(ctx, |ui| {
+            egui::Frame::default()
+                .fill(ui.style().visuals.window_fill)
+                .stroke(ui.style().visuals.window_stroke)
+                .shadow(egui::epaint::Shadow::default())
+                .inner_margin(egui::Margin::same(10))
+                .show(ui, |ui| {
+                    let response = ui.add_sized(
+                        [580.0, 25.0],
+                        egui::TextEdit::singleline(&mut tabular.table_palette.input)
+                            .hint_text("Go to table… (db.table narrows the database)"),
+                    );
+                    if response.changed() {
+                        tabular.table_palette.selected_index = 0;
+                    }
+                    if tabular.table_palette.input.is_empty() {
+                        response.request_focus();
+                    }
+                    let can_scope = active_connection_id(tabular).is_some();
+                    if ui
+                        .add_enabled(
+                            can_scope,
+                            egui::Checkbox::new(
+                                &mut tabular.table_palette.all_connections,
+                                "All connections",
+                            ),
+                        )
+                        .changed()
+                    {
+                        tabular.table_palette.selected_index = 0;
+                        load_candidates(tabular);
+                        response.request_focus();
+                    }
+                    ui.separator();
+
+                    let palette = &tabular.table_palette;
+                    let ranked = rank_candidates(&palette.candidates, &palette.input);
+                    if palette.candidates.is_empty() {
+                        ui.label(
+                            egui::RichText::new(
+                                "No cached tables yet — expand or refresh a connection first",
+                            )
+                            .weak(),
+                        );
+                    } else if ranked.is_empty() {
+                        ui.label(egui::RichText::new("No matching tables").weak());
+                    }
+                    let selected = palette.selected_index;
+                    egui::ScrollArea::vertical()
+                        .max_height(400.0)
+                        .show(ui, |ui| {
+                            for (row, &i) in ranked.iter().take(MAX_SHOWN).enumerate() {
+                                let c = &palette.candidates[i];
+                                let is_selected = row == selected;
+                                let resp = ui
+                                    .horizontal(|ui| {
+                                        let icon = if c.is_view { "👁" } else { "📋" };
+                                        let resp = ui.selectable_label(
+                                            is_selected,
+                                            format!("{} {}", icon, c.table),
+                                        );
+                                        ui.label(
+                                            egui::RichText::new(format!(
+                                                "{} · {}",
+                                                c.database, c.connection_name
+                                            ))
+                                            .weak()
+                                            .size(11.0),
+                                        );
+                                        if c.last_opened.is_some() {
+                                            ui.label(
+                                                egui::RichText::new("recent").weak().size(10.0),
+                                            );
+                                        }
+                                        resp
+                                    })
+                                    .inner;
+                                if is_selected {
+                                    resp.scroll_to_me(None);
+                                }
+                                if resp.clicked() {
+                                    open_index = Some(row);
+                                }
+                            }
+                        });
+                });
+        });
+    if let Some(row) = open_index {
+        tabular.table_palette.selected_index = row;
+        open_selected_table(tabular);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(database: &str, table: &str, last_opened: Option<i64>) -> TableCandidate {
+        TableCandidate {
+            connection_id: 1,
+            connection_name: "local".to_string(),
+            database: database.to_string(),
+            table: table.to_string(),
+            is_view: false,
+            last_opened,
+        }
+    }
+
+    #[test]
+    fn recent_tables_rank_first_but_prefix_matches_win() {
+        let candidates = vec![
+            candidate("shop", "customer_orders", None),
+            candidate("shop", "orders", None),
+            candidate("shop", "order_items", Some(100)),
+            candidate("crm", "contacts", Some(200)),
+            candidate("archive", "orders", None),
+        ];
+        // Empty query: recent first, newest on top
+        assert_eq!(rank_candidates(&candidates, "")[..2], [3, 2]);
+        // A prefix match beats a recent scattered one
+        let ranked = rank_candidates(&candidates, "ord");
+        assert_eq!(candidates[ranked[0]].table, "order_items");
+        assert_eq!(candidates[ranked[1]].table, "orders");
+        assert!(!ranked.contains(&3));
+        // A recently opened table beats unopened scattered matches
+        assert_eq!(rank_candidates(&candidates, "ois")[0], 2);
+        // db.table narrows to that database
+        assert_eq!(rank_candidates(&candidates, "arch.ord"), vec![4]);
+        assert!(rank_candidates(&candidates, "zzz").is_empty());
+    }
+}
@@ -412,7 +412,24 @@ impl super::Tabular {
                             views_folder.database_name = Some(db_name.clone());
                             views_folder.is_loaded = false;
 
-                            db_node.children = vec![tables_folder, views_folder];
+                            let mut procedures_folder = models::structs::TreeNode::new(
+                                "Stored Procedures".to_string(),
+                                models::enums::NodeType::StoredProceduresFolder,
+                            );
+                            procedures_folder.connection_id = Some(connection_id);
+                            procedures_folder.database_name = Some(db_name.clone());
+                            procedures_folder.is_loaded = false;
+
+                            let mut functions_folder = models::structs::TreeNode::new(
+                                "Functions".to_string(),
+                                models::enums::NodeType::UserFunctionsFolder,
+                            );
+                            functions_folder.connection_id = Some(connection_id);
+                            functions_folder.database_name = Some(db_name.clone());
+                            functions_folder.is_loaded = false;
+
+                            db_node.children =
+                                vec![tables_folder, views_folder, procedures_folder, functions_folder];
                             databases_folder.children.push(db_node);
                         }
                     }
@@ -483,24 +500,8 @@ impl super::Tabular {
                     main_children.push(databases_folder);
                 }
                 models::enums::DatabaseType::SQLite => {
-                    // SQLite structure - single database
-                    let mut tables_folder = models::structs::TreeNode::new(
-                        "Tables".to_string(),
-                        models::enums::NodeType::TablesFolder,
-                    );
-                    tables_folder.connection_id = Some(connection_id);
-                    tables_folder.database_name = Some("main".to_string());
-                    tables_folder.is_loaded = false;
-
-                    let mut views_folder = models::structs::TreeNode::new(
-                        "Views".to_string(),
-                        models::enums::NodeType::ViewsFolder,
-                    );
-                    views_folder.connection_id = Some(connection_id);
-                    views_folder.database_name = Some("main".to_string());
-                    views_folder.is_loaded = false;
-
-                    main_children = vec![tables_folder, views_folder];
+                    // Single file: flat Tables/Views; attached files: one node per schema
+                    main_children = driver_sqlite::structure_children(connection_id, connection);
                 }
                 models::enums::DatabaseType::Redis => {
                     // Redis structure with databases
@@ -805,6 +806,24 @@ impl super::Tabular {
                             trg_folder.is_loaded = false;
                             db_children.push(trg_folder);
                         }
+                        models::enums::DatabaseType::PostgreSQL => {
+                            let mut sp_folder = models::structs::TreeNode::new(
+                                "Stored Procedures".to_string(),
+                                models::enums::NodeType::StoredProceduresFolder,
+                            );
+                            sp_folder.connection_id = Some(connection_id);
+                            sp_folder.database_name = Some(db_name.clone());
+                            sp_folder.is_loaded = false;
+                            db_children.push(sp_folder);
+                            let mut fn_folder = models::structs::TreeNode::new(
+                                "Functions".to_string(),
+                                models::enums::NodeType::UserFunctionsFolder,
+                            );
+                            fn_folder.connection_id = Some(connection_id);
+                            fn_folder.database_name = Some(db_name.clone());
+                            fn_folder.is_loaded = false;
+                            db_children.push(fn_folder);
+                        }
                         _ => {}
                     }
                 }
@@ -1426,6 +1445,8 @@ impl super::Tabular {
         let table_type = match folder_type {
             models::enums::NodeType::TablesFolder => "table",
             models::enums::NodeType::ViewsFolder => "view",
+            models::enums::NodeType::StoredProceduresFolder => "procedure",
+            models::enums::NodeType::UserFunctionsFolder => "function",
             _ => {
                 node.children = vec![models::structs::TreeNode::new(
                     "Not supported for PostgreSQL".to_string(),
@@ -1449,16 +1470,20 @@ impl super::Tabular {
             node.children = cached
                 .into_iter()
                 .map(|name| {
-                    let mut child = models::structs::TreeNode::new(
-                        name,
-                        match folder_type {
-                            models::enums::NodeType::TablesFolder => models::enums::NodeType::Table,
-                            _ => models::enums::NodeType::View,
-                        },
-                    );
+                    let (node_type, is_loaded) = match folder_type {
+                        models::enums::NodeType::TablesFolder => (models::enums::NodeType::Table, false),
+                        models::enums::NodeType::StoredProceduresFolder => {
+                            (models::enums::NodeType::StoredProcedure, true)
+                        }
+                        models::enums::NodeType::UserFunctionsFolder => {
+                            (models::enums::NodeType::UserFunction, true)
+                        }
+                        _ => (models::enums::NodeType::View, false),
+                    };
+                    let mut child = models::structs::TreeNode::new(name, node_type);
                     child.connection_id = Some(connection_id);
                     child.database_name = Some(database_name.clone());
-                    child.is_loaded = false;
+                    child.is_loaded = is_loaded;
                     child
                 })
                 .collect();
@@ -1486,16 +1511,20 @@ impl super::Tabular {
             node.children = real_items
                 .into_iter()
                 .map(|name| {
-                    let mut child = models::structs::TreeNode::new(
-                        name,
-                        match folder_type {
-                            models::enums::NodeType::TablesFolder => models::enums::NodeType::Table,
-                            _ => models::enums::NodeType::View,
-                        },
-                    );
+                    let (node_type, is_loaded) = match folder_type {
+                        models::enums::NodeType::TablesFolder => (models::enums::NodeType::Table, false),
+                        models::enums::NodeType::StoredProceduresFolder => {
+                            (models::enums::NodeType::StoredProcedure, true)
+                        }
+                        models::enums::NodeType::UserFunctionsFolder => {
+                            (models::enums::NodeType::UserFunction, true)
+                        }
+                        _ => (models::enums::NodeType::View, false),
+                    };
+                    let mut child = models::structs::TreeNode::new(name, node_type);
                     child.connection_id = Some(connection_id);
                     child.database_name = Some(database_name.clone());
-                    child.is_loaded = false;
+                    child.is_loaded = is_loaded;
                     child
                 })
                 .collect();
@@ -1516,6 +1545,7 @@ impl super::Tabular {
         force_live_fetch: bool,
     ) {
         debug!("Loading {:?} content for SQLite", folder_type);
+        let schema = node.database_name.clone().unwrap_or_else(|| "main".to_string());
 
         // Try to get from cache first
         let table_type = match folder_type {
@@ -1534,7 +1564,7 @@ impl super::Tabular {
         // Try cache first (skipped when force_live_fetch is true)
         if !force_live_fetch
         && let Some(cached_items) =
-            cache_data::get_tables_from_cache(self, connection_id, "main", table_type)
+            cache_data::get_tables_from_cache(self, connection_id, &schema, table_type)
             && !cached_items.is_empty()
         {
             debug!(
@@ -1554,7 +1584,7 @@ impl super::Tabular {
 
                     let mut item_node = models::structs::TreeNode::new(item_name, node_type);
                     item_node.connection_id = Some(connection_id);
-                    item_node.database_name = Some("main".to_string());
+                    item_node.database_name = Some(schema.clone());
                     item_node.is_loaded = false; // Will load columns on expansion if it's a table
                     item_node
                 })
@@ -1571,7 +1601,7 @@ impl super::Tabular {
         );
 
         if let Some(real_items) =
-            driver_sqlite::fetch_tables_from_sqlite_connection(self, connection_id, table_type)
+            driver_sqlite::fetch_tables_from_sqlite_connection(self, connection_id, &schema, table_type)
         {
             debug!(
                 "Successfully fetched {} {} from SQLite database",
@@ -1584,7 +1614,7 @@ impl super::Tabular {
                 .iter()
                 .map(|name| (name.clone(), table_type.to_string()))
                 .collect();
-            cache_data::save_tables_to_cache(self, connection_id, "main", &table_data);
+            cache_data::save_tables_to_cache(self, connection_id, &schema, &table_data);
 
             // Create tree nodes from fetched data
             let child_nodes: Vec<models::structs::TreeNode> = real_items
@@ -1598,7 +1628,7 @@ impl super::Tabular {
 
                     let mut item_node = models::structs::TreeNode::new(item_name, node_type);
                     item_node.connection_id = Some(connection_id);
-                    item_node.database_name = Some("main".to_string());
+                    item_node.database_name = Some(schema.clone());
                     item_node.is_loaded = false; // Will load columns on expansion if it's a table
                     item_node
                 })
@@ -1638,7 +1668,7 @@ impl super::Tabular {
                     let mut item_node =
                         models::structs::TreeNode::new(item_name.clone(), item_type.clone());
                     item_node.connection_id = Some(connection_id);
-                    item_node.database_name = Some("main".to_string());
+                    item_node.database_name = Some(schema.clone());
                     item_node.is_loaded = false;
                     item_node
                 })
@@ -1941,85 +1971,7 @@ impl super::Tabular {
             let database_name = Tabular::find_table_database_name(nodes, table_name, connection_id)
                 .unwrap_or_else(|| connection.database.clone());
 
-            // Load columns, indexes, and primary keys from cache instead of querying server
-            let columns_from_cache =
-                self.load_table_columns_from_cache(connection_id, table_name, &database_name);
-            let (indexes_list, pk_columns) =
-                self.extract_indexes_and_pks_from_cache(connection_id, &database_name, table_name);
-            let partitions_list = self.extract_partitions_from_cache(connection_id, &database_name, table_name);
-
-            let mut columns_folder = models::structs::TreeNode::new(
-                "Columns".to_string(),
-                models::enums::NodeType::ColumnsFolder,
-            );
-            columns_folder.connection_id = Some(connection_id);
-            columns_folder.database_name = Some(database_name.clone());
-            columns_folder.table_name = Some(table_name.to_string());
-            columns_folder.is_loaded = true;
-            columns_folder.children = columns_from_cache;
-
-            let mut indexes_folder = models::structs::TreeNode::new(
-                "Indexes".to_string(),
-                models::enums::NodeType::IndexesFolder,
-            );
-            indexes_folder.connection_id = Some(connection_id);
-            indexes_folder.database_name = Some(database_name.clone());
-            indexes_folder.table_name = Some(table_name.to_string());
-            indexes_folder.is_loaded = true;
-            indexes_folder.children = indexes_list
-                .into_iter()
-                .map(|idx| {
-                    let mut n = models::structs::TreeNode::new(idx, models::enums::NodeType::Index);
-                    n.connection_id = Some(connection_id);
-                    n.database_name = Some(database_name.clone());
-                    n.table_name = Some(table_name.to_string());
-                    n
-                })
-                .collect();
-
-            let mut pks_folder = models::structs::TreeNode::new(
-                "Primary Keys".to_string(),
-                models::enums::NodeType::PrimaryKeysFolder,
-            );
-            pks_folder.connection_id = Some(connection_id);
-            pks_folder.database_name = Some(database_name.clone());
-            pks_folder.table_name = Some(table_name.to_string());
-            pks_folder.is_loaded = true;
-            pks_folder.children = pk_columns
-                .into_iter()
-                .map(|col| models::structs::TreeNode::new(col, models::enums::NodeType::Column))
-                .collect();
-
-            let mut partitions_folder = models::structs::TreeNode::new(
-                "Partitions".to_string(),
-                models::enums::NodeType::PartitionsFolder,
-            );
-            partitions_folder.connection_id = Some(connection_id);
-            partitions_folder.database_name = Some(database_name.clone());
-            partitions_folder.table_name = Some(table_name.to_string());
-            partitions_folder.is_loaded = true;
-            partitions_folder.children = partitions_list
-                .into_iter()
-                .map(|part| {
-                    // Format partition display: "name (TYPE)" if type is available
-                    let display_name = if let Some(ref ptype) = part.partition_type {
-                        format!("{} ({})", part.name, ptype)
-                    } else {
-                        part.name.clone()
-                    };
-                    let mut n = models::structs::TreeNode::new(display_name, models::enums::NodeType::Index);
-                    n.connection_id = Some(connection_id);
-                    n.database_name = Some(database_name.clone());
-                    n.table_name = Some(table_name.to_string());
-                    // Store the full partition info in file_path for later use
-                    if let Some(ref ptype) = part.partition_type {
-                        n.file_path = Some(format!("{}|{}", part.name, ptype));
-                    }
-                    n
-                })
-                .collect();
-
-            let subfolders = vec![columns_folder, indexes_folder, pks_folder, partitions_folder];
+            let subfolders = self.table_subfolders(connection_id, table_name, &database_name);
 
             // Find the table node recursively and update it with subfolders
             let updated = Self::update_table_node_with_columns_recursive(
@@ -2034,6 +1986,94 @@ impl super::Tabular {
             }
         }
     }
+    /// The Columns, Indexes, Primary Keys and Partitions folders shown under a
+    /// table node, built from the schema cache.
+    pub(crate) fn table_subfolders(
+        &mut self,
+        connection_id: i64,
+        table_name: &str,
+        database_name: &str,
+    ) -> Vec<models::structs::TreeNode> {
+        // Load columns, indexes, and primary keys from cache instead of querying server
+        let columns_from_cache =
+            self.load_table_columns_from_cache(connection_id, table_name, database_name);
+        let (indexes_list, pk_columns) =
+            self.extract_indexes_and_pks_from_cache(connection_id, database_name, table_name);
+        let partitions_list = self.extract_partitions_from_cache(connection_id, database_name, table_name);
+
+        let mut columns_folder = models::structs::TreeNode::new(
+            "Columns".to_string(),
+            models::enums::NodeType::ColumnsFolder,
+        );
+        columns_folder.connection_id = Some(connection_id);
+        columns_folder.database_name = Some(database_name.to_string());
+        columns_folder.table_name = Some(table_name.to_string());
+        columns_folder.is_loaded = true;
+        columns_folder.children = columns_from_cache;
+
+        let mut indexes_folder = models::structs::TreeNode::new(
+            "Indexes".to_string(),
+            models::enums::NodeType::IndexesFolder,
+        );
+        indexes_folder.connection_id = Some(connection_id);
+        indexes_folder.database_name = Some(database_name.to_string());
+        indexes_folder.table_name = Some(table_name.to_string());
+        indexes_folder.is_loaded = true;
+        indexes_folder.children = indexes_list
+            .into_iter()
+            .map(|idx| {
+                let mut n = models::structs::TreeNode::new(idx, models::enums::NodeType::Index);
+                n.connection_id = Some(connection_id);
+                n.database_name = Some(database_name.to_string());
+                n.table_name = Some(table_name.to_string());
+                n
+            })
+            .collect();
+
+        let mut pks_folder = models::structs::TreeNode::new(
+            "Primary Keys".to_string(),
+            models::enums::NodeType::PrimaryKeysFolder,
+        );
+        pks_folder.connection_id = Some(connection_id);
+        pks_folder.database_name = Some(database_name.to_string());
+        pks_folder.table_name = Some(table_name.to_string());
+        pks_folder.is_loaded = true;
+        pks_folder.children = pk_columns
+            .into_iter()
+            .map(|col| models::structs::TreeNode::new(col, models::enums::NodeType::Column))
+            .collect();
+
+        let mut partitions_folder = models::structs::TreeNode::new(
+            "Partitions".to_string(),
+            models::enums::NodeType::PartitionsFolder,
+        );
+        partitions_folder.connection_id = Some(connection_id);
+        partitions_folder.database_name = Some(database_name.to_string());
+        partitions_folder.table_name = Some(table_name.to_string());
+        partitions_folder.is_loaded = true;
+        partitions_folder.children = partitions_list
+            .into_iter()
+            .map(|part| {
+                // Format partition display: "name (TYPE)" if type is available
+                let display_name = if let Some(ref ptype) = part.partition_type {
+                    format!("{} ({})", part.name, ptype)
+                } else {
+                    part.name.clone()
+                };
+                let mut n = models::structs::TreeNode::new(display_name, models::enums::NodeType::Index);
+                n.connection_id = Some(connection_id);
+                n.database_name = Some(database_name.to_string());
+                n.table_name = Some(table_name.to_string());
+                // Store the full partition info in file_path for later use
+                if let Some(ref ptype) = part.partition_type {
+                    n.file_path = Some(format!("{}|{}", part.name, ptype));
+                }
+                n
+            })
+            .collect();
+
+        vec![columns_folder, indexes_folder, pks_folder, partitions_folder]
+    }
     pub fn find_table_database_name(
         nodes: &[models::structs::TreeNode],
         table_name: &str,
@@ -2262,6 +2302,7 @@ impl super::Tabular {
                                 method: None,
                                 unique: false,
                                 columns: Vec::new(),
+                                ..Default::default()
                             })
                             .collect();
                         cache_data::save_indexes_to_cache(
@@ -2299,6 +2340,7 @@ impl super::Tabular {
                             method: None,
                             unique: false,
                             columns: Vec::new(),
+                            ..Default::default()
                         })
                         .collect();
                     cache_data::save_indexes_to_cache(
@@ -2407,8 +2449,7 @@ impl super::Tabular {
                     if let Some(models::enums::DatabasePool::SQLite(sqlite_pool)) =
                         connection::get_or_create_connection_pool(self, connection_id).await
                     {
-                        let escaped = table_name.replace("'", "''");
-                        let q = format!("PRAGMA index_list('{}')", escaped);
+                        let q = driver_sqlite::schema_pragma(Some(database_name), "index_list", table_name);
                         match sqlx::query(sqlx::AssertSqlSafe(q.as_str())).fetch_all(sqlite_pool.as_ref()).await {
                             Ok(rows) => {
                                 use sqlx::Row;
@@ -2432,10 +2473,11 @@ impl super::Tabular {
                 let port: u16 = connection.port.parse().unwrap_or(1433);
                 let user = connection.username.clone();
                 let pass = connection.password.clone();
+                let tls = crate::driver_mssql::MssqlTls::from_connection(connection);
                 let db = database_name.to_string();
                 let tbl = table_name.to_string();
                 let rt_res = tokio::runtime::Runtime::new().unwrap().block_on(async move {
-                    let mut client = crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db)).await?;
+                    let mut client = crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db), tls).await?;
                     // Parse schema-qualified name
                     let parse = |name: &str| -> (Option<String>, String) {
                         if name.starts_with('[') && name.contains("].[") && name.ends_with(']') {
@@ -2545,8 +2587,7 @@ impl super::Tabular {
                     if let Some(models::enums::DatabasePool::SQLite(sqlite_pool)) =
                         connection::get_or_create_connection_pool(self, connection_id).await
                     {
-                        let escaped = table_name.replace("'", "''");
-                        let q = format!("PRAGMA table_info('{}')", escaped);
+                        let q = driver_sqlite::schema_pragma(Some(database_name), "table_info", table_name);
                         match sqlx::query(sqlx::AssertSqlSafe(q.as_str())).fetch_all(sqlite_pool.as_ref()).await {
                             Ok(rows) => {
                                 use sqlx::Row;
@@ -2573,10 +2614,11 @@ impl super::Tabular {
                 let port: u16 = connection.port.parse().unwrap_or(1433);
                 let user = connection.username.clone();
                 let pass = connection.password.clone();
+                let tls = crate::driver_mssql::MssqlTls::from_connection(connection);
                 let db = database_name.to_string();
                 let tbl = table_name.to_string();
                 let rt_res = tokio::runtime::Runtime::new().unwrap().block_on(async move {
-                    let mut client = crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db)).await?;
+                    let mut client = crate::driver_mssql::connect_mssql(&host, port, &user, &pass, Some(&db), tls).await?;
                     // Parse schema-qualified name
                     let parse = |name: &str| -> (Option<String>, String) {
                         if name.starts_with('[') && name.contains("].[") && name.ends_with(']') {
@@ -6,6 +6,11 @@ impl super::Tabular {
         if self.update_check_in_progress {
             return; // Already checking
         }
+        if self.offline_mode {
+            // The update dialog explains that checks are disabled
+            self.show_update_dialog = manual;
+            return;
+        }
 
         self.update_check_in_progress = true;
         self.update_check_error = None;
@@ -19,7 +24,9 @@ impl super::Tabular {
 
         // Send background task to check for updates
         if let Some(sender) = &self.background_sender {
-            let _ = sender.send(models::enums::BackgroundTask::CheckForUpdates);
+            let _ = sender.send(models::enums::BackgroundTask::CheckForUpdates {
+                channel: self.update_channel,
+            });
         }
     }
     pub fn render_update_dialog(&mut self, ctx: &egui::Context) {
@@ -34,7 +41,20 @@ impl super::Tabular {
             .show(ctx, |ui| {
                 ui.set_min_width(400.0);
 
-                if self.update_check_in_progress {
+                if self.offline_mode {
+                    ui.heading("Offline mode");
+                    ui.separator();
+                    ui.label("Update checks are disabled while offline mode is on.");
+                    ui.label(
+                        egui::RichText::new("Turn it off in Preferences → Update to check for new versions.")
+                            .size(11.0)
+                            .color(egui::Color32::from_gray(120)),
+                    );
+                    ui.separator();
+                    if ui.button("Close").clicked() {
+                        self.show_update_dialog = false;
+                    }
+                } else if self.update_check_in_progress {
                     ui.horizontal(|ui| {
                         ui.spinner();
                         ui.label("Checking for updates...");
@@ -66,6 +86,14 @@ impl super::Tabular {
                         ui.horizontal(|ui| {
                             ui.label("Latest version:");
                             ui.strong(&update_info.latest_version);
+                            if update_info.prerelease {
+                                ui.colored_label(egui::Color32::from_rgb(255, 165, 0), "pre-release");
+                            }
+                        });
+
+                        ui.horizontal(|ui| {
+                            ui.label("Channel:");
+                            ui.label(update_info.channel.display_name());
                         });
 
                         if let Some(published_at) = &update_info.published_at {
@@ -81,6 +109,31 @@ impl super::Tabular {
                                 ui.text_edit_multiline(&mut update_info.release_notes.clone());
                             });
 
+                        if let Some(error) = &self.update_install_error {
+                            ui.colored_label(egui::Color32::from_rgb(255, 100, 100), error);
+                        } else {
+                            match self.update_verification {
+                                Some(crate::self_update::UpdateVerification::Verified) => {
+                                    ui.label("🔒 Download matched the published SHA-256 checksum");
+                                }
+                                Some(crate::self_update::UpdateVerification::NoChecksum) => {
+                                    ui.colored_label(
+                                        egui::Color32::from_rgb(255, 165, 0),
+                                        "⚠ Downloaded without verification: no checksum was published",
+                                    );
+                                }
+                                None if update_info.checksum_url.is_some() => {
+                                    ui.label("🔒 The download will be verified against the published SHA-256 checksum");
+                                }
+                                None => {
+                                    ui.colored_label(
+                                        egui::Color32::from_rgb(255, 165, 0),
+                                        "⚠ This release has no published checksum; the download cannot be verified",
+                                    );
+                                }
+                            }
+                        }
+
                         ui.separator();
 
                         ui.horizontal(|ui| {
@@ -114,6 +167,7 @@ impl super::Tabular {
                             "Tabular {} is the latest version.",
                             update_info.current_version
                         ));
+                        ui.label(format!("Channel: {}", update_info.channel.display_name()));
                         ui.separator();
                         if ui.button("Close").clicked() {
                             self.show_update_dialog = false;
@@ -136,6 +190,12 @@ impl super::Tabular {
             return;
         }
 
+        if self.offline_mode {
+            log::warn!("⚠️ Offline mode is on, not downloading the update");
+            self.update_download_started = false;
+            return;
+        }
+
         // Prevent re-downloading if already completed
         if self.update_installed {
             log::warn!("⚠️ Update already downloaded, ignoring request");
@@ -153,12 +213,15 @@ impl super::Tabular {
                 log::debug!("📄 Asset name: {:?}", update_info.asset_name);
 
                 self.update_download_in_progress = true;
+                self.update_verification = None;
+                self.update_install_error = None;
                 // Prepare channel to receive completion signal
                 let (tx, rx) = std::sync::mpsc::channel();
                 self.update_install_receiver = Some(rx);
 
                 let update_info_clone = update_info.clone();
                 let auto_updater_clone = auto_updater.clone();
+                let channel = self.update_channel;
 
                 std::thread::spawn(move || {
                     log::debug!("🔄 Background update thread started (auto updater)");
@@ -171,21 +234,21 @@ impl super::Tabular {
                         Ok(rt) => rt,
                         Err(e) => {
                             log::error!("❌ Failed to create update runtime: {}", e);
-                            let _ = tx.send(false);
+                            let _ = tx.send(Err("Failed to start the update download".to_string()));
                             return;
                         }
                     };
 
                     match rt
-                        .block_on(auto_updater_clone.download_and_stage_update(&update_info_clone))
+                        .block_on(auto_updater_clone.download_and_stage_update(&update_info_clone, channel))
                     {
-                        Ok(()) => {
-                            log::debug!("✅ Update staged successfully");
-                            let _ = tx.send(true);
+                        Ok(verification) => {
+                            log::debug!("✅ Update staged successfully ({:?})", verification);
+                            let _ = tx.send(Ok(verification));
                         }
                         Err(e) => {
                             log::error!("❌ Update failed: {}", e);
-                            let _ = tx.send(false);
+                            let _ = tx.send(Err(e.to_string()));
                         }
                     }
                 });
@@ -0,0 +1,572 @@
+//! Named workspace profiles.
+//!
+//! A profile bundles the connections shown in the sidebar, the open query
+//! tabs, the expanded sidebar groups and the layout (sidebar and editor split)
+//! into `<app data>/profiles/<name>.json`. Switching snapshots the current
+//! workspace into the active profile before loading the target, so tabs come
+//! back as they were left. The `default` profile always exists.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use log::debug;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    directory, editor, models::structs::EditorBookmark, sidebar_database,
+    spreadsheet::SpreadsheetOperations, window_egui,
+};
+
+pub const DEFAULT_PROFILE: &str = "default";
+const ACTIVE_MARKER: &str = "active";
+
+/// One open query tab as stored in a profile.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ProfileTab {
+    pub title: String,
+    pub content: String,
+    #[serde(default)]
+    pub file_path: Option<String>,
+    #[serde(default)]
+    pub connection_id: Option<i64>,
+    #[serde(default)]
+    pub database_name: Option<String>,
+    #[serde(default)]
+    pub schema_name: Option<String>,
+    #[serde(default)]
+    pub is_modified: bool,
+    #[serde(default)]
+    pub bookmarks: Vec<EditorBookmark>,
+    /// Editor share of the editor/result split; `None` follows the default.
+    #[serde(default)]
+    pub split_ratio: Option<f32>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WorkspaceProfile {
+    pub name: String,
+    /// Connection ids shown in the sidebar; `None` shows every connection.
+    #[serde(default)]
+    pub visible_connections: Option<Vec<i64>>,
+    /// Top-level sidebar groups (connection folders) left expanded.
+    #[serde(default)]
+    pub expanded_groups: Vec<String>,
+    #[serde(default)]
+    pub tabs: Vec<ProfileTab>,
+    #[serde(default)]
+    pub active_tab: usize,
+    #[serde(default)]
+    pub sidebar_collapsed: bool,
+    #[serde(default = "crate::config::default_sidebar_width")]
+    pub sidebar_width: f32,
+    #[serde(default = "crate::config::default_editor_split_ratio")]
+    pub editor_split_ratio: f32,
+}
+
+/// Profile state kept on `Tabular`.
+pub struct ProfilesState {
+    pub active: String,
+    /// Sidebar filter of the active profile; `None` shows every connection.
+    pub visible_connections: Option<HashSet<i64>>,
+    pub show_window: bool,
+    pub names: Vec<String>,
+    pub new_name: String,
+    /// Switch waiting for the user to confirm discarding unsaved tabs.
+    pub pending_switch: Option<String>,
+}
+
+impl Default for ProfilesState {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE.to_string(),
+            visible_connections: None,
+            show_window: false,
+            names: Vec::new(),
+            new_name: String::new(),
+            pending_switch: None,
+        }
+    }
+}
+
+pub(crate) fn profiles_dir() -> PathBuf {
+    directory::get_app_data_dir().join("profiles")
+}
+
+fn profile_file(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", name))
+}
+
+/// Profile names may become file names: letters, digits, space, `-` and `_`.
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    let name = name.trim();
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+}
+
+/// Saved profile names, sorted, with `default` always first.
+pub(crate) fn list_profiles(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|rd| {
+            rd.flatten()
+                .filter_map(|e| {
+                    let path = e.path();
+                    (path.extension()? == "json")
+                        .then(|| path.file_stem()?.to_str().map(str::to_string))
+                        .flatten()
+                })
+                .filter(|n| n != DEFAULT_PROFILE)
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort_by_key(|n| n.to_lowercase());
+    names.insert(0, DEFAULT_PROFILE.to_string());
+    names
+}
+
+pub(crate) fn load_profile(dir: &Path, name: &str) -> Option<WorkspaceProfile> {
+    let text = std::fs::read_to_string(profile_file(dir, name)).ok()?;
+    match serde_json::from_str::<WorkspaceProfile>(&text) {
+        Ok(mut profile) => {
+            profile.name = name.to_string();
+            Some(profile)
+        }
+        Err(e) => {
+            log::error!("Invalid workspace profile '{}': {}", name, e);
+            None
+        }
+    }
+}
+
+pub(crate) fn save_profile(dir: &Path, profile: &WorkspaceProfile) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(profile).map_err(|e| e.to_string())?;
+    std::fs::write(profile_file(dir, &profile.name), json).map_err(|e| e.to_string())
+}
+
+fn read_active(dir: &Path) -> Option<String> {
+    let name = std::fs::read_to_string(dir.join(ACTIVE_MARKER)).ok()?;
+    let name = name.trim();
+    is_valid_name(name).then(|| name.to_string())
+}
+
+fn write_active(dir: &Path, name: &str) {
+    if std::fs::create_dir_all(dir).is_ok() {
+        let _ = std::fs::write(dir.join(ACTIVE_MARKER), name);
+    }
+}
+
+/// Snapshot the current workspace as profile `name`.
+pub(crate) fn capture(tabular: &mut window_egui::Tabular, name: &str) -> WorkspaceProfile {
+    // The editor buffer is newer than the active tab's stored content
+    let editor_text = tabular.editor.text.clone();
+    if let Some(tab) = tabular.query_tabs.get_mut(tabular.active_tab_index)
+        && tab.content != editor_text
+    {
+        tab.content = editor_text;
+        tab.is_modified = true;
+    }
+    let mut visible: Option<Vec<i64>> = tabular
+        .workspace_profiles
+        .visible_connections
+        .as_ref()
+        .map(|set| set.iter().copied().collect());
+    if let Some(ids) = visible.as_mut() {
+        ids.sort_unstable();
+    }
+    WorkspaceProfile {
+        name: name.to_string(),
+        visible_connections: visible,
+        expanded_groups: tabular
+            .items_tree
+            .iter()
+            .filter(|n| n.is_expanded)
+            .map(|n| n.name.clone())
+            .collect(),
+        tabs: tabular
+            .query_tabs
+            .iter()
+            .map(|t| ProfileTab {
+                title: t.title.clone(),
+                content: t.content.clone(),
+                file_path: t.file_path.clone(),
+                connection_id: t.connection_id,
+                database_name: t.database_name.clone(),
+                schema_name: t.schema_name.clone(),
+                is_modified: t.is_modified,
+                bookmarks: t.bookmarks.clone(),
+                split_ratio: t.split_ratio,
+            })
+            .collect(),
+        active_tab: tabular.active_tab_index,
+        sidebar_collapsed: tabular.sidebar_collapsed,
+        sidebar_width: tabular.sidebar_width,
+        editor_split_ratio: tabular.table_split_ratio,
+    }
+}
+
+/// Replace the open tabs, sidebar filter and layout with `profile`.
+pub(crate) fn apply(tabular: &mut window_egui::Tabular, profile: &WorkspaceProfile) {
+    if tabular.spreadsheet_state.editing_cell.is_some() {
+        tabular.spreadsheet_finish_cell_edit(true);
+    }
+    let known: HashSet<i64> = tabular.connections.iter().filter_map(|c| c.id).collect();
+    tabular.query_tabs.clear();
+    tabular.active_tab_index = 0;
+    for tab in &profile.tabs {
+        // Connections deleted since the profile was saved are dropped
+        let connection_id = tab.connection_id.filter(|id| known.contains(id));
+        editor::create_new_tab_with_connection_and_database(
+            tabular,
+            tab.title.clone(),
+            tab.content.clone(),
+            connection_id,
+            connection_id.and(tab.database_name.clone()),
+        );
+        if let Some(t) = tabular.query_tabs.last_mut() {
+            t.file_path = tab.file_path.clone();
+            t.schema_name = connection_id.and(tab.schema_name.clone());
+            t.is_saved = tab.file_path.is_some() && !tab.is_modified;
+            t.is_modified = tab.is_modified;
+            t.bookmarks = tab.bookmarks.clone();
+            t.split_ratio = tab.split_ratio.map(|r| {
+                r.clamp(
+                    window_egui::render_dialogs::SPLIT_RATIO_MIN,
+                    window_egui::render_dialogs::SPLIT_RATIO_MAX,
+                )
+            });
+        }
+    }
+    if tabular.query_tabs.is_empty() {
+        editor::create_new_tab(tabular, "Untitled Query".to_string(), String::new());
+    }
+    let active = profile.active_tab.min(tabular.query_tabs.len() - 1);
+    if active != tabular.active_tab_index {
+        editor::switch_to_tab(tabular, active);
+    }
+
+    tabular.workspace_profiles.visible_connections = profile
+        .visible_connections
+        .as_ref()
+        .map(|ids| ids.iter().copied().collect());
+    sidebar_database::refresh_connections_tree(tabular);
+    for node in tabular.items_tree.iter_mut() {
+        node.is_expanded = profile.expanded_groups.contains(&node.name);
+    }
+
+    tabular.sidebar_collapsed = profile.sidebar_collapsed;
+    tabular.sidebar_width = profile.sidebar_width.clamp(150.0, 500.0);
+    tabular.table_split_ratio = profile.editor_split_ratio.clamp(
+        window_egui::render_dialogs::SPLIT_RATIO_MIN,
+        window_egui::render_dialogs::SPLIT_RATIO_MAX,
+    );
+    tabular.workspace_profiles.active = profile.name.clone();
+}
+
+/// Save the current workspace into the active profile.
+pub(crate) fn save_active(tabular: &mut window_egui::Tabular) -> Result<(), String> {
+    let name = tabular.workspace_profiles.active.clone();
+    let profile = capture(tabular, &name);
+    save_profile(&profiles_dir(), &profile)
+}
+
+/// Snapshot the active profile, then load `target` (a fresh workspace when it
+/// has never been saved).
+pub(crate) fn switch_profile(tabular: &mut window_egui::Tabular, target: &str) {
+    let dir = profiles_dir();
+    if let Err(e) = save_active(tabular) {
+        tabular.toasts.error(format!(
+            "Could not save profile '{}': {}",
+            tabular.workspace_profiles.active, e
+        ));
+        return;
+    }
+    let profile = load_profile(&dir, target).unwrap_or_else(|| WorkspaceProfile {
+        name: target.to_string(),
+        sidebar_width: tabular.sidebar_width,
+        editor_split_ratio: tabular.table_split_ratio,
+        ..Default::default()
+    });
+    apply(tabular, &profile);
+    write_active(&dir, target);
+    tabular.workspace_profiles.names = list_profiles(&dir);
+    debug!("🗂️ Switched to workspace profile '{}'", target);
+    tabular
+        .toasts
+        .info(format!("Workspace profile: {}", target));
+}
+
+/// Called once at startup: reopen the profile that was active on exit.
+pub(crate) fn restore_on_startup(tabular: &mut window_egui::Tabular) {
+    let dir = profiles_dir();
+    let Some(name) = read_active(&dir) else {
+        return;
+    };
+    if let Some(profile) = load_profile(&dir, &name) {
+        apply(tabular, &profile);
+    }
+}
+
+/// Called on exit: keep the active profile's tabs for next time, but only
+/// once profiles are in use (the default profile has been saved).
+pub(crate) fn save_on_exit(tabular: &mut window_egui::Tabular) {
+    let dir = profiles_dir();
+    if read_active(&dir).is_some()
+        && let Err(e) = save_active(tabular)
+    {
+        log::error!("Failed to save workspace profile on exit: {}", e);
+    }
+}
+
+pub(crate) fn open_window(tabular: &mut window_egui::Tabular) {
+    tabular.workspace_profiles.names = list_profiles(&profiles_dir());
+    tabular.workspace_profiles.pending_switch = None;
+    tabular.workspace_profiles.show_window = true;
+}
+
+/// Titles of tabs whose edits are not written to a file.
+fn unsaved_tab_titles(tabular: &window_egui::Tabular) -> Vec<String> {
+    crate::unsaved_changes::unsaved_tab_indices(tabular)
+        .into_iter()
+        .map(|i| tabular.query_tabs[i].title.clone())
+        .collect()
+}
+
+/// Switch now, or ask first when tabs have unsaved edits.
+fn request_switch(tabular: &mut window_egui::Tabular, target: String) {
+    if unsaved_tab_titles(tabular).is_empty() {
+        switch_profile(tabular, &target);
+    } else {
+        tabular.workspace_profiles.pending_switch = Some(target);
+    }
+}
+
+pub(crate) fn render_profiles_window(tabular: &mut window_egui::Tabular, ctx: &egui::Context) {
+    if !tabular.workspace_profiles.show_window {
+        return;
+    }
+    let mut open = true;
+    let mut switch_to: Option<String> = None;
+    let mut confirm_switch: Option<String> = None;
+    let mut delete: Option<String> = None;
+    let mut save_as: Option<String> = None;
+    let mut visibility_changed = false;
+
+    egui::Window::new("🗂 Workspace Profiles")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(460.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .open(&mut open)
+        .show(ctx, |ui| {
+            let state = &tabular.workspace_profiles;
+            if let Some(target) = state.pending_switch.clone() {
+                let titles = unsaved_tab_titles(tabular);
+                ui.label(format!(
+                    "Switch to '{}'? These tabs have unsaved changes:",
+                    target
+                ));
+                for title in &titles {
+                    ui.label(format!("  • {}", title));
+                }
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Their edits are kept in profile '{}' but not written to their files.",
+                        state.active
+                    ))
+                    .small()
+                    .weak(),
+                );
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Switch").clicked() {
+                        confirm_switch = Some(target.clone());
+                    }
+                    if ui.button("Cancel").clicked() {
+                        tabular.workspace_profiles.pending_switch = None;
+                    }
+                });
+                return;
+            }
+
+            ui.label(format!("Active profile: {}", state.active));
+            ui.add_space(4.0);
+            egui::Grid::new("workspace_profiles_grid")
+                .striped(true)
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for name in &state.names {
+                        let is_active = *name == state.active;
+                        ui.label(if is_active {
+                            egui::RichText::new(name).strong()
+                        } else {
+                            egui::RichText::new(name)
+                        });
+                        ui.horizontal(|ui| {
+                            if ui
+                                .add_enabled(!is_active, egui::Button::new("Switch"))
+                                .clicked()
+                            {
+                                switch_to = Some(name.clone());
+                            }
+                            if name != DEFAULT_PROFILE
+                                && !is_active
+                                && ui.button("🗑").on_hover_text("Delete profile").clicked()
+                            {
+                                delete = Some(name.clone());
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut tabular.workspace_profiles.new_name)
+                        .hint_text("New profile name")
+                        .desired_width(200.0),
+                );
+                let name = tabular.workspace_profiles.new_name.trim().to_string();
+                let valid = is_valid_name(&name)
+                    && !tabular.workspace_profiles.names.iter().any(|n| n == &name);
+                if ui
+                    .add_enabled(valid, egui::Button::new("Save current as"))
+                    .on_hover_text("Create a profile from the open tabs and layout")
+                    .clicked()
+                {
+                    save_as = Some(name);
+                }
+            });
+
+            ui.separator();
+            ui.label("Connections shown in this profile:");
+            let all_ids: Vec<(i64, String)> = tabular
+                .connections
+                .iter()
+                .filter_map(|c| Some((c.id?, c.name.clone())))
+                .collect();
+            egui::ScrollArea::vertical()
+                .id_salt("workspace_profile_connections")
+                .max_height(220.0)
+                .show(ui, |ui| {
+                    for (id, name) in &all_ids {
+                        let mut shown = tabular
+                            .workspace_profiles
+                            .visible_connections
+                            .as_ref()
+                            .is_none_or(|set| set.contains(id));
+                        if ui.checkbox(&mut shown, name).changed() {
+                            let set = tabular
+                                .workspace_profiles
+                                .visible_connections
+                                .get_or_insert_with(|| all_ids.iter().map(|(i, _)| *i).collect());
+                            if shown {
+                                set.insert(*id);
+                            } else {
+                                set.remove(id);
+                            }
+                            visibility_changed = true;
+                        }
+                    }
+                });
+            if ui.small_button("Show all").clicked() {
+                tabular.workspace_profiles.visible_connections = None;
+                visibility_changed = true;
+            }
+        });
+
+    if visibility_changed {
+        sidebar_database::refresh_connections_tree(tabular);
+    }
+    if let Some(target) = confirm_switch {
+        tabular.workspace_profiles.pending_switch = None;
+        switch_profile(tabular, &target);
+    } else if let Some(target) = switch_to {
+        request_switch(tabular, target);
+    }
+    if let Some(name) = save_as {
+        let dir = profiles_dir();
+        let profile = capture(tabular, &name);
+        match save_profile(&dir, &profile) {
+            Ok(()) => {
+                tabular.workspace_profiles.active = name.clone();
+                write_active(&dir, &name);
+                tabular.workspace_profiles.names = list_profiles(&dir);
+                tabular.workspace_profiles.new_name.clear();
+                tabular
+                    .toasts
+                    .info(format!("Saved workspace profile '{}'", name));
+            }
+            Err(e) => tabular
+                .toasts
+                .error(format!("Could not save profile: {}", e)),
+        }
+    }
+    if let Some(name) = delete {
+        let dir = profiles_dir();
+        let _ = std::fs::remove_file(profile_file(&dir, &name));
+        tabular.workspace_profiles.names = list_profiles(&dir);
+    }
+    if !open {
+        tabular.workspace_profiles.show_window = false;
+        tabular.workspace_profiles.pending_switch = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_round_trip_with_default_first() {
+        let dir = std::env::temp_dir().join(format!("tabular-profiles-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(list_profiles(&dir), vec![DEFAULT_PROFILE.to_string()]);
+
+        let profile = WorkspaceProfile {
+            name: "Client B".to_string(),
+            visible_connections: Some(vec![3, 7]),
+            tabs: vec![ProfileTab {
+                title: "orders.sql".to_string(),
+                content: "SELECT 1".to_string(),
+                connection_id: Some(3),
+                split_ratio: Some(0.3),
+                ..Default::default()
+            }],
+            sidebar_width: 240.0,
+            ..Default::default()
+        };
+        save_profile(&dir, &profile).unwrap();
+        save_profile(
+            &dir,
+            &WorkspaceProfile {
+                name: "acme".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(list_profiles(&dir), vec!["default", "acme", "Client B"]);
+
+        let loaded = load_profile(&dir, "Client B").unwrap();
+        assert_eq!(loaded.visible_connections, Some(vec![3, 7]));
+        assert_eq!(loaded.tabs[0].content, "SELECT 1");
+        assert_eq!(loaded.tabs[0].split_ratio, Some(0.3));
+        assert_eq!(loaded.sidebar_width, 240.0);
+        assert!(load_profile(&dir, "missing").is_none());
+
+        write_active(&dir, "acme");
+        assert_eq!(read_active(&dir).as_deref(), Some("acme"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn profile_names_must_be_file_safe() {
+        assert!(is_valid_name("Client A - prod_2"));
+        assert!(!is_valid_name("  "));
+        assert!(!is_valid_name("../etc"));
+        assert!(!is_valid_name("a/b"));
+    }
+}
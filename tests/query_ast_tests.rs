@@ -198,4 +198,332 @@ mod query_ast_tests {
             "outer limit missing: {out}"
         );
     }
+
+    fn unquoted(sql: &str) -> String {
+        sql.replace(['"', '`'], "")
+    }
+
+    #[test]
+    fn union_round_trips_through_the_plan() {
+        let sql = "select id, name from users union all select id, name from admins";
+        let plan = tabular::query_ast::debug_plan(sql, &DatabaseType::PostgreSQL).expect("plan");
+        assert_eq!(plan.lines().nth(1), Some("SetOp UnionAll"), "{plan}");
+        let (out, headers) =
+            compile_single_select(sql, &DatabaseType::PostgreSQL, None, false).expect("ok");
+        assert_eq!(
+            unquoted(&out.to_lowercase()),
+            "(select id, name from users) union all (select id, name from admins)"
+        );
+        assert_eq!(headers, vec!["id", "name"]);
+        let reparsed = tabular::query_ast::debug_plan(&out, &DatabaseType::PostgreSQL).unwrap();
+        assert_eq!(unquoted(&reparsed), plan);
+    }
+
+    #[test]
+    fn three_way_set_op_chain_keeps_order_limit_on_the_compound() {
+        let sql = "select id from a union select id from b except select id from c order by id limit 5";
+        let plan = tabular::query_ast::debug_plan(sql, &DatabaseType::MySQL).expect("plan");
+        // ((a UNION b) EXCEPT c), with ORDER BY / LIMIT above the whole chain
+        let lines: Vec<&str> = plan.lines().skip(1).map(str::trim).collect();
+        assert!(lines[0].starts_with("Limit") && lines[1].starts_with("Sort"), "{plan}");
+        assert!(lines[2] == "SetOp Except" && lines[3] == "SetOp Union", "{plan}");
+        assert_eq!(plan.matches("Limit").count(), 1, "{plan}");
+
+        let (pg, _) = compile_single_select(sql, &DatabaseType::PostgreSQL, None, true).unwrap();
+        assert_eq!(
+            unquoted(&pg.to_lowercase()),
+            "select * from (((select id from a) union (select id from b)) except (select id from c)) set_op order by id asc limit 5"
+        );
+        let reparsed = tabular::query_ast::debug_plan(
+            "select id from a union select id from b except select id from c",
+            &DatabaseType::SQLite,
+        )
+        .unwrap();
+        let (sqlite, _) = compile_single_select(
+            "select id from a union select id from b except select id from c",
+            &DatabaseType::SQLite,
+            None,
+            false,
+        )
+        .unwrap();
+        // SQLite compounds are flat: no parenthesized operands
+        assert_eq!(
+            unquoted(&sqlite.to_lowercase()),
+            "select id from a union select id from b except select id from c"
+        );
+        assert_eq!(
+            unquoted(&tabular::query_ast::debug_plan(&sqlite, &DatabaseType::SQLite).unwrap()),
+            reparsed
+        );
+    }
+
+    #[test]
+    fn two_ctes_are_kept_when_one_depends_on_the_other() {
+        let sql = "with active as (select id, org_id from users where active = 1), \
+                   per_org as (select org_id, count(*) as n from active group by org_id) \
+                   select org_id, n from per_org";
+        let plan = tabular::query_ast::debug_plan(sql, &DatabaseType::MySQL).expect("plan");
+        assert!(plan.contains(r#"With ctes=["active", "per_org"]"#), "{plan}");
+        for db in [
+            DatabaseType::MySQL,
+            DatabaseType::PostgreSQL,
+            DatabaseType::SQLite,
+        ] {
+            let (out, headers) = compile_single_select(sql, &db, None, false).expect("ok");
+            let out = unquoted(&out.to_lowercase());
+            assert!(
+                out.starts_with("with active as (select id, org_id from users where active = 1), per_org as ("),
+                "{db:?}: {out}"
+            );
+            assert!(out.ends_with("select org_id, n from per_org"), "{db:?}: {out}");
+            assert_eq!(headers, vec!["org_id", "n"]);
+            // The emitted SQL parses back to the same plan shape
+            let reparsed = tabular::query_ast::debug_plan(&out, &db).expect("reparse");
+            assert!(reparsed.contains(r#"With ctes=["active", "per_org"]"#), "{reparsed}");
+        }
+    }
+
+    #[test]
+    fn recursive_cte_is_emitted_per_dialect() {
+        let sql = "with recursive nums (n) as (select 1 union all select n + 1 from nums where n < 10) \
+                   select n from nums";
+        let plan = tabular::query_ast::debug_plan(sql, &DatabaseType::PostgreSQL).expect("plan");
+        assert!(plan.contains(r#"With recursive ctes=["nums"]"#), "{plan}");
+        let body = "nums (n) as (select 1 union all select n + 1 from nums where n < 10)";
+        for (db, keyword) in [
+            (DatabaseType::PostgreSQL, "with recursive"),
+            (DatabaseType::MySQL, "with recursive"),
+            (DatabaseType::SQLite, "with recursive"),
+            // SQL Server has no RECURSIVE keyword
+            (DatabaseType::MsSQL, "with"),
+        ] {
+            let (out, _) = compile_single_select(sql, &db, None, false).expect("ok");
+            let out = unquoted(&out.to_lowercase());
+            assert!(out.starts_with(&format!("{keyword} {body} ")), "{db:?}: {out}");
+            assert!(out.contains("from nums"), "{db:?}: {out}");
+        }
+    }
+
+    #[test]
+    fn inspect_plan_reports_rules_and_surfaces_parse_errors() {
+        let sql = "select id, name from users where active = 1";
+        let report =
+            tabular::query_ast::inspect_plan(sql, &DatabaseType::PostgreSQL, true).expect("plan");
+        assert!(report.plan.contains("TableScan(users"), "{}", report.plan);
+        assert_eq!(report.metrics.0, 3, "Projection, Filter, TableScan");
+        assert!(report.rules.iter().any(|r| r == "auto_limit"), "{:?}", report.rules);
+        assert!(report.emitted_sql.to_lowercase().contains("limit 1000"));
+        let (hash, _) =
+            tabular::query_ast::plan_structural_hash(sql, &DatabaseType::PostgreSQL, None, true)
+                .unwrap();
+        assert_eq!(report.structural_hash, hash);
+
+        let err = tabular::query_ast::inspect_plan("delete from users", &DatabaseType::MySQL, false)
+            .unwrap_err();
+        assert!(err.to_string().contains("not a SELECT"), "{err}");
+    }
+
+    #[test]
+    fn convert_dialect_rewrites_quoting_limits_and_booleans() {
+        use tabular::query_ast::convert::convert_dialect;
+        let mysql = "SELECT `id`, `name` FROM `users` WHERE `active` = TRUE ORDER BY `id` LIMIT 5;";
+        let pg = convert_dialect(mysql, Some(&DatabaseType::MySQL), &DatabaseType::PostgreSQL).expect("ok");
+        assert_eq!(pg.sql, "SELECT id, name FROM users WHERE active = TRUE ORDER BY id ASC LIMIT 5");
+        assert!(pg.notes.is_empty(), "{:?}", pg.notes);
+
+        let ms = convert_dialect(mysql, Some(&DatabaseType::MySQL), &DatabaseType::MsSQL).expect("ok");
+        assert_eq!(ms.sql, "SELECT TOP 5 id, name FROM users WHERE active = 1 ORDER BY id ASC");
+
+        // ...and back: TOP and FETCH both become LIMIT
+        let top = convert_dialect("select top 3 [id] from [dbo].[users]", Some(&DatabaseType::MsSQL), &DatabaseType::MySQL)
+            .expect("ok");
+        assert_eq!(top.sql, "SELECT id FROM dbo.users LIMIT 3");
+        let fetch = convert_dialect(
+            "select id from users order by id offset 10 rows fetch next 5 rows only",
+            Some(&DatabaseType::MsSQL),
+            &DatabaseType::SQLite,
+        )
+        .expect("ok");
+        assert!(fetch.sql.ends_with("LIMIT 5 OFFSET 10"), "{}", fetch.sql);
+    }
+
+    #[test]
+    fn convert_dialect_annotates_what_it_cannot_translate() {
+        use tabular::query_ast::convert::convert_dialect;
+        let sql = "select a.id from a full join b on a.id = b.id where a.x between 1 and 2";
+        let out = convert_dialect(sql, None, &DatabaseType::MySQL).expect("ok");
+        assert!(out.notes.iter().any(|n| n.contains("FULL JOIN")), "{:?}", out.notes);
+        // BETWEEN is portable and converts without a note
+        assert!(!out.notes.iter().any(|n| n.contains("BETWEEN")), "{:?}", out.notes);
+        assert!(out.sql.contains("BETWEEN 1 AND 2"), "{}", out.sql);
+        let text = out.annotated_sql();
+        assert!(text.starts_with("-- NOTE: "), "{text}");
+        assert!(text.lines().last().unwrap().starts_with("SELECT "), "{text}");
+
+        // Subqueries are converted too
+        let sub = convert_dialect(
+            "select id from t where id in (select `tid` from `u` where `ok` = false)",
+            Some(&DatabaseType::MySQL),
+            &DatabaseType::MsSQL,
+        )
+        .expect("ok");
+        assert!(sub.sql.contains("(SELECT tid FROM u WHERE ok = 0)"), "{}", sub.sql);
+
+        assert!(convert_dialect("select 1 from t", None, &DatabaseType::Redis).is_err());
+    }
+
+    #[test]
+    fn emitter_quotes_reserved_words_and_folded_names_only() {
+        use tabular::query_ast::convert::{convert_dialect, convert_dialect_with_quoting};
+        use tabular::query_ast::emitter::dialect::{IdentQuoting, get_dialect};
+
+        let pg = get_dialect(&DatabaseType::PostgreSQL);
+        assert!(!pg.needs_quoting("users"));
+        assert!(pg.needs_quoting("order"));
+        assert!(pg.needs_quoting("user"));
+        assert!(pg.needs_quoting("Users"), "PostgreSQL folds unquoted names to lower case");
+        assert!(pg.needs_quoting("first name"));
+        assert!(pg.needs_quoting("1st"));
+        let my = get_dialect(&DatabaseType::MySQL);
+        assert!(!my.needs_quoting("Users"));
+        assert!(!my.needs_quoting("user"));
+        assert!(my.needs_quoting("rank"));
+        let lite = get_dialect(&DatabaseType::SQLite);
+        assert!(lite.needs_quoting("index"));
+        assert!(!lite.needs_quoting("`name`"));
+        let ms = get_dialect(&DatabaseType::MsSQL);
+        assert!(ms.needs_quoting("top"));
+        assert!(!ms.needs_quoting("Users"));
+        assert_eq!(ms.emit_ident("id", IdentQuoting::Always), "[id]");
+
+        let sql = "select `order`, `user`, `Name` from `Events` where `id` > 1";
+        let from = Some(&DatabaseType::MySQL);
+        let to_pg = convert_dialect(sql, from, &DatabaseType::PostgreSQL).expect("ok");
+        assert_eq!(to_pg.sql, r#"SELECT "order", "user", "Name" FROM "Events" WHERE id > 1"#);
+        let to_ms = convert_dialect(sql, from, &DatabaseType::MsSQL).expect("ok");
+        assert_eq!(to_ms.sql, "SELECT [order], [user], Name FROM Events WHERE id > 1");
+        let to_lite = convert_dialect(sql, from, &DatabaseType::SQLite).expect("ok");
+        assert_eq!(to_lite.sql, r#"SELECT "order", user, Name FROM Events WHERE id > 1"#);
+
+        // Force-quoting keeps the old behaviour
+        let all = convert_dialect_with_quoting(sql, from, &DatabaseType::PostgreSQL, IdentQuoting::Always)
+            .expect("ok");
+        assert_eq!(all.sql, r#"SELECT "order", "user", "Name" FROM "Events" WHERE "id" > 1"#);
+    }
+
+    #[test]
+    fn every_join_is_emitted_in_order_and_hashed() {
+        let sql = "select a.id from a join b on a.id = b.a_id left join c on b.id = c.b_id";
+        let (out, _) = compile_single_select(sql, &DatabaseType::PostgreSQL, None, false).expect("ok");
+        let lo = unquoted(&out.to_lowercase());
+        let first = lo.find(" inner join b on a.id = b.a_id").expect(&lo);
+        let second = lo.find(" left join c on b.id = c.b_id").expect(&lo);
+        assert!(first < second, "{lo}");
+        assert!(lo.find(" where ").is_none_or(|w| w > second), "{lo}");
+
+        let hash = |sql: &str| {
+            tabular::query_ast::plan_structural_hash(sql, &DatabaseType::PostgreSQL, None, false)
+                .unwrap()
+                .0
+        };
+        let one = "select a.id from a join b on a.id = b.a_id";
+        let swapped = "select a.id from a left join c on b.id = c.b_id join b on a.id = b.a_id";
+        assert_ne!(hash(sql), hash(one));
+        assert_ne!(hash(sql), hash(swapped));
+    }
+
+    #[test]
+    fn between_round_trips_through_every_sql_dialect() {
+        use tabular::query_ast::convert::convert_dialect;
+        let sql = "select id from products where price between 10 and 20 and qty not between 1 and 3";
+        for db in [
+            DatabaseType::MySQL,
+            DatabaseType::PostgreSQL,
+            DatabaseType::SQLite,
+            DatabaseType::MsSQL,
+            DatabaseType::MongoDB,
+        ] {
+            let (out, _) = compile_single_select(sql, &db, None, false).expect("ok");
+            let lo = unquoted(&out.to_lowercase()).replace(['[', ']'], "");
+            assert!(lo.contains("price between 10 and 20"), "{db:?}: {out}");
+            assert!(lo.contains("qty not between 1 and 3"), "{db:?}: {out}");
+            if matches!(db, DatabaseType::MongoDB) {
+                continue; // no SQL to convert back into
+            }
+            // Parsing the output back in its own dialect gives it again
+            let again = convert_dialect(&out, Some(&db), &db).expect("re-parse");
+            let again_lo = unquoted(&again.sql.to_lowercase()).replace(['[', ']'], "");
+            assert_eq!(again_lo, lo, "{db:?}");
+            assert!(again.notes.is_empty(), "{db:?}: {:?}", again.notes);
+        }
+        let (_, headers) =
+            compile_single_select("select x between 1 and 2 from t", &DatabaseType::MySQL, None, false)
+                .expect("ok");
+        assert_eq!(headers, ["between"]);
+    }
+
+    #[test]
+    fn distinct_on_is_postgres_only() {
+        use tabular::query_ast::errors::QueryAstError;
+        let sql = "select distinct on (customer_id, day) customer_id, day, total from orders order by customer_id, day, total desc";
+        let (out, _) =
+            compile_single_select(sql, &DatabaseType::PostgreSQL, None, false).expect("ok");
+        assert!(
+            unquoted(&out).starts_with(
+                "SELECT DISTINCT ON (customer_id, day) customer_id, day, total FROM orders ORDER BY"
+            ),
+            "{out}"
+        );
+        let err = compile_single_select(sql, &DatabaseType::MySQL, None, false).unwrap_err();
+        assert!(
+            matches!(&err, QueryAstError::DatabaseFeatureUnsupported { feature, .. } if feature == "DISTINCT ON"),
+            "{err:?}"
+        );
+        // Plain DISTINCT is unaffected and an explicit ALL is not DISTINCT at all
+        let (out, _) =
+            compile_single_select("select distinct a from t", &DatabaseType::MySQL, None, false).expect("ok");
+        assert!(unquoted(&out).starts_with("SELECT DISTINCT a"), "{out}");
+        let (out, _) =
+            compile_single_select("select all a from t", &DatabaseType::MySQL, None, false).expect("ok");
+        assert!(!out.contains("DISTINCT"), "{out}");
+    }
+
+    #[test]
+    fn qualified_names_quote_every_part_per_dialect() {
+        use tabular::query_ast::emitter::dialect::IdentQuoting;
+        use tabular::query_ast::emitter::emit_sql_with_quoting;
+        use tabular::query_ast::{Expr, LogicalQueryPlan};
+        let plan = LogicalQueryPlan::Projection {
+            exprs: vec![Expr::Column("a.b.c".into()), Expr::Column("[x.y].z".into())],
+            input: Box::new(LogicalQueryPlan::TableScan {
+                table: "a.b".into(),
+                alias: Some("t".into()),
+            }),
+        };
+        for (db, expected) in [
+            (DatabaseType::MsSQL, "SELECT [a].[b].[c], [x.y].[z] FROM [a].[b] [t]"),
+            (DatabaseType::MySQL, "SELECT `a`.`b`.`c`, `x.y`.`z` FROM `a`.`b` `t`"),
+            (DatabaseType::PostgreSQL, r#"SELECT "a"."b"."c", "x.y"."z" FROM "a"."b" "t""#),
+            (DatabaseType::SQLite, r#"SELECT "a"."b"."c", "x.y"."z" FROM "a"."b" "t""#),
+        ] {
+            let sql = emit_sql_with_quoting(&plan, &db, IdentQuoting::Always).expect("emit");
+            assert_eq!(sql, expected, "{db:?}");
+        }
+
+        let (ms, _) = compile_single_select(
+            "select o.id from dbo.Orders o",
+            &DatabaseType::MsSQL,
+            None,
+            false,
+        )
+        .expect("ok");
+        assert_eq!(ms, "SELECT [o].[id] FROM [dbo].[Orders] [o]");
+        let pg = tabular::query_ast::convert::convert_dialect(
+            "select o.id from dbo.Orders o",
+            None,
+            &DatabaseType::PostgreSQL,
+        )
+        .expect("ok");
+        assert_eq!(pg.sql, r#"SELECT o.id FROM dbo."Orders" o"#);
+    }
 }